@@ -1,12 +1,24 @@
 /*
  * ZeroClaw Arduino Uno Firmware
  *
- * Listens for JSON commands on Serial (115200 baud), executes gpio_read/gpio_write,
- * responds with JSON. Compatible with ZeroClaw SerialPeripheral protocol.
+ * Listens for JSON commands on Serial (115200 baud), executes gpio_read/gpio_write
+ * and the extended I2C/SPI/ADC/PWM commands, responds with JSON. Compatible with
+ * ZeroClaw SerialPeripheral protocol.
  *
  * Protocol (newline-delimited JSON):
  *   Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
  *   Response: {"id":"1","ok":true,"result":"done"}
+ *   Event:    {"event":"pin_change","data":{"pin":2,"value":0}}
+ *
+ * Extended commands return their data as JSON text in "result":
+ *   i2c_scan, i2c_read {address, register?, length}, i2c_write {address, bytes}
+ *     (A4 = SDA, A5 = SCL)
+ *   spi_transfer {bytes}        (13 = SCK, 12 = MISO, 11 = MOSI, 10 = CS)
+ *   adc_read {channel, samples?}  (A0-A5 = channel 0-5, raw 0-1023)
+ *   pwm_write {pin, duty}       (pins 3, 5, 6, 9, 10, 11; fixed frequency)
+ *   servo_write {pin, angle}    (one servo; disables PWM on 9 and 10)
+ *   gpio_watch {pin, enabled}   (pins 2-9; pushes pin_change events)
+ *   adc_watch {channel, threshold, enabled}  (pushes threshold events)
  *
  * Arduino Uno: Pin 13 has built-in LED. Digital pins 0-13 supported.
  *
@@ -16,12 +28,29 @@
  * 4. Upload
  */
 
+#include <SPI.h>
+#include <Servo.h>
+#include <Wire.h>
+
 #define BAUDRATE 115200
 #define MAX_LINE 256
+#define MAX_BYTES 32
+#define ADC_CHANNELS 6
+#define ADC_HYSTERESIS 8
+#define SPI_CS 10
 
 char lineBuf[MAX_LINE];
 int lineLen = 0;
 
+Servo servo;
+int servoPin = -1;
+
+// Watches: bit n of watchMask = pin n armed; lastLevels holds its last level.
+uint16_t watchMask = 0;
+uint16_t lastLevels = 0;
+int adcThreshold[ADC_CHANNELS] = {-1, -1, -1, -1, -1, -1};
+bool adcAbove[ADC_CHANNELS];
+
 // Parse integer from JSON: "pin":13 or "value":1
 int parseArg(const char* key, const char* json) {
   char search[32];
@@ -48,6 +77,73 @@ void copyId(char* out, int outLen, const char* json) {
   out[i] = '\0';
 }
 
+// Parse float from JSON: "duty":37.5
+float parseFloatArg(const char* key, const char* json) {
+  char search[32];
+  snprintf(search, sizeof(search), "\"%s\":", key);
+  const char* p = strstr(json, search);
+  if (!p) return -1;
+  return atof(p + strlen(search));
+}
+
+// "enabled" defaults to true; only an explicit false disables
+bool parseEnabled(const char* json) {
+  return strstr(json, "\"enabled\":false") == NULL;
+}
+
+// Parse "bytes":[1,2,3] into out; returns count or -1 when missing/invalid
+int parseBytes(const char* json, uint8_t* out, int maxLen) {
+  const char* p = strstr(json, "\"bytes\":[");
+  if (!p) return -1;
+  p += 9;
+  int n = 0;
+  while (*p && *p != ']') {
+    if (*p >= '0' && *p <= '9') {
+      int v = atoi(p);
+      if (v > 255 || n >= maxLen) return -1;
+      out[n++] = v;
+      while (*p >= '0' && *p <= '9') p++;
+    } else {
+      p++;
+    }
+  }
+  return n;
+}
+
+void printOkStart(const char* id) {
+  Serial.print("{\"id\":\"");
+  Serial.print(id);
+  Serial.print("\",\"ok\":true,\"result\":\"");
+}
+
+void printOkEnd() {
+  Serial.println("\"}");
+}
+
+void printError(const char* id, const char* msg) {
+  Serial.print("{\"id\":\"");
+  Serial.print(id);
+  Serial.print("\",\"ok\":false,\"result\":\"\",\"error\":\"");
+  Serial.print(msg);
+  Serial.println("\"}");
+}
+
+// Print {"bytes":[..]} escaped for use inside "result"
+void printBytesResult(const char* id, const uint8_t* bytes, int n) {
+  printOkStart(id);
+  Serial.print("{\\\"bytes\\\":[");
+  for (int i = 0; i < n; i++) {
+    if (i) Serial.print(",");
+    Serial.print(bytes[i]);
+  }
+  Serial.print("]}");
+  printOkEnd();
+}
+
+bool isPwmPin(int pin) {
+  return pin == 3 || pin == 5 || pin == 6 || pin == 9 || pin == 10 || pin == 11;
+}
+
 // Check if cmd is present
 bool hasCmd(const char* json, const char* cmd) {
   char search[64];
@@ -70,7 +166,8 @@ void handleLine(const char* line) {
   if (hasCmd(line, "capabilities")) {
     Serial.print("{\"id\":\"");
     Serial.print(idBuf);
-    Serial.print("\",\"ok\":true,\"result\":\"{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13}\"}");
+    Serial.print("\",\"ok\":true,\"result\":\"{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13,"
+                 "\\\"i2c\\\":true,\\\"spi\\\":true,\\\"adc\\\":[0,1,2,3,4,5],\\\"pwm\\\":[3,5,6,9,10,11]}\"}");
     Serial.println();
     return;
   }
@@ -114,14 +211,244 @@ void handleLine(const char* line) {
     return;
   }
 
+  if (hasCmd(line, "i2c_scan")) {
+    printOkStart(idBuf);
+    Serial.print("{\\\"addresses\\\":[");
+    bool first = true;
+    for (uint8_t addr = 0x08; addr < 0x78; addr++) {
+      Wire.beginTransmission(addr);
+      if (Wire.endTransmission() == 0) {
+        if (!first) Serial.print(",");
+        Serial.print(addr);
+        first = false;
+      }
+    }
+    Serial.print("]}");
+    printOkEnd();
+    return;
+  }
+
+  if (hasCmd(line, "i2c_read")) {
+    int addr = parseArg("address", line);
+    int reg = parseArg("register", line);
+    int len = parseArg("length", line);
+    if (addr < 0 || addr > 0x7f || len < 1 || len > MAX_BYTES) {
+      printError(idBuf, "Invalid address or length");
+      return;
+    }
+    if (reg >= 0) {
+      Wire.beginTransmission(addr);
+      Wire.write((uint8_t)reg);
+      if (Wire.endTransmission(false) != 0) {
+        printError(idBuf, "I2C device did not acknowledge");
+        return;
+      }
+    }
+    uint8_t buf[MAX_BYTES];
+    int n = Wire.requestFrom(addr, len);
+    for (int i = 0; i < n; i++) buf[i] = Wire.read();
+    if (n != len) {
+      printError(idBuf, "I2C short read");
+      return;
+    }
+    printBytesResult(idBuf, buf, n);
+    return;
+  }
+
+  if (hasCmd(line, "i2c_write")) {
+    int addr = parseArg("address", line);
+    uint8_t buf[MAX_BYTES];
+    int n = parseBytes(line, buf, MAX_BYTES);
+    if (addr < 0 || addr > 0x7f || n < 0) {
+      printError(idBuf, "Invalid address or bytes");
+      return;
+    }
+    Wire.beginTransmission(addr);
+    Wire.write(buf, n);
+    if (Wire.endTransmission() != 0) {
+      printError(idBuf, "I2C device did not acknowledge");
+      return;
+    }
+    printOkStart(idBuf);
+    Serial.print("{\\\"written\\\":");
+    Serial.print(n);
+    Serial.print("}");
+    printOkEnd();
+    return;
+  }
+
+  if (hasCmd(line, "spi_transfer")) {
+    uint8_t buf[MAX_BYTES];
+    int n = parseBytes(line, buf, MAX_BYTES);
+    if (n < 0) {
+      printError(idBuf, "Invalid bytes");
+      return;
+    }
+    SPI.beginTransaction(SPISettings(1000000, MSBFIRST, SPI_MODE0));
+    digitalWrite(SPI_CS, LOW);
+    SPI.transfer(buf, n);
+    digitalWrite(SPI_CS, HIGH);
+    SPI.endTransaction();
+    printBytesResult(idBuf, buf, n);
+    return;
+  }
+
+  if (hasCmd(line, "adc_read")) {
+    int channel = parseArg("channel", line);
+    int samples = parseArg("samples", line);
+    if (channel < 0 || channel >= ADC_CHANNELS) {
+      printError(idBuf, "Invalid ADC channel");
+      return;
+    }
+    if (samples < 1) samples = 1;
+    if (samples > 64) samples = 64;
+    long total = 0;
+    for (int i = 0; i < samples; i++) total += analogRead(A0 + channel);
+    long value = total / samples;
+    printOkStart(idBuf);
+    Serial.print("{\\\"channel\\\":");
+    Serial.print(channel);
+    Serial.print(",\\\"value\\\":");
+    Serial.print(value);
+    Serial.print(",\\\"millivolts\\\":");
+    Serial.print(value * 5000 / 1023);
+    Serial.print("}");
+    printOkEnd();
+    return;
+  }
+
+  if (hasCmd(line, "pwm_write")) {
+    int pin = parseArg("pin", line);
+    float duty = parseFloatArg("duty", line);
+    if (!isPwmPin(pin) || duty < 0 || duty > 100) {
+      printError(idBuf, "Invalid PWM pin or duty");
+      return;
+    }
+    if (strstr(line, "\"frequency\":")) {
+      printError(idBuf, "PWM frequency is fixed on this board");
+      return;
+    }
+    pinMode(pin, OUTPUT);
+    analogWrite(pin, (int)(duty * 255.0 / 100.0 + 0.5));
+    printOkStart(idBuf);
+    Serial.print("done");
+    printOkEnd();
+    return;
+  }
+
+  if (hasCmd(line, "servo_write")) {
+    int pin = parseArg("pin", line);
+    int angle = parseArg("angle", line);
+    if (pin < 2 || pin > 13 || angle < 0 || angle > 180) {
+      printError(idBuf, "Invalid servo pin or angle");
+      return;
+    }
+    if (servoPin != pin) {
+      if (servoPin >= 0) servo.detach();
+      servo.attach(pin);
+      servoPin = pin;
+    }
+    servo.write(angle);
+    printOkStart(idBuf);
+    Serial.print("done");
+    printOkEnd();
+    return;
+  }
+
+  if (hasCmd(line, "gpio_watch")) {
+    int pin = parseArg("pin", line);
+    if (pin < 2 || pin > 9) {
+      printError(idBuf, "Pin cannot be watched (use 2-9)");
+      return;
+    }
+    if (parseEnabled(line)) {
+      pinMode(pin, INPUT_PULLUP);
+      watchMask |= (1 << pin);
+      if (digitalRead(pin)) lastLevels |= (1 << pin);
+      else lastLevels &= ~(1 << pin);
+    } else {
+      watchMask &= ~(1 << pin);
+    }
+    printOkStart(idBuf);
+    Serial.print("done");
+    printOkEnd();
+    return;
+  }
+
+  if (hasCmd(line, "adc_watch")) {
+    int channel = parseArg("channel", line);
+    if (channel < 0 || channel >= ADC_CHANNELS) {
+      printError(idBuf, "Invalid ADC channel");
+      return;
+    }
+    if (parseEnabled(line)) {
+      int threshold = parseArg("threshold", line);
+      if (threshold < 0 || threshold > 1023) {
+        printError(idBuf, "Invalid threshold");
+        return;
+      }
+      adcThreshold[channel] = threshold;
+      adcAbove[channel] = analogRead(A0 + channel) >= threshold;
+    } else {
+      adcThreshold[channel] = -1;
+    }
+    printOkStart(idBuf);
+    Serial.print("done");
+    printOkEnd();
+    return;
+  }
+
   // Unknown command
   Serial.print("{\"id\":\"");
   Serial.print(idBuf);
   Serial.println("\",\"ok\":false,\"result\":\"\",\"error\":\"Unknown command\"}");
 }
 
+// Push events for armed watches
+void pollWatches() {
+  for (int pin = 2; pin <= 9; pin++) {
+    if (!(watchMask & (1 << pin))) continue;
+    bool level = digitalRead(pin);
+    if (level == (bool)(lastLevels & (1 << pin))) continue;
+    lastLevels ^= (1 << pin);
+    Serial.print("{\"event\":\"pin_change\",\"data\":{\"pin\":");
+    Serial.print(pin);
+    Serial.print(",\"value\":");
+    Serial.print(level ? 1 : 0);
+    Serial.println("}}");
+  }
+
+  for (int ch = 0; ch < ADC_CHANNELS; ch++) {
+    int threshold = adcThreshold[ch];
+    if (threshold < 0) continue;
+    int value = analogRead(A0 + ch);
+    const char* direction;
+    if (!adcAbove[ch] && value >= threshold + ADC_HYSTERESIS) {
+      direction = "rising";
+    } else if (adcAbove[ch] && value + ADC_HYSTERESIS <= threshold) {
+      direction = "falling";
+    } else {
+      continue;
+    }
+    adcAbove[ch] = !adcAbove[ch];
+    Serial.print("{\"event\":\"threshold\",\"data\":{\"channel\":");
+    Serial.print(ch);
+    Serial.print(",\"value\":");
+    Serial.print(value);
+    Serial.print(",\"threshold\":");
+    Serial.print(threshold);
+    Serial.print(",\"direction\":\"");
+    Serial.print(direction);
+    Serial.println("\"}}");
+  }
+}
+
 void setup() {
   Serial.begin(BAUDRATE);
+  Wire.begin();
+  SPI.begin();
+  pinMode(SPI_CS, OUTPUT);
+  digitalWrite(SPI_CS, HIGH);
   lineLen = 0;
 }
 
@@ -140,4 +467,5 @@ void loop() {
       lineLen = 0;  // Overflow, discard
     }
   }
+  pollWatches();
 }
@@ -1,15 +1,24 @@
 //! ZeroClaw ESP32 firmware — JSON-over-serial peripheral.
 //!
-//! Listens for newline-delimited JSON commands on UART0, executes gpio_read/gpio_write,
-//! responds with JSON. Compatible with host ZeroClaw SerialPeripheral protocol.
+//! Listens for newline-delimited JSON commands on UART0, executes gpio_read/gpio_write
+//! and the I2C/SPI/ADC/PWM commands in `periph.rs`, responds with JSON. Compatible
+//! with host ZeroClaw SerialPeripheral protocol. Armed watches push
+//! `{"event":…}` lines between responses.
+//! When built with Wi-Fi credentials, the same commands are also served over TCP
+//! (see `net.rs`).
 //!
 //! Protocol: same as STM32 — see docs/hardware-peripherals-design.md
 #![forbid(unsafe_code)]
 
+mod net;
+mod periph;
+
+use esp_idf_svc::eventloop::EspSystemEventLoop;
 use esp_idf_svc::hal::gpio::PinDriver;
 use esp_idf_svc::hal::peripherals::Peripherals;
 use esp_idf_svc::hal::uart::{UartConfig, UartDriver};
 use esp_idf_svc::hal::units::Hertz;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
 use log::info;
 use serde::{Deserialize, Serialize};
 
@@ -38,10 +47,38 @@ fn main() -> anyhow::Result<()> {
     let peripherals = Peripherals::take()?;
     let pins = peripherals.pins;
 
+    // Optional Wi-Fi transport; `_wifi` keeps the connection alive.
+    let (_wifi, jobs, net_events) = match net::start(
+        peripherals.modem,
+        EspSystemEventLoop::take()?,
+        EspDefaultNvsPartition::take()?,
+    )? {
+        Some((wifi, jobs, events)) => (Some(wifi), Some(jobs), Some(events)),
+        None => (None, None, None),
+    };
+
     // Create GPIO output drivers first (they take ownership of pins)
     let mut gpio2 = PinDriver::output(pins.gpio2)?;
     let mut gpio13 = PinDriver::output(pins.gpio13)?;
 
+    let mut board = periph::Board::new(periph::BoardPins {
+        i2c0: peripherals.i2c0,
+        spi2: peripherals.spi2,
+        adc1: peripherals.adc1,
+        ledc: peripherals.ledc,
+        gpio0: pins.gpio0,
+        gpio1: pins.gpio1,
+        gpio3: pins.gpio3,
+        gpio4: pins.gpio4,
+        gpio5: pins.gpio5,
+        gpio6: pins.gpio6,
+        gpio7: pins.gpio7,
+        gpio9: pins.gpio9,
+        gpio10: pins.gpio10,
+        gpio18: pins.gpio18,
+        gpio19: pins.gpio19,
+    })?;
+
     // UART0: TX=21, RX=20 (ESP32) — ESP32-C3 may use different pins; adjust for your board
     let config = UartConfig::new().baudrate(Hertz(115_200));
     let uart = UartDriver::new(
@@ -59,6 +96,22 @@ fn main() -> anyhow::Result<()> {
     let mut line = Vec::new();
 
     loop {
+        // Commands received over Wi-Fi run here, where the pins are owned.
+        if let Some(jobs) = &jobs {
+            while let Ok(job) = jobs.try_recv() {
+                let result = execute(&job.cmd, &job.params, &mut gpio2, &mut gpio13, &mut board)
+                    .map_err(|e| e.to_string());
+                let _ = job.reply.send(result);
+            }
+        }
+
+        for event in board.poll_events() {
+            let _ = uart.write(format!("{event}\n").as_bytes());
+            if let Some(net_events) = &net_events {
+                net_events.send(&event);
+            }
+        }
+
         match uart.read(&mut buf, 100) {
             Ok(0) => continue,
             Ok(n) => {
@@ -66,7 +119,8 @@ fn main() -> anyhow::Result<()> {
                     if b == b'\n' {
                         if !line.is_empty() {
                             if let Ok(line_str) = std::str::from_utf8(&line) {
-                                if let Ok(resp) = handle_request(line_str, &mut gpio2, &mut gpio13)
+                                if let Ok(resp) =
+                                    handle_request(line_str, &mut gpio2, &mut gpio13, &mut board)
                                 {
                                     let out = serde_json::to_string(&resp).unwrap_or_default();
                                     let _ = uart.write(format!("{}\n", out).as_bytes());
@@ -91,6 +145,7 @@ fn handle_request<G2, G13>(
     line: &str,
     gpio2: &mut PinDriver<'_, G2>,
     gpio13: &mut PinDriver<'_, G13>,
+    board: &mut periph::Board,
 ) -> anyhow::Result<Response>
 where
     G2: esp_idf_svc::hal::gpio::OutputMode,
@@ -99,28 +154,14 @@ where
     let req: Request = serde_json::from_str(line.trim())?;
     let id = req.id.clone();
 
-    let result = match req.cmd.as_str() {
-        "capabilities" => {
-            // Phase C: report GPIO pins and LED pin (matches Arduino protocol)
-            let caps = serde_json::json!({
-                "gpio": [0, 1, 2, 3, 4, 5, 12, 13, 14, 15, 16, 17, 18, 19],
-                "led_pin": 2
-            });
-            Ok(caps.to_string())
-        }
-        "gpio_read" => {
-            let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
-            let value = gpio_read(pin_num)?;
-            Ok(value.to_string())
-        }
-        "gpio_write" => {
-            let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
-            let value = req.args.get("value").and_then(|v| v.as_u64()).unwrap_or(0);
-            gpio_write(gpio2, gpio13, pin_num, value)?;
-            Ok("done".into())
-        }
-        _ => Err(anyhow::anyhow!("Unknown command: {}", req.cmd)),
-    };
+    // Serial results are strings: GPIO keeps its original plain values, the
+    // extended commands return their data as JSON text.
+    let result =
+        execute(&req.cmd, &req.args, gpio2, gpio13, board).map(|data| match req.cmd.as_str() {
+            "gpio_read" => data["value"].to_string(),
+            "gpio_write" => "done".into(),
+            _ => data.to_string(),
+        });
 
     match result {
         Ok(r) => Ok(Response {
@@ -138,6 +179,56 @@ where
     }
 }
 
+/// Run a command; results use the host `ZcResponse.data` shape.
+fn execute<G2, G13>(
+    cmd: &str,
+    params: &serde_json::Value,
+    gpio2: &mut PinDriver<'_, G2>,
+    gpio13: &mut PinDriver<'_, G13>,
+    board: &mut periph::Board,
+) -> anyhow::Result<serde_json::Value>
+where
+    G2: esp_idf_svc::hal::gpio::OutputMode,
+    G13: esp_idf_svc::hal::gpio::OutputMode,
+{
+    let pin = params.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
+    if let Some(result) = board.execute(cmd, params) {
+        return result;
+    }
+    match cmd {
+        "capabilities" => Ok(capabilities(board)),
+        "gpio_read" => {
+            let value = gpio_read(pin)?;
+            Ok(serde_json::json!({ "pin": pin, "value": value }))
+        }
+        "gpio_write" => {
+            let value = params.get("value").and_then(|v| v.as_u64()).unwrap_or(0);
+            gpio_write(gpio2, gpio13, pin, value)?;
+            Ok(serde_json::json!({
+                "pin": pin,
+                "value": value,
+                "state": if value != 0 { "HIGH" } else { "LOW" }
+            }))
+        }
+        _ => anyhow::bail!("Unknown command: {}", cmd),
+    }
+}
+
+/// Phase C: report GPIO pins and LED pin (matches Arduino protocol), plus
+/// the extended peripherals.
+fn capabilities(board: &periph::Board) -> serde_json::Value {
+    let mut caps = serde_json::json!({
+        "gpio": [2, 12, 13, 14, 15, 16, 17],
+        "led_pin": 2
+    });
+    if let (Some(caps), serde_json::Value::Object(extra)) =
+        (caps.as_object_mut(), board.capabilities())
+    {
+        caps.extend(extra);
+    }
+    caps
+}
+
 fn gpio_read(_pin: i32) -> anyhow::Result<u8> {
     // TODO: implement input pin read — requires storing InputPin drivers per pin
     Ok(0)
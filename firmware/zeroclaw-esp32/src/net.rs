@@ -0,0 +1,268 @@
+//! Wi-Fi transport — the host `ZcCommand`/`ZcResponse` protocol over TCP.
+//!
+//! Built in only when `ZEROCLAW_WIFI_SSID` is set at compile time; otherwise the
+//! firmware stays serial-only. Matches `[[hardware.network_devices]]` on the
+//! host (`address = "tcp://<board ip>:7777"`):
+//!
+//! - `{"cmd":"hello","params":{"nonce":"…"}}` → firmware id, device nonce and MAC
+//! - `{"cmd":"auth","params":{"mac":"…"}}` → required before any other command
+//!   when `ZEROCLAW_PSK` is set
+//! - `ping` is answered here; everything else is handed to the main loop, which
+//!   owns the GPIO drivers.
+//! - Once authenticated, the host also receives `{"event":…}` frames pushed by
+//!   the main loop through [`EventWriter`].
+//!
+//! MACs are hex HMAC-SHA256(psk, "device:" + host nonce) and
+//! HMAC-SHA256(psk, "host:" + device nonce).
+
+use esp_idf_svc::eventloop::EspSystemEventLoop;
+use esp_idf_svc::hal::modem::Modem;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
+use esp_idf_svc::wifi::{AuthMethod, BlockingWifi, ClientConfiguration, Configuration, EspWifi};
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+const WIFI_SSID: Option<&str> = option_env!("ZEROCLAW_WIFI_SSID");
+const WIFI_PASS: &str = match option_env!("ZEROCLAW_WIFI_PASS") {
+    Some(pass) => pass,
+    None => "",
+};
+const PSK: Option<&str> = option_env!("ZEROCLAW_PSK");
+const PORT: &str = match option_env!("ZEROCLAW_PORT") {
+    Some(port) => port,
+    None => "7777",
+};
+
+/// How long a connection waits for the main loop to run a command.
+const JOB_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Host-to-device command (same as the host's `ZcCommand`).
+#[derive(Debug, Deserialize)]
+struct ZcCommand {
+    cmd: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+/// Device-to-host response (same as the host's `ZcResponse`).
+#[derive(Debug, Serialize)]
+struct ZcResponse {
+    ok: bool,
+    data: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ZcResponse {
+    fn success(data: serde_json::Value) -> Self {
+        Self {
+            ok: true,
+            data,
+            error: None,
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            data: serde_json::Value::Null,
+            error: Some(message.into()),
+        }
+    }
+}
+
+/// A command for the main loop, which owns the peripherals.
+pub struct Job {
+    pub cmd: String,
+    pub params: serde_json::Value,
+    pub reply: Sender<Result<serde_json::Value, String>>,
+}
+
+/// Pushes event frames to the connected host, if it has authenticated.
+#[derive(Clone, Default)]
+pub struct EventWriter(Arc<Mutex<Option<TcpStream>>>);
+
+impl EventWriter {
+    /// Send one frame; a failed write detaches the client.
+    pub fn send(&self, frame: &serde_json::Value) {
+        let mut client = self.lock();
+        if let Some(stream) = client.as_mut() {
+            if stream.write_all(format!("{frame}\n").as_bytes()).is_err() {
+                *client = None;
+            }
+        }
+    }
+
+    /// Held while writing responses so events never split a line.
+    fn lock(&self) -> MutexGuard<'_, Option<TcpStream>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Join Wi-Fi and start the TCP server thread.
+///
+/// Returns `None` when the firmware was built without Wi-Fi credentials. The
+/// returned driver must be kept alive for the connection to stay up.
+pub fn start(
+    modem: Modem,
+    sysloop: EspSystemEventLoop,
+    nvs: EspDefaultNvsPartition,
+) -> anyhow::Result<Option<(BlockingWifi<EspWifi<'static>>, Receiver<Job>, EventWriter)>> {
+    let Some(ssid) = WIFI_SSID else {
+        info!("ZEROCLAW_WIFI_SSID not set at build time — network transport disabled");
+        return Ok(None);
+    };
+
+    let mut wifi = BlockingWifi::wrap(EspWifi::new(modem, sysloop.clone(), Some(nvs))?, sysloop)?;
+    wifi.set_configuration(&Configuration::Client(ClientConfiguration {
+        ssid: ssid
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("ZEROCLAW_WIFI_SSID is too long"))?,
+        password: WIFI_PASS
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("ZEROCLAW_WIFI_PASS is too long"))?,
+        auth_method: if WIFI_PASS.is_empty() {
+            AuthMethod::None
+        } else {
+            AuthMethod::WPA2Personal
+        },
+        ..Default::default()
+    }))?;
+    wifi.start()?;
+    wifi.connect()?;
+    wifi.wait_netif_up()?;
+    let ip = wifi.wifi().sta_netif().get_ip_info()?.ip;
+
+    let port: u16 = PORT.parse()?;
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    info!("ZeroClaw network transport listening on tcp://{ip}:{port}");
+    if PSK.is_none() {
+        warn!("ZEROCLAW_PSK not set — network commands are unauthenticated");
+    }
+
+    let (jobs, rx) = mpsc::channel();
+    let events = EventWriter::default();
+    let client = events.clone();
+    std::thread::Builder::new()
+        .name("zc-net".into())
+        .stack_size(8 * 1024)
+        .spawn(move || {
+            // One host at a time; the host keeps its connection open.
+            for stream in listener.incoming().flatten() {
+                if let Err(e) = serve(stream, &jobs, &client) {
+                    warn!("network client disconnected: {e}");
+                }
+                *client.lock() = None;
+            }
+        })?;
+
+    Ok(Some((wifi, rx, events)))
+}
+
+/// Serve one host connection until it closes.
+fn serve(stream: TcpStream, jobs: &Sender<Job>, events: &EventWriter) -> anyhow::Result<()> {
+    stream.set_nodelay(true)?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut authed = PSK.is_none();
+    if authed {
+        *events.lock() = Some(writer.try_clone()?);
+    }
+    let mut device_nonce = String::new();
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        if line.len() > 400 {
+            continue;
+        }
+        let resp = match serde_json::from_str::<ZcCommand>(line.trim()) {
+            Err(e) => ZcResponse::error(format!("invalid command: {e}")),
+            Ok(cmd) => match cmd.cmd.as_str() {
+                "hello" => {
+                    let host_nonce = cmd.params.get("nonce").and_then(|v| v.as_str());
+                    let mut data = serde_json::json!({ "firmware": "zeroclaw" });
+                    if let (Some(psk), Some(host_nonce)) = (PSK, host_nonce) {
+                        device_nonce = new_nonce()?;
+                        authed = false;
+                        *events.lock() = None;
+                        data["nonce"] = device_nonce.clone().into();
+                        data["mac"] = sign(psk, &format!("device:{host_nonce}")).into();
+                    }
+                    ZcResponse::success(data)
+                }
+                "auth" => {
+                    let mac = cmd.params.get("mac").and_then(|v| v.as_str()).unwrap_or("");
+                    authed = match PSK {
+                        Some(psk) if !device_nonce.is_empty() => {
+                            verify(psk, &format!("host:{device_nonce}"), mac)
+                        }
+                        Some(_) => false,
+                        None => true,
+                    };
+                    if authed {
+                        *events.lock() = Some(writer.try_clone()?);
+                        ZcResponse::success(serde_json::json!({}))
+                    } else {
+                        ZcResponse::error("authentication failed")
+                    }
+                }
+                _ if !authed => ZcResponse::error("not authenticated"),
+                "ping" => ZcResponse::success(serde_json::json!({ "firmware": "zeroclaw" })),
+                _ => run_job(jobs, cmd),
+            },
+        };
+        let out = serde_json::to_string(&resp)?;
+        let _guard = events.lock();
+        writer.write_all(format!("{out}\n").as_bytes())?;
+    }
+}
+
+/// Hand a command to the main loop and wait for its result.
+fn run_job(jobs: &Sender<Job>, cmd: ZcCommand) -> ZcResponse {
+    let (reply, result) = mpsc::channel();
+    let job = Job {
+        cmd: cmd.cmd,
+        params: cmd.params,
+        reply,
+    };
+    if jobs.send(job).is_err() {
+        return ZcResponse::error("command loop stopped");
+    }
+    match result.recv_timeout(JOB_TIMEOUT) {
+        Ok(Ok(data)) => ZcResponse::success(data),
+        Ok(Err(e)) => ZcResponse::error(e),
+        Err(_) => ZcResponse::error("command timed out"),
+    }
+}
+
+fn new_nonce() -> anyhow::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow::anyhow!("no entropy: {e}"))?;
+    Ok(hex::encode(bytes))
+}
+
+fn sign(psk: &str, message: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(psk.as_bytes()).expect("HMAC accepts any key");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn verify(psk: &str, message: &str, hex_mac: &str) -> bool {
+    let Ok(expected) = hex::decode(hex_mac) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(psk.as_bytes()).expect("HMAC accepts any key");
+    mac.update(message.as_bytes());
+    mac.verify_slice(&expected).is_ok()
+}
@@ -0,0 +1,365 @@
+//! Extended peripherals — I2C, SPI, ADC, PWM/servo and event watches.
+//!
+//! Default pin map (ESP32-C3 DevKit — adjust in `Board::new` for your board):
+//!
+//! | Function | Pins |
+//! |---|---|
+//! | I2C0 | SDA gpio4, SCL gpio5 (100 kHz) |
+//! | SPI2 | SCLK gpio6, MOSI gpio7, MISO gpio10, CS gpio3 (1 MHz, mode 0) |
+//! | ADC1 | channel 0 = gpio0, channel 1 = gpio1 (11 dB, raw 0–4095) |
+//! | PWM | gpio18 (LEDC timer0, 5 kHz) |
+//! | Servo | gpio19 (LEDC timer1, 50 Hz, 0.5–2.5 ms pulse) |
+//! | Watchable input | gpio9 (BOOT button, pulled up) |
+//!
+//! Watches are polled from the main loop; [`Board::poll_events`] returns the
+//! `{"event":…}` frames to push to the host.
+
+use esp_idf_svc::hal::adc::attenuation::DB_11;
+use esp_idf_svc::hal::adc::oneshot::config::AdcChannelConfig;
+use esp_idf_svc::hal::adc::oneshot::{AdcChannelDriver, AdcDriver};
+use esp_idf_svc::hal::adc::ADC1;
+use esp_idf_svc::hal::delay::{TickType, BLOCK};
+use esp_idf_svc::hal::gpio::{self, PinDriver, Pull};
+use esp_idf_svc::hal::i2c::{I2cConfig, I2cDriver, I2C0};
+use esp_idf_svc::hal::ledc::config::TimerConfig;
+use esp_idf_svc::hal::ledc::{LedcDriver, LedcTimerDriver, LEDC};
+use esp_idf_svc::hal::spi::{self, SpiDeviceDriver, SpiDriver, SpiDriverConfig, SPI2};
+use esp_idf_svc::hal::units::Hertz;
+use serde_json::{json, Value};
+use std::rc::Rc;
+
+/// Pin driven by `pwm_write`.
+const PWM_PIN: u64 = 18;
+/// PWM frequency; fixed because the LEDC timer is owned by the channel driver.
+const PWM_FREQUENCY_HZ: u32 = 5_000;
+/// Pin driven by `servo_write`.
+const SERVO_PIN: u64 = 19;
+/// Pin reported by `gpio_watch`.
+const WATCH_PIN: u64 = 9;
+/// Raw ADC units a reading must move past the threshold before re-reporting.
+const ADC_HYSTERESIS: u16 = 20;
+/// Per-address timeout while scanning the I2C bus.
+const I2C_SCAN_TIMEOUT_MS: u64 = 20;
+
+/// Reads one raw sample from an ADC channel.
+type AdcReader = Box<dyn FnMut() -> anyhow::Result<u16>>;
+
+/// An armed ADC threshold watch.
+struct AdcWatch {
+    threshold: u16,
+    above: bool,
+}
+
+/// Owns every peripheral beyond the two GPIO outputs.
+pub struct Board {
+    i2c: I2cDriver<'static>,
+    spi: SpiDeviceDriver<'static, SpiDriver<'static>>,
+    adc: Vec<AdcReader>,
+    pwm: LedcDriver<'static>,
+    servo: LedcDriver<'static>,
+    button: Box<dyn Fn() -> bool>,
+    pin_watch: Option<bool>,
+    adc_watch: Vec<Option<AdcWatch>>,
+}
+
+/// The pins and peripherals `Board::new` takes ownership of.
+pub struct BoardPins {
+    pub i2c0: I2C0,
+    pub spi2: SPI2,
+    pub adc1: ADC1,
+    pub ledc: LEDC,
+    pub gpio0: gpio::Gpio0,
+    pub gpio1: gpio::Gpio1,
+    pub gpio3: gpio::Gpio3,
+    pub gpio4: gpio::Gpio4,
+    pub gpio5: gpio::Gpio5,
+    pub gpio6: gpio::Gpio6,
+    pub gpio7: gpio::Gpio7,
+    pub gpio9: gpio::Gpio9,
+    pub gpio10: gpio::Gpio10,
+    pub gpio18: gpio::Gpio18,
+    pub gpio19: gpio::Gpio19,
+}
+
+impl Board {
+    pub fn new(p: BoardPins) -> anyhow::Result<Self> {
+        let i2c = I2cDriver::new(
+            p.i2c0,
+            p.gpio4,
+            p.gpio5,
+            &I2cConfig::new().baudrate(Hertz(100_000)),
+        )?;
+
+        let spi_bus = SpiDriver::new(
+            p.spi2,
+            p.gpio6,
+            p.gpio7,
+            Some(p.gpio10),
+            &SpiDriverConfig::new(),
+        )?;
+        let spi = SpiDeviceDriver::new(
+            spi_bus,
+            Some(p.gpio3),
+            &spi::config::Config::new().baudrate(Hertz(1_000_000)),
+        )?;
+
+        let adc = Rc::new(AdcDriver::new(p.adc1)?);
+        let adc_config = AdcChannelConfig {
+            attenuation: DB_11,
+            ..Default::default()
+        };
+        let mut ch0 = AdcChannelDriver::new(Rc::clone(&adc), p.gpio0, &adc_config)?;
+        let mut ch1 = AdcChannelDriver::new(adc, p.gpio1, &adc_config)?;
+        let adc: Vec<AdcReader> = vec![
+            Box::new(move || Ok(ch0.read_raw()?)),
+            Box::new(move || Ok(ch1.read_raw()?)),
+        ];
+
+        let pwm_timer = LedcTimerDriver::new(
+            p.ledc.timer0,
+            &TimerConfig::new().frequency(Hertz(PWM_FREQUENCY_HZ)),
+        )?;
+        let pwm = LedcDriver::new(p.ledc.channel0, pwm_timer, p.gpio18)?;
+        let servo_timer =
+            LedcTimerDriver::new(p.ledc.timer1, &TimerConfig::new().frequency(Hertz(50)))?;
+        let servo = LedcDriver::new(p.ledc.channel1, servo_timer, p.gpio19)?;
+
+        let mut button = PinDriver::input(p.gpio9)?;
+        button.set_pull(Pull::Up)?;
+
+        Ok(Self {
+            i2c,
+            spi,
+            adc_watch: adc.iter().map(|_| None).collect(),
+            adc,
+            pwm,
+            servo,
+            button: Box::new(move || button.is_high()),
+            pin_watch: None,
+        })
+    }
+
+    /// What `capabilities` reports beyond GPIO.
+    pub fn capabilities(&self) -> Value {
+        json!({
+            "i2c": true,
+            "spi": true,
+            "adc": (0..self.adc.len()).collect::<Vec<_>>(),
+            "pwm": [PWM_PIN, SERVO_PIN],
+            "watch": [WATCH_PIN]
+        })
+    }
+
+    /// Run an extended command. `None` when `cmd` is not one of ours.
+    pub fn execute(&mut self, cmd: &str, params: &Value) -> Option<anyhow::Result<Value>> {
+        let result = match cmd {
+            "i2c_scan" => self.i2c_scan(),
+            "i2c_read" => self.i2c_read(params),
+            "i2c_write" => self.i2c_write(params),
+            "spi_transfer" => self.spi_transfer(params),
+            "adc_read" => self.adc_read(params),
+            "pwm_write" => self.pwm_write(params),
+            "servo_write" => self.servo_write(params),
+            "gpio_watch" => self.gpio_watch(params),
+            "adc_watch" => self.adc_watch(params),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// Check armed watches and return event frames for anything that changed.
+    pub fn poll_events(&mut self) -> Vec<Value> {
+        let mut events = Vec::new();
+
+        if let Some(last) = self.pin_watch {
+            let level = (self.button)();
+            if level != last {
+                self.pin_watch = Some(level);
+                events.push(json!({
+                    "event": "pin_change",
+                    "data": { "pin": WATCH_PIN, "value": u8::from(level) }
+                }));
+            }
+        }
+
+        for (channel, watch) in self.adc_watch.iter_mut().enumerate() {
+            let Some(watch) = watch else { continue };
+            let Ok(value) = (self.adc[channel])() else {
+                continue;
+            };
+            let direction =
+                if !watch.above && value >= watch.threshold.saturating_add(ADC_HYSTERESIS) {
+                    "rising"
+                } else if watch.above && value.saturating_add(ADC_HYSTERESIS) <= watch.threshold {
+                    "falling"
+                } else {
+                    continue;
+                };
+            watch.above = !watch.above;
+            events.push(json!({
+                "event": "threshold",
+                "data": {
+                    "channel": channel,
+                    "value": value,
+                    "threshold": watch.threshold,
+                    "direction": direction
+                }
+            }));
+        }
+
+        events
+    }
+
+    fn i2c_scan(&mut self) -> anyhow::Result<Value> {
+        let timeout = TickType::new_millis(I2C_SCAN_TIMEOUT_MS).ticks();
+        let addresses: Vec<u8> = (0x08..0x78)
+            .filter(|&address| self.i2c.write(address, &[], timeout).is_ok())
+            .collect();
+        Ok(json!({ "addresses": addresses }))
+    }
+
+    fn i2c_read(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let address = u8_param(params, "address")?;
+        let length = usize::from(u8_param(params, "length")?).clamp(1, 32);
+        let mut bytes = vec![0u8; length];
+        match params.get("register").and_then(Value::as_u64) {
+            Some(register) => {
+                let register = u8::try_from(register)?;
+                self.i2c
+                    .write_read(address, &[register], &mut bytes, BLOCK)?;
+            }
+            None => self.i2c.read(address, &mut bytes, BLOCK)?,
+        }
+        Ok(json!({ "bytes": bytes }))
+    }
+
+    fn i2c_write(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let address = u8_param(params, "address")?;
+        let bytes = bytes_param(params)?;
+        self.i2c.write(address, &bytes, BLOCK)?;
+        Ok(json!({ "written": bytes.len() }))
+    }
+
+    fn spi_transfer(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let write = bytes_param(params)?;
+        let mut read = vec![0u8; write.len()];
+        self.spi.transfer(&mut read, &write)?;
+        Ok(json!({ "bytes": read }))
+    }
+
+    fn adc_read(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let channel = usize::from(u8_param(params, "channel")?);
+        let samples = params
+            .get("samples")
+            .and_then(Value::as_u64)
+            .unwrap_or(1)
+            .clamp(1, 64);
+        let read = self
+            .adc
+            .get_mut(channel)
+            .ok_or_else(|| anyhow::anyhow!("ADC channel {channel} not configured"))?;
+        let mut total = 0u32;
+        for _ in 0..samples {
+            total += u32::from(read()?);
+        }
+        Ok(json!({ "channel": channel, "value": total / samples as u32 }))
+    }
+
+    fn pwm_write(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let pin = params.get("pin").and_then(Value::as_u64).unwrap_or(0);
+        if pin != PWM_PIN {
+            anyhow::bail!("Pin {pin} has no PWM channel (use {PWM_PIN})");
+        }
+        if let Some(frequency) = params.get("frequency").and_then(Value::as_u64) {
+            if frequency != u64::from(PWM_FREQUENCY_HZ) {
+                anyhow::bail!("PWM frequency is fixed at {PWM_FREQUENCY_HZ} Hz");
+            }
+        }
+        let duty = params
+            .get("duty")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0)
+            .clamp(0.0, 100.0);
+        let max = self.pwm.get_max_duty();
+        self.pwm.set_duty((f64::from(max) * duty / 100.0) as u32)?;
+        Ok(json!({ "pin": pin, "duty": duty }))
+    }
+
+    fn servo_write(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let pin = params.get("pin").and_then(Value::as_u64).unwrap_or(0);
+        if pin != SERVO_PIN {
+            anyhow::bail!("Pin {pin} is not a servo output (use {SERVO_PIN})");
+        }
+        let angle = params
+            .get("angle")
+            .and_then(Value::as_u64)
+            .unwrap_or(90)
+            .min(180) as u32;
+        // 0.5 ms (0°) to 2.5 ms (180°) in a 20 ms period.
+        let pulse_us = 500 + angle * 2000 / 180;
+        let max = self.servo.get_max_duty();
+        self.servo.set_duty(max * pulse_us / 20_000)?;
+        Ok(json!({ "pin": pin, "angle": angle }))
+    }
+
+    fn gpio_watch(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let pin = params.get("pin").and_then(Value::as_u64).unwrap_or(0);
+        if pin != WATCH_PIN {
+            anyhow::bail!("Pin {pin} cannot be watched (use {WATCH_PIN})");
+        }
+        let enabled = params
+            .get("enabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        self.pin_watch = enabled.then(|| (self.button)());
+        Ok(json!({}))
+    }
+
+    fn adc_watch(&mut self, params: &Value) -> anyhow::Result<Value> {
+        let channel = usize::from(u8_param(params, "channel")?);
+        if channel >= self.adc.len() {
+            anyhow::bail!("ADC channel {channel} not configured");
+        }
+        let enabled = params
+            .get("enabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        self.adc_watch[channel] = if enabled {
+            let threshold = params
+                .get("threshold")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| anyhow::anyhow!("missing threshold"))?;
+            let threshold = u16::try_from(threshold)?;
+            let value = (self.adc[channel])()?;
+            Some(AdcWatch {
+                threshold,
+                above: value >= threshold,
+            })
+        } else {
+            None
+        };
+        Ok(json!({}))
+    }
+}
+
+fn u8_param(params: &Value, key: &str) -> anyhow::Result<u8> {
+    let value = params
+        .get(key)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| anyhow::anyhow!("missing {key}"))?;
+    Ok(u8::try_from(value)?)
+}
+
+fn bytes_param(params: &Value) -> anyhow::Result<Vec<u8>> {
+    params
+        .get("bytes")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("missing bytes"))?
+        .iter()
+        .map(|v| {
+            v.as_u64()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| anyhow::anyhow!("bytes must be 0-255"))
+        })
+        .collect()
+}
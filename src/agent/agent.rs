@@ -252,12 +252,19 @@ impl Agent {
     }
 
     pub fn from_config(config: &Config) -> Result<Self> {
-        if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
+        if let Err(error) = crate::plugins::runtime::initialize_from_config(
+            &config.plugins,
+            &config.security,
+            config.autonomy.level,
+        ) {
             tracing::warn!("plugin registry initialization skipped: {error}");
         }
 
-        let observer: Arc<dyn Observer> =
-            Arc::from(observability::create_observer(&config.observability));
+        let observer: Arc<dyn Observer> = Arc::from(observability::create_observer_with_ledger(
+            &config.observability,
+            &config.cost,
+            &config.workspace_dir,
+        ));
         let runtime: Arc<dyn runtime::RuntimeAdapter> =
             Arc::from(runtime::create_runtime(&config.runtime)?);
         let security = Arc::new(SecurityPolicy::from_config(
@@ -644,6 +651,7 @@ impl Agent {
             ping_pong_cycles: self.config.loop_detection_ping_pong_cycles,
             failure_streak_threshold: self.config.loop_detection_failure_streak,
         });
+        let turn_checkpoint = crate::checkpoint::TurnCheckpoint::current_or_new("agent");
 
         for iteration in 0..self.config.max_tool_iterations {
             let messages = self.tool_dispatcher.to_provider_messages(&self.history);
@@ -710,7 +718,8 @@ impl Agent {
                 reasoning_content: response.reasoning_content.clone(),
             });
 
-            let results = self.execute_tools(&calls).await;
+            let results =
+                crate::checkpoint::scope(turn_checkpoint.clone(), self.execute_tools(&calls)).await;
 
             // ── Loop detection: record calls ─────────────────────
             for (call, result) in calls.iter().zip(results.iter()) {
@@ -6,6 +6,7 @@ use crate::config::{Config, ProgressMode};
 use crate::cost::{BudgetCheck, CostTracker, UsagePeriod};
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
+use crate::observability::trace_context::{self, SpanKind};
 use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
 use crate::providers::{
     self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
@@ -313,6 +314,7 @@ tokio::task_local! {
     static SAFETY_HEARTBEAT_CONFIG: Option<SafetyHeartbeatConfig>;
     static TOOL_LOOP_PROGRESS_MODE: ProgressMode;
     static TOOL_LOOP_COST_ENFORCEMENT_CONTEXT: Option<CostEnforcementContext>;
+    static TOOL_LOOP_CANCELLATION_TOKEN: Option<CancellationToken>;
 }
 
 /// Configuration for periodic safety-constraint re-injection (heartbeat).
@@ -340,8 +342,8 @@ pub(crate) fn create_cost_enforcement_context(
     if !cost_config.enabled {
         return None;
     }
-    let tracker = match CostTracker::new(cost_config.clone(), workspace_dir) {
-        Ok(tracker) => Arc::new(tracker),
+    let tracker = match crate::cost::shared_tracker(cost_config, workspace_dir) {
+        Ok(tracker) => tracker,
         Err(error) => {
             tracing::warn!("Cost budget preflight disabled: failed to initialize tracker: {error}");
             return None;
@@ -374,6 +376,19 @@ where
         .await
 }
 
+/// Run `future` with `token` as the cancellation token of every tool loop
+/// started inside it that was not handed an explicit token. Lets entry
+/// points such as the WebSocket chat stop turns built deep inside
+/// [`process_message_with_session`].
+pub(crate) async fn scope_turn_cancellation<F>(token: CancellationToken, future: F) -> F::Output
+where
+    F: Future,
+{
+    TOOL_LOOP_CANCELLATION_TOKEN
+        .scope(Some(token), future)
+        .await
+}
+
 fn should_inject_safety_heartbeat(counter: usize, interval: usize) -> bool {
     interval > 0 && counter > 0 && counter % interval == 0
 }
@@ -459,6 +474,20 @@ fn estimate_request_cost_usd(
     input_cost + output_cost
 }
 
+fn check_dimension_budgets(
+    context: &CostEnforcementContext,
+    attribution: &crate::cost::CostAttribution,
+    estimated_cost_usd: f64,
+) -> Option<crate::cost::DimensionBudgetBreach> {
+    context
+        .tracker
+        .check_dimension_budgets(attribution, estimated_cost_usd)
+        .unwrap_or_else(|error| {
+            tracing::warn!("Dimension budget check failed: {error}");
+            None
+        })
+}
+
 fn usage_period_label(period: UsagePeriod) -> &'static str {
     match period {
         UsagePeriod::Session => "session",
@@ -652,6 +681,28 @@ async fn await_non_cli_approval_decision(
     channel_name: &str,
     reply_target: &str,
     cancellation_token: Option<&CancellationToken>,
+) -> ApprovalResponse {
+    let decision = poll_non_cli_approval_decision(
+        mgr,
+        request_id,
+        sender,
+        channel_name,
+        reply_target,
+        cancellation_token,
+    )
+    .await;
+    // Close the inbox copy when the decision was taken in the chat.
+    crate::inbox::settle_approval(request_id, decision, channel_name);
+    decision
+}
+
+async fn poll_non_cli_approval_decision(
+    mgr: &ApprovalManager,
+    request_id: &str,
+    sender: &str,
+    channel_name: &str,
+    reply_target: &str,
+    cancellation_token: Option<&CancellationToken>,
 ) -> ApprovalResponse {
     let started = Instant::now();
 
@@ -660,6 +711,16 @@ async fn await_non_cli_approval_decision(
             return decision;
         }
 
+        // Resolved from the inbox (CLI or dashboard) instead of the chat.
+        if let Some(decision) = crate::inbox::approval_decision(request_id) {
+            let _ = if decision == ApprovalResponse::No {
+                mgr.reject_non_cli_pending_request(request_id, sender, channel_name, reply_target)
+            } else {
+                mgr.confirm_non_cli_pending_request(request_id, sender, channel_name, reply_target)
+            };
+            return decision;
+        }
+
         if !mgr.has_non_cli_pending_request(request_id) {
             // Fail closed when the request disappears without an explicit resolution.
             return ApprovalResponse::No;
@@ -1229,6 +1290,9 @@ pub(crate) async fn run_tool_call_loop_with_non_cli_approval_context(
 
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
+///
+/// The turn runs inside an `invoke_agent` span so LLM requests and tool
+/// calls made during it share one trace.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_tool_call_loop(
     provider: &dyn Provider,
@@ -1247,6 +1311,57 @@ pub(crate) async fn run_tool_call_loop(
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
     excluded_tools: &[String],
+) -> Result<String> {
+    let mut span = trace_context::Span::start("invoke_agent zeroclaw", SpanKind::Internal);
+    span.set_attribute("gen_ai.operation.name", "invoke_agent");
+    span.set_attribute("gen_ai.agent.name", "zeroclaw");
+    span.set_attribute("gen_ai.provider.name", provider_name);
+    span.set_attribute("gen_ai.request.model", model);
+    span.set_attribute("zeroclaw.channel", channel_name);
+    let result = span
+        .scope(run_tool_call_loop_turn(
+            provider,
+            history,
+            tools_registry,
+            observer,
+            provider_name,
+            model,
+            temperature,
+            silent,
+            approval,
+            channel_name,
+            multimodal_config,
+            max_tool_iterations,
+            cancellation_token,
+            on_delta,
+            hooks,
+            excluded_tools,
+        ))
+        .await;
+    if let Err(e) = &result {
+        span.set_error(crate::providers::sanitize_api_error(&e.to_string()));
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_call_loop_turn(
+    provider: &dyn Provider,
+    history: &mut Vec<ChatMessage>,
+    tools_registry: &[Box<dyn Tool>],
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    temperature: f64,
+    silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
+    multimodal_config: &crate::config::MultimodalConfig,
+    max_tool_iterations: usize,
+    cancellation_token: Option<CancellationToken>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    hooks: Option<&crate::hooks::HookRunner>,
+    excluded_tools: &[String],
 ) -> Result<String> {
     let non_cli_approval_context = TOOL_LOOP_NON_CLI_APPROVAL_CONTEXT
         .try_with(Clone::clone)
@@ -1268,6 +1383,16 @@ pub(crate) async fn run_tool_call_loop(
         max_tool_iterations
     };
 
+    // Every turn gets a token, registered so the emergency stop can reach it.
+    let cancellation_token = Some(
+        cancellation_token
+            .or_else(|| TOOL_LOOP_CANCELLATION_TOKEN.try_with(Clone::clone).ok().flatten())
+            .unwrap_or_default(),
+    );
+    let _active_turn = cancellation_token
+        .as_ref()
+        .map(|token| super::turn_control::register(String::new(), token.clone()));
+
     let tool_specs: Vec<crate::tools::ToolSpec> = tools_registry
         .iter()
         .filter(|tool| !excluded_tools.iter().any(|ex| ex == tool.name()))
@@ -1275,6 +1400,8 @@ pub(crate) async fn run_tool_call_loop(
         .collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
     let turn_id = Uuid::new_v4().to_string();
+    // Files the tools modify this turn are snapshotted into one checkpoint.
+    let turn_checkpoint = crate::checkpoint::TurnCheckpoint::current_or_new(channel_name);
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
     let mut missing_tool_call_retry_used = false;
     let mut missing_tool_call_retry_prompt: Option<String> = None;
@@ -1532,6 +1659,95 @@ pub(crate) async fn run_tool_call_loop(
                     },
                 }
             }
+
+            let attribution = crate::cost::attribution::current();
+            if let Some(breach) =
+                check_dimension_budgets(cost_ctx, &attribution, estimated_cost_usd)
+            {
+                let route_down_model = matches!(breach.action, CostEnforcementMode::RouteDown)
+                    .then(|| {
+                        breach
+                            .route_down_model
+                            .clone()
+                            .or_else(|| cost_ctx.route_down_model.clone())
+                    })
+                    .flatten()
+                    .filter(|candidate| *candidate != active_model);
+                let breach = match route_down_model {
+                    Some(route_down_model) => {
+                        let previous_model = std::mem::replace(&mut active_model, route_down_model);
+                        estimated_cost_usd = estimate_request_cost_usd(
+                            cost_ctx,
+                            provider_name,
+                            active_model.as_str(),
+                            &request_messages,
+                            request_tools,
+                        );
+                        runtime_trace::record_event(
+                            "cost_budget_route_down",
+                            Some(channel_name),
+                            Some(provider_name),
+                            Some(active_model.as_str()),
+                            Some(&turn_id),
+                            Some(true),
+                            Some("dimension budget exceeded; route-down candidate applied"),
+                            serde_json::json!({
+                                "iteration": iteration + 1,
+                                "dimension": breach.dimension.as_str(),
+                                "key": breach.key,
+                                "from_model": previous_model,
+                                "to_model": active_model,
+                                "estimated_cost_usd": estimated_cost_usd,
+                            }),
+                        );
+                        check_dimension_budgets(cost_ctx, &attribution, estimated_cost_usd)
+                    }
+                    None => Some(breach),
+                };
+
+                if let Some(breach) = breach {
+                    let message = format!(
+                        "{} ({} budget for {} '{}')",
+                        budget_exceeded_message(
+                            active_model.as_str(),
+                            estimated_cost_usd,
+                            breach.current_usd,
+                            breach.limit_usd,
+                            breach.period,
+                        ),
+                        usage_period_label(breach.period),
+                        breach.dimension.as_str(),
+                        breach.key
+                    );
+                    let warn_only = breach.action == CostEnforcementMode::Warn;
+                    runtime_trace::record_event(
+                        if warn_only {
+                            "cost_budget_exceeded_warn_mode"
+                        } else {
+                            "cost_budget_blocked"
+                        },
+                        Some(channel_name),
+                        Some(provider_name),
+                        Some(active_model.as_str()),
+                        Some(&turn_id),
+                        Some(warn_only),
+                        Some(&message),
+                        serde_json::json!({
+                            "iteration": iteration + 1,
+                            "dimension": breach.dimension.as_str(),
+                            "key": breach.key,
+                            "period": usage_period_label(breach.period),
+                            "current_usd": breach.current_usd,
+                            "limit_usd": breach.limit_usd,
+                            "estimated_cost_usd": estimated_cost_usd,
+                        }),
+                    );
+                    if !warn_only {
+                        return Err(anyhow::anyhow!(message));
+                    }
+                    tracing::warn!("{message} (warn mode): continuing request");
+                }
+            }
         }
 
         observer.record_event(&ObserverEvent::LlmRequest {
@@ -1560,13 +1776,22 @@ pub(crate) async fn run_tool_call_loop(
             hooks.fire_llm_input(history, active_model.as_str()).await;
         }
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &request_messages,
-                tools: request_tools,
-            },
-            active_model.as_str(),
-            temperature,
+        let mut llm_span =
+            trace_context::Span::start(format!("chat {active_model}"), SpanKind::Client);
+        llm_span.set_attribute("gen_ai.operation.name", "chat");
+        llm_span.set_attribute("gen_ai.provider.name", provider_name);
+        llm_span.set_attribute("gen_ai.request.model", active_model.clone());
+        llm_span.set_attribute("gen_ai.request.temperature", temperature);
+        let chat_future = trace_context::scope(
+            llm_span.context(),
+            provider.chat(
+                ChatRequest {
+                    messages: &request_messages,
+                    tools: request_tools,
+                },
+                active_model.as_str(),
+                temperature,
+            ),
         );
 
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
@@ -1602,6 +1827,22 @@ pub(crate) async fn run_tool_call_loop(
                     input_tokens: resp_input_tokens,
                     output_tokens: resp_output_tokens,
                 });
+                if let Some(tokens) = resp_input_tokens {
+                    llm_span.set_attribute("gen_ai.usage.input_tokens", tokens);
+                }
+                if let Some(tokens) = resp_output_tokens {
+                    llm_span.set_attribute("gen_ai.usage.output_tokens", tokens);
+                }
+                let finish_reason = if resp.tool_calls.is_empty() {
+                    "stop"
+                } else {
+                    "tool_calls"
+                };
+                llm_span.set_attribute(
+                    "gen_ai.response.finish_reasons",
+                    vec![finish_reason.to_string()],
+                );
+                llm_span.end();
 
                 let response_text = resp.text_or_empty().to_string();
                 // First try native structured tool calls (OpenAI-format).
@@ -1701,6 +1942,9 @@ pub(crate) async fn run_tool_call_loop(
                     input_tokens: None,
                     output_tokens: None,
                 });
+                llm_span.set_attribute("error.type", "provider_error");
+                llm_span.set_error(safe_error.clone());
+                llm_span.end();
                 runtime_trace::record_event(
                     "llm_response",
                     Some(channel_name),
@@ -1985,6 +2229,8 @@ pub(crate) async fn run_tool_call_loop(
                             ),
                         );
 
+                        crate::inbox::mirror_tool_approval(&pending, &tool_args);
+
                         let _ = ctx.prompt_tx.send(NonCliApprovalPrompt {
                             request_id: pending.request_id.clone(),
                             tool_name: tool_name.clone(),
@@ -2106,23 +2352,31 @@ pub(crate) async fn run_tool_call_loop(
             progress_indices.push(progress_idx);
         }
 
-        let executed_outcomes = if allow_parallel_execution && executable_calls.len() > 1 {
-            execute_tools_parallel(
-                &executable_calls,
-                tools_registry,
-                observer,
-                cancellation_token.as_ref(),
-            )
-            .await?
-        } else {
-            execute_tools_sequential(
-                &executable_calls,
-                tools_registry,
-                observer,
-                cancellation_token.as_ref(),
-            )
-            .await?
-        };
+        let tool_progress_tx = on_delta
+            .as_ref()
+            .filter(|_| should_emit_verbose_progress(progress_mode));
+        let executed_outcomes = crate::checkpoint::scope(turn_checkpoint.clone(), async {
+            if allow_parallel_execution && executable_calls.len() > 1 {
+                execute_tools_parallel(
+                    &executable_calls,
+                    tools_registry,
+                    observer,
+                    cancellation_token.as_ref(),
+                    tool_progress_tx,
+                )
+                .await
+            } else {
+                execute_tools_sequential(
+                    &executable_calls,
+                    tools_registry,
+                    observer,
+                    cancellation_token.as_ref(),
+                    tool_progress_tx,
+                )
+                .await
+            }
+        })
+        .await?;
 
         for (((idx, call), mut outcome), progress_idx) in executable_indices
             .iter()
@@ -2437,14 +2691,22 @@ pub async fn run(
     interactive: bool,
     hooks: Option<&crate::hooks::HookRunner>,
 ) -> Result<String> {
-    if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
+    if let Err(error) = crate::plugins::runtime::initialize_from_config(
+        &config.plugins,
+        &config.security,
+        config.autonomy.level,
+    ) {
         tracing::warn!("plugin registry initialization skipped: {error}");
     }
 
     hydrate_provider_env_vars(&config);
 
     // ── Wire up agnostic subsystems ──────────────────────────────
-    let base_observer = observability::create_observer(&config.observability);
+    let base_observer = observability::create_observer_with_ledger(
+        &config.observability,
+        &config.cost,
+        &config.workspace_dir,
+    );
     let observer: Arc<dyn Observer> = Arc::from(base_observer);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
@@ -2550,6 +2812,12 @@ pub async fn run(
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
     }
+    let device_tools =
+        crate::hardware::create_device_tools(&config.hardware, &tools_registry).await;
+    if !device_tools.is_empty() {
+        tracing::info!(count = device_tools.len(), "Hardware device tools added");
+        tools_registry.extend(device_tools);
+    }
 
     // ── Resolve provider ─────────────────────────────────────────
     let provider_name = provider_override
@@ -2577,19 +2845,39 @@ pub async fn run(
         model_support_vision: config.model_support_vision,
     };
 
-    let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
-        provider_name,
-        config.api_key.as_deref(),
-        config.api_url.as_deref(),
-        &config.reliability,
-        &config.model_routes,
-        &model_name,
-        &provider_runtime_options,
-    )?;
+    let cassette = &config.agent.cassette;
+    let replaying = cassette.mode == crate::config::CassetteMode::Replay;
+    let provider: Box<dyn Provider> = if replaying {
+        let path = cassette
+            .path
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("agent.cassette.path is required for replay"))?;
+        Box::new(providers::cassette::ReplayProvider::from_file(
+            path,
+            cassette.matching,
+        )?)
+    } else {
+        providers::create_routed_provider_with_options(
+            provider_name,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+            &config.reliability,
+            &config.model_routes,
+            &model_name,
+            &provider_runtime_options,
+        )?
+    };
+    let provider: Box<dyn Provider> = match (cassette.mode, cassette.path.as_ref()) {
+        (crate::config::CassetteMode::Record, Some(path)) => Box::new(
+            providers::cassette::RecordingProvider::new(provider, path.clone()),
+        ),
+        _ => provider,
+    };
 
     // Validate provider credentials early so misconfigured API keys are caught
     // before heavy setup (memory, tools, RAG) instead of failing silently at chat time.
-    if !providers::has_provider_credential(provider_name, config.api_key.as_deref()) {
+    // A replayed session never reaches the provider, so it needs no key.
+    if !replaying && !providers::has_provider_credential(provider_name, config.api_key.as_deref()) {
         // Bedrock uses AWS AKSK, not a single API key — skip this check for it.
         if provider_name != "bedrock" && provider_name != "aws-bedrock" && provider_name != "ollama"
         {
@@ -3268,6 +3556,22 @@ pub async fn run(
     Ok(final_output)
 }
 
+/// Keep only the tools `role` may call without a TOTP code.
+fn retain_role_tools(
+    tools: &mut Vec<Box<dyn Tool>>,
+    security: &crate::config::SecurityConfig,
+    role: &str,
+) -> Result<()> {
+    let registry = crate::security::RoleRegistry::from_config(&security.roles)?;
+    if !registry.contains(role) {
+        anyhow::bail!("Unknown role '{role}'");
+    }
+    tools.retain(|tool| {
+        registry.allows_without_totp(role, tool.name(), &security.otp.gated_actions)
+    });
+    Ok(())
+}
+
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
@@ -3279,25 +3583,47 @@ pub async fn process_message_with_session(
     message: &str,
     session_id: Option<&str>,
 ) -> Result<String> {
-    if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
+    Box::pin(process_message_with_role(config, message, session_id, None)).await
+}
+
+/// Like [`process_message_with_session`], for a user holding a
+/// `[security.roles]` role: the turn only gets the tools that role may use
+/// without a TOTP code.
+pub async fn process_message_with_role(
+    config: Config,
+    message: &str,
+    session_id: Option<&str>,
+    role: Option<&str>,
+) -> Result<String> {
+    if let Err(error) = crate::plugins::runtime::initialize_from_config(
+        &config.plugins,
+        &config.security,
+        config.autonomy.level,
+    ) {
         tracing::warn!("plugin registry initialization skipped: {error}");
     }
 
     hydrate_provider_env_vars(&config);
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let observer: Arc<dyn Observer> = Arc::from(observability::create_observer_with_ledger(
+        &config.observability,
+        &config.cost,
+        &config.workspace_dir,
+    ));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
         &config.autonomy,
         &config.workspace_dir,
     ));
+    // Memory and sessions live in the caller's tenant directory when the
+    // gateway isolates users; otherwise this is the workspace itself.
+    let storage_dir = crate::tenancy::workspace_dir(&config.workspace_dir);
     let (mem, _sync_engine): (Arc<dyn Memory>, _) = if config.sync.enabled {
         let (synced_mem, engine) = memory::create_synced_memory(
             &config.memory,
             &config.sync,
-            &config.workspace_dir,
+            &storage_dir,
             config.api_key.as_deref(),
         )?;
         (synced_mem, engine)
@@ -3305,7 +3631,7 @@ pub async fn process_message_with_session(
         let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
             &config.memory,
             Some(&config.storage.provider.config),
-            &config.workspace_dir,
+            &storage_dir,
             config.api_key.as_deref(),
         )?);
         (mem, None)
@@ -3338,6 +3664,12 @@ pub async fn process_message_with_session(
     let peripheral_tools: Vec<Box<dyn Tool>> =
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
+    let device_tools =
+        crate::hardware::create_device_tools(&config.hardware, &tools_registry).await;
+    tools_registry.extend(device_tools);
+    if let Some(role) = role {
+        retain_role_tools(&mut tools_registry, &config.security, role)?;
+    }
 
     let provider_name = config.default_provider.as_deref().unwrap_or("gemini");
     let model_name = crate::config::resolve_default_model_id(
@@ -3492,6 +3824,9 @@ pub async fn process_message_with_session(
             "Query connected hardware for reported GPIO pins and LED pin.",
         ));
     }
+    if role.is_some() {
+        tool_descs.retain(|(name, _)| tools_registry.iter().any(|tool| tool.name() == *name));
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
@@ -3525,7 +3860,7 @@ pub async fn process_message_with_session(
     // saving ~95% of tokens on the current turn AND all future recalls.
     let message_for_processing: String =
         if crate::memory::document_summarizer::should_summarize(message) {
-            let doc_db_path = config.workspace_dir.join("memory").join("brain.db");
+            let doc_db_path = storage_dir.join("memory").join("brain.db");
             match crate::memory::document_store::DocumentStore::open(&doc_db_path) {
                 Ok(store) => {
                     // Infer category from content hints
@@ -3597,7 +3932,7 @@ pub async fn process_message_with_session(
     let sender_id = session_id.unwrap_or("gateway_user");
     let session_for_turns = match super::session::shared_session_manager(
         &config.agent.session,
-        &config.workspace_dir,
+        &storage_dir,
     ) {
         Ok(Some(mgr)) => mgr.get_or_create(sender_id).await.ok(),
         _ => None,
@@ -1,10 +1,12 @@
 use super::parsing::ParsedToolCall;
-use super::{scrub_credentials, ToolLoopCancelled};
+use super::{scrub_credentials, DRAFT_PROGRESS_SENTINEL};
 use crate::approval::ApprovalManager;
+use crate::observability::trace_context::{self, SpanKind};
 use crate::observability::{Observer, ObserverEvent};
-use crate::tools::Tool;
+use crate::tools::{Tool, ToolContext, ToolProgressSink, ToolResult};
 use anyhow::Result;
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 /// Harness: Maximum tool output size in characters.
@@ -16,6 +18,53 @@ const TOOL_OUTPUT_MAX_CHARS: usize = 50_000;
 /// If a tool takes longer than this, a performance warning is logged.
 const TOOL_SLOW_THRESHOLD: Duration = Duration::from_secs(30);
 
+/// Harness: How long a cancelled tool may keep running to hand back its
+/// partial output before the loop gives up on it.
+const TOOL_CANCEL_GRACE: Duration = Duration::from_secs(3);
+
+/// Build the progress sink for one tool call. Updates are traced and, when
+/// the caller streams drafts, forwarded as progress lines.
+fn tool_progress_sink(call_name: &str, on_progress: Option<&mpsc::Sender<String>>) -> ToolProgressSink {
+    let tool = call_name.to_string();
+    let tx = on_progress.cloned();
+    ToolProgressSink::new(move |progress| {
+        tracing::debug!(tool = %tool, fraction = ?progress.fraction, "{}", progress.message);
+        if let Some(tx) = tx.as_ref() {
+            let percent = progress
+                .fraction
+                .map(|f| format!(" ({:.0}%)", f * 100.0))
+                .unwrap_or_default();
+            let _ = tx.try_send(format!(
+                "{DRAFT_PROGRESS_SENTINEL}\u{23f3} {tool}: {}{percent}\n",
+                progress.message
+            ));
+        }
+    })
+}
+
+/// Render a cancelled tool result so the partial output survives in history.
+fn cancelled_tool_output(partial: &str) -> String {
+    if partial.trim().is_empty() {
+        "[Cancelled by user before the tool produced output]".to_string()
+    } else {
+        format!("[Cancelled by user — partial output follows]\n{partial}")
+    }
+}
+
+fn truncate_tool_output(output: String) -> String {
+    if output.chars().count() > TOOL_OUTPUT_MAX_CHARS {
+        let truncated: String = output.chars().take(TOOL_OUTPUT_MAX_CHARS).collect();
+        format!(
+            "{truncated}\n\n[Harness: output truncated from {} to {} chars. \
+             Use memory_recall to search for full content if needed.]",
+            output.chars().count(),
+            TOOL_OUTPUT_MAX_CHARS
+        )
+    } else {
+        output
+    }
+}
+
 fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> Option<&'a dyn Tool> {
     tools.iter().find(|t| t.name() == name).map(|t| t.as_ref())
 }
@@ -25,14 +74,20 @@ async fn execute_one_tool(
     tools_registry: &[Box<dyn Tool>],
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
+    on_progress: Option<&mpsc::Sender<String>>,
 ) -> Result<ToolExecutionOutcome> {
     observer.record_event(&ObserverEvent::ToolCallStart {
         tool: call_name.to_string(),
     });
     let start = Instant::now();
+    let mut span =
+        trace_context::Span::start(format!("execute_tool {call_name}"), SpanKind::Internal);
+    span.set_attribute("gen_ai.operation.name", "execute_tool");
+    span.set_attribute("gen_ai.tool.name", call_name);
 
     let Some(tool) = find_tool(tools_registry, call_name) else {
         let reason = format!("Unknown tool: {call_name}");
+        span.set_error(reason.clone());
         let duration = start.elapsed();
         observer.record_event(&ObserverEvent::ToolCall {
             tool: call_name.to_string(),
@@ -47,14 +102,27 @@ async fn execute_one_tool(
         });
     };
 
-    let tool_future = tool.execute(call_arguments);
-    let tool_result = if let Some(token) = cancellation_token {
-        tokio::select! {
-            () = token.cancelled() => return Err(ToolLoopCancelled.into()),
-            result = tool_future => result,
+    let ctx = ToolContext::new(
+        cancellation_token.map_or_else(CancellationToken::new, CancellationToken::child_token),
+        tool_progress_sink(call_name, on_progress),
+    );
+    let tool_future = trace_context::scope(
+        span.context(),
+        crate::cost::attribution::scope(
+            crate::cost::CostAttribution::tool(call_name),
+            tool.execute_with_context(call_arguments, &ctx),
+        ),
+    );
+    tokio::pin!(tool_future);
+    let tool_result = tokio::select! {
+        result = &mut tool_future => result,
+        () = ctx.cancelled() => {
+            // Cooperative tools wind down and return their partial output;
+            // anything still running after the grace period is dropped.
+            tokio::time::timeout(TOOL_CANCEL_GRACE, &mut tool_future)
+                .await
+                .unwrap_or_else(|_| Ok(ToolResult::cancelled(String::new())))
         }
-    } else {
-        tool_future.await
     };
 
     match tool_result {
@@ -78,26 +146,26 @@ async fn execute_one_tool(
             });
             if r.success {
                 // Harness: truncate oversized tool output to prevent context pollution
-                let output = scrub_credentials(&r.output);
-                let output = if output.chars().count() > TOOL_OUTPUT_MAX_CHARS {
-                    let truncated: String = output.chars().take(TOOL_OUTPUT_MAX_CHARS).collect();
-                    format!(
-                        "{truncated}\n\n[Harness: output truncated from {} to {} chars. \
-                         Use memory_recall to search for full content if needed.]",
-                        output.chars().count(),
-                        TOOL_OUTPUT_MAX_CHARS
-                    )
-                } else {
-                    output
-                };
+                let output = truncate_tool_output(scrub_credentials(&r.output));
                 Ok(ToolExecutionOutcome {
                     output,
                     success: true,
                     error_reason: None,
                     duration,
                 })
+            } else if r.is_cancelled() {
+                let output = truncate_tool_output(cancelled_tool_output(&scrub_credentials(
+                    &r.output,
+                )));
+                Ok(ToolExecutionOutcome {
+                    output,
+                    success: false,
+                    error_reason: r.error,
+                    duration,
+                })
             } else {
                 let reason = r.error.unwrap_or(r.output);
+                span.set_error(scrub_credentials(&reason));
                 Ok(ToolExecutionOutcome {
                     output: format!("Error: {reason}"),
                     success: false,
@@ -114,6 +182,7 @@ async fn execute_one_tool(
                 success: false,
             });
             let reason = format!("Error executing {call_name}: {e}");
+            span.set_error(scrub_credentials(&reason));
             Ok(ToolExecutionOutcome {
                 output: reason.clone(),
                 success: false,
@@ -155,6 +224,7 @@ pub(super) async fn execute_tools_parallel(
     tools_registry: &[Box<dyn Tool>],
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
+    on_progress: Option<&mpsc::Sender<String>>,
 ) -> Result<Vec<ToolExecutionOutcome>> {
     let futures: Vec<_> = tool_calls
         .iter()
@@ -165,6 +235,7 @@ pub(super) async fn execute_tools_parallel(
                 tools_registry,
                 observer,
                 cancellation_token,
+                on_progress,
             )
         })
         .collect();
@@ -178,10 +249,21 @@ pub(super) async fn execute_tools_sequential(
     tools_registry: &[Box<dyn Tool>],
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
+    on_progress: Option<&mpsc::Sender<String>>,
 ) -> Result<Vec<ToolExecutionOutcome>> {
     let mut outcomes = Vec::with_capacity(tool_calls.len());
 
     for call in tool_calls {
+        if cancellation_token.is_some_and(CancellationToken::is_cancelled) {
+            let skipped = "Skipped: the turn was cancelled before this tool ran.".to_string();
+            outcomes.push(ToolExecutionOutcome {
+                output: skipped.clone(),
+                success: false,
+                error_reason: Some(skipped),
+                duration: Duration::ZERO,
+            });
+            continue;
+        }
         outcomes.push(
             execute_one_tool(
                 &call.name,
@@ -189,6 +271,7 @@ pub(super) async fn execute_tools_sequential(
                 tools_registry,
                 observer,
                 cancellation_token,
+                on_progress,
             )
             .await?,
         );
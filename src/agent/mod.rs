@@ -8,6 +8,8 @@ pub mod prompt;
 pub mod quota_aware;
 pub mod research;
 pub mod session;
+pub mod team;
+pub mod turn_control;
 
 #[cfg(test)]
 mod tests;
@@ -15,4 +17,4 @@ mod tests;
 #[allow(unused_imports)]
 pub use agent::{Agent, AgentBuilder};
 #[allow(unused_imports)]
-pub use loop_::{process_message, process_message_with_session, run};
+pub use loop_::{process_message, process_message_with_role, process_message_with_session, run};
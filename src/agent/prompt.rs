@@ -348,7 +348,9 @@ impl PromptSection for OntologySection {
         );
 
         // Attempt to load preferences from workspace ontology DB.
-        let db_path = ctx.workspace_dir.join("memory").join("brain.db");
+        let db_path = crate::tenancy::workspace_dir(ctx.workspace_dir)
+            .join("memory")
+            .join("brain.db");
         if db_path.exists() {
             if let Ok(conn) = rusqlite::Connection::open(&db_path) {
                 let _ = conn.execute_batch("PRAGMA busy_timeout = 5000;");
@@ -1408,6 +1410,7 @@ mod tests {
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: None,
             always: false,
+            permissions: None,
         }];
 
         let ctx = PromptContext {
@@ -1447,6 +1450,7 @@ mod tests {
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: Some(Path::new("/tmp/workspace/skills/deploy/SKILL.md").to_path_buf()),
             always: false,
+            permissions: None,
         }];
 
         let ctx = PromptContext {
@@ -1508,6 +1512,7 @@ mod tests {
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
             always: false,
+            permissions: None,
         }];
         let ctx = PromptContext {
             workspace_dir: Path::new("/tmp/workspace"),
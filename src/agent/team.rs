@@ -0,0 +1,1258 @@
+//! Hierarchical agent teams — a supervisor plans, roles execute, the
+//! supervisor merges.
+//!
+//! A run goes through three phases:
+//! 1. The supervisor turns the goal into a JSON plan of subtasks, each
+//!    assigned to a configured role and optionally depending on others.
+//! 2. Subtasks run in dependency waves through [`DelegateTool`], so roles get
+//!    the same tool allowlists, agentic loops and timeouts as `[agents]`.
+//!    Outputs of dependencies are passed on as context, and a role may hand
+//!    follow-up work to the roles listed in its `handoff_to`.
+//! 3. The supervisor merges the subtask results into one answer.
+//!
+//! Assignments and results travel over a coordination bus under a single
+//! conversation (`team:<run_id>`): each role takes its own assignment off its
+//! inbox and acknowledges it when done, and the supervisor builds the subtask
+//! outcomes from the `team.result` messages in its inbox. The plan and
+//! per-subtask state are kept on the bus's shared-context blackboard under
+//! `team/<run_id>/`, and every phase is recorded as a runtime trace event with
+//! the run ID as turn ID. Supervisor and role calls are recorded on the
+//! run's cost tracker under the workflow `team:<name>:<run_id>`, and the team
+//! budget is checked after every role turn and before the merge.
+
+use crate::config::schema::ModelPricing;
+use crate::config::{DelegateAgentConfig, MultimodalConfig, TeamConfig};
+use crate::coordination::{CoordinationEnvelope, CoordinationPayload, MessageBus};
+use crate::cost::{CostAttribution, CostTracker};
+use crate::observability::{runtime_trace, CostObserver, NoopObserver, Observer, ObserverEvent};
+use crate::providers::{self, ChatMessage, ChatRequest, Provider, ProviderRuntimeOptions};
+use crate::security::SecurityPolicy;
+use crate::tools::{DelegateTool, Tool, ToolContext};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Prefix of the lines a role uses to hand follow-up work to another role.
+const HANDOFF_PREFIX: &str = "HANDOFF ";
+/// Maximum characters of subtask output kept on the blackboard and in traces.
+const PREVIEW_MAX_CHARS: usize = 400;
+
+const PLAN_SYSTEM_PROMPT: &str = "You are the supervisor of an agent team. Break the goal \
+into subtasks for the team's roles. Reply with a JSON array only, one object per subtask: \
+{\"id\": \"short-id\", \"role\": \"role name\", \"task\": \"self-contained instructions\", \
+\"depends_on\": [\"ids whose results this subtask needs\"]}. Use only the listed roles and \
+keep the plan as small as the goal allows.";
+
+const MERGE_SYSTEM_PROMPT: &str = "You are the supervisor of an agent team. Combine the \
+results of your team's subtasks into one complete answer to the goal. Resolve \
+contradictions, drop duplication and say plainly what could not be done.";
+
+/// One unit of work in a team plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subtask {
+    #[serde(default)]
+    pub id: String,
+    pub role: String,
+    pub task: String,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// How one subtask ended.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubtaskOutcome {
+    pub id: String,
+    pub role: String,
+    pub success: bool,
+    pub output: String,
+}
+
+/// Result of a team run.
+#[derive(Debug, Clone, Serialize)]
+pub struct TeamRunReport {
+    pub run_id: String,
+    pub team: String,
+    pub plan: Vec<Subtask>,
+    pub outcomes: Vec<SubtaskOutcome>,
+    /// Merged answer, or the raw subtask results when the run was stopped.
+    pub answer: String,
+    /// Spend attributed to the run; `None` without a cost tracker.
+    pub spent_usd: Option<f64>,
+    /// Why the run stopped before merging (budget, timeout, cancellation).
+    pub stopped: Option<String>,
+}
+
+/// Runs one configured team.
+pub struct TeamRunner {
+    name: String,
+    team: TeamConfig,
+    fallback_credential: Option<String>,
+    security: Arc<SecurityPolicy>,
+    provider_runtime_options: ProviderRuntimeOptions,
+    parent_tools: Arc<Vec<Arc<dyn Tool>>>,
+    multimodal_config: MultimodalConfig,
+    bus: MessageBus,
+    /// Ledger the run's spend is recorded on and checked against.
+    cost_tracker: Option<Arc<CostTracker>>,
+    cost_prices: HashMap<String, ModelPricing>,
+}
+
+impl TeamRunner {
+    pub fn new(
+        name: impl Into<String>,
+        team: TeamConfig,
+        fallback_credential: Option<String>,
+        security: Arc<SecurityPolicy>,
+        provider_runtime_options: ProviderRuntimeOptions,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            team,
+            fallback_credential,
+            security,
+            provider_runtime_options,
+            parent_tools: Arc::new(Vec::new()),
+            multimodal_config: MultimodalConfig::default(),
+            bus: MessageBus::new(),
+            cost_tracker: None,
+            cost_prices: HashMap::new(),
+        }
+    }
+
+    /// Attach parent tools used to build role allowlist registries.
+    pub fn with_parent_tools(mut self, parent_tools: Arc<Vec<Arc<dyn Tool>>>) -> Self {
+        self.parent_tools = parent_tools;
+        self
+    }
+
+    /// Attach multimodal configuration for agentic role loops.
+    pub fn with_multimodal_config(mut self, config: MultimodalConfig) -> Self {
+        self.multimodal_config = config;
+        self
+    }
+
+    /// Carry run traffic on `bus` instead of a private one.
+    pub fn with_coordination_bus(mut self, bus: MessageBus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Record the run's spend on `tracker`, priced with `prices`, and
+    /// enforce the team budget against it.
+    pub fn with_cost_tracker(
+        mut self,
+        tracker: Arc<CostTracker>,
+        prices: HashMap<String, ModelPricing>,
+    ) -> Self {
+        self.cost_tracker = Some(tracker);
+        self.cost_prices = prices;
+        self
+    }
+
+    /// Bus name of the supervisor or of a role.
+    fn bus_agent(&self, role: &str) -> String {
+        format!("{}.{role}", self.name)
+    }
+
+    /// Plan, execute and merge `goal`. Spend during the run is attributed to
+    /// the workflow `team:<name>:<run_id>`.
+    pub async fn run(&self, goal: &str, ctx: &ToolContext) -> Result<TeamRunReport> {
+        if self.team.roles.is_empty() {
+            bail!("Team '{}' has no roles configured", self.name);
+        }
+        let run_id = Uuid::new_v4().to_string();
+        let workflow = format!("team:{}:{run_id}", self.name);
+        crate::cost::attribution::scope(
+            CostAttribution::workflow(workflow.clone()),
+            self.run_scoped(goal, run_id, workflow, ctx),
+        )
+        .await
+    }
+
+    async fn run_scoped(
+        &self,
+        goal: &str,
+        run_id: String,
+        workflow: String,
+        ctx: &ToolContext,
+    ) -> Result<TeamRunReport> {
+        let observer: Arc<dyn Observer> = match &self.cost_tracker {
+            Some(tracker) => Arc::new(CostObserver::new(tracker.clone(), self.cost_prices.clone())),
+            None => Arc::new(NoopObserver),
+        };
+        let mut run = Run {
+            runner: self,
+            run_id,
+            workflow,
+            started_at: Utc::now(),
+            deadline: Instant::now() + Duration::from_secs(self.team.timeout_secs.max(1)),
+            versions: HashMap::new(),
+            observer,
+        };
+        for agent in std::iter::once("supervisor").chain(self.team.roles.keys().map(String::as_str))
+        {
+            if let Err(error) = self.bus.register_agent(self.bus_agent(agent)) {
+                tracing::warn!(
+                    "team '{}': failed to register '{agent}': {error}",
+                    self.name
+                );
+            }
+        }
+        if self.team.budget_usd.is_some() && self.cost_tracker.is_none() {
+            tracing::warn!(
+                "team '{}': budget_usd is set but cost tracking is off; budget not enforced",
+                self.name
+            );
+        }
+        run.trace(
+            "team_run_start",
+            None,
+            goal,
+            json!({ "team": self.name, "roles": self.team.roles.keys().collect::<Vec<_>>() }),
+        );
+
+        let supervisor = providers::create_provider_with_options(
+            &self.team.supervisor.provider,
+            self.team
+                .supervisor
+                .api_key
+                .as_deref()
+                .or(self.fallback_credential.as_deref()),
+            &self.provider_runtime_options,
+        )
+        .with_context(|| {
+            format!(
+                "Failed to create supervisor provider for team '{}'",
+                self.name
+            )
+        })?;
+
+        let plan_reply = run
+            .supervise(
+                &*supervisor,
+                PLAN_SYSTEM_PROMPT,
+                &self.plan_prompt(goal),
+                ctx,
+            )
+            .await
+            .context("Supervisor failed to plan")?;
+        let plan = parse_plan(&plan_reply, &self.team.roles, self.team.max_subtasks)?;
+        run.patch("plan", json!({ "goal": goal, "subtasks": plan }));
+        run.trace(
+            "team_plan",
+            Some(true),
+            &format!("{} subtasks", plan.len()),
+            json!({ "subtasks": plan }),
+        );
+
+        let (outcomes, mut stopped) = run.execute(goal, plan.clone(), ctx).await;
+        if stopped.is_none() {
+            stopped = run.budget_exhausted();
+        }
+
+        let answer = match &stopped {
+            Some(_) => render_outcomes(&outcomes),
+            None if outcomes.iter().all(|outcome| !outcome.success) => {
+                format!("No subtask succeeded.\n\n{}", render_outcomes(&outcomes))
+            }
+            None => run
+                .supervise(
+                    &*supervisor,
+                    MERGE_SYSTEM_PROMPT,
+                    &merge_prompt(goal, &outcomes),
+                    ctx,
+                )
+                .await
+                .context("Supervisor failed to merge results")?,
+        };
+
+        let spent_usd = run.spent_usd();
+        run.patch(
+            "result",
+            json!({ "stopped": stopped, "spent_usd": spent_usd, "answer": preview(&answer) }),
+        );
+        run.trace(
+            "team_run_end",
+            Some(stopped.is_none()),
+            stopped.as_deref().unwrap_or("completed"),
+            json!({
+                "succeeded": outcomes.iter().filter(|outcome| outcome.success).count(),
+                "subtasks": outcomes.len(),
+                "spent_usd": spent_usd,
+            }),
+        );
+
+        Ok(TeamRunReport {
+            run_id: run.run_id,
+            team: self.name.clone(),
+            plan,
+            outcomes,
+            answer,
+            spent_usd,
+            stopped,
+        })
+    }
+
+    fn plan_prompt(&self, goal: &str) -> String {
+        let mut roles: Vec<_> = self.team.roles.iter().collect();
+        roles.sort_by_key(|(name, _)| name.as_str());
+        let roles = roles
+            .iter()
+            .map(|(name, role)| format!("- {name}: {}", role.description))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let supervisor_prompt = self
+            .team
+            .supervisor
+            .system_prompt
+            .as_deref()
+            .map(|prompt| format!("{prompt}\n\n"))
+            .unwrap_or_default();
+        format!(
+            "{supervisor_prompt}[Roles]\n{roles}\n\n[Limits]\nAt most {} subtasks.\n\n[Goal]\n{goal}",
+            self.team.max_subtasks
+        )
+    }
+
+    fn delegate_tool(&self, observer: Arc<dyn Observer>) -> DelegateTool {
+        let agents: HashMap<String, DelegateAgentConfig> = self
+            .team
+            .roles
+            .iter()
+            .map(|(name, role)| (name.clone(), role.agent.clone()))
+            .collect();
+        // The team publishes its own assignment traffic; per-call delegate
+        // traces would split the run across conversations.
+        DelegateTool::new_with_options(
+            agents,
+            self.fallback_credential.clone(),
+            self.security.clone(),
+            self.provider_runtime_options.clone(),
+        )
+        .with_parent_tools(self.parent_tools.clone())
+        .with_multimodal_config(self.multimodal_config.clone())
+        .with_observer(observer)
+        .with_coordination_disabled()
+    }
+}
+
+/// State of one run in progress.
+struct Run<'a> {
+    runner: &'a TeamRunner,
+    run_id: String,
+    workflow: String,
+    started_at: DateTime<Utc>,
+    deadline: Instant,
+    /// Last written version of each blackboard key.
+    versions: HashMap<String, u64>,
+    /// Records supervisor and role LLM usage on the runner's cost tracker.
+    observer: Arc<dyn Observer>,
+}
+
+impl Run<'_> {
+    fn conversation_id(&self) -> String {
+        format!("team:{}", self.run_id)
+    }
+
+    async fn supervise(
+        &self,
+        provider: &dyn Provider,
+        instructions: &str,
+        prompt: &str,
+        ctx: &ToolContext,
+    ) -> Result<String> {
+        let supervisor = &self.runner.team.supervisor;
+        let messages = [ChatMessage::system(instructions), ChatMessage::user(prompt)];
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        let started_at = Instant::now();
+        let reply = tokio::select! {
+            () = ctx.cancelled() => bail!("Team run was cancelled"),
+            reply = tokio::time::timeout(
+                remaining,
+                provider.chat(
+                    ChatRequest {
+                        messages: &messages,
+                        tools: None,
+                    },
+                    &supervisor.model,
+                    supervisor.temperature.unwrap_or(0.2),
+                ),
+            ) => reply.map_err(|_| anyhow::anyhow!("Team run timed out"))?,
+        };
+        let usage = reply
+            .as_ref()
+            .ok()
+            .and_then(|response| response.usage.clone())
+            .unwrap_or_default();
+        self.observer.record_event(&ObserverEvent::LlmResponse {
+            provider: supervisor.provider.clone(),
+            model: supervisor.model.clone(),
+            duration: started_at.elapsed(),
+            success: reply.is_ok(),
+            error_message: reply
+                .as_ref()
+                .err()
+                .map(|e| providers::sanitize_api_error(&e.to_string())),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+        });
+        Ok(reply?.text.unwrap_or_default())
+    }
+
+    /// Run the plan wave by wave. Returns the outcomes in completion order
+    /// and, if the run was cut short, why.
+    async fn execute(
+        &mut self,
+        goal: &str,
+        plan: Vec<Subtask>,
+        ctx: &ToolContext,
+    ) -> (Vec<SubtaskOutcome>, Option<String>) {
+        let delegate = self.runner.delegate_tool(self.observer.clone());
+        let mut pending = plan;
+        let mut outcomes: Vec<SubtaskOutcome> = Vec::new();
+        let mut handoffs = 0;
+
+        for subtask in &pending {
+            self.patch_task(subtask, "queued", None);
+        }
+
+        while !pending.is_empty() {
+            if let Some(reason) = self.stop_reason(ctx) {
+                return (outcomes, Some(reason));
+            }
+            let finished: HashSet<&str> = outcomes.iter().map(|o| o.id.as_str()).collect();
+            let (wave, rest) = next_wave(pending, &finished);
+            pending = rest;
+            if wave.is_empty() {
+                for subtask in pending.drain(..) {
+                    let outcome = self.skip(&subtask, "dependency cycle in plan");
+                    outcomes.push(outcome);
+                }
+                break;
+            }
+
+            let mut runnable = Vec::new();
+            for subtask in wave {
+                let failed_dep = subtask.depends_on.iter().find(|dep| {
+                    outcomes
+                        .iter()
+                        .any(|outcome| &outcome.id == *dep && !outcome.success)
+                });
+                if let Some(dep) = failed_dep {
+                    let outcome = self.skip(&subtask, &format!("dependency '{dep}' failed"));
+                    outcomes.push(outcome);
+                } else {
+                    let assignment = self.assign(&subtask);
+                    runnable.push((subtask, assignment));
+                }
+            }
+
+            // Role turns still running are cancelled once one of them
+            // exhausts the budget.
+            let wave_ctx = ToolContext::new(ctx.cancellation.child_token(), ctx.progress.clone());
+            let run = &*self;
+            let delegate = &delegate;
+            let remaining = self.deadline.saturating_duration_since(Instant::now());
+            let results = tokio::time::timeout(
+                remaining,
+                futures_util::future::join_all(runnable.iter().map(|(subtask, assignment)| {
+                    let context = dependency_context(goal, subtask, &outcomes);
+                    let wave_ctx = &wave_ctx;
+                    async move {
+                        let result = run
+                            .work(delegate, subtask, assignment.as_deref(), context, wave_ctx)
+                            .await;
+                        if run.budget_exhausted().is_some() {
+                            wave_ctx.cancellation.cancel();
+                        }
+                        result
+                    }
+                })),
+            )
+            .await;
+            let Ok(results) = results else {
+                return (outcomes, Some("timeout".into()));
+            };
+            for ((subtask, assignment), (success, output)) in runnable.iter().zip(&results) {
+                self.report(subtask, assignment.as_deref(), *success, output);
+            }
+            let mut reported = self.collect_results();
+
+            for ((subtask, _), result) in runnable.into_iter().zip(results) {
+                // A result that could not be sent over the bus is used as is.
+                let (success, output) = reported.remove(&subtask.id).unwrap_or(result);
+                if success {
+                    for (role, task) in self.allowed_handoffs(&subtask.role, &output) {
+                        if handoffs >= self.runner.team.max_handoffs {
+                            break;
+                        }
+                        handoffs += 1;
+                        let next = Subtask {
+                            id: format!("{}.handoff{handoffs}", subtask.id),
+                            role,
+                            task,
+                            depends_on: vec![subtask.id.clone()],
+                        };
+                        self.patch_task(&next, "queued", None);
+                        pending.push(next);
+                    }
+                }
+                outcomes.push(SubtaskOutcome {
+                    id: subtask.id,
+                    role: subtask.role,
+                    success,
+                    output,
+                });
+            }
+        }
+        (outcomes, None)
+    }
+
+    async fn work(
+        &self,
+        delegate: &DelegateTool,
+        subtask: &Subtask,
+        assignment: Option<&str>,
+        context: String,
+        ctx: &ToolContext,
+    ) -> (bool, String) {
+        // The role picks up its own assignment from its inbox and acknowledges
+        // it once the work is done; roles running in parallel share an inbox.
+        let agent = self.runner.bus_agent(&subtask.role);
+        let received = assignment.and_then(|message_id| {
+            self.runner
+                .bus
+                .receive_message(&agent, message_id)
+                .ok()
+                .flatten()
+        });
+        // Only a subtask whose assignment could not be sent falls back to the
+        // plan's copy of the task.
+        let task = received
+            .as_ref()
+            .and_then(|entry| assigned_task(&entry.envelope))
+            .unwrap_or_else(|| subtask.task.clone());
+        let result = self.delegate(delegate, subtask, task, context, ctx).await;
+        if let Some(entry) = received {
+            let _ = self.runner.bus.ack(&agent, entry.sequence);
+        }
+        result
+    }
+
+    async fn delegate(
+        &self,
+        delegate: &DelegateTool,
+        subtask: &Subtask,
+        mut prompt: String,
+        context: String,
+        ctx: &ToolContext,
+    ) -> (bool, String) {
+        if let Some(role) = self.runner.team.roles.get(&subtask.role) {
+            if !role.handoff_to.is_empty() {
+                let _ = write!(
+                    prompt,
+                    "\n\nIf follow-up work belongs to another role, end your reply with one line \
+                     per item: `{HANDOFF_PREFIX}<role>: <task>`. Roles you can hand off to: {}.",
+                    role.handoff_to.join(", ")
+                );
+            }
+        }
+        let args = json!({ "agent": subtask.role, "prompt": prompt, "context": context });
+        match delegate.execute_with_context(args, ctx).await {
+            Ok(result) if result.success => (true, result.output),
+            Ok(result) => (
+                false,
+                result.error.unwrap_or_else(|| "subtask failed".to_string()),
+            ),
+            Err(error) => (false, error.to_string()),
+        }
+    }
+
+    fn allowed_handoffs(&self, from: &str, output: &str) -> Vec<(String, String)> {
+        let Some(role) = self.runner.team.roles.get(from) else {
+            return Vec::new();
+        };
+        parse_handoffs(output)
+            .into_iter()
+            .filter(|(to, _)| {
+                role.handoff_to.contains(to) && self.runner.team.roles.contains_key(to)
+            })
+            .collect()
+    }
+
+    fn stop_reason(&self, ctx: &ToolContext) -> Option<String> {
+        if ctx.is_cancelled() {
+            return Some("cancelled".into());
+        }
+        if Instant::now() >= self.deadline {
+            return Some("timeout".into());
+        }
+        self.budget_exhausted()
+    }
+
+    fn budget_exhausted(&self) -> Option<String> {
+        let budget = self.runner.team.budget_usd?;
+        let spent = self.spent_usd()?;
+        (spent >= budget).then(|| format!("budget exhausted (${spent:.4} of ${budget:.4})"))
+    }
+
+    fn spent_usd(&self) -> Option<f64> {
+        let report = self
+            .runner
+            .cost_tracker
+            .as_ref()?
+            .report(
+                crate::config::schema::CostDimension::Workflow,
+                Some(self.started_at),
+            )
+            .ok()?;
+        Some(
+            report
+                .rows
+                .iter()
+                .find(|row| row.key == self.workflow)
+                .map_or(0.0, |row| row.cost_usd),
+        )
+    }
+
+    fn skip(&mut self, subtask: &Subtask, reason: &str) -> SubtaskOutcome {
+        let output = format!("skipped: {reason}");
+        self.patch_task(subtask, "skipped", Some(&output));
+        SubtaskOutcome {
+            id: subtask.id.clone(),
+            role: subtask.role.clone(),
+            success: false,
+            output,
+        }
+    }
+
+    /// Send a subtask to its role. Returns the assignment message ID.
+    fn assign(&mut self, subtask: &Subtask) -> Option<String> {
+        let runner = self.runner;
+        let mut envelope = CoordinationEnvelope::new_direct(
+            runner.bus_agent("supervisor"),
+            runner.bus_agent(&subtask.role),
+            self.conversation_id(),
+            "team.assign",
+            CoordinationPayload::DelegateTask {
+                task_id: subtask.id.clone(),
+                summary: preview(&subtask.task),
+                metadata: json!({
+                    "role": subtask.role,
+                    "task": subtask.task,
+                    "depends_on": subtask.depends_on,
+                }),
+            },
+        );
+        envelope.correlation_id = Some(self.run_id.clone());
+        let message_id = envelope.id.clone();
+        self.patch_task(subtask, "running", None);
+        match runner.bus.publish(envelope) {
+            Ok(_) => Some(message_id),
+            Err(error) => {
+                tracing::warn!(
+                    "team '{}': failed to assign '{}': {error}",
+                    runner.name,
+                    subtask.id
+                );
+                None
+            }
+        }
+    }
+
+    /// Return a subtask result to the supervisor and record it.
+    fn report(&mut self, subtask: &Subtask, assignment: Option<&str>, success: bool, output: &str) {
+        let runner = self.runner;
+        let mut envelope = CoordinationEnvelope::new_direct(
+            runner.bus_agent(&subtask.role),
+            runner.bus_agent("supervisor"),
+            self.conversation_id(),
+            "team.result",
+            CoordinationPayload::TaskResult {
+                task_id: subtask.id.clone(),
+                success,
+                output: output.to_string(),
+            },
+        );
+        envelope.correlation_id = Some(self.run_id.clone());
+        envelope.causation_id = assignment.map(str::to_string);
+        if let Err(error) = runner.bus.publish(envelope) {
+            tracing::warn!(
+                "team '{}': failed to report '{}': {error}",
+                runner.name,
+                subtask.id
+            );
+        }
+        let phase = if success { "completed" } else { "failed" };
+        self.patch_task(subtask, phase, Some(output));
+        self.trace(
+            "team_subtask",
+            Some(success),
+            &subtask.id,
+            json!({ "role": subtask.role, "output": preview(output) }),
+        );
+    }
+
+    /// Consume the supervisor inbox and return the subtask results reported
+    /// for this run, by subtask ID.
+    fn collect_results(&self) -> HashMap<String, (bool, String)> {
+        let supervisor = self.runner.bus_agent("supervisor");
+        let Ok(received) = self.runner.bus.receive_for_agent(&supervisor, 0) else {
+            return HashMap::new();
+        };
+        let mut results = HashMap::new();
+        for entry in received {
+            let _ = self.runner.bus.ack(&supervisor, entry.sequence);
+            let envelope = entry.envelope;
+            if envelope.topic != "team.result"
+                || envelope.correlation_id.as_deref() != Some(self.run_id.as_str())
+            {
+                continue;
+            }
+            if let CoordinationPayload::TaskResult {
+                task_id,
+                success,
+                output,
+            } = envelope.payload
+            {
+                results.insert(task_id, (success, output));
+            }
+        }
+        results
+    }
+
+    fn patch_task(&mut self, subtask: &Subtask, phase: &str, output: Option<&str>) {
+        self.patch(
+            &format!("task/{}", subtask.id),
+            json!({
+                "phase": phase,
+                "role": subtask.role,
+                "depends_on": subtask.depends_on,
+                "output": output.map(preview),
+            }),
+        );
+    }
+
+    /// Write `team/<run_id>/<key>` on the blackboard.
+    fn patch(&mut self, key: &str, value: serde_json::Value) {
+        let key = format!("team/{}/{key}", self.run_id);
+        let expected_version = self.versions.get(&key).copied().unwrap_or(0);
+        let supervisor = self.runner.bus_agent("supervisor");
+        let mut envelope = CoordinationEnvelope::new_direct(
+            supervisor.clone(),
+            supervisor.clone(),
+            self.conversation_id(),
+            "team.state",
+            CoordinationPayload::ContextPatch {
+                key: key.clone(),
+                expected_version,
+                value,
+            },
+        );
+        envelope.correlation_id = Some(self.run_id.clone());
+        let message_id = envelope.id.clone();
+        match self.runner.bus.publish(envelope) {
+            Ok(_) => {
+                self.versions.insert(key, expected_version + 1);
+                // The patch is addressed to the supervisor itself and has
+                // already been applied; take it off the inbox.
+                let bus = &self.runner.bus;
+                if let Ok(Some(entry)) = bus.receive_message(&supervisor, &message_id) {
+                    let _ = bus.ack(&supervisor, entry.sequence);
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "team '{}': failed to write '{key}': {error}",
+                    self.runner.name
+                );
+            }
+        }
+    }
+
+    fn trace(&self, event: &str, success: Option<bool>, message: &str, payload: serde_json::Value) {
+        let supervisor = &self.runner.team.supervisor;
+        runtime_trace::record_event(
+            event,
+            None,
+            Some(&supervisor.provider),
+            Some(&supervisor.model),
+            Some(&self.run_id),
+            success,
+            Some(&preview(message)),
+            payload,
+        );
+    }
+}
+
+/// Parse the supervisor's plan. Accepts a bare JSON array or one wrapped in
+/// prose or a code fence; missing IDs are numbered, unknown dependencies
+/// dropped and the plan cut to `max_subtasks`.
+fn parse_plan(
+    reply: &str,
+    roles: &HashMap<String, crate::config::TeamRoleConfig>,
+    max_subtasks: usize,
+) -> Result<Vec<Subtask>> {
+    let (Some(start), Some(end)) = (reply.find('['), reply.rfind(']')) else {
+        bail!("Supervisor plan is not a JSON array: {}", preview(reply));
+    };
+    if end < start {
+        bail!("Supervisor plan is not a JSON array: {}", preview(reply));
+    }
+    let mut plan: Vec<Subtask> = serde_json::from_str(&reply[start..=end])
+        .with_context(|| format!("Supervisor plan is not valid JSON: {}", preview(reply)))?;
+    plan.truncate(max_subtasks.max(1));
+    if plan.is_empty() {
+        bail!("Supervisor returned an empty plan");
+    }
+
+    let mut ids = HashSet::new();
+    for (index, subtask) in plan.iter_mut().enumerate() {
+        subtask.id = subtask.id.trim().to_string();
+        if subtask.id.is_empty() {
+            subtask.id = format!("t{}", index + 1);
+        }
+        if !roles.contains_key(&subtask.role) {
+            bail!(
+                "Supervisor assigned subtask '{}' to unknown role '{}'",
+                subtask.id,
+                subtask.role
+            );
+        }
+        if !ids.insert(subtask.id.clone()) {
+            bail!("Supervisor plan repeats subtask id '{}'", subtask.id);
+        }
+    }
+    for subtask in &mut plan {
+        let own = subtask.id.clone();
+        subtask
+            .depends_on
+            .retain(|dep| *dep != own && ids.contains(dep));
+    }
+    Ok(plan)
+}
+
+/// Split `pending` into the subtasks whose dependencies have all finished
+/// and the rest.
+fn next_wave(pending: Vec<Subtask>, finished: &HashSet<&str>) -> (Vec<Subtask>, Vec<Subtask>) {
+    pending.into_iter().partition(|subtask| {
+        subtask
+            .depends_on
+            .iter()
+            .all(|dep| finished.contains(dep.as_str()))
+    })
+}
+
+/// Task text carried by a `team.assign` envelope.
+fn assigned_task(envelope: &CoordinationEnvelope) -> Option<String> {
+    match &envelope.payload {
+        CoordinationPayload::DelegateTask { metadata, .. } => metadata
+            .get("task")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// `HANDOFF <role>: <task>` lines in a role's reply.
+fn parse_handoffs(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (role, task) = line.trim().strip_prefix(HANDOFF_PREFIX)?.split_once(':')?;
+            let (role, task) = (role.trim(), task.trim());
+            (!role.is_empty() && !task.is_empty()).then(|| (role.to_string(), task.to_string()))
+        })
+        .collect()
+}
+
+fn dependency_context(goal: &str, subtask: &Subtask, outcomes: &[SubtaskOutcome]) -> String {
+    let mut context = format!("Team goal: {goal}");
+    for outcome in outcomes
+        .iter()
+        .filter(|outcome| subtask.depends_on.contains(&outcome.id))
+    {
+        let _ = write!(
+            context,
+            "\n\n[Result of '{}' by {}]\n{}",
+            outcome.id, outcome.role, outcome.output
+        );
+    }
+    context
+}
+
+fn merge_prompt(goal: &str, outcomes: &[SubtaskOutcome]) -> String {
+    format!(
+        "[Goal]\n{goal}\n\n[Subtask results]\n{}",
+        render_outcomes(outcomes)
+    )
+}
+
+fn render_outcomes(outcomes: &[SubtaskOutcome]) -> String {
+    outcomes
+        .iter()
+        .map(|outcome| {
+            let status = if outcome.success { "done" } else { "failed" };
+            format!(
+                "### {} ({}, {status})\n{}",
+                outcome.id, outcome.role, outcome.output
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn preview(text: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return "[empty]".to_string();
+    }
+    crate::util::truncate_with_ellipsis(text, PREVIEW_MAX_CHARS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TeamRoleConfig;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    /// OpenAI-compatible endpoint answering each chat request with the reply
+    /// of the first `(needle, reply)` pair whose needle occurs in the request.
+    /// Every reply reports 1000 input and 1000 output tokens ($0.018 at the
+    /// default price); requests mentioning `TASK-SLOW` are answered after 5s.
+    async fn llm(replies: Vec<(&'static str, String)>) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(move |request: &Request| {
+                let body = String::from_utf8_lossy(&request.body).to_string();
+                let reply = replies
+                    .iter()
+                    .find(|(needle, _)| body.contains(needle))
+                    .map_or("done", |(_, reply)| reply.as_str());
+                let delay = if body.contains("TASK-SLOW") {
+                    Duration::from_secs(5)
+                } else {
+                    Duration::ZERO
+                };
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({
+                        "choices": [{ "message": { "role": "assistant", "content": reply } }],
+                        "usage": { "prompt_tokens": 1000, "completion_tokens": 1000 }
+                    }))
+                    .set_delay(delay)
+            })
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Supervisor plan and merge replies, followed by role replies.
+    fn replies(plan: &str, roles: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+        let mut replies = vec![
+            ("Combine the results", "merged answer".to_string()),
+            ("Break the goal", plan.to_string()),
+        ];
+        replies.extend(
+            roles
+                .iter()
+                .map(|(needle, reply)| (*needle, reply.to_string())),
+        );
+        replies
+    }
+
+    /// Team with researcher, writer and broken roles; `extra` holds
+    /// top-level team keys.
+    fn team(server: &MockServer, extra: &str) -> TeamConfig {
+        let agent = format!(
+            "provider = \"custom:{}\"\nmodel = \"mock\"\napi_key = \"test-key\"",
+            server.uri()
+        );
+        toml::from_str(&format!(
+            "{extra}\n[supervisor]\n{agent}\n\
+             [roles.researcher]\n{agent}\nhandoff_to = [\"writer\"]\n\
+             [roles.writer]\n{agent}\n\
+             [roles.broken]\nprovider = \"totally-invalid-provider\"\nmodel = \"mock\"\n"
+        ))
+        .unwrap()
+    }
+
+    fn runner(name: &str, team: TeamConfig) -> TeamRunner {
+        TeamRunner::new(
+            name,
+            team,
+            None,
+            Arc::new(SecurityPolicy::default()),
+            ProviderRuntimeOptions::default(),
+        )
+    }
+
+    fn outcome<'a>(report: &'a TeamRunReport, id: &str) -> &'a SubtaskOutcome {
+        report
+            .outcomes
+            .iter()
+            .find(|outcome| outcome.id == id)
+            .unwrap_or_else(|| panic!("no outcome for '{id}'"))
+    }
+
+    async fn role_prompts(server: &MockServer) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .map(|request| String::from_utf8_lossy(&request.body).to_string())
+            .filter(|body| {
+                !body.contains("Break the goal") && !body.contains("Combine the results")
+            })
+            .collect()
+    }
+
+    fn roles(names: &[&str]) -> HashMap<String, TeamRoleConfig> {
+        names
+            .iter()
+            .map(|name| {
+                let role: TeamRoleConfig =
+                    toml::from_str("provider = \"ollama\"\nmodel = \"llama3\"").unwrap();
+                (name.to_string(), role)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plan_is_parsed_from_fenced_reply_and_cleaned_up() {
+        let reply = "Here is the plan:\n```json\n[\
+            {\"id\": \"a\", \"role\": \"researcher\", \"task\": \"find sources\"},\
+            {\"role\": \"writer\", \"task\": \"draft\", \"depends_on\": [\"a\", \"zzz\"]},\
+            {\"id\": \"c\", \"role\": \"writer\", \"task\": \"extra\"}\
+            ]\n```";
+        let plan = parse_plan(reply, &roles(&["researcher", "writer"]), 2).unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[1].id, "t2");
+        assert_eq!(plan[1].depends_on, vec!["a".to_string()]);
+
+        let error = parse_plan(
+            "[{\"id\": \"a\", \"role\": \"hacker\", \"task\": \"x\"}]",
+            &roles(&["writer"]),
+            4,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown role 'hacker'"));
+    }
+
+    #[test]
+    fn waves_follow_dependencies() {
+        let task = |id: &str, deps: &[&str]| Subtask {
+            id: id.into(),
+            role: "writer".into(),
+            task: id.into(),
+            depends_on: deps.iter().map(|dep| dep.to_string()).collect(),
+        };
+        let plan = vec![task("a", &[]), task("b", &["a"]), task("c", &[])];
+        let (wave, rest) = next_wave(plan, &HashSet::new());
+        assert_eq!(
+            wave.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+        let (wave, rest) = next_wave(rest, &HashSet::from(["a", "c"]));
+        assert_eq!(wave[0].id, "b");
+        assert!(rest.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_executes_waves_over_the_bus_and_merges() {
+        let plan = r#"[
+            {"id": "research", "role": "researcher", "task": "TASK-RESEARCH"},
+            {"id": "draft", "role": "writer", "task": "TASK-DRAFT", "depends_on": ["research"]},
+            {"id": "outline", "role": "writer", "task": "TASK-OUTLINE"}
+        ]"#;
+        let server = llm(replies(
+            plan,
+            &[
+                ("TASK-RESEARCH", "three sources found"),
+                ("TASK-DRAFT", "draft written"),
+                ("TASK-OUTLINE", "outline written"),
+            ],
+        ))
+        .await;
+        let bus = MessageBus::new();
+        let runner = runner("docs", team(&server, "")).with_coordination_bus(bus.clone());
+
+        let report = runner
+            .run("write the docs", &ToolContext::default())
+            .await
+            .unwrap();
+
+        assert!(report.stopped.is_none());
+        assert_eq!(report.answer, "merged answer");
+        assert_eq!(report.outcomes.len(), 3);
+        assert!(report.outcomes.iter().all(|outcome| outcome.success));
+        // `draft` waits for `research`; `outline` runs in the first wave.
+        assert_eq!(report.outcomes[2].id, "draft");
+        assert!(outcome(&report, "draft").output.contains("draft written"));
+        let prompts = role_prompts(&server).await;
+        let draft = prompts
+            .iter()
+            .find(|body| body.contains("TASK-DRAFT"))
+            .unwrap();
+        assert!(draft.contains("three sources found"));
+
+        // Assignments and results were all consumed and acknowledged.
+        for agent in ["docs.supervisor", "docs.researcher", "docs.writer"] {
+            assert_eq!(bus.pending_for_agent(agent).unwrap(), 0, "{agent}");
+            assert_eq!(bus.in_flight_for_agent(agent).unwrap(), 0, "{agent}");
+        }
+        let task = bus
+            .context_entry(&format!("team/{}/task/draft", report.run_id))
+            .unwrap();
+        assert_eq!(task.value["phase"], json!("completed"));
+    }
+
+    #[tokio::test]
+    async fn run_skips_subtasks_whose_dependency_failed() {
+        let plan = r#"[
+            {"id": "fetch", "role": "broken", "task": "TASK-FETCH"},
+            {"id": "summarize", "role": "writer", "task": "TASK-SUMMARIZE", "depends_on": ["fetch"]},
+            {"id": "intro", "role": "writer", "task": "TASK-INTRO"}
+        ]"#;
+        let server = llm(replies(plan, &[("TASK-INTRO", "intro written")])).await;
+
+        let report = runner("docs", team(&server, ""))
+            .run("write the docs", &ToolContext::default())
+            .await
+            .unwrap();
+
+        assert!(!outcome(&report, "fetch").success);
+        let summarize = outcome(&report, "summarize");
+        assert!(!summarize.success);
+        assert_eq!(summarize.output, "skipped: dependency 'fetch' failed");
+        assert!(outcome(&report, "intro").success);
+        assert_eq!(report.answer, "merged answer");
+        assert!(!role_prompts(&server)
+            .await
+            .iter()
+            .any(|body| body.contains("TASK-SUMMARIZE")));
+    }
+
+    #[tokio::test]
+    async fn run_caps_handoffs() {
+        let plan = r#"[{"id": "research", "role": "researcher", "task": "TASK-RESEARCH"}]"#;
+        let server = llm(replies(
+            plan,
+            &[
+                (
+                    "TASK-RESEARCH",
+                    "found it\nHANDOFF writer: TASK-FOLLOW-1\nHANDOFF writer: TASK-FOLLOW-2\n\
+                     HANDOFF broken: TASK-NOT-ALLOWED",
+                ),
+                ("TASK-FOLLOW", "followed up"),
+            ],
+        ))
+        .await;
+
+        let report = runner("docs", team(&server, "max_handoffs = 1"))
+            .run("write the docs", &ToolContext::default())
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = report.outcomes.iter().map(|o| o.id.as_str()).collect();
+        assert_eq!(ids, ["research", "research.handoff1"]);
+        let handoff = outcome(&report, "research.handoff1");
+        assert_eq!(handoff.role, "writer");
+        assert!(handoff.success);
+        // The research reply itself plus the one handoff under the cap.
+        assert_eq!(role_prompts(&server).await.len(), 2);
+    }
+
+    fn tracker(workspace: &tempfile::TempDir) -> Arc<CostTracker> {
+        let config = crate::config::schema::CostConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        Arc::new(CostTracker::new(config, workspace.path()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn run_records_supervisor_and_role_spend_under_the_workflow() {
+        let plan = r#"[{"id": "research", "role": "researcher", "task": "TASK-RESEARCH"}]"#;
+        let server = llm(replies(plan, &[])).await;
+        let workspace = tempfile::TempDir::new().unwrap();
+        let tracker = tracker(&workspace);
+
+        let report = runner("docs", team(&server, ""))
+            .with_cost_tracker(tracker.clone(), HashMap::new())
+            .run("write the docs", &ToolContext::default())
+            .await
+            .unwrap();
+
+        // Plan, role turn and merge.
+        let spent = report.spent_usd.unwrap();
+        assert!((spent - 3.0 * 0.018).abs() < 1e-9, "{spent}");
+        let by_workflow = tracker
+            .report(crate::config::schema::CostDimension::Workflow, None)
+            .unwrap();
+        assert_eq!(by_workflow.rows.len(), 1);
+        assert_eq!(
+            by_workflow.rows[0].key,
+            format!("team:docs:{}", report.run_id)
+        );
+        assert_eq!(by_workflow.rows[0].request_count, 3);
+    }
+
+    #[tokio::test]
+    async fn run_stops_when_a_role_turn_exhausts_the_budget() {
+        let plan = r#"[
+            {"id": "fast", "role": "writer", "task": "TASK-FAST"},
+            {"id": "slow", "role": "researcher", "task": "TASK-SLOW"},
+            {"id": "later", "role": "writer", "task": "TASK-LATER", "depends_on": ["fast"]}
+        ]"#;
+        let server = llm(replies(plan, &[])).await;
+        let workspace = tempfile::TempDir::new().unwrap();
+
+        let started = Instant::now();
+        let report = runner("docs", team(&server, "budget_usd = 0.03"))
+            .with_cost_tracker(tracker(&workspace), HashMap::new())
+            .run("write the docs", &ToolContext::default())
+            .await
+            .unwrap();
+
+        // The plan and the fast role turn together cost $0.036.
+        let stopped = report.stopped.as_deref().unwrap();
+        assert!(stopped.starts_with("budget exhausted"), "{stopped}");
+        assert!(report.spent_usd.unwrap() >= 0.03);
+        assert!(outcome(&report, "fast").success);
+        // The slow turn was cancelled instead of waited for.
+        assert!(!outcome(&report, "slow").success);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(report.outcomes.iter().all(|outcome| outcome.id != "later"));
+        assert!(!role_prompts(&server)
+            .await
+            .iter()
+            .any(|body| body.contains("TASK-LATER")));
+        assert!(!server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .any(|request| String::from_utf8_lossy(&request.body).contains("Combine the results")));
+    }
+
+    #[test]
+    fn handoff_lines_are_extracted() {
+        let output = "Draft done.\nHANDOFF reviewer: check the citations\nHANDOFF : nothing\n  HANDOFF editor:tighten intro";
+        assert_eq!(
+            parse_handoffs(output),
+            vec![
+                ("reviewer".to_string(), "check the citations".to_string()),
+                ("editor".to_string(), "tighten intro".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Process-wide registry of in-flight agent turns.
+//!
+//! Every running tool-call loop registers its [`CancellationToken`] here so
+//! that out-of-band controls can stop it: the emergency-stop manager cancels
+//! every active turn on `kill-all`, and the channel `/stop` command or the
+//! WebSocket `cancel` frame cancel the turns registered under one scope key.
+//!
+//! Registration is RAII-based — dropping the returned [`ActiveTurnGuard`]
+//! removes the entry, so a finished turn can never be cancelled by a later
+//! command aimed at the same conversation.
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+use tokio_util::sync::CancellationToken;
+
+struct ActiveTurn {
+    id: u64,
+    scope: String,
+    token: CancellationToken,
+}
+
+static ACTIVE_TURNS: LazyLock<Mutex<Vec<ActiveTurn>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static NEXT_TURN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Keeps a turn registered until dropped.
+#[must_use = "the turn is unregistered as soon as the guard is dropped"]
+pub struct ActiveTurnGuard {
+    id: u64,
+}
+
+impl Drop for ActiveTurnGuard {
+    fn drop(&mut self) {
+        ACTIVE_TURNS.lock().retain(|turn| turn.id != self.id);
+    }
+}
+
+/// Register `token` under `scope`. An empty scope is reachable only through
+/// [`cancel_all`].
+pub fn register(scope: impl Into<String>, token: CancellationToken) -> ActiveTurnGuard {
+    let id = NEXT_TURN_ID.fetch_add(1, Ordering::Relaxed);
+    ACTIVE_TURNS.lock().push(ActiveTurn {
+        id,
+        scope: scope.into(),
+        token,
+    });
+    ActiveTurnGuard { id }
+}
+
+/// Cancel every not-yet-cancelled turn registered under `scope`.
+/// Returns `true` when at least one turn was stopped.
+pub fn cancel(scope: &str) -> bool {
+    if scope.is_empty() {
+        return false;
+    }
+    let mut stopped = false;
+    for turn in ACTIVE_TURNS.lock().iter() {
+        if turn.scope == scope && !turn.token.is_cancelled() {
+            turn.token.cancel();
+            stopped = true;
+        }
+    }
+    stopped
+}
+
+/// Cancel every registered turn. Returns the number of turns stopped.
+pub fn cancel_all() -> usize {
+    let mut stopped = 0;
+    for turn in ACTIVE_TURNS.lock().iter() {
+        if !turn.token.is_cancelled() {
+            turn.token.cancel();
+            stopped += 1;
+        }
+    }
+    stopped
+}
+
+/// Whether a not-yet-cancelled turn is registered under `scope`.
+pub fn is_active(scope: &str) -> bool {
+    ACTIVE_TURNS
+        .lock()
+        .iter()
+        .any(|turn| turn.scope == scope && !turn.token.is_cancelled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_targets_only_matching_scope() {
+        let a = CancellationToken::new();
+        let b = CancellationToken::new();
+        let _ga = register("turn-control-test:a", a.clone());
+        let _gb = register("turn-control-test:b", b.clone());
+
+        assert!(cancel("turn-control-test:a"));
+        assert!(a.is_cancelled());
+        assert!(!b.is_cancelled());
+        assert!(!cancel("turn-control-test:a"));
+    }
+
+    #[test]
+    fn dropped_guard_unregisters_turn() {
+        let token = CancellationToken::new();
+        let guard = register("turn-control-test:drop", token.clone());
+        assert!(is_active("turn-control-test:drop"));
+        drop(guard);
+        assert!(!is_active("turn-control-test:drop"));
+        assert!(!cancel("turn-control-test:drop"));
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn empty_scope_is_not_cancellable_by_name() {
+        let token = CancellationToken::new();
+        let _guard = register("", token.clone());
+        assert!(!cancel(""));
+        assert!(!token.is_cancelled());
+    }
+}
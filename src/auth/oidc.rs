@@ -0,0 +1,887 @@
+//! OpenID Connect relying party for gateway and dashboard single sign-on.
+//!
+//! Implements the authorization code flow with PKCE against any issuer that
+//! publishes discovery metadata. ID tokens are verified against the issuer's
+//! JWKS (RS256 and ES256), and the configured groups claim is mapped onto a
+//! [`crate::security::RoleRegistry`] role. The gateway turns a verified
+//! identity into a regular [`super::store::AuthStore`] session, so the
+//! dashboard, `/ws/chat` and `/api/*` accept SSO users without OIDC-specific
+//! checks.
+
+use super::oauth_common::{generate_pkce_state, random_base64url, url_encode};
+use crate::config::schema::{OidcConfig, OidcRoleMapping};
+use crate::security::pairing::constant_time_eq;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How long an authorization request may wait for its callback.
+const PENDING_LOGIN_TTL: Duration = Duration::from_secs(600);
+
+/// Cap on outstanding authorization requests; the oldest are dropped first.
+const MAX_PENDING_LOGINS: usize = 1024;
+
+/// Minimum interval between JWKS refetches triggered by an unknown key ID.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Subset of the discovery document the relying party needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default)]
+    alg: Option<String>,
+    #[serde(default, rename = "use")]
+    key_use: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+    #[serde(default)]
+    crv: Option<String>,
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+struct CachedJwks {
+    keys: Vec<Jwk>,
+    fetched_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    #[serde(default)]
+    id_token: Option<String>,
+}
+
+struct PendingLogin {
+    code_verifier: String,
+    nonce: String,
+    return_to: String,
+    created_at: Instant,
+}
+
+/// Identity asserted by a verified ID token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcIdentity {
+    pub issuer: String,
+    pub subject: String,
+    /// Preferred local username (from `username_claim`, then `email`, then `sub`).
+    pub username: String,
+    pub email: Option<String>,
+    pub groups: Vec<String>,
+}
+
+/// A started login: the identity provider URL to send the browser to and the
+/// `state` the browser must present again on the callback.
+#[derive(Debug, Clone)]
+pub struct OidcLoginStart {
+    pub url: String,
+    pub state: String,
+}
+
+/// A completed login: who signed in and where the dashboard should go next.
+#[derive(Debug, Clone)]
+pub struct OidcLogin {
+    pub identity: OidcIdentity,
+    pub return_to: String,
+}
+
+/// OIDC relying party bound to one issuer and client registration.
+pub struct OidcProvider {
+    config: OidcConfig,
+    redirect_url: String,
+    http: reqwest::Client,
+    metadata: tokio::sync::OnceCell<ProviderMetadata>,
+    jwks: tokio::sync::RwLock<Option<CachedJwks>>,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+impl OidcProvider {
+    /// Create a relying party. `redirect_url` must match the redirect URI
+    /// registered with the identity provider.
+    pub fn new(config: OidcConfig, redirect_url: String) -> Result<Self> {
+        let issuer = config.issuer.trim();
+        if issuer.is_empty() {
+            bail!("auth.oidc.issuer is required");
+        }
+        if config.client_id.trim().is_empty() {
+            bail!("auth.oidc.client_id is required");
+        }
+        let parsed = reqwest::Url::parse(issuer).context("auth.oidc.issuer is not a valid URL")?;
+        let loopback = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if parsed.scheme() != "https" && !(parsed.scheme() == "http" && loopback) {
+            bail!("auth.oidc.issuer must use https (plain http is only allowed for localhost)");
+        }
+
+        Ok(Self {
+            config,
+            redirect_url,
+            http: crate::config::build_runtime_proxy_client_with_timeouts("auth.oidc", 15, 10),
+            metadata: tokio::sync::OnceCell::new(),
+            jwks: tokio::sync::RwLock::new(None),
+            pending: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn redirect_url(&self) -> &str {
+        &self.redirect_url
+    }
+
+    /// Discovery metadata, fetched once and cached for the process lifetime.
+    pub async fn metadata(&self) -> Result<&ProviderMetadata> {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.config.issuer.trim().trim_end_matches('/')
+                );
+                let metadata: ProviderMetadata = self
+                    .http
+                    .get(&url)
+                    .send()
+                    .await
+                    .with_context(|| format!("Failed to fetch OIDC discovery document {url}"))?
+                    .error_for_status()?
+                    .json()
+                    .await
+                    .context("Invalid OIDC discovery document")?;
+                if !same_issuer(&metadata.issuer, &self.config.issuer) {
+                    bail!(
+                        "Discovery issuer '{}' does not match configured issuer '{}'",
+                        metadata.issuer,
+                        self.config.issuer
+                    );
+                }
+                Ok(metadata)
+            })
+            .await
+    }
+
+    /// How long a started login stays redeemable.
+    pub fn login_ttl(&self) -> Duration {
+        PENDING_LOGIN_TTL
+    }
+
+    /// Start a login. The caller redirects the browser to the returned URL
+    /// and binds the returned `state` to it (the gateway uses an HttpOnly
+    /// cookie); `return_to` is handed back by [`Self::complete_login`].
+    pub async fn begin_login(&self, return_to: &str) -> Result<OidcLoginStart> {
+        let metadata = self.metadata().await?;
+        let pkce = generate_pkce_state();
+        let nonce = random_base64url(24);
+
+        {
+            let mut pending = self.pending.lock();
+            pending.retain(|_, login| login.created_at.elapsed() < PENDING_LOGIN_TTL);
+            if pending.len() >= MAX_PENDING_LOGINS {
+                if let Some(oldest) = pending
+                    .iter()
+                    .min_by_key(|(_, login)| login.created_at)
+                    .map(|(state, _)| state.clone())
+                {
+                    pending.remove(&oldest);
+                }
+            }
+            pending.insert(
+                pkce.state.clone(),
+                PendingLogin {
+                    code_verifier: pkce.code_verifier,
+                    nonce: nonce.clone(),
+                    return_to: return_to.to_string(),
+                    created_at: Instant::now(),
+                },
+            );
+        }
+
+        let separator = if metadata.authorization_endpoint.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let url = format!(
+            "{}{separator}response_type=code&client_id={}&redirect_uri={}&scope={}&state={}&nonce={}&code_challenge={}&code_challenge_method=S256",
+            metadata.authorization_endpoint,
+            url_encode(&self.config.client_id),
+            url_encode(&self.redirect_url),
+            url_encode(&self.config.scopes.join(" ")),
+            url_encode(&pkce.state),
+            url_encode(&nonce),
+            url_encode(&pkce.code_challenge),
+        );
+        Ok(OidcLoginStart {
+            url,
+            state: pkce.state,
+        })
+    }
+
+    /// Finish a login from the callback's `code` and `state`: redeem the
+    /// code and verify the returned ID token. `browser_state` is the state
+    /// bound to the browser by [`Self::begin_login`]; a callback from any
+    /// other browser is refused, so a login started elsewhere cannot be
+    /// planted in this one.
+    pub async fn complete_login(
+        &self,
+        code: &str,
+        state: &str,
+        browser_state: Option<&str>,
+    ) -> Result<OidcLogin> {
+        if !browser_state.is_some_and(|bound| constant_time_eq(bound, state)) {
+            bail!("Login state is not bound to this browser");
+        }
+        let pending = self
+            .pending
+            .lock()
+            .remove(state)
+            .filter(|login| login.created_at.elapsed() < PENDING_LOGIN_TTL)
+            .ok_or_else(|| anyhow!("Unknown or expired login state"))?;
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.redirect_url.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", pending.code_verifier.as_str()),
+        ];
+        if let Some(secret) = self.config.client_secret.as_deref() {
+            form.push(("client_secret", secret));
+        }
+
+        let response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .context("Failed to contact the OIDC token endpoint")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("OIDC token exchange failed ({status}): {body}");
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .context("Invalid OIDC token response")?;
+        let id_token = token
+            .id_token
+            .ok_or_else(|| anyhow!("Token response did not include an id_token"))?;
+
+        let claims = self.verify_id_token(&id_token, &pending.nonce).await?;
+        Ok(OidcLogin {
+            identity: self.identity_from_claims(&claims)?,
+            return_to: pending.return_to,
+        })
+    }
+
+    /// Verify an ID token's signature, issuer, audience, lifetime and nonce.
+    /// Returns its claims.
+    pub async fn verify_id_token(&self, token: &str, nonce: &str) -> Result<Map<String, Value>> {
+        let mut parts = token.split('.');
+        let (Some(header_b64), Some(claims_b64), Some(signature_b64), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            bail!("ID token is not a compact JWS");
+        };
+
+        let header: JwtHeader =
+            serde_json::from_slice(&decode_b64(header_b64)?).context("Invalid ID token header")?;
+        let jwk = self.signing_key(&header).await?;
+        let signing_input = &token[..header_b64.len() + 1 + claims_b64.len()];
+        verify_signature(
+            &header.alg,
+            &jwk,
+            signing_input.as_bytes(),
+            &decode_b64(signature_b64)?,
+        )?;
+
+        let claims: Map<String, Value> =
+            serde_json::from_slice(&decode_b64(claims_b64)?).context("Invalid ID token claims")?;
+        let metadata = self.metadata().await?;
+        validate_claims(
+            &claims,
+            &metadata.issuer,
+            &self.config.client_id,
+            nonce,
+            epoch_secs(),
+            self.config.clock_skew_secs,
+        )?;
+        Ok(claims)
+    }
+
+    /// Role for a user in `groups`: the first matching mapping, otherwise
+    /// `default_role`. `None` means the user may not sign in.
+    pub fn resolve_role(&self, groups: &[String]) -> Option<String> {
+        map_groups_to_role(
+            &self.config.role_mappings,
+            self.config.default_role.as_deref(),
+            groups,
+        )
+    }
+
+    fn identity_from_claims(&self, claims: &Map<String, Value>) -> Result<OidcIdentity> {
+        let claim_str = |name: &str| {
+            claims
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let subject = claim_str("sub").ok_or_else(|| anyhow!("ID token has no subject"))?;
+        let issuer = claim_str("iss").unwrap_or_else(|| self.config.issuer.clone());
+        let email = claim_str("email");
+        let username = claim_str(&self.config.username_claim)
+            .or_else(|| email.clone())
+            .unwrap_or_else(|| subject.clone());
+        let groups = match lookup_claim(claims, &self.config.groups_claim) {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            Some(Value::String(group)) => group
+                .split([',', ' '])
+                .filter(|g| !g.is_empty())
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(OidcIdentity {
+            issuer,
+            subject,
+            username,
+            email,
+            groups,
+        })
+    }
+
+    /// JWKS entry for the token's `kid`, refetching the key set once when
+    /// the key is unknown (the issuer may have rotated keys).
+    async fn signing_key(&self, header: &JwtHeader) -> Result<Jwk> {
+        {
+            let cached = self.jwks.read().await;
+            if let Some(jwks) = cached.as_ref() {
+                if let Some(jwk) = select_key(&jwks.keys, header) {
+                    return Ok(jwk.clone());
+                }
+                if jwks.fetched_at.elapsed() < JWKS_REFRESH_INTERVAL {
+                    bail!("No JWKS key matches the ID token");
+                }
+            }
+        }
+
+        let metadata = self.metadata().await?;
+        let set: JwkSet = self
+            .http
+            .get(&metadata.jwks_uri)
+            .send()
+            .await
+            .context("Failed to fetch OIDC JWKS")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid OIDC JWKS")?;
+        let jwk = select_key(&set.keys, header).cloned();
+        *self.jwks.write().await = Some(CachedJwks {
+            keys: set.keys,
+            fetched_at: Instant::now(),
+        });
+        jwk.ok_or_else(|| anyhow!("No JWKS key matches the ID token"))
+    }
+}
+
+/// Normalize a post-login redirect target to a same-origin path.
+pub fn sanitize_return_to(return_to: Option<&str>) -> String {
+    match return_to.map(str::trim) {
+        Some(path)
+            if path.starts_with('/')
+                && !path.starts_with("//")
+                && !path.contains('\\')
+                && !path.chars().any(char::is_control) =>
+        {
+            path.to_string()
+        }
+        _ => "/".to_string(),
+    }
+}
+
+fn map_groups_to_role(
+    mappings: &[OidcRoleMapping],
+    default_role: Option<&str>,
+    groups: &[String],
+) -> Option<String> {
+    mappings
+        .iter()
+        .find(|mapping| groups.iter().any(|group| group == &mapping.group))
+        .map(|mapping| mapping.role.clone())
+        .or_else(|| default_role.map(str::to_string))
+        .map(|role| role.trim().to_ascii_lowercase())
+        .filter(|role| !role.is_empty())
+}
+
+/// Claim by name, where dots walk into nested objects
+/// (e.g. Keycloak's `realm_access.roles`).
+fn lookup_claim<'a>(claims: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(path) {
+        return Some(value);
+    }
+    let mut segments = path.split('.');
+    let mut value = claims.get(segments.next()?)?;
+    for segment in segments {
+        value = value.get(segment)?;
+    }
+    Some(value)
+}
+
+fn select_key<'a>(keys: &'a [Jwk], header: &JwtHeader) -> Option<&'a Jwk> {
+    let kty = match header.alg.as_str() {
+        "RS256" => "RSA",
+        "ES256" => "EC",
+        _ => return None,
+    };
+    let mut candidates = keys.iter().filter(|key| {
+        key.kty == kty
+            && key.key_use.as_deref().is_none_or(|u| u == "sig")
+            && key.alg.as_deref().is_none_or(|alg| alg == header.alg)
+    });
+    match header.kid.as_deref() {
+        Some(kid) => candidates.find(|key| key.kid.as_deref() == Some(kid)),
+        None => {
+            let first = candidates.next()?;
+            candidates.next().is_none().then_some(first)
+        }
+    }
+}
+
+fn verify_signature(alg: &str, jwk: &Jwk, message: &[u8], signature: &[u8]) -> Result<()> {
+    use ring::signature::{
+        RsaPublicKeyComponents, UnparsedPublicKey, ECDSA_P256_SHA256_FIXED,
+        RSA_PKCS1_2048_8192_SHA256,
+    };
+
+    let field = |value: &Option<String>, name: &str| {
+        value
+            .as_deref()
+            .ok_or_else(|| anyhow!("JWKS key is missing '{name}'"))
+            .and_then(decode_b64)
+    };
+
+    let verified = match alg {
+        "RS256" => {
+            let n = field(&jwk.n, "n")?;
+            let e = field(&jwk.e, "e")?;
+            RsaPublicKeyComponents { n: &n, e: &e }.verify(
+                &RSA_PKCS1_2048_8192_SHA256,
+                message,
+                signature,
+            )
+        }
+        "ES256" => {
+            if jwk.crv.as_deref() != Some("P-256") {
+                bail!("ES256 key must use the P-256 curve");
+            }
+            let mut point = vec![0x04];
+            point.extend(field(&jwk.x, "x")?);
+            point.extend(field(&jwk.y, "y")?);
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point).verify(message, signature)
+        }
+        other => bail!("Unsupported ID token algorithm '{other}'"),
+    };
+    verified.map_err(|_| anyhow!("ID token signature is invalid"))
+}
+
+fn validate_claims(
+    claims: &Map<String, Value>,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+    now: u64,
+    skew: u64,
+) -> Result<()> {
+    let iss = claims
+        .get("iss")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !same_issuer(iss, issuer) {
+        bail!("ID token issuer '{iss}' is not '{issuer}'");
+    }
+
+    let audiences: Vec<&str> = match claims.get("aud") {
+        Some(Value::String(aud)) => vec![aud.as_str()],
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !audiences.contains(&client_id) {
+        bail!("ID token was not issued for this client");
+    }
+    if audiences.len() > 1 {
+        let azp = claims.get("azp").and_then(Value::as_str);
+        if azp != Some(client_id) {
+            bail!("ID token authorized party does not match this client");
+        }
+    }
+
+    let time_claim = |name: &str| claims.get(name).and_then(Value::as_u64);
+    let exp = time_claim("exp").ok_or_else(|| anyhow!("ID token has no expiry"))?;
+    if exp + skew <= now {
+        bail!("ID token has expired");
+    }
+    if time_claim("nbf").is_some_and(|nbf| nbf > now + skew) {
+        bail!("ID token is not valid yet");
+    }
+    if time_claim("iat").is_some_and(|iat| iat > now + skew) {
+        bail!("ID token was issued in the future");
+    }
+
+    if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+        bail!("ID token nonce does not match the login request");
+    }
+    Ok(())
+}
+
+fn same_issuer(a: &str, b: &str) -> bool {
+    a.trim().trim_end_matches('/') == b.trim().trim_end_matches('/')
+}
+
+fn decode_b64(input: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(input.trim_end_matches('='))
+        .context("Invalid base64url in ID token")
+}
+
+fn epoch_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Mock identity provider for tests here and in the gateway.
+#[cfg(test)]
+pub(crate) mod test_idp {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    pub(crate) fn b64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Minimal identity provider: discovery, JWKS and an ES256 signing key.
+    pub(crate) struct MockIdp {
+        pub(crate) server: MockServer,
+        key: EcdsaKeyPair,
+    }
+
+    impl MockIdp {
+        pub(crate) async fn start() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+            let key =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+            let point = key.public_key().as_ref();
+
+            let server = MockServer::start().await;
+            let issuer = server.uri();
+            Mock::given(method("GET"))
+                .and(path("/.well-known/openid-configuration"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "issuer": issuer,
+                    "authorization_endpoint": format!("{issuer}/authorize"),
+                    "token_endpoint": format!("{issuer}/token"),
+                    "jwks_uri": format!("{issuer}/jwks"),
+                })))
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/jwks"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "keys": [{
+                        "kty": "EC", "crv": "P-256", "kid": "k1", "use": "sig", "alg": "ES256",
+                        "x": b64(&point[1..33]), "y": b64(&point[33..]),
+                    }]
+                })))
+                .mount(&server)
+                .await;
+
+            Self { server, key }
+        }
+
+        pub(crate) fn sign(&self, claims: &Value) -> String {
+            let header = b64(br#"{"alg":"ES256","kid":"k1","typ":"JWT"}"#);
+            let body = b64(claims.to_string().as_bytes());
+            let input = format!("{header}.{body}");
+            let signature = self
+                .key
+                .sign(&SystemRandom::new(), input.as_bytes())
+                .unwrap();
+            format!("{input}.{}", b64(signature.as_ref()))
+        }
+
+        pub(crate) fn claims(&self, nonce: &str) -> Value {
+            self.claims_with_groups(nonce, &["eng", "platform-admins"])
+        }
+
+        /// ID token claims for `ada`, a member of `groups`.
+        pub(crate) fn claims_with_groups(&self, nonce: &str, groups: &[&str]) -> Value {
+            let now = epoch_secs();
+            serde_json::json!({
+                "iss": self.server.uri(),
+                "aud": "zeroclaw",
+                "sub": "u-123",
+                "preferred_username": "ada",
+                "email": "ada@example.com",
+                "groups": groups,
+                "nonce": nonce,
+                "iat": now,
+                "exp": now + 300,
+            })
+        }
+
+        /// Answer the next token request with an ID token for `claims`.
+        pub(crate) async fn issue_once(&self, claims: &Value) {
+            Mock::given(method("POST"))
+                .and(path("/token"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "at",
+                    "token_type": "Bearer",
+                    "id_token": self.sign(claims),
+                })))
+                .up_to_n_times(1)
+                .mount(&self.server)
+                .await;
+        }
+
+        pub(crate) fn config(&self) -> OidcConfig {
+            OidcConfig {
+                enabled: true,
+                issuer: self.server.uri(),
+                client_id: "zeroclaw".into(),
+                role_mappings: vec![
+                    OidcRoleMapping {
+                        group: "platform-admins".into(),
+                        role: "admin".into(),
+                    },
+                    OidcRoleMapping {
+                        group: "eng".into(),
+                        role: "operator".into(),
+                    },
+                ],
+                ..OidcConfig::default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_idp::{b64, MockIdp};
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    fn query_param(url: &str, name: &str) -> String {
+        let url = reqwest::Url::parse(url).unwrap();
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn authorization_code_flow_with_pkce_against_mock_idp() {
+        let idp = MockIdp::start().await;
+        let provider = OidcProvider::new(
+            idp.config(),
+            "http://localhost:3000/api/auth/oidc/callback".into(),
+        )
+        .unwrap();
+
+        let start = provider.begin_login("/cost").await.unwrap();
+        let url = start.url;
+        assert!(url.starts_with(&format!("{}/authorize?", idp.server.uri())));
+        assert_eq!(query_param(&url, "code_challenge_method"), "S256");
+        let state = query_param(&url, "state");
+        assert_eq!(start.state, state);
+        let nonce = query_param(&url, "nonce");
+
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "at",
+                "token_type": "Bearer",
+                "id_token": idp.sign(&idp.claims(&nonce)),
+            })))
+            .expect(1)
+            .mount(&idp.server)
+            .await;
+
+        // A callback from a browser that did not start the login is refused
+        // without consuming the state.
+        assert!(provider
+            .complete_login("auth-code", &state, None)
+            .await
+            .is_err());
+        assert!(provider
+            .complete_login("auth-code", &state, Some("another-login"))
+            .await
+            .is_err());
+
+        let login = provider
+            .complete_login("auth-code", &state, Some(&state))
+            .await
+            .unwrap();
+        assert_eq!(login.return_to, "/cost");
+        assert_eq!(login.identity.subject, "u-123");
+        assert_eq!(login.identity.username, "ada");
+        assert_eq!(login.identity.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(
+            provider.resolve_role(&login.identity.groups).as_deref(),
+            Some("admin")
+        );
+
+        let requests = idp.server.received_requests().await.unwrap();
+        let token_request = requests.iter().find(|r| r.url.path() == "/token").unwrap();
+        let body = String::from_utf8_lossy(&token_request.body);
+        assert!(body.contains("grant_type=authorization_code"));
+        assert!(body.contains("code_verifier="));
+
+        // The state is single-use.
+        assert!(provider
+            .complete_login("auth-code", &state, Some(&state))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_tampered_expired_and_misaddressed_tokens() {
+        let idp = MockIdp::start().await;
+        let provider = OidcProvider::new(idp.config(), "http://localhost/cb".into()).unwrap();
+
+        let good = idp.sign(&idp.claims("n1"));
+        assert!(provider.verify_id_token(&good, "n1").await.is_ok());
+        assert!(provider
+            .verify_id_token(&good, "other-nonce")
+            .await
+            .is_err());
+
+        let mut forged = idp.claims("n1");
+        forged["sub"] = "someone-else".into();
+        let parts: Vec<&str> = good.split('.').collect();
+        let tampered = format!(
+            "{}.{}.{}",
+            parts[0],
+            b64(forged.to_string().as_bytes()),
+            parts[2]
+        );
+        assert!(provider.verify_id_token(&tampered, "n1").await.is_err());
+
+        let mut expired = idp.claims("n1");
+        expired["exp"] = (epoch_secs() - 3600).into();
+        assert!(provider
+            .verify_id_token(&idp.sign(&expired), "n1")
+            .await
+            .is_err());
+
+        let mut other_client = idp.claims("n1");
+        other_client["aud"] = "someone-else".into();
+        assert!(provider
+            .verify_id_token(&idp.sign(&other_client), "n1")
+            .await
+            .is_err());
+
+        let unsigned = format!(
+            "{}.{}.",
+            b64(br#"{"alg":"none"}"#),
+            b64(idp.claims("n1").to_string().as_bytes())
+        );
+        assert!(provider.verify_id_token(&unsigned, "n1").await.is_err());
+    }
+
+    #[test]
+    fn group_mapping_prefers_first_match_then_default() {
+        let mappings = vec![
+            OidcRoleMapping {
+                group: "admins".into(),
+                role: "Admin".into(),
+            },
+            OidcRoleMapping {
+                group: "staff".into(),
+                role: "operator".into(),
+            },
+        ];
+        let groups = |names: &[&str]| names.iter().map(|g| (*g).to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            map_groups_to_role(&mappings, Some("viewer"), &groups(&["staff", "admins"])),
+            Some("admin".into())
+        );
+        assert_eq!(
+            map_groups_to_role(&mappings, Some("viewer"), &groups(&["contractors"])),
+            Some("viewer".into())
+        );
+        assert_eq!(map_groups_to_role(&mappings, None, &groups(&[])), None);
+    }
+
+    #[test]
+    fn nested_group_claims_and_return_paths() {
+        let claims: Map<String, Value> =
+            serde_json::from_str(r#"{"realm_access":{"roles":["eng"]}}"#).unwrap();
+        assert_eq!(
+            lookup_claim(&claims, "realm_access.roles"),
+            Some(&serde_json::json!(["eng"]))
+        );
+
+        assert_eq!(sanitize_return_to(Some("/agent?x=1")), "/agent?x=1");
+        assert_eq!(sanitize_return_to(Some("//evil.example")), "/");
+        assert_eq!(sanitize_return_to(Some("https://evil.example")), "/");
+        assert_eq!(sanitize_return_to(None), "/");
+    }
+
+    #[test]
+    fn requires_https_issuer_except_on_loopback() {
+        let config = |issuer: &str| OidcConfig {
+            issuer: issuer.into(),
+            client_id: "c".into(),
+            ..OidcConfig::default()
+        };
+        assert!(OidcProvider::new(config("http://idp.example.com"), String::new()).is_err());
+        assert!(OidcProvider::new(config("http://127.0.0.1:8080"), String::new()).is_ok());
+        assert!(OidcProvider::new(config("https://idp.example.com"), String::new()).is_ok());
+    }
+}
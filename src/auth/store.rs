@@ -1,9 +1,10 @@
 //! SQLite-backed user authentication store.
 //!
 //! Tables:
-//! - `users`: username, password_hash, salt, created_at
+//! - `users`: username, password_hash, salt, created_at, email, role
 //! - `sessions`: token_hash, user_id, device_id, expires_at
 //! - `devices`: device_id, user_id, device_name, last_seen
+//! - `oidc_identities`: issuer, subject, user_id (SSO users)
 
 use anyhow::{bail, Result};
 use parking_lot::Mutex;
@@ -153,6 +154,25 @@ impl AuthStore {
             let _ = conn.execute_batch("ALTER TABLE users ADD COLUMN email TEXT;");
         }
 
+        // Migration: add role column to users table if missing
+        let has_role: bool = conn.prepare("SELECT role FROM users LIMIT 0").is_ok();
+        if !has_role {
+            let _ = conn.execute_batch("ALTER TABLE users ADD COLUMN role TEXT;");
+        }
+
+        // ── SSO identities (issuer + subject → local user) ──
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS oidc_identities (
+                issuer TEXT NOT NULL,
+                subject TEXT NOT NULL,
+                user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at INTEGER NOT NULL,
+                last_login INTEGER NOT NULL,
+                PRIMARY KEY (issuer, subject)
+            );
+            CREATE INDEX IF NOT EXISTS idx_oidc_user ON oidc_identities(user_id);",
+        )?;
+
         // ── Usage analytics table ──
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS usage_stats (
@@ -676,6 +696,122 @@ impl AuthStore {
         Ok(email)
     }
 
+    // ── Roles ───────────────────────────────────────────────────────
+
+    /// Set the `[security.roles]` role of a user.
+    pub fn set_user_role(&self, user_id: &str, role: &str) -> Result<()> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE users SET role = ?1 WHERE id = ?2",
+            rusqlite::params![role.trim().to_ascii_lowercase(), user_id],
+        )?;
+        if updated == 0 {
+            bail!("User not found");
+        }
+        Ok(())
+    }
+
+    /// Get the role of a user. Returns None if no role has been assigned.
+    pub fn get_user_role(&self, user_id: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock();
+        let role: Option<String> = conn
+            .query_row(
+                "SELECT role FROM users WHERE id = ?1",
+                rusqlite::params![user_id],
+                |row| row.get(0),
+            )
+            .map_err(|_| anyhow::anyhow!("User not found"))?;
+        Ok(role)
+    }
+
+    // ── Single Sign-On ──────────────────────────────────────────────
+
+    /// Find or create the local user for an SSO identity (just-in-time
+    /// provisioning). On every login the user's email and role are refreshed
+    /// from the identity provider, so group changes take effect at next
+    /// sign-in. Provisioned users have no password and cannot use
+    /// `/api/auth/login` until one is set.
+    pub fn provision_oidc_user(
+        &self,
+        issuer: &str,
+        subject: &str,
+        preferred_username: &str,
+        email: Option<&str>,
+        role: &str,
+    ) -> Result<User> {
+        let now = epoch_secs() as i64;
+        let role = role.trim().to_ascii_lowercase();
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+
+        let existing: Option<String> = match tx.query_row(
+            "SELECT user_id FROM oidc_identities WHERE issuer = ?1 AND subject = ?2",
+            rusqlite::params![issuer, subject],
+            |row| row.get(0),
+        ) {
+            Ok(user_id) => Some(user_id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let user_id = if let Some(user_id) = existing {
+            tx.execute(
+                "UPDATE users SET role = ?1, email = COALESCE(?2, email) WHERE id = ?3",
+                rusqlite::params![role, email, user_id],
+            )?;
+            tx.execute(
+                "UPDATE oidc_identities SET last_login = ?1 WHERE issuer = ?2 AND subject = ?3",
+                rusqlite::params![now, issuer, subject],
+            )?;
+            user_id
+        } else {
+            let base = sso_username(preferred_username);
+            let mut username = base.clone();
+            let mut suffix = 1;
+            while tx
+                .query_row(
+                    "SELECT 1 FROM users WHERE username = ?1 COLLATE NOCASE",
+                    rusqlite::params![username],
+                    |_| Ok(()),
+                )
+                .is_ok()
+            {
+                suffix += 1;
+                username = format!("{base}-{suffix}");
+            }
+
+            // An empty hash never matches `hash_password`, so password login
+            // stays closed until the user sets one.
+            let user_id = uuid::Uuid::new_v4().to_string();
+            tx.execute(
+                "INSERT INTO users (id, username, password_hash, salt, created_at, email, role)
+                 VALUES (?1, ?2, '', '', ?3, ?4, ?5)",
+                rusqlite::params![user_id, username, now, email, role],
+            )?;
+            tx.execute(
+                "INSERT INTO oidc_identities (issuer, subject, user_id, created_at, last_login)
+                 VALUES (?1, ?2, ?3, ?4, ?4)",
+                rusqlite::params![issuer, subject, user_id, now],
+            )?;
+            user_id
+        };
+
+        let user = tx.query_row(
+            "SELECT id, username, email, created_at FROM users WHERE id = ?1",
+            rusqlite::params![user_id],
+            |row| {
+                Ok(User {
+                    id: row.get(0)?,
+                    username: row.get(1)?,
+                    email: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            },
+        )?;
+        tx.commit()?;
+        Ok(user)
+    }
+
     // ── Channel Linking ─────────────────────────────────────────────
 
     /// Ensure the channel_links table exists (safe to call multiple times).
@@ -952,6 +1088,17 @@ impl AuthStore {
             .map_err(|e| e.into())
     }
 
+    /// Delete a user account. Sessions, devices, SSO identities, channel
+    /// links and usage stats go with it (`ON DELETE CASCADE`).
+    pub fn delete_user(&self, user_id: &str) -> Result<bool> {
+        let conn = self.conn.lock();
+        let deleted = conn.execute(
+            "DELETE FROM users WHERE id = ?1",
+            rusqlite::params![user_id],
+        )?;
+        Ok(deleted > 0)
+    }
+
     /// Record a usage event for analytics.
     pub fn record_usage(&self, user_id: &str, category: &str, chars: i64) -> Result<()> {
         let conn = self.conn.lock();
@@ -1139,6 +1286,22 @@ fn hash_token(token: &str) -> String {
     hex::encode(h.finalize())
 }
 
+/// Local username for an SSO user: the IdP's preferred name reduced to
+/// characters that are safe in URLs and logs, at most 64 characters.
+fn sso_username(preferred: &str) -> String {
+    let cleaned: String = preferred
+        .trim()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '-' | '@'))
+        .take(56)
+        .collect();
+    if cleaned.is_empty() {
+        "sso_user".to_string()
+    } else {
+        cleaned
+    }
+}
+
 /// Constant-time byte comparison to prevent timing attacks.
 fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
@@ -1433,6 +1596,43 @@ mod tests {
         assert_ne!(h1, h2);
     }
 
+    #[test]
+    fn oidc_users_are_provisioned_once_and_refreshed_on_login() {
+        let (_tmp, store) = test_store();
+        store.register("ada", "localpassword1").unwrap();
+
+        let first = store
+            .provision_oidc_user("https://idp", "sub-1", "ada", Some("ada@corp"), "viewer")
+            .unwrap();
+        // "ada" is taken by a local account, so the SSO user gets a suffix.
+        assert_eq!(first.username, "ada-2");
+        assert_eq!(first.email.as_deref(), Some("ada@corp"));
+        assert_eq!(
+            store.get_user_role(&first.id).unwrap().as_deref(),
+            Some("viewer")
+        );
+
+        let again = store
+            .provision_oidc_user("https://idp", "sub-1", "renamed", None, "Admin")
+            .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.email.as_deref(), Some("ada@corp"));
+        assert_eq!(
+            store.get_user_role(&first.id).unwrap().as_deref(),
+            Some("admin")
+        );
+
+        // Same subject at another issuer is a different person.
+        let other = store
+            .provision_oidc_user("https://other-idp", "sub-1", "ada", None, "viewer")
+            .unwrap();
+        assert_ne!(other.id, first.id);
+
+        // SSO users have no usable password.
+        assert!(store.authenticate("ada-2", "").is_err());
+        assert_eq!(store.user_count().unwrap(), 3);
+    }
+
     #[test]
     fn constant_time_eq_works() {
         assert!(constant_time_eq(b"hello", b"hello"));
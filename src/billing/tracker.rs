@@ -1,12 +1,16 @@
 //! Cost tracking engine for API usage billing.
 //!
-//! Records per-request costs in a local SQLite ledger and enforces
-//! configurable spending limits.
+//! A billing view over the unified cost ledger (`state/costs.jsonl`, see
+//! [`crate::cost`]): entries recorded here land in the same ledger as agent
+//! turns, and the totals and spending limits below are computed from it.
 
-use chrono::Datelike;
-use rusqlite::{params, Connection};
+use crate::config::schema::CostConfig;
+use crate::cost::attribution;
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
 /// Default daily spending limit in USD (0 = no limit).
 const DEFAULT_DAILY_LIMIT_USD: f64 = 0.0;
@@ -58,51 +62,31 @@ pub struct ProviderUsage {
     pub request_count: i64,
 }
 
-/// Cost tracker with SQLite persistence and spending limits.
+/// Cost tracker with spending limits, backed by the unified cost ledger.
 pub struct CostTracker {
-    /// Persistent SQLite connection (avoids per-operation open overhead).
-    conn: Option<Connection>,
+    /// Shared ledger (`None` when billing is disabled).
+    ledger: Option<Arc<crate::cost::CostTracker>>,
     daily_limit_usd: f64,
     monthly_limit_usd: f64,
-    enabled: bool,
 }
 
 impl CostTracker {
     /// Create a new cost tracker for the given workspace.
     pub fn new(workspace_dir: &Path, enabled: bool) -> anyhow::Result<Self> {
-        let conn = if enabled {
-            let db_path = workspace_dir.join("billing.db");
-            let conn = Connection::open(&db_path)?;
-            conn.execute_batch(
-                "PRAGMA journal_mode = WAL;
-                 PRAGMA synchronous = NORMAL;
-                 PRAGMA busy_timeout = 5000;",
-            )?;
-            conn.execute_batch(
-                "CREATE TABLE IF NOT EXISTS cost_ledger (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    provider TEXT NOT NULL,
-                    model TEXT NOT NULL,
-                    input_tokens INTEGER NOT NULL DEFAULT 0,
-                    output_tokens INTEGER NOT NULL DEFAULT 0,
-                    cost_usd REAL NOT NULL DEFAULT 0.0,
-                    channel TEXT,
-                    timestamp INTEGER NOT NULL,
-                    created_at TEXT DEFAULT (datetime('now'))
-                );
-                CREATE INDEX IF NOT EXISTS idx_cost_timestamp ON cost_ledger(timestamp);
-                CREATE INDEX IF NOT EXISTS idx_cost_provider ON cost_ledger(provider);",
-            )?;
-            Some(conn)
+        let ledger = if enabled {
+            let config = CostConfig {
+                enabled: true,
+                ..CostConfig::default()
+            };
+            Some(crate::cost::shared_tracker(&config, workspace_dir)?)
         } else {
             None
         };
 
         Ok(Self {
-            conn,
+            ledger,
             daily_limit_usd: DEFAULT_DAILY_LIMIT_USD,
             monthly_limit_usd: DEFAULT_MONTHLY_LIMIT_USD,
-            enabled,
         })
     }
 
@@ -116,27 +100,30 @@ impl CostTracker {
         self.monthly_limit_usd = limit_usd;
     }
 
-    /// Record a cost entry in the ledger.
+    /// Record a cost entry in the ledger, attributed to the current scope
+    /// and to the entry's channel.
     pub fn record(&self, entry: &CostEntry) -> anyhow::Result<()> {
-        let Some(ref conn) = self.conn else {
+        let Some(ref ledger) = self.ledger else {
             return Ok(());
         };
 
-        conn.execute(
-            "INSERT INTO cost_ledger (provider, model, input_tokens, output_tokens, cost_usd, channel, timestamp)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![
-                entry.provider,
-                entry.model,
-                entry.input_tokens,
-                entry.output_tokens,
-                entry.cost_usd,
-                entry.channel,
-                entry.timestamp,
-            ],
-        )?;
-
-        Ok(())
+        let input_tokens = u64::try_from(entry.input_tokens).unwrap_or(0);
+        let output_tokens = u64::try_from(entry.output_tokens).unwrap_or(0);
+        let mut usage = crate::cost::TokenUsage::new(
+            format!("{}/{}", entry.provider, entry.model),
+            input_tokens,
+            output_tokens,
+            0.0,
+            0.0,
+        );
+        usage.cost_usd = entry.cost_usd;
+        usage.timestamp = DateTime::from_timestamp(entry.timestamp, 0).unwrap_or_else(Utc::now);
+
+        let mut attribution = attribution::current();
+        if entry.channel.is_some() {
+            attribution.channel = entry.channel.clone();
+        }
+        ledger.record_usage_with_attribution(usage, attribution)
     }
 
     /// Check if daily spending limit has been exceeded.
@@ -144,19 +131,7 @@ impl CostTracker {
         if self.daily_limit_usd <= 0.0 {
             return Ok(false);
         }
-
-        let Some(ref conn) = self.conn else {
-            return Ok(false);
-        };
-
-        let today_start = today_start_epoch();
-        let total: f64 = conn.query_row(
-            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM cost_ledger WHERE timestamp >= ?1",
-            params![today_start],
-            |row| row.get(0),
-        )?;
-
-        Ok(total >= self.daily_limit_usd)
+        Ok(self.today_total()? >= self.daily_limit_usd)
     }
 
     /// Check if monthly spending limit has been exceeded.
@@ -164,102 +139,77 @@ impl CostTracker {
         if self.monthly_limit_usd <= 0.0 {
             return Ok(false);
         }
-
-        let Some(ref conn) = self.conn else {
-            return Ok(false);
-        };
-
-        let month_start = month_start_epoch();
-        let total: f64 = conn.query_row(
-            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM cost_ledger WHERE timestamp >= ?1",
-            params![month_start],
-            |row| row.get(0),
-        )?;
-
-        Ok(total >= self.monthly_limit_usd)
+        Ok(self.month_total()? >= self.monthly_limit_usd)
     }
 
     /// Get today's total spending.
     pub fn today_total(&self) -> anyhow::Result<f64> {
-        let Some(ref conn) = self.conn else {
+        let Some(ref ledger) = self.ledger else {
             return Ok(0.0);
         };
-
-        let today_start = today_start_epoch();
-        let total: f64 = conn.query_row(
-            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM cost_ledger WHERE timestamp >= ?1",
-            params![today_start],
-            |row| row.get(0),
-        )?;
-
-        Ok(total)
+        ledger.get_daily_cost(Utc::now().date_naive())
     }
 
     /// Get this month's total spending.
     pub fn month_total(&self) -> anyhow::Result<f64> {
-        let Some(ref conn) = self.conn else {
+        let Some(ref ledger) = self.ledger else {
             return Ok(0.0);
         };
-
-        let month_start = month_start_epoch();
-        let total: f64 = conn.query_row(
-            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM cost_ledger WHERE timestamp >= ?1",
-            params![month_start],
-            |row| row.get(0),
-        )?;
-
-        Ok(total)
+        let now = Utc::now();
+        ledger.get_monthly_cost(now.year(), now.month())
     }
 
     /// Get a usage summary for a given time range.
     pub fn summary(&self, from_timestamp: i64, to_timestamp: i64) -> anyhow::Result<UsageSummary> {
-        let Some(ref conn) = self.conn else {
-            return Ok(UsageSummary {
-                total_cost_usd: 0.0,
-                total_input_tokens: 0,
-                total_output_tokens: 0,
-                request_count: 0,
-                by_provider: Vec::new(),
-            });
+        let mut summary = UsageSummary {
+            total_cost_usd: 0.0,
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            request_count: 0,
+            by_provider: Vec::new(),
+        };
+        let Some(ref ledger) = self.ledger else {
+            return Ok(summary);
         };
 
-        // Overall totals
-        let (total_cost, total_input, total_output, count): (f64, i64, i64, i64) = conn.query_row(
-            "SELECT COALESCE(SUM(cost_usd), 0.0), COALESCE(SUM(input_tokens), 0),
-                        COALESCE(SUM(output_tokens), 0), COUNT(*)
-                 FROM cost_ledger WHERE timestamp >= ?1 AND timestamp <= ?2",
-            params![from_timestamp, to_timestamp],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
-        )?;
-
-        // Per-provider breakdown
-        let mut stmt = conn.prepare_cached(
-            "SELECT provider, COALESCE(SUM(cost_usd), 0.0), COALESCE(SUM(input_tokens), 0),
-                    COALESCE(SUM(output_tokens), 0), COUNT(*)
-             FROM cost_ledger WHERE timestamp >= ?1 AND timestamp <= ?2
-             GROUP BY provider ORDER BY SUM(cost_usd) DESC",
-        )?;
-
-        let by_provider: Vec<ProviderUsage> = stmt
-            .query_map(params![from_timestamp, to_timestamp], |row| {
-                Ok(ProviderUsage {
-                    provider: row.get(0)?,
-                    cost_usd: row.get(1)?,
-                    input_tokens: row.get(2)?,
-                    output_tokens: row.get(3)?,
-                    request_count: row.get(4)?,
-                })
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(UsageSummary {
-            total_cost_usd: total_cost,
-            total_input_tokens: total_input,
-            total_output_tokens: total_output,
-            request_count: count,
-            by_provider,
-        })
+        let mut by_provider: HashMap<String, ProviderUsage> = HashMap::new();
+        ledger.for_each_record(|record| {
+            let timestamp = record.usage.timestamp.timestamp();
+            if timestamp < from_timestamp || timestamp > to_timestamp {
+                return;
+            }
+            let input_tokens = i64::try_from(record.usage.input_tokens).unwrap_or(i64::MAX);
+            let output_tokens = i64::try_from(record.usage.output_tokens).unwrap_or(i64::MAX);
+            summary.total_cost_usd += record.usage.cost_usd;
+            summary.total_input_tokens += input_tokens;
+            summary.total_output_tokens += output_tokens;
+            summary.request_count += 1;
+
+            let provider = record
+                .usage
+                .model
+                .split_once('/')
+                .map_or(record.usage.model.as_str(), |(provider, _)| provider);
+            let row = by_provider
+                .entry(provider.to_string())
+                .or_insert_with(|| ProviderUsage {
+                    provider: provider.to_string(),
+                    cost_usd: 0.0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    request_count: 0,
+                });
+            row.cost_usd += record.usage.cost_usd;
+            row.input_tokens += input_tokens;
+            row.output_tokens += output_tokens;
+            row.request_count += 1;
+        })?;
+
+        summary.by_provider = by_provider.into_values().collect();
+        summary
+            .by_provider
+            .sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+        Ok(summary)
     }
 
     /// Estimate cost for a given model based on token counts.
@@ -273,25 +223,6 @@ impl CostTracker {
     }
 }
 
-/// Get the Unix epoch timestamp for the start of today (UTC).
-fn today_start_epoch() -> i64 {
-    let now = chrono::Utc::now();
-    let today = now.date_naive().and_hms_opt(0, 0, 0).unwrap();
-    today.and_utc().timestamp()
-}
-
-/// Get the Unix epoch timestamp for the start of this month (UTC).
-fn month_start_epoch() -> i64 {
-    let now = chrono::Utc::now();
-    let month_start = now
-        .date_naive()
-        .with_day(1)
-        .unwrap()
-        .and_hms_opt(0, 0, 0)
-        .unwrap();
-    month_start.and_utc().timestamp()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,6 +329,37 @@ mod tests {
         assert_eq!(summary.by_provider.len(), 2);
     }
 
+    #[test]
+    fn entries_land_in_the_unified_ledger() {
+        let (tmp, tracker) = make_tracker();
+        tracker
+            .record(&CostEntry {
+                provider: "openai".into(),
+                model: "gpt-4o".into(),
+                input_tokens: 10,
+                output_tokens: 5,
+                cost_usd: 0.25,
+                channel: Some("telegram".into()),
+                timestamp: now_epoch(),
+            })
+            .unwrap();
+
+        let ledger = crate::cost::CostTracker::new(
+            CostConfig {
+                enabled: true,
+                ..CostConfig::default()
+            },
+            tmp.path(),
+        )
+        .unwrap();
+        let report = ledger
+            .report(crate::config::schema::CostDimension::Channel, None)
+            .unwrap();
+        assert_eq!(report.request_count, 1);
+        assert_eq!(report.rows[0].key, "telegram");
+        assert!((report.total_cost_usd - 0.25).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn estimate_cost_known_models() {
         let cost = CostTracker::estimate_cost("gpt-4o", 1_000_000, 1_000_000);
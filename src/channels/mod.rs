@@ -95,6 +95,7 @@ use crate::agent::session::{resolve_session_id, shared_session_manager, Session,
 use crate::approval::{ApprovalManager, ApprovalResponse, PendingApprovalError};
 use crate::config::{Config, NonCliNaturalLanguageApprovalMode, ProgressMode};
 use crate::identity;
+use crate::inbox::{HumanDecision, HumanTaskKind};
 use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
 use crate::providers::{self, ChatMessage, Provider};
@@ -231,6 +232,7 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    CancelTurn,
     RequestAllToolsOnce,
     RequestToolApproval(String),
     ConfirmToolApproval(String),
@@ -240,10 +242,16 @@ enum ChannelRuntimeCommand {
     ApproveTool(String),
     UnapproveTool(String),
     ListApprovals,
+    ListInbox,
+    ResolveInboxTask(HumanDecision, String),
 }
 
 const APPROVAL_ALL_TOOLS_ONCE_TOKEN: &str = "__all_tools_once__";
 
+/// How long a cancelled turn may keep running to stop cooperatively before
+/// the worker drops it.
+const CANCELLED_TURN_GRACE: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Default, Deserialize)]
 struct ModelCacheState {
     entries: Vec<ModelCacheEntry>,
@@ -866,6 +874,7 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
     match base_command.as_str() {
         // History reset commands are safe for all channels.
         "/new" | "/clear" => Some(ChannelRuntimeCommand::NewSession),
+        "/stop" | "/cancel" => Some(ChannelRuntimeCommand::CancelTurn),
         "/approve-all-once" => Some(ChannelRuntimeCommand::RequestAllToolsOnce),
         "/approve-request" => Some(ChannelRuntimeCommand::RequestToolApproval(tail)),
         "/approve-confirm" => Some(ChannelRuntimeCommand::ConfirmToolApproval(tail)),
@@ -875,6 +884,19 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
         "/approve" => Some(ChannelRuntimeCommand::ApproveTool(tail)),
         "/unapprove" => Some(ChannelRuntimeCommand::UnapproveTool(tail)),
         "/approvals" => Some(ChannelRuntimeCommand::ListApprovals),
+        "/inbox" => Some(ChannelRuntimeCommand::ListInbox),
+        "/inbox-approve" => Some(ChannelRuntimeCommand::ResolveInboxTask(
+            HumanDecision::Approve,
+            tail,
+        )),
+        "/inbox-reject" => Some(ChannelRuntimeCommand::ResolveInboxTask(
+            HumanDecision::Reject,
+            tail,
+        )),
+        "/inbox-answer" => Some(ChannelRuntimeCommand::ResolveInboxTask(
+            HumanDecision::Answer,
+            tail,
+        )),
         // Provider/model switching remains limited to channels with session routing.
         "/models" if supports_runtime_model_switch(channel_name) => {
             if let Some(provider) = args.first() {
@@ -954,6 +976,10 @@ fn parse_natural_language_runtime_command(content: &str) -> Option<ChannelRuntim
     }
 
     let lower = trimmed.to_ascii_lowercase();
+    if matches!(lower.as_str(), "stop" | "cancel") || matches!(trimmed, "중지" | "멈춰" | "停止")
+    {
+        return Some(ChannelRuntimeCommand::CancelTurn);
+    }
     if matches!(
         lower.as_str(),
         "show pending approvals" | "list pending approvals" | "pending approvals"
@@ -2645,6 +2671,23 @@ async fn handle_runtime_command_if_needed(
             clear_sender_history(ctx, &sender_key);
             "Conversation history cleared. Starting fresh.".to_string()
         }
+        ChannelRuntimeCommand::CancelTurn => {
+            if crate::agent::turn_control::cancel(&interruption_scope_key(msg)) {
+                runtime_trace::record_event(
+                    "channel_turn_cancel_requested",
+                    Some(source_channel),
+                    None,
+                    None,
+                    None,
+                    Some(true),
+                    Some("sender requested cancellation of the in-flight turn"),
+                    serde_json::json!({ "sender": sender }),
+                );
+                "Stopping the current request.".to_string()
+            } else {
+                "Nothing is running right now.".to_string()
+            }
+        }
         ChannelRuntimeCommand::RequestAllToolsOnce => {
             let req = ctx.approval_manager.create_non_cli_pending_request(
                 APPROVAL_ALL_TOOLS_ONCE_TOKEN,
@@ -2951,6 +2994,92 @@ async fn handle_runtime_command_if_needed(
                 response
             }
         }
+        ChannelRuntimeCommand::ListInbox => match crate::inbox::global() {
+            None => "The human task inbox is disabled.".to_string(),
+            Some(inbox) => match inbox.list(false, 50) {
+                Ok(tasks) => {
+                    let tasks: Vec<_> = tasks
+                        .into_iter()
+                        .filter(|task| task.answerable_by(source_channel, sender, reply_target))
+                        .collect();
+                    if tasks.is_empty() {
+                        "No inbox tasks are waiting on you.".to_string()
+                    } else {
+                        let mut response = String::from("Inbox tasks waiting on you:\n");
+                        for task in tasks {
+                            let _ = writeln!(
+                                response,
+                                "- `{}` ({}): {}",
+                                task.id,
+                                task.kind.as_str(),
+                                task.title
+                            );
+                        }
+                        response.push_str(
+                            "Resolve with `/inbox-approve <id>`, `/inbox-reject <id>` or `/inbox-answer <id> <answer>`.",
+                        );
+                        response
+                    }
+                }
+                Err(e) => format!("Failed to read the inbox: {e}"),
+            },
+        },
+        ChannelRuntimeCommand::ResolveInboxTask(decision, raw_tail) => {
+            let (task_id, response) = raw_tail
+                .trim()
+                .split_once(char::is_whitespace)
+                .map_or((raw_tail.trim(), ""), |(id, rest)| (id, rest.trim()));
+            match crate::inbox::global() {
+                None => "The human task inbox is disabled.".to_string(),
+                Some(_) if task_id.is_empty() => {
+                    "Usage: `/inbox-approve <id> [comment]`, `/inbox-reject <id> [reason]` or `/inbox-answer <id> <answer>`".to_string()
+                }
+                Some(inbox) => match inbox.get(task_id) {
+                    Ok(Some(task)) if !task.answerable_by(source_channel, sender, reply_target) => {
+                        format!("Task `{task_id}` is assigned to someone else.")
+                    }
+                    Ok(Some(task))
+                        if task.kind == HumanTaskKind::ToolApproval
+                            && !ctx
+                                .approval_manager
+                                .is_non_cli_approval_actor_allowed(source_channel, sender) =>
+                    {
+                        format!(
+                            "Sender `{sender}` may not resolve tool approval `{}`.",
+                            task.id
+                        )
+                    }
+                    Ok(Some(_)) => match inbox.resolve(
+                        task_id,
+                        decision,
+                        Some(response),
+                        &format!("{source_channel}:{sender}"),
+                        source_channel,
+                    ) {
+                        Ok(task) => {
+                            runtime_trace::record_event(
+                                "inbox_task_resolved",
+                                Some(source_channel),
+                                None,
+                                None,
+                                None,
+                                Some(true),
+                                Some(task.title.as_str()),
+                                serde_json::json!({
+                                    "task_id": task.id,
+                                    "status": task.status.as_str(),
+                                    "sender": sender,
+                                }),
+                            );
+                            format!("Task `{}` {}.", task.id, task.status)
+                        }
+                        Err(e) => format!("{e}"),
+                    },
+                    Ok(None) => format!("Unknown inbox task `{task_id}`."),
+                    Err(e) => format!("Failed to read the inbox: {e}"),
+                },
+            }
+        }
         ChannelRuntimeCommand::ApproveTool(raw_tool_name) => {
             let tool_name = raw_tool_name.trim().to_string();
             if tool_name.is_empty() {
@@ -4115,32 +4244,48 @@ or tune thresholds in config.",
     } else {
         None
     };
+    let _active_turn = crate::agent::turn_control::register(
+        interruption_scope_key(&msg),
+        cancellation_token.clone(),
+    );
     let llm_result = tokio::select! {
-        () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
+        () = async {
+            cancellation_token.cancelled().await;
+            // Let the tool loop stop cooperatively so in-flight tools can wind
+            // down and report partial output; drop it if it overstays.
+            tokio::time::sleep(CANCELLED_TURN_GRACE).await;
+        } => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
             crate::agent::loop_::scope_cost_enforcement_context(
                 cost_enforcement_context,
-                run_tool_call_loop_with_non_cli_approval_context(
-                    active_provider.as_ref(),
-                    &mut history,
-                    ctx.tools_registry.as_ref(),
-                    ctx.observer.as_ref(),
-                    route.provider.as_str(),
-                    route.model.as_str(),
-                    runtime_defaults.temperature,
-                    true,
-                    Some(ctx.approval_manager.as_ref()),
-                    msg.channel.as_str(),
-                    non_cli_approval_context,
-                    &ctx.multimodal,
-                    ctx.max_tool_iterations,
-                    Some(cancellation_token.clone()),
-                    delta_tx,
-                    ctx.hooks.as_deref(),
-                    &excluded_tools_snapshot,
-                    progress_mode,
-                    ctx.safety_heartbeat.clone(),
+                crate::cost::attribution::scope(
+                    crate::cost::CostAttribution::turn(
+                        msg.channel.as_str(),
+                        msg.sender.as_str(),
+                        history_key.as_str(),
+                    ),
+                    run_tool_call_loop_with_non_cli_approval_context(
+                        active_provider.as_ref(),
+                        &mut history,
+                        ctx.tools_registry.as_ref(),
+                        ctx.observer.as_ref(),
+                        route.provider.as_str(),
+                        route.model.as_str(),
+                        runtime_defaults.temperature,
+                        true,
+                        Some(ctx.approval_manager.as_ref()),
+                        msg.channel.as_str(),
+                        non_cli_approval_context,
+                        &ctx.multimodal,
+                        ctx.max_tool_iterations,
+                        Some(cancellation_token.clone()),
+                        delta_tx,
+                        ctx.hooks.as_deref(),
+                        &excluded_tools_snapshot,
+                        progress_mode,
+                        ctx.safety_heartbeat.clone(),
+                    ),
                 ),
             ),
         ) => LlmExecutionResult::Completed(result),
@@ -4648,8 +4793,11 @@ async fn run_message_dispatch_loop(
         let task_sequence = Arc::clone(&task_sequence);
         workers.spawn(async move {
             let _permit = permit;
-            let interrupt_enabled =
-                worker_ctx.interrupt_on_new_message && msg.channel == "telegram";
+            // `/stop` must reach the running turn instead of replacing it.
+            let interrupt_enabled = worker_ctx.interrupt_on_new_message
+                && msg.channel == "telegram"
+                && parse_runtime_command(&msg.channel, &msg.content)
+                    != Some(ChannelRuntimeCommand::CancelTurn);
             let sender_scope_key = interruption_scope_key(&msg);
             let cancellation_token = CancellationToken::new();
             let completion = Arc::new(InFlightTaskCompletion::new());
@@ -4679,7 +4827,13 @@ async fn run_message_dispatch_loop(
                 }
             }
 
-            Box::pin(process_channel_message(worker_ctx, msg, cancellation_token)).await;
+            // Linked senders read and write their own tenant's storage.
+            let tenant = crate::tenancy::tenant_for_channel(&msg.channel, &msg.sender);
+            Box::pin(crate::tenancy::scope(
+                tenant,
+                process_channel_message(worker_ctx, msg, cancellation_token),
+            ))
+            .await;
 
             if interrupt_enabled {
                 let mut active = in_flight.lock().await;
@@ -6222,7 +6376,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
     // Ensure stale channel handles are never reused across restarts.
     clear_live_channels();
 
-    if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
+    if let Err(error) = crate::plugins::runtime::initialize_from_config(
+        &config.plugins,
+        &config.security,
+        config.autonomy.level,
+    ) {
         tracing::warn!("plugin registry initialization skipped: {error}");
     }
 
@@ -6272,8 +6430,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let observer: Arc<dyn Observer> = Arc::from(observability::create_observer_with_ledger(
+        &config.observability,
+        &config.cost,
+        &config.workspace_dir,
+    ));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -6288,6 +6449,8 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config.workspace_dir,
         config.api_key.as_deref(),
     )?);
+    crate::tenancy::init_from_config(&config);
+    let mem = crate::tenancy::wrap_memory(mem, &config)?;
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
             config.composio.api_key.as_deref(),
@@ -6608,8 +6771,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .unwrap_or_default();
     set_runtime_telegram_progress_mode(telegram_progress_mode);
 
-    let session_manager = shared_session_manager(&config.agent.session, &config.workspace_dir)?
-        .map(|mgr| mgr as Arc<dyn SessionManager + Send + Sync>);
+    let session_manager = crate::tenancy::wrap_session_manager(
+        shared_session_manager(&config.agent.session, &config.workspace_dir)?,
+        &config,
+    )
+    .map(|mgr| mgr as Arc<dyn SessionManager + Send + Sync>);
 
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
@@ -6794,6 +6960,17 @@ mod tests {
             parse_runtime_command("slack", "/approve-pending"),
             Some(ChannelRuntimeCommand::ListPendingApprovals)
         );
+        assert_eq!(
+            parse_runtime_command("slack", "/inbox"),
+            Some(ChannelRuntimeCommand::ListInbox)
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/inbox-answer hit-deadbeef use eu-west"),
+            Some(ChannelRuntimeCommand::ResolveInboxTask(
+                HumanDecision::Answer,
+                "hit-deadbeef use eu-west".to_string()
+            ))
+        );
         assert_eq!(
             parse_runtime_command("slack", "/approve shell"),
             Some(ChannelRuntimeCommand::ApproveTool("shell".to_string()))
@@ -6813,6 +6990,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_runtime_command_recognizes_cancel_turn() {
+        for input in ["/stop", "/cancel", "/stop@zeroclaw_bot", "stop", "Cancel", "중지"] {
+            assert_eq!(
+                parse_runtime_command("telegram", input),
+                Some(ChannelRuntimeCommand::CancelTurn),
+                "{input}"
+            );
+        }
+        assert_eq!(parse_runtime_command("telegram", "stop the build"), None);
+    }
+
     #[test]
     fn parse_runtime_command_supports_natural_language_approval_intents() {
         assert_eq!(
@@ -11555,6 +11744,7 @@ BTC is currently around $65,000 based on latest tool output."#
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
             always: false,
+            permissions: None,
         }];
 
         let prompt = build_system_prompt(ws.path(), "model", &[], &skills, None, None);
@@ -11591,6 +11781,7 @@ BTC is currently around $65,000 based on latest tool output."#
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
             always: false,
+            permissions: None,
         }];
 
         let prompt = build_system_prompt_with_mode(
@@ -11633,6 +11824,7 @@ BTC is currently around $65,000 based on latest tool output."#
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
             always: false,
+            permissions: None,
         }];
 
         let prompt = build_system_prompt(ws.path(), "model", &[], &skills, None, None);
@@ -1,7 +1,7 @@
 use anyhow::{bail, Context, Result};
 use reqwest::multipart::{Form, Part};
 
-use crate::config::TranscriptionConfig;
+use crate::config::{TranscriptionBackend, TranscriptionConfig};
 
 /// Maximum upload size accepted by the Groq Whisper API (25 MB).
 const MAX_AUDIO_BYTES: usize = 25 * 1024 * 1024;
@@ -122,6 +122,48 @@ pub async fn transcribe_audio(
     Ok(text)
 }
 
+/// Upper bound for audio handed to the local backend (100 MB). Local engines
+/// have no upload limit, but decoding holds the whole clip in memory.
+const MAX_LOCAL_AUDIO_BYTES: usize = 100 * 1024 * 1024;
+
+/// Transcribe audio received on `channel`, using the backend selected for it
+/// by `[transcription].backend` / `[transcription].channel_backends`.
+pub async fn transcribe_audio_for_channel(
+    audio_data: Vec<u8>,
+    file_name: &str,
+    config: &TranscriptionConfig,
+    channel: &str,
+) -> Result<String> {
+    match config.backend_for(channel) {
+        TranscriptionBackend::Cloud => {
+            let text = transcribe_audio(audio_data, file_name, config).await?;
+            // The JSON response carries no duration; estimate it from the
+            // transcript at a typical ~150 spoken words per minute.
+            let words = text.split_whitespace().count();
+            crate::cost::record_metered(crate::cost::CostKind::Stt, "cloud", words as f64 / 150.0);
+            Ok(text)
+        }
+        TranscriptionBackend::Local => {
+            if audio_data.len() > MAX_LOCAL_AUDIO_BYTES {
+                bail!(
+                    "Audio file too large ({} bytes, max {MAX_LOCAL_AUDIO_BYTES})",
+                    audio_data.len()
+                );
+            }
+            let transcript = crate::voice::local_stt::transcribe(
+                audio_data,
+                &normalize_audio_filename(file_name),
+                &config.local,
+                config.language.as_deref(),
+            )
+            .await?;
+            let seconds = transcript.segments.last().map_or(0.0, |s| s.end);
+            crate::cost::record_metered(crate::cost::CostKind::Stt, "local", seconds / 60.0);
+            Ok(transcript.text)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +292,40 @@ mod tests {
             "error should mention the rejected extension, got: {msg}"
         );
     }
+
+    #[test]
+    fn channel_backend_overrides_default() {
+        let mut config = TranscriptionConfig::default();
+        config
+            .channel_backends
+            .insert("telegram".into(), TranscriptionBackend::Local);
+        assert_eq!(config.backend_for("telegram"), TranscriptionBackend::Local);
+        assert_eq!(config.backend_for("discord"), TranscriptionBackend::Cloud);
+    }
+
+    #[tokio::test]
+    async fn local_channel_routes_to_local_server() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/inference"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"text":" offline hello "}"#),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = TranscriptionConfig::default();
+        config.backend = TranscriptionBackend::Local;
+        config.local.server_url = format!("{}/inference", server.uri());
+
+        // AAC cannot be decoded locally, so the original file is forwarded.
+        let text = transcribe_audio_for_channel(vec![0u8; 100], "clip.aac", &config, "telegram")
+            .await
+            .unwrap();
+        assert_eq!(text, "offline hello");
+    }
 }
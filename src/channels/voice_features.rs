@@ -226,7 +226,7 @@ pub async fn download_kakao_audio(
 
 /// Transcribe a voice message using the shared transcription service.
 ///
-/// Wraps [`super::transcription::transcribe_audio`] with duration/size
+/// Wraps [`super::transcription::transcribe_audio_for_channel`] with duration/size
 /// validation and structured logging.
 pub async fn transcribe_voice_message(
     audio_data: Vec<u8>,
@@ -241,7 +241,9 @@ pub async fn transcribe_voice_message(
         "Transcribing voice message"
     );
 
-    let text = super::transcription::transcribe_audio(audio_data, file_name, config).await?;
+    let text =
+        super::transcription::transcribe_audio_for_channel(audio_data, file_name, config, channel)
+            .await?;
 
     tracing::info!(channel, chars = text.len(), "Voice transcription completed");
 
@@ -618,10 +618,11 @@ impl Channel for WhatsAppWebChannel {
                                         // &AudioMessage which implements Downloadable.
                                         match _client.download(audio_msg.as_ref()).await {
                                             Ok(audio_bytes) => {
-                                                match super::transcription::transcribe_audio(
+                                                match super::transcription::transcribe_audio_for_channel(
                                                     audio_bytes,
                                                     file_name,
                                                     tc,
+                                                    "whatsapp",
                                                 )
                                                 .await
                                                 {
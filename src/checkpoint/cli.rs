@@ -0,0 +1,74 @@
+use super::store::{CheckpointStore, RestoreReport};
+use crate::config::Config;
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Result};
+use console::style;
+
+/// Handle `zeroclaw checkpoint <subcommand>` CLI commands.
+pub fn handle_command(command: crate::CheckpointCommands, config: &Config) -> Result<()> {
+    let store = CheckpointStore::for_workspace(&config.workspace_dir);
+    match command {
+        crate::CheckpointCommands::List { limit } => handle_list(&store, limit),
+        crate::CheckpointCommands::Restore { id } => {
+            print_report(&store.restore(&id, &restore_policy(config))?);
+            Ok(())
+        }
+    }
+}
+
+/// Handle `zeroclaw undo`: roll back the most recent checkpoint.
+pub fn handle_undo(config: &Config) -> Result<()> {
+    let store = CheckpointStore::for_workspace(&config.workspace_dir);
+    let Some(latest) = store.list()?.into_iter().next() else {
+        bail!("No checkpoints to undo.");
+    };
+    print_report(&store.restore(&latest.id, &restore_policy(config))?);
+    Ok(())
+}
+
+fn restore_policy(config: &Config) -> SecurityPolicy {
+    SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+}
+
+fn handle_list(store: &CheckpointStore, limit: usize) -> Result<()> {
+    let checkpoints = store.list()?;
+    if checkpoints.is_empty() {
+        println!("No checkpoints found.");
+        return Ok(());
+    }
+
+    println!("Checkpoints ({} total, newest first):\n", checkpoints.len());
+    for checkpoint in checkpoints.iter().take(limit) {
+        println!(
+            "- {} [{}] {} file(s), {}",
+            style(&checkpoint.id).white().bold(),
+            checkpoint.label,
+            checkpoint.files.len(),
+            checkpoint.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        );
+        for file in &checkpoint.files {
+            let note = if file.blob.is_none() {
+                " (created)"
+            } else {
+                ""
+            };
+            println!("    {}{note}", file.path.display());
+        }
+    }
+    Ok(())
+}
+
+fn print_report(report: &RestoreReport) {
+    println!(
+        "{} Rolled back {} checkpoint(s): {}",
+        style("✓").green().bold(),
+        report.checkpoints.len(),
+        report.checkpoints.join(", ")
+    );
+    for path in &report.restored {
+        println!("  restored {}", path.display());
+    }
+    for path in &report.removed {
+        println!("  removed  {}", path.display());
+    }
+}
@@ -0,0 +1,124 @@
+//! Per-turn checkpoints of workspace files modified by the agent.
+//!
+//! Before `file_write`, `file_edit` or `apply_patch` touch a file they call
+//! [`record_before_write`], which copies the file's current content into a
+//! content-addressed [`CheckpointStore`] under the workspace state dir. All
+//! files touched while one tool-call loop runs share a checkpoint, so a bad
+//! turn can be reverted with `zeroclaw undo`, `zeroclaw checkpoint restore`,
+//! or the WebSocket `checkpoint_restore` frame — without relying on git.
+
+pub mod cli;
+pub mod store;
+
+#[allow(unused_imports)]
+pub use store::{Checkpoint, CheckpointStore, FileSnapshot, RestoreReport, MAX_CHECKPOINTS};
+
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+tokio::task_local! {
+    static TURN_CHECKPOINT: Arc<TurnCheckpoint>;
+}
+
+/// Checkpoint shared by every file write of one agent turn.
+pub struct TurnCheckpoint {
+    id: String,
+    label: String,
+    recorded: Mutex<HashSet<(PathBuf, PathBuf)>>,
+}
+
+impl TurnCheckpoint {
+    pub fn new(label: impl Into<String>) -> Arc<Self> {
+        Arc::new(Self {
+            id: store::new_checkpoint_id(),
+            label: label.into(),
+            recorded: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// The checkpoint of the enclosing turn, so nested loops (delegated
+    /// sub-agents) add to their parent's checkpoint instead of starting one.
+    pub fn current_or_new(label: impl Into<String>) -> Arc<Self> {
+        TURN_CHECKPOINT
+            .try_with(Arc::clone)
+            .unwrap_or_else(|_| Self::new(label))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn record(&self, store: &CheckpointStore, path: &Path) -> anyhow::Result<()> {
+        let mut recorded = self.recorded.lock();
+        let key = (store.root().to_path_buf(), path.to_path_buf());
+        if recorded.contains(&key) {
+            return Ok(());
+        }
+        store.snapshot(&self.id, &self.label, path)?;
+        recorded.insert(key);
+        Ok(())
+    }
+}
+
+/// Run `future` with `checkpoint` as the active turn checkpoint.
+pub async fn scope<F: Future>(checkpoint: Arc<TurnCheckpoint>, future: F) -> F::Output {
+    TURN_CHECKPOINT.scope(checkpoint, future).await
+}
+
+/// Snapshot `path` into the workspace checkpoint store before it is modified.
+///
+/// Outside a turn scope the write gets a checkpoint of its own. Failures are
+/// logged and never block the write.
+pub fn record_before_write(workspace_dir: &Path, path: &Path) {
+    let store = CheckpointStore::for_workspace(workspace_dir);
+    let result = match TURN_CHECKPOINT.try_with(Arc::clone) {
+        Ok(checkpoint) => checkpoint.record(&store, path),
+        Err(_) => TurnCheckpoint::new("tool").record(&store, path),
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to checkpoint {} before write: {e:#}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_in_one_turn_share_a_checkpoint() {
+        let tmp = tempfile::tempdir().unwrap();
+        let a = tmp.path().join("a.txt");
+        let b = tmp.path().join("b.txt");
+        std::fs::write(&a, "a").unwrap();
+
+        let turn = TurnCheckpoint::new("cli");
+        scope(turn.clone(), async {
+            record_before_write(tmp.path(), &a);
+            record_before_write(tmp.path(), &b);
+            let nested = TurnCheckpoint::current_or_new("delegate");
+            assert_eq!(nested.id(), turn.id());
+        })
+        .await;
+
+        let checkpoints = CheckpointStore::for_workspace(tmp.path()).list().unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].id, turn.id());
+        assert_eq!(checkpoints[0].label, "cli");
+        assert_eq!(checkpoints[0].files.len(), 2);
+    }
+
+    #[test]
+    fn writes_outside_a_turn_get_their_own_checkpoint() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("a.txt");
+        record_before_write(tmp.path(), &file);
+        record_before_write(tmp.path(), &file);
+
+        let checkpoints = CheckpointStore::for_workspace(tmp.path()).list().unwrap();
+        assert_eq!(checkpoints.len(), 2);
+        assert!(checkpoints.iter().all(|c| c.label == "tool"));
+    }
+}
@@ -0,0 +1,520 @@
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Checkpoints kept per workspace; older ones are pruned on creation.
+pub const MAX_CHECKPOINTS: usize = 100;
+
+/// Pre-modification state of one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSnapshot {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// SHA-256 of the previous content, or `None` if the file did not exist.
+    pub blob: Option<String>,
+}
+
+/// Files modified during one agent turn, as they were before the turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    /// Where the turn came from (channel name, `agent`, `tool`, ...).
+    pub label: String,
+    pub files: Vec<FileSnapshot>,
+}
+
+/// Outcome of [`CheckpointStore::restore`].
+#[derive(Debug, Default, Serialize)]
+pub struct RestoreReport {
+    /// Checkpoints rolled back, newest first.
+    pub checkpoints: Vec<String>,
+    /// Files whose previous content was written back.
+    pub restored: Vec<PathBuf>,
+    /// Files the agent created, now deleted again.
+    pub removed: Vec<PathBuf>,
+}
+
+/// Content-addressed checkpoint store under `<workspace>/state/checkpoints`.
+///
+/// File contents live in `objects/<sha256>`, one JSON manifest per checkpoint
+/// in `manifests/<id>.json`. Checkpoint ids sort chronologically.
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    root: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Store for the given workspace.
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self::new(workspace_dir.join("state").join("checkpoints"))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn objects_dir(&self) -> PathBuf {
+        self.root.join("objects")
+    }
+
+    fn manifests_dir(&self) -> PathBuf {
+        self.root.join("manifests")
+    }
+
+    fn manifest_path(&self, id: &str) -> PathBuf {
+        self.manifests_dir().join(format!("{id}.json"))
+    }
+
+    /// All checkpoints, newest first.
+    pub fn list(&self) -> Result<Vec<Checkpoint>> {
+        let dir = self.manifests_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut checkpoints = Vec::new();
+        for entry in fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read checkpoints in {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            match read_manifest(&path) {
+                Ok(checkpoint) => checkpoints.push(checkpoint),
+                Err(e) => tracing::warn!("Skipping unreadable checkpoint {}: {e}", path.display()),
+            }
+        }
+        checkpoints.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(checkpoints)
+    }
+
+    pub fn load(&self, id: &str) -> Result<Option<Checkpoint>> {
+        validate_id(id)?;
+        let path = self.manifest_path(id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_manifest(&path).map(Some)
+    }
+
+    /// Add the current state of `path` to checkpoint `id`, creating the
+    /// checkpoint on first use. Files already in the checkpoint are kept as
+    /// they were, so the checkpoint always holds the pre-turn content.
+    pub fn snapshot(&self, id: &str, label: &str, path: &Path) -> Result<()> {
+        validate_id(id)?;
+        let blob = match fs::metadata(path) {
+            Ok(meta) if meta.is_file() => {
+                let content =
+                    fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+                Some(self.put_blob(&content)?)
+            }
+            Ok(_) => bail!("{} is not a regular file", path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).with_context(|| format!("Failed to stat {}", path.display())),
+        };
+
+        let existing = self.load(id)?;
+        let is_new = existing.is_none();
+        let mut checkpoint = existing.unwrap_or_else(|| Checkpoint {
+            id: id.to_string(),
+            created_at: Utc::now(),
+            label: label.to_string(),
+            files: Vec::new(),
+        });
+        if checkpoint.files.iter().any(|file| file.path == path) {
+            return Ok(());
+        }
+        checkpoint.files.push(FileSnapshot {
+            path: path.to_path_buf(),
+            blob,
+        });
+        self.save(&checkpoint)?;
+
+        if is_new {
+            self.prune(MAX_CHECKPOINTS)?;
+        }
+        Ok(())
+    }
+
+    /// Roll back checkpoint `id` and every newer one, newest first, then
+    /// drop them from the store.
+    ///
+    /// Manifests live inside the workspace, so they are not trusted: every
+    /// target is resolved and must stay inside `policy.workspace_dir`, outside
+    /// the store itself, and be allowed by `policy`. Nothing is touched if
+    /// any target is refused.
+    pub fn restore(&self, id: &str, policy: &SecurityPolicy) -> Result<RestoreReport> {
+        validate_id(id)?;
+        let checkpoints = self.list()?;
+        let Some(position) = checkpoints
+            .iter()
+            .position(|checkpoint| checkpoint.id == id)
+        else {
+            bail!("Checkpoint not found: {id}");
+        };
+        let rolled_back = &checkpoints[..=position];
+
+        // Older checkpoints win: their snapshot is the state before the
+        // first turn being undone.
+        let mut targets: BTreeMap<&Path, Option<&str>> = BTreeMap::new();
+        for checkpoint in rolled_back {
+            for file in &checkpoint.files {
+                targets.insert(&file.path, file.blob.as_deref());
+            }
+        }
+
+        let workspace_root = policy.workspace_dir.canonicalize().with_context(|| {
+            format!(
+                "Failed to resolve workspace {}",
+                policy.workspace_dir.display()
+            )
+        })?;
+        let store_root = resolve_target(&self.root)?;
+        let mut resolved = Vec::with_capacity(targets.len());
+        for (path, blob) in targets {
+            let target = resolve_target(path)
+                .with_context(|| format!("Refusing to restore {}", path.display()))?;
+            if !target.starts_with(&workspace_root) {
+                bail!(
+                    "Refusing to restore {}: outside the workspace {}",
+                    path.display(),
+                    workspace_root.display()
+                );
+            }
+            if target.starts_with(&store_root) {
+                bail!(
+                    "Refusing to restore {}: inside the checkpoint store",
+                    path.display()
+                );
+            }
+            if !policy.is_resolved_path_allowed(&target) {
+                bail!(
+                    "Refusing to restore {}: {}",
+                    path.display(),
+                    policy.resolved_path_violation_message(&target)
+                );
+            }
+            resolved.push((path, target, blob));
+        }
+
+        let mut report = RestoreReport {
+            checkpoints: rolled_back.iter().map(|c| c.id.clone()).collect(),
+            ..RestoreReport::default()
+        };
+        for (path, target, blob) in resolved {
+            match blob {
+                Some(hash) => {
+                    let content = self.read_blob(hash)?;
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&target, content)
+                        .with_context(|| format!("Failed to restore {}", path.display()))?;
+                    report.restored.push(path.to_path_buf());
+                }
+                None => match fs::remove_file(&target) {
+                    Ok(()) => report.removed.push(path.to_path_buf()),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => {
+                        return Err(e)
+                            .with_context(|| format!("Failed to remove {}", path.display()))
+                    }
+                },
+            }
+        }
+
+        for checkpoint in rolled_back {
+            fs::remove_file(self.manifest_path(&checkpoint.id))?;
+        }
+        self.collect_garbage()?;
+        Ok(report)
+    }
+
+    /// Keep the newest `keep` checkpoints and drop unreferenced content.
+    pub fn prune(&self, keep: usize) -> Result<()> {
+        let checkpoints = self.list()?;
+        if checkpoints.len() <= keep {
+            return Ok(());
+        }
+        for checkpoint in &checkpoints[keep..] {
+            fs::remove_file(self.manifest_path(&checkpoint.id))?;
+        }
+        self.collect_garbage()
+    }
+
+    fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        fs::create_dir_all(self.manifests_dir())?;
+        let path = self.manifest_path(&checkpoint.id);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(checkpoint)?)?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+
+    fn put_blob(&self, content: &[u8]) -> Result<String> {
+        let hash = hex::encode(Sha256::digest(content));
+        let dir = self.objects_dir();
+        let path = dir.join(&hash);
+        if !path.exists() {
+            fs::create_dir_all(&dir)?;
+            let tmp = dir.join(format!("{hash}.tmp"));
+            fs::write(&tmp, content)?;
+            fs::rename(&tmp, &path)?;
+        }
+        Ok(hash)
+    }
+
+    fn read_blob(&self, hash: &str) -> Result<Vec<u8>> {
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("Invalid checkpoint object hash: {hash}");
+        }
+        fs::read(self.objects_dir().join(hash))
+            .with_context(|| format!("Checkpoint object {hash} is missing"))
+    }
+
+    fn collect_garbage(&self) -> Result<()> {
+        let dir = self.objects_dir();
+        if !dir.exists() {
+            return Ok(());
+        }
+        let referenced: HashSet<String> = self
+            .list()?
+            .into_iter()
+            .flat_map(|checkpoint| checkpoint.files)
+            .filter_map(|file| file.blob)
+            .collect();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !referenced.contains(&name) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// New chronologically sortable checkpoint id.
+pub fn new_checkpoint_id() -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!(
+        "{}-{}",
+        Utc::now().format("%Y%m%dT%H%M%S%3fZ"),
+        &suffix[..8]
+    )
+}
+
+fn validate_id(id: &str) -> Result<()> {
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("Invalid checkpoint id: {id}");
+    }
+    Ok(())
+}
+
+/// Canonical form of `path`, which may not exist yet: the deepest existing
+/// ancestor is canonicalized (following symlinks) and the rest appended.
+fn resolve_target(path: &Path) -> Result<PathBuf> {
+    if !path.is_absolute() {
+        bail!("{} is not an absolute path", path.display());
+    }
+    if path
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        bail!("{} contains '..'", path.display());
+    }
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while fs::symlink_metadata(existing).is_err() {
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            bail!("{} has no existing ancestor", path.display());
+        };
+        rest.push(name);
+        existing = parent;
+    }
+    let mut resolved = existing
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", existing.display()))?;
+    resolved.extend(rest.iter().rev());
+    Ok(resolved)
+}
+
+fn read_manifest(path: &Path) -> Result<Checkpoint> {
+    let raw = fs::read(path)?;
+    Ok(serde_json::from_slice(&raw)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (tempfile::TempDir, CheckpointStore) {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::for_workspace(tmp.path());
+        (tmp, store)
+    }
+
+    fn policy(tmp: &tempfile::TempDir) -> SecurityPolicy {
+        SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_first_content_per_turn() {
+        let (tmp, store) = store();
+        let file = tmp.path().join("notes.txt");
+        fs::write(&file, "before").unwrap();
+
+        store.snapshot("t1", "cli", &file).unwrap();
+        fs::write(&file, "middle").unwrap();
+        store.snapshot("t1", "cli", &file).unwrap();
+
+        let checkpoint = store.load("t1").unwrap().unwrap();
+        assert_eq!(checkpoint.files.len(), 1);
+        let hash = checkpoint.files[0].blob.as_deref().unwrap();
+        assert_eq!(store.read_blob(hash).unwrap(), b"before");
+    }
+
+    #[test]
+    fn restore_reverts_edits_and_removes_created_files() {
+        let (tmp, store) = store();
+        let edited = tmp.path().join("edited.txt");
+        let created = tmp.path().join("sub").join("created.txt");
+        fs::write(&edited, "original").unwrap();
+
+        store.snapshot("t1", "cli", &edited).unwrap();
+        store.snapshot("t1", "cli", &created).unwrap();
+        fs::write(&edited, "changed").unwrap();
+        fs::create_dir_all(created.parent().unwrap()).unwrap();
+        fs::write(&created, "new").unwrap();
+
+        let report = store.restore("t1", &policy(&tmp)).unwrap();
+        assert_eq!(report.checkpoints, ["t1"]);
+        assert_eq!(fs::read_to_string(&edited).unwrap(), "original");
+        assert!(!created.exists());
+        assert_eq!(report.removed, [created]);
+        assert!(store.list().unwrap().is_empty());
+        assert_eq!(fs::read_dir(store.objects_dir()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn restore_rolls_back_newer_checkpoints_too() {
+        let (tmp, store) = store();
+        let file = tmp.path().join("a.txt");
+        fs::write(&file, "v1").unwrap();
+        store.snapshot("t1", "cli", &file).unwrap();
+        fs::write(&file, "v2").unwrap();
+        store.snapshot("t2", "cli", &file).unwrap();
+        fs::write(&file, "v3").unwrap();
+        store
+            .snapshot("t0", "cli", &tmp.path().join("other.txt"))
+            .unwrap();
+
+        let report = store.restore("t1", &policy(&tmp)).unwrap();
+        assert_eq!(report.checkpoints, ["t2", "t1"]);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v1");
+        let remaining: Vec<_> = store.list().unwrap().into_iter().map(|c| c.id).collect();
+        assert_eq!(remaining, ["t0"]);
+    }
+
+    #[test]
+    fn restore_refuses_forged_targets_outside_the_workspace() {
+        let (tmp, store) = store();
+        let outside = tempfile::tempdir().unwrap();
+        let victim = outside.path().join("victim.txt");
+        fs::write(&victim, "keep").unwrap();
+        let inside = tmp.path().join("a.txt");
+        fs::write(&inside, "v1").unwrap();
+        store.snapshot("t1", "cli", &inside).unwrap();
+        fs::write(&inside, "v2").unwrap();
+
+        let mut checkpoint = store.load("t1").unwrap().unwrap();
+        checkpoint.files.push(FileSnapshot {
+            path: victim.clone(),
+            blob: None,
+        });
+        store.save(&checkpoint).unwrap();
+
+        let err = store.restore("t1", &policy(&tmp)).unwrap_err();
+        assert!(err.to_string().contains("outside the workspace"), "{err}");
+        assert_eq!(fs::read_to_string(&victim).unwrap(), "keep");
+        assert_eq!(fs::read_to_string(&inside).unwrap(), "v2");
+        assert!(store.load("t1").unwrap().is_some());
+
+        checkpoint.files.pop();
+        checkpoint.files.push(FileSnapshot {
+            path: tmp.path().join("sub").join("..").join("..").join("x"),
+            blob: None,
+        });
+        store.save(&checkpoint).unwrap();
+        assert!(store.restore("t1", &policy(&tmp)).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restore_refuses_symlink_escapes_and_the_store_itself() {
+        let (tmp, store) = store();
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), tmp.path().join("link")).unwrap();
+        let mut checkpoint = Checkpoint {
+            id: "t1".into(),
+            created_at: Utc::now(),
+            label: "cli".into(),
+            files: vec![FileSnapshot {
+                path: tmp.path().join("link").join("escape.txt"),
+                blob: Some(store.put_blob(b"pwned").unwrap()),
+            }],
+        };
+        store.save(&checkpoint).unwrap();
+        assert!(store.restore("t1", &policy(&tmp)).is_err());
+        assert!(!outside.path().join("escape.txt").exists());
+
+        checkpoint.files[0].path = store.manifest_path("t0");
+        store.save(&checkpoint).unwrap();
+        assert!(store.restore("t1", &policy(&tmp)).is_err());
+        assert!(!store.manifest_path("t0").exists());
+    }
+
+    #[test]
+    fn prune_keeps_newest_checkpoints() {
+        let (tmp, store) = store();
+        let file = tmp.path().join("a.txt");
+        for id in ["t1", "t2", "t3"] {
+            fs::write(&file, id).unwrap();
+            store.snapshot(id, "cli", &file).unwrap();
+        }
+        store.prune(2).unwrap();
+        let ids: Vec<_> = store.list().unwrap().into_iter().map(|c| c.id).collect();
+        assert_eq!(ids, ["t3", "t2"]);
+        assert_eq!(fs::read_dir(store.objects_dir()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn rejects_path_like_ids() {
+        let (tmp, store) = store();
+        assert!(store.load("../escape").is_err());
+        assert!(store.restore("", &policy(&tmp)).is_err());
+        assert!(
+            new_checkpoint_id() < {
+                std::thread::sleep(std::time::Duration::from_millis(2));
+                new_checkpoint_id()
+            }
+        );
+    }
+}
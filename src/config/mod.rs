@@ -11,15 +11,19 @@ pub use schema::{
     AckReactionRuleAction, AckReactionRuleConfig, AckReactionStrategy, AgentConfig,
     AgentSessionBackend, AgentSessionConfig, AgentSessionStrategy, AgentsIpcConfig,
     ApiKeyInventory, AppleCalendarConfig, AuditConfig, AuthConfig, AutonomyConfig,
-    BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig, CalendarConfig, ChannelsConfig,
+    BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig, CalDavCalendarConfig,
+    CalendarConfig, CassetteConfig, CassetteMatch, CassetteMode, ChannelsConfig,
     ClassificationRule, CodingConfig, ComposioConfig, Config, CoordinationConfig, CostConfig,
     CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EconomicConfig,
     EconomicTokenPricing, ElevenLabsApiConfig, EmbeddingRouteConfig, EstopConfig, FeishuConfig,
     AdvisorConfig, FreepikApiConfig, GatekeeperConfig, GatewayConfig, GoogleCalendarConfig, GroupReplyConfig,
     GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, HttpRequestCredentialProfile, IMessageConfig, IdentityConfig,
-    KakaoCalendarConfig, LarkConfig, LiveKitConfig, MatrixConfig, MediaApiConfig, MemoryConfig, ModelRouteConfig,
-    MultimodalConfig, NextcloudTalkConfig, NonCliNaturalLanguageApprovalMode, ObservabilityConfig,
+    HttpRequestConfig, HttpRequestCredentialProfile, IMessageConfig, IcsCalendarConfig,
+    IdentityConfig, InboxConfig, InboxEscalationRule,
+    KakaoCalendarConfig, LarkConfig, LiveKitConfig, LocalSttConfig, LocalSttEngine,
+    MatrixConfig, MediaApiConfig, MemoryConfig, ModelRouteConfig,
+    MultimodalConfig, NetworkDeviceConfig, NextcloudTalkConfig,
+    NonCliNaturalLanguageApprovalMode, ObservabilityConfig,
     OtpChallengeDelivery, OtpConfig, OtpMethod, OutboundLeakGuardAction, OutboundLeakGuardConfig,
     OutlookCalendarConfig, PeripheralBoardConfig, PeripheralsConfig, PerplexityFilterConfig,
     PerplexitySearchApiConfig, PlatformRoutingConfig, PluginEntryConfig, PluginsConfig,
@@ -27,12 +31,15 @@ pub use schema::{
     QueryClassificationConfig, ReliabilityConfig, RerankConfig, ResearchPhaseConfig,
     ResearchTrigger, ResourceLimitsConfig, RuntimeConfig, RunwayApiConfig, SandboxBackend,
     SandboxConfig,
-    SchedulerConfig, SecretsConfig, SecurityConfig, SecurityRoleConfig, SkillsConfig,
+    SchedulerConfig, SeccompProfile, SeccompSandboxConfig, SecretsConfig, SecurityConfig,
+    SecurityRoleConfig, SkillsConfig,
     SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
     StorageProviderSection, StreamMode, SunoApiConfig, SyncConfig, SyscallAnomalyConfig,
-    TaskCategory, TelegramConfig, TelemetryConfig, TranscriptionConfig, TunnelConfig,
-    UrlAccessConfig, VoiceConfig, WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy,
-    WasmRuntimeConfig, WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
+    TaskCategory, TeamConfig, TeamRoleConfig, TelegramConfig, TelemetryConfig, TranscriptionBackend,
+    TranscriptionConfig,
+    TunnelConfig,
+    UrlAccessConfig, VirtualDeviceConfig, VoiceConfig, WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy,
+    WasmRuntimeConfig, WasmSecurityConfig, WaveformConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
     DEFAULT_MODEL_FALLBACK,
 };
 
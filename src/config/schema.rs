@@ -390,6 +390,10 @@ pub struct Config {
     #[serde(default)]
     pub goal_loop: GoalLoopConfig,
 
+    /// Signed webhook endpoints feeding the dispatch router (`[webhooks]`).
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+
     /// Channel configurations: Telegram, Discord, Slack, etc. (`[channels_config]`).
     #[serde(default)]
     pub channels_config: ChannelsConfig,
@@ -478,6 +482,14 @@ pub struct Config {
     #[serde(default)]
     pub coordination: CoordinationConfig,
 
+    /// Hierarchical agent teams run by the `team_run` tool (`[teams.<name>]`).
+    #[serde(default)]
+    pub teams: HashMap<String, TeamConfig>,
+
+    /// Human-in-the-loop task inbox (`[inbox]`).
+    #[serde(default)]
+    pub inbox: InboxConfig,
+
     /// Hooks configuration (lifecycle hooks and built-in hook toggles).
     #[serde(default)]
     pub hooks: HooksConfig,
@@ -786,12 +798,101 @@ pub struct HardwareConfig {
     /// Enable workspace datasheet RAG (index PDF schematics for AI pin lookups)
     #[serde(default)]
     pub workspace_datasheets: bool,
+    /// Wi-Fi boards reached over TCP/WebSocket (`[[hardware.network_devices]]`)
+    #[serde(default)]
+    pub network_devices: Vec<NetworkDeviceConfig>,
+    /// Simulated boards for testing without hardware (`[[hardware.virtual_devices]]`)
+    #[serde(default)]
+    pub virtual_devices: Vec<VirtualDeviceConfig>,
 }
 
 fn default_baud_rate() -> u32 {
     115_200
 }
 
+fn default_network_device_board() -> String {
+    "esp32".into()
+}
+
+/// A statically registered network board speaking the ZeroClaw JSON protocol.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkDeviceConfig {
+    /// Board name used for the alias (e.g. "esp32" → `esp0`)
+    #[serde(default = "default_network_device_board")]
+    pub board: String,
+    /// Device address: `tcp://host:port`, `ws://host:port/path` or `wss://…`
+    pub address: String,
+    /// Pre-shared key for mutual HMAC authentication (encrypted at rest)
+    #[serde(default)]
+    pub psk: Option<String>,
+}
+
+fn default_virtual_device_board() -> String {
+    "sim".into()
+}
+
+fn default_virtual_device_gpio() -> Vec<u8> {
+    (0..=13).collect()
+}
+
+/// A simulated board that answers the ZeroClaw JSON protocol in-process.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VirtualDeviceConfig {
+    /// Board name used for the alias (e.g. "sim" → `sim0`)
+    #[serde(default = "default_virtual_device_board")]
+    pub board: String,
+    /// GPIO pins the board exposes
+    #[serde(default = "default_virtual_device_gpio")]
+    pub gpio: Vec<u8>,
+    /// Initial pin levels keyed by pin number (e.g. `{ "9" = 1 }`)
+    #[serde(default)]
+    pub pins: HashMap<String, u8>,
+    /// ADC channels; channel N produces the Nth waveform
+    #[serde(default)]
+    pub adc: Vec<WaveformConfig>,
+    /// I2C addresses that answer a bus scan
+    #[serde(default)]
+    pub i2c_addresses: Vec<u8>,
+    /// Delay added to every command, in milliseconds
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Probability (0.0–1.0) that a command fails with a transport timeout
+    #[serde(default)]
+    pub fault_rate: f64,
+    /// Commands that always answer with a device error
+    #[serde(default)]
+    pub failing_commands: Vec<String>,
+}
+
+impl Default for VirtualDeviceConfig {
+    fn default() -> Self {
+        Self {
+            board: default_virtual_device_board(),
+            gpio: default_virtual_device_gpio(),
+            pins: HashMap::new(),
+            adc: Vec::new(),
+            i2c_addresses: Vec::new(),
+            latency_ms: 0,
+            fault_rate: 0.0,
+            failing_commands: Vec::new(),
+        }
+    }
+}
+
+/// Signal produced by a virtual ADC channel, in raw 12-bit units.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum WaveformConfig {
+    /// A fixed reading.
+    Constant { value: u16 },
+    /// A sine wave between `min` and `max`.
+    Sine { min: u16, max: u16, period_ms: u64 },
+    /// `min` for the first half of each period, `max` for the second.
+    Square { min: u16, max: u16, period_ms: u64 },
+    /// A sawtooth rising from `min` to `max` once per period.
+    Ramp { min: u16, max: u16, period_ms: u64 },
+}
+
 impl HardwareConfig {
     /// Return the active transport mode.
     pub fn transport_mode(&self) -> HardwareTransport {
@@ -808,6 +909,8 @@ impl Default for HardwareConfig {
             baud_rate: default_baud_rate(),
             probe_target: None,
             workspace_datasheets: false,
+            network_devices: Vec::new(),
+            virtual_devices: Vec::new(),
         }
     }
 }
@@ -849,6 +952,16 @@ pub struct TranscriptionConfig {
     /// Maximum voice duration in seconds (messages longer than this are skipped).
     #[serde(default = "default_transcription_max_duration_secs")]
     pub max_duration_secs: u64,
+    /// Default backend: `cloud` (Whisper API above) or `local` (offline).
+    #[serde(default)]
+    pub backend: TranscriptionBackend,
+    /// Per-channel backend overrides, keyed by channel name
+    /// (e.g. `telegram = "local"`).
+    #[serde(default)]
+    pub channel_backends: HashMap<String, TranscriptionBackend>,
+    /// Offline speech-to-text settings (`[transcription.local]`).
+    #[serde(default)]
+    pub local: LocalSttConfig,
 }
 
 impl Default for TranscriptionConfig {
@@ -860,6 +973,116 @@ impl Default for TranscriptionConfig {
             model: default_transcription_model(),
             language: None,
             max_duration_secs: default_transcription_max_duration_secs(),
+            backend: TranscriptionBackend::default(),
+            channel_backends: HashMap::new(),
+            local: LocalSttConfig::default(),
+        }
+    }
+}
+
+impl TranscriptionConfig {
+    /// Backend to use for audio arriving on `channel`.
+    pub fn backend_for(&self, channel: &str) -> TranscriptionBackend {
+        self.channel_backends
+            .get(channel)
+            .copied()
+            .unwrap_or(self.backend)
+    }
+}
+
+/// Where voice transcription runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionBackend {
+    /// Whisper-compatible cloud API (`api_url`).
+    #[default]
+    Cloud,
+    /// Offline engine configured under `[transcription.local]`.
+    Local,
+}
+
+/// Engine used by the local transcription backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalSttEngine {
+    /// whisper.cpp `server` (or any OpenAI-compatible transcription server).
+    #[default]
+    Server,
+    /// ggml Whisper model loaded in-process (requires the `stt-whisper` feature).
+    InProcess,
+}
+
+fn default_local_stt_server_url() -> String {
+    "http://127.0.0.1:8080/inference".into()
+}
+
+fn default_local_stt_vad_aggressiveness() -> u8 {
+    2
+}
+
+fn default_local_stt_min_silence_ms() -> u32 {
+    600
+}
+
+fn default_local_stt_max_segment_secs() -> u32 {
+    30
+}
+
+fn default_local_stt_timeout_secs() -> u64 {
+    300
+}
+
+/// Offline speech-to-text configuration (`[transcription.local]`).
+///
+/// Audio is decoded to 16 kHz mono, split into speech segments by an energy
+/// VAD, and each segment is sent to the selected engine.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LocalSttConfig {
+    /// `server` or `in_process`.
+    #[serde(default)]
+    pub engine: LocalSttEngine,
+    /// Transcription endpoint for the `server` engine.
+    #[serde(default = "default_local_stt_server_url")]
+    pub server_url: String,
+    /// Model name sent to OpenAI-compatible servers (ignored by whisper.cpp).
+    #[serde(default)]
+    pub server_model: Option<String>,
+    /// Path to a ggml Whisper model for the `in_process` engine.
+    #[serde(default)]
+    pub model_path: Option<String>,
+    /// Inference threads for the `in_process` engine (0 = library default).
+    #[serde(default)]
+    pub threads: usize,
+    /// VAD aggressiveness, 0 (keeps most audio) to 3 (strictest).
+    #[serde(default = "default_local_stt_vad_aggressiveness")]
+    pub vad_aggressiveness: u8,
+    /// Silence needed to close a speech segment.
+    #[serde(default = "default_local_stt_min_silence_ms")]
+    pub min_silence_ms: u32,
+    /// Longest segment sent to the engine; longer speech is split.
+    #[serde(default = "default_local_stt_max_segment_secs")]
+    pub max_segment_secs: u32,
+    /// Request word-level timestamps.
+    #[serde(default = "default_true")]
+    pub word_timestamps: bool,
+    /// Per-request timeout for the `server` engine.
+    #[serde(default = "default_local_stt_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for LocalSttConfig {
+    fn default() -> Self {
+        Self {
+            engine: LocalSttEngine::default(),
+            server_url: default_local_stt_server_url(),
+            server_model: None,
+            model_path: None,
+            threads: 0,
+            vad_aggressiveness: default_local_stt_vad_aggressiveness(),
+            min_silence_ms: default_local_stt_min_silence_ms(),
+            max_segment_secs: default_local_stt_max_segment_secs(),
+            word_timestamps: true,
+            timeout_secs: default_local_stt_timeout_secs(),
         }
     }
 }
@@ -878,6 +1101,10 @@ pub struct CalendarConfig {
     pub kakao: KakaoCalendarConfig,
     #[serde(default)]
     pub apple: AppleCalendarConfig,
+    #[serde(default)]
+    pub caldav: CalDavCalendarConfig,
+    #[serde(default)]
+    pub ics: IcsCalendarConfig,
 }
 
 
@@ -967,7 +1194,7 @@ pub struct KakaoCalendarConfig {
 
 
 /// Apple Calendar (CalDAV) configuration.
-/// **Status: planned** — config schema ready, CalDAV implementation pending.
+/// Served by the generic CalDAV backend against `caldav_url`.
 /// Apple CalDAV requires app-specific password from appleid.apple.com.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AppleCalendarConfig {
@@ -996,6 +1223,71 @@ impl Default for AppleCalendarConfig {
     }
 }
 
+/// Generic CalDAV calendar (`[calendar.caldav]`) — Nextcloud, Radicale, etc.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalDavCalendarConfig {
+    /// Enable the CalDAV calendar tools.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Server or principal URL (e.g. `https://cloud.example.com/remote.php/dav`).
+    /// Calendars are discovered from here.
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password or app password.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Calendar display name, or collection path to skip discovery.
+    /// If unset, the first calendar that accepts events is used.
+    #[serde(default)]
+    pub calendar: Option<String>,
+    /// Timezone for floating times and recurring events created by the agent.
+    #[serde(default = "default_calendar_timezone")]
+    pub timezone: String,
+}
+
+fn default_calendar_timezone() -> String {
+    "Asia/Seoul".into()
+}
+
+impl Default for CalDavCalendarConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            username: None,
+            password: None,
+            calendar: None,
+            timezone: default_calendar_timezone(),
+        }
+    }
+}
+
+/// Local iCalendar file (`[calendar.ics]`), read and written in place.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IcsCalendarConfig {
+    /// Enable the ICS file calendar tools.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path to the `.ics` file (`~` is expanded). Created on first write.
+    #[serde(default)]
+    pub path: String,
+    /// Timezone for floating times and recurring events created by the agent.
+    #[serde(default = "default_calendar_timezone")]
+    pub timezone: String,
+}
+
+impl Default for IcsCalendarConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            timezone: default_calendar_timezone(),
+        }
+    }
+}
+
 // ── Media API ──────────────────────────────────────────────────
 
 /// Configuration for external media generation APIs (`[media_api]`).
@@ -1305,6 +1597,18 @@ fn default_coordination_max_seen_message_ids() -> usize {
     4096
 }
 
+fn default_coordination_backend() -> String {
+    "memory".into()
+}
+
+fn default_coordination_ack_timeout_secs() -> u64 {
+    300
+}
+
+fn default_coordination_max_delivery_attempts() -> u32 {
+    5
+}
+
 /// Delegate coordination runtime configuration (`[coordination]` section).
 ///
 /// Controls typed delegate message-bus integration used by `delegate` and
@@ -1329,6 +1633,17 @@ pub struct CoordinationConfig {
     /// Maximum retained dedupe window size for processed message IDs.
     #[serde(default = "default_coordination_max_seen_message_ids")]
     pub max_seen_message_ids: usize,
+    /// Bus storage: `"memory"` (lost on restart) or `"sqlite"` (persisted to
+    /// `coordination.db` in the workspace and restored on startup).
+    #[serde(default = "default_coordination_backend")]
+    pub backend: String,
+    /// Seconds a received message may stay unacknowledged before it is
+    /// delivered again.
+    #[serde(default = "default_coordination_ack_timeout_secs")]
+    pub ack_timeout_secs: u64,
+    /// Deliveries of one message before it moves to the dead letters.
+    #[serde(default = "default_coordination_max_delivery_attempts")]
+    pub max_delivery_attempts: u32,
 }
 
 impl Default for CoordinationConfig {
@@ -1340,10 +1655,151 @@ impl Default for CoordinationConfig {
             max_dead_letters: default_coordination_max_dead_letters(),
             max_context_entries: default_coordination_max_context_entries(),
             max_seen_message_ids: default_coordination_max_seen_message_ids(),
+            backend: default_coordination_backend(),
+            ack_timeout_secs: default_coordination_ack_timeout_secs(),
+            max_delivery_attempts: default_coordination_max_delivery_attempts(),
+        }
+    }
+}
+
+// ── Agent Teams ──────────────────────────────────────────────────
+
+fn default_team_max_subtasks() -> usize {
+    6
+}
+
+fn default_team_max_handoffs() -> usize {
+    3
+}
+
+fn default_team_timeout_secs() -> u64 {
+    900
+}
+
+/// Agent team definition (`[teams.<name>]`).
+///
+/// A supervisor plans the goal into subtasks, assigns them to roles over the
+/// coordination bus, tracks progress on the shared-context blackboard and
+/// merges the results into one answer.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TeamConfig {
+    /// What the team is for; shown in the `team_run` tool description.
+    #[serde(default)]
+    pub description: String,
+    /// Agent that plans subtasks and merges their results.
+    pub supervisor: DelegateAgentConfig,
+    /// Roles the supervisor can assign subtasks to, keyed by role name.
+    #[serde(default)]
+    pub roles: HashMap<String, TeamRoleConfig>,
+    /// Maximum number of subtasks in the supervisor's plan. Default: `6`.
+    #[serde(default = "default_team_max_subtasks")]
+    pub max_subtasks: usize,
+    /// Maximum follow-up subtasks roles may hand off per run. Default: `3`.
+    #[serde(default = "default_team_max_handoffs")]
+    pub max_handoffs: usize,
+    /// Spend limit per run in USD across the supervisor and all roles.
+    /// Enforced from the cost ledger, so it requires `[cost] enabled = true`.
+    #[serde(default)]
+    pub budget_usd: Option<f64>,
+    /// Wall-clock limit per run in seconds. Default: `900`.
+    #[serde(default = "default_team_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// One role in an agent team (`[teams.<name>.roles.<role>]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TeamRoleConfig {
+    /// What this role is good at; the supervisor plans from it.
+    #[serde(default)]
+    pub description: String,
+    /// Provider, model, prompt and tool allowlist, as for `[agents.<name>]`.
+    #[serde(flatten)]
+    pub agent: DelegateAgentConfig,
+    /// Roles this role may hand follow-up work to.
+    #[serde(default)]
+    pub handoff_to: Vec<String>,
+}
+
+// ── Human Task Inbox ─────────────────────────────────────────────
+
+fn default_inbox_enabled() -> bool {
+    true
+}
+
+fn default_inbox_deadline_secs() -> u64 {
+    86_400
+}
+
+fn default_inbox_reminder_interval_secs() -> u64 {
+    3_600
+}
+
+fn default_inbox_max_reminders() -> u32 {
+    3
+}
+
+fn default_inbox_poll_secs() -> u64 {
+    30
+}
+
+/// Human-in-the-loop task inbox (`[inbox]` section).
+///
+/// Tool approvals, workflow confirmations, agent questions and draft reviews
+/// are kept as tasks until someone resolves them from a channel, the
+/// dashboard or `zeroclaw inbox`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InboxConfig {
+    /// Enable the inbox, its `ask_human` tool and the daemon notifier.
+    #[serde(default = "default_inbox_enabled")]
+    pub enabled: bool,
+    /// Assignee for tasks that do not name one, as `channel:recipient`
+    /// (e.g. `telegram:123456`) to be notified there.
+    #[serde(default)]
+    pub default_assignee: Option<String>,
+    /// Seconds until an unanswered task expires; `0` disables. Default: `86400`.
+    #[serde(default = "default_inbox_deadline_secs")]
+    pub default_deadline_secs: u64,
+    /// Seconds between reminders to the assignee; `0` disables. Default: `3600`.
+    #[serde(default = "default_inbox_reminder_interval_secs")]
+    pub reminder_interval_secs: u64,
+    /// Maximum reminders per task. Default: `3`.
+    #[serde(default = "default_inbox_max_reminders")]
+    pub max_reminders: u32,
+    /// How often the daemon sweeps for due notifications. Default: `30`.
+    #[serde(default = "default_inbox_poll_secs")]
+    pub poll_secs: u64,
+    /// Escalation rules; the first rule matching a task's kind applies.
+    #[serde(default)]
+    pub escalation: Vec<InboxEscalationRule>,
+}
+
+impl Default for InboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_inbox_enabled(),
+            default_assignee: None,
+            default_deadline_secs: default_inbox_deadline_secs(),
+            reminder_interval_secs: default_inbox_reminder_interval_secs(),
+            max_reminders: default_inbox_max_reminders(),
+            poll_secs: default_inbox_poll_secs(),
+            escalation: Vec::new(),
         }
     }
 }
 
+/// Hand an unanswered task to someone else (`[[inbox.escalation]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InboxEscalationRule {
+    /// Task kinds the rule covers (`tool_approval`, `workflow_confirm`,
+    /// `question`, `review`); empty covers all.
+    #[serde(default)]
+    pub kinds: Vec<String>,
+    /// Seconds after creation before the task is escalated.
+    pub after_secs: u64,
+    /// New assignee, as `channel:recipient`.
+    pub to: String,
+}
+
 /// Agent orchestration configuration (`[agent]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
@@ -1397,6 +1853,48 @@ pub struct AgentConfig {
     /// set to `0` for explicit disable.
     #[serde(default = "default_safety_heartbeat_turn_interval")]
     pub safety_heartbeat_turn_interval: usize,
+    /// Record provider traffic to, or replay it from, a cassette file.
+    /// Usually set per run with `zeroclaw agent --record` / `--replay`.
+    #[serde(default)]
+    pub cassette: CassetteConfig,
+}
+
+/// Provider cassette settings (`[agent.cassette]`).
+///
+/// A cassette is a JSON file of provider request/response pairs. Recording
+/// captures a real session with secrets scrubbed; replaying serves it back
+/// offline so a reported bug can be reproduced without the user's keys.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CassetteConfig {
+    /// `off`, `record` or `replay`. Default: `off`.
+    #[serde(default)]
+    pub mode: CassetteMode,
+    /// Cassette file path. Required unless `mode = "off"`.
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// How replayed requests are matched to recorded ones. Default: `strict`.
+    #[serde(default)]
+    pub matching: CassetteMatch,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CassetteMode {
+    #[default]
+    Off,
+    Record,
+    Replay,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CassetteMatch {
+    /// Requests must arrive in recorded order and be identical.
+    #[default]
+    Strict,
+    /// Requests are matched on their conversation, ignoring the system
+    /// prompt, model and temperature, falling back to recorded order.
+    Fuzzy,
 }
 
 /// Sandbox loop tunables (used in Coding mode).
@@ -1615,6 +2113,7 @@ impl Default for AgentConfig {
             loop_detection_failure_streak: default_loop_detection_failure_streak(),
             safety_heartbeat_interval: default_safety_heartbeat_interval(),
             safety_heartbeat_turn_interval: default_safety_heartbeat_turn_interval(),
+            cassette: CassetteConfig::default(),
         }
     }
 }
@@ -1686,6 +2185,14 @@ pub struct SkillsConfig {
     /// Set via config: `clawhub_token = "..."` under `[skills]`.
     #[serde(default)]
     pub clawhub_token: Option<String>,
+    /// Publisher name → base64 Ed25519 public key. A skill shipping a `SKILL.sig`
+    /// is only accepted when it is signed by one of these keys.
+    #[serde(default)]
+    pub trusted_publishers: HashMap<String, String>,
+    /// Refuse to install or load workspace skills without a valid publisher signature.
+    /// Default: `false`.
+    #[serde(default)]
+    pub require_signatures: bool,
 }
 
 /// WASM plugin engine configuration (`[wasm]` section).
@@ -1846,6 +2353,119 @@ pub struct CostConfig {
     /// Runtime budget enforcement policy (`[cost.enforcement]`).
     #[serde(default)]
     pub enforcement: CostEnforcementConfig,
+
+    /// Per-dimension budgets (`[[cost.budgets]]`), enforced in addition to
+    /// the global daily/monthly limits.
+    #[serde(default)]
+    pub budgets: Vec<CostBudgetConfig>,
+
+    /// Prices for non-chat usage, keyed by `<kind>/<resource>` or `<kind>`:
+    /// `embedding` per 1M tokens, `search` per request, `tts`/`stt` per audio
+    /// minute. The most specific key wins; unpriced usage is recorded at $0.
+    #[serde(default = "default_metered_prices")]
+    pub metered_prices: std::collections::HashMap<String, f64>,
+}
+
+/// Dimension that spend is attributed to, budgeted on and grouped by.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CostDimension {
+    User,
+    Channel,
+    Session,
+    CronJob,
+    Workflow,
+    Tool,
+    /// Model or metered resource (report grouping only).
+    Model,
+    /// `llm`, `embedding`, `search`, `tts` or `stt` (report grouping only).
+    Kind,
+}
+
+impl CostDimension {
+    pub const ALL: [Self; 8] = [
+        Self::User,
+        Self::Channel,
+        Self::Session,
+        Self::CronJob,
+        Self::Workflow,
+        Self::Tool,
+        Self::Model,
+        Self::Kind,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Channel => "channel",
+            Self::Session => "session",
+            Self::CronJob => "cron_job",
+            Self::Workflow => "workflow",
+            Self::Tool => "tool",
+            Self::Model => "model",
+            Self::Kind => "kind",
+        }
+    }
+
+    /// Parse a dimension name; `cron`/`job` and `-` separators are accepted.
+    pub fn from_name(raw: &str) -> Option<Self> {
+        let normalized = raw.trim().to_ascii_lowercase().replace('-', "_");
+        match normalized.as_str() {
+            "cron" | "job" => Some(Self::CronJob),
+            name => Self::ALL.into_iter().find(|d| d.as_str() == name),
+        }
+    }
+}
+
+/// Spending cap on one attribution dimension (`[[cost.budgets]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CostBudgetConfig {
+    /// `user`, `channel`, `session`, `cron_job`, `workflow` or `tool`.
+    pub dimension: CostDimension,
+    /// Value to cap (user ID, channel name, job ID, ...). `*` caps every value
+    /// separately. Default: `*`.
+    #[serde(default = "default_cost_budget_key")]
+    pub key: String,
+    /// Daily limit in USD for each matching value.
+    #[serde(default)]
+    pub daily_limit_usd: Option<f64>,
+    /// Monthly limit in USD for each matching value.
+    #[serde(default)]
+    pub monthly_limit_usd: Option<f64>,
+    /// What to do once exceeded: `warn`, `route_down` or `block`. Default: `block`.
+    #[serde(default = "default_cost_budget_action")]
+    pub action: CostEnforcementMode,
+    /// Model to downgrade to under `route_down`. Falls back to
+    /// `[cost.enforcement].route_down_model`.
+    #[serde(default)]
+    pub route_down_model: Option<String>,
+}
+
+fn default_cost_budget_key() -> String {
+    "*".to_string()
+}
+
+fn default_cost_budget_action() -> CostEnforcementMode {
+    CostEnforcementMode::Block
+}
+
+fn default_metered_prices() -> std::collections::HashMap<String, f64> {
+    [
+        ("embedding", 0.02),
+        ("embedding/openai/text-embedding-3-large", 0.13),
+        ("search/brave", 0.005),
+        ("search/tavily", 0.008),
+        ("search/perplexity", 0.005),
+        ("search/exa", 0.005),
+        ("search/firecrawl", 0.001),
+        ("stt", 0.006),
+        ("stt/local", 0.0),
+        ("tts", 0.015),
+        ("tts/elevenlabs", 0.18),
+    ]
+    .into_iter()
+    .map(|(key, price)| (key.to_string(), price))
+    .collect()
 }
 
 /// Budget enforcement behavior when projected spend approaches/exceeds limits.
@@ -1930,6 +2550,8 @@ impl Default for CostConfig {
             allow_override: false,
             prices: get_default_pricing(),
             enforcement: CostEnforcementConfig::default(),
+            budgets: Vec::new(),
+            metered_prices: default_metered_prices(),
         }
     }
 }
@@ -2701,6 +3323,129 @@ fn default_mqtt_keep_alive() -> u64 {
     60
 }
 
+// ── Webhook ingestion (named /hooks/{name} endpoints via src/dispatch/) ──
+
+/// Named webhook endpoints that publish into the dispatch router (`[webhooks]`).
+///
+/// Each endpoint is served at `POST /hooks/{name}` on the gateway. The
+/// sender's signature is the credential (pairing tokens are not required),
+/// and every accepted delivery becomes a `DispatchEvent` with source
+/// `webhook`. Reactions subscribe to those events by topic prefix.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhooksConfig {
+    /// Serve the configured endpoints. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Accepted clock skew for signed timestamps, and how long a delivery
+    /// is remembered for replay rejection. Default: `300`.
+    #[serde(default = "default_webhook_replay_window_secs")]
+    pub replay_window_secs: u64,
+    /// Timeout in seconds for an agent reaction. Default: `300`.
+    #[serde(default = "default_webhook_agent_timeout_secs")]
+    pub agent_timeout_secs: u64,
+    /// Endpoints (`[[webhooks.endpoints]]`).
+    #[serde(default)]
+    pub endpoints: Vec<WebhookEndpointConfig>,
+    /// Reactions to webhook events (`[[webhooks.reactions]]`).
+    #[serde(default)]
+    pub reactions: Vec<WebhookReactionConfig>,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            replay_window_secs: default_webhook_replay_window_secs(),
+            agent_timeout_secs: default_webhook_agent_timeout_secs(),
+            endpoints: Vec::new(),
+            reactions: Vec::new(),
+        }
+    }
+}
+
+fn default_webhook_replay_window_secs() -> u64 {
+    300
+}
+
+fn default_webhook_agent_timeout_secs() -> u64 {
+    300
+}
+
+/// How a webhook endpoint authenticates deliveries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookSignature {
+    /// GitHub: `X-Hub-Signature-256: sha256=<hex>` over the body; replays
+    /// are detected by the signature.
+    Github,
+    /// Stripe: `Stripe-Signature: t=<unix>,v1=<hex>` over `"{t}.{body}"`.
+    Stripe,
+    /// Generic: `X-Webhook-Signature: sha256=<hex>` over
+    /// `"{timestamp}.{body}"`, with the timestamp in `X-Webhook-Timestamp`.
+    #[default]
+    Sha256,
+    /// No verification. Only for trusted networks.
+    None,
+}
+
+/// A named webhook endpoint (`[[webhooks.endpoints]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookEndpointConfig {
+    /// Endpoint name; served at `/hooks/{name}`. Letters, digits, `-` and `_`.
+    pub name: String,
+    /// Signature scheme. Default: `"sha256"`.
+    #[serde(default)]
+    pub signature: WebhookSignature,
+    /// HMAC-SHA256 signing secret. Required unless `signature = "none"`.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Override the header carrying the signature.
+    #[serde(default)]
+    pub signature_header: Option<String>,
+    /// Override the header carrying the timestamp (`sha256` scheme only).
+    #[serde(default)]
+    pub timestamp_header: Option<String>,
+    /// Event topic template. `{name}` is the endpoint name, `{$.a.b}` a
+    /// value from the JSON body and `{header:X-Name}` a request header.
+    /// Default: `"webhook/{name}"`.
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// JSON path (`$.a.b`) selecting the event payload. Default: the whole body.
+    #[serde(default)]
+    pub payload: Option<String>,
+    /// Dispatch only deliveries whose body matches this condition
+    /// (e.g. `$.action == opened`); others are acknowledged and dropped.
+    #[serde(default)]
+    pub condition: Option<String>,
+}
+
+/// A reaction to webhook events (`[[webhooks.reactions]]`).
+///
+/// With `prompt` set the event is handed to the agent, and its reply is sent
+/// to `channel`/`to` when both are given. Without `prompt` the event is
+/// rendered with `template` and sent to `channel`/`to`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookReactionConfig {
+    /// Reaction name, used in the dispatch audit log.
+    pub name: String,
+    /// Only react to events whose topic starts with this prefix. Unset: all webhook events.
+    #[serde(default)]
+    pub topic_prefix: Option<String>,
+    /// Instruction for the agent, followed by the event.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Channel to notify (e.g. `"telegram"`).
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel`.
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Notification template with `{topic}`, `{payload}` and `{source}`.
+    /// Default: `"[{source}] {topic}: {payload}"`.
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
 // ── Economic Agent Config ─────────────────────────────────────────
 
 /// Token pricing configuration for economic tracking.
@@ -4616,12 +5361,21 @@ pub struct PluginsConfig {
     /// Per-plugin configuration entries.
     #[serde(default)]
     pub entries: std::collections::HashMap<String, PluginEntryConfig>,
+
+    /// Re-instantiate a WASM component plugin when its `.wasm` file changes.
+    /// Default: `true`.
+    #[serde(default = "default_plugins_hot_reload")]
+    pub hot_reload: bool,
 }
 
 fn default_plugins_enabled() -> bool {
     true
 }
 
+fn default_plugins_hot_reload() -> bool {
+    true
+}
+
 impl Default for PluginsConfig {
     fn default() -> Self {
         Self {
@@ -4630,6 +5384,7 @@ impl Default for PluginsConfig {
             deny: Vec::new(),
             load_paths: Vec::new(),
             entries: std::collections::HashMap::new(),
+            hot_reload: true,
         }
     }
 }
@@ -5571,10 +6326,28 @@ pub struct GoalLoopConfig {
     pub max_steps_per_cycle: u32,
     /// Optional channel to deliver goal events to (e.g. "lark", "telegram").
     #[serde(default)]
-    pub channel: Option<String>,
-    /// Optional recipient/chat_id for goal event delivery.
+    pub channel: Option<String>,
+    /// Optional recipient/chat_id for goal event delivery.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Only advance goals within this daily window (`"HH:MM-HH:MM"`, may
+    /// wrap past midnight). Unset: around the clock.
+    #[serde(default)]
+    pub active_hours: Option<String>,
+    /// IANA timezone for `active_hours` (e.g. `"Europe/Berlin"`). Unset: host local time.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Spend cap in USD for all goal work per UTC day. Requires `[cost]`.
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    /// Default spend cap in USD per goal; a goal's own `budget_usd` wins.
+    /// A goal that reaches its cap is blocked and its owner notified.
+    #[serde(default)]
+    pub goal_budget_usd: Option<f64>,
+    /// Notify the owner after every completed step, not only at goal
+    /// completion, stalls and checkpoints. Default: `false`.
     #[serde(default)]
-    pub target: Option<String>,
+    pub notify_each_step: bool,
 }
 
 impl Default for GoalLoopConfig {
@@ -5586,10 +6359,37 @@ impl Default for GoalLoopConfig {
             max_steps_per_cycle: 3,
             channel: None,
             target: None,
+            active_hours: None,
+            timezone: None,
+            daily_budget_usd: None,
+            goal_budget_usd: None,
+            notify_each_step: false,
         }
     }
 }
 
+impl GoalLoopConfig {
+    /// Parsed `active_hours` as `(start, end)`; `None` when unrestricted.
+    pub fn active_window(&self) -> Result<Option<(chrono::NaiveTime, chrono::NaiveTime)>> {
+        let Some(raw) = self
+            .active_hours
+            .as_deref()
+            .map(str::trim)
+            .filter(|raw| !raw.is_empty())
+        else {
+            return Ok(None);
+        };
+        let (start, end) = raw
+            .split_once('-')
+            .with_context(|| format!("expected HH:MM-HH:MM, got {raw:?}"))?;
+        let parse = |part: &str| {
+            chrono::NaiveTime::parse_from_str(part.trim(), "%H:%M")
+                .with_context(|| format!("invalid time {:?} in {raw:?}", part.trim()))
+        };
+        Ok(Some((parse(start)?, parse(end)?)))
+    }
+}
+
 // ── Cron ────────────────────────────────────────────────────────
 
 /// Cron job configuration (`[cron]` section).
@@ -7509,6 +8309,10 @@ pub struct SandboxConfig {
     /// Custom Firejail arguments (when backend = firejail)
     #[serde(default)]
     pub firejail_args: Vec<String>,
+
+    /// Built-in seccomp-bpf sandbox settings (when backend = seccomp)
+    #[serde(default)]
+    pub seccomp: SeccompSandboxConfig,
 }
 
 impl Default for SandboxConfig {
@@ -7517,6 +8321,78 @@ impl Default for SandboxConfig {
             enabled: None, // Auto-detect
             backend: SandboxBackend::Auto,
             firejail_args: Vec::new(),
+            seccomp: SeccompSandboxConfig::default(),
+        }
+    }
+}
+
+/// Syscall allowlist applied by the built-in seccomp sandbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SeccompProfile {
+    /// Standard allowlist without network sockets (Unix sockets only)
+    Strict,
+    /// Common process, filesystem and network syscalls
+    Standard,
+    /// No syscall filter; resource limits still apply
+    Permissive,
+}
+
+/// Built-in seccomp-bpf sandbox configuration.
+///
+/// Shell and process tool children get a syscall allowlist chosen by the
+/// agent's autonomy level, plus the `[security.resources]` limits enforced
+/// through cgroup v2 when delegated, or `setrlimit` otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SeccompSandboxConfig {
+    /// Profile used when autonomy = "read_only"
+    #[serde(default = "default_seccomp_read_only_profile")]
+    pub read_only_profile: SeccompProfile,
+
+    /// Profile used when autonomy = "supervised"
+    #[serde(default = "default_seccomp_supervised_profile")]
+    pub supervised_profile: SeccompProfile,
+
+    /// Profile used when autonomy = "full"
+    #[serde(default = "default_seccomp_full_profile")]
+    pub full_profile: SeccompProfile,
+
+    /// Enforce memory and pid limits through a cgroup v2 child group when the
+    /// current cgroup allows it (falls back to rlimits otherwise)
+    #[serde(default = "default_true")]
+    pub use_cgroup: bool,
+}
+
+fn default_seccomp_read_only_profile() -> SeccompProfile {
+    SeccompProfile::Strict
+}
+
+fn default_seccomp_supervised_profile() -> SeccompProfile {
+    SeccompProfile::Standard
+}
+
+fn default_seccomp_full_profile() -> SeccompProfile {
+    SeccompProfile::Permissive
+}
+
+impl Default for SeccompSandboxConfig {
+    fn default() -> Self {
+        Self {
+            read_only_profile: default_seccomp_read_only_profile(),
+            supervised_profile: default_seccomp_supervised_profile(),
+            full_profile: default_seccomp_full_profile(),
+            use_cgroup: true,
+        }
+    }
+}
+
+impl SeccompSandboxConfig {
+    /// Profile selected for the given autonomy level.
+    pub fn profile_for(&self, level: AutonomyLevel) -> SeccompProfile {
+        match level {
+            AutonomyLevel::ReadOnly => self.read_only_profile,
+            AutonomyLevel::Supervised => self.supervised_profile,
+            AutonomyLevel::Full => self.full_profile,
         }
     }
 }
@@ -7536,6 +8412,8 @@ pub enum SandboxBackend {
     Bubblewrap,
     /// Docker container isolation
     Docker,
+    /// Built-in seccomp-bpf filter with cgroup/rlimit resource limits
+    Seccomp,
     /// No sandboxing (application-layer only)
     None,
 }
@@ -7831,6 +8709,7 @@ impl Default for Config {
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
             goal_loop: GoalLoopConfig::default(),
+            webhooks: WebhooksConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -7852,6 +8731,8 @@ impl Default for Config {
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             coordination: CoordinationConfig::default(),
+            teams: HashMap::new(),
+            inbox: InboxConfig::default(),
             hooks: HooksConfig::default(),
             plugins: PluginsConfig::default(),
             hardware: HardwareConfig::default(),
@@ -8853,6 +9734,11 @@ impl Config {
                 &mut config.web_search.jina_api_key,
                 "config.web_search.jina_api_key",
             )?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.auth.oidc.client_secret,
+                "config.auth.oidc.client_secret",
+            )?;
 
             decrypt_optional_secret(
                 &store,
@@ -8874,6 +9760,22 @@ impl Config {
                 decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
             }
 
+            for device in &mut config.hardware.network_devices {
+                decrypt_optional_secret(
+                    &store,
+                    &mut device.psk,
+                    "config.hardware.network_devices.*.psk",
+                )?;
+            }
+
+            for endpoint in &mut config.webhooks.endpoints {
+                decrypt_optional_secret(
+                    &store,
+                    &mut endpoint.secret,
+                    "config.webhooks.endpoints.*.secret",
+                )?;
+            }
+
             decrypt_channel_secrets(&store, &mut config.channels_config)?;
 
             config.apply_env_overrides();
@@ -9809,6 +10711,175 @@ impl Config {
         if self.coordination.max_seen_message_ids == 0 {
             anyhow::bail!("coordination.max_seen_message_ids must be greater than 0");
         }
+        if !matches!(self.coordination.backend.as_str(), "memory" | "sqlite") {
+            anyhow::bail!(
+                "coordination.backend must be \"memory\" or \"sqlite\", got {:?}",
+                self.coordination.backend
+            );
+        }
+        if self.coordination.max_delivery_attempts == 0 {
+            anyhow::bail!("coordination.max_delivery_attempts must be greater than 0");
+        }
+
+        // Human task inbox escalation rules.
+        for (i, rule) in self.inbox.escalation.iter().enumerate() {
+            if rule.to.trim().is_empty() {
+                anyhow::bail!("inbox.escalation[{i}].to must not be empty");
+            }
+            if rule.after_secs == 0 {
+                anyhow::bail!("inbox.escalation[{i}].after_secs must be greater than 0");
+            }
+            if let Some(kind) = rule.kinds.iter().find(|kind| {
+                !matches!(
+                    kind.as_str(),
+                    "tool_approval" | "workflow_confirm" | "question" | "review"
+                )
+            }) {
+                anyhow::bail!("inbox.escalation[{i}].kinds has unknown kind {kind:?}");
+            }
+        }
+
+        // Goal loop schedule and budgets.
+        self.goal_loop
+            .active_window()
+            .context("goal_loop.active_hours is invalid")?;
+        if let Some(tz) = self.goal_loop.timezone.as_deref() {
+            if tz.parse::<chrono_tz::Tz>().is_err() {
+                anyhow::bail!("goal_loop.timezone {tz:?} is not a valid IANA timezone");
+            }
+        }
+        for (name, budget) in [
+            ("daily_budget_usd", self.goal_loop.daily_budget_usd),
+            ("goal_budget_usd", self.goal_loop.goal_budget_usd),
+        ] {
+            if budget.is_some_and(|usd| !usd.is_finite() || usd <= 0.0) {
+                anyhow::bail!("goal_loop.{name} must be a positive amount");
+            }
+        }
+
+        // Webhook endpoints and reactions.
+        if self.webhooks.replay_window_secs == 0 {
+            anyhow::bail!("webhooks.replay_window_secs must be greater than 0");
+        }
+        let mut endpoint_names = std::collections::HashSet::new();
+        for (i, endpoint) in self.webhooks.endpoints.iter().enumerate() {
+            let name = endpoint.name.trim();
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "webhooks.endpoints[{i}].name must be non-empty and use only letters, digits, '-' or '_'"
+                );
+            }
+            if !endpoint_names.insert(name) {
+                anyhow::bail!("webhooks.endpoints[{i}].name {name:?} is used more than once");
+            }
+            let has_secret = endpoint
+                .secret
+                .as_deref()
+                .is_some_and(|secret| !secret.trim().is_empty());
+            if endpoint.signature != WebhookSignature::None && !has_secret {
+                anyhow::bail!(
+                    "webhooks.endpoints[{i}].secret is required unless signature = \"none\""
+                );
+            }
+        }
+        let mut reaction_names = std::collections::HashSet::new();
+        for (i, reaction) in self.webhooks.reactions.iter().enumerate() {
+            let name = reaction.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("webhooks.reactions[{i}].name must not be empty");
+            }
+            if !reaction_names.insert(name) {
+                anyhow::bail!("webhooks.reactions[{i}].name {name:?} is used more than once");
+            }
+            if reaction.channel.is_some() != reaction.to.is_some() {
+                anyhow::bail!("webhooks.reactions[{i}] must set channel and to together");
+            }
+            if reaction.prompt.is_none() && reaction.channel.is_none() {
+                anyhow::bail!("webhooks.reactions[{i}] needs a prompt or a channel and to");
+            }
+        }
+
+        // Network hardware devices.
+        for (i, device) in self.hardware.network_devices.iter().enumerate() {
+            let address = device.address.trim();
+            if !["tcp://", "ws://", "wss://"]
+                .iter()
+                .any(|scheme| address.starts_with(scheme) && address.len() > scheme.len())
+            {
+                anyhow::bail!(
+                    "hardware.network_devices[{i}].address must start with tcp://, ws:// or wss://"
+                );
+            }
+            if device.board.trim().is_empty() {
+                anyhow::bail!("hardware.network_devices[{i}].board must not be empty");
+            }
+        }
+        for (i, device) in self.hardware.virtual_devices.iter().enumerate() {
+            if device.board.trim().is_empty() {
+                anyhow::bail!("hardware.virtual_devices[{i}].board must not be empty");
+            }
+            if let Some(pin) = device.pins.keys().find(|pin| pin.parse::<u8>().is_err()) {
+                anyhow::bail!("hardware.virtual_devices[{i}].pins key {pin:?} is not a pin number");
+            }
+            if !(0.0..=1.0).contains(&device.fault_rate) {
+                anyhow::bail!("hardware.virtual_devices[{i}].fault_rate must be between 0.0 and 1.0");
+            }
+            for (channel, waveform) in device.adc.iter().enumerate() {
+                let valid = match *waveform {
+                    WaveformConfig::Constant { .. } => true,
+                    WaveformConfig::Sine {
+                        min,
+                        max,
+                        period_ms,
+                    }
+                    | WaveformConfig::Square {
+                        min,
+                        max,
+                        period_ms,
+                    }
+                    | WaveformConfig::Ramp {
+                        min,
+                        max,
+                        period_ms,
+                    } => min <= max && period_ms > 0,
+                };
+                if !valid {
+                    anyhow::bail!(
+                        "hardware.virtual_devices[{i}].adc[{channel}] needs min <= max and period_ms > 0"
+                    );
+                }
+            }
+        }
+
+        if self.agent.cassette.mode != CassetteMode::Off
+            && self
+                .agent
+                .cassette
+                .path
+                .as_ref()
+                .is_none_or(|path| path.as_os_str().is_empty())
+        {
+            anyhow::bail!("agent.cassette.path is required when agent.cassette.mode is not \"off\"");
+        }
+
+        for (publisher, key) in &self.skills.trusted_publishers {
+            use base64::Engine;
+            let decoded = base64::engine::general_purpose::STANDARD.decode(key.trim());
+            if !matches!(decoded, Ok(ref bytes) if bytes.len() == 32) {
+                anyhow::bail!(
+                    "skills.trusted_publishers.{publisher} must be a base64 Ed25519 public key (32 bytes)"
+                );
+            }
+        }
+        if self.skills.require_signatures && self.skills.trusted_publishers.is_empty() {
+            anyhow::bail!(
+                "skills.require_signatures needs at least one skills.trusted_publishers entry"
+            );
+        }
 
         // WASM config
         if self.wasm.memory_limit_mb == 0 || self.wasm.memory_limit_mb > 256 {
@@ -11362,6 +12433,11 @@ impl Config {
             &mut config_to_save.web_search.jina_api_key,
             "config.web_search.jina_api_key",
         )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.auth.oidc.client_secret,
+            "config.auth.oidc.client_secret",
+        )?;
 
         encrypt_optional_secret(
             &store,
@@ -11383,6 +12459,22 @@ impl Config {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
 
+        for device in &mut config_to_save.hardware.network_devices {
+            encrypt_optional_secret(
+                &store,
+                &mut device.psk,
+                "config.hardware.network_devices.*.psk",
+            )?;
+        }
+
+        for endpoint in &mut config_to_save.webhooks.endpoints {
+            encrypt_optional_secret(
+                &store,
+                &mut endpoint.secret,
+                "config.webhooks.endpoints.*.secret",
+            )?;
+        }
+
         encrypt_channel_secrets(&store, &mut config_to_save.channels_config)?;
 
         let toml_str =
@@ -12078,6 +13170,8 @@ ws_url = "ws://127.0.0.1:3002"
             reliability: ReliabilityConfig::default(),
             scheduler: SchedulerConfig::default(),
             coordination: CoordinationConfig::default(),
+            teams: HashMap::new(),
+            inbox: InboxConfig::default(),
             skills: SkillsConfig::default(),
             plugins: PluginsConfig::default(),
             model_routes: Vec::new(),
@@ -12092,6 +13186,7 @@ ws_url = "ws://127.0.0.1:3002"
             },
             cron: CronConfig::default(),
             goal_loop: GoalLoopConfig::default(),
+            webhooks: WebhooksConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
                 acp: None,
@@ -12519,6 +13614,8 @@ tool_dispatcher = "xml"
             reliability: ReliabilityConfig::default(),
             scheduler: SchedulerConfig::default(),
             coordination: CoordinationConfig::default(),
+            teams: HashMap::new(),
+            inbox: InboxConfig::default(),
             skills: SkillsConfig::default(),
             plugins: PluginsConfig::default(),
             model_routes: Vec::new(),
@@ -12527,6 +13624,7 @@ tool_dispatcher = "xml"
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
             goal_loop: GoalLoopConfig::default(),
+            webhooks: WebhooksConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -16400,6 +17498,9 @@ sensitivity = 0.9
         assert_eq!(config.coordination.max_dead_letters, 256);
         assert_eq!(config.coordination.max_context_entries, 512);
         assert_eq!(config.coordination.max_seen_message_ids, 4096);
+        assert_eq!(config.coordination.backend, "memory");
+        assert_eq!(config.coordination.ack_timeout_secs, 300);
+        assert_eq!(config.coordination.max_delivery_attempts, 5);
     }
 
     #[test]
@@ -16456,6 +17557,13 @@ sensitivity = 0.9
             .to_string()
             .contains("coordination.max_seen_message_ids"));
 
+        let mut config = Config::default();
+        config.coordination.backend = "redis".into();
+        let err = config
+            .validate()
+            .expect_err("expected coordination backend validation failure");
+        assert!(err.to_string().contains("coordination.backend"));
+
         let mut config = Config::default();
         config.coordination.lead_agent = "   ".into();
         let err = config
@@ -16464,6 +17572,220 @@ sensitivity = 0.9
         assert!(err.to_string().contains("coordination.lead_agent"));
     }
 
+    #[test]
+    async fn inbox_escalation_rules_are_validated() {
+        let mut config = Config::default();
+        config.inbox.escalation.push(InboxEscalationRule {
+            kinds: vec!["question".into()],
+            after_secs: 600,
+            to: "telegram:42".into(),
+        });
+        config.validate().expect("valid escalation rule");
+
+        config.inbox.escalation[0].kinds.push("meeting".into());
+        let err = config
+            .validate()
+            .expect_err("expected unknown escalation kind failure");
+        assert!(err.to_string().contains("inbox.escalation[0].kinds"));
+
+        config.inbox.escalation[0].kinds.clear();
+        config.inbox.escalation[0].to = " ".into();
+        let err = config
+            .validate()
+            .expect_err("expected empty escalation target failure");
+        assert!(err.to_string().contains("inbox.escalation[0].to"));
+    }
+
+    #[test]
+    async fn goal_loop_schedule_and_budgets_are_validated() {
+        let mut config = Config::default();
+        config.goal_loop.active_hours = Some("22:00-06:30".into());
+        config.goal_loop.timezone = Some("Europe/Berlin".into());
+        config.goal_loop.daily_budget_usd = Some(2.5);
+        config.validate().expect("valid goal loop settings");
+
+        config.goal_loop.active_hours = Some("9am-5pm".into());
+        let err = config
+            .validate()
+            .expect_err("expected active hours validation failure");
+        assert!(err.to_string().contains("goal_loop.active_hours"));
+
+        config.goal_loop.active_hours = None;
+        config.goal_loop.timezone = Some("Mars/Olympus".into());
+        let err = config
+            .validate()
+            .expect_err("expected timezone validation failure");
+        assert!(err.to_string().contains("goal_loop.timezone"));
+
+        config.goal_loop.timezone = None;
+        config.goal_loop.goal_budget_usd = Some(0.0);
+        let err = config
+            .validate()
+            .expect_err("expected goal budget validation failure");
+        assert!(err.to_string().contains("goal_loop.goal_budget_usd"));
+    }
+
+    #[test]
+    async fn hardware_network_devices_parse_and_validate() {
+        let mut config = Config::default();
+        config.hardware = toml::from_str(
+            r#"
+enabled = true
+
+[[network_devices]]
+address = "tcp://192.168.1.40:7777"
+psk = "s3cret"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.hardware.network_devices[0].board, "esp32");
+        assert!(config.validate().is_ok());
+
+        config.hardware.network_devices[0].address = "192.168.1.40:7777".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("network_devices[0].address"), "{err}");
+    }
+
+    #[test]
+    async fn hardware_virtual_devices_parse_and_validate() {
+        let mut config = Config::default();
+        config.hardware = toml::from_str(
+            r#"
+enabled = true
+
+[[virtual_devices]]
+pins = { "9" = 1 }
+i2c_addresses = [0x3c]
+latency_ms = 5
+
+[[virtual_devices.adc]]
+shape = "sine"
+min = 0
+max = 4095
+period_ms = 1000
+"#,
+        )
+        .unwrap();
+        let device = &config.hardware.virtual_devices[0];
+        assert_eq!(device.board, "sim");
+        assert_eq!(device.gpio.len(), 14);
+        assert_eq!(
+            device.adc[0],
+            WaveformConfig::Sine {
+                min: 0,
+                max: 4095,
+                period_ms: 1000
+            }
+        );
+        assert!(config.validate().is_ok());
+
+        config.hardware.virtual_devices[0].fault_rate = 1.5;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("virtual_devices[0].fault_rate"), "{err}");
+
+        config.hardware.virtual_devices[0].fault_rate = 0.0;
+        config.hardware.virtual_devices[0]
+            .pins
+            .insert("led".into(), 1);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("virtual_devices[0].pins"), "{err}");
+    }
+
+    #[test]
+    async fn agent_cassette_parses_and_requires_path() {
+        let mut config = Config::default();
+        assert_eq!(config.agent.cassette.mode, CassetteMode::Off);
+        config.agent = toml::from_str(
+            r#"
+[cassette]
+mode = "replay"
+path = "bug-report.json"
+matching = "fuzzy"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.agent.cassette.mode, CassetteMode::Replay);
+        assert_eq!(config.agent.cassette.matching, CassetteMatch::Fuzzy);
+        assert!(config.validate().is_ok());
+
+        config.agent.cassette.path = None;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("agent.cassette.path"), "{err}");
+    }
+
+    #[test]
+    async fn skills_trusted_publishers_are_validated() {
+        let mut config = Config::default();
+        config.skills = toml::from_str(
+            r#"
+require_signatures = true
+
+[trusted_publishers]
+acme = "O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik="
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        config
+            .skills
+            .trusted_publishers
+            .insert("broken".into(), "not-a-key".into());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("skills.trusted_publishers.broken"), "{err}");
+
+        config.skills.trusted_publishers.clear();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("skills.require_signatures"), "{err}");
+    }
+
+    #[test]
+    async fn webhook_endpoints_and_reactions_are_validated() {
+        let mut config = Config::default();
+        config.webhooks = toml::from_str(
+            r#"
+enabled = true
+
+[[endpoints]]
+name = "github"
+signature = "github"
+secret = "s3cret"
+topic = "github/{header:X-GitHub-Event}"
+
+[[reactions]]
+name = "push-alert"
+topic_prefix = "github/push"
+channel = "telegram"
+to = "12345"
+"#,
+        )
+        .unwrap();
+        config.validate().expect("valid webhook settings");
+        assert_eq!(config.webhooks.replay_window_secs, 300);
+        assert_eq!(
+            config.webhooks.endpoints[0].signature,
+            WebhookSignature::Github
+        );
+
+        config.webhooks.endpoints[0].secret = None;
+        let err = config.validate().expect_err("expected missing secret");
+        assert!(err.to_string().contains("webhooks.endpoints[0].secret"));
+
+        config.webhooks.endpoints[0].signature = WebhookSignature::None;
+        config
+            .validate()
+            .expect("unsigned endpoint needs no secret");
+
+        config.webhooks.endpoints[0].name = "git hub".into();
+        let err = config.validate().expect_err("expected bad endpoint name");
+        assert!(err.to_string().contains("webhooks.endpoints[0].name"));
+
+        config.webhooks.endpoints[0].name = "github".into();
+        config.webhooks.reactions[0].to = None;
+        let err = config.validate().expect_err("expected lone channel");
+        assert!(err.to_string().contains("webhooks.reactions[0]"));
+    }
+
     #[test]
     async fn coordination_validation_allows_empty_lead_agent_when_disabled() {
         let mut config = Config::default();
@@ -16949,7 +18271,8 @@ pub struct VoiceConfig {
     /// Default voice provider: "gemini", "openai", or "deepgram" (default: "gemini").
     #[serde(default)]
     pub default_provider: Option<String>,
-    /// Default STT provider: "deepgram", "gemini", or "openai" (default: "deepgram").
+    /// Default STT provider: "deepgram", "gemini", "openai", or "local"
+    /// (offline engine from `[transcription.local]`; default: "deepgram").
     /// Used for chat voice input and standalone speech-to-text.
     #[serde(default = "default_stt_provider")]
     pub stt_provider: String,
@@ -16973,6 +18296,16 @@ pub struct VoiceConfig {
     /// Milliseconds of silence before committing remaining stable text.
     #[serde(default = "default_voice_silence_commit_ms")]
     pub silence_commit_ms: u64,
+
+    // ── Conference rooms ──────────────────────────────────────────
+    /// Maximum concurrent conference rooms (sessions started with a `roomId`).
+    #[serde(default = "default_voice_max_conference_rooms")]
+    pub max_conference_rooms: usize,
+    /// Keep a diarized meeting record for every conference room and archive
+    /// it with minutes to `meetings/`, memory and the ontology when the room
+    /// closes (default: false).
+    #[serde(default)]
+    pub record_meetings: bool,
 }
 
 fn default_voice_max_sessions() -> usize {
@@ -17002,6 +18335,9 @@ fn default_voice_max_uncommitted_chars() -> usize {
 fn default_voice_silence_commit_ms() -> u64 {
     600
 }
+fn default_voice_max_conference_rooms() -> usize {
+    10
+}
 fn default_deepgram_model() -> String {
     "nova-3".to_string()
 }
@@ -17028,6 +18364,8 @@ impl Default for VoiceConfig {
             min_commit_chars: default_voice_min_commit_chars(),
             max_uncommitted_chars: default_voice_max_uncommitted_chars(),
             silence_commit_ms: default_voice_silence_commit_ms(),
+            max_conference_rooms: default_voice_max_conference_rooms(),
+            record_meetings: false,
         }
     }
 }
@@ -17330,9 +18668,115 @@ pub struct AuthConfig {
     /// Maximum registered users (0 = unlimited, default: 0).
     #[serde(default)]
     pub max_users: u64,
+    /// Give every user their own memory, vault, ontology and session storage
+    /// under `{workspace}/tenants/{user_id}/` (default: false). Requests are
+    /// attributed by login session; channel messages by linked identity.
+    #[serde(default)]
+    pub tenant_isolation: bool,
     /// Email verification for remote device access (`[auth.email_verification]`).
     #[serde(default)]
     pub email_verification: EmailVerificationConfig,
+    /// OpenID Connect single sign-on (`[auth.oidc]`).
+    #[serde(default)]
+    pub oidc: OidcConfig,
+}
+
+/// OpenID Connect relying-party configuration for dashboard and API sign-in.
+///
+/// Users authenticate at the identity provider (authorization code + PKCE),
+/// are provisioned into the `users` table on first login, and get the role
+/// their groups map to.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OidcConfig {
+    /// Enable OIDC sign-in (default: false). Requires `auth.enabled`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Issuer URL. Discovery is read from `{issuer}/.well-known/openid-configuration`.
+    #[serde(default)]
+    pub issuer: String,
+    /// Client ID registered with the identity provider.
+    #[serde(default)]
+    pub client_id: String,
+    /// Client secret for confidential clients. Public clients rely on PKCE alone.
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Redirect URI registered with the identity provider.
+    /// Default: `{ZEROCLAW_PUBLIC_URL}/api/auth/oidc/callback`, or the local
+    /// gateway address when that is unset.
+    #[serde(default)]
+    pub redirect_url: Option<String>,
+    /// Scopes to request (default: `openid`, `profile`, `email`, `groups`).
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: Vec<String>,
+    /// ID token claim listing the user's groups (default: `groups`).
+    /// Dots address nested claims, e.g. `realm_access.roles`.
+    #[serde(default = "default_oidc_groups_claim")]
+    pub groups_claim: String,
+    /// ID token claim used as the local username (default: `preferred_username`).
+    /// Falls back to `email`, then `sub`.
+    #[serde(default = "default_oidc_username_claim")]
+    pub username_claim: String,
+    /// Group to role mappings, checked in order. The first match wins.
+    #[serde(default)]
+    pub role_mappings: Vec<OidcRoleMapping>,
+    /// Role for users whose groups match no mapping (default: `viewer`).
+    /// Unset to refuse sign-in to users outside the mapped groups.
+    #[serde(default = "default_oidc_default_role")]
+    pub default_role: Option<String>,
+    /// Allowed clock skew when checking token timestamps (default: 60 seconds).
+    #[serde(default = "default_oidc_clock_skew_secs")]
+    pub clock_skew_secs: u64,
+}
+
+/// Maps an identity provider group onto a `[security.roles]` role.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct OidcRoleMapping {
+    /// Group name as it appears in the groups claim.
+    pub group: String,
+    /// Built-in (`owner`, `admin`, `operator`, `viewer`, `guest`) or custom role name.
+    pub role: String,
+}
+
+fn default_oidc_scopes() -> Vec<String> {
+    ["openid", "profile", "email", "groups"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_oidc_groups_claim() -> String {
+    "groups".to_string()
+}
+
+fn default_oidc_username_claim() -> String {
+    "preferred_username".to_string()
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn default_oidc_default_role() -> Option<String> {
+    Some("viewer".to_string())
+}
+
+fn default_oidc_clock_skew_secs() -> u64 {
+    60
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            issuer: String::new(),
+            client_id: String::new(),
+            client_secret: None,
+            redirect_url: None,
+            scopes: default_oidc_scopes(),
+            groups_claim: default_oidc_groups_claim(),
+            username_claim: default_oidc_username_claim(),
+            role_mappings: Vec::new(),
+            default_role: default_oidc_default_role(),
+            clock_skew_secs: default_oidc_clock_skew_secs(),
+        }
+    }
 }
 
 /// Email-based verification code configuration for remote device access.
@@ -17425,7 +18869,9 @@ impl Default for AuthConfig {
             session_ttl_secs: default_session_ttl_secs(),
             max_devices_per_user: default_max_devices_per_user(),
             max_users: 0,
+            tenant_isolation: false,
             email_verification: EmailVerificationConfig::default(),
+            oidc: OidcConfig::default(),
         }
     }
 }
@@ -1,7 +1,12 @@
+mod store;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use store::{BusStore, Snapshot};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -234,6 +239,10 @@ pub enum CoordinationError {
         expected: u64,
         actual: u64,
     },
+    #[error("no dead letter for message `{message_id}`")]
+    UnknownDeadLetter { message_id: String },
+    #[error("coordination store failed to {what}: {reason}")]
+    Journal { what: String, reason: String },
 }
 
 /// Sequenced message emitted by the bus.
@@ -267,16 +276,16 @@ pub struct PublishReceipt {
     pub delivered_to: usize,
 }
 
-/// Capacity limits used by `InMemoryMessageBus` retention policies.
+/// Capacity limits used by `MessageBus` retention policies.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-pub struct InMemoryMessageBusLimits {
+pub struct MessageBusLimits {
     pub max_inbox_messages_per_agent: usize,
     pub max_dead_letters: usize,
     pub max_context_entries: usize,
     pub max_seen_message_ids: usize,
 }
 
-impl Default for InMemoryMessageBusLimits {
+impl Default for MessageBusLimits {
     fn default() -> Self {
         Self {
             max_inbox_messages_per_agent: 256,
@@ -287,9 +296,29 @@ impl Default for InMemoryMessageBusLimits {
     }
 }
 
+/// Redelivery rules for envelopes handed out by
+/// [`MessageBus::receive_for_agent`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveryPolicy {
+    /// Seconds a received envelope may stay unacknowledged before it is
+    /// put back at the front of the inbox.
+    pub ack_timeout_secs: u64,
+    /// Deliveries of one envelope before it moves to the dead letters.
+    pub max_delivery_attempts: u32,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            ack_timeout_secs: 300,
+            max_delivery_attempts: 5,
+        }
+    }
+}
+
 /// Runtime counters for operational visibility.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
-pub struct InMemoryMessageBusStats {
+pub struct MessageBusStats {
     /// Total publish attempts that passed envelope validation.
     pub publish_attempts_total: u64,
     /// Total successful deliveries (fan-out count for broadcast).
@@ -304,6 +333,15 @@ pub struct InMemoryMessageBusStats {
     pub context_evictions_total: u64,
     /// Number of idempotency IDs evicted due to dedupe-window capacity limits.
     pub seen_message_id_evictions_total: u64,
+    /// Envelopes put back into an inbox after their ack timeout expired.
+    pub redeliveries_total: u64,
+}
+
+/// Envelope handed to an agent and not yet acknowledged.
+#[derive(Debug)]
+struct InFlight {
+    entry: SequencedEnvelope,
+    delivered_at: Instant,
 }
 
 #[derive(Debug, Default)]
@@ -321,12 +359,21 @@ struct BusState {
     context_order_by_correlation: HashMap<String, VecDeque<String>>,
     delegate_context_order_by_correlation: HashMap<String, VecDeque<String>>,
     context_correlation_by_key: HashMap<String, String>,
-    limits: InMemoryMessageBusLimits,
-    stats: InMemoryMessageBusStats,
+    in_flight: HashMap<String, BTreeMap<u64, InFlight>>,
+    delivery_attempts: HashMap<(String, u64), u32>,
+    delivery: DeliveryPolicy,
+    limits: MessageBusLimits,
+    stats: MessageBusStats,
+    store: Option<BusStore>,
+    /// Inside [`BusState::journaled`]: writes join its transaction and the
+    /// first failure is kept in `journal_error` instead of being logged.
+    in_transaction: bool,
+    journal_error: Option<String>,
+    redelivery_started: bool,
 }
 
 impl BusState {
-    fn with_limits(mut limits: InMemoryMessageBusLimits) -> Self {
+    fn with_limits(mut limits: MessageBusLimits) -> Self {
         if limits.max_inbox_messages_per_agent == 0 {
             limits.max_inbox_messages_per_agent = 1;
         }
@@ -354,48 +401,253 @@ impl BusState {
             context_order_by_correlation: HashMap::new(),
             delegate_context_order_by_correlation: HashMap::new(),
             context_correlation_by_key: HashMap::new(),
+            in_flight: HashMap::new(),
+            delivery_attempts: HashMap::new(),
+            delivery: DeliveryPolicy::default(),
             limits,
-            stats: InMemoryMessageBusStats::default(),
+            stats: MessageBusStats::default(),
+            store: None,
+            in_transaction: false,
+            journal_error: None,
+            redelivery_started: false,
+        }
+    }
+
+    /// Rebuild state read back from a journal. Runs before the store is
+    /// attached, so nothing is written back.
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.next_sequence = snapshot.next_sequence;
+        for agent in snapshot.agents {
+            self.inboxes.entry(agent.clone()).or_default();
+            self.inbox_correlation_counts.entry(agent).or_default();
+        }
+        for (agent, sequence, envelope, attempts) in snapshot.inbox {
+            if !self.inboxes.contains_key(&agent) {
+                continue;
+            }
+            if attempts > 0 {
+                self.delivery_attempts
+                    .insert((agent.clone(), sequence), attempts);
+            }
+            push_inbox_entry_locked(self, &agent, SequencedEnvelope { sequence, envelope });
+        }
+        for dead_letter in snapshot.dead_letters {
+            if let Some(correlation_id) =
+                normalized_non_empty(dead_letter.envelope.correlation_id.as_deref())
+            {
+                self.dead_letters_by_correlation
+                    .entry(correlation_id.to_string())
+                    .or_default()
+                    .push_back(dead_letter.clone());
+            }
+            self.dead_letters.push(dead_letter);
+        }
+        for (entry, correlation_id) in snapshot.context {
+            let key = entry.key.clone();
+            let is_delegate = key.starts_with("delegate/");
+            self.context_order.push_back(key.clone());
+            if is_delegate {
+                self.delegate_context_order.push_back(key.clone());
+            }
+            if let Some(correlation_id) = correlation_id {
+                self.context_order_by_correlation
+                    .entry(correlation_id.clone())
+                    .or_default()
+                    .push_back(key.clone());
+                if is_delegate {
+                    self.delegate_context_order_by_correlation
+                        .entry(correlation_id.clone())
+                        .or_default()
+                        .push_back(key.clone());
+                }
+                self.context_correlation_by_key
+                    .insert(key.clone(), correlation_id);
+            }
+            self.context.insert(key, entry);
+        }
+        for message_id in snapshot.seen_message_ids {
+            if self.seen_message_ids.insert(message_id.clone()) {
+                self.seen_message_order.push_back(message_id);
+            }
+        }
+    }
+
+    /// Write a change through to the journal, if there is one. Outside
+    /// [`Self::journaled`] a failed write is logged and the in-memory state
+    /// stays authoritative.
+    fn persist(&mut self, what: &str, write: impl FnOnce(&BusStore) -> anyhow::Result<()>) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        if self.in_transaction {
+            if self.journal_error.is_none() {
+                if let Err(error) = write(store) {
+                    self.journal_error = Some(format!("{what}: {error:#}"));
+                }
+            }
+        } else if let Err(error) = write(store) {
+            tracing::error!("coordination store: failed to {what}: {error:#}");
+        }
+    }
+
+    /// Apply `change` with all of its journal writes in one SQLite
+    /// transaction. If any write fails the transaction is rolled back, the
+    /// in-memory state is reloaded from the journal so both agree again,
+    /// and the failure is returned instead of `change`'s result.
+    fn journaled<T>(
+        &mut self,
+        what: &str,
+        change: impl FnOnce(&mut Self) -> Result<T, CoordinationError>,
+    ) -> Result<T, CoordinationError> {
+        let Some(store) = &self.store else {
+            return change(self);
+        };
+        let journal_failure = |reason: String| CoordinationError::Journal {
+            what: what.to_string(),
+            reason,
+        };
+        store
+            .begin()
+            .map_err(|error| journal_failure(format!("{error:#}")))?;
+
+        self.in_transaction = true;
+        let result = change(self);
+        self.in_transaction = false;
+
+        let Some(store) = &self.store else {
+            return result;
+        };
+        let reason = match self.journal_error.take() {
+            Some(reason) => reason,
+            None => match store.commit() {
+                Ok(()) => return result,
+                Err(error) => format!("{error:#}"),
+            },
+        };
+        if let Err(error) = store.rollback() {
+            tracing::error!("coordination store: failed to roll back: {error:#}");
         }
+        self.reload_from_journal();
+        Err(journal_failure(reason))
+    }
+
+    /// Replace the in-memory inboxes, dead letters, context and dedupe
+    /// window with what the journal holds. Envelopes that were in flight go
+    /// back to their inboxes and are delivered again.
+    fn reload_from_journal(&mut self) {
+        let Some(store) = self.store.take() else {
+            return;
+        };
+        match store.load() {
+            Ok(snapshot) => {
+                let mut fresh = Self::with_limits(self.limits);
+                fresh.restore(snapshot);
+                fresh.delivery = self.delivery;
+                fresh.stats = self.stats;
+                fresh.redelivery_started = self.redelivery_started;
+                *self = fresh;
+            }
+            Err(error) => {
+                tracing::error!("coordination store: failed to reload after a rollback: {error:#}");
+            }
+        }
+        self.store = Some(store);
     }
 }
 
-/// Deterministic in-memory coordination message bus with:
+/// Longest pause between two background redelivery sweeps.
+pub const REDELIVERY_SWEEP_MAX_SECS: u64 = 30;
+
+/// Persistent buses opened in this process, by journal path, so every
+/// tool registry built from one config shares a single bus.
+static PERSISTENT_BUSES: LazyLock<Mutex<HashMap<PathBuf, MessageBus>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Deterministic coordination message bus, served from memory, with:
 /// - typed envelope validation
 /// - idempotency guard on message id
 /// - per-agent ordered delivery
-/// - dead-letter retention for invalid/conflicting messages
+/// - at-least-once delivery with acknowledgements and redelivery
+/// - dead-letter retention and replay for invalid/conflicting messages
 /// - optimistic-locking context patches
+///
+/// A bus opened with [`MessageBus::open_persistent`] journals every
+/// change to SQLite and restores it on the next start, so pending work
+/// survives a restart.
 #[derive(Debug, Clone)]
-pub struct InMemoryMessageBus {
+pub struct MessageBus {
     inner: Arc<Mutex<BusState>>,
 }
 
-impl Default for InMemoryMessageBus {
+impl Default for MessageBus {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl InMemoryMessageBus {
+impl MessageBus {
     pub fn new() -> Self {
-        Self::with_limits(InMemoryMessageBusLimits::default())
+        Self::with_limits(MessageBusLimits::default())
     }
 
-    pub fn with_limits(limits: InMemoryMessageBusLimits) -> Self {
+    pub fn with_limits(limits: MessageBusLimits) -> Self {
         Self {
             inner: Arc::new(Mutex::new(BusState::with_limits(limits))),
         }
     }
 
+    /// Open the bus journaled at `path`, restoring its registered agents,
+    /// unacknowledged envelopes, dead letters, shared context and dedupe
+    /// window. Envelopes that were received but not acknowledged before the
+    /// restart are delivered again. Opening the same path twice in one
+    /// process returns the same bus.
+    pub fn open_persistent(path: &Path, limits: MessageBusLimits) -> anyhow::Result<Self> {
+        let mut buses = PERSISTENT_BUSES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(bus) = buses.get(path) {
+            return Ok(bus.clone());
+        }
+        let bus = Self::load_persistent(path, limits)?;
+        buses.insert(path.to_path_buf(), bus.clone());
+        Ok(bus)
+    }
+
+    fn load_persistent(path: &Path, limits: MessageBusLimits) -> anyhow::Result<Self> {
+        let (store, snapshot) = BusStore::open(path, limits)?;
+        let mut state = BusState::with_limits(limits);
+        state.restore(snapshot);
+        state.store = Some(store);
+        Ok(Self {
+            inner: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    pub fn delivery_policy(&self) -> DeliveryPolicy {
+        self.lock_state().delivery
+    }
+
+    pub fn set_delivery_policy(&self, policy: DeliveryPolicy) {
+        self.lock_state().delivery = DeliveryPolicy {
+            max_delivery_attempts: policy.max_delivery_attempts.max(1),
+            ..policy
+        };
+    }
+
     /// Register an agent inbox.
     pub fn register_agent(&self, agent: impl Into<String>) -> Result<(), CoordinationError> {
         let agent = agent.into();
         require_non_empty(&agent, "agent")?;
         let mut state = self.lock_state();
-        state.inboxes.entry(agent.clone()).or_default();
-        state.inbox_correlation_counts.entry(agent).or_default();
-        Ok(())
+        state.journaled("register agent", |state| {
+            state.inboxes.entry(agent.clone()).or_default();
+            state
+                .inbox_correlation_counts
+                .entry(agent.clone())
+                .or_default();
+            state.persist("register agent", |store| store.insert_agent(&agent));
+            Ok(())
+        })
     }
 
     /// Remove an existing agent inbox.
@@ -403,6 +655,11 @@ impl InMemoryMessageBus {
         let mut state = self.lock_state();
         let removed = state.inboxes.remove(agent).is_some();
         state.inbox_correlation_counts.remove(agent);
+        state.in_flight.remove(agent);
+        state
+            .delivery_attempts
+            .retain(|(owner, _), _| owner != agent);
+        state.persist("unregister agent", |store| store.delete_agent(agent));
         removed
     }
 
@@ -417,100 +674,7 @@ impl InMemoryMessageBus {
         }
 
         let mut state = self.lock_state();
-        state.stats.publish_attempts_total += 1;
-        if state.seen_message_ids.contains(&envelope.id) {
-            let error = CoordinationError::DuplicateMessageId {
-                message_id: envelope.id.clone(),
-            };
-            push_dead_letter_locked(&mut state, envelope, error.to_string());
-            return Err(error);
-        }
-        if state.seen_message_ids.len() >= state.limits.max_seen_message_ids {
-            if let Some(evicted_id) = state.seen_message_order.pop_front() {
-                if state.seen_message_ids.remove(&evicted_id) {
-                    state.stats.seen_message_id_evictions_total += 1;
-                }
-            }
-        }
-        state.seen_message_ids.insert(envelope.id.clone());
-        state.seen_message_order.push_back(envelope.id.clone());
-
-        if let CoordinationPayload::ContextPatch {
-            key,
-            expected_version,
-            value,
-        } = &envelope.payload
-        {
-            if let Err(error) =
-                apply_context_patch_locked(&mut state, &envelope, key, *expected_version, value)
-            {
-                push_dead_letter_locked(&mut state, envelope, error.to_string());
-                return Err(error);
-            }
-        }
-
-        state.next_sequence += 1;
-        let sequence = state.next_sequence;
-        let sequenced = SequencedEnvelope {
-            sequence,
-            envelope: envelope.clone(),
-        };
-
-        let delivered_to = match envelope.scope {
-            DeliveryScope::Direct => {
-                let target = envelope.to.as_deref().expect("validated direct target");
-                if !state.inboxes.contains_key(target) {
-                    let error = CoordinationError::UnknownTarget {
-                        agent: target.to_string(),
-                        message_id: envelope.id.clone(),
-                    };
-                    push_dead_letter_locked(&mut state, envelope, error.to_string());
-                    return Err(error);
-                }
-
-                let dropped = push_inbox_entry_locked(&mut state, target, sequenced);
-                if let Some(dropped) = dropped {
-                    state.stats.inbox_overflow_evictions_total += 1;
-                    push_dead_letter_locked(
-                        &mut state,
-                        dropped,
-                        format!("inbox overflow: dropped oldest message for agent '{target}'"),
-                    );
-                }
-                1
-            }
-            DeliveryScope::Broadcast => {
-                if state.inboxes.is_empty() {
-                    0
-                } else {
-                    let fanout = state.inboxes.len();
-                    let mut dropped_items: Vec<(String, CoordinationEnvelope)> = Vec::new();
-                    let agents = state.inboxes.keys().cloned().collect::<Vec<_>>();
-                    for agent in &agents {
-                        if let Some(dropped) =
-                            push_inbox_entry_locked(&mut state, agent, sequenced.clone())
-                        {
-                            dropped_items.push((agent.clone(), dropped));
-                        }
-                    }
-                    for (agent, dropped) in dropped_items {
-                        state.stats.inbox_overflow_evictions_total += 1;
-                        push_dead_letter_locked(
-                            &mut state,
-                            dropped,
-                            format!("inbox overflow: dropped oldest message for agent '{agent}'"),
-                        );
-                    }
-                    fanout
-                }
-            }
-        };
-        state.stats.deliveries_total += delivered_to as u64;
-
-        Ok(PublishReceipt {
-            sequence,
-            delivered_to,
-        })
+        state.journaled("publish message", |state| publish_locked(state, envelope))
     }
 
     /// Drain up to `max` pending envelopes for an agent inbox.
@@ -548,12 +712,235 @@ impl InMemoryMessageBus {
                     .entry(agent_owned.clone())
                     .or_default();
                 decrement_correlation_count(correlation_counts, &envelope.envelope);
+                state
+                    .delivery_attempts
+                    .remove(&(agent_owned.clone(), envelope.sequence));
+                state.persist("remove drained envelope", |store| {
+                    store.delete_inbox(agent, envelope.sequence)
+                });
                 drained.push(envelope);
             }
         }
         Ok(drained)
     }
 
+    /// Hand up to `max` pending envelopes to an agent without consuming
+    /// them. Each must be confirmed with [`Self::ack`]; envelopes still
+    /// unacknowledged after the ack timeout are delivered again, and after
+    /// `max_delivery_attempts` deliveries they move to the dead letters.
+    /// Use `max = 0` to receive all pending envelopes.
+    pub fn receive_for_agent(
+        &self,
+        agent: &str,
+        max: usize,
+    ) -> Result<Vec<SequencedEnvelope>, CoordinationError> {
+        self.receive_matching(agent, max, |_| true)
+    }
+
+    /// Receive one specific envelope, identified by its message ID, from an
+    /// agent inbox and leave everything else pending. Consumers that share
+    /// an inbox use this to pick up only their own work. The envelope must
+    /// be confirmed with [`Self::ack`] like any other received envelope.
+    pub fn receive_message(
+        &self,
+        agent: &str,
+        message_id: &str,
+    ) -> Result<Option<SequencedEnvelope>, CoordinationError> {
+        let mut received =
+            self.receive_matching(agent, 1, |entry| entry.envelope.id == message_id)?;
+        Ok(received.pop())
+    }
+
+    fn receive_matching(
+        &self,
+        agent: &str,
+        max: usize,
+        matches: impl Fn(&SequencedEnvelope) -> bool,
+    ) -> Result<Vec<SequencedEnvelope>, CoordinationError> {
+        let mut state = self.lock_state();
+        if !state.inboxes.contains_key(agent) {
+            return Err(CoordinationError::UnknownAgent {
+                agent: agent.to_string(),
+            });
+        }
+        requeue_expired_locked(&mut state, agent, Instant::now());
+
+        let taken: Vec<SequencedEnvelope> = {
+            let inbox = state
+                .inboxes
+                .get_mut(agent)
+                .expect("agent existence should be validated before receive");
+            let limit = if max == 0 { inbox.len() } else { max };
+            let mut taken = Vec::new();
+            let mut index = 0;
+            while index < inbox.len() && taken.len() < limit {
+                if matches(&inbox[index]) {
+                    taken.extend(inbox.remove(index));
+                } else {
+                    index += 1;
+                }
+            }
+            taken
+        };
+
+        let mut received = Vec::with_capacity(taken.len());
+        for entry in taken {
+            if let Some(counts) = state.inbox_correlation_counts.get_mut(agent) {
+                decrement_correlation_count(counts, &entry.envelope);
+            }
+            let attempts = state
+                .delivery_attempts
+                .entry((agent.to_string(), entry.sequence))
+                .or_insert(0);
+            *attempts += 1;
+            let attempts = *attempts;
+            state.persist("record delivery attempt", |store| {
+                store.set_attempts(agent, entry.sequence, attempts)
+            });
+            received.push(entry.clone());
+            state
+                .in_flight
+                .entry(agent.to_string())
+                .or_default()
+                .insert(
+                    entry.sequence,
+                    InFlight {
+                        entry,
+                        delivered_at: Instant::now(),
+                    },
+                );
+        }
+        Ok(received)
+    }
+
+    /// Confirm that an agent finished with a received envelope. Returns
+    /// `false` if the envelope was not awaiting acknowledgement.
+    pub fn ack(&self, agent: &str, sequence: u64) -> Result<bool, CoordinationError> {
+        let mut state = self.lock_state();
+        if !state.inboxes.contains_key(agent) {
+            return Err(CoordinationError::UnknownAgent {
+                agent: agent.to_string(),
+            });
+        }
+        let acked = state
+            .in_flight
+            .get_mut(agent)
+            .and_then(|in_flight| in_flight.remove(&sequence))
+            .is_some();
+        if acked {
+            state
+                .delivery_attempts
+                .remove(&(agent.to_string(), sequence));
+            state.persist("remove acknowledged envelope", |store| {
+                store.delete_inbox(agent, sequence)
+            });
+        }
+        Ok(acked)
+    }
+
+    /// Number of envelopes an agent has received but not acknowledged.
+    pub fn in_flight_for_agent(&self, agent: &str) -> Result<usize, CoordinationError> {
+        let state = self.lock_state();
+        if !state.inboxes.contains_key(agent) {
+            return Err(CoordinationError::UnknownAgent {
+                agent: agent.to_string(),
+            });
+        }
+        Ok(state.in_flight.get(agent).map_or(0, BTreeMap::len))
+    }
+
+    /// Put envelopes whose ack timeout has expired back into their inboxes.
+    /// Returns how many were requeued or dead-lettered.
+    pub fn redeliver_expired(&self) -> usize {
+        let mut state = self.lock_state();
+        let agents: Vec<String> = state.in_flight.keys().cloned().collect();
+        let now = Instant::now();
+        agents
+            .iter()
+            .map(|agent| requeue_expired_locked(&mut state, agent, now))
+            .sum()
+    }
+
+    /// Run [`Self::redeliver_expired`] in the background, so unacknowledged
+    /// envelopes return to their inboxes (or the dead letters) even when
+    /// nobody receives from that agent again. The sweep runs at most every
+    /// [`REDELIVERY_SWEEP_MAX_SECS`] seconds, stops once the last handle to
+    /// the bus is dropped, and is started only once per bus. Does nothing
+    /// outside a Tokio runtime.
+    pub fn start_redelivery(&self) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        {
+            let mut state = self.lock_state();
+            if state.redelivery_started {
+                return;
+            }
+            state.redelivery_started = true;
+        }
+        let bus = Arc::downgrade(&self.inner);
+        runtime.spawn(async move {
+            loop {
+                let Some(inner) = bus.upgrade() else {
+                    return;
+                };
+                let timeout = Self { inner }.delivery_policy().ack_timeout_secs;
+                let period = Duration::from_secs(timeout.clamp(1, REDELIVERY_SWEEP_MAX_SECS));
+                tokio::time::sleep(period).await;
+                let Some(inner) = bus.upgrade() else {
+                    return;
+                };
+                let requeued = Self { inner }.redeliver_expired();
+                if requeued > 0 {
+                    tracing::debug!("coordination: requeued {requeued} unacknowledged envelopes");
+                }
+            }
+        });
+    }
+
+    /// Publish a dead-lettered envelope again under a new message ID, for
+    /// example after registering the agent it was addressed to. If it fails
+    /// again it returns to the dead letters.
+    pub fn replay_dead_letter(
+        &self,
+        message_id: &str,
+    ) -> Result<PublishReceipt, CoordinationError> {
+        let mut envelope = {
+            let mut state = self.lock_state();
+            let position = state
+                .dead_letters
+                .iter()
+                .rposition(|dead_letter| dead_letter.envelope.id == message_id)
+                .ok_or_else(|| CoordinationError::UnknownDeadLetter {
+                    message_id: message_id.to_string(),
+                })?;
+            let dead_letter = state.dead_letters.remove(position);
+            if let Some(correlation_id) =
+                normalized_non_empty(dead_letter.envelope.correlation_id.as_deref())
+            {
+                let mut remove_correlation_key = false;
+                if let Some(entries) = state.dead_letters_by_correlation.get_mut(correlation_id) {
+                    if let Some(index) = entries
+                        .iter()
+                        .rposition(|entry| entry.envelope.id == message_id)
+                    {
+                        let _ = entries.remove(index);
+                    }
+                    remove_correlation_key = entries.is_empty();
+                }
+                if remove_correlation_key {
+                    state.dead_letters_by_correlation.remove(correlation_id);
+                }
+            }
+            state.persist("remove replayed dead letter", |store| {
+                store.delete_dead_letter(message_id)
+            });
+            dead_letter.envelope
+        };
+        envelope.id = Uuid::new_v4().to_string();
+        self.publish(envelope)
+    }
+
     pub fn pending_for_agent(&self, agent: &str) -> Result<usize, CoordinationError> {
         let state = self.lock_state();
         state
@@ -685,11 +1072,11 @@ impl InMemoryMessageBus {
         agents
     }
 
-    pub fn limits(&self) -> InMemoryMessageBusLimits {
+    pub fn limits(&self) -> MessageBusLimits {
         self.lock_state().limits
     }
 
-    pub fn stats(&self) -> InMemoryMessageBusStats {
+    pub fn stats(&self) -> MessageBusStats {
         self.lock_state().stats
     }
 
@@ -998,6 +1385,114 @@ impl InMemoryMessageBus {
     }
 }
 
+/// Record, sequence and deliver a validated envelope. Every journal write
+/// lands in the caller's transaction.
+fn publish_locked(
+    state: &mut BusState,
+    envelope: CoordinationEnvelope,
+) -> Result<PublishReceipt, CoordinationError> {
+    state.stats.publish_attempts_total += 1;
+    if state.seen_message_ids.contains(&envelope.id) {
+        let error = CoordinationError::DuplicateMessageId {
+            message_id: envelope.id.clone(),
+        };
+        push_dead_letter_locked(state, envelope, error.to_string());
+        return Err(error);
+    }
+    if state.seen_message_ids.len() >= state.limits.max_seen_message_ids {
+        if let Some(evicted_id) = state.seen_message_order.pop_front() {
+            if state.seen_message_ids.remove(&evicted_id) {
+                state.stats.seen_message_id_evictions_total += 1;
+            }
+            state.persist("evict seen message id", |store| {
+                store.delete_seen(&evicted_id)
+            });
+        }
+    }
+    state.seen_message_ids.insert(envelope.id.clone());
+    state.seen_message_order.push_back(envelope.id.clone());
+    state.persist("record message id", |store| store.insert_seen(&envelope.id));
+
+    if let CoordinationPayload::ContextPatch {
+        key,
+        expected_version,
+        value,
+    } = &envelope.payload
+    {
+        if let Err(error) =
+            apply_context_patch_locked(state, &envelope, key, *expected_version, value)
+        {
+            push_dead_letter_locked(state, envelope, error.to_string());
+            return Err(error);
+        }
+    }
+
+    state.next_sequence += 1;
+    let sequence = state.next_sequence;
+    state.persist("advance sequence", |store| {
+        store.set_next_sequence(sequence)
+    });
+    let sequenced = SequencedEnvelope {
+        sequence,
+        envelope: envelope.clone(),
+    };
+
+    let delivered_to = match envelope.scope {
+        DeliveryScope::Direct => {
+            let target = envelope.to.as_deref().expect("validated direct target");
+            if !state.inboxes.contains_key(target) {
+                let error = CoordinationError::UnknownTarget {
+                    agent: target.to_string(),
+                    message_id: envelope.id.clone(),
+                };
+                push_dead_letter_locked(state, envelope, error.to_string());
+                return Err(error);
+            }
+
+            let dropped = push_inbox_entry_locked(state, target, sequenced);
+            if let Some(dropped) = dropped {
+                state.stats.inbox_overflow_evictions_total += 1;
+                push_dead_letter_locked(
+                    state,
+                    dropped,
+                    format!("inbox overflow: dropped oldest message for agent '{target}'"),
+                );
+            }
+            1
+        }
+        DeliveryScope::Broadcast => {
+            if state.inboxes.is_empty() {
+                0
+            } else {
+                let fanout = state.inboxes.len();
+                let mut dropped_items: Vec<(String, CoordinationEnvelope)> = Vec::new();
+                let agents = state.inboxes.keys().cloned().collect::<Vec<_>>();
+                for agent in &agents {
+                    if let Some(dropped) = push_inbox_entry_locked(state, agent, sequenced.clone())
+                    {
+                        dropped_items.push((agent.clone(), dropped));
+                    }
+                }
+                for (agent, dropped) in dropped_items {
+                    state.stats.inbox_overflow_evictions_total += 1;
+                    push_dead_letter_locked(
+                        state,
+                        dropped,
+                        format!("inbox overflow: dropped oldest message for agent '{agent}'"),
+                    );
+                }
+                fanout
+            }
+        }
+    };
+    state.stats.deliveries_total += delivered_to as u64;
+
+    Ok(PublishReceipt {
+        sequence,
+        delivered_to,
+    })
+}
+
 fn push_inbox_entry_locked(
     state: &mut BusState,
     agent: &str,
@@ -1023,8 +1518,68 @@ fn push_inbox_entry_locked(
     }
 
     increment_correlation_count(correlation_counts, &entry.envelope);
-    inbox.push_back(entry);
-    dropped.map(|value| value.envelope)
+    state.persist("store inbox envelope", |store| {
+        store.insert_inbox(agent, entry.sequence, &entry.envelope)
+    });
+    state
+        .inboxes
+        .get_mut(agent)
+        .expect("agent existence should be validated before pushing inbox entry")
+        .push_back(entry);
+    let dropped = dropped?;
+    state
+        .delivery_attempts
+        .remove(&(agent.to_string(), dropped.sequence));
+    state.persist("remove evicted inbox envelope", |store| {
+        store.delete_inbox(agent, dropped.sequence)
+    });
+    Some(dropped.envelope)
+}
+
+/// Move an agent's expired in-flight envelopes back to the front of its
+/// inbox, or to the dead letters once they used up their delivery attempts.
+fn requeue_expired_locked(state: &mut BusState, agent: &str, now: Instant) -> usize {
+    let timeout = Duration::from_secs(state.delivery.ack_timeout_secs);
+    let Some(in_flight) = state.in_flight.get_mut(agent) else {
+        return 0;
+    };
+    let expired: Vec<u64> = in_flight
+        .iter()
+        .filter(|(_, item)| now.saturating_duration_since(item.delivered_at) >= timeout)
+        .map(|(sequence, _)| *sequence)
+        .collect();
+    let expired: Vec<SequencedEnvelope> = expired
+        .iter()
+        .filter_map(|sequence| in_flight.remove(sequence))
+        .map(|item| item.entry)
+        .collect();
+    let count = expired.len();
+
+    // Newest first, so the oldest ends up at the front of the inbox.
+    for entry in expired.into_iter().rev() {
+        let key = (agent.to_string(), entry.sequence);
+        let attempts = state.delivery_attempts.get(&key).copied().unwrap_or(0);
+        if attempts >= state.delivery.max_delivery_attempts {
+            state.delivery_attempts.remove(&key);
+            state.persist("remove undeliverable envelope", |store| {
+                store.delete_inbox(agent, entry.sequence)
+            });
+            push_dead_letter_locked(
+                state,
+                entry.envelope,
+                format!("not acknowledged by '{agent}' after {attempts} deliveries"),
+            );
+            continue;
+        }
+        state.stats.redeliveries_total += 1;
+        if let Some(counts) = state.inbox_correlation_counts.get_mut(agent) {
+            increment_correlation_count(counts, &entry.envelope);
+        }
+        if let Some(inbox) = state.inboxes.get_mut(agent) {
+            inbox.push_front(entry);
+        }
+    }
+    count
 }
 
 fn increment_correlation_count(
@@ -1076,9 +1631,13 @@ fn push_dead_letter_locked(state: &mut BusState, envelope: CoordinationEnvelope,
             }
         }
         let _ = state.dead_letters.remove(0);
+        state.persist("evict dead letter", BusStore::delete_oldest_dead_letter);
     }
 
     let dead_letter = DeadLetter { envelope, reason };
+    state.persist("store dead letter", |store| {
+        store.insert_dead_letter(&dead_letter)
+    });
     if let Some(correlation_id) =
         normalized_non_empty(dead_letter.envelope.correlation_id.as_deref())
     {
@@ -1141,6 +1700,9 @@ fn apply_context_patch_locked(
             if state.context.remove(&evicted_key).is_some() {
                 state.stats.context_evictions_total += 1;
             }
+            state.persist("evict context entry", |store| {
+                store.delete_context(&evicted_key)
+            });
             let evicted_correlation = state.context_correlation_by_key.remove(&evicted_key);
             if let Some(correlation_id) = evicted_correlation.as_deref() {
                 remove_key_from_context_correlation_order(state, correlation_id, &evicted_key);
@@ -1193,16 +1755,20 @@ fn apply_context_patch_locked(
         state.context_correlation_by_key.remove(&key_owned);
     }
 
-    state.context.insert(
-        key_owned.clone(),
-        SharedContextEntry {
-            key: key_owned,
-            value: value.clone(),
-            version: current_version + 1,
-            updated_by: envelope.from.clone(),
-            last_message_id: envelope.id.clone(),
-        },
-    );
+    let entry = SharedContextEntry {
+        key: key_owned.clone(),
+        value: value.clone(),
+        version: current_version + 1,
+        updated_by: envelope.from.clone(),
+        last_message_id: envelope.id.clone(),
+    };
+    state.persist("store context entry", |store| {
+        store.upsert_context(
+            &entry,
+            normalized_non_empty(envelope.correlation_id.as_deref()),
+        )
+    });
+    state.context.insert(key_owned, entry);
 
     Ok(())
 }
@@ -1368,7 +1934,7 @@ mod tests {
 
     #[test]
     fn duplicate_message_ids_are_rejected_and_dead_lettered() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("worker").expect("register worker");
 
         let mut envelope = CoordinationEnvelope::new_direct(
@@ -1405,7 +1971,7 @@ mod tests {
 
     #[test]
     fn dedupe_window_evicts_old_ids_and_allows_reuse_after_eviction() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 32,
             max_dead_letters: 32,
             max_context_entries: 32,
@@ -1474,7 +2040,7 @@ mod tests {
 
     #[test]
     fn context_patch_conflict_goes_to_dead_letter() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("lead").expect("register lead");
 
         let first_patch = CoordinationEnvelope::new_broadcast(
@@ -1521,7 +2087,7 @@ mod tests {
 
     #[test]
     fn delegate_context_patch_requires_correlation_id() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
 
         let mut patch = CoordinationEnvelope::new_broadcast(
             "lead",
@@ -1549,7 +2115,7 @@ mod tests {
 
     #[test]
     fn delegate_context_patch_rejects_mismatched_correlation_id() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
 
         let mut patch = CoordinationEnvelope::new_broadcast(
             "lead",
@@ -1580,7 +2146,7 @@ mod tests {
 
     #[test]
     fn delegate_context_patch_rejects_invalid_delegate_key_shape() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
 
         let mut patch = CoordinationEnvelope::new_broadcast(
             "lead",
@@ -1609,7 +2175,7 @@ mod tests {
 
     #[test]
     fn delegate_context_patch_rejects_empty_tail_segment() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
 
         let mut patch = CoordinationEnvelope::new_broadcast(
             "lead",
@@ -1638,7 +2204,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn concurrent_publish_keeps_inbox_order() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("lead").expect("register lead");
         bus.register_agent("worker").expect("register worker");
 
@@ -1693,7 +2259,7 @@ mod tests {
 
     #[test]
     fn multi_agent_delegation_flow_updates_context_and_returns_result() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("lead").expect("register lead");
         bus.register_agent("researcher")
             .expect("register researcher");
@@ -1767,7 +2333,7 @@ mod tests {
 
     #[test]
     fn peek_does_not_consume_messages() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("worker").expect("register worker");
 
         let mut envelope = CoordinationEnvelope::new_direct(
@@ -1794,7 +2360,7 @@ mod tests {
 
     #[test]
     fn correlation_pending_and_peek_paging_follow_inbox_lifecycle() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("worker").expect("register worker");
 
         for (message_id, correlation_id) in [
@@ -1850,7 +2416,7 @@ mod tests {
 
     #[test]
     fn inbox_correlation_counts_stay_consistent_with_overflow_evictions() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 2,
             max_dead_letters: 16,
             max_context_entries: 16,
@@ -1902,7 +2468,7 @@ mod tests {
 
     #[test]
     fn correlation_peek_normalizes_whitespace_in_message_correlation_id() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("worker").expect("register worker");
 
         let mut envelope = CoordinationEnvelope::new_direct(
@@ -1934,7 +2500,7 @@ mod tests {
 
     #[test]
     fn registered_agents_and_context_snapshot_are_available() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("worker-b").expect("register worker-b");
         bus.register_agent("worker-a").expect("register worker-a");
 
@@ -1966,7 +2532,7 @@ mod tests {
 
     #[test]
     fn inbox_limit_drops_oldest_and_records_dead_letter() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 2,
             max_dead_letters: 8,
             max_context_entries: 16,
@@ -2028,7 +2594,7 @@ mod tests {
 
     #[test]
     fn dead_letter_limit_is_capped() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 16,
             max_dead_letters: 2,
             max_context_entries: 16,
@@ -2071,7 +2637,7 @@ mod tests {
 
     #[test]
     fn context_limit_evicts_oldest_entries_and_tracks_stats() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 16,
             max_dead_letters: 16,
             max_context_entries: 2,
@@ -2110,7 +2676,7 @@ mod tests {
 
     #[test]
     fn context_limit_uses_write_recency_and_preserves_hot_keys() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 16,
             max_dead_letters: 16,
             max_context_entries: 2,
@@ -2196,7 +2762,7 @@ mod tests {
 
     #[test]
     fn context_entries_recent_with_offset_returns_newest_first_pages() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         for key in [
             "delegate/corr-a/state",
             "delegate/corr-b/state",
@@ -2226,7 +2792,7 @@ mod tests {
 
     #[test]
     fn dead_letters_recent_returns_newest_first_pages() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
         bus.register_agent("worker").expect("register worker");
 
         for index in 0..4 {
@@ -2254,7 +2820,7 @@ mod tests {
 
     #[test]
     fn context_entries_recent_for_correlation_support_paging_and_count() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
 
         let mut patch_a_state = CoordinationEnvelope::new_broadcast(
             "lead",
@@ -2332,7 +2898,7 @@ mod tests {
 
     #[test]
     fn delegate_context_indexes_exclude_non_delegate_keys_and_support_paging() {
-        let bus = InMemoryMessageBus::new();
+        let bus = MessageBus::new();
 
         let mut delegate_a_state = CoordinationEnvelope::new_broadcast(
             "lead",
@@ -2400,7 +2966,7 @@ mod tests {
 
     #[test]
     fn dead_letter_correlation_index_tracks_evictions_and_paging() {
-        let bus = InMemoryMessageBus::with_limits(InMemoryMessageBusLimits {
+        let bus = MessageBus::with_limits(MessageBusLimits {
             max_inbox_messages_per_agent: 16,
             max_dead_letters: 2,
             max_context_entries: 16,
@@ -2445,4 +3011,274 @@ mod tests {
         assert_eq!(corr_b_page.len(), 1);
         assert_eq!(corr_b_page[0].envelope.id, "dead-corr-b-0");
     }
+
+    fn direct_task(to: &str, task_id: &str) -> CoordinationEnvelope {
+        let mut envelope = CoordinationEnvelope::new_direct(
+            "lead",
+            to,
+            "conv-delivery",
+            "coordination",
+            CoordinationPayload::DelegateTask {
+                task_id: task_id.to_string(),
+                summary: format!("work on {task_id}"),
+                metadata: json!({}),
+            },
+        );
+        envelope.correlation_id = Some("corr-delivery".to_string());
+        envelope
+    }
+
+    #[test]
+    fn received_envelopes_are_redelivered_until_acked() {
+        let bus = MessageBus::new();
+        bus.register_agent("worker").expect("register worker");
+        bus.set_delivery_policy(DeliveryPolicy {
+            ack_timeout_secs: 0,
+            max_delivery_attempts: 2,
+        });
+        bus.publish(direct_task("worker", "task-1"))
+            .expect("publish task-1");
+        bus.publish(direct_task("worker", "task-2"))
+            .expect("publish task-2");
+
+        let first = bus.receive_for_agent("worker", 1).expect("receive");
+        assert_eq!(first.len(), 1);
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 1);
+        assert_eq!(bus.in_flight_for_agent("worker").unwrap(), 1);
+        assert_eq!(
+            bus.pending_for_agent_correlation("worker", "corr-delivery")
+                .unwrap(),
+            1
+        );
+
+        // Not acknowledged within the (zero) timeout: delivered again first.
+        let again = bus.receive_for_agent("worker", 0).expect("receive again");
+        assert_eq!(again.len(), 2);
+        assert_eq!(again[0].sequence, first[0].sequence);
+        assert!(bus.ack("worker", again[1].sequence).unwrap());
+        assert!(!bus.ack("worker", again[1].sequence).unwrap());
+
+        // Second delivery of task-1 was its last attempt.
+        assert_eq!(bus.redeliver_expired(), 1);
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 0);
+        assert_eq!(bus.in_flight_for_agent("worker").unwrap(), 0);
+        let dead = bus.dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert!(dead[0].reason.contains("after 2 deliveries"));
+        assert_eq!(bus.stats().redeliveries_total, 1);
+    }
+
+    #[test]
+    fn receive_message_leaves_other_envelopes_pending() {
+        let bus = MessageBus::new();
+        bus.register_agent("worker").expect("register worker");
+        bus.set_delivery_policy(DeliveryPolicy {
+            ack_timeout_secs: 0,
+            max_delivery_attempts: 3,
+        });
+        bus.publish(direct_task("worker", "task-1"))
+            .expect("publish task-1");
+        let second = direct_task("worker", "task-2");
+        let second_id = second.id.clone();
+        bus.publish(second).expect("publish task-2");
+
+        let received = bus
+            .receive_message("worker", &second_id)
+            .expect("receive")
+            .expect("task-2 should be pending");
+        assert_eq!(received.envelope.id, second_id);
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 1);
+        assert_eq!(bus.in_flight_for_agent("worker").unwrap(), 1);
+
+        // Unacknowledged, so it comes back for the next receive.
+        assert_eq!(bus.redeliver_expired(), 1);
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 2);
+        assert!(bus.receive_message("worker", "missing").unwrap().is_none());
+        let again = bus
+            .receive_message("worker", &second_id)
+            .expect("receive again")
+            .expect("task-2 should be redelivered");
+        assert!(bus.ack("worker", again.sequence).unwrap());
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 1);
+        assert_eq!(bus.in_flight_for_agent("worker").unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn background_redelivery_requeues_unacked_envelopes() {
+        let bus = MessageBus::new();
+        bus.register_agent("worker").expect("register worker");
+        bus.set_delivery_policy(DeliveryPolicy {
+            ack_timeout_secs: 0,
+            max_delivery_attempts: 1,
+        });
+        bus.publish(direct_task("worker", "task-1"))
+            .expect("publish task-1");
+        assert_eq!(bus.receive_for_agent("worker", 0).unwrap().len(), 1);
+
+        bus.start_redelivery();
+        bus.start_redelivery();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while bus.dead_letter_count() == 0 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("sweep should dead-letter the envelope without another receive");
+        assert_eq!(bus.in_flight_for_agent("worker").unwrap(), 0);
+    }
+
+    #[test]
+    fn dead_letter_replay_republishes_under_new_id() {
+        let bus = MessageBus::new();
+        bus.register_agent("lead").expect("register lead");
+        let envelope = direct_task("late-worker", "task-late");
+        let original_id = envelope.id.clone();
+        bus.publish(envelope)
+            .expect_err("unknown target should dead-letter");
+        assert_eq!(bus.dead_letter_count_for_correlation("corr-delivery"), 1);
+
+        bus.register_agent("late-worker").expect("register worker");
+        let receipt = bus
+            .replay_dead_letter(&original_id)
+            .expect("replay should deliver");
+        assert_eq!(receipt.delivered_to, 1);
+        assert_eq!(bus.dead_letter_count(), 0);
+        assert_eq!(bus.dead_letter_count_for_correlation("corr-delivery"), 0);
+
+        let delivered = bus.drain_for_agent("late-worker", 0).expect("drain");
+        assert_eq!(delivered.len(), 1);
+        assert_ne!(delivered[0].envelope.id, original_id);
+        assert_eq!(
+            bus.replay_dead_letter(&original_id).unwrap_err(),
+            CoordinationError::UnknownDeadLetter {
+                message_id: original_id
+            }
+        );
+    }
+
+    #[test]
+    fn persistent_bus_survives_restart() {
+        let tmp = tempfile::TempDir::new().expect("temp dir");
+        let path = tmp.path().join("coordination.db");
+        let limits = MessageBusLimits::default();
+        let (in_flight_sequence, duplicate_id) = {
+            let bus = MessageBus::load_persistent(&path, limits).expect("open bus");
+            bus.register_agent("lead").expect("register lead");
+            bus.register_agent("worker").expect("register worker");
+            let first = direct_task("worker", "task-1");
+            let duplicate_id = first.id.clone();
+            bus.publish(first).expect("publish task-1");
+            bus.publish(direct_task("worker", "task-2"))
+                .expect("publish task-2");
+            bus.publish(direct_task("worker", "task-3"))
+                .expect("publish task-3");
+            let mut patch = CoordinationEnvelope::new_direct(
+                "lead",
+                "lead",
+                "conv-delivery",
+                "context",
+                CoordinationPayload::ContextPatch {
+                    key: "delegate/corr-delivery/state".to_string(),
+                    expected_version: 0,
+                    value: json!({"phase": "queued"}),
+                },
+            );
+            patch.correlation_id = Some("corr-delivery".to_string());
+            bus.publish(patch).expect("publish patch");
+            let _ = bus.publish(direct_task("nobody", "task-lost"));
+
+            let received = bus.receive_for_agent("worker", 2).expect("receive");
+            assert!(bus.ack("worker", received[0].sequence).unwrap());
+            (received[1].sequence, duplicate_id)
+        };
+
+        let bus = MessageBus::load_persistent(&path, limits).expect("reopen bus");
+        assert_eq!(bus.registered_agents(), vec!["lead", "worker"]);
+        // The unacknowledged envelope is delivered again, ahead of task-3.
+        let pending = bus.peek_for_agent("worker", 0).expect("peek");
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].sequence, in_flight_sequence);
+        assert_eq!(
+            bus.pending_for_agent_correlation("worker", "corr-delivery")
+                .unwrap(),
+            2
+        );
+        let entry = bus
+            .context_entry("delegate/corr-delivery/state")
+            .expect("context restored");
+        assert_eq!(entry.version, 1);
+        assert_eq!(
+            bus.delegate_context_count_for_correlation("corr-delivery"),
+            1
+        );
+        assert_eq!(bus.dead_letter_count_for_correlation("corr-delivery"), 1);
+        assert_eq!(
+            bus.publish(direct_task("worker", "task-1")).map(|_| ()),
+            Ok(())
+        );
+        let mut duplicate = direct_task("worker", "task-1");
+        duplicate.id = duplicate_id.clone();
+        assert_eq!(
+            bus.publish(duplicate).unwrap_err(),
+            CoordinationError::DuplicateMessageId {
+                message_id: duplicate_id
+            }
+        );
+        let sequences: Vec<u64> = bus
+            .drain_for_agent("worker", 0)
+            .expect("drain")
+            .iter()
+            .map(|entry| entry.sequence)
+            .collect();
+        assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+        drop(bus);
+
+        let bus = MessageBus::load_persistent(&path, limits).expect("reopen bus");
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 0);
+    }
+
+    #[test]
+    fn failed_journal_write_rolls_back_the_whole_publish() {
+        let tmp = tempfile::TempDir::new().expect("temp dir");
+        let path = tmp.path().join("coordination.db");
+        let limits = MessageBusLimits::default();
+        let bus = MessageBus::load_persistent(&path, limits).expect("open bus");
+        bus.register_agent("worker").expect("register worker");
+        bus.publish(direct_task("worker", "task-1"))
+            .expect("publish task-1");
+
+        let conn = rusqlite::Connection::open(&path).expect("open journal");
+        conn.execute_batch(
+            "CREATE TRIGGER fail_inbox BEFORE INSERT ON bus_inbox
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+        )
+        .expect("install trigger");
+
+        let envelope = direct_task("worker", "task-2");
+        let message_id = envelope.id.clone();
+        let error = bus.publish(envelope.clone()).unwrap_err();
+        assert!(
+            matches!(&error, CoordinationError::Journal { reason, .. } if reason.contains("disk full")),
+            "{error}"
+        );
+        // Neither the journal nor memory kept the message ID or sequence.
+        let seen: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM bus_seen WHERE message_id = ?1",
+                [&message_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(seen, 0);
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 1);
+
+        conn.execute_batch("DROP TRIGGER fail_inbox")
+            .expect("drop trigger");
+        let receipt = bus.publish(envelope).expect("publish after recovery");
+        assert_eq!(receipt.sequence, 2);
+        drop(bus);
+
+        let bus = MessageBus::load_persistent(&path, limits).expect("reopen bus");
+        assert_eq!(bus.pending_for_agent("worker").unwrap(), 2);
+    }
 }
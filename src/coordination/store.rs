@@ -0,0 +1,360 @@
+//! SQLite journal behind a persistent coordination bus.
+//!
+//! The bus keeps serving reads from memory; every change to its inboxes,
+//! dead letters, shared context and dedupe window is written through to
+//! this store, and [`BusStore::open`] reads it all back after a restart.
+//!
+//! Tables:
+//! - `bus_meta`: last issued sequence number
+//! - `bus_agents`: registered agent inboxes
+//! - `bus_inbox`: undelivered and unacknowledged envelopes per agent
+//! - `bus_dead_letters`: dead-lettered envelopes with their reason
+//! - `bus_context`: shared-context entries in write order
+//! - `bus_seen`: message IDs in the dedupe window
+
+use super::{CoordinationEnvelope, DeadLetter, MessageBusLimits, SharedContextEntry};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+/// State read back from the journal, oldest first in every list.
+#[derive(Debug, Default)]
+pub(super) struct Snapshot {
+    pub next_sequence: u64,
+    pub agents: Vec<String>,
+    /// `(agent, sequence, envelope, delivery attempts)`
+    pub inbox: Vec<(String, u64, CoordinationEnvelope, u32)>,
+    pub dead_letters: Vec<DeadLetter>,
+    /// Context entries with the correlation ID of their last write.
+    pub context: Vec<(SharedContextEntry, Option<String>)>,
+    pub seen_message_ids: Vec<String>,
+}
+
+pub(super) struct BusStore {
+    conn: Connection,
+}
+
+impl std::fmt::Debug for BusStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BusStore")
+            .field("path", &self.conn.path())
+            .finish()
+    }
+}
+
+impl BusStore {
+    /// Open (or create) the journal at `path`, drop whatever exceeds
+    /// `limits` and return the retained state.
+    pub fn open(path: &Path, limits: MessageBusLimits) -> Result<(Self, Snapshot)> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open coordination store {}", path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             CREATE TABLE IF NOT EXISTS bus_meta (
+                 key   TEXT PRIMARY KEY,
+                 value INTEGER NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS bus_agents (
+                 agent TEXT PRIMARY KEY
+             );
+             CREATE TABLE IF NOT EXISTS bus_inbox (
+                 agent    TEXT NOT NULL,
+                 sequence INTEGER NOT NULL,
+                 envelope TEXT NOT NULL,
+                 attempts INTEGER NOT NULL DEFAULT 0,
+                 PRIMARY KEY (agent, sequence)
+             );
+             CREATE TABLE IF NOT EXISTS bus_dead_letters (
+                 id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                 message_id TEXT NOT NULL,
+                 envelope   TEXT NOT NULL,
+                 reason     TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_bus_dead_letters_message
+                 ON bus_dead_letters(message_id);
+             CREATE TABLE IF NOT EXISTS bus_context (
+                 key            TEXT PRIMARY KEY,
+                 entry          TEXT NOT NULL,
+                 correlation_id TEXT,
+                 position       INTEGER NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS bus_seen (
+                 message_id TEXT PRIMARY KEY,
+                 position   INTEGER NOT NULL
+             );",
+        )?;
+        let store = Self { conn };
+        store.apply_limits(limits)?;
+        let snapshot = store.load()?;
+        Ok((store, snapshot))
+    }
+
+    fn apply_limits(&self, limits: MessageBusLimits) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM bus_inbox WHERE rowid IN (
+                 SELECT rowid FROM (
+                     SELECT rowid, ROW_NUMBER() OVER (
+                         PARTITION BY agent ORDER BY sequence DESC
+                     ) AS newer
+                     FROM bus_inbox
+                 ) WHERE newer > ?1
+             )",
+            params![limit(limits.max_inbox_messages_per_agent)],
+        )?;
+        self.conn.execute(
+            "DELETE FROM bus_dead_letters WHERE id NOT IN (
+                 SELECT id FROM bus_dead_letters ORDER BY id DESC LIMIT ?1
+             )",
+            params![limit(limits.max_dead_letters)],
+        )?;
+        self.conn.execute(
+            "DELETE FROM bus_context WHERE key NOT IN (
+                 SELECT key FROM bus_context ORDER BY position DESC LIMIT ?1
+             )",
+            params![limit(limits.max_context_entries)],
+        )?;
+        self.conn.execute(
+            "DELETE FROM bus_seen WHERE message_id NOT IN (
+                 SELECT message_id FROM bus_seen ORDER BY position DESC LIMIT ?1
+             )",
+            params![limit(limits.max_seen_message_ids)],
+        )?;
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<Snapshot> {
+        let mut snapshot = Snapshot {
+            next_sequence: self
+                .conn
+                .query_row(
+                    "SELECT value FROM bus_meta WHERE key = 'next_sequence'",
+                    [],
+                    |row| row.get::<_, i64>(0),
+                )
+                .optional()?
+                .map_or(0, |value| u64::try_from(value).unwrap_or(0)),
+            ..Snapshot::default()
+        };
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT agent FROM bus_agents ORDER BY agent")?;
+        snapshot.agents = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT agent, sequence, envelope, attempts FROM bus_inbox ORDER BY agent, sequence",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (agent, sequence, envelope, attempts) = row?;
+            let sequence = u64::try_from(sequence).unwrap_or(0);
+            let envelope = serde_json::from_str(&envelope)
+                .with_context(|| format!("Corrupt inbox entry {sequence} for agent '{agent}'"))?;
+            snapshot.next_sequence = snapshot.next_sequence.max(sequence);
+            snapshot.inbox.push((
+                agent,
+                sequence,
+                envelope,
+                u32::try_from(attempts).unwrap_or(0),
+            ));
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT envelope, reason FROM bus_dead_letters ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (envelope, reason) = row?;
+            snapshot.dead_letters.push(DeadLetter {
+                envelope: serde_json::from_str(&envelope).context("Corrupt dead letter")?,
+                reason,
+            });
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT entry, correlation_id FROM bus_context ORDER BY position")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?;
+        for row in rows {
+            let (entry, correlation_id) = row?;
+            snapshot.context.push((
+                serde_json::from_str(&entry).context("Corrupt shared-context entry")?,
+                correlation_id,
+            ));
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT message_id FROM bus_seen ORDER BY position")?;
+        snapshot.seen_message_ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(snapshot)
+    }
+
+    /// Start a transaction; the writes up to [`Self::commit`] land together.
+    pub fn begin(&self) -> Result<()> {
+        self.conn.execute_batch("BEGIN IMMEDIATE")?;
+        Ok(())
+    }
+
+    pub fn commit(&self) -> Result<()> {
+        self.conn.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    pub fn rollback(&self) -> Result<()> {
+        if !self.conn.is_autocommit() {
+            self.conn.execute_batch("ROLLBACK")?;
+        }
+        Ok(())
+    }
+
+    pub fn set_next_sequence(&self, sequence: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO bus_meta (key, value) VALUES ('next_sequence', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![sequence as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_agent(&self, agent: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO bus_agents (agent) VALUES (?1)",
+            params![agent],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_agent(&self, agent: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM bus_inbox WHERE agent = ?1", params![agent])?;
+        self.conn
+            .execute("DELETE FROM bus_agents WHERE agent = ?1", params![agent])?;
+        Ok(())
+    }
+
+    pub fn insert_inbox(
+        &self,
+        agent: &str,
+        sequence: u64,
+        envelope: &CoordinationEnvelope,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO bus_inbox (agent, sequence, envelope, attempts)
+             VALUES (?1, ?2, ?3, 0)",
+            params![agent, sequence as i64, serde_json::to_string(envelope)?],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_attempts(&self, agent: &str, sequence: u64, attempts: u32) -> Result<()> {
+        self.conn.execute(
+            "UPDATE bus_inbox SET attempts = ?3 WHERE agent = ?1 AND sequence = ?2",
+            params![agent, sequence as i64, attempts],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_inbox(&self, agent: &str, sequence: u64) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM bus_inbox WHERE agent = ?1 AND sequence = ?2",
+            params![agent, sequence as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_dead_letter(&self, dead_letter: &DeadLetter) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO bus_dead_letters (message_id, envelope, reason) VALUES (?1, ?2, ?3)",
+            params![
+                dead_letter.envelope.id,
+                serde_json::to_string(&dead_letter.envelope)?,
+                dead_letter.reason
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_oldest_dead_letter(&self) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM bus_dead_letters WHERE id = (SELECT MIN(id) FROM bus_dead_letters)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Delete the newest dead letter recorded for `message_id`.
+    pub fn delete_dead_letter(&self, message_id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM bus_dead_letters WHERE id = (
+                 SELECT MAX(id) FROM bus_dead_letters WHERE message_id = ?1
+             )",
+            params![message_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn upsert_context(
+        &self,
+        entry: &SharedContextEntry,
+        correlation_id: Option<&str>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO bus_context (key, entry, correlation_id, position)
+             VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position), 0) + 1 FROM bus_context))
+             ON CONFLICT(key) DO UPDATE SET
+                 entry = excluded.entry,
+                 correlation_id = excluded.correlation_id,
+                 position = excluded.position",
+            params![entry.key, serde_json::to_string(entry)?, correlation_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_context(&self, key: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM bus_context WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
+    pub fn insert_seen(&self, message_id: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO bus_seen (message_id, position)
+             VALUES (?1, (SELECT COALESCE(MAX(position), 0) + 1 FROM bus_seen))",
+            params![message_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_seen(&self, message_id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM bus_seen WHERE message_id = ?1",
+            params![message_id],
+        )?;
+        Ok(())
+    }
+}
+
+fn limit(value: usize) -> i64 {
+    i64::try_from(value.max(1)).unwrap_or(i64::MAX)
+}
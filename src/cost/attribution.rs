@@ -0,0 +1,139 @@
+//! Attribution of spend to whoever and whatever caused it.
+//!
+//! Entry points (channel turns, cron jobs, workflows, tool calls) wrap their
+//! work in [`scope`]. Nested scopes inherit the fields of the enclosing one
+//! and fill in their own, so a web search run by a tool inside a cron job is
+//! charged to both the job and the tool. Every record written to the ledger
+//! picks up [`current`] automatically.
+
+use crate::config::schema::CostDimension;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+tokio::task_local! {
+    static COST_ATTRIBUTION: CostAttribution;
+}
+
+/// The user, channel, session, cron job, workflow and tool behind a cost.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostAttribution {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron_job: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+impl CostAttribution {
+    /// Attribution for a conversation turn on a channel.
+    pub fn turn(
+        channel: impl Into<String>,
+        user: impl Into<String>,
+        session: impl Into<String>,
+    ) -> Self {
+        Self {
+            user: Some(user.into()),
+            channel: Some(channel.into()),
+            session: Some(session.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn cron_job(id: impl Into<String>) -> Self {
+        Self {
+            cron_job: Some(id.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn workflow(name: impl Into<String>) -> Self {
+        Self {
+            workflow: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn tool(name: impl Into<String>) -> Self {
+        Self {
+            tool: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Value of an attribution dimension. `model` and `kind` describe the
+    /// record rather than its cause, so they are always `None` here.
+    pub fn get(&self, dimension: CostDimension) -> Option<&str> {
+        match dimension {
+            CostDimension::User => self.user.as_deref(),
+            CostDimension::Channel => self.channel.as_deref(),
+            CostDimension::Session => self.session.as_deref(),
+            CostDimension::CronJob => self.cron_job.as_deref(),
+            CostDimension::Workflow => self.workflow.as_deref(),
+            CostDimension::Tool => self.tool.as_deref(),
+            CostDimension::Model | CostDimension::Kind => None,
+        }
+    }
+
+    /// Fill unset fields from `outer`.
+    fn inherit(self, outer: &Self) -> Self {
+        Self {
+            user: self.user.or_else(|| outer.user.clone()),
+            channel: self.channel.or_else(|| outer.channel.clone()),
+            session: self.session.or_else(|| outer.session.clone()),
+            cron_job: self.cron_job.or_else(|| outer.cron_job.clone()),
+            workflow: self.workflow.or_else(|| outer.workflow.clone()),
+            tool: self.tool.or_else(|| outer.tool.clone()),
+        }
+    }
+}
+
+/// Attribution of the enclosing [`scope`], or empty outside of one.
+pub fn current() -> CostAttribution {
+    COST_ATTRIBUTION.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// Run `future` with `attribution` layered over the current attribution.
+pub async fn scope<F>(attribution: CostAttribution, future: F) -> F::Output
+where
+    F: Future,
+{
+    let merged = attribution.inherit(&current());
+    COST_ATTRIBUTION.scope(merged, future).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn nested_scopes_inherit_outer_fields() {
+        let seen = scope(CostAttribution::cron_job("nightly"), async {
+            scope(CostAttribution::tool("web_search"), async { current() }).await
+        })
+        .await;
+        assert_eq!(seen.cron_job.as_deref(), Some("nightly"));
+        assert_eq!(seen.tool.as_deref(), Some("web_search"));
+        assert!(current().is_empty());
+    }
+
+    #[test]
+    fn legacy_records_deserialize_without_attribution() {
+        let attribution: CostAttribution = serde_json::from_str("{}").unwrap();
+        assert!(attribution.is_empty());
+        assert_eq!(
+            serde_json::to_string(&CostAttribution::tool("shell")).unwrap(),
+            r#"{"tool":"shell"}"#
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use super::tracker::CostTracker;
+use super::types::CostReport;
+use crate::config::schema::CostDimension;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+/// Handle `zeroclaw cost <subcommand>` CLI commands.
+pub fn handle_command(command: crate::CostCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::CostCommands::Report {
+            by,
+            period,
+            since,
+            json,
+        } => {
+            let group_by = CostDimension::from_name(&by).with_context(|| {
+                format!(
+                    "Unknown dimension '{by}'. Expected one of: {}",
+                    dimension_names()
+                )
+            })?;
+            let since = super::report_window(&period, since.as_deref(), Utc::now())?;
+            let tracker = CostTracker::new(config.cost.clone(), &config.workspace_dir)?;
+            let report = tracker.report(group_by, since)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_report(&report);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn dimension_names() -> String {
+    CostDimension::ALL
+        .iter()
+        .map(|d| d.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_report(report: &CostReport) {
+    let window = report.since.map_or_else(
+        || "all time".to_string(),
+        |since| format!("since {}", since.format("%Y-%m-%d")),
+    );
+    println!(
+        "Cost by {} ({window}): ${:.4} across {} record(s)\n",
+        report.group_by.as_str(),
+        report.total_cost_usd,
+        report.request_count
+    );
+    if report.rows.is_empty() {
+        println!("No usage recorded.");
+        return;
+    }
+
+    let width = report
+        .rows
+        .iter()
+        .map(|row| row.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    println!(
+        "{:<width$}  {:>12}  {:>8}  {:>12}",
+        report.group_by.as_str(),
+        "cost (USD)",
+        "records",
+        "tokens"
+    );
+    for row in &report.rows {
+        println!(
+            "{:<width$}  {:>12.4}  {:>8}  {:>12}",
+            row.key, row.cost_usd, row.request_count, row.total_tokens
+        );
+    }
+}
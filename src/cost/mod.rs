@@ -1,8 +1,150 @@
+//! Unified cost ledger.
+//!
+//! Every provider call, embedding request, search API call and TTS/STT
+//! minute is appended to one JSONL ledger (`state/costs.jsonl`) together with
+//! the user, channel, session, cron job, workflow and tool it is
+//! [attributed](attribution) to. Budgets — global and per dimension — are
+//! enforced from the same ledger, and `zeroclaw cost report` / `GET
+//! /api/cost` break spend down by any dimension.
+
+pub mod attribution;
+pub mod cli;
 pub mod tracker;
 pub mod types;
 
 // Re-exported for potential external use (public API)
 #[allow(unused_imports)]
+pub use attribution::CostAttribution;
+#[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
-pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+pub use types::{
+    BudgetCheck, CostBreakdownRow, CostKind, CostRecord, CostReport, CostSummary,
+    DimensionBudgetBreach, ModelStats, TokenUsage, UsagePeriod,
+};
+
+use crate::config::schema::CostConfig;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use std::path::Path;
+use std::sync::{Arc, LazyLock, RwLock};
+
+static LEDGER: LazyLock<RwLock<Option<Arc<CostTracker>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Open the process-wide ledger (or clear it when cost tracking is disabled).
+pub fn init_from_config(config: &CostConfig, workspace_dir: &Path) {
+    let ledger = if config.enabled {
+        match CostTracker::new(config.clone(), workspace_dir) {
+            Ok(tracker) => Some(Arc::new(tracker)),
+            Err(error) => {
+                tracing::warn!("Cost ledger disabled: failed to open storage: {error}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut guard = LEDGER.write().unwrap_or_else(|e| e.into_inner());
+    *guard = ledger;
+}
+
+/// The process-wide ledger, if cost tracking is enabled.
+pub fn ledger() -> Option<Arc<CostTracker>> {
+    LEDGER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// The process-wide ledger when it writes to `workspace_dir`, otherwise a
+/// tracker of its own. Sharing one tracker keeps the in-memory budget
+/// aggregates consistent across the gateway, channels and agent loops.
+pub fn shared_tracker(config: &CostConfig, workspace_dir: &Path) -> Result<Arc<CostTracker>> {
+    let storage_path = tracker::resolve_storage_path(workspace_dir)?;
+    if let Some(ledger) = ledger().filter(|ledger| ledger.storage_path() == storage_path) {
+        return Ok(ledger);
+    }
+    Ok(Arc::new(CostTracker::new(config.clone(), workspace_dir)?))
+}
+
+/// Record metered (non-chat) usage on the process-wide ledger, attributed
+/// to the current scope. A no-op when cost tracking is disabled.
+pub fn record_metered(kind: CostKind, resource: &str, units: f64) {
+    let Some(ledger) = ledger() else {
+        return;
+    };
+    if let Err(error) = ledger.record_metered(kind, resource, units) {
+        tracing::warn!("Failed to record {} usage: {error}", kind.as_str());
+    }
+}
+
+/// Record already-priced token usage on the process-wide ledger, attributed
+/// to the current scope. A no-op when cost tracking is disabled.
+pub fn record_usage(usage: TokenUsage) {
+    let Some(ledger) = ledger() else {
+        return;
+    };
+    if let Err(error) = ledger.record_usage(usage) {
+        tracing::warn!("Failed to record usage: {error}");
+    }
+}
+
+/// Record already-priced metered usage on the process-wide ledger.
+pub fn record_charge(kind: CostKind, resource: &str, units: f64, cost_usd: f64) {
+    let Some(ledger) = ledger() else {
+        return;
+    };
+    if let Err(error) = ledger.record_charge(kind, resource, units, cost_usd) {
+        tracing::warn!("Failed to record {} usage: {error}", kind.as_str());
+    }
+}
+
+/// Start of a report window: an explicit `since` date (`YYYY-MM-DD`) or a
+/// named period (`today`, `week`, `month`, `all`). `None` means all time.
+pub fn report_window(
+    period: &str,
+    since: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>> {
+    if let Some(raw) = since {
+        let date = NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+            .with_context(|| format!("Invalid date '{raw}', expected YYYY-MM-DD"))?;
+        return Ok(Some(start_of_day(date)));
+    }
+
+    let today = now.date_naive();
+    let start = match period.trim().to_ascii_lowercase().as_str() {
+        "today" | "day" => today,
+        "week" => today - chrono::Duration::days(i64::from(today.weekday().num_days_from_monday())),
+        "month" => today.with_day(1).unwrap_or(today),
+        "all" => return Ok(None),
+        other => bail!("Unknown period '{other}'. Expected today, week, month or all"),
+    };
+    Ok(Some(start_of_day(start)))
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_time(chrono::NaiveTime::MIN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_window_resolves_periods_and_dates() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 19, 15, 30, 0).unwrap();
+        let start = |p| report_window(p, None, now).unwrap().map(|d| d.date_naive());
+        assert_eq!(start("today"), NaiveDate::from_ymd_opt(2026, 3, 19));
+        // 2026-03-19 is a Thursday.
+        assert_eq!(start("week"), NaiveDate::from_ymd_opt(2026, 3, 16));
+        assert_eq!(start("month"), NaiveDate::from_ymd_opt(2026, 3, 1));
+        assert_eq!(start("all"), None);
+        assert!(report_window("year", None, now).is_err());
+
+        let since = report_window("month", Some("2026-01-05"), now).unwrap();
+        assert_eq!(
+            since.map(|d| d.date_naive()),
+            NaiveDate::from_ymd_opt(2026, 1, 5)
+        );
+        assert!(report_window("month", Some("05/01/2026"), now).is_err());
+    }
+}
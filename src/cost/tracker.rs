@@ -1,7 +1,11 @@
-use super::types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
-use crate::config::schema::CostConfig;
+use super::attribution::{self, CostAttribution};
+use super::types::{
+    BudgetCheck, CostBreakdownRow, CostKind, CostRecord, CostReport, CostSummary,
+    DimensionBudgetBreach, ModelStats, TokenUsage, UsagePeriod,
+};
+use crate::config::schema::{CostConfig, CostDimension};
 use anyhow::{anyhow, Context, Result};
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use parking_lot::{Mutex, MutexGuard};
 use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
@@ -9,6 +13,19 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Label used in reports for records without a value for the grouped dimension.
+pub const UNATTRIBUTED: &str = "(unattributed)";
+
+/// Dimensions that `[[cost.budgets]]` can cap; spend is aggregated per value.
+const BUDGET_DIMENSIONS: [CostDimension; 6] = [
+    CostDimension::User,
+    CostDimension::Channel,
+    CostDimension::Session,
+    CostDimension::CronJob,
+    CostDimension::Workflow,
+    CostDimension::Tool,
+];
+
 /// Cost tracker for API usage monitoring and budget enforcement.
 pub struct CostTracker {
     config: CostConfig,
@@ -106,20 +123,144 @@ impl CostTracker {
         Ok(BudgetCheck::Allowed)
     }
 
-    /// Record a usage event.
+    /// Check the per-dimension budgets (`[[cost.budgets]]`) that apply to
+    /// `attribution`. Returns the first budget the request would exceed.
+    pub fn check_dimension_budgets(
+        &self,
+        attribution: &CostAttribution,
+        estimated_cost_usd: f64,
+    ) -> Result<Option<DimensionBudgetBreach>> {
+        if !self.config.enabled || self.config.budgets.is_empty() {
+            return Ok(None);
+        }
+
+        if !estimated_cost_usd.is_finite() || estimated_cost_usd < 0.0 {
+            return Err(anyhow!(
+                "Estimated cost must be a finite, non-negative value"
+            ));
+        }
+
+        let mut storage = self.lock_storage();
+        for budget in &self.config.budgets {
+            let Some(value) = attribution.get(budget.dimension) else {
+                continue;
+            };
+            if budget.key != "*" && budget.key != value {
+                continue;
+            }
+
+            let (daily_cost, monthly_cost) =
+                storage.get_dimension_costs(budget.dimension, value)?;
+            let limits = [
+                (UsagePeriod::Day, daily_cost, budget.daily_limit_usd),
+                (UsagePeriod::Month, monthly_cost, budget.monthly_limit_usd),
+            ];
+            for (period, current_usd, limit) in limits {
+                let Some(limit_usd) = limit else {
+                    continue;
+                };
+                if current_usd + estimated_cost_usd > limit_usd {
+                    return Ok(Some(DimensionBudgetBreach {
+                        dimension: budget.dimension,
+                        key: value.to_string(),
+                        period,
+                        current_usd,
+                        limit_usd,
+                        action: budget.action,
+                        route_down_model: budget.route_down_model.clone(),
+                    }));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Record a usage event, attributed to the current [`attribution::scope`].
     pub fn record_usage(&self, usage: TokenUsage) -> Result<()> {
+        self.record_usage_with_attribution(usage, attribution::current())
+    }
+
+    /// Record a usage event with explicit attribution (for callers outside
+    /// of a scope, such as the LLM proxy which knows the user directly).
+    pub fn record_usage_with_attribution(
+        &self,
+        usage: TokenUsage,
+        attribution: CostAttribution,
+    ) -> Result<()> {
+        self.record(CostRecord::new(&self.session_id, usage).with_attribution(attribution))
+    }
+
+    /// Record non-chat usage (embedding tokens, search requests, TTS/STT
+    /// minutes) priced from `[cost].metered_prices`.
+    pub fn record_metered(&self, kind: CostKind, resource: &str, units: f64) -> Result<()> {
+        let label = format!("{}/{resource}", kind.as_str());
+        let price = self.metered_price(&label);
+        self.record_charge(kind, resource, units, units / kind.price_unit() * price)
+    }
+
+    /// Record non-chat usage that the caller has already priced.
+    pub fn record_charge(
+        &self,
+        kind: CostKind,
+        resource: &str,
+        units: f64,
+        cost_usd: f64,
+    ) -> Result<()> {
+        if !units.is_finite() || units < 0.0 {
+            return Err(anyhow!(
+                "Metered units must be a finite, non-negative value"
+            ));
+        }
+
+        let label = format!("{}/{resource}", kind.as_str());
+        let mut usage = TokenUsage::new(&label, 0, 0, 0.0, 0.0);
+        usage.cost_usd = cost_usd;
+        if kind == CostKind::Embedding {
+            // Embedding units are tokens; keep token totals comparable.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let tokens = units as u64;
+            usage.input_tokens = tokens;
+            usage.total_tokens = tokens;
+        }
+
+        self.record(
+            CostRecord::new(&self.session_id, usage)
+                .with_units(kind, units)
+                .with_attribution(attribution::current()),
+        )
+    }
+
+    /// Most specific metered price for `label` (`kind/resource/...`), trying
+    /// each shorter prefix down to the bare kind. Unpriced usage costs $0.
+    fn metered_price(&self, label: &str) -> f64 {
+        let mut key = label;
+        loop {
+            if let Some(price) = self.config.metered_prices.get(key) {
+                return if price.is_finite() {
+                    price.max(0.0)
+                } else {
+                    0.0
+                };
+            }
+            match key.rsplit_once('/') {
+                Some((parent, _)) => key = parent,
+                None => return 0.0,
+            }
+        }
+    }
+
+    fn record(&self, record: CostRecord) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
 
-        if !usage.cost_usd.is_finite() || usage.cost_usd < 0.0 {
+        if !record.usage.cost_usd.is_finite() || record.usage.cost_usd < 0.0 {
             return Err(anyhow!(
                 "Token usage cost must be a finite, non-negative value"
             ));
         }
 
-        let record = CostRecord::new(&self.session_id, usage);
-
         // Persist first for durability guarantees.
         {
             let mut storage = self.lock_storage();
@@ -173,9 +314,60 @@ impl CostTracker {
         let storage = self.lock_storage();
         storage.get_cost_for_month(year, month)
     }
+
+    /// Group ledger spend since `since` (or all of it) by `group_by`.
+    pub fn report(
+        &self,
+        group_by: CostDimension,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<CostReport> {
+        let mut rows: HashMap<String, CostBreakdownRow> = HashMap::new();
+        let mut total_cost_usd = 0.0;
+        let mut request_count = 0;
+
+        self.lock_storage().for_each_record(|record| {
+            if since.is_some_and(|since| record.usage.timestamp < since) {
+                return;
+            }
+            let key = record.dimension_value(group_by).unwrap_or(UNATTRIBUTED);
+            let row = rows
+                .entry(key.to_string())
+                .or_insert_with(|| CostBreakdownRow {
+                    key: key.to_string(),
+                    ..CostBreakdownRow::default()
+                });
+            row.cost_usd += record.usage.cost_usd;
+            row.total_tokens += record.usage.total_tokens;
+            row.request_count += 1;
+            row.units += record.units.unwrap_or(0.0);
+            total_cost_usd += record.usage.cost_usd;
+            request_count += 1;
+        })?;
+
+        let mut rows: Vec<CostBreakdownRow> = rows.into_values().collect();
+        rows.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd).then(a.key.cmp(&b.key)));
+
+        Ok(CostReport {
+            group_by,
+            since,
+            total_cost_usd,
+            request_count,
+            rows,
+        })
+    }
+
+    /// Visit every ledger record, oldest first.
+    pub fn for_each_record(&self, on_record: impl FnMut(CostRecord)) -> Result<()> {
+        self.lock_storage().for_each_record(on_record)
+    }
+
+    /// Path of the JSONL ledger backing this tracker.
+    pub fn storage_path(&self) -> PathBuf {
+        self.lock_storage().path.clone()
+    }
 }
 
-fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
+pub(super) fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
     let storage_path = workspace_dir.join("state").join("costs.jsonl");
     let legacy_path = workspace_dir.join(".zeroclaw").join("costs.db");
 
@@ -225,11 +417,32 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
     by_model
 }
 
+fn accumulate_dimensions(
+    dimension_costs: &mut HashMap<(CostDimension, String), (f64, f64)>,
+    record: &CostRecord,
+    in_day: bool,
+) {
+    for dimension in BUDGET_DIMENSIONS {
+        let Some(value) = record.attribution.get(dimension) else {
+            continue;
+        };
+        let entry = dimension_costs
+            .entry((dimension, value.to_string()))
+            .or_insert((0.0, 0.0));
+        if in_day {
+            entry.0 += record.usage.cost_usd;
+        }
+        entry.1 += record.usage.cost_usd;
+    }
+}
+
 /// Persistent storage for cost records.
 struct CostStorage {
     path: PathBuf,
     daily_cost_usd: f64,
     monthly_cost_usd: f64,
+    /// (daily, monthly) spend per budget dimension value.
+    dimension_costs: HashMap<(CostDimension, String), (f64, f64)>,
     cached_day: NaiveDate,
     cached_year: i32,
     cached_month: u32,
@@ -248,6 +461,7 @@ impl CostStorage {
             path: path.to_path_buf(),
             daily_cost_usd: 0.0,
             monthly_cost_usd: 0.0,
+            dimension_costs: HashMap::new(),
             cached_day: now.date_naive(),
             cached_year: now.year(),
             cached_month: now.month(),
@@ -306,21 +520,26 @@ impl CostStorage {
     fn rebuild_aggregates(&mut self, day: NaiveDate, year: i32, month: u32) -> Result<()> {
         let mut daily_cost = 0.0;
         let mut monthly_cost = 0.0;
+        let mut dimension_costs = HashMap::new();
 
         self.for_each_record(|record| {
             let timestamp = record.usage.timestamp.naive_utc();
+            let in_day = timestamp.date() == day;
+            let in_month = timestamp.year() == year && timestamp.month() == month;
 
-            if timestamp.date() == day {
+            if in_day {
                 daily_cost += record.usage.cost_usd;
             }
 
-            if timestamp.year() == year && timestamp.month() == month {
+            if in_month {
                 monthly_cost += record.usage.cost_usd;
+                accumulate_dimensions(&mut dimension_costs, &record, in_day);
             }
         })?;
 
         self.daily_cost_usd = daily_cost;
         self.monthly_cost_usd = monthly_cost;
+        self.dimension_costs = dimension_costs;
         self.cached_day = day;
         self.cached_year = year;
         self.cached_month = month;
@@ -357,11 +576,13 @@ impl CostStorage {
         self.ensure_period_cache_current()?;
 
         let timestamp = record.usage.timestamp.naive_utc();
-        if timestamp.date() == self.cached_day {
+        let in_day = timestamp.date() == self.cached_day;
+        if in_day {
             self.daily_cost_usd += record.usage.cost_usd;
         }
         if timestamp.year() == self.cached_year && timestamp.month() == self.cached_month {
             self.monthly_cost_usd += record.usage.cost_usd;
+            accumulate_dimensions(&mut self.dimension_costs, &record, in_day);
         }
 
         Ok(())
@@ -373,6 +594,16 @@ impl CostStorage {
         Ok((self.daily_cost_usd, self.monthly_cost_usd))
     }
 
+    /// Get current day and month costs attributed to one dimension value.
+    fn get_dimension_costs(&mut self, dimension: CostDimension, value: &str) -> Result<(f64, f64)> {
+        self.ensure_period_cache_current()?;
+        Ok(self
+            .dimension_costs
+            .get(&(dimension, value.to_string()))
+            .copied()
+            .unwrap_or((0.0, 0.0)))
+    }
+
     /// Get cost for a specific date.
     fn get_cost_for_date(&self, date: NaiveDate) -> Result<f64> {
         let mut cost = 0.0;
@@ -404,6 +635,7 @@ impl CostStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::schema::{CostBudgetConfig, CostEnforcementMode};
     use tempfile::TempDir;
 
     fn enabled_config() -> CostConfig {
@@ -523,6 +755,98 @@ mod tests {
         assert!((today_cost - valid_usage.cost_usd).abs() < f64::EPSILON);
     }
 
+    #[tokio::test]
+    async fn dimension_budget_applies_only_to_matching_value() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            budgets: vec![CostBudgetConfig {
+                dimension: CostDimension::User,
+                key: "alice".into(),
+                daily_limit_usd: Some(0.01),
+                monthly_limit_usd: None,
+                action: CostEnforcementMode::RouteDown,
+                route_down_model: Some("cheap/model".into()),
+            }],
+            ..Default::default()
+        };
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+        let alice = CostAttribution::turn("telegram", "alice", "s1");
+        let bob = CostAttribution::turn("telegram", "bob", "s2");
+
+        attribution::scope(alice.clone(), async {
+            tracker
+                .record_usage(TokenUsage::new("test/model", 10_000, 5_000, 1.0, 2.0))
+                .unwrap();
+        })
+        .await;
+
+        let breach = tracker
+            .check_dimension_budgets(&alice, 0.001)
+            .unwrap()
+            .expect("alice is over budget");
+        assert_eq!(breach.key, "alice");
+        assert_eq!(breach.period, UsagePeriod::Day);
+        assert_eq!(breach.route_down_model.as_deref(), Some("cheap/model"));
+        assert!(tracker
+            .check_dimension_budgets(&bob, 0.001)
+            .unwrap()
+            .is_none());
+
+        // The per-value aggregates survive a reload from disk.
+        let reloaded = CostTracker::new(tracker.config.clone(), tmp.path()).unwrap();
+        assert!(reloaded
+            .check_dimension_budgets(&alice, 0.001)
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn report_groups_spend_by_dimension() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = enabled_config();
+        config.metered_prices = HashMap::from([
+            ("search".to_string(), 0.01),
+            ("search/brave".to_string(), 0.005),
+        ]);
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+
+        attribution::scope(CostAttribution::cron_job("digest"), async {
+            attribution::scope(CostAttribution::tool("web_search"), async {
+                tracker
+                    .record_metered(CostKind::Search, "brave", 2.0)
+                    .unwrap();
+                tracker
+                    .record_metered(CostKind::Search, "exa", 1.0)
+                    .unwrap();
+            })
+            .await;
+            tracker
+                .record_usage(TokenUsage::new("test/model", 1_000_000, 0, 1.0, 1.0))
+                .unwrap();
+        })
+        .await;
+        tracker
+            .record_usage(TokenUsage::new("test/model", 0, 1_000_000, 1.0, 1.0))
+            .unwrap();
+
+        let by_tool = tracker.report(CostDimension::Tool, None).unwrap();
+        assert_eq!(by_tool.request_count, 4);
+        assert!((by_tool.total_cost_usd - 2.02).abs() < 1e-9);
+        assert_eq!(by_tool.rows[0].key, UNATTRIBUTED);
+        let search = by_tool.rows.iter().find(|r| r.key == "web_search").unwrap();
+        assert!((search.cost_usd - 0.02).abs() < 1e-9);
+        assert!((search.units - 3.0).abs() < f64::EPSILON);
+
+        let by_job = tracker.report(CostDimension::CronJob, None).unwrap();
+        let digest = by_job.rows.iter().find(|r| r.key == "digest").unwrap();
+        assert_eq!(digest.request_count, 3);
+
+        let later = Utc::now() + chrono::Duration::minutes(1);
+        let empty = tracker.report(CostDimension::Kind, Some(later)).unwrap();
+        assert!(empty.rows.is_empty());
+    }
+
     #[test]
     fn invalid_budget_estimate_is_rejected() {
         let tmp = TempDir::new().unwrap();
@@ -1,3 +1,5 @@
+use super::attribution::CostAttribution;
+use crate::config::schema::{CostDimension, CostEnforcementMode};
 use serde::{Deserialize, Serialize};
 
 /// Token usage information from a single API call.
@@ -68,6 +70,43 @@ pub enum UsagePeriod {
     Month,
 }
 
+/// What a ledger entry paid for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostKind {
+    /// Chat/completion tokens.
+    #[default]
+    Llm,
+    /// Embedding tokens (`units` = tokens).
+    Embedding,
+    /// Search API requests (`units` = requests).
+    Search,
+    /// Speech synthesis (`units` = audio minutes).
+    Tts,
+    /// Speech recognition (`units` = audio minutes).
+    Stt,
+}
+
+impl CostKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Llm => "llm",
+            Self::Embedding => "embedding",
+            Self::Search => "search",
+            Self::Tts => "tts",
+            Self::Stt => "stt",
+        }
+    }
+
+    /// Quantity that `[cost].metered_prices` entries of this kind are priced per.
+    pub fn price_unit(self) -> f64 {
+        match self {
+            Self::Llm | Self::Embedding => 1_000_000.0,
+            Self::Search | Self::Tts | Self::Stt => 1.0,
+        }
+    }
+}
+
 /// A single cost record for persistent storage.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostRecord {
@@ -77,6 +116,15 @@ pub struct CostRecord {
     pub usage: TokenUsage,
     /// Session identifier (for grouping)
     pub session_id: String,
+    /// What the spend was for (records written before this field existed are `llm`)
+    #[serde(default)]
+    pub kind: CostKind,
+    /// Metered quantity for non-chat usage (tokens, requests or audio minutes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<f64>,
+    /// Who and what caused the spend
+    #[serde(default, skip_serializing_if = "CostAttribution::is_empty")]
+    pub attribution: CostAttribution,
 }
 
 impl CostRecord {
@@ -86,6 +134,31 @@ impl CostRecord {
             id: uuid::Uuid::new_v4().to_string(),
             usage,
             session_id: session_id.into(),
+            kind: CostKind::Llm,
+            units: None,
+            attribution: CostAttribution::default(),
+        }
+    }
+
+    /// Attach attribution to the record.
+    pub fn with_attribution(mut self, attribution: CostAttribution) -> Self {
+        self.attribution = attribution;
+        self
+    }
+
+    /// Mark the record as metered (non-chat) usage.
+    pub fn with_units(mut self, kind: CostKind, units: f64) -> Self {
+        self.kind = kind;
+        self.units = Some(units);
+        self
+    }
+
+    /// Grouping key for `dimension`, if the record has one.
+    pub fn dimension_value(&self, dimension: CostDimension) -> Option<&str> {
+        match dimension {
+            CostDimension::Model => Some(&self.usage.model),
+            CostDimension::Kind => Some(self.kind.as_str()),
+            _ => self.attribution.get(dimension),
         }
     }
 }
@@ -109,6 +182,19 @@ pub enum BudgetCheck {
     },
 }
 
+/// A per-dimension budget (`[[cost.budgets]]`) that a request would exceed.
+#[derive(Debug, Clone)]
+pub struct DimensionBudgetBreach {
+    pub dimension: CostDimension,
+    /// The value that is over budget (e.g. the user ID).
+    pub key: String,
+    pub period: UsagePeriod,
+    pub current_usd: f64,
+    pub limit_usd: f64,
+    pub action: CostEnforcementMode,
+    pub route_down_model: Option<String>,
+}
+
 /// Cost summary for reporting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostSummary {
@@ -139,6 +225,30 @@ pub struct ModelStats {
     pub request_count: usize,
 }
 
+/// Spend for one value of the grouped dimension.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostBreakdownRow {
+    /// Dimension value, or `(unattributed)`
+    pub key: String,
+    pub cost_usd: f64,
+    pub total_tokens: u64,
+    pub request_count: usize,
+    /// Metered units (search requests, audio minutes, embedding tokens) summed
+    /// across kinds; only meaningful when grouping by `kind`.
+    pub units: f64,
+}
+
+/// Ledger spend grouped by one dimension, most expensive first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostReport {
+    pub group_by: CostDimension,
+    /// Start of the reporting window (`None` = whole ledger)
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub total_cost_usd: f64,
+    pub request_count: usize,
+    pub rows: Vec<CostBreakdownRow>,
+}
+
 impl Default for CostSummary {
     fn default() -> Self {
         Self {
@@ -190,4 +300,15 @@ mod tests {
         assert!(!record.id.is_empty());
         assert_eq!(record.usage.model, "test/model");
     }
+
+    #[test]
+    fn legacy_cost_record_defaults_to_unattributed_llm() {
+        let usage = TokenUsage::new("test/model", 100, 50, 1.0, 2.0);
+        let legacy = serde_json::json!({"id": "a", "usage": usage, "session_id": "s"});
+        let record: CostRecord = serde_json::from_value(legacy).unwrap();
+        assert_eq!(record.kind, CostKind::Llm);
+        assert!(record.attribution.is_empty());
+        assert_eq!(record.dimension_value(CostDimension::Kind), Some("llm"));
+        assert_eq!(record.dimension_value(CostDimension::User), None);
+    }
 }
@@ -72,10 +72,16 @@ async fn execute_job_with_retry(
     let mut backoff_ms = config.reliability.provider_backoff_ms.max(200);
 
     for attempt in 0..=retries {
-        let (success, output) = match job.job_type {
-            JobType::Shell => run_job_command(config, security, job).await,
-            JobType::Agent => Box::pin(run_agent_job(config, security, job)).await,
-        };
+        let (success, output) = crate::cost::attribution::scope(
+            crate::cost::CostAttribution::cron_job(&job.id),
+            async {
+                match job.job_type {
+                    JobType::Shell => run_job_command(config, security, job).await,
+                    JobType::Agent => Box::pin(run_agent_job(config, security, job)).await,
+                }
+            },
+        )
+        .await;
         last_output = output;
 
         if success {
@@ -174,7 +180,11 @@ async fn run_agent_job(
     }
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
-    let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
+    let mut prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
+    if let Some(resolved) = crate::inbox::cron_resume_context(&job.id) {
+        prefixed_prompt.push_str("\n\n");
+        prefixed_prompt.push_str(&resolved);
+    }
 
     // Economy tier: use Gemini Flash Lite for cron jobs when no explicit model is set.
     // Cost-effective for routine tasks (weather alerts, schedule reminders).
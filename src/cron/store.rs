@@ -254,6 +254,22 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     get_job(config, job_id)
 }
 
+/// Make a job due on the next scheduler poll without changing its schedule.
+pub fn schedule_now(config: &Config, job_id: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE cron_jobs SET next_run = ?1 WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), job_id],
+            )
+            .context("Failed to schedule cron job")?;
+        if updated == 0 {
+            anyhow::bail!("Cron job '{job_id}' not found");
+        }
+        Ok(())
+    })
+}
+
 pub fn record_last_run(
     config: &Config,
     job_id: &str,
@@ -42,6 +42,15 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
 
+    if config.security.estop.enabled {
+        if let Some(config_dir) = config.config_path.parent() {
+            handles.push(crate::security::estop::spawn_turn_cancellation_watcher(
+                &config.security.estop,
+                config_dir,
+            ));
+        }
+    }
+
     {
         let gateway_cfg = config.clone();
         let gateway_host = host.clone();
@@ -104,6 +113,32 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.inbox.enabled {
+        let inbox_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "inbox",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = inbox_cfg.clone();
+                async move { crate::inbox::run_notifier(cfg).await }
+            },
+        ));
+    }
+
+    if config.goal_loop.enabled {
+        let goals_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "goals",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = goals_cfg.clone();
+                async move { Box::pin(crate::goals::runner::run(cfg)).await }
+            },
+        ));
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
@@ -192,7 +227,11 @@ where
 
 async fn run_heartbeat_worker(config: Config) -> Result<()> {
     let observer: std::sync::Arc<dyn crate::observability::Observer> =
-        std::sync::Arc::from(crate::observability::create_observer(&config.observability));
+        std::sync::Arc::from(crate::observability::create_observer_with_ledger(
+            &config.observability,
+            &config.cost,
+            &config.workspace_dir,
+        ));
     let engine = crate::heartbeat::engine::HeartbeatEngine::new(
         config.heartbeat.clone(),
         config.workspace_dir.clone(),
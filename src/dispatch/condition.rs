@@ -46,6 +46,15 @@ pub fn evaluate_condition(condition: &str, payload: Option<&str>) -> bool {
     }
 }
 
+/// Resolve a `$.path.to.field` path against a JSON document and render the
+/// value as text (strings unquoted, everything else as JSON). `$` alone
+/// selects the whole document. Returns `None` when the path does not resolve.
+pub fn extract_json_path(document: &Value, path: &str) -> Option<String> {
+    let rest = path.trim().strip_prefix('$')?;
+    let segments: Vec<&str> = rest.split('.').filter(|s| !s.is_empty()).collect();
+    resolve_json_path(document, &segments).map(value_as_string)
+}
+
 /// Evaluate `$.path.to.field op value` against a JSON payload.
 fn evaluate_json_path_condition(path_and_op: &str, payload: &str) -> bool {
     let json: Value = match serde_json::from_str(payload) {
@@ -364,4 +373,25 @@ mod tests {
         assert!(evaluate_condition("> 3.14", Some("3.15")));
         assert!(!evaluate_condition("> 3.14", Some("3.13")));
     }
+
+    #[test]
+    fn extract_json_path_renders_values() {
+        let doc: Value =
+            serde_json::from_str(r#"{"repo":{"name":"zc","stars":3},"tags":["a","b"]}"#).unwrap();
+        assert_eq!(
+            extract_json_path(&doc, "$.repo.name").as_deref(),
+            Some("zc")
+        );
+        assert_eq!(
+            extract_json_path(&doc, "$.repo.stars").as_deref(),
+            Some("3")
+        );
+        assert_eq!(extract_json_path(&doc, "$.tags.1").as_deref(), Some("b"));
+        assert_eq!(
+            extract_json_path(&doc, "$.repo").as_deref(),
+            Some(r#"{"name":"zc","stars":3}"#)
+        );
+        assert!(extract_json_path(&doc, "$.missing").is_none());
+        assert!(extract_json_path(&doc, "repo.name").is_none());
+    }
 }
@@ -10,6 +10,13 @@
 //! - [`audit`] — persists events and dispatch results to the memory backend
 //! - [`router`] — handler registration and event fan-out
 //! - [`types`] — shared event/result types
+//! - [`webhook`] — signed `/hooks/{name}` endpoints and their reactions
+//!
+//! ## Shared router
+//!
+//! The gateway installs one router and audit logger for the whole process
+//! with [`init_shared`]. Webhook reactions register on it and hardware
+//! devices publish their events into it; see [`shared`].
 //!
 //! ## Origin
 //!
@@ -64,6 +71,7 @@ pub mod handlers;
 pub mod mqtt;
 pub mod router;
 pub mod types;
+pub mod webhook;
 
 // These re-exports are part of the public API but the binary crate does not
 // use them directly yet. Allow unused so `cargo check` stays clean while the
@@ -78,3 +86,30 @@ pub use handlers::{AgentTriggerHandler, EventFilter, NotificationHandler};
 pub use router::{EventHandler, EventRouter};
 #[allow(unused_imports)]
 pub use types::{DispatchEvent, DispatchResult, EventSource, HandlerOutcome};
+
+use std::sync::{Arc, LazyLock, RwLock};
+
+static SHARED: LazyLock<RwLock<Option<SharedDispatch>>> = LazyLock::new(|| RwLock::new(None));
+
+/// The router and audit logger shared by every event source in the process.
+#[derive(Clone)]
+pub struct SharedDispatch {
+    pub router: Arc<EventRouter>,
+    pub audit: Arc<DispatchAuditLogger>,
+}
+
+/// Create the process-wide router, auditing to `memory`, replacing any
+/// previous one.
+pub fn init_shared(memory: Arc<dyn crate::memory::traits::Memory>) -> SharedDispatch {
+    let dispatch = SharedDispatch {
+        router: Arc::new(EventRouter::new()),
+        audit: Arc::new(DispatchAuditLogger::new(memory)),
+    };
+    *SHARED.write().unwrap_or_else(|e| e.into_inner()) = Some(dispatch.clone());
+    dispatch
+}
+
+/// The process-wide router, if the gateway has installed one.
+pub fn shared() -> Option<SharedDispatch> {
+    SHARED.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
@@ -45,7 +45,8 @@ pub trait EventHandler: Send + Sync {
 ///
 /// Used by:
 /// - `src/peripherals/signal.rs` (peripheral GPIO/sensor events)
-/// - Future MQTT/webhook/cron callers
+/// - `src/dispatch/webhook.rs` (signed `/hooks/{name}` deliveries)
+/// - Future MQTT/cron callers
 pub struct EventRouter {
     handlers: RwLock<Vec<Arc<dyn EventHandler>>>,
 }
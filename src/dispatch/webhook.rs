@@ -0,0 +1,723 @@
+//! Named webhook endpoints that publish into the dispatch router.
+//!
+//! `[[webhooks.endpoints]]` are served by the gateway at `POST /hooks/{name}`.
+//! Every delivery is checked against the endpoint's HMAC-SHA256 secret in
+//! one of three styles (GitHub, Stripe or the generic `X-Webhook-Signature`),
+//! rejected if it is a replay, and turned into a
+//! `DispatchEvent { source: Webhook, topic, payload }` whose topic and payload
+//! are extracted from the body with JSON paths. Events and handler outcomes
+//! are recorded through `DispatchAuditLogger`.
+//!
+//! ## Replay protection
+//!
+//! - Stripe and generic deliveries carry a signed timestamp that must be
+//!   within `replay_window_secs` of local time; their signatures are then
+//!   remembered until the timestamp could no longer pass that check.
+//! - GitHub signs no timestamp, so signatures are remembered for a day
+//!   instead. `X-GitHub-Delivery` is not covered by the HMAC and is never
+//!   part of the replay key.
+//! - Keys are lowercased, so re-encoding the hex digest is still a replay.
+//!
+//! ## Reactions
+//!
+//! Each `[[webhooks.reactions]]` entry registers a `NotificationHandler` or an
+//! `AgentTriggerHandler` named `webhook:<reaction>`, filtered by topic
+//! prefix. Agent reactions are run one at a time by a worker owned by this
+//! module, with spend charged to the `webhook:<reaction>` cost workflow.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use super::audit::DispatchAuditLogger;
+use super::condition::{evaluate_condition, extract_json_path};
+use super::handlers::{AgentTriggerHandler, EventFilter, NotificationHandler};
+use super::router::EventRouter;
+use super::types::{DispatchEvent, DispatchResult, EventSource};
+use super::SharedDispatch;
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::schema::{
+    WebhookEndpointConfig, WebhookReactionConfig, WebhookSignature, WebhooksConfig,
+};
+use crate::config::Config;
+use crate::observability::runtime_trace;
+
+/// How long GitHub signatures are remembered (GitHub signs no timestamp).
+const DELIVERY_RETENTION_SECS: u64 = 86_400;
+/// Upper bound on remembered deliveries; the oldest is evicted first.
+const MAX_SEEN_DELIVERIES: usize = 10_000;
+/// Agent reactions that may queue before new ones are reported as failed.
+const AGENT_QUEUE_CAPACITY: usize = 32;
+const DEFAULT_TOPIC: &str = "webhook/{name}";
+const DEFAULT_TEMPLATE: &str = "[{source}] {topic}: {payload}";
+
+static INGRESS: LazyLock<RwLock<Option<Arc<WebhookIngress>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Why a delivery was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// No endpoint with that name.
+    UnknownEndpoint,
+    /// Missing or invalid signature, or a timestamp outside the replay window.
+    Unauthorized(&'static str),
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownEndpoint => write!(f, "unknown webhook endpoint"),
+            Self::Unauthorized(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// What became of an authenticated delivery.
+#[derive(Debug)]
+pub enum Delivery {
+    /// Routed to the matching handlers.
+    Dispatched(DispatchResult),
+    /// The endpoint's `condition` did not match the body.
+    Filtered,
+    /// Already received within the replay window.
+    Duplicate,
+}
+
+/// Verifies webhook deliveries and publishes them to the dispatch router.
+pub struct WebhookIngress {
+    config: WebhooksConfig,
+    router: Arc<EventRouter>,
+    audit: Arc<DispatchAuditLogger>,
+    /// Replay keys and the unix time until which they are remembered.
+    seen: Mutex<HashMap<String, u64>>,
+}
+
+impl WebhookIngress {
+    pub fn new(
+        config: WebhooksConfig,
+        router: Arc<EventRouter>,
+        audit: Arc<DispatchAuditLogger>,
+    ) -> Self {
+        Self {
+            config,
+            router,
+            audit,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Verify a delivery to endpoint `name` and dispatch it.
+    pub async fn receive(
+        &self,
+        name: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Delivery, Rejection> {
+        self.receive_at(name, headers, body, unix_now()).await
+    }
+
+    async fn receive_at(
+        &self,
+        name: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: u64,
+    ) -> Result<Delivery, Rejection> {
+        let endpoint = self
+            .config
+            .endpoints
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or(Rejection::UnknownEndpoint)?;
+
+        let window = self.config.replay_window_secs;
+        if let Some((key, retention)) = verify(endpoint, headers, body, now, window)? {
+            if !self.remember(format!("{name}:{key}"), now + retention, now) {
+                return Ok(Delivery::Duplicate);
+            }
+        }
+
+        let Some(event) = build_event(endpoint, headers, body) else {
+            return Ok(Delivery::Filtered);
+        };
+        if let Err(error) = self.audit.log_event(&event).await {
+            warn!(endpoint = name, "Webhook: failed to record event: {error}");
+        }
+        let result = self.router.dispatch(event).await;
+        if let Err(error) = self.audit.log_result(&result).await {
+            warn!(endpoint = name, "Webhook: failed to record result: {error}");
+        }
+        Ok(Delivery::Dispatched(result))
+    }
+
+    /// Record `key` until `expires_at`; false if it is already recorded.
+    fn remember(&self, key: String, expires_at: u64, now: u64) -> bool {
+        let mut seen = self.seen.lock();
+        seen.retain(|_, until| *until > now);
+        if seen.contains_key(&key) {
+            return false;
+        }
+        if seen.len() >= MAX_SEEN_DELIVERIES {
+            let oldest = seen
+                .iter()
+                .min_by_key(|(_, until)| **until)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                seen.remove(&oldest);
+            }
+        }
+        seen.insert(key, expires_at);
+        true
+    }
+}
+
+/// Build the process-wide ingress from `[webhooks]` (or clear it when
+/// disabled). Reactions register on `dispatch.router`.
+pub fn init_from_config(config: &Config, dispatch: &SharedDispatch) {
+    let ingress = config
+        .webhooks
+        .enabled
+        .then(|| Arc::new(build(config, dispatch)));
+    let mut guard = INGRESS.write().unwrap_or_else(|e| e.into_inner());
+    *guard = ingress;
+}
+
+/// The process-wide ingress, if webhooks are enabled.
+pub fn global() -> Option<Arc<WebhookIngress>> {
+    INGRESS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn build(config: &Config, dispatch: &SharedDispatch) -> WebhookIngress {
+    let shared = Arc::new(config.clone());
+    let router = &dispatch.router;
+    let (tx, rx) = mpsc::channel(AGENT_QUEUE_CAPACITY);
+    let mut agent_reactions = HashMap::new();
+
+    for reaction in &config.webhooks.reactions {
+        let name = handler_name(reaction);
+        let mut filter = EventFilter::any().source(EventSource::Webhook);
+        if let Some(prefix) = reaction.topic_prefix.as_deref() {
+            filter = filter.topic_prefix(prefix);
+        }
+        if let Some(prompt) = reaction.prompt.as_deref() {
+            let handler = AgentTriggerHandler::new(name.clone(), tx.clone(), prompt);
+            router.register(Arc::new(handler.with_filter(filter)));
+            agent_reactions.insert(name, reaction.clone());
+        } else if let (Some(channel), Some(to)) = (&reaction.channel, &reaction.to) {
+            let channel = Arc::new(AnnouncementChannel {
+                config: Arc::clone(&shared),
+                name: channel.clone(),
+            });
+            let template = reaction.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+            let handler = NotificationHandler::new(name, channel, to, template);
+            router.register(Arc::new(handler.with_filter(filter)));
+        }
+    }
+    if !agent_reactions.is_empty() {
+        tokio::spawn(run_agent_reactions(shared, agent_reactions, rx));
+    }
+
+    WebhookIngress::new(
+        config.webhooks.clone(),
+        Arc::clone(router),
+        Arc::clone(&dispatch.audit),
+    )
+}
+
+fn handler_name(reaction: &WebhookReactionConfig) -> String {
+    format!("webhook:{}", reaction.name.trim())
+}
+
+// ── Verification ────────────────────────────────────────────────
+
+/// Check the delivery's signature. On success returns the key that
+/// identifies it for replay detection and how long to remember that key.
+fn verify(
+    endpoint: &WebhookEndpointConfig,
+    headers: &HeaderMap,
+    body: &[u8],
+    now: u64,
+    window: u64,
+) -> Result<Option<(String, u64)>, Rejection> {
+    let secret = endpoint
+        .secret
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or_default();
+    let signature_header = endpoint.signature_header.as_deref();
+    match endpoint.signature {
+        WebhookSignature::None => Ok(None),
+        WebhookSignature::Github => {
+            let signature = header(headers, signature_header.unwrap_or("X-Hub-Signature-256"))
+                .ok_or(Rejection::Unauthorized("missing signature"))?;
+            let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+            if !hmac_matches(secret, body, signature) {
+                return Err(Rejection::Unauthorized("invalid signature"));
+            }
+            Ok(Some((
+                replay_key(signature),
+                DELIVERY_RETENTION_SECS.max(window),
+            )))
+        }
+        WebhookSignature::Stripe => {
+            let raw = header(headers, signature_header.unwrap_or("Stripe-Signature"))
+                .ok_or(Rejection::Unauthorized("missing signature"))?;
+            let mut timestamp = None;
+            let mut candidates = Vec::new();
+            for part in raw.split(',') {
+                match part.trim().split_once('=') {
+                    Some(("t", value)) => timestamp = Some(value),
+                    Some(("v1", value)) => candidates.push(value),
+                    _ => {}
+                }
+            }
+            let timestamp = timestamp.ok_or(Rejection::Unauthorized("missing timestamp"))?;
+            check_timestamp(timestamp, now, window)?;
+            let signed = [timestamp.as_bytes(), b".", body].concat();
+            let signature = candidates
+                .into_iter()
+                .find(|candidate| hmac_matches(secret, &signed, candidate))
+                .ok_or(Rejection::Unauthorized("invalid signature"))?;
+            Ok(Some((replay_key(signature), window * 2)))
+        }
+        WebhookSignature::Sha256 => {
+            let signature = header(headers, signature_header.unwrap_or("X-Webhook-Signature"))
+                .ok_or(Rejection::Unauthorized("missing signature"))?;
+            let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+            let timestamp_header = endpoint
+                .timestamp_header
+                .as_deref()
+                .unwrap_or("X-Webhook-Timestamp");
+            let timestamp = header(headers, timestamp_header)
+                .ok_or(Rejection::Unauthorized("missing timestamp"))?;
+            check_timestamp(timestamp, now, window)?;
+            let signed = [timestamp.as_bytes(), b".", body].concat();
+            if !hmac_matches(secret, &signed, signature) {
+                return Err(Rejection::Unauthorized("invalid signature"));
+            }
+            Ok(Some((replay_key(signature), window * 2)))
+        }
+    }
+}
+
+/// Replay key for a verified hex signature. Hex decoding ignores case, so
+/// the key must too.
+fn replay_key(signature: &str) -> String {
+    signature.trim().to_ascii_lowercase()
+}
+
+fn check_timestamp(raw: &str, now: u64, window: u64) -> Result<(), Rejection> {
+    let timestamp: u64 = raw
+        .trim()
+        .parse()
+        .map_err(|_| Rejection::Unauthorized("invalid timestamp"))?;
+    if timestamp.abs_diff(now) > window {
+        return Err(Rejection::Unauthorized("timestamp outside replay window"));
+    }
+    Ok(())
+}
+
+/// Constant-time check of a hex HMAC-SHA256 signature.
+fn hmac_matches(secret: &str, message: &[u8], hex_signature: &str) -> bool {
+    use ring::hmac;
+
+    let Ok(expected) = hex::decode(hex_signature.trim()) else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, message, &expected).is_ok()
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+// ── Extraction ──────────────────────────────────────────────────
+
+/// Turn a verified delivery into an event; `None` when the endpoint's
+/// condition does not match.
+fn build_event(
+    endpoint: &WebhookEndpointConfig,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Option<DispatchEvent> {
+    let text = String::from_utf8_lossy(body);
+    if let Some(condition) = endpoint.condition.as_deref() {
+        if !evaluate_condition(condition, Some(&text)) {
+            return None;
+        }
+    }
+    let document: Option<Value> = serde_json::from_slice(body).ok();
+    let topic = render_topic(
+        endpoint.topic.as_deref().unwrap_or(DEFAULT_TOPIC),
+        &endpoint.name,
+        headers,
+        document.as_ref(),
+    );
+    let payload = match endpoint.payload.as_deref() {
+        Some(path) => document
+            .as_ref()
+            .and_then(|doc| extract_json_path(doc, path)),
+        None => (!text.is_empty()).then(|| text.into_owned()),
+    };
+    Some(DispatchEvent::new(
+        EventSource::Webhook,
+        Some(topic),
+        payload,
+    ))
+}
+
+/// Fill `{name}`, `{header:X-Name}` and `{$.json.path}` placeholders.
+/// Placeholders that do not resolve render empty; unknown ones are kept.
+fn render_topic(
+    template: &str,
+    name: &str,
+    headers: &HeaderMap,
+    document: Option<&Value>,
+) -> String {
+    let mut topic = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        topic.push_str(&rest[..start]);
+        let key = &rest[start + 1..start + len];
+        if key == "name" {
+            topic.push_str(name);
+        } else if let Some(header_name) = key.strip_prefix("header:") {
+            topic.push_str(header(headers, header_name).unwrap_or_default());
+        } else if key.starts_with('$') {
+            if let Some(value) = document.and_then(|doc| extract_json_path(doc, key)) {
+                topic.push_str(&value);
+            }
+        } else {
+            topic.push_str(&rest[start..=start + len]);
+        }
+        rest = &rest[start + len + 1..];
+    }
+    topic.push_str(rest);
+    topic
+}
+
+// ── Reactions ───────────────────────────────────────────────────
+
+/// Send-only channel that delivers the way cron announcements do.
+struct AnnouncementChannel {
+    config: Arc<Config>,
+    name: String,
+}
+
+#[async_trait]
+impl Channel for AnnouncementChannel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        crate::cron::scheduler::deliver_announcement(
+            &self.config,
+            &self.name,
+            &message.recipient,
+            &message.content,
+        )
+        .await
+    }
+
+    async fn listen(&self, _tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        anyhow::bail!("{} is send-only for webhook reactions", self.name)
+    }
+}
+
+/// Run queued agent reactions one at a time until the router is dropped.
+async fn run_agent_reactions(
+    config: Arc<Config>,
+    reactions: HashMap<String, WebhookReactionConfig>,
+    mut rx: mpsc::Receiver<ChannelMessage>,
+) {
+    while let Some(message) = rx.recv().await {
+        let handler = message
+            .sender
+            .strip_prefix("dispatch::")
+            .unwrap_or(&message.sender);
+        if let Some(reaction) = reactions.get(handler) {
+            run_agent_reaction(&config, handler, reaction, message.content).await;
+        }
+    }
+}
+
+async fn run_agent_reaction(
+    config: &Config,
+    handler: &str,
+    reaction: &WebhookReactionConfig,
+    prompt: String,
+) {
+    let timeout_secs = config.webhooks.agent_timeout_secs.max(1);
+    let run = crate::cost::attribution::scope(
+        crate::cost::CostAttribution::workflow(handler),
+        Box::pin(crate::agent::run(
+            config.clone(),
+            Some(prompt),
+            None,
+            None,
+            config.default_temperature,
+            vec![],
+            false,
+            None,
+        )),
+    );
+    let (success, output) = match tokio::time::timeout(Duration::from_secs(timeout_secs), run).await
+    {
+        Ok(Ok(output)) => (true, output),
+        Ok(Err(error)) => (false, format!("error: {error}")),
+        Err(_) => (false, format!("error: timed out after {timeout_secs}s")),
+    };
+    runtime_trace::record_event(
+        "webhook_reaction",
+        reaction.channel.as_deref(),
+        None,
+        None,
+        None,
+        Some(success),
+        Some(&crate::util::truncate_with_ellipsis(&output, 500)),
+        serde_json::json!({ "reaction": reaction.name }),
+    );
+    if !success {
+        warn!(reaction = %reaction.name, "Webhook agent reaction failed: {output}");
+        return;
+    }
+    if let (Some(channel), Some(to)) = (&reaction.channel, &reaction.to) {
+        if let Err(error) =
+            crate::cron::scheduler::deliver_announcement(config, channel, to, &output).await
+        {
+            warn!(reaction = %reaction.name, "Webhook reaction delivery failed: {error}");
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatch::router::EventHandler;
+    use crate::dispatch::types::HandlerOutcome;
+    use crate::memory::traits::Memory;
+
+    const NOW: u64 = 1_760_000_000;
+
+    struct RecordingHandler {
+        events: Mutex<Vec<DispatchEvent>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for RecordingHandler {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn matches(&self, event: &DispatchEvent) -> bool {
+            event.source == EventSource::Webhook
+        }
+
+        async fn handle(&self, event: &DispatchEvent) -> anyhow::Result<HandlerOutcome> {
+            self.events.lock().push(event.clone());
+            Ok(HandlerOutcome::Handled {
+                summary: "recorded".into(),
+            })
+        }
+    }
+
+    fn endpoint(name: &str, signature: WebhookSignature) -> WebhookEndpointConfig {
+        WebhookEndpointConfig {
+            name: name.into(),
+            signature,
+            secret: Some("s3cret".into()),
+            signature_header: None,
+            timestamp_header: None,
+            topic: None,
+            payload: None,
+            condition: None,
+        }
+    }
+
+    fn sign(message: &[u8]) -> String {
+        use ring::hmac;
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"s3cret");
+        hex::encode(hmac::sign(&key, message).as_ref())
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    fn ingress(
+        endpoints: Vec<WebhookEndpointConfig>,
+    ) -> (WebhookIngress, Arc<RecordingHandler>, tempfile::TempDir) {
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let memory: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let recorder = Arc::new(RecordingHandler {
+            events: Mutex::new(Vec::new()),
+        });
+        let router = Arc::new(EventRouter::new());
+        router.register(recorder.clone());
+        let config = WebhooksConfig {
+            enabled: true,
+            endpoints,
+            ..WebhooksConfig::default()
+        };
+        let audit = Arc::new(DispatchAuditLogger::new(memory));
+        (WebhookIngress::new(config, router, audit), recorder, tmp)
+    }
+
+    #[tokio::test]
+    async fn github_deliveries_are_verified_and_deduplicated() {
+        let (ingress, recorder, _tmp) = ingress(vec![endpoint("gh", WebhookSignature::Github)]);
+        let body = br#"{"zen":"hi"}"#;
+        let good = headers(&[
+            ("x-hub-signature-256", format!("sha256={}", sign(body))),
+            ("x-github-delivery", "d-1".into()),
+        ]);
+
+        let delivery = ingress.receive_at("gh", &good, body, NOW).await.unwrap();
+        let Delivery::Dispatched(result) = delivery else {
+            panic!("expected dispatch, got {delivery:?}");
+        };
+        assert_eq!(result.handled_count(), 1);
+        let audited = ingress.audit.get_event(&result.event_id).await.unwrap();
+        assert_eq!(audited.unwrap().topic.as_deref(), Some("webhook/gh"));
+
+        let replay = ingress.receive_at("gh", &good, body, NOW + 60).await;
+        assert!(matches!(replay, Ok(Delivery::Duplicate)));
+
+        // The delivery id is unsigned: changing it or the digest's case
+        // must not get a replay through.
+        let renamed = headers(&[
+            ("x-hub-signature-256", format!("sha256={}", sign(body))),
+            ("x-github-delivery", "d-2".into()),
+        ]);
+        let replay = ingress.receive_at("gh", &renamed, body, NOW + 120).await;
+        assert!(matches!(replay, Ok(Delivery::Duplicate)));
+        let upper = headers(&[(
+            "x-hub-signature-256",
+            format!("sha256={}", sign(body).to_uppercase()),
+        )]);
+        let replay = ingress.receive_at("gh", &upper, body, NOW + 180).await;
+        assert!(matches!(replay, Ok(Delivery::Duplicate)));
+
+        let forged = headers(&[("x-hub-signature-256", format!("sha256={}", sign(b"other")))]);
+        let rejected = ingress.receive_at("gh", &forged, body, NOW).await;
+        assert_eq!(
+            rejected.unwrap_err(),
+            Rejection::Unauthorized("invalid signature")
+        );
+        assert_eq!(
+            ingress
+                .receive_at("nope", &good, body, NOW)
+                .await
+                .unwrap_err(),
+            Rejection::UnknownEndpoint
+        );
+        assert_eq!(recorder.events.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stripe_signatures_must_be_fresh() {
+        let (ingress, _, _tmp) = ingress(vec![endpoint("pay", WebhookSignature::Stripe)]);
+        let body = br#"{"type":"invoice.paid"}"#;
+        let stripe = |t: u64| {
+            let signed = [t.to_string().as_bytes(), b".", body].concat();
+            headers(&[(
+                "stripe-signature",
+                format!("t={t},v1=deadbeef,v1={}", sign(&signed)),
+            )])
+        };
+
+        let stale = ingress
+            .receive_at("pay", &stripe(NOW - 301), body, NOW)
+            .await;
+        assert_eq!(
+            stale.unwrap_err(),
+            Rejection::Unauthorized("timestamp outside replay window")
+        );
+        let fresh = ingress
+            .receive_at("pay", &stripe(NOW - 30), body, NOW)
+            .await;
+        assert!(matches!(fresh, Ok(Delivery::Dispatched(_))));
+        let replay = ingress
+            .receive_at("pay", &stripe(NOW - 30), body, NOW)
+            .await;
+        assert!(matches!(replay, Ok(Delivery::Duplicate)));
+    }
+
+    #[tokio::test]
+    async fn sha256_deliveries_extract_topic_and_payload() {
+        let mut hook = endpoint("ci", WebhookSignature::Sha256);
+        hook.topic = Some("ci/{$.pipeline.name}/{header:X-Event}".into());
+        hook.payload = Some("$.pipeline.status".into());
+        hook.condition = Some("$.pipeline.status != running".into());
+        let (ingress, recorder, _tmp) = ingress(vec![hook]);
+
+        let send = |body: &'static [u8], id: &str| {
+            let ts = NOW.to_string();
+            let signed = [ts.as_bytes(), b".", body].concat();
+            let map = headers(&[
+                ("x-webhook-signature", sign(&signed)),
+                ("x-webhook-timestamp", ts),
+                ("x-event", id.to_string()),
+            ]);
+            (map, body)
+        };
+
+        let (map, body) = send(
+            br#"{"pipeline":{"name":"deploy","status":"failed"}}"#,
+            "done",
+        );
+        let delivery = ingress.receive_at("ci", &map, body, NOW).await.unwrap();
+        assert!(matches!(delivery, Delivery::Dispatched(_)));
+
+        let (map, body) = send(
+            br#"{"pipeline":{"name":"deploy","status":"running"}}"#,
+            "tick",
+        );
+        let delivery = ingress.receive_at("ci", &map, body, NOW).await.unwrap();
+        assert!(matches!(delivery, Delivery::Filtered));
+
+        let (mut map, body) = send(br#"{"pipeline":{"name":"x","status":"ok"}}"#, "done");
+        map.remove("x-webhook-timestamp");
+        let missing = ingress.receive_at("ci", &map, body, NOW).await;
+        assert_eq!(
+            missing.unwrap_err(),
+            Rejection::Unauthorized("missing timestamp")
+        );
+
+        let events = recorder.events.lock();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].topic.as_deref(), Some("ci/deploy/done"));
+        assert_eq!(events[0].payload.as_deref(), Some("failed"));
+    }
+}
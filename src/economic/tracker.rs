@@ -2,6 +2,9 @@
 //!
 //! Tracks balance, token costs, work income, and survival status following
 //! the ClawWork LiveBench economic model. Persists state to JSONL files.
+//! Every charge is also appended to the unified cost ledger
+//! ([`crate::cost`]), so budgets and `zeroclaw cost report` include it; the
+//! totals kept here only drive the balance and survival status.
 
 use super::costs::{
     ApiCallRecord, ApiUsageSummary, BalanceRecord, CostBreakdown, LlmCallRecord, LlmUsageSummary,
@@ -284,6 +287,16 @@ impl EconomicTracker {
                 .calculate_cost(input_tokens, output_tokens)
         });
 
+        let mut usage = crate::cost::TokenUsage::new(
+            format!("{}/{api_name}", self.signature),
+            input_tokens,
+            output_tokens,
+            0.0,
+            0.0,
+        );
+        usage.cost_usd = cost;
+        crate::cost::record_usage(usage);
+
         let mut state = self.state.lock();
 
         // Update session tracking
@@ -360,6 +373,24 @@ impl EconomicTracker {
         price_per_million: Option<f64>,
         pricing_model: PricingModel,
     ) {
+        let api_lower = api_name.to_lowercase();
+        let is_search = api_lower.contains("search")
+            || api_lower.contains("jina")
+            || api_lower.contains("tavily");
+        if is_search {
+            crate::cost::record_charge(crate::cost::CostKind::Search, api_name, 1.0, cost);
+        } else {
+            let mut usage = crate::cost::TokenUsage::new(
+                format!("{}/{api_name}", self.signature),
+                tokens.unwrap_or(0),
+                0,
+                0.0,
+                0.0,
+            );
+            usage.cost_usd = cost;
+            crate::cost::record_usage(usage);
+        }
+
         let mut state = self.state.lock();
 
         // Update session/daily
@@ -367,11 +398,7 @@ impl EconomicTracker {
         state.daily.cost += cost;
 
         // Categorize by API type
-        let api_lower = api_name.to_lowercase();
-        if api_lower.contains("search")
-            || api_lower.contains("jina")
-            || api_lower.contains("tavily")
-        {
+        if is_search {
             state.task.costs.search_api += cost;
         } else if api_lower.contains("ocr") {
             state.task.costs.ocr_api += cost;
@@ -16,17 +16,24 @@
 //! - GET  /api/admin/usage/users — Per-user usage breakdown
 //! - GET  /api/admin/sessions — Active sessions
 //! - GET  /api/admin/overview — Dashboard overview (all data in one call)
+//! - GET  /api/admin/api-tokens — List scoped API tokens
+//! - POST /api/admin/api-tokens — Create a scoped API token
+//! - DELETE /api/admin/api-tokens/{id} — Revoke a scoped API token
+//! - GET  /api/admin/tenants — List tenants with per-user storage
+//! - POST /api/admin/tenants/{id}/export — Export one tenant's storage
+//! - DELETE /api/admin/tenants/{id} — Delete one tenant's storage and account
 
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 
 use super::AppState;
+use crate::security::api_tokens::ApiTokenStore;
 
 /// Online threshold: device is "online" if last_seen within this window.
 const DEVICE_ONLINE_THRESHOLD_SECS: u64 = 120;
@@ -43,6 +50,14 @@ pub fn admin_router() -> Router<AppState> {
         .route("/usage/users", get(handle_admin_usage_users))
         .route("/sessions", get(handle_admin_sessions))
         .route("/overview", get(handle_admin_overview))
+        .route(
+            "/api-tokens",
+            get(handle_admin_api_tokens_list).post(handle_admin_api_tokens_create),
+        )
+        .route("/api-tokens/{id}", delete(handle_admin_api_tokens_revoke))
+        .route("/tenants", get(handle_admin_tenants_list))
+        .route("/tenants/{id}", delete(handle_admin_tenants_delete))
+        .route("/tenants/{id}/export", post(handle_admin_tenants_export))
 }
 
 // ── Auth ────────────────────────────────────────────────────────────
@@ -286,3 +301,234 @@ async fn handle_admin_overview(
         })),
     )
 }
+
+// ── API tokens ──────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct CreateApiTokenBody {
+    name: String,
+    scopes: Vec<String>,
+    #[serde(default)]
+    expires_in_days: Option<u32>,
+    #[serde(default)]
+    ip_allowlist: Vec<String>,
+    #[serde(default)]
+    rate_limit_per_minute: u32,
+}
+
+fn api_token_store(
+    state: &AppState,
+) -> Result<&ApiTokenStore, (StatusCode, Json<serde_json::Value>)> {
+    state
+        .api_tokens
+        .as_deref()
+        .map(|auth| auth.store.as_ref())
+        .ok_or((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "API token store not available"})),
+        ))
+}
+
+/// GET /api/admin/api-tokens — All tokens with scopes and last use (never the secret).
+async fn handle_admin_api_tokens_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin_auth(&state, &headers) {
+        return e;
+    }
+    let store = match api_token_store(&state) {
+        Ok(store) => store,
+        Err(e) => return e,
+    };
+
+    match store.list() {
+        Ok(tokens) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "tokens": tokens })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e}")})),
+        ),
+    }
+}
+
+/// POST /api/admin/api-tokens — Create a token. The plaintext token is
+/// returned once in `token`.
+async fn handle_admin_api_tokens_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<CreateApiTokenBody>,
+) -> impl IntoResponse {
+    use crate::security::api_tokens::{ApiScope, NewApiToken};
+
+    if let Err(e) = require_admin_auth(&state, &headers) {
+        return e;
+    }
+    let store = match api_token_store(&state) {
+        Ok(store) => store,
+        Err(e) => return e,
+    };
+
+    let scopes = match body
+        .scopes
+        .iter()
+        .map(|raw| raw.parse::<ApiScope>())
+        .collect::<anyhow::Result<Vec<_>>>()
+    {
+        Ok(scopes) => scopes,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("{e}")})),
+            )
+        }
+    };
+    let spec = NewApiToken {
+        name: body.name,
+        scopes,
+        expires_at: body
+            .expires_in_days
+            .map(|days| chrono::Utc::now().timestamp() + i64::from(days) * 86_400),
+        ip_allowlist: body.ip_allowlist,
+        rate_limit_per_minute: body.rate_limit_per_minute,
+    };
+
+    match store.create(spec) {
+        Ok((record, token)) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({ "token": token, "api_token": record })),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        ),
+    }
+}
+
+/// DELETE /api/admin/api-tokens/{id} — Revoke a token by ID or name.
+async fn handle_admin_api_tokens_revoke(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin_auth(&state, &headers) {
+        return e;
+    }
+    let store = match api_token_store(&state) {
+        Ok(store) => store,
+        Err(e) => return e,
+    };
+
+    match store.revoke(&id) {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"message": "API token revoked"})),
+        ),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "No active API token with that ID or name"})),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e}")})),
+        ),
+    }
+}
+
+// ── Tenants ─────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct DeleteTenantQuery {
+    #[serde(default)]
+    keep_account: bool,
+}
+
+/// GET /api/admin/tenants — Tenants with stored data and their size.
+async fn handle_admin_tenants_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin_auth(&state, &headers) {
+        return e;
+    }
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+
+    match crate::tenancy::list(&workspace_dir, state.auth_store.as_deref()) {
+        Ok(tenants) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "tenants": tenants })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e}")})),
+        ),
+    }
+}
+
+/// POST /api/admin/tenants/{id}/export — Copy one tenant's storage to
+/// `{workspace}/exports/` and return the export directory.
+async fn handle_admin_tenants_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin_auth(&state, &headers) {
+        return e;
+    }
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let auth_store = state.auth_store.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        crate::tenancy::export(&workspace_dir, auth_store.as_deref(), &id, None)
+    })
+    .await;
+    match result {
+        Ok(Ok(path)) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "path": path.display().to_string() })),
+        ),
+        Ok(Err(e)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e}")})),
+        ),
+    }
+}
+
+/// DELETE /api/admin/tenants/{id} — Delete one tenant's storage and,
+/// unless `?keep_account=true`, the user account.
+async fn handle_admin_tenants_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(query): Query<DeleteTenantQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin_auth(&state, &headers) {
+        return e;
+    }
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+
+    match crate::tenancy::delete(
+        &workspace_dir,
+        state.auth_store.as_deref(),
+        &id,
+        query.keep_account,
+    ) {
+        Ok(deletion) if deletion.data_removed || deletion.account_removed => {
+            (StatusCode::OK, Json(serde_json::json!(deletion)))
+        }
+        Ok(_) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "No tenant with that ID"})),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        ),
+    }
+}
@@ -1,6 +1,8 @@
 //! REST API handlers for the web dashboard.
 //!
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
+//! Login sessions are further limited by the user's `[security.roles]` role;
+//! administrative routes need an admin role.
 
 use super::AppState;
 use axum::{
@@ -24,26 +26,50 @@ fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
 }
 
 /// Verify bearer token against PairingGuard. Returns error response if unauthorized.
+///
+/// A login session whose user holds a `[security.roles]` role (assigned at
+/// SSO sign-in) is only accepted while that role still exists.
 fn require_auth(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
-    if !state.pairing.require_pairing() {
-        return Ok(());
-    }
+    authorize(state, headers, false)
+}
+
+/// Like [`require_auth`], for administrative routes (configuration,
+/// credentials, pairing, diagnostics, pricing): a session user with a role
+/// must hold an admin role (`owner`, `admin` or one inheriting from them).
+/// Pairing and API tokens, and users without a role, are not restricted.
+fn require_admin(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    authorize(state, headers, true)
+}
 
+fn authorize(
+    state: &AppState,
+    headers: &HeaderMap,
+    admin: bool,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
     let token = extract_bearer_token(headers).unwrap_or("");
 
-    // Accept pairing token
-    if state.pairing.is_authenticated(token) {
+    // Role checks apply whenever the caller is a login session, even when
+    // pairing is not required.
+    if let Some(store) = &state.auth_store {
+        if let Some(session) = store.validate_session(token) {
+            let role = store.get_user_role(&session.user_id).ok().flatten();
+            return check_session_role(state, role.as_deref(), admin);
+        }
+    }
+
+    if !state.pairing.require_pairing() {
         return Ok(());
     }
 
-    // Also accept JWT session token (from /api/auth/login)
-    if let Some(store) = &state.auth_store {
-        if store.validate_session(token).is_some() {
-            return Ok(());
-        }
+    // Accept pairing token or scoped API token
+    if state.is_bearer_authorized(token) {
+        return Ok(());
     }
 
     Err((
@@ -54,6 +80,41 @@ fn require_auth(
     ))
 }
 
+/// Refuse a session whose role is unknown, or not an admin role on an
+/// administrative route.
+pub(super) fn check_session_role(
+    state: &AppState,
+    role: Option<&str>,
+    admin: bool,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let Some(role) = role else {
+        return Ok(());
+    };
+    let forbidden = |message: &str| {
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({ "error": message })),
+        ))
+    };
+    let registry = {
+        let config = state.config.lock();
+        crate::security::RoleRegistry::from_config(&config.security.roles)
+    };
+    match registry {
+        Ok(registry) if !registry.contains(role) => {
+            forbidden("Your role is not permitted to use this workspace")
+        }
+        Ok(registry) if admin && !registry.is_admin(role) => {
+            forbidden("This action requires an administrator role")
+        }
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::error!("Invalid [security.roles] configuration: {e:#}");
+            forbidden("Role configuration is invalid")
+        }
+    }
+}
+
 /// Resolve the Kakao ID for the current session user (for Supabase lookups).
 ///
 /// Returns `None` if auth is not configured or the user has no Kakao link.
@@ -75,6 +136,43 @@ pub struct MemoryQuery {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct CostQuery {
+    /// Comma-separated dimensions to break spend down by (e.g. `user,tool`).
+    pub group_by: Option<String>,
+    /// `today`, `week`, `month` (default) or `all`.
+    pub period: Option<String>,
+    /// Explicit window start (`YYYY-MM-DD`); overrides `period`.
+    pub since: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct InboxQuery {
+    /// Include resolved, expired and cancelled tasks.
+    #[serde(default)]
+    pub all: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct GoalsQuery {
+    /// Include completed and cancelled goals.
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Deserialize)]
+pub struct GoalApproveBody {
+    /// Step to approve; defaults to the next step waiting for approval.
+    pub step: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GoalStatusBody {
+    /// `active`, `paused`, `completed` or `cancelled`.
+    pub status: String,
+}
+
 #[derive(Deserialize)]
 pub struct MemoryStoreBody {
     pub key: String,
@@ -89,6 +187,14 @@ pub struct CronAddBody {
     pub command: String,
 }
 
+#[derive(Deserialize)]
+pub struct InboxResolveBody {
+    /// `approve`, `reject` or `answer`.
+    pub decision: String,
+    /// The answer to a question, or comments on an approval.
+    pub response: Option<String>,
+}
+
 // ── Handlers ────────────────────────────────────────────────────
 
 /// GET /api/status — system status overview
@@ -130,7 +236,7 @@ pub async fn handle_api_config_get(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -162,7 +268,7 @@ pub async fn handle_api_config_put(
     headers: HeaderMap,
     body: String,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -226,7 +332,7 @@ pub async fn handle_api_config_api_key_put(
     headers: HeaderMap,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -345,7 +451,7 @@ pub async fn handle_api_config_tool_api_key_put(
     headers: HeaderMap,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -452,7 +558,7 @@ pub async fn handle_api_workspace_put(
     headers: HeaderMap,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -599,7 +705,7 @@ pub async fn handle_api_workspace_folder_put(
     headers: HeaderMap,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -834,6 +940,296 @@ pub async fn handle_api_cron_delete(
     }
 }
 
+fn inbox_unavailable() -> axum::response::Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": "Human task inbox is disabled"})),
+    )
+        .into_response()
+}
+
+/// GET /api/inbox — pending human tasks (`?all=true` includes closed ones)
+pub async fn handle_api_inbox_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<InboxQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let Some(inbox) = crate::inbox::global() else {
+        return inbox_unavailable();
+    };
+
+    match inbox.list(params.all, params.limit.unwrap_or(100).min(500)) {
+        Ok(tasks) => Json(serde_json::json!({"tasks": tasks})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list inbox tasks: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/inbox/:id — one human task
+pub async fn handle_api_inbox_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let Some(inbox) = crate::inbox::global() else {
+        return inbox_unavailable();
+    };
+
+    match inbox.get(&id) {
+        Ok(Some(task)) => Json(serde_json::json!({"task": task})).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Inbox task {id} not found")})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read inbox task: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/inbox/:id/resolve — approve, reject or answer a human task
+pub async fn handle_api_inbox_resolve(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<InboxResolveBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let Some(inbox) = crate::inbox::global() else {
+        return inbox_unavailable();
+    };
+    let Some(decision) = crate::inbox::HumanDecision::from_name(&body.decision) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "decision must be \"approve\", \"reject\" or \"answer\""
+            })),
+        )
+            .into_response();
+    };
+    let resolved_by = extract_bearer_token(&headers)
+        .and_then(|token| state.auth_store.as_ref()?.validate_session(token))
+        .map_or_else(|| "dashboard".to_string(), |session| session.user_id);
+
+    match inbox.resolve(
+        &id,
+        decision,
+        body.response.as_deref(),
+        &resolved_by,
+        "dashboard",
+    ) {
+        Ok(task) => Json(serde_json::json!({"status": "ok", "task": task})).into_response(),
+        Err(e) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": format!("{e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// DELETE /api/inbox/:id — withdraw a human task
+pub async fn handle_api_inbox_cancel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let Some(inbox) = crate::inbox::global() else {
+        return inbox_unavailable();
+    };
+
+    match inbox.cancel(&id, "dashboard") {
+        Ok(task) => Json(serde_json::json!({"status": "ok", "task": task})).into_response(),
+        Err(e) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": format!("{e}")})),
+        )
+            .into_response(),
+    }
+}
+
+fn goal_engine(state: &AppState) -> crate::goals::engine::GoalEngine {
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    crate::goals::engine::GoalEngine::new(&workspace_dir)
+}
+
+fn goal_error(status: StatusCode, e: &anyhow::Error) -> axum::response::Response {
+    (status, Json(serde_json::json!({"error": format!("{e}")}))).into_response()
+}
+
+/// GET /api/goals — active, paused and blocked goals (`?all=true` includes finished ones)
+pub async fn handle_api_goals_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<GoalsQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    match goal_engine(&state).load_state().await {
+        Ok(goals) => {
+            let goals: Vec<_> = goals
+                .goals
+                .into_iter()
+                .filter(|g| {
+                    params.all
+                        || !matches!(
+                            g.status,
+                            crate::goals::engine::GoalStatus::Completed
+                                | crate::goals::engine::GoalStatus::Cancelled
+                        )
+                })
+                .collect();
+            Json(serde_json::json!({"goals": goals})).into_response()
+        }
+        Err(e) => goal_error(StatusCode::INTERNAL_SERVER_ERROR, &e),
+    }
+}
+
+/// POST /api/goals — add a goal for the daemon to work on
+pub async fn handle_api_goals_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<crate::goals::engine::NewGoal>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    match goal_engine(&state)
+        .update(|goals| goals.add_goal(body, &now))
+        .await
+    {
+        Ok(goal) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({"status": "ok", "goal": goal})),
+        )
+            .into_response(),
+        Err(e) => goal_error(StatusCode::BAD_REQUEST, &e),
+    }
+}
+
+/// GET /api/goals/:id — one goal with its steps and attempt history
+pub async fn handle_api_goals_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    match goal_engine(&state).load_state().await {
+        Ok(mut goals) => match goals.goal_mut(&id) {
+            Ok(goal) => Json(serde_json::json!({"goal": goal})).into_response(),
+            Err(e) => goal_error(StatusCode::NOT_FOUND, &e),
+        },
+        Err(e) => goal_error(StatusCode::INTERNAL_SERVER_ERROR, &e),
+    }
+}
+
+/// DELETE /api/goals/:id — cancel a goal
+pub async fn handle_api_goals_cancel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    match goal_engine(&state)
+        .update(|goals| {
+            crate::goals::set_status(goals, &id, crate::goals::engine::GoalStatus::Cancelled)
+        })
+        .await
+    {
+        Ok(goal) => Json(serde_json::json!({"status": "ok", "goal": goal})).into_response(),
+        Err(e) => goal_error(StatusCode::NOT_FOUND, &e),
+    }
+}
+
+/// POST /api/goals/:id/status — pause, resume, complete or cancel a goal
+pub async fn handle_api_goals_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<GoalStatusBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+    let status = match body.status.as_str() {
+        "active" => crate::goals::engine::GoalStatus::InProgress,
+        "paused" => crate::goals::engine::GoalStatus::Pending,
+        "completed" => crate::goals::engine::GoalStatus::Completed,
+        "cancelled" => crate::goals::engine::GoalStatus::Cancelled,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "status must be \"active\", \"paused\", \"completed\" or \"cancelled\""
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    match goal_engine(&state)
+        .update(|goals| crate::goals::set_status(goals, &id, status))
+        .await
+    {
+        Ok(goal) => Json(serde_json::json!({"status": "ok", "goal": goal})).into_response(),
+        Err(e) => goal_error(StatusCode::NOT_FOUND, &e),
+    }
+}
+
+/// POST /api/goals/:id/approve — approve a checkpointed step
+pub async fn handle_api_goals_approve(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<GoalApproveBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    match goal_engine(&state)
+        .update(|goals| {
+            Ok(goals
+                .goal_mut(&id)?
+                .approve_checkpoint(body.step.as_deref())?
+                .clone())
+        })
+        .await
+    {
+        Ok(step) => {
+            crate::goals::settle_checkpoint_task(&step);
+            Json(serde_json::json!({"status": "ok", "step": step})).into_response()
+        }
+        Err(e) => goal_error(StatusCode::CONFLICT, &e),
+    }
+}
+
 /// GET /api/integrations — list all integrations with status
 pub async fn handle_api_integrations(
     State(state): State<AppState>,
@@ -867,7 +1263,7 @@ pub async fn handle_api_doctor(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -995,18 +1391,38 @@ pub async fn handle_api_memory_delete(
     }
 }
 
-/// GET /api/cost — cost summary
+/// GET /api/cost — cost summary, plus ledger breakdowns when `group_by` is set
 pub async fn handle_api_cost(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Query(params): Query<CostQuery>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
 
     if let Some(ref tracker) = state.cost_tracker {
+        let breakdowns = match params.group_by.as_deref() {
+            Some(group_by) => match cost_breakdowns(tracker, group_by, &params) {
+                Ok(breakdowns) => Some(breakdowns),
+                Err(e) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({"error": format!("{e:#}")})),
+                    )
+                        .into_response();
+                }
+            },
+            None => None,
+        };
         match tracker.get_summary() {
-            Ok(summary) => Json(serde_json::json!({"cost": summary})).into_response(),
+            Ok(summary) => match breakdowns {
+                Some(breakdowns) => {
+                    Json(serde_json::json!({"cost": summary, "breakdowns": breakdowns}))
+                        .into_response()
+                }
+                None => Json(serde_json::json!({"cost": summary})).into_response(),
+            },
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": format!("Cost summary failed: {e}")})),
@@ -1028,6 +1444,30 @@ pub async fn handle_api_cost(
     }
 }
 
+/// Ledger reports keyed by dimension name, for `GET /api/cost?group_by=...`.
+fn cost_breakdowns(
+    tracker: &crate::cost::CostTracker,
+    group_by: &str,
+    params: &CostQuery,
+) -> anyhow::Result<serde_json::Map<String, serde_json::Value>> {
+    let since = crate::cost::report_window(
+        params.period.as_deref().unwrap_or("month"),
+        params.since.as_deref(),
+        chrono::Utc::now(),
+    )?;
+    let mut breakdowns = serde_json::Map::new();
+    for name in group_by.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let dimension = crate::config::schema::CostDimension::from_name(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown cost dimension '{name}'"))?;
+        let report = tracker.report(dimension, since)?;
+        breakdowns.insert(
+            dimension.as_str().to_string(),
+            serde_json::to_value(report)?,
+        );
+    }
+    Ok(breakdowns)
+}
+
 // ── Billing / Credits ────────────────────────────────────────────
 
 /// GET /api/credits/balance — current credit balance for the user
@@ -1849,7 +2289,7 @@ pub async fn handle_api_admin_pricing_list(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -1871,7 +2311,7 @@ pub async fn handle_api_admin_pricing_get(
     headers: HeaderMap,
     Path(model_id): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -1907,7 +2347,7 @@ pub async fn handle_api_admin_pricing_upsert(
     Path(model_id): Path<String>,
     Json(body): Json<UpsertPricingRequest>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -1963,7 +2403,7 @@ pub async fn handle_api_admin_pricing_delete(
     headers: HeaderMap,
     Path(model_id): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -2002,7 +2442,7 @@ pub async fn handle_api_admin_pricing_estimate(
     headers: HeaderMap,
     Json(body): Json<EstimateCostRequest>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -2656,7 +3096,7 @@ pub async fn handle_api_pairing_devices(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
@@ -2670,7 +3110,7 @@ pub async fn handle_api_pairing_device_revoke(
     headers: HeaderMap,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_admin(&state, &headers) {
         return e.into_response();
     }
 
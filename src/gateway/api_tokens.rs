@@ -0,0 +1,255 @@
+//! Enforcement of scoped API tokens on gateway routes.
+//!
+//! Requests carrying a `zc_` token (see [`crate::security::api_tokens`]) are
+//! checked here before they reach a handler: the token must be active, hold
+//! the scope the route requires, come from an allowed address and stay
+//! within its rate limit. Every such request — allowed or not — is written
+//! to the audit log under the token's ID and name. Pairing and session
+//! tokens pass through untouched.
+
+use super::AppState;
+use crate::config::Config;
+use crate::security::api_tokens::{ApiScope, ApiToken, ApiTokenStore, API_TOKEN_PREFIX};
+use crate::security::{AuditEvent, AuditEventType, AuditLogger};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Token store plus the audit log that token requests are attributed in.
+pub struct ApiTokenAuth {
+    pub store: Arc<ApiTokenStore>,
+    pub audit: Option<AuditLogger>,
+}
+
+impl ApiTokenAuth {
+    /// Open the workspace token store and the audit log next to the config.
+    pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let store = ApiTokenStore::open(&config.workspace_dir)?;
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .map_or_else(|| config.workspace_dir.clone(), Path::to_path_buf);
+        let audit = AuditLogger::new(config.security.audit.clone(), zeroclaw_dir)?;
+        Ok(Self {
+            store: Arc::new(store),
+            audit: Some(audit),
+        })
+    }
+
+    /// Whether `token` is a known, active API token. Scope, address and
+    /// rate limit have already been enforced by [`enforce_api_tokens`].
+    pub fn is_active(&self, token: &str) -> bool {
+        self.store
+            .find(token)
+            .ok()
+            .flatten()
+            .is_some_and(|record| record.is_active(chrono::Utc::now().timestamp()))
+    }
+
+    fn audit(&self, token: &ApiToken, action: &str, required: ApiScope, outcome: Outcome) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let (event_type, allowed, status, duration_ms, error) = match outcome {
+            Outcome::Denied(status, reason) => (
+                AuditEventType::AuthFailure,
+                false,
+                status,
+                0,
+                Some(reason.to_string()),
+            ),
+            Outcome::Completed(status, duration_ms) => {
+                (AuditEventType::ApiRequest, true, status, duration_ms, None)
+            }
+        };
+        let event = AuditEvent::new(event_type)
+            .with_actor(
+                "api_token".to_string(),
+                Some(token.id.clone()),
+                Some(token.name.clone()),
+            )
+            .with_action(
+                action.to_string(),
+                required.as_str().to_string(),
+                allowed,
+                allowed,
+            )
+            .with_result(
+                status.is_success(),
+                Some(i32::from(status.as_u16())),
+                duration_ms,
+                error,
+            );
+        if let Err(e) = audit.log(&event) {
+            tracing::warn!("Failed to write API token audit event: {e}");
+        }
+    }
+}
+
+enum Outcome {
+    Denied(StatusCode, &'static str),
+    Completed(StatusCode, u64),
+}
+
+/// Scope a route requires from an API token, or `None` for routes that do
+/// not take bearer authentication at all. Authenticated routes without a
+/// narrower mapping require `admin`.
+pub fn required_scope(method: &Method, path: &str) -> Option<ApiScope> {
+    let protected = ["/api/", "/v1/", "/ws/"]
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+        || matches!(path, "/webhook" | "/metrics");
+    if !protected {
+        return None;
+    }
+
+    let read_only = *method == Method::GET || *method == Method::HEAD;
+    let scope = match path {
+        "/api/chat" | "/webhook" | "/ws/chat" | "/v1/chat/completions" | "/v1/models" => {
+            ApiScope::Chat
+        }
+        p if p == "/api/memory" || p.starts_with("/api/memory/") => {
+            if read_only {
+                ApiScope::MemoryRead
+            } else {
+                ApiScope::MemoryWrite
+            }
+        }
+        p if p == "/api/cron" || p.starts_with("/api/cron/") => {
+            if read_only {
+                ApiScope::CronRead
+            } else {
+                ApiScope::CronWrite
+            }
+        }
+        "/api/status" | "/api/health" | "/api/tools" | "/api/integrations" | "/api/cost"
+        | "/api/events" | "/metrics"
+            if read_only =>
+        {
+            ApiScope::Read
+        }
+        _ => ApiScope::Admin,
+    };
+    Some(scope)
+}
+
+/// Middleware that authorizes requests made with API tokens.
+pub async fn enforce_api_tokens(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(auth) = state.api_tokens.clone() else {
+        return next.run(request).await;
+    };
+    let Some(presented) = super::ws::request_bearer_token(request.headers(), request.uri().query())
+        .filter(|token| token.starts_with(API_TOKEN_PREFIX))
+    else {
+        return next.run(request).await;
+    };
+    let Some(required) = required_scope(request.method(), request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let action = format!("{} {}", request.method(), request.uri().path());
+    let token = match auth.store.find(&presented) {
+        Ok(Some(token)) => token,
+        Ok(None) => return deny(StatusCode::UNAUTHORIZED, "Unknown API token"),
+        Err(e) => {
+            tracing::error!("API token lookup failed: {e}");
+            return deny(StatusCode::INTERNAL_SERVER_ERROR, "API token lookup failed");
+        }
+    };
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let client_ip =
+        super::client_key_from_request(peer, request.headers(), state.trust_forwarded_headers)
+            .parse::<IpAddr>()
+            .ok();
+
+    let denial = if !token.is_active(chrono::Utc::now().timestamp()) {
+        Some((StatusCode::UNAUTHORIZED, "API token is revoked or expired"))
+    } else if !token.has_scope(required) {
+        Some((StatusCode::FORBIDDEN, "API token lacks the required scope"))
+    } else if !token.allows_ip(client_ip) {
+        Some((
+            StatusCode::FORBIDDEN,
+            "API token is not allowed from this address",
+        ))
+    } else if !state
+        .rate_limiter
+        .allow_api_token(&token.id, token.rate_limit_per_minute)
+    {
+        Some((
+            StatusCode::TOO_MANY_REQUESTS,
+            "API token rate limit exceeded",
+        ))
+    } else {
+        None
+    };
+    if let Some((status, reason)) = denial {
+        auth.audit(&token, &action, required, Outcome::Denied(status, reason));
+        return deny(status, reason);
+    }
+
+    let client_ip = client_ip.map(|ip| ip.to_string());
+    if let Err(e) = auth.store.record_use(&token.id, client_ip.as_deref()) {
+        tracing::warn!("Failed to record API token use: {e}");
+    }
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    auth.audit(
+        &token,
+        &action,
+        required,
+        Outcome::Completed(response.status(), duration_ms),
+    );
+    response
+}
+
+fn deny(status: StatusCode, reason: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": reason }))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_map_to_the_narrowest_scope() {
+        let scope = |method: Method, path: &str| required_scope(&method, path);
+        assert_eq!(
+            scope(Method::POST, "/v1/chat/completions"),
+            Some(ApiScope::Chat)
+        );
+        assert_eq!(scope(Method::GET, "/ws/chat"), Some(ApiScope::Chat));
+        assert_eq!(
+            scope(Method::GET, "/api/memory"),
+            Some(ApiScope::MemoryRead)
+        );
+        assert_eq!(
+            scope(Method::DELETE, "/api/memory/notes"),
+            Some(ApiScope::MemoryWrite)
+        );
+        assert_eq!(scope(Method::GET, "/api/cron"), Some(ApiScope::CronRead));
+        assert_eq!(scope(Method::POST, "/api/cron"), Some(ApiScope::CronWrite));
+        assert_eq!(scope(Method::GET, "/api/status"), Some(ApiScope::Read));
+        assert_eq!(scope(Method::PUT, "/api/config"), Some(ApiScope::Admin));
+        assert_eq!(scope(Method::GET, "/api/memoryleak"), Some(ApiScope::Admin));
+        assert_eq!(scope(Method::GET, "/health"), None);
+        assert_eq!(scope(Method::POST, "/pair"), None);
+    }
+}
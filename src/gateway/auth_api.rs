@@ -10,6 +10,8 @@
 //! - PUT  /api/auth/devices/{device_id}/pairing-code
 //! - POST /api/auth/devices/{device_id}/verify-pairing
 //! - POST /api/auth/heartbeat
+//! - GET  /api/auth/oidc/login
+//! - GET  /api/auth/oidc/callback
 //! - GET  /api/agent/info
 
 use super::AppState;
@@ -346,6 +348,7 @@ pub async fn handle_user_profile(
         "user_id": session.user_id,
         "username": user.as_ref().map(|u| u.username.as_str()),
         "email": user.as_ref().and_then(|u| u.email.as_deref()),
+        "role": auth_store.get_user_role(&session.user_id).ok().flatten(),
         "created_at": user.as_ref().map(|u| u.created_at),
         "device_count": devices.len(),
     })).into_response()
@@ -933,6 +936,196 @@ pub async fn handle_auth_kakao_redirect(
     axum::response::Redirect::temporary(&redirect_url).into_response()
 }
 
+// ── GET /api/auth/oidc/login ────────────────────────────────────
+// Browser entry point for SSO — redirects to the identity provider.
+
+/// Cookie binding a pending SSO login to the browser that started it.
+const OIDC_STATE_COOKIE: &str = "zeroclaw_oidc_state";
+
+/// `Set-Cookie` value for the SSO state cookie, scoped to the callback path.
+/// An empty `value` with `max_age_secs == 0` clears it.
+fn oidc_state_cookie(redirect_url: &str, value: &str, max_age_secs: u64) -> String {
+    let url = reqwest::Url::parse(redirect_url).ok();
+    let path = url
+        .as_ref()
+        .map_or("/api/auth/oidc/callback", |url| url.path());
+    let secure = if url.as_ref().is_some_and(|url| url.scheme() == "https") {
+        "; Secure"
+    } else {
+        ""
+    };
+    format!(
+        "{OIDC_STATE_COOKIE}={value}; Path={path}; Max-Age={max_age_secs}; HttpOnly; SameSite=Lax{secure}"
+    )
+}
+
+/// Value of cookie `name` from the request's `Cookie` headers.
+fn request_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+#[derive(Deserialize)]
+pub struct OidcLoginQuery {
+    /// Dashboard path to return to after sign-in (default: `/`).
+    pub return_to: Option<String>,
+}
+
+pub async fn handle_auth_oidc_login(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<OidcLoginQuery>,
+) -> impl IntoResponse {
+    let Some(oidc) = state.oidc.as_ref() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "SSO is not configured" })),
+        )
+            .into_response();
+    };
+
+    let return_to = crate::auth::oidc::sanitize_return_to(query.return_to.as_deref());
+    match oidc.begin_login(&return_to).await {
+        Ok(start) => (
+            [(
+                header::SET_COOKIE,
+                oidc_state_cookie(
+                    oidc.redirect_url(),
+                    &start.state,
+                    oidc.login_ttl().as_secs(),
+                ),
+            )],
+            axum::response::Redirect::to(&start.url),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("OIDC login could not start: {e:#}");
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({ "error": "Identity provider is unavailable" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ── GET /api/auth/oidc/callback ─────────────────────────────────
+// Redirect URI registered with the identity provider. Checks the state
+// against the browser's state cookie, verifies the ID token, provisions the
+// user, and hands the session token to the SPA in the URL fragment (never
+// sent to servers or written to access logs).
+
+#[derive(Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+pub async fn handle_auth_oidc_callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<OidcCallbackQuery>,
+) -> impl IntoResponse {
+    let mut response = oidc_callback(&state, &headers, query).await;
+    // The state cookie is single-use, whatever the outcome.
+    if let Some(oidc) = state.oidc.as_ref() {
+        if let Ok(cookie) = oidc_state_cookie(oidc.redirect_url(), "", 0).parse() {
+            response.headers_mut().append(header::SET_COOKIE, cookie);
+        }
+    }
+    response
+}
+
+async fn oidc_callback(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: OidcCallbackQuery,
+) -> axum::response::Response {
+    let fail = |message: &str| {
+        axum::response::Redirect::to(&format!(
+            "/auth/oidc/callback#error={}",
+            urlencoding::encode(message)
+        ))
+        .into_response()
+    };
+
+    let (Some(oidc), Some(auth_store)) = (state.oidc.as_ref(), state.auth_store.as_ref()) else {
+        return fail("SSO is not configured");
+    };
+
+    if let Some(error) = query.error.as_deref() {
+        // The description comes from the identity provider (or whoever
+        // crafted the link); log it rather than showing it to the user.
+        let description = query.error_description.as_deref().unwrap_or_default();
+        tracing::warn!("OIDC provider returned an error: {error}: {description}");
+        return fail("The identity provider did not complete sign-in. Please try again.");
+    }
+    let (Some(code), Some(login_state)) = (query.code.as_deref(), query.state.as_deref()) else {
+        return fail("Missing authorization code");
+    };
+
+    let browser_state = request_cookie(headers, OIDC_STATE_COOKIE);
+    let login = match oidc.complete_login(code, login_state, browser_state).await {
+        Ok(login) => login,
+        Err(e) => {
+            tracing::warn!("OIDC login failed: {e:#}");
+            return fail("Sign-in could not be verified. Please try again.");
+        }
+    };
+    let identity = &login.identity;
+
+    let Some(role) = oidc.resolve_role(&identity.groups) else {
+        tracing::warn!(
+            subject = %identity.subject,
+            groups = ?identity.groups,
+            "OIDC login refused: no role mapping matches the user's groups"
+        );
+        return fail("Your account is not permitted to use this workspace");
+    };
+
+    let user = match auth_store.provision_oidc_user(
+        &identity.issuer,
+        &identity.subject,
+        &identity.username,
+        identity.email.as_deref(),
+        &role,
+    ) {
+        Ok(user) => user,
+        Err(e) => {
+            tracing::error!("OIDC user provisioning failed: {e:#}");
+            return fail("Account provisioning failed");
+        }
+    };
+
+    let token = match auth_store.create_session(&user.id, None, Some("sso")) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("OIDC session creation failed: {e:#}");
+            return fail("Session error");
+        }
+    };
+
+    tracing::info!(
+        user_id = %user.id,
+        username = %user.username,
+        role = %role,
+        "OIDC sign-in"
+    );
+    axum::response::Redirect::to(&format!(
+        "/auth/oidc/callback#token={}&return_to={}",
+        urlencoding::encode(&token),
+        urlencoding::encode(&login.return_to)
+    ))
+    .into_response()
+}
+
 // ── GET /api/agent/info ─────────────────────────────────────────
 
 pub async fn handle_agent_info(State(state): State<AppState>) -> impl IntoResponse {
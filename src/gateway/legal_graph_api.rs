@@ -57,7 +57,7 @@ async fn handle_subgraph(
 ) -> impl IntoResponse {
     let depth = q.depth.clamp(1, 3) as usize;
     let kinds = parse_kinds(q.kinds.as_deref());
-    let workspace = state.config.lock().workspace_dir.clone();
+    let workspace = crate::tenancy::workspace_dir(&state.config.lock().workspace_dir);
 
     let result = tokio::task::spawn_blocking(move || {
         let conn = open_read_only(&workspace)?;
@@ -91,7 +91,7 @@ async fn handle_path(
     Query(q): Query<PathQuery>,
 ) -> impl IntoResponse {
     let max_depth = q.max_depth.clamp(1, 6) as usize;
-    let workspace = state.config.lock().workspace_dir.clone();
+    let workspace = crate::tenancy::workspace_dir(&state.config.lock().workspace_dir);
     let res = tokio::task::spawn_blocking(move || {
         let conn = open_read_only(&workspace)?;
         graph_query::shortest_path(&conn, &q.from, &q.to, max_depth)
@@ -108,7 +108,7 @@ async fn handle_path(
 // ───────── /api/legal/graph/stats ─────────
 
 async fn handle_stats(State(state): State<AppState>) -> impl IntoResponse {
-    let workspace = state.config.lock().workspace_dir.clone();
+    let workspace = crate::tenancy::workspace_dir(&state.config.lock().workspace_dir);
     let res = tokio::task::spawn_blocking(move || -> anyhow::Result<Value> {
         let conn = open_read_only(&workspace)?;
         let statutes: i64 = conn.query_row(
@@ -193,7 +193,7 @@ async fn handle_vendor_asset(
     if !allowed {
         return http_error(StatusCode::NOT_FOUND, "unknown vendor asset");
     }
-    let workspace = state.config.lock().workspace_dir.clone();
+    let workspace = crate::tenancy::workspace_dir(&state.config.lock().workspace_dir);
     let bytes = match crate::vault::legal::vendor::load_asset_bytes(&workspace, &asset) {
         Ok(Some(b)) => b,
         Ok(None) => {
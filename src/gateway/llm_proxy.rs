@@ -217,7 +217,12 @@ pub async fn handle_llm_proxy(
                     cost_usd,
                     timestamp: chrono::Utc::now(),
                 };
-                let _ = tracker.record_usage(usage);
+                let attribution = crate::cost::CostAttribution {
+                    user: Some(user_id.clone()),
+                    channel: Some("llm_proxy".into()),
+                    ..Default::default()
+                };
+                let _ = tracker.record_usage_with_attribution(usage, attribution);
             }
 
             // Serialize tool_calls if present
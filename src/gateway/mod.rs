@@ -9,6 +9,7 @@
 
 pub mod admin_api;
 pub mod api;
+pub mod api_tokens;
 pub mod auth_api;
 pub mod bootstrap_state;
 pub mod channel_router;
@@ -22,6 +23,7 @@ pub mod pair;
 pub mod remote;
 pub mod sse;
 pub mod static_files;
+pub mod tenancy;
 pub mod timesync;
 pub mod ws;
 
@@ -40,7 +42,7 @@ use crate::security::SecurityPolicy;
 use crate::tools::traits::ToolSpec;
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
-use crate::voice::VoiceSessionManager;
+use crate::voice::{ConferenceManager, VoiceSessionManager};
 use anyhow::{Context, Result};
 use axum::{
     body::{Body, Bytes},
@@ -159,7 +161,13 @@ impl SlidingWindowRateLimiter {
     }
 
     fn allow(&self, key: &str) -> bool {
-        if self.limit_per_window == 0 {
+        self.allow_with_limit(key, self.limit_per_window)
+    }
+
+    /// Like [`Self::allow`], with a per-call limit for keys that carry
+    /// their own quota.
+    fn allow_with_limit(&self, key: &str, limit_per_window: u32) -> bool {
+        if limit_per_window == 0 {
             return true;
         }
 
@@ -194,7 +202,7 @@ impl SlidingWindowRateLimiter {
         let entry = requests.entry(key.to_owned()).or_default();
         entry.retain(|instant| *instant > cutoff);
 
-        if entry.len() >= self.limit_per_window as usize {
+        if entry.len() >= limit_per_window as usize {
             return false;
         }
 
@@ -207,6 +215,7 @@ impl SlidingWindowRateLimiter {
 pub struct GatewayRateLimiter {
     pair: SlidingWindowRateLimiter,
     webhook: SlidingWindowRateLimiter,
+    api_token: SlidingWindowRateLimiter,
 }
 
 impl GatewayRateLimiter {
@@ -215,6 +224,7 @@ impl GatewayRateLimiter {
         Self {
             pair: SlidingWindowRateLimiter::new(pair_per_minute, window, max_keys),
             webhook: SlidingWindowRateLimiter::new(webhook_per_minute, window, max_keys),
+            api_token: SlidingWindowRateLimiter::new(0, window, max_keys),
         }
     }
 
@@ -225,6 +235,11 @@ impl GatewayRateLimiter {
     fn allow_webhook(&self, key: &str) -> bool {
         self.webhook.allow(key)
     }
+
+    /// Per-token limit for scoped API tokens. `0` means unlimited.
+    fn allow_api_token(&self, token_id: &str, per_minute: u32) -> bool {
+        self.api_token.allow_with_limit(token_id, per_minute)
+    }
 }
 
 #[derive(Debug)]
@@ -410,6 +425,12 @@ pub struct AppState {
     pub device_router: Option<Arc<remote::DeviceRouter>>,
     /// Email verification service for 3rd-factor auth on remote device access.
     pub email_verify_service: Option<Arc<crate::auth::email_verify::EmailVerifyService>>,
+    /// OpenID Connect relying party for SSO sign-in (`[auth.oidc]`).
+    /// Present only when auth and OIDC are both enabled.
+    pub oidc: Option<Arc<crate::auth::oidc::OidcProvider>>,
+    /// Scoped API tokens for automation (`zeroclaw api-token`), enforced by
+    /// [`api_tokens::enforce_api_tokens`].
+    pub api_tokens: Option<Arc<api_tokens::ApiTokenAuth>>,
     /// Supabase client for cloud user management, credits, and audit logging.
     /// Present only when SUPABASE_URL + SUPABASE_SERVICE_KEY are configured.
     pub supabase: Option<Arc<crate::integrations::supabase::SupabaseClient>>,
@@ -446,10 +467,31 @@ pub struct AppState {
     pub slm_executor: Option<Arc<crate::advisor::SlmExecutor>>,
 }
 
+impl AppState {
+    /// Whether a bearer token grants access to pairing-protected routes:
+    /// a paired device token, or an active scoped API token. Scope, address
+    /// and rate limit of API tokens are enforced by the
+    /// [`api_tokens::enforce_api_tokens`] middleware before handlers run.
+    pub fn is_bearer_authorized(&self, token: &str) -> bool {
+        if self.pairing.is_authenticated(token) {
+            return true;
+        }
+        token.starts_with(crate::security::api_tokens::API_TOKEN_PREFIX)
+            && self
+                .api_tokens
+                .as_ref()
+                .is_some_and(|auth| auth.is_active(token))
+    }
+}
+
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
 #[allow(clippy::too_many_lines)]
 pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
-    if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
+    if let Err(error) = crate::plugins::runtime::initialize_from_config(
+        &config.plugins,
+        &config.security,
+        config.autonomy.level,
+    ) {
         tracing::warn!("plugin registry initialization skipped: {error}");
     }
 
@@ -778,6 +820,11 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         )?);
         (mem, None, None)
     };
+    // Route memory calls to the caller's own store when users are isolated.
+    let mem = crate::tenancy::wrap_memory(mem, &config)?;
+    crate::tenancy::init_from_config(&config);
+    let dispatch = crate::dispatch::init_shared(Arc::clone(&mem));
+    crate::dispatch::webhook::init_from_config(&config, &dispatch);
 
     // ── Clock drift check ─────────────────────────────────────────────
     // MoA uses occurred_at (real-world time) as the primary sort key for
@@ -832,8 +879,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     // Cost tracker (optional)
     let cost_tracker = if config.cost.enabled {
-        match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
-            Ok(ct) => Some(Arc::new(ct)),
+        match crate::cost::shared_tracker(&config.cost, &config.workspace_dir) {
+            Ok(ct) => Some(ct),
             Err(e) => {
                 tracing::warn!("Failed to initialize cost tracker: {e}");
                 None
@@ -888,14 +935,22 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     // SSE broadcast channel for real-time events
     let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
 
-    // Voice session manager (for simultaneous interpretation)
-    let voice_sessions = Arc::new(VoiceSessionManager::with_defaults(
-        config.voice.enabled,
-        config.voice.max_sessions_per_user,
-        config.voice.default_source_language.clone(),
-        config.voice.default_target_language.clone(),
-        config.voice.default_provider.clone(),
-    ));
+    // Voice session manager (simultaneous interpretation and conference rooms)
+    let voice_sessions = Arc::new(
+        VoiceSessionManager::with_defaults(
+            config.voice.enabled,
+            config.voice.max_sessions_per_user,
+            config.voice.default_source_language.clone(),
+            config.voice.default_target_language.clone(),
+            config.voice.default_provider.clone(),
+        )
+        .with_conferences(ConferenceManager::from_config(
+            &config,
+            Arc::clone(&mem),
+            Arc::clone(&provider),
+            &model,
+        )),
+    );
     // Extract webhook secret for authentication
     let webhook_secret_hash: Option<Arc<str>> =
         config.channels_config.webhook.as_ref().and_then(|webhook| {
@@ -1117,6 +1172,11 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if qq_webhook_enabled {
         println!("  POST /qq        — QQ Bot webhook (validation + events)");
     }
+    if config.webhooks.enabled {
+        for endpoint in &config.webhooks.endpoints {
+            println!("  POST /hooks/{} — signed dispatch webhook", endpoint.name);
+        }
+    }
     if config.gateway.node_control.enabled {
         println!("  POST /api/node-control — experimental node-control RPC scaffold");
     }
@@ -1397,6 +1457,31 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             (None, None, false, None, None)
         };
 
+    // ── OIDC single sign-on ──────────────────────────────────────
+    let oidc = if auth_store.is_some() && config.auth.oidc.enabled {
+        match build_oidc_provider(&config, actual_port) {
+            Ok(provider) => {
+                tracing::info!("OIDC sign-in enabled (issuer={})", config.auth.oidc.issuer);
+                Some(Arc::new(provider))
+            }
+            Err(e) => {
+                tracing::error!("Failed to initialize OIDC sign-in: {e:#}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // ── Scoped API tokens ────────────────────────────────────────
+    let api_tokens = match api_tokens::ApiTokenAuth::from_config(&config) {
+        Ok(auth) => Some(Arc::new(auth)),
+        Err(e) => {
+            tracing::warn!("Failed to open api_tokens.db ({e}); API tokens are disabled.");
+            None
+        }
+    };
+
     // Kakao share-back token store. File-backed when a workspace dir is
     // present; falls back to None on open error so the deployment keeps
     // working without share-back support.
@@ -1479,6 +1564,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         auth_allow_registration,
         device_router,
         email_verify_service,
+        oidc,
+        api_tokens,
         supabase,
         r2_config: crate::storage::r2::R2Config::from_env(),
         pricing_registry: crate::billing::SharedPricingRegistry::new(&config.workspace_dir),
@@ -1540,6 +1627,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
         .route("/qq", post(handle_qq_webhook))
         .route("/kakao", post(handle_kakao_webhook))
+        .route("/hooks/{name}", post(handle_dispatch_hook))
         // ── OpenClaw migration: tools-enabled chat endpoint (extended timeout) ──
         .merge(chat_routes)
         // ── OpenAI-compatible endpoints (extended timeout) ──
@@ -1572,6 +1660,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
         .route("/api/cron/{id}", delete(api::handle_api_cron_delete))
+        .route(
+            "/api/goals",
+            get(api::handle_api_goals_list).post(api::handle_api_goals_create),
+        )
+        .route(
+            "/api/goals/{id}",
+            get(api::handle_api_goals_get).delete(api::handle_api_goals_cancel),
+        )
+        .route("/api/goals/{id}/status", post(api::handle_api_goals_status))
+        .route(
+            "/api/goals/{id}/approve",
+            post(api::handle_api_goals_approve),
+        )
+        .route("/api/inbox", get(api::handle_api_inbox_list))
+        .route(
+            "/api/inbox/{id}",
+            get(api::handle_api_inbox_get).delete(api::handle_api_inbox_cancel),
+        )
+        .route(
+            "/api/inbox/{id}/resolve",
+            post(api::handle_api_inbox_resolve),
+        )
         .route("/api/integrations", get(api::handle_api_integrations))
         .route(
             "/api/doctor",
@@ -1776,6 +1886,14 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             "/api/auth/kakao/redirect",
             get(auth_api::handle_auth_kakao_redirect),
         )
+        .route(
+            "/api/auth/oidc/login",
+            get(auth_api::handle_auth_oidc_login),
+        )
+        .route(
+            "/api/auth/oidc/callback",
+            get(auth_api::handle_auth_oidc_callback),
+        )
         .route("/api/agent/info", get(auth_api::handle_agent_info))
         // ── Remote device access ──
         .route("/api/remote/login", post(remote::handle_remote_login))
@@ -1808,6 +1926,14 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            tenancy::resolve_tenant,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            api_tokens::enforce_api_tokens,
+        ))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -1855,6 +1981,32 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     Ok(())
 }
 
+/// Build the OIDC relying party. The redirect URI defaults to the public
+/// gateway URL (`ZEROCLAW_PUBLIC_URL`), like the Kakao login flow.
+fn build_oidc_provider(config: &Config, port: u16) -> Result<crate::auth::oidc::OidcProvider> {
+    let oidc = &config.auth.oidc;
+    let registry = crate::security::RoleRegistry::from_config(&config.security.roles)?;
+    for role in oidc
+        .role_mappings
+        .iter()
+        .map(|mapping| mapping.role.as_str())
+        .chain(oidc.default_role.as_deref())
+    {
+        if !registry.contains(role) {
+            anyhow::bail!("auth.oidc maps to unknown role '{role}'");
+        }
+    }
+
+    let redirect_url = oidc.redirect_url.clone().unwrap_or_else(|| {
+        let base = std::env::var("ZEROCLAW_PUBLIC_URL")
+            .ok()
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| format!("http://localhost:{port}"));
+        format!("{}/api/auth/oidc/callback", base.trim_end_matches('/'))
+    });
+    crate::auth::oidc::OidcProvider::new(oidc.clone(), redirect_url)
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // AXUM HANDLERS
 // ══════════════════════════════════════════════════════════════════════════════
@@ -1865,6 +2017,7 @@ async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
         "status": "ok",
         "paired": state.pairing.is_paired(),
         "require_pairing": state.pairing.require_pairing(),
+        "sso": state.oidc.is_some(),
         "runtime": crate::health::snapshot_json(),
     });
     Json(body)
@@ -1885,7 +2038,7 @@ async fn handle_metrics(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("").trim();
-        if !state.pairing.is_authenticated(token) {
+        if !state.is_bearer_authorized(token) {
             return (
                 StatusCode::UNAUTHORIZED,
                 [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
@@ -2181,14 +2334,14 @@ pub(super) async fn process_channel_message_rich(
     let auth_store = match state.auth_store.as_ref() {
         Some(s) => s,
         None => {
-            let text = run_gateway_chat_with_tools(state, content, session_id).await?;
+            let text = run_gateway_chat_with_tools(state, content, session_id, None).await?;
             return Ok(channel_router::ChannelReply::text(text));
         }
     };
     let device_router = match state.device_router.as_ref() {
         Some(r) => r,
         None => {
-            let text = run_gateway_chat_with_tools(state, content, session_id).await?;
+            let text = run_gateway_chat_with_tools(state, content, session_id, None).await?;
             return Ok(channel_router::ChannelReply::text(text));
         }
     };
@@ -2318,6 +2471,7 @@ pub(super) async fn run_gateway_chat_with_tools(
     state: &AppState,
     message: &str,
     session_id: Option<&str>,
+    role: Option<&str>,
 ) -> anyhow::Result<String> {
     // Reload config from disk so that runtime config-tool changes (e.g. web_search_config
     // enabling web search, setting API keys) take effect on subsequent requests.
@@ -2335,7 +2489,10 @@ pub(super) async fn run_gateway_chat_with_tools(
         // Update the in-memory state so the gateway stays consistent.
         *state.config.lock() = config.clone();
     }
-    crate::agent::process_message_with_session(config, message, session_id).await
+    Box::pin(crate::agent::process_message_with_role(
+        config, message, session_id, role,
+    ))
+    .await
 }
 
 /// Reload config from the on-disk TOML file without applying env-var overrides.
@@ -2549,7 +2706,7 @@ async fn handle_node_control(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
+        if !state.is_bearer_authorized(token) {
             let err = serde_json::json!({
                 "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
             });
@@ -2967,7 +3124,7 @@ async fn handle_webhook(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
+        if !state.is_bearer_authorized(token) {
             tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
             let err = serde_json::json!({
                 "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
@@ -3258,6 +3415,52 @@ pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header
     hmac::verify(&key, body, &expected).is_ok()
 }
 
+/// POST /hooks/{name} — signed delivery to a `[[webhooks.endpoints]]` entry
+async fn handle_dispatch_hook(
+    axum::extract::Path(name): axum::extract::Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    use crate::dispatch::webhook::{Delivery, Rejection};
+
+    let Some(ingress) = crate::dispatch::webhook::global() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Webhooks are not enabled"})),
+        );
+    };
+    match ingress.receive(&name, &headers, &body).await {
+        Ok(Delivery::Dispatched(result)) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "status": "dispatched",
+                "event_id": result.event_id,
+                "handled": result.handled_count(),
+                "failed": result.failed_count(),
+            })),
+        ),
+        Ok(Delivery::Filtered) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"status": "filtered"})),
+        ),
+        Ok(Delivery::Duplicate) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"status": "duplicate"})),
+        ),
+        Err(Rejection::UnknownEndpoint) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Unknown webhook endpoint '{name}'")})),
+        ),
+        Err(rejection) => {
+            tracing::warn!(endpoint = %name, "Webhook delivery rejected: {rejection}");
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": rejection.to_string()})),
+            )
+        }
+    }
+}
+
 /// POST /whatsapp — incoming message webhook
 async fn handle_whatsapp_message(
     State(state): State<AppState>,
@@ -4508,6 +4711,8 @@ mod tests {
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -4588,6 +4793,8 @@ mod tests {
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -4651,6 +4858,8 @@ mod tests {
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -4715,6 +4924,8 @@ mod tests {
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5223,6 +5434,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5315,6 +5528,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5388,6 +5603,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5462,6 +5679,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5545,6 +5764,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5620,6 +5841,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5700,6 +5923,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5806,6 +6031,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5882,6 +6109,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -5963,6 +6192,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6058,6 +6289,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6132,6 +6365,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6217,6 +6452,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6307,6 +6544,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6387,6 +6626,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6460,6 +6701,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -6532,6 +6775,8 @@ Reminder set successfully."#;
             payment_manager: None,
             low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
             auth_store: None,
+            oidc: None,
+            api_tokens: None,
             channel_pairing: None,
             case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
             kakao_share_store: None,
@@ -7045,4 +7290,219 @@ Reminder set successfully."#;
         assert_eq!(entry.message_text, "body content");
         assert_eq!(entry.user_id, "kakao_user_1");
     }
+
+    fn sso_state(idp: &crate::auth::oidc::test_idp::MockIdp, dir: &std::path::Path) -> AppState {
+        let memory: Arc<dyn Memory> = Arc::new(MockMemory);
+        let auth_store = crate::auth::store::AuthStore::new(&dir.join("auth.db"), None).unwrap();
+        let oidc = crate::auth::oidc::OidcProvider::new(
+            idp.config(),
+            "http://localhost:3000/api/auth/oidc/callback".into(),
+        )
+        .unwrap();
+
+        AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            bluebubbles: None,
+            bluebubbles_webhook_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            qq: None,
+            qq_webhook_enabled: false,
+            security: Arc::new(SecurityPolicy::default()),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            tools_registry_exec: Arc::new(Vec::new()),
+            multimodal: crate::config::MultimodalConfig::default(),
+            max_tool_iterations: 10,
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            voice_sessions: Arc::new(VoiceSessionManager::new(true, 5)),
+            sync_coordinator: None,
+            relay_client: None,
+            payment_manager: None,
+            low_balance_alert_state: crate::billing::alerts::LowBalanceAlertState::new(),
+            auth_store: Some(Arc::new(auth_store)),
+            oidc: Some(Arc::new(oidc)),
+            api_tokens: None,
+            channel_pairing: None,
+            case_sessions: Arc::new(crate::channels::case_session::CaseSessionStore::new()),
+            kakao_share_store: None,
+            auth_allow_registration: false,
+            device_router: None,
+            email_verify_service: None,
+            supabase: None,
+            r2_config: None,
+            pricing_registry: crate::billing::SharedPricingRegistry::new(dir),
+            gatekeeper: None,
+            advisor: None,
+            slm_executor: None,
+        }
+    }
+
+    /// Sign in through the mock IdP as a member of `groups` and return
+    /// headers carrying the session token handed to the dashboard.
+    async fn sso_login(
+        state: &AppState,
+        idp: &crate::auth::oidc::test_idp::MockIdp,
+        groups: &[&str],
+    ) -> HeaderMap {
+        let response = auth_api::handle_auth_oidc_login(
+            State(state.clone()),
+            axum::extract::Query(auth_api::OidcLoginQuery { return_to: None }),
+        )
+        .await
+        .into_response();
+        let cookie = response.headers()[axum::http::header::SET_COOKIE]
+            .to_str()
+            .unwrap()
+            .split(';')
+            .next()
+            .unwrap()
+            .to_string();
+        let url = reqwest::Url::parse(
+            response.headers()[axum::http::header::LOCATION]
+                .to_str()
+                .unwrap(),
+        )
+        .unwrap();
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+                .unwrap()
+        };
+        idp.issue_once(&idp.claims_with_groups(&param("nonce"), groups))
+            .await;
+
+        let mut cookies = HeaderMap::new();
+        cookies.insert(
+            axum::http::header::COOKIE,
+            HeaderValue::from_str(&cookie).unwrap(),
+        );
+        let response = auth_api::handle_auth_oidc_callback(
+            State(state.clone()),
+            cookies,
+            axum::extract::Query(auth_api::OidcCallbackQuery {
+                code: Some("auth-code".into()),
+                state: Some(param("state")),
+                error: None,
+                error_description: None,
+            }),
+        )
+        .await
+        .into_response();
+        let location = response.headers()[axum::http::header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let token = location
+            .split_once("#token=")
+            .and_then(|(_, rest)| rest.split('&').next())
+            .unwrap_or_else(|| panic!("SSO login failed: {location}"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        headers
+    }
+
+    #[tokio::test]
+    async fn sso_role_gates_admin_api_routes() {
+        let idp = crate::auth::oidc::test_idp::MockIdp::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let state = sso_state(&idp, dir.path());
+
+        let operator = sso_login(&state, &idp, &["eng"]).await;
+        let response = api::handle_api_config_get(State(state.clone()), operator.clone())
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = api::handle_api_tools(State(state.clone()), operator)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let admin = sso_login(&state, &idp, &["platform-admins"]).await;
+        let response = api::handle_api_config_get(State(state.clone()), admin)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn sso_callback_requires_the_browser_that_started_the_login() {
+        let idp = crate::auth::oidc::test_idp::MockIdp::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let state = sso_state(&idp, dir.path());
+        let callback = |headers: HeaderMap, query: auth_api::OidcCallbackQuery| {
+            let state = state.clone();
+            async move {
+                let response = auth_api::handle_auth_oidc_callback(
+                    State(state),
+                    headers,
+                    axum::extract::Query(query),
+                )
+                .await
+                .into_response();
+                let location = response.headers()[axum::http::header::LOCATION]
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                let cookie = response.headers()[axum::http::header::SET_COOKIE]
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                (location, cookie)
+            }
+        };
+
+        // A login started in another browser (e.g. an attacker's) cannot be
+        // completed in this one.
+        let start = state.oidc.as_ref().unwrap().begin_login("/").await.unwrap();
+        let (location, cookie) = callback(
+            HeaderMap::new(),
+            auth_api::OidcCallbackQuery {
+                code: Some("auth-code".into()),
+                state: Some(start.state),
+                error: None,
+                error_description: None,
+            },
+        )
+        .await;
+        assert!(location.contains("#error="), "{location}");
+        assert!(!location.contains("token="));
+        assert!(cookie.contains("Max-Age=0"));
+        assert!(cookie.contains("HttpOnly"));
+
+        // Provider errors are logged, not echoed back to the dashboard.
+        let (location, _) = callback(
+            HeaderMap::new(),
+            auth_api::OidcCallbackQuery {
+                code: None,
+                state: None,
+                error: Some("access_denied".into()),
+                error_description: Some("Call +1-555-0100 to restore access".into()),
+            },
+        )
+        .await;
+        assert!(location.contains("#error="));
+        assert!(!location.contains("555"), "{location}");
+    }
 }
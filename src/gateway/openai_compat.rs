@@ -149,7 +149,7 @@ pub async fn handle_v1_chat_completions(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
+        if !state.is_bearer_authorized(token) {
             tracing::warn!("/v1/chat/completions: rejected — not paired / invalid bearer token");
             let err = serde_json::json!({
                 "error": {
@@ -556,7 +556,7 @@ pub async fn handle_v1_models(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
+        if !state.is_bearer_authorized(token) {
             let err = serde_json::json!({
                 "error": {
                     "message": "Invalid API key",
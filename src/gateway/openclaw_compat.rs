@@ -176,9 +176,9 @@ pub async fn handle_api_chat(
             .and_then(|store| store.validate_session(bearer_token))
             .is_some();
 
-        // Then try pairing auth
+        // Then try pairing or scoped API token auth
         let pairing_ok =
-            state.pairing.require_pairing() && state.pairing.is_authenticated(bearer_token);
+            state.pairing.require_pairing() && state.is_bearer_authorized(bearer_token);
 
         // Then try webhook secret (verify X-Webhook-Secret header against stored hash)
         let webhook_ok = if let Some(ref secret_hash) = state.webhook_secret_hash {
@@ -1345,7 +1345,7 @@ pub async fn handle_v1_chat_completions_with_tools(
             .and_then(|v| v.to_str().ok())
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
+        if !state.is_bearer_authorized(token) {
             tracing::warn!(
                 "/v1/chat/completions (compat): rejected — not paired / invalid bearer token"
             );
@@ -1536,7 +1536,9 @@ pub async fn handle_v1_chat_completions_with_tools(
     );
 
     // ── Run the full agent loop ──
-    let reply = match run_gateway_chat_with_tools(&state, &enriched_message, session_id).await {
+    let reply = match run_gateway_chat_with_tools(&state, &enriched_message, session_id, None)
+        .await
+    {
         Ok(response) => {
             let leak_guard_cfg = state.config.lock().security.outbound_leak_guard.clone();
             let safe = sanitize_gateway_response(
@@ -0,0 +1,34 @@
+//! Tenant resolution for gateway requests.
+//!
+//! With `[auth] tenant_isolation` on, a request authenticated by a login
+//! session runs inside [`crate::tenancy::scope`] for the session's user, so
+//! the memory, vault, ontology and sessions its handler touches live in that
+//! user's directory. Pairing tokens, API tokens and the admin dashboard are
+//! not tied to a user and keep using the shared workspace.
+
+use super::AppState;
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+/// Middleware that runs the rest of the request on behalf of its tenant.
+pub async fn resolve_tenant(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let tenant = session_tenant(&state, &request);
+    crate::tenancy::scope(tenant, next.run(request)).await
+}
+
+fn session_tenant(state: &AppState, request: &Request) -> Option<String> {
+    if !crate::tenancy::is_enabled(&state.config.lock()) {
+        return None;
+    }
+    let token = super::ws::request_bearer_token(request.headers(), request.uri().query())?;
+    let session = state.auth_store.as_ref()?.validate_session(&token)?;
+    // Admin dashboard sessions belong to no user account.
+    (session.user_id != "admin").then_some(session.user_id)
+}
@@ -7,12 +7,23 @@
 //! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
+//! Client -> Server: {"type":"cancel"}            (stop the in-flight turn)
+//! Server -> Client: {"type":"cancelling"}
+//! Server -> Client: {"type":"cancelled","session_id":"..."}
+//! Client -> Server: {"type":"checkpoint_list","limit":20}
+//! Server -> Client: {"type":"checkpoints","checkpoints":[{"id":"...","files":[...]}]}
+//! Client -> Server: {"type":"checkpoint_restore","id":"..."}   (id omitted = undo last turn)
+//! Server -> Client: {"type":"checkpoint_restored","checkpoints":[...],"restored":[...],"removed":[...]}
 //! ```
 
 use super::AppState;
 use crate::agent::loop_::{build_shell_policy_instructions, build_tool_instructions_from_specs};
 use crate::memory::MemoryCategory;
+use crate::observability::trace_context;
 use crate::providers::ChatMessage;
+use crate::voice::conference::{ConferenceConfig, ConferenceRoom};
+use crate::voice::events::ServerMessage;
+use crate::voice::pipeline::LanguageCode;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -21,6 +32,9 @@ use axum::{
     http::{header, HeaderMap},
     response::IntoResponse,
 };
+use std::collections::VecDeque;
+use std::future::Future;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 const EMPTY_WS_RESPONSE_FALLBACK: &str =
@@ -45,7 +59,7 @@ pub async fn handle_ws_sync(
     if state.pairing.require_pairing() {
         let token =
             extract_ws_bearer_token(&headers, query_params.token.as_deref()).unwrap_or_default();
-        if !state.pairing.is_authenticated(&token) {
+        if !state.is_bearer_authorized(&token) {
             return (axum::http::StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
         }
     }
@@ -513,7 +527,15 @@ pub async fn handle_ws_chat(
     let auth_token = extract_ws_bearer_token(&headers, query_params.token.as_deref())
         .unwrap_or_default()
         .to_string();
-    if state.pairing.require_pairing() && !state.pairing.is_authenticated(&auth_token) {
+    // Login sessions (password or SSO) are accepted alongside pairing tokens.
+    let session = state
+        .auth_store
+        .as_ref()
+        .and_then(|store| store.validate_session(&auth_token));
+    if state.pairing.require_pairing()
+        && !state.is_bearer_authorized(&auth_token)
+        && session.is_none()
+    {
         return (
             axum::http::StatusCode::UNAUTHORIZED,
             "Unauthorized — provide Authorization: Bearer <token>, Sec-WebSocket-Protocol: bearer.<token>, or ?token=<token>",
@@ -521,20 +543,31 @@ pub async fn handle_ws_chat(
             .into_response();
     }
 
-    // Resolve user_id from session token (for device routing).
-    let user_id = state
-        .auth_store
-        .as_ref()
-        .and_then(|store| store.validate_session(&auth_token))
-        .map(|session| session.user_id);
+    // Resolve user_id from session token (for device routing), and the
+    // user's role, which limits the tools their chat turns may use.
+    let user_id = session.map(|session| session.user_id);
+    let role = match (state.auth_store.as_ref(), user_id.as_deref()) {
+        (Some(store), Some(user_id)) => store.get_user_role(user_id).ok().flatten(),
+        _ => None,
+    };
+    if let Err((status, body)) = super::api::check_session_role(&state, role.as_deref(), false) {
+        return (status, body).into_response();
+    }
 
     let session_id = query_params
         .session_id
         .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
     let target_device_id = query_params.target_device_id;
+    // The socket outlives this handler, so carry the request's tenant over.
+    let tenant = crate::tenancy::current();
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state, session_id, user_id, target_device_id))
-        .into_response()
+    ws.on_upgrade(move |socket| {
+        crate::tenancy::scope(
+            tenant,
+            handle_socket(socket, state, session_id, user_id, role, target_device_id),
+        )
+    })
+    .into_response()
 }
 
 /// Attempt to relay a chat message to the user's local MoA device.
@@ -993,6 +1026,7 @@ async fn handle_socket(
     state: AppState,
     session_id: String,
     user_id: Option<String>,
+    role: Option<String>,
     target_device_id: Option<String>,
 ) {
     let ws_session_id = format!("ws_{}", Uuid::new_v4());
@@ -1020,11 +1054,22 @@ async fn handle_socket(
         .send(Message::Text(history_payload.to_string().into()))
         .await;
 
-    while let Some(msg) = socket.recv().await {
-        let msg = match msg {
-            Ok(Message::Text(text)) => text,
-            Ok(Message::Close(_)) | Err(_) => break,
-            _ => continue,
+    // Frames that arrived while a turn was running, replayed in order.
+    let mut queued: VecDeque<String> = VecDeque::new();
+    let mut client_closed = false;
+
+    loop {
+        if client_closed {
+            break;
+        }
+        let msg = if let Some(text) = queued.pop_front() {
+            text
+        } else {
+            match socket.recv().await {
+                Some(Ok(Message::Text(text))) => text.to_string(),
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            }
         };
 
         // Parse incoming message
@@ -1038,6 +1083,18 @@ async fn handle_socket(
         };
 
         let msg_type = parsed["type"].as_str().unwrap_or("");
+        if matches!(msg_type, "checkpoint_list" | "checkpoint_restore") {
+            let policy = {
+                let config = state.config.lock();
+                crate::security::SecurityPolicy::from_config(
+                    &config.autonomy,
+                    &config.workspace_dir,
+                )
+            };
+            let reply = handle_ws_checkpoint_frame(&policy, &parsed);
+            let _ = socket.send(Message::Text(reply.to_string().into())).await;
+            continue;
+        }
         if msg_type != "message" {
             continue;
         }
@@ -1047,6 +1104,19 @@ async fn handle_socket(
             continue;
         }
 
+        // One server span per turn. Browsers cannot set headers on a
+        // WebSocket, so callers continue their own trace by sending a W3C
+        // `traceparent` field on the message frame.
+        let mut turn_span = trace_context::Span::start_with_parent(
+            "ws.chat",
+            trace_context::SpanKind::Server,
+            parsed["traceparent"]
+                .as_str()
+                .and_then(trace_context::TraceContext::from_traceparent),
+        );
+        turn_span.set_attribute("zeroclaw.session_id", session_id.clone());
+        let trace_id = turn_span.context().trace_id_hex();
+
         // ── SLM-first gatekeeper (★ MoA core workflow) ──
         //
         // Symmetrical to the block in openclaw_compat::handle_api_chat.
@@ -1079,6 +1149,7 @@ async fn handle_socket(
                     "type": "done",
                     "full_response": local_reply,
                     "session_id": session_id.as_str(),
+                    "trace_id": trace_id,
                     "active_provider": "ollama",
                     "active_model": router.model(),
                     "is_local_path": true,
@@ -1096,6 +1167,7 @@ async fn handle_socket(
                     "model": router.model(),
                     "is_local_path": true,
                     "network_status": "local",
+                    "trace_id": trace_id,
                 }));
                 continue;
             }
@@ -1429,13 +1501,29 @@ async fn handle_socket(
             if eligible {
                 // Same safe_for_slm filtering as the REST path — the
                 // on-device SLM never sees shell/delegate/file_write etc.
+                // The user's role narrows the set further.
+                let role_access = role.as_deref().map(|role| {
+                    let config = state.config.lock();
+                    (
+                        crate::security::RoleRegistry::from_config(&config.security.roles),
+                        config.security.otp.gated_actions.clone(),
+                        role,
+                    )
+                });
                 let tool_refs: Vec<&dyn crate::tools::Tool> = state
                     .tools_registry_exec
                     .as_ref()
                     .iter()
                     .filter_map(|boxed| {
                         let t = boxed.as_ref();
-                        t.safe_for_slm().then_some(t)
+                        let permitted = match &role_access {
+                            None => true,
+                            Some((Ok(registry), gated, role)) => {
+                                registry.allows_without_totp(role, t.name(), gated)
+                            }
+                            Some((Err(_), _, _)) => false,
+                        };
+                        (t.safe_for_slm() && permitted).then_some(t)
                     })
                     .collect();
                 match executor.run(&enriched_content, &tool_refs).await {
@@ -1463,12 +1551,34 @@ async fn handle_socket(
         let agent_outcome = if let Some(r) = ws_slm_reply.clone() {
             Ok(r)
         } else {
-            Box::pin(super::run_gateway_chat_with_tools(
-                &state,
-                &enriched_content,
-                Some(&ws_session_id),
-            ))
-            .await
+            let turn_cancel = CancellationToken::new();
+            let _active_turn =
+                crate::agent::turn_control::register(ws_session_id.clone(), turn_cancel.clone());
+            let attribution = crate::cost::CostAttribution {
+                user: user_id.clone(),
+                channel: Some("ws".into()),
+                session: Some(session_id.clone()),
+                ..Default::default()
+            };
+            let turn = Box::pin(crate::agent::loop_::scope_turn_cancellation(
+                turn_cancel.clone(),
+                crate::cost::attribution::scope(
+                    attribution,
+                    trace_context::scope(
+                        turn_span.context(),
+                        super::run_gateway_chat_with_tools(
+                            &state,
+                            &enriched_content,
+                            Some(&ws_session_id),
+                            role.as_deref(),
+                        ),
+                    ),
+                ),
+            ));
+            let (outcome, closed) =
+                drive_cancellable_ws_turn(&mut socket, turn, &turn_cancel, &mut queued).await;
+            client_closed = closed;
+            outcome
         };
         match agent_outcome {
             Ok(response) => {
@@ -1583,6 +1693,7 @@ async fn handle_socket(
                     "network_status": if net_online { "online" } else { "offline" },
                     "advisor": advisor_meta,
                     "slm_executor": slm_meta,
+                    "trace_id": trace_id,
                 });
                 let _ = socket.send(Message::Text(done.to_string().into())).await;
 
@@ -1593,10 +1704,24 @@ async fn handle_socket(
                     "model": state.model,
                     "is_local_path": is_local_path,
                     "network_status": if net_online { "online" } else { "offline" },
+                    "trace_id": trace_id,
+                }));
+            }
+            Err(e) if crate::agent::loop_::is_tool_loop_cancelled(&e) => {
+                let cancelled = serde_json::json!({
+                    "type": "cancelled",
+                    "session_id": session_id.as_str(),
+                    "trace_id": trace_id,
+                });
+                let _ = socket.send(Message::Text(cancelled.to_string().into())).await;
+                let _ = state.event_tx.send(serde_json::json!({
+                    "type": "agent_cancelled",
+                    "component": "ws_chat",
                 }));
             }
             Err(e) => {
                 let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+                turn_span.set_error(sanitized.clone());
 
                 // Detect provider authentication errors (401 Unauthorized) so
                 // the client can fall back to relay or prompt the user.
@@ -1621,11 +1746,13 @@ async fn handle_socket(
                         ),
                         "detail": sanitized,
                         "fallback_to_relay": true,
+                        "trace_id": trace_id,
                     })
                 } else {
                     serde_json::json!({
                         "type": "error",
                         "message": sanitized,
+                        "trace_id": trace_id,
                     })
                 };
                 let _ = socket.send(Message::Text(err.to_string().into())).await;
@@ -1641,6 +1768,79 @@ async fn handle_socket(
     }
 }
 
+/// Answer a `checkpoint_list` / `checkpoint_restore` frame. Turns never
+/// overlap a restore: frames received mid-turn are queued until it ends.
+fn handle_ws_checkpoint_frame(
+    policy: &crate::security::SecurityPolicy,
+    frame: &serde_json::Value,
+) -> serde_json::Value {
+    let store = crate::checkpoint::CheckpointStore::for_workspace(&policy.workspace_dir);
+    let result = if frame["type"] == "checkpoint_list" {
+        let limit = frame["limit"]
+            .as_u64()
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(20);
+        store.list().map(|checkpoints| {
+            let checkpoints: Vec<_> = checkpoints.into_iter().take(limit).collect();
+            serde_json::json!({ "type": "checkpoints", "checkpoints": checkpoints })
+        })
+    } else {
+        let id = match frame["id"].as_str() {
+            Some(id) => Ok(Some(id.to_string())),
+            None => store
+                .list()
+                .map(|checkpoints| checkpoints.into_iter().next().map(|c| c.id)),
+        };
+        id.and_then(|id| {
+            let id = id.ok_or_else(|| anyhow::anyhow!("No checkpoints to undo"))?;
+            store.restore(&id, policy)
+        })
+        .map(|report| {
+            serde_json::json!({
+                "type": "checkpoint_restored",
+                "checkpoints": report.checkpoints,
+                "restored": report.restored,
+                "removed": report.removed,
+            })
+        })
+    };
+    result.unwrap_or_else(|e| serde_json::json!({ "type": "error", "message": e.to_string() }))
+}
+
+fn is_ws_cancel_frame(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text).is_ok_and(|frame| frame["type"] == "cancel")
+}
+
+/// Drive an agent turn while still reading the socket so a `cancel` frame
+/// can stop it. Other frames are queued for after the turn. A disconnect
+/// also cancels the turn; the second tuple element reports it.
+async fn drive_cancellable_ws_turn<F: Future + Unpin>(
+    socket: &mut WebSocket,
+    mut turn: F,
+    cancel: &CancellationToken,
+    queued: &mut VecDeque<String>,
+) -> (F::Output, bool) {
+    let mut closed = false;
+    loop {
+        tokio::select! {
+            outcome = &mut turn => return (outcome, closed),
+            frame = socket.recv(), if !closed => match frame {
+                Some(Ok(Message::Text(text))) if is_ws_cancel_frame(&text) => {
+                    cancel.cancel();
+                    let ack = serde_json::json!({ "type": "cancelling" });
+                    let _ = socket.send(Message::Text(ack.to_string().into())).await;
+                }
+                Some(Ok(Message::Text(text))) => queued.push_back(text.to_string()),
+                Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                    closed = true;
+                    cancel.cancel();
+                }
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
 fn extract_ws_bearer_token(headers: &HeaderMap, query_token: Option<&str>) -> Option<String> {
     if let Some(auth_header) = headers
         .get(header::AUTHORIZATION)
@@ -1677,6 +1877,12 @@ fn extract_query_token(raw_query: Option<&str>) -> Option<String> {
     parse_ws_query_params(raw_query).token
 }
 
+/// Bearer token from any place a WebSocket handler accepts one. Used by the
+/// API token middleware so it inspects the same token the handler will.
+pub(super) fn request_bearer_token(headers: &HeaderMap, raw_query: Option<&str>) -> Option<String> {
+    extract_ws_bearer_token(headers, extract_query_token(raw_query).as_deref())
+}
+
 // ── Voice interpretation WebSocket ────────────────────────────────
 
 /// GET /ws/voice — WebSocket upgrade for simultaneous interpretation
@@ -1692,7 +1898,7 @@ pub async fn handle_ws_voice(
     if state.pairing.require_pairing() {
         let token =
             extract_ws_bearer_token(&headers, query_params.token.as_deref()).unwrap_or_default();
-        if !state.pairing.is_authenticated(&token) {
+        if !state.is_bearer_authorized(&token) {
             return (
                 axum::http::StatusCode::UNAUTHORIZED,
                 "Unauthorized — provide Authorization: Bearer <token>, Sec-WebSocket-Protocol: bearer.<token>, or ?token=<token>",
@@ -1722,6 +1928,7 @@ pub async fn handle_ws_voice(
 /// Protocol:
 /// ```text
 /// Client -> Server: {"type":"session_start","sessionId":"...","sourceLang":"ko","targetLang":"en",...}
+/// Client -> Server: {"type":"session_start",...,"roomId":"...","displayName":"..."}   (join a conference room)
 /// Client -> Server: {"type":"audio_chunk","sessionId":"...","seq":0,"ts":...,"pcm16le":"base64..."}
 /// Client -> Server: {"type":"session_stop","sessionId":"..."}
 /// Server -> Client: {"type":"session_ready","sessionId":"...","liveSessionId":"..."}
@@ -1730,7 +1937,13 @@ pub async fn handle_ws_voice(
 /// Server -> Client: {"type":"commit_tgt","sessionId":"...","commitId":1,"text":"..."}
 /// Server -> Client: {"type":"audio_out","sessionId":"...","seq":0,"pcm16le":"base64..."}
 /// Server -> Client: {"type":"session_ended","sessionId":"...","totalSegments":5}
+/// Server -> Client: {"type":"conference_event","event":{"type":"transcript","participant_id":"...",...}}
 /// ```
+///
+/// A session that names a `roomId` also takes a seat in that conference room:
+/// its committed source text (and translation) reaches the other participants,
+/// and with `[voice] record_meetings` the room keeps a meeting record that is
+/// archived when the last participant leaves.
 /// Unified session handle — wraps either Gemini-based or Deepgram-based session.
 ///
 /// Both providers share the same `send_audio` / `event_rx` / `stop` interface,
@@ -1777,9 +1990,9 @@ impl VoiceSessionHandle {
 async fn handle_voice_socket(mut socket: WebSocket, state: AppState) {
     use crate::voice::{
         deepgram_simul::{DeepgramSimulConfig, DeepgramSimulSession},
-        events::{ClientMessage, ServerMessage},
+        events::ClientMessage,
         gemma_simul::{GemmaSimulConfig, GemmaSimulSession},
-        pipeline::{Domain, Formality, LanguageCode, VoiceAge, VoiceGender},
+        pipeline::{Domain, Formality, VoiceAge, VoiceGender},
         simul::SegmentationConfig,
         simul_session::{SimulSession, SimulSessionConfig},
         typecast_interp::{TypecastInterpConfig, TypecastInterpSession},
@@ -1833,7 +2046,9 @@ async fn handle_voice_socket(mut socket: WebSocket, state: AppState) {
                 voice_gender,
                 voice_age,
                 voice_clone_id,
-                device_id: _,
+                device_id,
+                room_id,
+                display_name,
             } => {
                 // Stop any existing session
                 if let Some(session) = active_session.take() {
@@ -2038,9 +2253,49 @@ async fn handle_voice_socket(mut socket: WebSocket, state: AppState) {
                         let event_rx = session.event_rx();
                         let (ws_tx, mut ws_rx) = tokio::sync::mpsc::channel::<String>(256);
 
+                        let seat = match room_id {
+                            Some(room_id) => {
+                                let seat = ConferenceSeat::join(
+                                    &state,
+                                    ConferenceConfig::new(room_id, device_id.clone()),
+                                    &session_id,
+                                    display_name.unwrap_or(device_id),
+                                    src_lang,
+                                    tgt_lang,
+                                    ws_tx.clone(),
+                                )
+                                .await;
+                                match seat {
+                                    Ok(seat) => Some(seat),
+                                    Err(e) => {
+                                        session.stop().await;
+                                        let err = ServerMessage::Error {
+                                            session_id,
+                                            code: "CONFERENCE_JOIN_FAILED".into(),
+                                            message: format!("Failed to join conference: {e}"),
+                                        };
+                                        let _ = socket
+                                            .send(Message::Text(
+                                                serde_json::to_string(&err)
+                                                    .unwrap_or_default()
+                                                    .into(),
+                                            ))
+                                            .await;
+                                        continue;
+                                    }
+                                }
+                            }
+                            None => None,
+                        };
+                        let seat_room = seat.as_ref().map(|seat| std::sync::Arc::clone(&seat.room));
+                        let participant_id = session_id.clone();
+
                         let relay = tokio::spawn(async move {
                             let mut rx = event_rx.lock().await;
                             while let Some(event) = rx.recv().await {
+                                if let Some(room) = &seat_room {
+                                    record_conference_segment(room, &participant_id, &event).await;
+                                }
                                 if let Ok(json) = serde_json::to_string(&event) {
                                     if ws_tx.send(json).await.is_err() {
                                         break;
@@ -2072,6 +2327,9 @@ async fn handle_voice_socket(mut socket: WebSocket, state: AppState) {
                         if let Some(handle) = relay_handle.take() {
                             handle.abort();
                         }
+                        if let Some(seat) = seat {
+                            seat.leave(&state).await;
+                        }
                         return;
                     }
                     Err(e) => {
@@ -2127,6 +2385,110 @@ async fn handle_voice_socket(mut socket: WebSocket, state: AppState) {
     }
 }
 
+/// A voice session's seat in the conference room named by `roomId`.
+struct ConferenceSeat {
+    room: std::sync::Arc<ConferenceRoom>,
+    participant_id: String,
+    /// Forwards the room's events (other participants) to this socket.
+    forward: tokio::task::JoinHandle<()>,
+}
+
+impl ConferenceSeat {
+    /// Open (or create) the room and join it as `participant_id`.
+    async fn join(
+        state: &AppState,
+        room: ConferenceConfig,
+        participant_id: &str,
+        display_name: String,
+        source_lang: LanguageCode,
+        target_lang: LanguageCode,
+        ws_tx: tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<Self> {
+        let conferences = state.voice_sessions.conferences();
+        let room = conferences.open_room(room).await?;
+        let mut events = match room
+            .join(
+                participant_id.to_string(),
+                display_name,
+                source_lang,
+                target_lang,
+            )
+            .await
+        {
+            Ok(events) => events,
+            Err(e) => {
+                // Do not leave a room we just opened sitting empty.
+                if room.summary().await.participant_count == 0 {
+                    let _ = conferences.close_room(room.room_id()).await;
+                }
+                return Err(e);
+            }
+        };
+        let forward = tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                let frame = serde_json::json!({ "type": "conference_event", "event": event });
+                if ws_tx.send(frame.to_string()).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            room,
+            participant_id: participant_id.to_string(),
+            forward,
+        })
+    }
+
+    /// Leave the room; the last participant out closes (and archives) it.
+    async fn leave(self, state: &AppState) {
+        self.forward.abort();
+        let room_id = self.room.room_id();
+        match state
+            .voice_sessions
+            .conferences()
+            .leave_room(room_id, &self.participant_id)
+            .await
+        {
+            Ok(Some(record)) => tracing::info!(
+                room_id,
+                transcript = %record.transcript_path.display(),
+                "Conference closed and meeting record archived"
+            ),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(room_id, "Failed to leave conference: {e}"),
+        }
+    }
+}
+
+/// Feed a participant's interpretation events into their conference room:
+/// a partial source transcript opens a segment and a commit closes it and
+/// reaches the other participants.
+async fn record_conference_segment(
+    room: &ConferenceRoom,
+    participant_id: &str,
+    event: &ServerMessage,
+) {
+    let result = match event {
+        ServerMessage::PartialSrc { .. } => {
+            room.mark_segment_start(participant_id).await;
+            Ok(())
+        }
+        ServerMessage::CommitSrc { text, .. } => {
+            room.broadcast_transcript(participant_id, text, true).await
+        }
+        ServerMessage::CommitTgt { text, .. } => {
+            room.broadcast_transcript(participant_id, text, false).await
+        }
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::warn!(
+            room_id = room.room_id(),
+            "Failed to relay conference transcript: {e}"
+        );
+    }
+}
+
 /// Inner loop for an active voice interpretation session (unified for all providers).
 ///
 /// Simultaneously drains relay events (ServerMessage → WebSocket) and receives
@@ -2136,7 +2498,7 @@ async fn voice_session_loop_unified(
     session: &VoiceSessionHandle,
     relay_rx: &mut tokio::sync::mpsc::Receiver<String>,
 ) {
-    use crate::voice::events::{ClientMessage, ServerMessage};
+    use crate::voice::events::ClientMessage;
     use base64::Engine;
 
     loop {
@@ -2220,7 +2582,7 @@ pub async fn handle_ws_stt(
     if state.pairing.require_pairing() {
         let token =
             extract_ws_bearer_token(&headers, query_params.token.as_deref()).unwrap_or_default();
-        if !state.pairing.is_authenticated(&token) {
+        if !state.is_bearer_authorized(&token) {
             return (axum::http::StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
         }
     }
@@ -2244,14 +2606,55 @@ pub async fn handle_ws_stt(
 /// Server -> Client: {"type":"stt_utterance_end","last_word_end":2.3}
 /// Server -> Client: {"type":"stt_closed"}
 /// ```
+/// STT session behind `/ws/stt`, selected by `voice.stt_provider`.
+enum SttSocketSession {
+    Deepgram(crate::voice::DeepgramSttSession),
+    Local(crate::voice::LocalSttSession),
+}
+
+impl SttSocketSession {
+    async fn send_audio(&self, pcm: Vec<u8>) -> anyhow::Result<()> {
+        match self {
+            Self::Deepgram(s) => s.send_audio(pcm).await,
+            Self::Local(s) => s.send_audio(pcm).await,
+        }
+    }
+
+    async fn finalize(&self) -> anyhow::Result<()> {
+        match self {
+            Self::Deepgram(s) => s.finalize().await,
+            Self::Local(s) => s.finalize().await,
+        }
+    }
+
+    async fn close(&self) {
+        match self {
+            Self::Deepgram(s) => s.close().await,
+            Self::Local(s) => s.close().await,
+        }
+    }
+
+    async fn recv_event(&self) -> Option<crate::voice::SttEvent> {
+        match self {
+            Self::Deepgram(s) => s.recv_event().await,
+            Self::Local(s) => s.recv_event().await,
+        }
+    }
+}
+
 async fn handle_stt_socket(mut socket: WebSocket, state: AppState) {
     use crate::voice::deepgram_stt::{DeepgramConfig, DeepgramSttSession, SttEvent};
+    use crate::voice::LocalSttSession;
 
     // Read config
-    let voice_config = {
+    let (voice_config, local_stt_config) = {
         let config_guard = state.config.lock();
-        config_guard.voice.clone()
+        (
+            config_guard.voice.clone(),
+            config_guard.transcription.local.clone(),
+        )
     };
+    let use_local = voice_config.stt_provider.eq_ignore_ascii_case("local");
 
     // Resolve Deepgram API key
     let api_key = voice_config
@@ -2260,7 +2663,7 @@ async fn handle_stt_socket(mut socket: WebSocket, state: AppState) {
         .or_else(|| std::env::var("DEEPGRAM_API_KEY").ok())
         .unwrap_or_default();
 
-    if api_key.is_empty() {
+    if api_key.is_empty() && !use_local {
         let err_json = serde_json::json!({
             "type": "stt_error",
             "message": "Deepgram API key not configured. Set it in voice.deepgram_api_key or DEEPGRAM_API_KEY env var."
@@ -2303,27 +2706,41 @@ async fn handle_stt_socket(mut socket: WebSocket, state: AppState) {
         _ => {}
     }
 
-    // Connect to Deepgram
     let session_id = uuid::Uuid::new_v4().to_string();
-    let dg_config = DeepgramConfig {
-        api_key,
-        model,
-        language,
-        interim_results: true,
-        smart_format: true,
-        punctuate: true,
-        endpointing_ms: Some(300),
-        utterance_end_ms: Some(1000),
-        vad_events: true,
-        ..DeepgramConfig::default()
+    let connected = if use_local {
+        // "multi" is Deepgram's auto-detect; the local engine auto-detects
+        // when no language is given.
+        let language = Some(language).filter(|l| l != "multi");
+        LocalSttSession::start(session_id.clone(), local_stt_config, language)
+            .await
+            .map(SttSocketSession::Local)
+            .map_err(|e| format!("Failed to start local STT: {e}"))
+    } else {
+        // Connect to Deepgram
+        let dg_config = DeepgramConfig {
+            api_key,
+            model,
+            language,
+            interim_results: true,
+            smart_format: true,
+            punctuate: true,
+            endpointing_ms: Some(300),
+            utterance_end_ms: Some(1000),
+            vad_events: true,
+            ..DeepgramConfig::default()
+        };
+        DeepgramSttSession::connect(session_id.clone(), &dg_config)
+            .await
+            .map(SttSocketSession::Deepgram)
+            .map_err(|e| format!("Failed to connect to Deepgram: {e}"))
     };
 
-    let dg_session = match DeepgramSttSession::connect(session_id.clone(), &dg_config).await {
+    let dg_session = match connected {
         Ok(s) => s,
-        Err(e) => {
+        Err(message) => {
             let err_json = serde_json::json!({
                 "type": "stt_error",
-                "message": format!("Failed to connect to Deepgram: {e}")
+                "message": message
             });
             let _ = socket
                 .send(Message::Text(err_json.to_string().into()))
@@ -2370,7 +2787,7 @@ async fn handle_stt_socket(mut socket: WebSocket, state: AppState) {
                 match msg {
                     Ok(Message::Binary(data)) => {
                         if let Err(e) = dg_session.send_audio(data.to_vec()).await {
-                            tracing::warn!(error = %e, "Failed to send audio to Deepgram");
+                            tracing::warn!(error = %e, "Failed to send audio to STT session");
                             break;
                         }
                     }
@@ -2488,6 +2905,39 @@ mod tests {
         assert!(extract_query_token(Some("foo=1")).is_none());
     }
 
+    #[test]
+    fn is_ws_cancel_frame_matches_only_cancel_type() {
+        assert!(is_ws_cancel_frame(r#"{"type":"cancel"}"#));
+        assert!(!is_ws_cancel_frame(r#"{"type":"message","content":"cancel"}"#));
+        assert!(!is_ws_cancel_frame("cancel"));
+    }
+
+    #[test]
+    fn checkpoint_frames_list_and_undo_latest_turn() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("notes.txt");
+        std::fs::write(&file, "before").unwrap();
+        crate::checkpoint::record_before_write(tmp.path(), &file);
+        std::fs::write(&file, "after").unwrap();
+        let policy = crate::security::SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..crate::security::SecurityPolicy::default()
+        };
+
+        let listed =
+            handle_ws_checkpoint_frame(&policy, &serde_json::json!({ "type": "checkpoint_list" }));
+        assert_eq!(listed["type"], "checkpoints");
+        assert_eq!(listed["checkpoints"].as_array().unwrap().len(), 1);
+
+        let restore = serde_json::json!({ "type": "checkpoint_restore" });
+        let restored = handle_ws_checkpoint_frame(&policy, &restore);
+        assert_eq!(restored["type"], "checkpoint_restored");
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "before");
+
+        let empty = handle_ws_checkpoint_frame(&policy, &restore);
+        assert_eq!(empty["type"], "error");
+    }
+
     #[test]
     fn parse_ws_query_params_reads_token_and_session_id() {
         let parsed = parse_ws_query_params(Some("foo=1&session_id=sess_123&token=query-token"));
@@ -0,0 +1,207 @@
+use super::engine::{Goal, GoalEngine, GoalPriority, GoalStatus, NewGoal, NewStep, StepStatus};
+use crate::config::Config;
+use anyhow::{bail, Result};
+use chrono::Utc;
+
+/// Attempts shown by `zeroclaw goals show`.
+const SHOWN_ATTEMPTS: usize = 5;
+
+/// Handle `zeroclaw goals <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::GoalCommands, config: &Config) -> Result<()> {
+    let engine = GoalEngine::new(&config.workspace_dir);
+    match command {
+        crate::GoalCommands::List { all, json } => {
+            let state = engine.load_state().await?;
+            let goals: Vec<&Goal> = state
+                .goals
+                .iter()
+                .filter(|g| {
+                    all || !matches!(g.status, GoalStatus::Completed | GoalStatus::Cancelled)
+                })
+                .collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&goals)?);
+            } else if goals.is_empty() {
+                println!("No goals.");
+            } else {
+                for goal in goals {
+                    print_row(goal);
+                }
+            }
+            Ok(())
+        }
+        crate::GoalCommands::Show { id, json } => {
+            let mut state = engine.load_state().await?;
+            let goal = state.goal_mut(&id)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(goal)?);
+            } else {
+                print_goal(goal);
+            }
+            Ok(())
+        }
+        crate::GoalCommands::Add {
+            description,
+            steps,
+            checkpoints,
+            priority,
+            owner,
+            budget_usd,
+            paused,
+        } => {
+            if let Some(n) = checkpoints.iter().find(|&&n| n == 0 || n > steps.len()) {
+                bail!(
+                    "--checkpoint {n} does not name one of the {} steps",
+                    steps.len()
+                );
+            }
+            let new = NewGoal {
+                description,
+                steps: steps
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, description)| NewStep {
+                        description,
+                        checkpoint: checkpoints.contains(&(i + 1)),
+                    })
+                    .collect(),
+                priority: parse_priority(&priority)?,
+                owner,
+                budget_usd,
+                paused,
+            };
+            let now = Utc::now().to_rfc3339();
+            let goal = engine.update(|state| state.add_goal(new, &now)).await?;
+            println!("Added {}: {}", goal.id, goal.description);
+            if !config.goal_loop.enabled {
+                println!("Note: [goal_loop] is disabled, so the daemon will not work on it yet.");
+            }
+            Ok(())
+        }
+        crate::GoalCommands::AddStep {
+            id,
+            description,
+            checkpoint,
+        } => {
+            let step = engine
+                .update(|state| {
+                    Ok(state
+                        .goal_mut(&id)?
+                        .add_step(NewStep {
+                            description,
+                            checkpoint,
+                        })?
+                        .clone())
+                })
+                .await?;
+            println!("Added step {} to {id}", step.id);
+            Ok(())
+        }
+        crate::GoalCommands::Approve { id, step } => {
+            let step = engine
+                .update(|state| {
+                    Ok(state
+                        .goal_mut(&id)?
+                        .approve_checkpoint(step.as_deref())?
+                        .clone())
+                })
+                .await?;
+            super::settle_checkpoint_task(&step);
+            println!("Approved step {} of {id}: {}", step.id, step.description);
+            Ok(())
+        }
+        crate::GoalCommands::Pause { id } => set_status(&engine, &id, GoalStatus::Pending).await,
+        crate::GoalCommands::Resume { id } => {
+            set_status(&engine, &id, GoalStatus::InProgress).await
+        }
+        crate::GoalCommands::Cancel { id } => set_status(&engine, &id, GoalStatus::Cancelled).await,
+    }
+}
+
+async fn set_status(engine: &GoalEngine, id: &str, status: GoalStatus) -> Result<()> {
+    let goal = engine
+        .update(|state| super::set_status(state, id, status))
+        .await?;
+    println!("{} is now {}", goal.id, status_label(&goal.status));
+    Ok(())
+}
+
+fn parse_priority(raw: &str) -> Result<GoalPriority> {
+    Ok(match raw.trim().to_ascii_lowercase().as_str() {
+        "low" => GoalPriority::Low,
+        "medium" => GoalPriority::Medium,
+        "high" => GoalPriority::High,
+        "critical" => GoalPriority::Critical,
+        other => bail!("Unknown priority '{other}'. Expected low, medium, high or critical"),
+    })
+}
+
+fn status_label(status: &GoalStatus) -> &'static str {
+    match status {
+        GoalStatus::Pending => "paused",
+        GoalStatus::InProgress => "active",
+        GoalStatus::Completed => "completed",
+        GoalStatus::Blocked => "blocked",
+        GoalStatus::Cancelled => "cancelled",
+    }
+}
+
+fn print_row(goal: &Goal) {
+    let done = goal
+        .steps
+        .iter()
+        .filter(|s| s.status == StepStatus::Completed)
+        .count();
+    println!(
+        "{}  {:<9}  {:<8}  {done}/{} steps  ${:.2}  {}",
+        goal.id,
+        status_label(&goal.status),
+        format!("{:?}", goal.priority).to_ascii_lowercase(),
+        goal.steps.len(),
+        goal.spent_usd(),
+        goal.description
+    );
+}
+
+fn print_goal(goal: &Goal) {
+    println!("{} — {}", goal.id, goal.description);
+    println!("  Status:   {}", status_label(&goal.status));
+    println!("  Priority: {:?}", goal.priority);
+    println!("  Owner:    {}", goal.owner.as_deref().unwrap_or("-"));
+    match goal.budget_usd {
+        Some(budget) => println!("  Spent:    ${:.4} of ${budget:.2}", goal.spent_usd()),
+        None => println!("  Spent:    ${:.4}", goal.spent_usd()),
+    }
+    if let Some(error) = &goal.last_error {
+        println!("  Last error: {error}");
+    }
+    println!("\nSteps:");
+    for step in &goal.steps {
+        let gate = if step.awaiting_approval() {
+            " (awaiting approval)"
+        } else {
+            ""
+        };
+        println!(
+            "  {:<4} {:<11} {}{gate}  [{} attempt(s)]",
+            step.id,
+            format!("{:?}", step.status).to_ascii_lowercase(),
+            step.description,
+            step.attempts
+        );
+    }
+    if !goal.history.is_empty() {
+        println!("\nRecent attempts:");
+        for attempt in goal.history.iter().rev().take(SHOWN_ATTEMPTS) {
+            println!(
+                "  {}  {:?} {}  {}  ${:.4}  {}",
+                attempt.at,
+                attempt.kind,
+                attempt.step_id.as_deref().unwrap_or("-"),
+                if attempt.success { "ok" } else { "failed" },
+                attempt.cost_usd,
+                crate::util::truncate_with_ellipsis(&attempt.summary, 120)
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
@@ -6,6 +6,12 @@ use std::path::{Path, PathBuf};
 /// Maximum retry attempts per step before marking the goal as blocked.
 const MAX_STEP_ATTEMPTS: u32 = 3;
 
+/// Attempts and reflections kept per goal; older entries are dropped.
+const MAX_GOAL_HISTORY: usize = 50;
+
+/// Serializes read-modify-write cycles on the state file within a process.
+static STATE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
 // ── Data Structures ─────────────────────────────────────────────
 
 /// Root state persisted to `{workspace}/state/goals.json`.
@@ -36,6 +42,64 @@ pub struct Goal {
     /// Last error encountered during step execution.
     #[serde(default)]
     pub last_error: Option<String>,
+    /// Who hears about milestones and stalls, as `channel:recipient`.
+    /// Falls back to `[goal_loop] channel` and `target`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Spend cap for this goal in USD; overrides `[goal_loop] goal_budget_usd`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_usd: Option<f64>,
+    /// Step attempts and reflections run by the goal loop, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<GoalAttempt>,
+}
+
+impl Goal {
+    /// Index of the step the loop would run next for this goal.
+    pub fn next_step_index(&self) -> Option<usize> {
+        self.steps
+            .iter()
+            .position(|s| s.status == StepStatus::Pending && s.attempts < MAX_STEP_ATTEMPTS)
+    }
+
+    /// Total spend recorded against this goal's attempts.
+    pub fn spent_usd(&self) -> f64 {
+        self.history.iter().map(|a| a.cost_usd).sum()
+    }
+
+    /// Append an attempt, dropping the oldest beyond `MAX_GOAL_HISTORY`.
+    pub fn record(&mut self, attempt: GoalAttempt) {
+        self.updated_at.clone_from(&attempt.at);
+        self.history.push(attempt);
+        if self.history.len() > MAX_GOAL_HISTORY {
+            let excess = self.history.len() - MAX_GOAL_HISTORY;
+            self.history.drain(..excess);
+        }
+    }
+}
+
+/// One step execution or reflection run by the goal loop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalAttempt {
+    /// RFC 3339 start time.
+    pub at: String,
+    pub kind: AttemptKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+    pub success: bool,
+    /// Truncated agent output or failure reason.
+    #[serde(default)]
+    pub summary: String,
+    /// Ledger spend attributed to the attempt (`0` when cost tracking is off).
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptKind {
+    Step,
+    Reflection,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq, Default)]
@@ -94,6 +158,29 @@ pub struct Step {
     pub result: Option<String>,
     #[serde(default)]
     pub attempts: u32,
+    /// Human sign-off required before the step runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<StepCheckpoint>,
+}
+
+/// Approval state of a checkpointed step.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StepCheckpoint {
+    #[serde(default)]
+    pub approved: bool,
+    /// When the owner was first asked to approve.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_at: Option<String>,
+    /// Inbox task carrying the request, when the inbox is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+impl Step {
+    /// Whether the step is held back by an unapproved checkpoint.
+    pub fn awaiting_approval(&self) -> bool {
+        self.checkpoint.as_ref().is_some_and(|c| !c.approved)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq, Default)]
@@ -120,6 +207,124 @@ impl<'de> Deserialize<'de> for StepStatus {
     }
 }
 
+// ── Management ──────────────────────────────────────────────────
+
+/// A goal to create through the CLI, gateway or `goals` tool.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NewGoal {
+    pub description: String,
+    #[serde(default)]
+    pub steps: Vec<NewStep>,
+    #[serde(default)]
+    pub priority: GoalPriority,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub budget_usd: Option<f64>,
+    /// Create the goal paused instead of handing it to the goal loop.
+    #[serde(default)]
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NewStep {
+    pub description: String,
+    /// Require human approval before the step runs.
+    #[serde(default)]
+    pub checkpoint: bool,
+}
+
+impl GoalState {
+    /// Add a goal and return it. IDs are `goal-` plus eight hex digits.
+    pub fn add_goal(&mut self, new: NewGoal, now: &str) -> Result<Goal> {
+        let description = new.description.trim();
+        if description.is_empty() {
+            bail!("Goal description must not be empty");
+        }
+        if new
+            .budget_usd
+            .is_some_and(|usd| !usd.is_finite() || usd <= 0.0)
+        {
+            bail!("Goal budget must be a positive amount");
+        }
+        let mut goal = Goal {
+            id: format!("goal-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]),
+            description: description.to_string(),
+            status: if new.paused {
+                GoalStatus::Pending
+            } else {
+                GoalStatus::InProgress
+            },
+            priority: new.priority,
+            created_at: now.to_string(),
+            updated_at: now.to_string(),
+            steps: Vec::new(),
+            context: String::new(),
+            last_error: None,
+            owner: new.owner.filter(|owner| !owner.trim().is_empty()),
+            budget_usd: new.budget_usd,
+            history: Vec::new(),
+        };
+        for step in new.steps {
+            goal.add_step(step)?;
+        }
+        self.goals.push(goal.clone());
+        Ok(goal)
+    }
+
+    pub fn goal_mut(&mut self, id: &str) -> Result<&mut Goal> {
+        self.goals
+            .iter_mut()
+            .find(|g| g.id == id)
+            .with_context(|| format!("Goal {id} not found"))
+    }
+}
+
+impl Goal {
+    /// Append a pending step with the next free `sN` ID.
+    pub fn add_step(&mut self, new: NewStep) -> Result<&Step> {
+        let description = new.description.trim();
+        if description.is_empty() {
+            bail!("Step description must not be empty");
+        }
+        let mut n = self.steps.len() + 1;
+        while self.steps.iter().any(|s| s.id == format!("s{n}")) {
+            n += 1;
+        }
+        self.steps.push(Step {
+            id: format!("s{n}"),
+            description: description.to_string(),
+            status: StepStatus::Pending,
+            result: None,
+            attempts: 0,
+            checkpoint: new.checkpoint.then(StepCheckpoint::default),
+        });
+        Ok(&self.steps[self.steps.len() - 1])
+    }
+
+    /// Approve the checkpoint of `step_id`, or of the next step waiting on one.
+    pub fn approve_checkpoint(&mut self, step_id: Option<&str>) -> Result<&Step> {
+        let index = match step_id {
+            Some(id) => self
+                .steps
+                .iter()
+                .position(|s| s.id == id)
+                .with_context(|| format!("Step {id} not found in goal {}", self.id))?,
+            None => self
+                .steps
+                .iter()
+                .position(Step::awaiting_approval)
+                .with_context(|| format!("Goal {} has no step awaiting approval", self.id))?,
+        };
+        let step = &mut self.steps[index];
+        let Some(checkpoint) = step.checkpoint.as_mut() else {
+            bail!("Step {} has no checkpoint", step.id);
+        };
+        checkpoint.approved = true;
+        Ok(&self.steps[index])
+    }
+}
+
 // ── GoalEngine ──────────────────────────────────────────────────
 
 pub struct GoalEngine {
@@ -146,6 +351,17 @@ impl GoalEngine {
         Ok(state)
     }
 
+    /// Load, modify and save the state while holding the process-wide state
+    /// lock. The agent may edit `goals.json` itself between calls, so callers
+    /// re-resolve goals by ID rather than holding on to indices.
+    pub async fn update<R>(&self, f: impl FnOnce(&mut GoalState) -> Result<R>) -> Result<R> {
+        let _guard = STATE_LOCK.lock().await;
+        let mut state = self.load_state().await?;
+        let result = f(&mut state)?;
+        self.save_state(&state).await?;
+        Ok(result)
+    }
+
     /// Atomic save: write to .tmp then rename.
     pub async fn save_state(&self, state: &GoalState) -> Result<()> {
         if let Some(parent) = self.state_path.parent() {
@@ -163,17 +379,22 @@ impl GoalEngine {
     /// Strategy: highest-priority in-progress goal, first pending step
     /// that hasn't exceeded `MAX_STEP_ATTEMPTS`.
     pub fn select_next_actionable(state: &GoalState) -> Option<(usize, usize)> {
+        Self::select_next_actionable_where(state, |_| true)
+    }
+
+    /// [`Self::select_next_actionable`] restricted to goals accepted by
+    /// `eligible` (e.g. skipping goals waiting on a checkpoint).
+    pub fn select_next_actionable_where(
+        state: &GoalState,
+        eligible: impl Fn(&Goal) -> bool,
+    ) -> Option<(usize, usize)> {
         let mut best: Option<(usize, usize, GoalPriority)> = None;
 
         for (gi, goal) in state.goals.iter().enumerate() {
-            if goal.status != GoalStatus::InProgress {
+            if goal.status != GoalStatus::InProgress || !eligible(goal) {
                 continue;
             }
-            if let Some(si) = goal
-                .steps
-                .iter()
-                .position(|s| s.status == StepStatus::Pending && s.attempts < MAX_STEP_ATTEMPTS)
-            {
+            if let Some(si) = goal.next_step_index() {
                 match best {
                     Some((_, _, ref bp)) if goal.priority <= *bp => {}
                     _ => best = Some((gi, si, goal.priority)),
@@ -260,6 +481,11 @@ impl GoalEngine {
         MAX_STEP_ATTEMPTS
     }
 
+    /// Find a goal by ID.
+    pub fn find_goal<'a>(state: &'a mut GoalState, id: &str) -> Option<&'a mut Goal> {
+        state.goals.iter_mut().find(|g| g.id == id)
+    }
+
     /// Find in-progress goals that have no actionable steps remaining.
     ///
     /// A goal is "stalled" when it is `InProgress` but every step is either
@@ -272,13 +498,7 @@ impl GoalEngine {
             .iter()
             .enumerate()
             .filter(|(_, g)| g.status == GoalStatus::InProgress)
-            .filter(|(_, g)| {
-                !g.steps.is_empty()
-                    && !g
-                        .steps
-                        .iter()
-                        .any(|s| s.status == StepStatus::Pending && s.attempts < MAX_STEP_ATTEMPTS)
-            })
+            .filter(|(_, g)| !g.steps.is_empty() && g.next_step_index().is_none())
             .map(|(i, _)| i)
             .collect()
     }
@@ -350,6 +570,7 @@ mod tests {
                             status: StepStatus::Completed,
                             result: Some("Found 3 tools".into()),
                             attempts: 1,
+                            checkpoint: None,
                         },
                         Step {
                             id: "s2".into(),
@@ -357,6 +578,7 @@ mod tests {
                             status: StepStatus::Pending,
                             result: None,
                             attempts: 0,
+                            checkpoint: None,
                         },
                         Step {
                             id: "s3".into(),
@@ -364,10 +586,14 @@ mod tests {
                             status: StepStatus::Pending,
                             result: None,
                             attempts: 0,
+                            checkpoint: None,
                         },
                     ],
                     context: "Using Python + Selenium".into(),
                     last_error: None,
+                    owner: None,
+                    budget_usd: None,
+                    history: Vec::new(),
                 },
                 Goal {
                     id: "g2".into(),
@@ -382,9 +608,13 @@ mod tests {
                         status: StepStatus::Pending,
                         result: None,
                         attempts: 0,
+                        checkpoint: None,
                     }],
                     context: String::new(),
                     last_error: None,
+                    owner: None,
+                    budget_usd: None,
+                    history: Vec::new(),
                 },
             ],
         }
@@ -556,6 +786,7 @@ target = "oc_test"
                         status: StepStatus::Completed,
                         result: Some("ok".into()),
                         attempts: 1,
+                        checkpoint: None,
                     },
                     Step {
                         id: "s2".into(),
@@ -563,10 +794,14 @@ target = "oc_test"
                         status: StepStatus::Pending,
                         result: None,
                         attempts: 3, // >= MAX_STEP_ATTEMPTS
+                        checkpoint: None,
                     },
                 ],
                 context: String::new(),
                 last_error: Some("step failed 3 times".into()),
+                owner: None,
+                budget_usd: None,
+                history: Vec::new(),
             }],
         };
 
@@ -597,9 +832,13 @@ target = "oc_test"
                     status: StepStatus::Completed,
                     result: Some("ok".into()),
                     attempts: 1,
+                    checkpoint: None,
                 }],
                 context: String::new(),
                 last_error: None,
+                owner: None,
+                budget_usd: None,
+                history: Vec::new(),
             }],
         };
 
@@ -623,6 +862,7 @@ target = "oc_test"
                     status: StepStatus::Completed,
                     result: Some("worked".into()),
                     attempts: 1,
+                    checkpoint: None,
                 },
                 Step {
                     id: "s2".into(),
@@ -630,10 +870,14 @@ target = "oc_test"
                     status: StepStatus::Pending,
                     result: None,
                     attempts: 3,
+                    checkpoint: None,
                 },
             ],
             context: "some context".into(),
             last_error: Some("policy_denied".into()),
+            owner: None,
+            budget_usd: None,
+            history: Vec::new(),
         };
 
         let prompt = GoalEngine::build_reflection_prompt(&goal);
@@ -726,6 +970,9 @@ target = "oc_test"
                 steps: vec![],
                 context: String::new(),
                 last_error: None,
+                owner: None,
+                budget_usd: None,
+                history: Vec::new(),
             }],
         };
         assert!(GoalEngine::find_stalled_goals(&state).is_empty());
@@ -746,9 +993,13 @@ target = "oc_test"
                 status: StepStatus::Pending,
                 result: None,
                 attempts: MAX_STEP_ATTEMPTS,
+                checkpoint: None,
             }],
             context: String::new(),
             last_error: None,
+            owner: None,
+            budget_usd: None,
+            history: Vec::new(),
         };
         let state = GoalState {
             goals: vec![stalled_goal("g1"), stalled_goal("g2"), stalled_goal("g3")],
@@ -773,6 +1024,7 @@ target = "oc_test"
                         status: StepStatus::Completed,
                         result: Some("ok".into()),
                         attempts: 1,
+                        checkpoint: None,
                     },
                     Step {
                         id: "s2".into(),
@@ -780,10 +1032,14 @@ target = "oc_test"
                         status: StepStatus::Completed,
                         result: Some("ok".into()),
                         attempts: 1,
+                        checkpoint: None,
                     },
                 ],
                 context: String::new(),
                 last_error: None,
+                owner: None,
+                budget_usd: None,
+                history: Vec::new(),
             }],
         };
         assert_eq!(GoalEngine::find_stalled_goals(&state), vec![0]);
@@ -806,6 +1062,7 @@ target = "oc_test"
                         status: StepStatus::Completed,
                         result: Some("ok".into()),
                         attempts: 1,
+                        checkpoint: None,
                     },
                     Step {
                         id: "s2".into(),
@@ -813,10 +1070,14 @@ target = "oc_test"
                         status: StepStatus::Blocked,
                         result: None,
                         attempts: 0,
+                        checkpoint: None,
                     },
                 ],
                 context: String::new(),
                 last_error: None,
+                owner: None,
+                budget_usd: None,
+                history: Vec::new(),
             }],
         };
         assert_eq!(GoalEngine::find_stalled_goals(&state), vec![0]);
@@ -839,9 +1100,13 @@ target = "oc_test"
                 status: StepStatus::Completed,
                 result: Some("ok".into()),
                 attempts: 1,
+                checkpoint: None,
             }],
             context: String::new(),
             last_error: None,
+            owner: None,
+            budget_usd: None,
+            history: Vec::new(),
         };
         let prompt = GoalEngine::build_reflection_prompt(&goal);
         assert!(!prompt.contains("Accumulated context"));
@@ -862,9 +1127,13 @@ target = "oc_test"
                 status: StepStatus::Completed,
                 result: Some("ok".into()),
                 attempts: 1,
+                checkpoint: None,
             }],
             context: "some ctx".into(),
             last_error: None,
+            owner: None,
+            budget_usd: None,
+            history: Vec::new(),
         };
         let prompt = GoalEngine::build_reflection_prompt(&goal);
         assert!(!prompt.contains("Last error"));
@@ -886,6 +1155,7 @@ target = "oc_test"
                     status: StepStatus::Completed,
                     result: Some("ok".into()),
                     attempts: 1,
+                    checkpoint: None,
                 },
                 Step {
                     id: "s2".into(),
@@ -893,10 +1163,14 @@ target = "oc_test"
                     status: StepStatus::Completed,
                     result: Some("ok".into()),
                     attempts: 1,
+                    checkpoint: None,
                 },
             ],
             context: String::new(),
             last_error: None,
+            owner: None,
+            budget_usd: None,
+            history: Vec::new(),
         };
         let prompt = GoalEngine::build_reflection_prompt(&goal);
         assert!(prompt.contains("[done] First"));
@@ -1 +1,56 @@
+//! Long-running goals.
+//!
+//! Goals and their steps live in `{workspace}/state/goals.json`. The
+//! [`runner`] advances them from the daemon under `[goal_loop]`; they can be
+//! managed with `zeroclaw goals`, the gateway (`/api/goals`) or the agent's
+//! `goals` tool.
+
+pub mod cli;
 pub mod engine;
+pub mod runner;
+
+use anyhow::Result;
+use engine::{Goal, GoalState, GoalStatus, Step, StepCheckpoint, StepStatus};
+
+/// Change a goal's status. Resuming a goal also gives failed, blocked and
+/// exhausted steps a fresh set of attempts.
+pub fn set_status(state: &mut GoalState, id: &str, status: GoalStatus) -> Result<Goal> {
+    let goal = state.goal_mut(id)?;
+    if status == GoalStatus::InProgress {
+        for step in &mut goal.steps {
+            if step.status == StepStatus::Completed {
+                continue;
+            }
+            if matches!(step.status, StepStatus::Blocked | StepStatus::Failed) {
+                step.status = StepStatus::Pending;
+                if step.checkpoint.as_ref().is_some_and(|c| !c.approved) {
+                    step.checkpoint = Some(StepCheckpoint::default());
+                }
+            }
+            step.attempts = 0;
+        }
+        goal.last_error = None;
+    }
+    goal.status = status;
+    goal.updated_at = chrono::Utc::now().to_rfc3339();
+    Ok(goal.clone())
+}
+
+/// Close the inbox task of a checkpoint that was approved directly.
+pub fn settle_checkpoint_task(step: &Step) {
+    let (Some(task_id), Some(inbox)) = (
+        step.checkpoint.as_ref().and_then(|c| c.task_id.as_deref()),
+        crate::inbox::global(),
+    ) else {
+        return;
+    };
+    if let Err(error) = inbox.resolve(
+        task_id,
+        crate::inbox::HumanDecision::Approve,
+        None,
+        "goals",
+        "goals",
+    ) {
+        tracing::debug!("Failed to settle checkpoint task {task_id}: {error}");
+    }
+}
@@ -0,0 +1,892 @@
+//! Daemon component that advances goals on its own.
+//!
+//! Every `[goal_loop] interval_minutes`, inside `active_hours`, the runner
+//! picks the most important actionable step (or a stalled goal to reflect
+//! on), runs it through the agent with spend attributed to the workflow
+//! `goal:<id>`, and records the attempt in `state/goals.json`. Goals over
+//! budget are blocked, checkpointed steps wait for a human, and owners hear
+//! about completions, stalls and checkpoints on their channel.
+
+use super::engine::{
+    AttemptKind, Goal, GoalAttempt, GoalEngine, GoalState, GoalStatus, StepStatus,
+};
+use crate::config::schema::{CostDimension, GoalLoopConfig};
+use crate::config::Config;
+use crate::cost::CostTracker;
+use crate::inbox::{HumanInbox, HumanTaskKind, HumanTaskStatus, NewHumanTask};
+use crate::observability::runtime_trace;
+use crate::security::SecurityPolicy;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+const COMPONENT: &str = "goals";
+
+/// Output kept per attempt in the goal history and step results.
+const SUMMARY_CHARS: usize = 500;
+
+/// Something the owner of a goal should hear about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoalNotice {
+    pub goal_id: String,
+    pub event: GoalEvent,
+    /// `channel:recipient` from the goal, if it names one.
+    pub owner: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalEvent {
+    StepCompleted,
+    Completed,
+    Blocked,
+    Stalled,
+    BudgetExhausted,
+    Checkpoint,
+}
+
+impl GoalEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::StepCompleted => "step_completed",
+            Self::Completed => "completed",
+            Self::Blocked => "blocked",
+            Self::Stalled => "stalled",
+            Self::BudgetExhausted => "budget_exhausted",
+            Self::Checkpoint => "checkpoint",
+        }
+    }
+}
+
+/// Work picked for the next slot of a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    Step {
+        goal_id: String,
+        step_id: String,
+        prompt: String,
+    },
+    Reflect {
+        goal_id: String,
+        prompt: String,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Plan {
+    action: Option<Action>,
+    notices: Vec<GoalNotice>,
+    daily_budget_reached: bool,
+}
+
+/// Result of running one step or reflection through the agent.
+struct Outcome {
+    started_at: DateTime<Utc>,
+    success: bool,
+    output: String,
+    cost_usd: f64,
+}
+
+/// Daemon entry point.
+pub async fn run(config: Config) -> Result<()> {
+    let engine = GoalEngine::new(&config.workspace_dir);
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let ledger = goal_ledger(&config);
+
+    // Steps left running by a previous process go back in the queue.
+    engine
+        .update(|state| {
+            for step in state.goals.iter_mut().flat_map(|g| g.steps.iter_mut()) {
+                if step.status == StepStatus::InProgress {
+                    step.status = StepStatus::Pending;
+                }
+            }
+            Ok(())
+        })
+        .await?;
+
+    let minutes = u64::from(config.goal_loop.interval_minutes.max(1));
+    let mut interval = tokio::time::interval(Duration::from_secs(minutes * 60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut budget_notified_on: Option<NaiveDate> = None;
+
+    crate::health::mark_component_ok(COMPONENT);
+    loop {
+        interval.tick().await;
+        match run_cycle(
+            &config,
+            &engine,
+            &security,
+            ledger.as_deref(),
+            &mut budget_notified_on,
+        )
+        .await
+        {
+            Ok(()) => crate::health::mark_component_ok(COMPONENT),
+            Err(error) => {
+                crate::health::mark_component_error(COMPONENT, error.to_string());
+                tracing::warn!("Goal loop cycle failed: {error}");
+            }
+        }
+    }
+}
+
+async fn run_cycle(
+    config: &Config,
+    engine: &GoalEngine,
+    security: &SecurityPolicy,
+    ledger: Option<&CostTracker>,
+    budget_notified_on: &mut Option<NaiveDate>,
+) -> Result<()> {
+    let cfg = &config.goal_loop;
+    if !within_active_hours(cfg, Utc::now())? {
+        tracing::debug!("Outside goal_loop.active_hours; skipping cycle");
+        return Ok(());
+    }
+
+    let inbox = crate::inbox::global();
+    for _ in 0..cfg.max_steps_per_cycle.max(1) {
+        let now = Utc::now();
+        let plan = engine
+            .update(|state| Ok(plan_next(state, cfg, inbox.as_deref(), now)))
+            .await?;
+        notify(config, plan.notices).await;
+
+        if plan.daily_budget_reached {
+            if *budget_notified_on != Some(now.date_naive()) {
+                *budget_notified_on = Some(now.date_naive());
+                notify(
+                    config,
+                    vec![GoalNotice {
+                        goal_id: String::new(),
+                        event: GoalEvent::BudgetExhausted,
+                        owner: None,
+                        message: format!(
+                            "💸 Goal loop paused until tomorrow: today's budget of ${:.2} is spent.",
+                            cfg.daily_budget_usd.unwrap_or_default()
+                        ),
+                    }],
+                )
+                .await;
+            }
+            return Ok(());
+        }
+        let Some(action) = plan.action else {
+            return Ok(());
+        };
+
+        if !security.can_act() || security.is_rate_limited() || !security.record_action() {
+            tracing::warn!("Goal loop held back by security policy (read-only or rate limited)");
+            return Ok(());
+        }
+
+        let notices = match action {
+            Action::Step {
+                goal_id,
+                step_id,
+                prompt,
+            } => {
+                let outcome = execute(config, ledger, &goal_id, prompt).await;
+                trace_attempt("goal_step_attempt", &goal_id, Some(&step_id), &outcome);
+                engine
+                    .update(|state| Ok(record_step(state, cfg, &goal_id, &step_id, &outcome)))
+                    .await?
+            }
+            Action::Reflect { goal_id, prompt } => {
+                let outcome = execute(config, ledger, &goal_id, prompt).await;
+                trace_attempt("goal_reflection", &goal_id, None, &outcome);
+                engine
+                    .update(|state| Ok(record_reflection(state, &goal_id, &outcome)))
+                    .await?
+            }
+        };
+        notify(config, notices).await;
+    }
+    Ok(())
+}
+
+/// Whether `now` falls inside `active_hours` (always, when unset).
+fn within_active_hours(cfg: &GoalLoopConfig, now: DateTime<Utc>) -> Result<bool> {
+    let Some((start, end)) = cfg.active_window()? else {
+        return Ok(true);
+    };
+    let local: NaiveTime = match cfg.timezone.as_deref() {
+        Some(tz) => now
+            .with_timezone(&tz.parse::<chrono_tz::Tz>().map_err(anyhow::Error::msg)?)
+            .time(),
+        None => now.with_timezone(&chrono::Local).time(),
+    };
+    Ok(if start <= end {
+        start <= local && local < end
+    } else {
+        local >= start || local < end
+    })
+}
+
+/// Settle budgets, completions and checkpoints, then pick the next action:
+/// reflection on a stalled goal first, otherwise the most important step.
+fn plan_next(
+    state: &mut GoalState,
+    cfg: &GoalLoopConfig,
+    inbox: Option<&HumanInbox>,
+    now: DateTime<Utc>,
+) -> Plan {
+    let mut plan = Plan::default();
+    let stamp = now.to_rfc3339();
+    let mut waiting = HashSet::new();
+
+    for goal in state
+        .goals
+        .iter_mut()
+        .filter(|g| g.status == GoalStatus::InProgress)
+    {
+        if let Some(cap) = goal.budget_usd.or(cfg.goal_budget_usd) {
+            let spent = goal.spent_usd();
+            if spent >= cap {
+                let reason = format!("budget of ${cap:.2} exhausted (${spent:.2} spent)");
+                block(goal, &reason, &stamp);
+                plan.notices.push(notice(
+                    goal,
+                    GoalEvent::BudgetExhausted,
+                    format!(
+                        "💸 Goal `{}` stopped: {reason}\n{}",
+                        goal.id, goal.description
+                    ),
+                ));
+                continue;
+            }
+        }
+
+        if !goal.steps.is_empty() && goal.steps.iter().all(|s| s.status == StepStatus::Completed) {
+            goal.status = GoalStatus::Completed;
+            goal.updated_at.clone_from(&stamp);
+            plan.notices.push(completed_notice(goal));
+            continue;
+        }
+
+        if let Some(event) = settle_checkpoint(goal, inbox, &stamp) {
+            plan.notices.push(event);
+        }
+        if goal.status == GoalStatus::InProgress
+            && goal
+                .next_step_index()
+                .is_some_and(|i| goal.steps[i].awaiting_approval())
+        {
+            waiting.insert(goal.id.clone());
+        }
+    }
+
+    if let Some(daily) = cfg.daily_budget_usd {
+        if spent_on(state, now.date_naive()) >= daily {
+            plan.daily_budget_reached = true;
+            return plan;
+        }
+    }
+
+    if let Some(&gi) = GoalEngine::find_stalled_goals(state)
+        .iter()
+        .max_by_key(|&&gi| state.goals[gi].priority)
+    {
+        let goal = &state.goals[gi];
+        plan.action = Some(Action::Reflect {
+            goal_id: goal.id.clone(),
+            prompt: GoalEngine::build_reflection_prompt(goal),
+        });
+        return plan;
+    }
+
+    if let Some((gi, si)) =
+        GoalEngine::select_next_actionable_where(state, |g| !waiting.contains(&g.id))
+    {
+        let goal = &mut state.goals[gi];
+        let prompt = GoalEngine::build_step_prompt(goal, &goal.steps[si]);
+        let step = &mut goal.steps[si];
+        step.status = StepStatus::InProgress;
+        step.attempts += 1;
+        plan.action = Some(Action::Step {
+            goal_id: goal.id.clone(),
+            step_id: step.id.clone(),
+            prompt,
+        });
+        goal.updated_at = stamp;
+    }
+    plan
+}
+
+/// Ask for, or pick up, the approval of the goal's next checkpointed step.
+fn settle_checkpoint(
+    goal: &mut Goal,
+    inbox: Option<&HumanInbox>,
+    stamp: &str,
+) -> Option<GoalNotice> {
+    let index = goal
+        .next_step_index()
+        .filter(|&i| goal.steps[i].awaiting_approval())?;
+    let goal_id = goal.id.clone();
+    let title = format!("Approve goal step: {}", goal.steps[index].description);
+    let body = goal.description.clone();
+    let owner = goal.owner.clone();
+    let step = &mut goal.steps[index];
+    let step_id = step.id.clone();
+    let checkpoint = step.checkpoint.get_or_insert_with(Default::default);
+
+    if let (Some(task_id), Some(inbox)) = (checkpoint.task_id.as_deref(), inbox) {
+        let task = inbox.get(task_id).ok().flatten()?;
+        match task.status {
+            HumanTaskStatus::Pending => return None,
+            HumanTaskStatus::Approved => {
+                checkpoint.approved = true;
+                return None;
+            }
+            status => {
+                step.status = StepStatus::Blocked;
+                let reason = format!("checkpoint for step {step_id} was {status}");
+                block(goal, &reason, stamp);
+                return Some(notice(
+                    goal,
+                    GoalEvent::Blocked,
+                    format!(
+                        "⛔ Goal `{goal_id}` blocked: {reason}\n{}",
+                        goal.description
+                    ),
+                ));
+            }
+        }
+    }
+    if checkpoint.requested_at.is_some() {
+        return None;
+    }
+    checkpoint.requested_at = Some(stamp.to_string());
+
+    // With the inbox on, the task itself reaches the owner.
+    if let Some(inbox) = inbox {
+        let task = NewHumanTask::new(HumanTaskKind::Review, title)
+            .with_body(body)
+            .with_assignee(owner)
+            .with_external_ref(format!("{goal_id}:{step_id}"));
+        match inbox.create(task) {
+            Ok(task) => {
+                checkpoint.task_id = Some(task.id);
+                return None;
+            }
+            Err(error) => tracing::warn!("Failed to raise checkpoint for {goal_id}: {error}"),
+        }
+    }
+    Some(notice(
+        goal,
+        GoalEvent::Checkpoint,
+        format!(
+            "✋ Goal `{goal_id}` is waiting for approval of step {step_id}: {}\n\
+             Approve with `zeroclaw goals approve {goal_id} {step_id}`.",
+            goal.steps[index].description
+        ),
+    ))
+}
+
+/// Apply the outcome of a step run to the (re-loaded) state.
+fn record_step(
+    state: &mut GoalState,
+    cfg: &GoalLoopConfig,
+    goal_id: &str,
+    step_id: &str,
+    outcome: &Outcome,
+) -> Vec<GoalNotice> {
+    let mut notices = Vec::new();
+    let Some(goal) = GoalEngine::find_goal(state, goal_id) else {
+        return notices;
+    };
+    let summary = crate::util::truncate_with_ellipsis(outcome.output.trim(), SUMMARY_CHARS);
+    goal.record(attempt(AttemptKind::Step, Some(step_id), outcome, &summary));
+
+    let Some(step) = goal.steps.iter_mut().find(|s| s.id == step_id) else {
+        return notices;
+    };
+    if outcome.success {
+        step.status = StepStatus::Completed;
+        step.result = Some(summary.clone());
+        let description = step.description.clone();
+        if !goal.context.is_empty() {
+            goal.context.push('\n');
+        }
+        let _ = write!(goal.context, "- {description}: {summary}");
+        goal.last_error = None;
+        if cfg.notify_each_step {
+            notices.push(notice(
+                goal,
+                GoalEvent::StepCompleted,
+                format!("✅ Goal `{goal_id}` step done: {description}\n{summary}"),
+            ));
+        }
+        if goal.steps.iter().all(|s| s.status == StepStatus::Completed) {
+            goal.status = GoalStatus::Completed;
+            notices.push(completed_notice(goal));
+        }
+    } else {
+        if step.status == StepStatus::InProgress {
+            step.status = StepStatus::Pending;
+        }
+        goal.last_error = Some(summary);
+    }
+    notices
+}
+
+/// Record a reflection and tell the owner where it left the goal. A goal
+/// that is still stalled afterwards is blocked rather than reflected on again.
+fn record_reflection(state: &mut GoalState, goal_id: &str, outcome: &Outcome) -> Vec<GoalNotice> {
+    let Some(goal) = GoalEngine::find_goal(state, goal_id) else {
+        return Vec::new();
+    };
+    let summary = crate::util::truncate_with_ellipsis(outcome.output.trim(), SUMMARY_CHARS);
+    goal.record(attempt(AttemptKind::Reflection, None, outcome, &summary));
+
+    let event = match goal.status {
+        GoalStatus::Completed => Some(completed_notice(goal)),
+        GoalStatus::Blocked => Some(notice(
+            goal,
+            GoalEvent::Blocked,
+            format!(
+                "⛔ Goal `{goal_id}` needs you: {}\n{}",
+                goal.description,
+                goal.last_error.as_deref().unwrap_or(&summary)
+            ),
+        )),
+        GoalStatus::InProgress if goal.steps.iter().all(|s| s.status == StepStatus::Completed) => {
+            goal.status = GoalStatus::Completed;
+            Some(completed_notice(goal))
+        }
+        GoalStatus::InProgress if goal.next_step_index().is_none() => {
+            let stamp = goal.updated_at.clone();
+            block(goal, "stalled: reflection found no way forward", &stamp);
+            Some(notice(
+                goal,
+                GoalEvent::Stalled,
+                format!(
+                    "🧱 Goal `{goal_id}` is stalled: {}\n{summary}",
+                    goal.description
+                ),
+            ))
+        }
+        _ => None,
+    };
+    event.into_iter().collect()
+}
+
+/// Ledger the agent's cost observer writes to for this workspace, so each
+/// attempt's spend can be read back. Goal budgets are not enforced without it.
+fn goal_ledger(config: &Config) -> Option<Arc<CostTracker>> {
+    let budgeted =
+        config.goal_loop.goal_budget_usd.is_some() || config.goal_loop.daily_budget_usd.is_some();
+    if !config.cost.enabled {
+        if budgeted {
+            tracing::warn!("[goal_loop] budgets need [cost] enabled = true; spend is not tracked");
+        }
+        return None;
+    }
+    match crate::cost::shared_tracker(&config.cost, &config.workspace_dir) {
+        Ok(ledger) => Some(ledger),
+        Err(error) => {
+            tracing::warn!("Goal spend is not tracked: failed to open the cost ledger: {error}");
+            None
+        }
+    }
+}
+
+/// Run `prompt` through the agent, charging spend to `goal:<goal_id>`.
+///
+/// The agent's cost observer records usage on the workspace ledger; the
+/// attempt's cost is what `ledger` holds for the workflow afterwards.
+async fn execute(
+    config: &Config,
+    ledger: Option<&CostTracker>,
+    goal_id: &str,
+    prompt: String,
+) -> Outcome {
+    let started_at = Utc::now();
+    let workflow = goal_workflow(goal_id);
+    let timeout_secs = config.goal_loop.step_timeout_secs.max(1);
+    let run = crate::cost::attribution::scope(
+        crate::cost::CostAttribution::workflow(workflow.clone()),
+        Box::pin(crate::agent::run(
+            config.clone(),
+            Some(prompt),
+            None,
+            None,
+            config.default_temperature,
+            vec![],
+            false,
+            None,
+        )),
+    );
+    let (success, output) = match tokio::time::timeout(Duration::from_secs(timeout_secs), run).await
+    {
+        Ok(Ok(output)) => (GoalEngine::interpret_result(&output), output),
+        Ok(Err(error)) => (false, format!("error: {error}")),
+        Err(_) => (false, format!("error: timed out after {timeout_secs}s")),
+    };
+    Outcome {
+        started_at,
+        success,
+        output,
+        cost_usd: ledger.map_or(0.0, |ledger| spent_since(ledger, &workflow, started_at)),
+    }
+}
+
+/// Cost workflow for a goal's runs.
+pub fn goal_workflow(goal_id: &str) -> String {
+    format!("goal:{goal_id}")
+}
+
+fn spent_since(ledger: &CostTracker, workflow: &str, since: DateTime<Utc>) -> f64 {
+    ledger
+        .report(CostDimension::Workflow, Some(since))
+        .ok()
+        .and_then(|report| report.rows.into_iter().find(|row| row.key == workflow))
+        .map_or(0.0, |row| row.cost_usd)
+}
+
+/// Goal-loop spend recorded on `day` (UTC) across all goals.
+fn spent_on(state: &GoalState, day: NaiveDate) -> f64 {
+    state
+        .goals
+        .iter()
+        .flat_map(|g| &g.history)
+        .filter(|a| {
+            DateTime::parse_from_rfc3339(&a.at)
+                .is_ok_and(|at| at.with_timezone(&Utc).date_naive() == day)
+        })
+        .map(|a| a.cost_usd)
+        .sum()
+}
+
+fn attempt(
+    kind: AttemptKind,
+    step_id: Option<&str>,
+    outcome: &Outcome,
+    summary: &str,
+) -> GoalAttempt {
+    GoalAttempt {
+        at: outcome.started_at.to_rfc3339(),
+        kind,
+        step_id: step_id.map(str::to_string),
+        success: outcome.success,
+        summary: summary.to_string(),
+        cost_usd: outcome.cost_usd,
+    }
+}
+
+fn block(goal: &mut Goal, reason: &str, stamp: &str) {
+    goal.status = GoalStatus::Blocked;
+    goal.last_error = Some(reason.to_string());
+    goal.updated_at = stamp.to_string();
+}
+
+fn notice(goal: &Goal, event: GoalEvent, message: String) -> GoalNotice {
+    GoalNotice {
+        goal_id: goal.id.clone(),
+        event,
+        owner: goal.owner.clone(),
+        message,
+    }
+}
+
+fn completed_notice(goal: &Goal) -> GoalNotice {
+    notice(
+        goal,
+        GoalEvent::Completed,
+        format!("🎯 Goal `{}` completed: {}", goal.id, goal.description),
+    )
+}
+
+fn trace_attempt(event: &str, goal_id: &str, step_id: Option<&str>, outcome: &Outcome) {
+    runtime_trace::record_event(
+        event,
+        None,
+        None,
+        None,
+        None,
+        Some(outcome.success),
+        Some(crate::util::truncate_with_ellipsis(&outcome.output, 200).as_str()),
+        serde_json::json!({
+            "goal_id": goal_id,
+            "step_id": step_id,
+            "cost_usd": outcome.cost_usd,
+        }),
+    );
+}
+
+/// Deliver notices to the goal owner, or to `[goal_loop] channel`/`target`.
+async fn notify(config: &Config, notices: Vec<GoalNotice>) {
+    let cfg = &config.goal_loop;
+    for notice in notices {
+        runtime_trace::record_event(
+            "goal_notice",
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            Some(notice.message.as_str()),
+            serde_json::json!({
+                "goal_id": notice.goal_id,
+                "event": notice.event.as_str(),
+                "owner": notice.owner,
+            }),
+        );
+        let target = notice
+            .owner
+            .as_deref()
+            .and_then(|owner| owner.split_once(':'))
+            .or_else(|| cfg.channel.as_deref().zip(cfg.target.as_deref()));
+        let Some((channel, target)) = target else {
+            continue;
+        };
+        if let Err(error) =
+            crate::cron::scheduler::deliver_announcement(config, channel, target, &notice.message)
+                .await
+        {
+            tracing::warn!(
+                "Failed to notify {channel}:{target} about goal {}: {error}",
+                notice.goal_id
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goals::engine::{NewGoal, NewStep};
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn state_with(steps: &[(&str, bool)]) -> (GoalState, String) {
+        let mut state = GoalState::default();
+        let goal = state
+            .add_goal(
+                NewGoal {
+                    description: "Ship the newsletter".into(),
+                    steps: steps
+                        .iter()
+                        .map(|(description, checkpoint)| NewStep {
+                            description: (*description).into(),
+                            checkpoint: *checkpoint,
+                        })
+                        .collect(),
+                    owner: Some("telegram:42".into()),
+                    ..NewGoal::default()
+                },
+                "2026-10-01T09:00:00Z",
+            )
+            .unwrap();
+        (state, goal.id)
+    }
+
+    fn outcome(success: bool, output: &str, cost_usd: f64) -> Outcome {
+        Outcome {
+            started_at: Utc::now(),
+            success,
+            output: output.into(),
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn active_hours_wrap_past_midnight() {
+        let cfg = GoalLoopConfig {
+            active_hours: Some("22:00-06:00".into()),
+            timezone: Some("UTC".into()),
+            ..GoalLoopConfig::default()
+        };
+        let at = |h: u32| {
+            DateTime::parse_from_rfc3339(&format!("2026-10-01T{h:02}:30:00Z"))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert!(within_active_hours(&cfg, at(23)).unwrap());
+        assert!(within_active_hours(&cfg, at(5)).unwrap());
+        assert!(!within_active_hours(&cfg, at(12)).unwrap());
+        assert!(within_active_hours(&GoalLoopConfig::default(), at(12)).unwrap());
+    }
+
+    #[test]
+    fn plan_runs_steps_and_records_attempts() {
+        let (mut state, id) = state_with(&[("Draft", false), ("Send", false)]);
+        let cfg = GoalLoopConfig {
+            notify_each_step: true,
+            ..GoalLoopConfig::default()
+        };
+
+        let plan = plan_next(&mut state, &cfg, None, Utc::now());
+        let Some(Action::Step { step_id, .. }) = plan.action else {
+            panic!("expected a step");
+        };
+        assert_eq!(step_id, "s1");
+        assert_eq!(state.goals[0].steps[0].status, StepStatus::InProgress);
+        assert_eq!(state.goals[0].steps[0].attempts, 1);
+
+        let notices = record_step(&mut state, &cfg, &id, "s1", &outcome(true, "drafted", 0.1));
+        assert_eq!(notices[0].event, GoalEvent::StepCompleted);
+        assert_eq!(state.goals[0].history.len(), 1);
+        assert!(state.goals[0].context.contains("Draft: drafted"));
+
+        plan_next(&mut state, &cfg, None, Utc::now());
+        let notices = record_step(&mut state, &cfg, &id, "s2", &outcome(true, "sent", 0.1));
+        assert_eq!(notices.last().unwrap().event, GoalEvent::Completed);
+        assert_eq!(
+            notices.last().unwrap().owner.as_deref(),
+            Some("telegram:42")
+        );
+        assert_eq!(state.goals[0].status, GoalStatus::Completed);
+        assert!((state.goals[0].spent_usd() - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn plan_blocks_goals_over_budget_and_stops_at_daily_cap() {
+        let (mut state, id) = state_with(&[("Draft", false), ("Send", false)]);
+        let cfg = GoalLoopConfig {
+            goal_budget_usd: Some(1.0),
+            ..GoalLoopConfig::default()
+        };
+        plan_next(&mut state, &cfg, None, Utc::now());
+        record_step(
+            &mut state,
+            &cfg,
+            &id,
+            "s1",
+            &outcome(false, "error: flaky", 1.5),
+        );
+
+        let plan = plan_next(&mut state, &cfg, None, Utc::now());
+        assert!(plan.action.is_none());
+        assert_eq!(plan.notices[0].event, GoalEvent::BudgetExhausted);
+        assert_eq!(state.goals[0].status, GoalStatus::Blocked);
+
+        let (mut state, id) = state_with(&[("Draft", false), ("Send", false)]);
+        let cfg = GoalLoopConfig {
+            daily_budget_usd: Some(1.0),
+            ..GoalLoopConfig::default()
+        };
+        plan_next(&mut state, &cfg, None, Utc::now());
+        record_step(&mut state, &cfg, &id, "s1", &outcome(true, "drafted", 1.0));
+        let plan = plan_next(&mut state, &cfg, None, Utc::now());
+        assert!(plan.daily_budget_reached);
+        assert!(plan.action.is_none());
+    }
+
+    #[test]
+    fn checkpointed_steps_wait_for_approval() {
+        let (mut state, id) = state_with(&[("Publish", true)]);
+        let cfg = GoalLoopConfig::default();
+
+        let plan = plan_next(&mut state, &cfg, None, Utc::now());
+        assert!(plan.action.is_none());
+        assert_eq!(plan.notices[0].event, GoalEvent::Checkpoint);
+        // Asked once only.
+        assert!(plan_next(&mut state, &cfg, None, Utc::now())
+            .notices
+            .is_empty());
+
+        state
+            .goal_mut(&id)
+            .unwrap()
+            .approve_checkpoint(None)
+            .unwrap();
+        let plan = plan_next(&mut state, &cfg, None, Utc::now());
+        assert!(matches!(plan.action, Some(Action::Step { .. })));
+    }
+
+    #[test]
+    fn stalled_goal_is_blocked_when_reflection_changes_nothing() {
+        let (mut state, id) = state_with(&[("Draft", false)]);
+        let cfg = GoalLoopConfig::default();
+        for _ in 0..GoalEngine::max_step_attempts() {
+            plan_next(&mut state, &cfg, None, Utc::now());
+            record_step(
+                &mut state,
+                &cfg,
+                &id,
+                "s1",
+                &outcome(false, "error: no API", 0.0),
+            );
+        }
+
+        let plan = plan_next(&mut state, &cfg, None, Utc::now());
+        assert!(matches!(plan.action, Some(Action::Reflect { .. })));
+
+        let notices = record_reflection(&mut state, &id, &outcome(true, "Need an API key.", 0.0));
+        assert_eq!(notices[0].event, GoalEvent::Stalled);
+        assert_eq!(state.goals[0].status, GoalStatus::Blocked);
+        assert_eq!(
+            state.goals[0].history.last().unwrap().kind,
+            AttemptKind::Reflection
+        );
+    }
+
+    #[tokio::test]
+    async fn goal_is_blocked_once_recorded_spend_reaches_its_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "choices": [{ "message": { "role": "assistant", "content": "Drafted." } }],
+                "usage": { "prompt_tokens": 1000, "completion_tokens": 1000 }
+            })))
+            .mount(&server)
+            .await;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            default_provider: Some(format!("custom:{}", server.uri())),
+            default_model: Some("mock".into()),
+            api_key: Some("test-key".into()),
+            ..Config::default()
+        };
+        config.cost.enabled = true;
+        config.goal_loop.goal_budget_usd = Some(0.01);
+        config.goal_loop.max_steps_per_cycle = 2;
+        tokio::fs::create_dir_all(&config.workspace_dir)
+            .await
+            .unwrap();
+
+        let engine = GoalEngine::new(&config.workspace_dir);
+        let id = engine
+            .update(|state| {
+                state.add_goal(
+                    NewGoal {
+                        description: "Ship the newsletter".into(),
+                        steps: ["Draft", "Send"]
+                            .into_iter()
+                            .map(|description| NewStep {
+                                description: description.into(),
+                                checkpoint: false,
+                            })
+                            .collect(),
+                        ..NewGoal::default()
+                    },
+                    &Utc::now().to_rfc3339(),
+                )
+            })
+            .await
+            .unwrap()
+            .id;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let ledger = goal_ledger(&config).expect("cost tracking is enabled");
+
+        run_cycle(&config, &engine, &security, Some(&ledger), &mut None)
+            .await
+            .unwrap();
+
+        let state = engine.load_state().await.unwrap();
+        let goal = state.goals.iter().find(|g| g.id == id).unwrap();
+        assert_eq!(goal.history.len(), 1, "the second step must not run");
+        assert!(goal.history[0].cost_usd > 0.01);
+        assert_eq!(goal.status, GoalStatus::Blocked);
+        assert_eq!(goal.steps[1].attempts, 0);
+    }
+}
@@ -0,0 +1,341 @@
+//! Analog tools — `adc_read`, `pwm_write` and `servo_write`.
+//!
+//! Same shape as the [bus tools](super::bus): resolve a device that advertises
+//! ADC or PWM, send one `ZcCommand`, format the reply.
+
+use super::bus::{device_call, failure, int_param};
+use super::device::{Capability, DeviceRegistry};
+use super::protocol::ZcCommand;
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Most ADC samples averaged by a single `adc_read`.
+const MAX_ADC_SAMPLES: u64 = 64;
+
+fn device_property() -> serde_json::Value {
+    json!({
+        "type": "string",
+        "description": "Device alias e.g. esp0, arduino0"
+    })
+}
+
+// ── AdcReadTool ───────────────────────────────────────────────────────────────
+
+/// Tool: sample an ADC channel.
+pub struct AdcReadTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl AdcReadTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for AdcReadTool {
+    fn name(&self) -> &str {
+        "adc_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read an analog input (ADC channel) on a connected device, optionally averaging several samples"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": device_property(),
+                "channel": {
+                    "type": "integer",
+                    "description": "ADC channel (Arduino: A0 = 0; ESP32: see firmware pin map)"
+                },
+                "samples": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_ADC_SAMPLES,
+                    "description": "Samples to average (default 1)"
+                }
+            },
+            "required": ["channel"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let channel = match int_param(&args, "channel", 31) {
+            Ok(c) => c,
+            Err(result) => return Ok(result),
+        };
+        let samples = if args.get("samples").is_some() {
+            match int_param(&args, "samples", MAX_ADC_SAMPLES) {
+                Ok(0) => return Ok(failure("samples must be at least 1")),
+                Ok(s) => s,
+                Err(result) => return Ok(result),
+            }
+        } else {
+            1
+        };
+
+        let cmd = ZcCommand::new(
+            "adc_read",
+            json!({ "channel": channel, "samples": samples }),
+        );
+        let (alias, resp) = match device_call(&self.registry, &args, Capability::Adc, &cmd).await {
+            Ok(ok) => ok,
+            Err(result) => return Ok(result),
+        };
+        let value = resp.data.get("value").and_then(|v| v.as_u64()).unwrap_or(0);
+        let output = match resp.data.get("millivolts").and_then(|v| v.as_u64()) {
+            Some(mv) => format!("ADC {channel} on {alias}: {value} ({mv} mV)"),
+            None => format!("ADC {channel} on {alias}: {value}"),
+        };
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+// ── PwmWriteTool ──────────────────────────────────────────────────────────────
+
+/// Tool: set the PWM duty cycle of a pin.
+pub struct PwmWriteTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl PwmWriteTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for PwmWriteTool {
+    fn name(&self) -> &str {
+        "pwm_write"
+    }
+
+    fn description(&self) -> &str {
+        "Set the PWM duty cycle (0-100 %) of a pin on a connected device, e.g. to dim an LED or drive a motor"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": device_property(),
+                "pin": {
+                    "type": "integer",
+                    "description": "PWM-capable pin number"
+                },
+                "duty": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 100,
+                    "description": "Duty cycle in percent"
+                },
+                "frequency": {
+                    "type": "integer",
+                    "description": "PWM frequency in Hz (firmware default when omitted)"
+                }
+            },
+            "required": ["pin", "duty"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pin = match int_param(&args, "pin", u64::from(u16::MAX)) {
+            Ok(p) => p,
+            Err(result) => return Ok(result),
+        };
+        let duty = match args.get("duty").and_then(|v| v.as_f64()) {
+            Some(d) if (0.0..=100.0).contains(&d) => d,
+            Some(_) => return Ok(failure("duty must be between 0 and 100")),
+            None => return Ok(failure("missing required parameter: duty")),
+        };
+        let mut params = json!({ "pin": pin, "duty": duty });
+        if let Some(frequency) = args.get("frequency").and_then(|v| v.as_u64()) {
+            if frequency == 0 {
+                return Ok(failure("frequency must be greater than 0"));
+            }
+            params["frequency"] = json!(frequency);
+        }
+
+        let cmd = ZcCommand::new("pwm_write", params);
+        Ok(
+            match device_call(&self.registry, &args, Capability::Pwm, &cmd).await {
+                Ok((alias, _)) => ToolResult {
+                    success: true,
+                    output: format!("PWM pin {pin} set to {duty}% on {alias}"),
+                    error: None,
+                },
+                Err(result) => result,
+            },
+        )
+    }
+}
+
+// ── ServoWriteTool ────────────────────────────────────────────────────────────
+
+/// Tool: move a hobby servo to an angle.
+pub struct ServoWriteTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl ServoWriteTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for ServoWriteTool {
+    fn name(&self) -> &str {
+        "servo_write"
+    }
+
+    fn description(&self) -> &str {
+        "Move a servo on a connected device to an angle between 0 and 180 degrees"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": device_property(),
+                "pin": {
+                    "type": "integer",
+                    "description": "Servo signal pin number"
+                },
+                "angle": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 180,
+                    "description": "Target angle in degrees"
+                }
+            },
+            "required": ["pin", "angle"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pin = match int_param(&args, "pin", u64::from(u16::MAX)) {
+            Ok(p) => p,
+            Err(result) => return Ok(result),
+        };
+        let angle = match int_param(&args, "angle", 180) {
+            Ok(a) => a,
+            Err(result) => return Ok(result),
+        };
+
+        let cmd = ZcCommand::new("servo_write", json!({ "pin": pin, "angle": angle }));
+        Ok(
+            match device_call(&self.registry, &args, Capability::Pwm, &cmd).await {
+                Ok((alias, _)) => ToolResult {
+                    success: true,
+                    output: format!("Servo on pin {pin} moved to {angle}° on {alias}"),
+                    error: None,
+                },
+                Err(result) => result,
+            },
+        )
+    }
+}
+
+// ── Factory ───────────────────────────────────────────────────────────────────
+
+/// Create the ADC, PWM and servo tools for a given device registry.
+pub fn analog_tools(registry: Arc<RwLock<DeviceRegistry>>) -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(AdcReadTool::new(registry.clone())),
+        Box::new(PwmWriteTool::new(registry.clone())),
+        Box::new(ServoWriteTool::new(registry)),
+    ]
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::bus::tests::registry_with;
+    use crate::hardware::device::DeviceCapabilities;
+    use crate::hardware::protocol::ZcResponse;
+
+    fn analog() -> DeviceCapabilities {
+        DeviceCapabilities {
+            adc: true,
+            pwm: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn adc_read_reports_value_and_millivolts() {
+        let (reg, mock) = registry_with(
+            analog(),
+            ZcResponse::success(json!({"channel": 0, "value": 2048, "millivolts": 1650})),
+        );
+        let result = AdcReadTool::new(reg)
+            .execute(json!({"channel": 0, "samples": 4}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "ADC 0 on esp0: 2048 (1650 mV)");
+        assert_eq!(mock.last_cmd.lock().clone().unwrap().params["samples"], 4);
+    }
+
+    #[tokio::test]
+    async fn pwm_write_validates_duty() {
+        let (reg, mock) = registry_with(analog(), ZcResponse::success(json!({})));
+        let tool = PwmWriteTool::new(reg);
+
+        let result = tool.execute(json!({"pin": 18, "duty": 120})).await.unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("duty must be between 0 and 100")
+        );
+
+        let result = tool
+            .execute(json!({"pin": 18, "duty": 37.5, "frequency": 1000}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "PWM pin 18 set to 37.5% on esp0");
+        let cmd = mock.last_cmd.lock().clone().unwrap();
+        assert_eq!(cmd.cmd, "pwm_write");
+        assert_eq!(cmd.params["frequency"], 1000);
+    }
+
+    #[tokio::test]
+    async fn servo_write_limits_angle() {
+        let (reg, _) = registry_with(analog(), ZcResponse::success(json!({})));
+        let tool = ServoWriteTool::new(reg);
+
+        let result = tool
+            .execute(json!({"pin": 19, "angle": 200}))
+            .await
+            .unwrap();
+        assert_eq!(result.error.as_deref(), Some("angle must be at most 180"));
+
+        let result = tool.execute(json!({"pin": 19, "angle": 90})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "Servo on pin 19 moved to 90° on esp0");
+    }
+
+    #[tokio::test]
+    async fn device_errors_are_passed_through() {
+        let (reg, _) = registry_with(analog(), ZcResponse::error("pin 5 has no PWM channel"));
+        let result = PwmWriteTool::new(reg)
+            .execute(json!({"pin": 5, "duty": 10}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("pin 5 has no PWM channel"));
+    }
+}
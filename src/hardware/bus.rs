@@ -0,0 +1,538 @@
+//! Bus tools — `i2c_scan`, `i2c_read`, `i2c_write` and `spi_transfer`.
+//!
+//! Like the [GPIO tools](super::gpio), each tool resolves a device that
+//! advertises the bus (see [`DeviceRegistry::refresh_capabilities`]), sends one
+//! `ZcCommand` over its transport and formats the reply. Byte payloads travel
+//! as JSON arrays of integers; see [`protocol`](super::protocol) for the wire
+//! shapes.
+//!
+//! [`DeviceRegistry::refresh_capabilities`]: super::device::DeviceRegistry::refresh_capabilities
+
+use super::device::{Capability, DeviceRegistry};
+use super::protocol::{ZcCommand, ZcResponse};
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Largest payload a single bus command may carry (firmware line buffers are small).
+const MAX_TRANSFER_BYTES: usize = 32;
+
+/// A failed `ToolResult` with `message` as the error.
+pub(super) fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// Resolve a device with `capability`, send `cmd` and return the alias and the
+/// successful response — or the failed `ToolResult` to hand back to the LLM.
+pub(super) async fn device_call(
+    registry: &RwLock<DeviceRegistry>,
+    args: &serde_json::Value,
+    capability: Capability,
+    cmd: &ZcCommand,
+) -> Result<(String, ZcResponse), ToolResult> {
+    // Resolve before any I/O so the registry lock is not held across the send.
+    let (alias, ctx) = registry
+        .read()
+        .await
+        .resolve_device(args, capability)
+        .map_err(failure)?;
+
+    match ctx.transport.send(cmd).await {
+        Ok(resp) if resp.ok => Ok((alias, resp)),
+        Ok(resp) => Err(failure(
+            resp.error
+                .unwrap_or_else(|| "device returned ok:false".to_string()),
+        )),
+        Err(e) => Err(failure(format!("transport error: {}", e))),
+    }
+}
+
+/// Read a required integer parameter no larger than `max`.
+pub(super) fn int_param(args: &serde_json::Value, key: &str, max: u64) -> Result<u64, ToolResult> {
+    match args.get(key).and_then(|v| v.as_u64()) {
+        Some(v) if v <= max => Ok(v),
+        Some(_) => Err(failure(format!("{key} must be at most {max}"))),
+        None => Err(failure(format!("missing required parameter: {key}"))),
+    }
+}
+
+/// Read a required byte array (`[0, 255, …]`) of 1..=`MAX_TRANSFER_BYTES` items.
+fn bytes_param(args: &serde_json::Value, key: &str) -> Result<Vec<u8>, ToolResult> {
+    let Some(items) = args.get(key).and_then(|v| v.as_array()) else {
+        return Err(failure(format!("missing required parameter: {key}")));
+    };
+    if items.is_empty() || items.len() > MAX_TRANSFER_BYTES {
+        return Err(failure(format!(
+            "{key} must hold 1 to {MAX_TRANSFER_BYTES} bytes"
+        )));
+    }
+    items
+        .iter()
+        .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| failure(format!("{key} must contain integers 0-255")))
+}
+
+/// 7-bit I2C address parameter.
+fn address_param(args: &serde_json::Value) -> Result<u64, ToolResult> {
+    int_param(args, "address", 0x7f)
+}
+
+/// Bytes from `data.bytes` rendered as `0x12 0xAB`.
+fn hex_bytes(data: &serde_json::Value) -> String {
+    data.get("bytes")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_u64())
+                .map(|b| format!("0x{b:02X}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default()
+}
+
+fn device_property() -> serde_json::Value {
+    json!({
+        "type": "string",
+        "description": "Device alias e.g. esp0, arduino0"
+    })
+}
+
+fn bytes_property(description: &str) -> serde_json::Value {
+    json!({
+        "type": "array",
+        "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+        "maxItems": MAX_TRANSFER_BYTES,
+        "description": description
+    })
+}
+
+// ── I2cScanTool ───────────────────────────────────────────────────────────────
+
+/// Tool: list the addresses that acknowledge on a device's I2C bus.
+pub struct I2cScanTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl I2cScanTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for I2cScanTool {
+    fn name(&self) -> &str {
+        "i2c_scan"
+    }
+
+    fn description(&self) -> &str {
+        "Scan the I2C bus of a connected device and list responding 7-bit addresses"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "device": device_property() }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let cmd = ZcCommand::simple("i2c_scan");
+        let (alias, resp) = match device_call(&self.registry, &args, Capability::I2c, &cmd).await {
+            Ok(ok) => ok,
+            Err(result) => return Ok(result),
+        };
+        let addresses: Vec<String> = resp
+            .data
+            .get("addresses")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_u64())
+                    .map(|a| format!("0x{a:02X}"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let output = if addresses.is_empty() {
+            format!("No I2C devices found on {alias}")
+        } else {
+            format!(
+                "Found {} I2C device(s) on {}: {}",
+                addresses.len(),
+                alias,
+                addresses.join(", ")
+            )
+        };
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+// ── I2cReadTool ───────────────────────────────────────────────────────────────
+
+/// Tool: read bytes from an I2C device, optionally starting at a register.
+pub struct I2cReadTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl I2cReadTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for I2cReadTool {
+    fn name(&self) -> &str {
+        "i2c_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read bytes from an I2C peripheral (optionally from a register) on a connected device"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": device_property(),
+                "address": {
+                    "type": "integer",
+                    "description": "7-bit I2C address e.g. 0x68 = 104"
+                },
+                "register": {
+                    "type": "integer",
+                    "description": "Register to read from (written before the read); omit for a plain read"
+                },
+                "length": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_TRANSFER_BYTES,
+                    "description": "Number of bytes to read"
+                }
+            },
+            "required": ["address", "length"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let address = match address_param(&args) {
+            Ok(a) => a,
+            Err(result) => return Ok(result),
+        };
+        let length = match int_param(&args, "length", MAX_TRANSFER_BYTES as u64) {
+            Ok(0) => return Ok(failure("length must be at least 1")),
+            Ok(l) => l,
+            Err(result) => return Ok(result),
+        };
+        let mut params = json!({ "address": address, "length": length });
+        if args.get("register").is_some() {
+            match int_param(&args, "register", 0xff) {
+                Ok(register) => params["register"] = json!(register),
+                Err(result) => return Ok(result),
+            }
+        }
+
+        let cmd = ZcCommand::new("i2c_read", params);
+        Ok(
+            match device_call(&self.registry, &args, Capability::I2c, &cmd).await {
+                Ok((alias, resp)) => ToolResult {
+                    success: true,
+                    output: format!("I2C 0x{address:02X} on {alias}: {}", hex_bytes(&resp.data)),
+                    error: None,
+                },
+                Err(result) => result,
+            },
+        )
+    }
+}
+
+// ── I2cWriteTool ──────────────────────────────────────────────────────────────
+
+/// Tool: write bytes to an I2C device.
+pub struct I2cWriteTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl I2cWriteTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for I2cWriteTool {
+    fn name(&self) -> &str {
+        "i2c_write"
+    }
+
+    fn description(&self) -> &str {
+        "Write bytes to an I2C peripheral on a connected device (put the register first to write a register)"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": device_property(),
+                "address": {
+                    "type": "integer",
+                    "description": "7-bit I2C address e.g. 0x3C = 60"
+                },
+                "bytes": bytes_property("Bytes to write, e.g. [register, value]")
+            },
+            "required": ["address", "bytes"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let address = match address_param(&args) {
+            Ok(a) => a,
+            Err(result) => return Ok(result),
+        };
+        let bytes = match bytes_param(&args, "bytes") {
+            Ok(b) => b,
+            Err(result) => return Ok(result),
+        };
+
+        let cmd = ZcCommand::new("i2c_write", json!({ "address": address, "bytes": bytes }));
+        Ok(
+            match device_call(&self.registry, &args, Capability::I2c, &cmd).await {
+                Ok((alias, _)) => ToolResult {
+                    success: true,
+                    output: format!(
+                        "Wrote {} byte(s) to I2C 0x{address:02X} on {alias}",
+                        bytes.len()
+                    ),
+                    error: None,
+                },
+                Err(result) => result,
+            },
+        )
+    }
+}
+
+// ── SpiTransferTool ───────────────────────────────────────────────────────────
+
+/// Tool: full-duplex SPI transfer on a device's SPI bus.
+pub struct SpiTransferTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl SpiTransferTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for SpiTransferTool {
+    fn name(&self) -> &str {
+        "spi_transfer"
+    }
+
+    fn description(&self) -> &str {
+        "Send bytes over SPI on a connected device and return the bytes clocked in at the same time"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": device_property(),
+                "bytes": bytes_property("Bytes to send; pad with 0 to clock out a reply, e.g. [0x9F, 0, 0, 0]")
+            },
+            "required": ["bytes"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let bytes = match bytes_param(&args, "bytes") {
+            Ok(b) => b,
+            Err(result) => return Ok(result),
+        };
+
+        let cmd = ZcCommand::new("spi_transfer", json!({ "bytes": bytes }));
+        Ok(
+            match device_call(&self.registry, &args, Capability::Spi, &cmd).await {
+                Ok((alias, resp)) => ToolResult {
+                    success: true,
+                    output: format!("SPI on {alias} received: {}", hex_bytes(&resp.data)),
+                    error: None,
+                },
+                Err(result) => result,
+            },
+        )
+    }
+}
+
+// ── Factory ───────────────────────────────────────────────────────────────────
+
+/// Create the I2C and SPI tools for a given device registry.
+pub fn bus_tools(registry: Arc<RwLock<DeviceRegistry>>) -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(I2cScanTool::new(registry.clone())),
+        Box::new(I2cReadTool::new(registry.clone())),
+        Box::new(I2cWriteTool::new(registry.clone())),
+        Box::new(SpiTransferTool::new(registry)),
+    ]
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::hardware::device::DeviceCapabilities;
+    use crate::hardware::transport::{Transport, TransportError, TransportKind};
+
+    /// Mock transport that answers every command with one response and
+    /// records the last command.
+    pub(crate) struct MockTransport {
+        response: ZcResponse,
+        pub(crate) last_cmd: parking_lot::Mutex<Option<ZcCommand>>,
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        async fn send(&self, cmd: &ZcCommand) -> Result<ZcResponse, TransportError> {
+            *self.last_cmd.lock() = Some(cmd.clone());
+            Ok(self.response.clone())
+        }
+
+        fn kind(&self) -> TransportKind {
+            TransportKind::Serial
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    /// Registry with one `esp0` device advertising `caps`, answering `response`.
+    pub(crate) fn registry_with(
+        caps: DeviceCapabilities,
+        response: ZcResponse,
+    ) -> (Arc<RwLock<DeviceRegistry>>, Arc<MockTransport>) {
+        let transport = Arc::new(MockTransport {
+            response,
+            last_cmd: parking_lot::Mutex::new(None),
+        });
+        let mut reg = DeviceRegistry::new();
+        let alias = reg.register("esp32", Some(0x10c4), None, None, None);
+        reg.attach_transport(&alias, transport.clone() as Arc<dyn Transport>, caps)
+            .expect("alias was just registered");
+        (Arc::new(RwLock::new(reg)), transport)
+    }
+
+    fn i2c_spi() -> DeviceCapabilities {
+        DeviceCapabilities {
+            i2c: true,
+            spi: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn i2c_scan_lists_addresses() {
+        let (reg, _) = registry_with(
+            i2c_spi(),
+            ZcResponse::success(json!({"addresses": [60, 104]})),
+        );
+        let result = I2cScanTool::new(reg).execute(json!({})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "Found 2 I2C device(s) on esp0: 0x3C, 0x68");
+    }
+
+    #[tokio::test]
+    async fn i2c_read_sends_register_and_formats_bytes() {
+        let (reg, mock) = registry_with(i2c_spi(), ZcResponse::success(json!({"bytes": [1, 171]})));
+        let result = I2cReadTool::new(reg)
+            .execute(json!({"address": 104, "register": 117, "length": 2}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "I2C 0x68 on esp0: 0x01 0xAB");
+
+        let cmd = mock.last_cmd.lock().clone().unwrap();
+        assert_eq!(cmd.cmd, "i2c_read");
+        assert_eq!(cmd.params["register"], 117);
+        assert_eq!(cmd.params["length"], 2);
+    }
+
+    #[tokio::test]
+    async fn i2c_write_rejects_bad_bytes_and_addresses() {
+        let (reg, _) = registry_with(i2c_spi(), ZcResponse::success(json!({})));
+        let tool = I2cWriteTool::new(reg);
+
+        let result = tool
+            .execute(json!({"address": 60, "bytes": [0, 300]}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("bytes must contain integers 0-255")
+        );
+
+        let result = tool
+            .execute(json!({"address": 200, "bytes": [0]}))
+            .await
+            .unwrap();
+        assert_eq!(result.error.as_deref(), Some("address must be at most 127"));
+
+        let result = tool
+            .execute(json!({"address": 60, "bytes": [0, 175]}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "Wrote 2 byte(s) to I2C 0x3C on esp0");
+    }
+
+    #[tokio::test]
+    async fn spi_transfer_requires_spi_capability() {
+        let (reg, _) = registry_with(
+            DeviceCapabilities {
+                gpio: true,
+                ..Default::default()
+            },
+            ZcResponse::success(json!({"bytes": [255]})),
+        );
+        let result = SpiTransferTool::new(reg)
+            .execute(json!({"bytes": [159]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("no SPI-capable device"));
+    }
+
+    #[tokio::test]
+    async fn spi_transfer_returns_received_bytes() {
+        let (reg, _) = registry_with(
+            i2c_spi(),
+            ZcResponse::success(json!({"bytes": [255, 239, 64]})),
+        );
+        let result = SpiTransferTool::new(reg)
+            .execute(json!({"device": "esp0", "bytes": [159, 0, 0]}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "SPI on esp0 received: 0xFF 0xEF 0x40");
+    }
+}
@@ -84,6 +84,18 @@ impl DeviceKind {
             _ => None,
         }
     }
+
+    /// Derive the device kind from a board name, for boards without a USB VID
+    /// (e.g. network-attached devices).
+    pub fn from_board_name(board_name: &str) -> Self {
+        match alias_prefix(board_name).as_str() {
+            "pico" => Self::Pico,
+            "arduino" => Self::Arduino,
+            "esp" => Self::Esp32,
+            "nucleo" => Self::Nucleo,
+            _ => Self::Generic,
+        }
+    }
 }
 
 impl std::fmt::Display for DeviceKind {
@@ -113,6 +125,60 @@ pub struct DeviceCapabilities {
     pub pwm: bool,
 }
 
+impl DeviceCapabilities {
+    /// Whether the device advertises `capability`.
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Gpio => self.gpio,
+            Capability::I2c => self.i2c,
+            Capability::Spi => self.spi,
+            Capability::Adc => self.adc,
+            Capability::Pwm => self.pwm,
+        }
+    }
+
+    /// Merge the `data` of a firmware `capabilities` response into these flags.
+    ///
+    /// A key counts when it is `true`, a non-empty array (pin/channel list)
+    /// or an object. Flags already set are kept.
+    pub fn merge_response(&mut self, data: &serde_json::Value) {
+        let has = |key: &str| match data.get(key) {
+            Some(serde_json::Value::Bool(b)) => *b,
+            Some(serde_json::Value::Array(items)) => !items.is_empty(),
+            Some(serde_json::Value::Object(_)) => true,
+            _ => false,
+        };
+        self.gpio |= has("gpio");
+        self.i2c |= has("i2c");
+        self.spi |= has("spi");
+        self.uart |= has("uart");
+        self.adc |= has("adc");
+        self.pwm |= has("pwm");
+    }
+}
+
+/// A peripheral capability a tool needs from its device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Gpio,
+    I2c,
+    Spi,
+    Adc,
+    Pwm,
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gpio => write!(f, "GPIO"),
+            Self::I2c => write!(f, "I2C"),
+            Self::Spi => write!(f, "SPI"),
+            Self::Adc => write!(f, "ADC"),
+            Self::Pwm => write!(f, "PWM"),
+        }
+    }
+}
+
 /// A discovered and registered hardware device.
 #[derive(Debug, Clone)]
 pub struct Device {
@@ -128,7 +194,8 @@ pub struct Device {
     pub vid: Option<u16>,
     /// USB Product ID (if USB-connected).
     pub pid: Option<u16>,
-    /// Raw device path (e.g. `"/dev/ttyACM0"`) — internal use only.
+    /// Raw device path (e.g. `"/dev/ttyACM0"`, or `"tcp://…"` for network
+    /// devices) — internal use only.
     /// Tools MUST NOT use this directly; always go through Transport.
     pub device_path: Option<String>,
     /// Architecture description (e.g. `"ARM Cortex-M0+"`).
@@ -172,7 +239,7 @@ pub const NO_HW_DEVICES_SUMMARY: &str = "No hardware devices connected.";
 /// Registry of discovered devices with stable session aliases.
 ///
 /// - Scans at startup (via `hardware::discover`)
-/// - Assigns aliases: `pico0`, `pico1`, `arduino0`, `nucleo0`, `device0`, etc.
+/// - Assigns aliases: `pico0`, `pico1`, `arduino0`, `nucleo0`, `sim0`, `device0`, etc.
 /// - Provides alias-based lookup for tool dispatch
 /// - Generates prompt summaries for LLM context
 pub struct DeviceRegistry {
@@ -313,39 +380,52 @@ impl DeviceRegistry {
 
     /// Resolve a GPIO-capable device alias from tool arguments.
     ///
+    /// Same as [`resolve_device`](Self::resolve_device) with [`Capability::Gpio`].
+    pub fn resolve_gpio_device(
+        &self,
+        args: &serde_json::Value,
+    ) -> Result<(String, DeviceContext), String> {
+        self.resolve_device(args, Capability::Gpio)
+    }
+
+    /// Resolve a device alias with `capability` from tool arguments.
+    ///
     /// If `args["device"]` is provided, uses that alias directly.
-    /// Otherwise, auto-selects the single GPIO-capable device, returning an
-    /// error description if zero or multiple GPIO devices are available.
+    /// Otherwise, auto-selects the single capable device, returning an
+    /// error description if zero or multiple capable devices are available.
     ///
     /// On success returns `(alias, DeviceContext)` — both are owned / Arc-based
     /// so the caller can drop the registry lock before doing async I/O.
-    pub fn resolve_gpio_device(
+    pub fn resolve_device(
         &self,
         args: &serde_json::Value,
+        capability: Capability,
     ) -> Result<(String, DeviceContext), String> {
         let device_alias: String = match args.get("device").and_then(|v| v.as_str()) {
             Some(a) => a.to_string(),
             None => {
-                let gpio_aliases: Vec<String> = self
+                let mut capable: Vec<String> = self
                     .aliases()
                     .into_iter()
                     .filter(|a| {
                         self.context(a)
-                            .map(|c| c.capabilities.gpio)
+                            .map(|c| c.capabilities.supports(capability))
                             .unwrap_or(false)
                     })
                     .map(|a| a.to_string())
                     .collect();
-                match gpio_aliases.as_slice() {
+                capable.sort();
+                match capable.as_slice() {
                     [single] => single.clone(),
                     [] => {
-                        return Err("no GPIO-capable device found; specify \"device\" parameter"
-                            .to_string());
+                        return Err(format!(
+                            "no {capability}-capable device found; specify \"device\" parameter"
+                        ));
                     }
                     _ => {
                         return Err(format!(
                             "multiple devices available ({}); specify \"device\" parameter",
-                            gpio_aliases.join(", ")
+                            capable.join(", ")
                         ));
                     }
                 }
@@ -359,17 +439,63 @@ impl DeviceRegistry {
             )
         })?;
 
-        // Verify the device advertises GPIO capability.
-        if !ctx.capabilities.gpio {
+        // Verify the device advertises the capability.
+        if !ctx.capabilities.supports(capability) {
             return Err(format!(
-                "device '{}' does not support GPIO; specify a GPIO-capable device",
-                device_alias
+                "device '{device_alias}' does not support {capability}; specify a {capability}-capable device"
             ));
         }
 
         Ok((device_alias, ctx))
     }
 
+    /// Attach an [`EventSink`] for every device with a transport, so event
+    /// frames they push reach `tx` (see [`spawn_forwarder`]).
+    ///
+    /// [`EventSink`]: super::events::EventSink
+    /// [`spawn_forwarder`]: super::events::spawn_forwarder
+    pub fn route_events(
+        &self,
+        tx: &tokio::sync::mpsc::UnboundedSender<super::events::DeviceEvent>,
+    ) {
+        for (alias, entry) in &self.devices {
+            if let Some(transport) = &entry.transport {
+                transport.set_event_sink(super::events::EventSink::new(alias.clone(), tx.clone()));
+            }
+        }
+    }
+
+    /// Ask every device for its `capabilities` and merge the reported
+    /// peripherals (I2C, SPI, ADC, PWM) into its flags.
+    ///
+    /// Devices that do not answer keep their current flags.
+    pub async fn refresh_capabilities(&mut self) {
+        let cmd = super::protocol::ZcCommand::simple("capabilities");
+        for (alias, entry) in &mut self.devices {
+            let Some(transport) = entry.transport.clone() else {
+                continue;
+            };
+            match transport.send(&cmd).await {
+                Ok(resp) if resp.ok => {
+                    // Older firmware returns the object JSON-encoded in a string.
+                    let data = match &resp.data {
+                        serde_json::Value::String(raw) => {
+                            serde_json::from_str(raw).unwrap_or_default()
+                        }
+                        other => other.clone(),
+                    };
+                    entry.capabilities.merge_response(&data);
+                }
+                Ok(resp) => tracing::debug!(
+                    alias = %alias,
+                    "capabilities not supported: {}",
+                    resp.error.unwrap_or_default()
+                ),
+                Err(e) => tracing::debug!(alias = %alias, "capabilities query failed: {e}"),
+            }
+        }
+    }
+
     /// Number of registered devices.
     pub fn len(&self) -> usize {
         self.devices.len()
@@ -409,6 +535,83 @@ impl DeviceRegistry {
         lines.join("\n")
     }
 
+    /// Register the statically configured network devices
+    /// (`[[hardware.network_devices]]`) and attach a [`NetworkTransport`] to each.
+    ///
+    /// No connection is made here — the transport connects on first use and
+    /// reconnects on its own. Returns the assigned aliases; entries with an
+    /// invalid address are skipped with a warning.
+    ///
+    /// [`NetworkTransport`]: super::network::NetworkTransport
+    pub fn register_network_devices(
+        &mut self,
+        devices: &[crate::config::NetworkDeviceConfig],
+    ) -> Vec<String> {
+        use super::network::{NetworkEndpoint, NetworkTransport};
+
+        let mut aliases = Vec::with_capacity(devices.len());
+        for config in devices {
+            let endpoint = match NetworkEndpoint::parse(&config.address) {
+                Ok(endpoint) => endpoint,
+                Err(e) => {
+                    tracing::warn!(address = %config.address, "skipping network device: {e}");
+                    continue;
+                }
+            };
+            let board = config.board.trim();
+            let alias = self.register(board, None, None, Some(endpoint.to_string()), None);
+            if let Some(entry) = self.devices.get_mut(&alias) {
+                let kind = DeviceKind::from_board_name(board);
+                let mut device = (*entry.device).clone();
+                device.runtime = DeviceRuntime::from_kind(&kind);
+                device.kind = kind;
+                entry.device = Arc::new(device);
+            }
+
+            let transport = Arc::new(NetworkTransport::new(endpoint, config.psk.clone()));
+            let caps = DeviceCapabilities {
+                gpio: true, // same assumption as USB boards until a capabilities handshake exists
+                ..DeviceCapabilities::default()
+            };
+            if let Err(e) = self.attach_transport(&alias, transport, caps) {
+                tracing::warn!(alias = %alias, err = %e, "attach_transport: unexpected unknown alias");
+                continue;
+            }
+            tracing::info!(alias = %alias, address = %config.address, "network device registered");
+            aliases.push(alias);
+        }
+        aliases
+    }
+
+    /// Register the simulated boards from config
+    /// (`[[hardware.virtual_devices]]`) and attach a [`SimulatedTransport`] to each.
+    ///
+    /// Capabilities are taken from what each board reports. Returns the
+    /// assigned aliases (`sim0`, `sim1`, … for the default board name).
+    ///
+    /// [`SimulatedTransport`]: super::simulator::SimulatedTransport
+    pub fn register_virtual_devices(
+        &mut self,
+        devices: &[crate::config::VirtualDeviceConfig],
+    ) -> Vec<String> {
+        use super::simulator::SimulatedTransport;
+
+        let mut aliases = Vec::with_capacity(devices.len());
+        for config in devices {
+            let alias = self.register(config.board.trim(), None, None, None, None);
+            let transport = Arc::new(SimulatedTransport::new(config));
+            let mut caps = DeviceCapabilities::default();
+            caps.merge_response(&transport.capabilities());
+            if let Err(e) = self.attach_transport(&alias, transport, caps) {
+                tracing::warn!(alias = %alias, err = %e, "attach_transport: unexpected unknown alias");
+                continue;
+            }
+            tracing::info!(alias = %alias, "virtual device registered");
+            aliases.push(alias);
+        }
+        aliases
+    }
+
     /// Discover all connected serial devices and populate the registry.
     ///
     /// Steps:
@@ -505,6 +708,17 @@ impl DeviceRegistry {
             .get_mut(alias)
             .ok_or_else(|| anyhow::anyhow!("unknown device alias: {alias}"))?;
 
+        // Network transports reconnect (with backoff) on their own.
+        match entry.transport.as_ref().map(|t| t.kind()) {
+            Some(super::transport::TransportKind::Network) => anyhow::bail!(
+                "{alias} is a network device; it reconnects automatically on next use"
+            ),
+            Some(super::transport::TransportKind::Simulated) => {
+                anyhow::bail!("{alias} is a virtual device; there is nothing to reconnect")
+            }
+            _ => {}
+        }
+
         // Determine the port path — prefer the caller's override.
         let port_path = match new_port {
             Some(p) => {
@@ -555,6 +769,7 @@ fn alias_prefix(board_name: &str) -> String {
         s if s.starts_with("esp32") || s.starts_with("esp") => "esp".to_string(),
         s if s.starts_with("nucleo") || s.starts_with("stm32") => "nucleo".to_string(),
         s if s.starts_with("rpi") || s == "raspberry-pi" => "rpi".to_string(),
+        s if s.starts_with("sim") => "sim".to_string(),
         _ => "device".to_string(),
     }
 }
@@ -594,6 +809,12 @@ mod tests {
         assert_eq!(alias_prefix("raspberry-pi"), "rpi");
     }
 
+    #[test]
+    fn alias_prefix_sim() {
+        assert_eq!(alias_prefix("sim"), "sim");
+        assert_eq!(alias_prefix("sim-esp32"), "sim");
+    }
+
     #[test]
     fn alias_prefix_unknown() {
         assert_eq!(alias_prefix("custom-board"), "device");
@@ -791,6 +1012,89 @@ mod tests {
         assert!(s.contains("/dev/ttyACM0"));
     }
 
+    #[test]
+    fn capabilities_merge_firmware_response() {
+        let mut caps = DeviceCapabilities {
+            gpio: true,
+            ..Default::default()
+        };
+        caps.merge_response(&serde_json::json!({
+            "gpio": [2, 13],
+            "i2c": true,
+            "spi": false,
+            "adc": [0, 1],
+            "pwm": []
+        }));
+        assert!(caps.gpio && caps.i2c && caps.adc);
+        assert!(!caps.spi && !caps.pwm);
+        assert!(caps.supports(Capability::I2c));
+        assert!(!caps.supports(Capability::Pwm));
+        assert_eq!(Capability::I2c.to_string(), "I2C");
+    }
+
+    #[test]
+    fn device_kind_from_board_name() {
+        assert_eq!(DeviceKind::from_board_name("esp32-s3"), DeviceKind::Esp32);
+        assert_eq!(DeviceKind::from_board_name("pico-w"), DeviceKind::Pico);
+        assert_eq!(DeviceKind::from_board_name("custom"), DeviceKind::Generic);
+    }
+
+    #[test]
+    fn register_network_devices_assigns_aliases_and_transport() {
+        use crate::config::NetworkDeviceConfig;
+        use crate::hardware::transport::TransportKind;
+
+        let mut reg = DeviceRegistry::new();
+        let aliases = reg.register_network_devices(&[
+            NetworkDeviceConfig {
+                board: "esp32".into(),
+                address: "tcp://192.168.1.40:7777".into(),
+                psk: Some("s3cret".into()),
+            },
+            NetworkDeviceConfig {
+                board: "esp32".into(),
+                address: "bogus".into(),
+                psk: None,
+            },
+            NetworkDeviceConfig {
+                board: "esp32-c3".into(),
+                address: "ws://192.168.1.41:7777/zc".into(),
+                psk: None,
+            },
+        ]);
+        assert_eq!(aliases, vec!["esp0", "esp1"]);
+
+        let ctx = reg.context("esp0").unwrap();
+        assert_eq!(ctx.device.kind, DeviceKind::Esp32);
+        assert_eq!(ctx.device.port(), Some("tcp://192.168.1.40:7777"));
+        assert_eq!(ctx.transport.kind(), TransportKind::Network);
+        assert!(!ctx.transport.is_connected());
+        assert!(ctx.capabilities.gpio);
+        assert!(reg.prompt_summary().contains("esp1 — esp32-c3"));
+    }
+
+    #[test]
+    fn register_virtual_devices_reports_board_capabilities() {
+        use crate::config::{VirtualDeviceConfig, WaveformConfig};
+        use crate::hardware::transport::TransportKind;
+
+        let mut reg = DeviceRegistry::new();
+        let aliases = reg.register_virtual_devices(&[
+            VirtualDeviceConfig::default(),
+            VirtualDeviceConfig {
+                adc: vec![WaveformConfig::Constant { value: 1 }],
+                ..VirtualDeviceConfig::default()
+            },
+        ]);
+        assert_eq!(aliases, vec!["sim0", "sim1"]);
+
+        let sim0 = reg.context("sim0").unwrap();
+        assert_eq!(sim0.transport.kind(), TransportKind::Simulated);
+        assert!(sim0.capabilities.gpio && sim0.capabilities.i2c);
+        assert!(!sim0.capabilities.adc);
+        assert!(reg.context("sim1").unwrap().capabilities.adc);
+    }
+
     #[test]
     fn registry_summary_empty_when_no_devices() {
         let reg = DeviceRegistry::new();
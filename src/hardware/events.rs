@@ -0,0 +1,309 @@
+//! Device-pushed events — unsolicited `{"event":…}` frames into the dispatch router.
+//!
+//! Transports that read an event frame (see [`ZcEvent`]) hand it to the
+//! [`EventSink`] attached by [`DeviceRegistry::route_events`]. A single
+//! forwarding task then publishes each event with
+//! [`emit_signal`](crate::peripherals::signal::emit_signal), so the topic is
+//! `{alias}/{signal}` — e.g. `esp0/pin_9` or `esp0/adc_0` — and handlers match
+//! device events exactly like RPi GPIO watcher events.
+//!
+//! Events are armed per device with the `hardware_watch` tool (firmware
+//! commands `gpio_watch` / `adc_watch`). Serial transports open the port only
+//! for a command, so they deliver events received while a command is in
+//! flight; network transports keep their connection and deliver them as they
+//! arrive.
+//!
+//! [`DeviceRegistry::route_events`]: super::device::DeviceRegistry::route_events
+
+use super::bus::{device_call, failure};
+use super::device::{Capability, DeviceRegistry};
+use super::protocol::{ZcCommand, ZcEvent};
+use crate::dispatch::{DispatchAuditLogger, EventRouter};
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+
+/// An event received from a registered device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceEvent {
+    /// Alias of the device that sent the event (e.g. `"esp0"`).
+    pub device: String,
+    /// The raw event frame.
+    pub event: ZcEvent,
+}
+
+/// Per-device handle transports use to publish event frames.
+#[derive(Debug, Clone)]
+pub struct EventSink {
+    device: String,
+    tx: mpsc::UnboundedSender<DeviceEvent>,
+}
+
+impl EventSink {
+    pub fn new(device: impl Into<String>, tx: mpsc::UnboundedSender<DeviceEvent>) -> Self {
+        Self {
+            device: device.into(),
+            tx,
+        }
+    }
+
+    /// Publish an event. Never blocks; events are dropped once the forwarder stops.
+    pub fn emit(&self, event: ZcEvent) {
+        tracing::debug!(device = %self.device, event = %event.event, "device event");
+        let _ = self.tx.send(DeviceEvent {
+            device: self.device.clone(),
+            event,
+        });
+    }
+}
+
+/// Spawn the task that publishes device events through `router` + `audit`.
+///
+/// Pass the returned sender to [`DeviceRegistry::route_events`]. The task ends
+/// when every sender (and so every transport sink) is dropped.
+pub fn spawn_forwarder(
+    router: Arc<EventRouter>,
+    audit: Arc<DispatchAuditLogger>,
+) -> mpsc::UnboundedSender<DeviceEvent> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<DeviceEvent>();
+    tokio::spawn(async move {
+        while let Some(DeviceEvent { device, event }) = rx.recv().await {
+            let signal = event.signal();
+            let payload = event.payload();
+            if let Err(e) = crate::peripherals::signal::emit_signal(
+                &router,
+                &audit,
+                &device,
+                &signal,
+                Some(&payload),
+            )
+            .await
+            {
+                tracing::warn!(device = %device, signal = %signal, "device event: emit_signal failed: {e}");
+            }
+        }
+        tracing::debug!("device event forwarder stopped");
+    });
+    tx
+}
+
+// ── HardwareWatchTool ─────────────────────────────────────────────────────────
+
+/// Tool: arm or disarm device-pushed events (pin changes, ADC thresholds).
+pub struct HardwareWatchTool {
+    registry: Arc<RwLock<DeviceRegistry>>,
+}
+
+impl HardwareWatchTool {
+    pub fn new(registry: Arc<RwLock<DeviceRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for HardwareWatchTool {
+    fn name(&self) -> &str {
+        "hardware_watch"
+    }
+
+    fn description(&self) -> &str {
+        "Start or stop device events: GPIO pin changes (topic <device>/pin_<n>) or ADC threshold crossings (topic <device>/adc_<channel>)"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": {
+                    "type": "string",
+                    "description": "Device alias e.g. esp0, arduino0"
+                },
+                "kind": {
+                    "type": "string",
+                    "enum": ["pin", "adc"],
+                    "description": "pin = report level changes, adc = report threshold crossings"
+                },
+                "pin": {
+                    "type": "integer",
+                    "description": "GPIO pin number (kind = pin)"
+                },
+                "channel": {
+                    "type": "integer",
+                    "description": "ADC channel (kind = adc)"
+                },
+                "threshold": {
+                    "type": "integer",
+                    "description": "Raw ADC value to report crossings of (kind = adc)"
+                },
+                "enabled": {
+                    "type": "boolean",
+                    "description": "false stops the watch (default true)"
+                }
+            },
+            "required": ["kind"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let enabled = args
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let (capability, cmd, what) = match args.get("kind").and_then(|v| v.as_str()) {
+            Some("pin") => {
+                let Some(pin) = args.get("pin").and_then(|v| v.as_u64()) else {
+                    return Ok(failure("missing required parameter: pin"));
+                };
+                (
+                    Capability::Gpio,
+                    ZcCommand::new("gpio_watch", json!({ "pin": pin, "enabled": enabled })),
+                    format!("pin_{pin}"),
+                )
+            }
+            Some("adc") => {
+                let Some(channel) = args.get("channel").and_then(|v| v.as_u64()) else {
+                    return Ok(failure("missing required parameter: channel"));
+                };
+                let threshold = args.get("threshold").and_then(|v| v.as_u64());
+                if enabled && threshold.is_none() {
+                    return Ok(failure("missing required parameter: threshold"));
+                }
+                (
+                    Capability::Adc,
+                    ZcCommand::new(
+                        "adc_watch",
+                        json!({ "channel": channel, "threshold": threshold, "enabled": enabled }),
+                    ),
+                    format!("adc_{channel}"),
+                )
+            }
+            _ => return Ok(failure("kind must be \"pin\" or \"adc\"")),
+        };
+
+        Ok(
+            match device_call(&self.registry, &args, capability, &cmd).await {
+                Ok((alias, _)) if enabled => ToolResult {
+                    success: true,
+                    output: format!("Watching {alias}/{what}; events are dispatched on that topic"),
+                    error: None,
+                },
+                Ok((alias, _)) => ToolResult {
+                    success: true,
+                    output: format!("Stopped watching {alias}/{what}"),
+                    error: None,
+                },
+                Err(result) => result,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatch::types::HandlerOutcome;
+    use crate::dispatch::{DispatchEvent, EventHandler};
+
+    struct Recorder {
+        seen: parking_lot::Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn matches(&self, event: &DispatchEvent) -> bool {
+            event
+                .topic
+                .as_deref()
+                .is_some_and(|t| t.starts_with("esp0/"))
+        }
+
+        async fn handle(&self, event: &DispatchEvent) -> anyhow::Result<HandlerOutcome> {
+            self.seen.lock().push((
+                event.topic.clone().unwrap_or_default(),
+                event.payload.clone(),
+            ));
+            Ok(HandlerOutcome::Handled {
+                summary: "recorded".into(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn forwarder_publishes_device_events_as_signals() {
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let memory: Arc<dyn crate::memory::traits::Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let audit = Arc::new(DispatchAuditLogger::new(memory));
+        let router = Arc::new(EventRouter::new());
+        let recorder = Arc::new(Recorder {
+            seen: parking_lot::Mutex::new(Vec::new()),
+        });
+        router.register(recorder.clone());
+
+        let sink = EventSink::new("esp0", spawn_forwarder(router, audit));
+        sink.emit(ZcEvent {
+            event: "pin_change".into(),
+            data: json!({"pin": 9, "value": 1}),
+        });
+        sink.emit(ZcEvent {
+            event: "threshold".into(),
+            data: json!({"channel": 0, "value": 2101, "direction": "rising"}),
+        });
+
+        for _ in 0..50 {
+            if recorder.seen.lock().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let seen = recorder.seen.lock().clone();
+        assert_eq!(seen[0], ("esp0/pin_9".to_string(), Some("1".to_string())));
+        assert_eq!(seen[1].0, "esp0/adc_0");
+        assert!(seen[1].1.as_deref().unwrap().contains("rising"));
+    }
+
+    #[tokio::test]
+    async fn hardware_watch_arms_adc_threshold() {
+        use crate::hardware::bus::tests::registry_with;
+        use crate::hardware::device::DeviceCapabilities;
+        use crate::hardware::protocol::ZcResponse;
+
+        let (reg, mock) = registry_with(
+            DeviceCapabilities {
+                adc: true,
+                ..Default::default()
+            },
+            ZcResponse::success(json!({})),
+        );
+        let tool = HardwareWatchTool::new(reg);
+
+        let result = tool
+            .execute(json!({"kind": "adc", "channel": 0}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("missing required parameter: threshold")
+        );
+
+        let result = tool
+            .execute(json!({"kind": "adc", "channel": 0, "threshold": 2000}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("esp0/adc_0"));
+        let cmd = mock.last_cmd.lock().clone().unwrap();
+        assert_eq!(cmd.cmd, "adc_watch");
+        assert_eq!(cmd.params["threshold"], 2000);
+    }
+}
@@ -2,10 +2,15 @@
 //!
 //! See `docs/hardware-peripherals-design.md` for the full design.
 
+pub mod analog;
+pub mod bus;
 pub mod device;
+pub mod events;
 pub mod gpio;
+pub mod network;
 pub mod protocol;
 pub mod registry;
+pub mod simulator;
 pub mod transport;
 
 #[cfg(all(
@@ -24,19 +29,33 @@ pub mod introspect;
 pub mod serial;
 
 use crate::config::Config;
+use crate::dispatch::SharedDispatch;
+use crate::tools::Tool;
 use anyhow::Result;
+use std::sync::Arc;
+use tokio::sync::{OnceCell, RwLock};
 
 // Re-export config types so wizard can use `hardware::HardwareConfig` etc.
 pub use crate::config::{HardwareConfig, HardwareTransport};
 #[allow(unused_imports)]
+pub use analog::{analog_tools, AdcReadTool, PwmWriteTool, ServoWriteTool};
+#[allow(unused_imports)]
+pub use bus::{bus_tools, I2cReadTool, I2cScanTool, I2cWriteTool, SpiTransferTool};
+#[allow(unused_imports)]
 pub use device::{
-    Device, DeviceCapabilities, DeviceContext, DeviceKind, DeviceRegistry, DeviceRuntime,
-    NO_HW_DEVICES_SUMMARY,
+    Capability, Device, DeviceCapabilities, DeviceContext, DeviceKind, DeviceRegistry,
+    DeviceRuntime, NO_HW_DEVICES_SUMMARY,
 };
 #[allow(unused_imports)]
+pub use events::{spawn_forwarder, DeviceEvent, EventSink, HardwareWatchTool};
+#[allow(unused_imports)]
 pub use gpio::{gpio_tools, GpioReadTool, GpioWriteTool};
 #[allow(unused_imports)]
-pub use protocol::{ZcCommand, ZcResponse};
+pub use network::{NetworkEndpoint, NetworkTransport};
+#[allow(unused_imports)]
+pub use protocol::{ZcCommand, ZcEvent, ZcFrame, ZcResponse};
+#[allow(unused_imports)]
+pub use simulator::{Fault, SimulatedTransport};
 #[allow(unused_imports)]
 pub use transport::{Transport, TransportError, TransportKind};
 
@@ -123,6 +142,73 @@ pub fn config_from_wizard_choice(choice: usize, devices: &[DiscoveredDevice]) ->
     }
 }
 
+/// Registry of the devices configured under `[hardware]`, shared by every
+/// agent in the process. Built on first use by [`create_device_tools`];
+/// device changes take effect on restart.
+static DEVICE_REGISTRY: OnceCell<Arc<RwLock<DeviceRegistry>>> = OnceCell::const_new();
+
+/// Register the statically configured devices (`[[hardware.network_devices]]`
+/// and `[[hardware.virtual_devices]]`) and publish the events they push
+/// through `dispatch`, when given.
+pub fn build_device_registry(
+    config: &HardwareConfig,
+    dispatch: Option<&SharedDispatch>,
+) -> DeviceRegistry {
+    let mut registry = DeviceRegistry::new();
+    registry.register_network_devices(&config.network_devices);
+    registry.register_virtual_devices(&config.virtual_devices);
+    if let Some(dispatch) = dispatch {
+        let tx = spawn_forwarder(Arc::clone(&dispatch.router), Arc::clone(&dispatch.audit));
+        registry.route_events(&tx);
+    }
+    registry
+}
+
+/// Agent tools over `registry`: GPIO, I2C/SPI, ADC/PWM/servo and
+/// `hardware_watch`.
+pub fn device_tools(registry: &Arc<RwLock<DeviceRegistry>>) -> Vec<Box<dyn Tool>> {
+    let mut tools = gpio_tools(Arc::clone(registry));
+    tools.extend(bus_tools(Arc::clone(registry)));
+    tools.extend(analog_tools(Arc::clone(registry)));
+    tools.push(Box::new(HardwareWatchTool::new(Arc::clone(registry))));
+    tools
+}
+
+/// Tools for the devices configured under `[hardware]`, or none when there
+/// are none. Tools named like one in `existing` (e.g. a peripheral board's
+/// `gpio_read`) are left out.
+///
+/// Device events go to the gateway's shared dispatch router; processes
+/// without one (a plain `zeroclaw agent`) do not route them. Capabilities
+/// are queried in the background so unreachable devices do not delay startup.
+pub async fn create_device_tools(
+    config: &HardwareConfig,
+    existing: &[Box<dyn Tool>],
+) -> Vec<Box<dyn Tool>> {
+    if config.network_devices.is_empty() && config.virtual_devices.is_empty() {
+        return Vec::new();
+    }
+    let registry = DEVICE_REGISTRY
+        .get_or_init(|| async {
+            let dispatch = crate::dispatch::shared();
+            if dispatch.is_none() {
+                tracing::debug!("no dispatch router in this process; device events are dropped");
+            }
+            let registry = Arc::new(RwLock::new(build_device_registry(
+                config,
+                dispatch.as_ref(),
+            )));
+            let refresh = Arc::clone(&registry);
+            tokio::spawn(async move { refresh.write().await.refresh_capabilities().await });
+            registry
+        })
+        .await;
+    device_tools(registry)
+        .into_iter()
+        .filter(|tool| !existing.iter().any(|other| other.name() == tool.name()))
+        .collect()
+}
+
 /// Handle `zeroclaw hardware` subcommands.
 #[allow(clippy::module_name_repetitions)]
 pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<()> {
@@ -287,3 +373,147 @@ fn info_via_probe(chip: &str) -> anyhow::Result<()> {
     println!("Info read via USB (SWD) — no firmware on target needed.");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{NetworkDeviceConfig, VirtualDeviceConfig};
+
+    #[tokio::test]
+    async fn configured_network_devices_get_registered_with_tools() {
+        let config = HardwareConfig {
+            network_devices: vec![NetworkDeviceConfig {
+                board: "esp32".into(),
+                address: "tcp://127.0.0.1:7777".into(),
+                psk: None,
+            }],
+            ..HardwareConfig::default()
+        };
+        let registry = Arc::new(RwLock::new(build_device_registry(&config, None)));
+        {
+            let registry = registry.read().await;
+            assert_eq!(registry.aliases(), ["esp0"]);
+            let transport = registry.context("esp0").unwrap().transport;
+            assert_eq!(transport.kind(), TransportKind::Network);
+        }
+
+        let names: Vec<_> = device_tools(&registry)
+            .iter()
+            .map(|tool| tool.name().to_string())
+            .collect();
+        assert!(names.contains(&"gpio_read".to_string()), "{names:?}");
+        assert!(create_device_tools(&HardwareConfig::default(), &[])
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn configured_virtual_devices_answer_device_tools() {
+        let config = HardwareConfig {
+            virtual_devices: vec![VirtualDeviceConfig {
+                pins: [("9".to_string(), 1)].into(),
+                ..VirtualDeviceConfig::default()
+            }],
+            ..HardwareConfig::default()
+        };
+        let registry = Arc::new(RwLock::new(build_device_registry(&config, None)));
+        assert_eq!(registry.read().await.aliases(), ["sim0"]);
+
+        let tools = device_tools(&registry);
+        let read = tools
+            .iter()
+            .find(|tool| tool.name() == "gpio_read")
+            .unwrap();
+        let result = read
+            .execute(serde_json::json!({"device": "sim0", "pin": 9}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("HIGH"), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn device_events_reach_the_shared_router() {
+        use crate::config::WaveformConfig;
+        use crate::dispatch::{DispatchEvent, EventHandler, HandlerOutcome};
+
+        struct Recorder(parking_lot::Mutex<Vec<String>>);
+
+        #[async_trait::async_trait]
+        impl EventHandler for Recorder {
+            fn name(&self) -> &str {
+                "recorder"
+            }
+            fn matches(&self, _event: &DispatchEvent) -> bool {
+                true
+            }
+            async fn handle(&self, event: &DispatchEvent) -> anyhow::Result<HandlerOutcome> {
+                self.0.lock().push(event.topic.clone().unwrap_or_default());
+                Ok(HandlerOutcome::Handled {
+                    summary: "recorded".into(),
+                })
+            }
+        }
+
+        let tmp = tempfile::tempdir().unwrap();
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let memory = crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap();
+        let dispatch = SharedDispatch {
+            router: Arc::new(crate::dispatch::EventRouter::new()),
+            audit: Arc::new(crate::dispatch::DispatchAuditLogger::new(Arc::from(memory))),
+        };
+        let recorder = Arc::new(Recorder(parking_lot::Mutex::new(Vec::new())));
+        dispatch.router.register(recorder.clone());
+
+        let config = HardwareConfig {
+            virtual_devices: vec![VirtualDeviceConfig {
+                adc: vec![WaveformConfig::Square {
+                    min: 0,
+                    max: 4095,
+                    period_ms: 100,
+                }],
+                ..VirtualDeviceConfig::default()
+            }],
+            ..HardwareConfig::default()
+        };
+        let registry = Arc::new(RwLock::new(build_device_registry(&config, Some(&dispatch))));
+        let tools = device_tools(&registry);
+        for name in [
+            "i2c_scan",
+            "spi_transfer",
+            "adc_read",
+            "servo_write",
+            "hardware_watch",
+        ] {
+            assert!(
+                tools.iter().any(|tool| tool.name() == name),
+                "{name} missing"
+            );
+        }
+
+        let watch = tools
+            .iter()
+            .find(|tool| tool.name() == "hardware_watch")
+            .unwrap();
+        let result = watch
+            .execute(serde_json::json!({
+                "device": "sim0", "kind": "adc", "channel": 0, "threshold": 2000
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+
+        for _ in 0..200 {
+            if !recorder.0.lock().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            recorder.0.lock().first().map(String::as_str),
+            Some("sim0/adc_0")
+        );
+    }
+}
@@ -0,0 +1,584 @@
+//! Network transport — the ZeroClaw serial JSON protocol over TCP or WebSocket.
+//!
+//! Lets Wi-Fi boards (e.g. an ESP32 running `firmware/zeroclaw-esp32`) be
+//! driven exactly like USB boards. Devices are registered statically from
+//! `[[hardware.network_devices]]` (no mDNS). The address selects the framing:
+//!
+//! - `tcp://host:port` — one JSON object per `\n`-terminated line
+//! - `ws://host:port/path` or `wss://…` — one JSON object per text message
+//!
+//! Unlike the serial transport, the connection stays open between commands.
+//! It is (re)established on demand: after a failed connect, further attempts
+//! back off exponentially from 1 s to 30 s, and a command that fails on a
+//! stale connection is retried once on a fresh one. While connected, a reader
+//! task passes event frames to the attached [`EventSink`] as they arrive.
+//!
+//! ## Authentication
+//!
+//! With a pre-shared key, host and device prove knowledge of it to each
+//! other before any other command:
+//! ```text
+//! Host → Device:  {"cmd":"hello","params":{"nonce":"<host nonce>"}}
+//! Device → Host:  {"ok":true,"data":{"firmware":"zeroclaw","nonce":"<device nonce>","mac":"<hex>"}}
+//! Host → Device:  {"cmd":"auth","params":{"mac":"<hex>"}}
+//! Device → Host:  {"ok":true}
+//! ```
+//! The device's `mac` is HMAC-SHA256(psk, `"device:" + host nonce`) and the
+//! host's is HMAC-SHA256(psk, `"host:" + device nonce`). Without a key the
+//! `hello` only confirms that ZeroClaw firmware is answering.
+
+use super::{
+    events::EventSink,
+    protocol::{ZcCommand, ZcFrame, ZcResponse},
+    transport::{Transport, TransportError, TransportKind},
+};
+use async_trait::async_trait;
+use futures_util::stream::SplitSink;
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Event sink shared between the transport and its connection's reader task.
+type SharedSink = Arc<Mutex<Option<EventSink>>>;
+
+/// Timeout for a single send→receive round-trip (seconds).
+const SEND_TIMEOUT_SECS: u64 = 5;
+
+/// Timeout for connecting and authenticating (seconds).
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+
+/// First and largest delay between reconnect attempts.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Where a network device listens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkEndpoint {
+    /// `host:port` for newline-delimited JSON over TCP.
+    Tcp(String),
+    /// `ws://` or `wss://` URL; one JSON object per text message.
+    WebSocket(String),
+}
+
+impl NetworkEndpoint {
+    /// Parse a `tcp://`, `ws://` or `wss://` device address.
+    pub fn parse(address: &str) -> Result<Self, TransportError> {
+        let address = address.trim();
+        if let Some(authority) = address.strip_prefix("tcp://") {
+            let authority = authority.trim_end_matches('/');
+            match authority.rsplit_once(':') {
+                Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {
+                    Ok(Self::Tcp(authority.to_string()))
+                }
+                _ => Err(TransportError::Other(format!(
+                    "network device address {address:?} must be tcp://host:port"
+                ))),
+            }
+        } else if address.starts_with("ws://") || address.starts_with("wss://") {
+            Ok(Self::WebSocket(address.to_string()))
+        } else {
+            Err(TransportError::Other(format!(
+                "network device address {address:?} must start with tcp://, ws:// or wss://"
+            )))
+        }
+    }
+}
+
+impl std::fmt::Display for NetworkEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(authority) => write!(f, "tcp://{authority}"),
+            Self::WebSocket(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+/// Reconnect pacing after failed connection attempts.
+#[derive(Debug, Default)]
+struct Backoff {
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    fn delay(&self) -> Duration {
+        INITIAL_BACKOFF
+            .saturating_mul(1 << self.failures.saturating_sub(1).min(5))
+            .min(MAX_BACKOFF)
+    }
+
+    fn record_failure(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        self.retry_at = Some(now + self.delay());
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Network transport for ZeroClaw hardware devices.
+pub struct NetworkTransport {
+    endpoint: NetworkEndpoint,
+    psk: Option<String>,
+    connection: tokio::sync::Mutex<Option<Connection>>,
+    backoff: Mutex<Backoff>,
+    connected: AtomicBool,
+    events: SharedSink,
+}
+
+impl NetworkTransport {
+    /// Create a transport for `endpoint`. Does NOT connect — that happens on
+    /// the first `send()` call.
+    pub fn new(endpoint: NetworkEndpoint, psk: Option<String>) -> Self {
+        Self {
+            endpoint,
+            psk: psk.filter(|k| !k.trim().is_empty()),
+            connection: tokio::sync::Mutex::new(None),
+            backoff: Mutex::new(Backoff::default()),
+            connected: AtomicBool::new(false),
+            events: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Device address this transport is bound to.
+    pub fn endpoint(&self) -> &NetworkEndpoint {
+        &self.endpoint
+    }
+
+    /// Connect and authenticate, honouring the reconnect backoff.
+    async fn connect(&self) -> Result<Connection, TransportError> {
+        if let Some(retry_at) = self.backoff.lock().retry_at {
+            if Instant::now() < retry_at {
+                return Err(TransportError::Disconnected);
+            }
+        }
+        let attempt = tokio::time::timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS), async {
+            let mut conn = Connection::open(&self.endpoint, Arc::clone(&self.events)).await?;
+            handshake(&mut conn, self.psk.as_deref()).await?;
+            Ok::<_, TransportError>(conn)
+        })
+        .await
+        .unwrap_or(Err(TransportError::Timeout(CONNECT_TIMEOUT_SECS)));
+
+        match attempt {
+            Ok(conn) => {
+                self.backoff.lock().reset();
+                tracing::info!(endpoint = %self.endpoint, "network device connected");
+                Ok(conn)
+            }
+            Err(error) => {
+                let mut backoff = self.backoff.lock();
+                backoff.record_failure(Instant::now());
+                tracing::warn!(
+                    endpoint = %self.endpoint,
+                    retry_in_secs = backoff.delay().as_secs(),
+                    "network device connect failed: {error}"
+                );
+                Err(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for NetworkTransport {
+    async fn send(&self, cmd: &ZcCommand) -> Result<ZcResponse, TransportError> {
+        let json = serde_json::to_string(cmd)
+            .map_err(|e| TransportError::Protocol(format!("failed to serialize command: {e}")))?;
+        // Log command name only — never log the full payload.
+        tracing::info!(endpoint = %self.endpoint, cmd = %cmd.cmd, "network send");
+
+        let mut guard = self.connection.lock().await;
+        // A reused connection may have been dropped by the device (reboot,
+        // Wi-Fi roam); retry once on a fresh connection in that case.
+        let reused = guard.is_some();
+        for attempt in 0..2 {
+            let conn = match guard.as_mut() {
+                Some(conn) => conn,
+                None => guard.insert(self.connect().await.inspect_err(|_| {
+                    self.connected.store(false, Ordering::Relaxed);
+                })?),
+            };
+            let result =
+                tokio::time::timeout(Duration::from_secs(SEND_TIMEOUT_SECS), conn.request(&json))
+                    .await
+                    .unwrap_or(Err(TransportError::Timeout(SEND_TIMEOUT_SECS)));
+
+            match result {
+                Err(TransportError::Disconnected | TransportError::Io(_))
+                    if reused && attempt == 0 =>
+                {
+                    *guard = None;
+                }
+                Err(error) => {
+                    if !matches!(error, TransportError::Protocol(_)) {
+                        *guard = None;
+                    }
+                    self.connected.store(guard.is_some(), Ordering::Relaxed);
+                    return Err(error);
+                }
+                Ok(response) => {
+                    self.connected.store(true, Ordering::Relaxed);
+                    return Ok(response);
+                }
+            }
+        }
+        self.connected.store(false, Ordering::Relaxed);
+        Err(TransportError::Disconnected)
+    }
+
+    fn kind(&self) -> TransportKind {
+        TransportKind::Network
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    fn set_event_sink(&self, sink: EventSink) {
+        *self.events.lock() = Some(sink);
+    }
+}
+
+/// Run the `hello` (and, with a key, `auth`) exchange on a fresh connection.
+async fn handshake(conn: &mut Connection, psk: Option<&str>) -> Result<(), TransportError> {
+    let host_nonce = uuid::Uuid::new_v4().simple().to_string();
+    let hello = ZcCommand::new("hello", json!({ "nonce": host_nonce }));
+    let reply = conn
+        .request(&serde_json::to_string(&hello).unwrap_or_default())
+        .await?;
+    if !reply.ok || reply.data.get("firmware").and_then(|v| v.as_str()) != Some("zeroclaw") {
+        return Err(TransportError::Protocol(
+            "device did not identify as ZeroClaw firmware".into(),
+        ));
+    }
+    let Some(psk) = psk else {
+        return Ok(());
+    };
+
+    let device_mac = reply.data.get("mac").and_then(|v| v.as_str()).unwrap_or("");
+    if !verify_mac(psk, &format!("device:{host_nonce}"), device_mac) {
+        return Err(TransportError::Other(
+            "device failed pre-shared key authentication".into(),
+        ));
+    }
+    let device_nonce = reply
+        .data
+        .get("nonce")
+        .and_then(|v| v.as_str())
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| TransportError::Protocol("device sent no auth nonce".into()))?;
+    let auth = ZcCommand::new(
+        "auth",
+        json!({ "mac": sign_mac(psk, &format!("host:{device_nonce}")) }),
+    );
+    let reply = conn
+        .request(&serde_json::to_string(&auth).unwrap_or_default())
+        .await?;
+    if !reply.ok {
+        return Err(TransportError::Other(format!(
+            "device rejected pre-shared key: {}",
+            reply.error.as_deref().unwrap_or("no reason given")
+        )));
+    }
+    Ok(())
+}
+
+/// Hex HMAC-SHA256 of `message` under `psk`.
+fn sign_mac(psk: &str, message: &str) -> String {
+    use ring::hmac;
+    let key = hmac::Key::new(hmac::HMAC_SHA256, psk.as_bytes());
+    hex::encode(hmac::sign(&key, message.as_bytes()).as_ref())
+}
+
+/// Constant-time check of a hex HMAC-SHA256.
+fn verify_mac(psk: &str, message: &str, hex_mac: &str) -> bool {
+    use ring::hmac;
+    let Ok(expected) = hex::decode(hex_mac) else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, psk.as_bytes());
+    hmac::verify(&key, message.as_bytes(), &expected).is_ok()
+}
+
+/// An open connection to a device.
+///
+/// A reader task owns the receive half: it hands event frames to the sink as
+/// they arrive and queues every other frame as the response to the pending
+/// command. Dropping the connection stops the task.
+struct Connection {
+    writer: Writer,
+    responses: mpsc::Receiver<Result<ZcResponse, TransportError>>,
+    reader: tokio::task::JoinHandle<()>,
+}
+
+enum Writer {
+    Tcp(OwnedWriteHalf),
+    WebSocket(Box<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, WsMessage>>),
+}
+
+impl Connection {
+    async fn open(endpoint: &NetworkEndpoint, events: SharedSink) -> Result<Self, TransportError> {
+        let (tx, responses) = mpsc::channel(4);
+        let (writer, reader) = match endpoint {
+            NetworkEndpoint::Tcp(authority) => {
+                let stream = TcpStream::connect(authority.as_str()).await?;
+                stream.set_nodelay(true)?;
+                let (read, write) = stream.into_split();
+                let reader = tokio::spawn(async move {
+                    let mut lines = BufReader::new(read).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        if !deliver(&line, &tx, &events).await {
+                            break;
+                        }
+                    }
+                });
+                (Writer::Tcp(write), reader)
+            }
+            NetworkEndpoint::WebSocket(url) => {
+                let (stream, _response) = tokio_tungstenite::connect_async(url.as_str())
+                    .await
+                    .map_err(|e| TransportError::Other(format!("failed to open {url}: {e}")))?;
+                let (write, mut read) = stream.split();
+                let reader = tokio::spawn(async move {
+                    while let Some(Ok(message)) = read.next().await {
+                        let text = match message {
+                            WsMessage::Text(text) => text.to_string(),
+                            WsMessage::Binary(data) => String::from_utf8_lossy(&data).into_owned(),
+                            WsMessage::Close(_) => break,
+                            _ => continue,
+                        };
+                        if !deliver(&text, &tx, &events).await {
+                            break;
+                        }
+                    }
+                });
+                (Writer::WebSocket(Box::new(write)), reader)
+            }
+        };
+        Ok(Self {
+            writer,
+            responses,
+            reader,
+        })
+    }
+
+    /// Write one command and wait for its response.
+    ///
+    /// The caller owns the deadline — do NOT add a timeout here.
+    async fn request(&mut self, json: &str) -> Result<ZcResponse, TransportError> {
+        match &mut self.writer {
+            Writer::Tcp(stream) => stream.write_all(format!("{json}\n").as_bytes()).await?,
+            Writer::WebSocket(sink) => sink
+                .send(WsMessage::Text(json.to_string().into()))
+                .await
+                .map_err(|_| TransportError::Disconnected)?,
+        }
+        self.responses
+            .recv()
+            .await
+            .unwrap_or(Err(TransportError::Disconnected))
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Route one received frame. Returns `false` once nobody awaits responses.
+async fn deliver(
+    line: &str,
+    responses: &mpsc::Sender<Result<ZcResponse, TransportError>>,
+    events: &SharedSink,
+) -> bool {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return true;
+    }
+    let response = match ZcFrame::from_line(trimmed) {
+        Ok(ZcFrame::Event(event)) => {
+            if let Some(sink) = events.lock().as_ref() {
+                sink.emit(event);
+            }
+            return true;
+        }
+        Ok(ZcFrame::Response(response)) => Ok(response),
+        Err(e) => Err(TransportError::Protocol(format!(
+            "invalid JSON response: {e} — got: {trimmed:?}"
+        ))),
+    };
+    responses.send(response).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Minimal device speaking the network protocol with an optional key.
+    async fn fake_device(listener: TcpListener, psk: Option<&'static str>, connections: usize) {
+        for _ in 0..connections {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            let device_nonce = "d3v1c3";
+            let mut authed = psk.is_none();
+            let mut line = String::new();
+            while stream.read_line(&mut line).await.unwrap_or(0) > 0 {
+                let cmd: ZcCommand = serde_json::from_str(line.trim()).unwrap();
+                line.clear();
+                let reply = match cmd.cmd.as_str() {
+                    "hello" => {
+                        let nonce = cmd.params["nonce"].as_str().unwrap_or("");
+                        let mut data = json!({"firmware": "zeroclaw"});
+                        if let Some(psk) = psk {
+                            data["nonce"] = json!(device_nonce);
+                            data["mac"] = json!(sign_mac(psk, &format!("device:{nonce}")));
+                        }
+                        ZcResponse::success(data)
+                    }
+                    "auth" => {
+                        let mac = cmd.params["mac"].as_str().unwrap_or("");
+                        authed = psk
+                            .is_some_and(|k| verify_mac(k, &format!("host:{device_nonce}"), mac));
+                        if authed {
+                            ZcResponse::success(json!({}))
+                        } else {
+                            ZcResponse::error("bad key")
+                        }
+                    }
+                    _ if !authed => ZcResponse::error("unauthenticated"),
+                    "gpio_read" => ZcResponse::success(json!({"pin": 2, "value": 1})),
+                    "gpio_watch" => {
+                        // An event may precede the response to the command.
+                        let event = r#"{"event":"pin_change","data":{"pin":9,"value":0}}"#;
+                        stream
+                            .get_mut()
+                            .write_all(format!("{event}\n").as_bytes())
+                            .await
+                            .unwrap();
+                        ZcResponse::success(json!({}))
+                    }
+                    "drop" => break,
+                    other => ZcResponse::error(format!("unknown {other}")),
+                };
+                let out = serde_json::to_string(&reply).unwrap();
+                stream
+                    .get_mut()
+                    .write_all(format!("{out}\n").as_bytes())
+                    .await
+                    .unwrap();
+            }
+        }
+    }
+
+    async fn device(psk: Option<&'static str>, connections: usize) -> NetworkEndpoint {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(fake_device(listener, psk, connections));
+        NetworkEndpoint::parse(&format!("tcp://{addr}")).unwrap()
+    }
+
+    #[test]
+    fn endpoint_parse_accepts_tcp_and_websocket() {
+        assert_eq!(
+            NetworkEndpoint::parse("tcp://192.168.1.40:7777").unwrap(),
+            NetworkEndpoint::Tcp("192.168.1.40:7777".into())
+        );
+        assert!(matches!(
+            NetworkEndpoint::parse("wss://esp.local/zc"),
+            Ok(NetworkEndpoint::WebSocket(_))
+        ));
+        assert!(NetworkEndpoint::parse("tcp://esp.local").is_err());
+        assert!(NetworkEndpoint::parse("http://esp.local:80").is_err());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let mut backoff = Backoff::default();
+        let now = Instant::now();
+        backoff.record_failure(now);
+        assert_eq!(backoff.delay(), Duration::from_secs(1));
+        backoff.record_failure(now);
+        assert_eq!(backoff.delay(), Duration::from_secs(2));
+        for _ in 0..10 {
+            backoff.record_failure(now);
+        }
+        assert_eq!(backoff.delay(), MAX_BACKOFF);
+        backoff.reset();
+        assert!(backoff.retry_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn authenticated_send_and_reconnect() {
+        let endpoint = device(Some("s3cret"), 3).await;
+        let transport = NetworkTransport::new(endpoint, Some("s3cret".into()));
+        assert_eq!(transport.kind(), TransportKind::Network);
+        assert!(!transport.is_connected());
+
+        let resp = transport
+            .send(&ZcCommand::new("gpio_read", json!({"pin": 2})))
+            .await
+            .unwrap();
+        assert_eq!(resp.data["value"], 1);
+        assert!(transport.is_connected());
+
+        // The device drops the connection (twice, as the send is retried once
+        // on a fresh connection); the next command reconnects again.
+        let _ = transport.send(&ZcCommand::simple("drop")).await;
+        let resp = transport
+            .send(&ZcCommand::new("gpio_read", json!({"pin": 2})))
+            .await
+            .unwrap();
+        assert!(resp.ok);
+    }
+
+    #[tokio::test]
+    async fn wrong_key_is_rejected_and_backs_off() {
+        let endpoint = device(Some("s3cret"), 1).await;
+        let transport = NetworkTransport::new(endpoint, Some("guess".into()));
+        let err = transport
+            .send(&ZcCommand::simple("ping"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("pre-shared key"), "{err}");
+        // Within the backoff window no new connection is attempted.
+        let err = transport
+            .send(&ZcCommand::simple("ping"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TransportError::Disconnected));
+        assert!(!transport.is_connected());
+    }
+
+    #[tokio::test]
+    async fn event_frames_reach_the_sink() {
+        let endpoint = device(None, 1).await;
+        let transport = NetworkTransport::new(endpoint, None);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        transport.set_event_sink(EventSink::new("esp0", tx));
+
+        let resp = transport
+            .send(&ZcCommand::new("gpio_watch", json!({"pin": 9})))
+            .await
+            .unwrap();
+        assert!(resp.ok);
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.device, "esp0");
+        assert_eq!(event.event.signal(), "pin_9");
+    }
+}
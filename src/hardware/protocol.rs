@@ -7,6 +7,26 @@
 //!   Host → Device:  `{"cmd":"gpio_write","params":{"pin":25,"value":1}}\n`
 //!   Device → Host:  `{"ok":true,"data":{"pin":25,"value":1,"state":"HIGH"}}\n`
 //!
+//! Commands beyond GPIO (all optional — firmware answers `ok:false` for
+//! anything it does not implement; `capabilities` reports what it does):
+//!
+//! | `cmd` | `params` | `data` |
+//! |---|---|---|
+//! | `i2c_scan` | — | `{"addresses":[60,104]}` |
+//! | `i2c_read` | `{"address":60,"register":0,"length":2}` (`register` optional) | `{"bytes":[1,2]}` |
+//! | `i2c_write` | `{"address":60,"bytes":[0,175]}` | `{"written":2}` |
+//! | `spi_transfer` | `{"bytes":[159,0,0]}` | `{"bytes":[255,239,64]}` (full duplex) |
+//! | `adc_read` | `{"channel":0,"samples":4}` | `{"channel":0,"value":2048,"millivolts":1650}` |
+//! | `pwm_write` | `{"pin":18,"duty":50.0,"frequency":5000}` (duty in %) | `{"pin":18,"duty":50.0}` |
+//! | `servo_write` | `{"pin":19,"angle":90}` | `{"pin":19,"angle":90}` |
+//! | `gpio_watch` | `{"pin":9,"enabled":true}` | `{}` |
+//! | `adc_watch` | `{"channel":0,"threshold":2000,"enabled":true}` | `{}` |
+//!
+//! Device → Host event frames are unsolicited and may arrive at any time,
+//! including while the host waits for a response:
+//!   `{"event":"pin_change","data":{"pin":9,"value":0}}\n`
+//!   `{"event":"threshold","data":{"channel":0,"value":2101,"threshold":2000,"direction":"rising"}}\n`
+//!
 //! Both sides MUST agree on these struct definitions. Any change here is a
 //! breaking firmware contract change.
 
@@ -77,6 +97,57 @@ impl ZcResponse {
     }
 }
 
+/// Unsolicited device-to-host event frame (pin change, threshold crossed).
+///
+/// Serialized as one JSON line terminated by `\n`; told apart from a
+/// [`ZcResponse`] by its `event` key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ZcEvent {
+    /// Event name (e.g. `"pin_change"`, `"threshold"`).
+    pub event: String,
+    /// Event payload — schema depends on the event.
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+impl ZcEvent {
+    /// Dispatch signal name, relative to the device: `pin_{n}` for pin changes
+    /// (matching the RPi GPIO watcher), `adc_{channel}` for threshold
+    /// crossings, otherwise the event name.
+    pub fn signal(&self) -> String {
+        let index = |key: &str| self.data.get(key).and_then(|v| v.as_u64());
+        match (self.event.as_str(), index("pin"), index("channel")) {
+            ("pin_change", Some(pin), _) => format!("pin_{pin}"),
+            ("threshold", _, Some(channel)) => format!("adc_{channel}"),
+            _ => self.event.clone(),
+        }
+    }
+
+    /// Dispatch payload: the bare `"0"`/`"1"` level for pin changes, the JSON
+    /// `data` object for everything else.
+    pub fn payload(&self) -> String {
+        match (self.event.as_str(), self.data.get("value")) {
+            ("pin_change", Some(value)) => value.to_string(),
+            _ => self.data.to_string(),
+        }
+    }
+}
+
+/// Any frame a device may send: a response to the pending command, or an event.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ZcFrame {
+    Event(ZcEvent),
+    Response(ZcResponse),
+}
+
+impl ZcFrame {
+    /// Parse one received line (or WebSocket message).
+    pub fn from_line(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line.trim())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +216,35 @@ mod tests {
         assert!(resp.data.is_null());
         assert!(resp.error.is_none());
     }
+
+    #[test]
+    fn zc_frame_tells_events_from_responses() {
+        let frame =
+            ZcFrame::from_line(r#"{"event":"pin_change","data":{"pin":9,"value":0}}"#).unwrap();
+        let ZcFrame::Event(event) = frame else {
+            panic!("expected event frame");
+        };
+        assert_eq!(event.signal(), "pin_9");
+        assert_eq!(event.payload(), "0");
+
+        let frame = ZcFrame::from_line(r#"{"ok":true,"data":{"value":1}}"#).unwrap();
+        assert!(matches!(frame, ZcFrame::Response(r) if r.ok));
+    }
+
+    #[test]
+    fn zc_event_threshold_signal_and_payload() {
+        let event = ZcEvent {
+            event: "threshold".into(),
+            data: json!({"channel": 0, "value": 2101, "direction": "rising"}),
+        };
+        assert_eq!(event.signal(), "adc_0");
+        let payload: serde_json::Value = serde_json::from_str(&event.payload()).unwrap();
+        assert_eq!(payload["direction"], "rising");
+
+        let other = ZcEvent {
+            event: "boot".into(),
+            data: serde_json::Value::Null,
+        };
+        assert_eq!(other.signal(), "boot");
+    }
 }
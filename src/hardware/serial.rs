@@ -11,13 +11,18 @@
 //! Device → Host:  {"ok":true,"data":{"pin":25,"value":1,"state":"HIGH"}}\n
 //! ```
 //!
+//! Event frames (`{"event":…}`) read while waiting for a response are passed
+//! to the attached [`EventSink`] and the read continues.
+//!
 //! All I/O is wrapped in `tokio::time::timeout` — no blocking reads.
 
 use super::{
-    protocol::{ZcCommand, ZcResponse},
+    events::EventSink,
+    protocol::{ZcCommand, ZcFrame, ZcResponse},
     transport::{Transport, TransportError, TransportKind},
 };
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio_serial::SerialPortBuilderExt;
 
@@ -42,6 +47,7 @@ use crate::util::is_serial_path_allowed as is_path_allowed;
 pub struct HardwareSerialTransport {
     port_path: String,
     baud_rate: u32,
+    events: Mutex<Option<EventSink>>,
 }
 
 impl HardwareSerialTransport {
@@ -52,6 +58,7 @@ impl HardwareSerialTransport {
         Self {
             port_path: port_path.into(),
             baud_rate,
+            events: Mutex::new(None),
         }
     }
 
@@ -80,7 +87,7 @@ impl HardwareSerialTransport {
         };
         let result = tokio::time::timeout(
             std::time::Duration::from_millis(PING_TIMEOUT_MS),
-            do_send(&self.port_path, self.baud_rate, &json),
+            do_send(&self.port_path, self.baud_rate, &json, None),
         )
         .await;
 
@@ -115,9 +122,10 @@ impl Transport for HardwareSerialTransport {
         // Log command name only — never log the full payload (may contain large or sensitive data).
         tracing::info!(port = %self.port_path, cmd = %cmd.cmd, "serial send");
 
+        let events = self.events.lock().clone();
         tokio::time::timeout(
             std::time::Duration::from_secs(SEND_TIMEOUT_SECS),
-            do_send(&self.port_path, self.baud_rate, &json),
+            do_send(&self.port_path, self.baud_rate, &json, events.as_ref()),
         )
         .await
         .map_err(|_| TransportError::Timeout(SEND_TIMEOUT_SECS))?
@@ -131,13 +139,24 @@ impl Transport for HardwareSerialTransport {
         // Lightweight connectivity check: the device file must exist.
         std::path::Path::new(&self.port_path).exists()
     }
+
+    fn set_event_sink(&self, sink: EventSink) {
+        *self.events.lock() = Some(sink);
+    }
 }
 
-/// Open the port, write the command, read one response line, return the parsed response.
+/// Open the port, write the command, read lines until the response, return it parsed.
+///
+/// Event frames read on the way are handed to `events` (or dropped without one).
 ///
 /// This is the inner function wrapped with `tokio::time::timeout` by the caller.
 /// Do NOT add a timeout here — the outer caller owns the deadline.
-async fn do_send(path: &str, baud: u32, json: &str) -> Result<ZcResponse, TransportError> {
+async fn do_send(
+    path: &str,
+    baud: u32,
+    json: &str,
+    events: Option<&EventSink>,
+) -> Result<ZcResponse, TransportError> {
     // Open port lazily — released when this function returns
     let mut port = tokio_serial::new(path, baud)
         .open_native_async()
@@ -160,28 +179,37 @@ async fn do_send(path: &str, baud: u32, json: &str) -> Result<ZcResponse, Transp
 
     // Read response line — port is moved into BufReader; write phase complete
     let mut reader = BufReader::new(port);
-    let mut response_line = String::new();
-    reader
-        .read_line(&mut response_line)
-        .await
-        .map_err(|e: std::io::Error| {
-            if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                TransportError::Disconnected
-            } else {
-                TransportError::Io(e)
-            }
-        })?;
+    loop {
+        let mut response_line = String::new();
+        reader
+            .read_line(&mut response_line)
+            .await
+            .map_err(|e: std::io::Error| {
+                if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                    TransportError::Disconnected
+                } else {
+                    TransportError::Io(e)
+                }
+            })?;
 
-    let trimmed = response_line.trim();
-    if trimmed.is_empty() {
-        return Err(TransportError::Protocol(
-            "empty response from device".to_string(),
-        ));
-    }
+        let trimmed = response_line.trim();
+        if trimmed.is_empty() {
+            return Err(TransportError::Protocol(
+                "empty response from device".to_string(),
+            ));
+        }
 
-    serde_json::from_str(trimmed).map_err(|e| {
-        TransportError::Protocol(format!("invalid JSON response: {e} — got: {trimmed:?}"))
-    })
+        match ZcFrame::from_line(trimmed).map_err(|e| {
+            TransportError::Protocol(format!("invalid JSON response: {e} — got: {trimmed:?}"))
+        })? {
+            ZcFrame::Response(response) => return Ok(response),
+            ZcFrame::Event(event) => {
+                if let Some(sink) = events {
+                    sink.emit(event);
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,726 @@
+//! Virtual board — an in-process device for exercising hardware tools without hardware.
+//!
+//! [`SimulatedTransport`] answers the same `ZcCommand`s as the firmware (GPIO,
+//! I2C, SPI, ADC, PWM/servo, watches, `capabilities`), so tools, capability
+//! discovery and device events can run in CI and on laptops. Boards come from
+//! `[[hardware.virtual_devices]]` (see
+//! [`DeviceRegistry::register_virtual_devices`]) or are built directly with
+//! [`SimulatedTransport::new`]; tests keep the `Arc` to script the board while
+//! tools run against it:
+//!
+//! - [`set_pin`](SimulatedTransport::set_pin) drives an input; watched pins push `pin_change`
+//! - [`set_waveform`](SimulatedTransport::set_waveform) replaces an ADC channel's signal
+//! - [`inject_fault`](SimulatedTransport::inject_fault) makes the next command fail
+//! - [`set_connected`](SimulatedTransport::set_connected) unplugs or replugs the board
+//! - [`commands`](SimulatedTransport::commands) returns everything the host sent
+//!
+//! The simulated I2C bus gives every address in `i2c_addresses` a 256-byte
+//! register file: `i2c_write` stores `bytes[1..]` starting at register
+//! `bytes[0]`, `i2c_read` reads from `register` (default 0). SPI is a loopback.
+//!
+//! [`DeviceRegistry::register_virtual_devices`]: super::device::DeviceRegistry::register_virtual_devices
+
+use super::events::EventSink;
+use super::protocol::{ZcCommand, ZcEvent, ZcResponse};
+use super::transport::{Transport, TransportError, TransportKind};
+use crate::config::{VirtualDeviceConfig, WaveformConfig};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+/// Seconds reported by injected timeouts (the serial transport's send timeout).
+const FAULT_TIMEOUT_SECS: u64 = 5;
+
+/// How often armed ADC watches are sampled.
+const WATCH_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Raw units a reading must move past a threshold before it is reported
+/// (same as the ESP32 firmware).
+const ADC_HYSTERESIS: u16 = 20;
+
+/// Full-scale ADC reading and the voltage it represents.
+const ADC_MAX: u16 = 4095;
+const ADC_FULL_SCALE_MV: u64 = 3300;
+
+/// A one-shot failure for the next command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fault {
+    /// The command times out.
+    Timeout,
+    /// The device drops off mid-command; later commands succeed again.
+    Disconnect,
+    /// The device answers with a line that is not valid JSON.
+    Garbage,
+    /// The device answers `ok:false` with this message.
+    DeviceError(String),
+}
+
+/// An armed ADC threshold watch.
+struct AdcWatch {
+    threshold: u16,
+    above: bool,
+}
+
+/// Everything the simulated device remembers.
+struct Board {
+    started: Instant,
+    connected: bool,
+    gpio: BTreeSet<u8>,
+    pins: BTreeMap<u8, u8>,
+    adc: Vec<WaveformConfig>,
+    i2c: BTreeMap<u8, Vec<u8>>,
+    pwm: BTreeMap<u8, f64>,
+    servo: BTreeMap<u8, u64>,
+    failing: HashSet<String>,
+    faults: VecDeque<Fault>,
+    log: Vec<ZcCommand>,
+    pin_watches: BTreeSet<u8>,
+    adc_watches: BTreeMap<usize, AdcWatch>,
+}
+
+/// A virtual board implementing [`Transport`].
+pub struct SimulatedTransport {
+    board: Arc<Mutex<Board>>,
+    latency: Duration,
+    fault_rate: f64,
+    events: Arc<Mutex<Option<EventSink>>>,
+    watcher_started: AtomicBool,
+}
+
+impl SimulatedTransport {
+    /// Build a board from its config entry.
+    ///
+    /// Pin keys that are not numbers are ignored (`Config::validate` rejects them).
+    pub fn new(config: &VirtualDeviceConfig) -> Self {
+        let pins = config
+            .pins
+            .iter()
+            .filter_map(|(pin, level)| Some((pin.parse().ok()?, u8::from(*level != 0))))
+            .collect();
+        let board = Board {
+            started: Instant::now(),
+            connected: true,
+            gpio: config.gpio.iter().copied().collect(),
+            pins,
+            adc: config.adc.clone(),
+            i2c: config
+                .i2c_addresses
+                .iter()
+                .map(|address| (*address, vec![0; 256]))
+                .collect(),
+            pwm: BTreeMap::new(),
+            servo: BTreeMap::new(),
+            failing: config.failing_commands.iter().cloned().collect(),
+            faults: VecDeque::new(),
+            log: Vec::new(),
+            pin_watches: BTreeSet::new(),
+            adc_watches: BTreeMap::new(),
+        };
+        Self {
+            board: Arc::new(Mutex::new(board)),
+            latency: Duration::from_millis(config.latency_ms),
+            fault_rate: config.fault_rate,
+            events: Arc::new(Mutex::new(None)),
+            watcher_started: AtomicBool::new(false),
+        }
+    }
+
+    /// The `data` this board answers to `capabilities`.
+    pub fn capabilities(&self) -> Value {
+        self.board.lock().capabilities()
+    }
+
+    /// Drive a pin to `level` from outside, as a button or sensor would.
+    pub fn set_pin(&self, pin: u8, level: u8) {
+        let level = u8::from(level != 0);
+        let event = {
+            let mut board = self.board.lock();
+            let previous = board.pins.insert(pin, level).unwrap_or(0);
+            (previous != level && board.pin_watches.contains(&pin)).then(|| ZcEvent {
+                event: "pin_change".into(),
+                data: json!({ "pin": pin, "value": level }),
+            })
+        };
+        if let Some(event) = event {
+            self.emit(event);
+        }
+    }
+
+    /// Current level of a pin, if it was ever written or driven.
+    pub fn pin(&self, pin: u8) -> Option<u8> {
+        self.board.lock().pins.get(&pin).copied()
+    }
+
+    /// Last duty cycle (percent) written to a PWM pin.
+    pub fn pwm_duty(&self, pin: u8) -> Option<f64> {
+        self.board.lock().pwm.get(&pin).copied()
+    }
+
+    /// Last angle written to a servo pin.
+    pub fn servo_angle(&self, pin: u8) -> Option<u64> {
+        self.board.lock().servo.get(&pin).copied()
+    }
+
+    /// Replace the signal on an ADC channel, adding channels (at 0) as needed.
+    pub fn set_waveform(&self, channel: usize, waveform: WaveformConfig) {
+        let mut board = self.board.lock();
+        if board.adc.len() <= channel {
+            board
+                .adc
+                .resize(channel + 1, WaveformConfig::Constant { value: 0 });
+        }
+        board.adc[channel] = waveform;
+    }
+
+    /// Queue a failure; each queued fault affects exactly one command.
+    pub fn inject_fault(&self, fault: Fault) {
+        self.board.lock().faults.push_back(fault);
+    }
+
+    /// Unplug (`false`) or replug (`true`) the board.
+    pub fn set_connected(&self, connected: bool) {
+        self.board.lock().connected = connected;
+    }
+
+    /// Every command received so far, oldest first.
+    pub fn commands(&self) -> Vec<ZcCommand> {
+        self.board.lock().log.clone()
+    }
+
+    fn emit(&self, event: ZcEvent) {
+        if let Some(sink) = self.events.lock().as_ref() {
+            sink.emit(event);
+        }
+    }
+
+    /// Sample armed ADC watches in the background while the board exists.
+    fn spawn_watcher(&self) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::debug!("simulated device: no runtime, ADC watches will not fire");
+            return;
+        };
+        if self.watcher_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let board: Weak<Mutex<Board>> = Arc::downgrade(&self.board);
+        let events = Arc::clone(&self.events);
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(WATCH_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(board) = board.upgrade() else {
+                    break;
+                };
+                let fired = board.lock().poll_adc_watches();
+                if let Some(sink) = events.lock().as_ref() {
+                    for event in fired {
+                        sink.emit(event);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl Transport for SimulatedTransport {
+    async fn send(&self, cmd: &ZcCommand) -> Result<ZcResponse, TransportError> {
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+
+        let mut board = self.board.lock();
+        board.log.push(cmd.clone());
+        if !board.connected {
+            return Err(TransportError::Disconnected);
+        }
+        let fault = board.faults.pop_front().or_else(|| {
+            (self.fault_rate > 0.0 && rand::random::<f64>() < self.fault_rate)
+                .then_some(Fault::Timeout)
+        });
+        match fault {
+            Some(Fault::Timeout) => Err(TransportError::Timeout(FAULT_TIMEOUT_SECS)),
+            Some(Fault::Disconnect) => Err(TransportError::Disconnected),
+            Some(Fault::Garbage) => Err(TransportError::Protocol(
+                "invalid JSON from simulated device".into(),
+            )),
+            Some(Fault::DeviceError(message)) => Ok(ZcResponse::error(message)),
+            None => Ok(board.execute(cmd)),
+        }
+    }
+
+    fn kind(&self) -> TransportKind {
+        TransportKind::Simulated
+    }
+
+    fn is_connected(&self) -> bool {
+        self.board.lock().connected
+    }
+
+    fn set_event_sink(&self, sink: EventSink) {
+        *self.events.lock() = Some(sink);
+        self.spawn_watcher();
+    }
+}
+
+impl Board {
+    fn capabilities(&self) -> Value {
+        json!({
+            "gpio": self.gpio,
+            "adc": (0..self.adc.len()).collect::<Vec<_>>(),
+            "pwm": self.gpio,
+            "i2c": true,
+            "spi": true,
+            "firmware": "zeroclaw-sim"
+        })
+    }
+
+    fn read_adc(&self, channel: usize) -> Option<u16> {
+        self.adc
+            .get(channel)
+            .map(|waveform| sample(waveform, self.started.elapsed()))
+    }
+
+    fn poll_adc_watches(&mut self) -> Vec<ZcEvent> {
+        let mut fired = Vec::new();
+        let readings: Vec<(usize, u16)> = self
+            .adc_watches
+            .keys()
+            .filter_map(|&channel| Some((channel, self.read_adc(channel)?)))
+            .collect();
+        for (channel, value) in readings {
+            let Some(watch) = self.adc_watches.get_mut(&channel) else {
+                continue;
+            };
+            let direction =
+                if !watch.above && value >= watch.threshold.saturating_add(ADC_HYSTERESIS) {
+                    "rising"
+                } else if watch.above && value.saturating_add(ADC_HYSTERESIS) <= watch.threshold {
+                    "falling"
+                } else {
+                    continue;
+                };
+            watch.above = !watch.above;
+            fired.push(ZcEvent {
+                event: "threshold".into(),
+                data: json!({
+                    "channel": channel,
+                    "value": value,
+                    "threshold": watch.threshold,
+                    "direction": direction
+                }),
+            });
+        }
+        fired
+    }
+
+    fn execute(&mut self, cmd: &ZcCommand) -> ZcResponse {
+        if self.failing.contains(&cmd.cmd) {
+            return ZcResponse::error(format!("simulated failure: {}", cmd.cmd));
+        }
+        let result = match cmd.cmd.as_str() {
+            "ping" => Ok(json!({ "firmware": "zeroclaw-sim" })),
+            "capabilities" => Ok(self.capabilities()),
+            "gpio_read" => self.gpio_read(&cmd.params),
+            "gpio_write" => self.gpio_write(&cmd.params),
+            "adc_read" => self.adc_read(&cmd.params),
+            "i2c_scan" => Ok(json!({ "addresses": self.i2c.keys().collect::<Vec<_>>() })),
+            "i2c_read" => self.i2c_read(&cmd.params),
+            "i2c_write" => self.i2c_write(&cmd.params),
+            "spi_transfer" => bytes(&cmd.params).map(|bytes| json!({ "bytes": bytes })),
+            "pwm_write" => self.pwm_write(&cmd.params),
+            "servo_write" => self.servo_write(&cmd.params),
+            "gpio_watch" => self.gpio_watch(&cmd.params),
+            "adc_watch" => self.adc_watch(&cmd.params),
+            other => Err(format!("unknown command: {other}")),
+        };
+        match result {
+            Ok(data) => ZcResponse::success(data),
+            Err(message) => ZcResponse::error(message),
+        }
+    }
+
+    fn gpio_pin(&self, params: &Value) -> Result<u8, String> {
+        let pin = int(params, "pin")?;
+        u8::try_from(pin)
+            .ok()
+            .filter(|pin| self.gpio.contains(pin))
+            .ok_or_else(|| format!("pin {pin} not available"))
+    }
+
+    fn gpio_read(&self, params: &Value) -> Result<Value, String> {
+        let pin = self.gpio_pin(params)?;
+        let value = self.pins.get(&pin).copied().unwrap_or(0);
+        Ok(json!({ "pin": pin, "value": value, "state": level_name(value) }))
+    }
+
+    fn gpio_write(&mut self, params: &Value) -> Result<Value, String> {
+        let pin = self.gpio_pin(params)?;
+        let value = u8::from(int(params, "value")? != 0);
+        self.pins.insert(pin, value);
+        Ok(json!({ "pin": pin, "value": value, "state": level_name(value) }))
+    }
+
+    fn adc_read(&self, params: &Value) -> Result<Value, String> {
+        let channel = int(params, "channel")?;
+        let value = usize::try_from(channel)
+            .ok()
+            .and_then(|channel| self.read_adc(channel))
+            .ok_or_else(|| format!("ADC channel {channel} not configured"))?;
+        Ok(json!({
+            "channel": channel,
+            "value": value,
+            "millivolts": u64::from(value) * ADC_FULL_SCALE_MV / u64::from(ADC_MAX)
+        }))
+    }
+
+    fn i2c_registers(&mut self, params: &Value) -> Result<&mut Vec<u8>, String> {
+        let address = int(params, "address")?;
+        u8::try_from(address)
+            .ok()
+            .and_then(|address| self.i2c.get_mut(&address))
+            .ok_or_else(|| format!("no ACK from I2C address 0x{address:02X}"))
+    }
+
+    fn i2c_read(&mut self, params: &Value) -> Result<Value, String> {
+        let register = params.get("register").and_then(Value::as_u64).unwrap_or(0);
+        let length = int(params, "length")?;
+        let registers = self.i2c_registers(params)?;
+        let bytes: Vec<u8> = (register..register + length)
+            .map(|r| registers[(r % 256) as usize])
+            .collect();
+        Ok(json!({ "bytes": bytes }))
+    }
+
+    fn i2c_write(&mut self, params: &Value) -> Result<Value, String> {
+        let bytes = bytes(params)?;
+        let registers = self.i2c_registers(params)?;
+        if let Some((&start, data)) = bytes.split_first() {
+            for (offset, byte) in data.iter().enumerate() {
+                registers[(usize::from(start) + offset) % 256] = *byte;
+            }
+        }
+        Ok(json!({ "written": bytes.len() }))
+    }
+
+    fn pwm_write(&mut self, params: &Value) -> Result<Value, String> {
+        let pin = self.gpio_pin(params)?;
+        let duty = params
+            .get("duty")
+            .and_then(Value::as_f64)
+            .ok_or("missing duty")?;
+        self.pwm.insert(pin, duty);
+        Ok(json!({ "pin": pin, "duty": duty }))
+    }
+
+    fn servo_write(&mut self, params: &Value) -> Result<Value, String> {
+        let pin = self.gpio_pin(params)?;
+        let angle = int(params, "angle")?;
+        self.servo.insert(pin, angle);
+        Ok(json!({ "pin": pin, "angle": angle }))
+    }
+
+    fn gpio_watch(&mut self, params: &Value) -> Result<Value, String> {
+        let pin = self.gpio_pin(params)?;
+        if enabled(params) {
+            self.pin_watches.insert(pin);
+        } else {
+            self.pin_watches.remove(&pin);
+        }
+        Ok(json!({}))
+    }
+
+    fn adc_watch(&mut self, params: &Value) -> Result<Value, String> {
+        let channel = usize::try_from(int(params, "channel")?).unwrap_or(usize::MAX);
+        let value = self
+            .read_adc(channel)
+            .ok_or_else(|| format!("ADC channel {channel} not configured"))?;
+        if enabled(params) {
+            let threshold = u16::try_from(int(params, "threshold")?)
+                .map_err(|_| "threshold out of range".to_string())?;
+            self.adc_watches.insert(
+                channel,
+                AdcWatch {
+                    threshold,
+                    above: value >= threshold,
+                },
+            );
+        } else {
+            self.adc_watches.remove(&channel);
+        }
+        Ok(json!({}))
+    }
+}
+
+/// The value of `waveform` at `elapsed` since the board started.
+fn sample(waveform: &WaveformConfig, elapsed: Duration) -> u16 {
+    let (min, max, period_ms) = match *waveform {
+        WaveformConfig::Constant { value } => return value,
+        WaveformConfig::Sine {
+            min,
+            max,
+            period_ms,
+        }
+        | WaveformConfig::Square {
+            min,
+            max,
+            period_ms,
+        }
+        | WaveformConfig::Ramp {
+            min,
+            max,
+            period_ms,
+        } => (min, max, period_ms.max(1)),
+    };
+    let elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+    let phase = (elapsed_ms % period_ms) as f64 / period_ms as f64;
+    let span = f64::from(max.saturating_sub(min));
+    let offset = match waveform {
+        // Starts at `min` and peaks half-way through the period.
+        WaveformConfig::Sine { .. } => span * (1.0 - (phase * std::f64::consts::TAU).cos()) / 2.0,
+        WaveformConfig::Square { .. } if phase >= 0.5 => span,
+        WaveformConfig::Ramp { .. } => span * phase,
+        _ => 0.0,
+    };
+    // `offset` is within 0..=span, so it always fits in a u16.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let offset = offset.round() as u16;
+    min.saturating_add(offset)
+}
+
+fn level_name(value: u8) -> &'static str {
+    if value == 0 {
+        "LOW"
+    } else {
+        "HIGH"
+    }
+}
+
+fn int(params: &Value, key: &str) -> Result<u64, String> {
+    params
+        .get(key)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| format!("missing {key}"))
+}
+
+fn enabled(params: &Value) -> bool {
+    params
+        .get("enabled")
+        .and_then(Value::as_bool)
+        .unwrap_or(true)
+}
+
+fn bytes(params: &Value) -> Result<Vec<u8>, String> {
+    params
+        .get("bytes")
+        .and_then(Value::as_array)
+        .ok_or("missing bytes")?
+        .iter()
+        .map(|b| {
+            b.as_u64()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| "bytes must be 0-255".to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::device::{DeviceCapabilities, DeviceRegistry};
+    use crate::hardware::events::DeviceEvent;
+    use crate::hardware::gpio::{GpioReadTool, GpioWriteTool};
+    use crate::tools::traits::Tool;
+    use tokio::sync::{mpsc, RwLock};
+
+    fn registry(sim: &Arc<SimulatedTransport>) -> Arc<RwLock<DeviceRegistry>> {
+        let mut caps = DeviceCapabilities::default();
+        caps.merge_response(&sim.capabilities());
+        let mut reg = DeviceRegistry::new();
+        let alias = reg.register("sim", None, None, None, None);
+        reg.attach_transport(&alias, sim.clone() as Arc<dyn Transport>, caps)
+            .unwrap();
+        Arc::new(RwLock::new(reg))
+    }
+
+    #[test]
+    fn waveforms_sample_over_their_period() {
+        let sine = WaveformConfig::Sine {
+            min: 100,
+            max: 300,
+            period_ms: 1000,
+        };
+        assert_eq!(sample(&sine, Duration::ZERO), 100);
+        assert_eq!(sample(&sine, Duration::from_millis(500)), 300);
+        assert_eq!(sample(&sine, Duration::from_millis(1250)), 200);
+
+        let square = WaveformConfig::Square {
+            min: 0,
+            max: 4095,
+            period_ms: 100,
+        };
+        assert_eq!(sample(&square, Duration::from_millis(10)), 0);
+        assert_eq!(sample(&square, Duration::from_millis(60)), 4095);
+
+        let ramp = WaveformConfig::Ramp {
+            min: 0,
+            max: 1000,
+            period_ms: 100,
+        };
+        assert_eq!(sample(&ramp, Duration::from_millis(25)), 250);
+        assert_eq!(
+            sample(&WaveformConfig::Constant { value: 7 }, Duration::ZERO),
+            7
+        );
+    }
+
+    #[tokio::test]
+    async fn gpio_tools_run_against_the_simulator() {
+        let sim = Arc::new(SimulatedTransport::new(&VirtualDeviceConfig {
+            pins: [("9".to_string(), 1)].into(),
+            ..VirtualDeviceConfig::default()
+        }));
+        let reg = registry(&sim);
+
+        let result = GpioReadTool::new(reg.clone())
+            .execute(json!({"device": "sim0", "pin": 9}))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+        assert!(result.output.contains("HIGH"), "{}", result.output);
+
+        let result = GpioWriteTool::new(reg)
+            .execute(json!({"device": "sim0", "pin": 13, "value": 1}))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+        assert_eq!(sim.pin(13), Some(1));
+        assert_eq!(sim.commands().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn capability_discovery_reflects_config() {
+        let sim = SimulatedTransport::new(&VirtualDeviceConfig {
+            adc: vec![WaveformConfig::Constant { value: 2048 }],
+            ..VirtualDeviceConfig::default()
+        });
+        let resp = sim.send(&ZcCommand::simple("capabilities")).await.unwrap();
+        let mut caps = DeviceCapabilities::default();
+        caps.merge_response(&resp.data);
+        assert!(caps.gpio && caps.adc && caps.pwm && caps.i2c && caps.spi);
+
+        let resp = sim
+            .send(&ZcCommand::new("adc_read", json!({"channel": 0})))
+            .await
+            .unwrap();
+        assert_eq!(resp.data["value"], 2048);
+        assert_eq!(resp.data["millivolts"], 1650);
+    }
+
+    #[tokio::test]
+    async fn i2c_register_file_round_trips() {
+        let sim = SimulatedTransport::new(&VirtualDeviceConfig {
+            i2c_addresses: vec![0x3c],
+            ..VirtualDeviceConfig::default()
+        });
+        sim.send(&ZcCommand::new(
+            "i2c_write",
+            json!({"address": 0x3c, "bytes": [0x10, 0xAA, 0xBB]}),
+        ))
+        .await
+        .unwrap();
+        let resp = sim
+            .send(&ZcCommand::new(
+                "i2c_read",
+                json!({"address": 0x3c, "register": 0x10, "length": 2}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.data["bytes"], json!([0xAA, 0xBB]));
+
+        let resp = sim
+            .send(&ZcCommand::new(
+                "i2c_read",
+                json!({"address": 0x50, "length": 1}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.error.as_deref(), Some("no ACK from I2C address 0x50"));
+    }
+
+    #[tokio::test]
+    async fn faults_fail_exactly_one_command() {
+        let sim = SimulatedTransport::new(&VirtualDeviceConfig {
+            failing_commands: vec!["spi_transfer".into()],
+            ..VirtualDeviceConfig::default()
+        });
+        let ping = ZcCommand::simple("ping");
+
+        sim.inject_fault(Fault::Timeout);
+        sim.inject_fault(Fault::DeviceError("brownout".into()));
+        assert!(matches!(
+            sim.send(&ping).await,
+            Err(TransportError::Timeout(_))
+        ));
+        let resp = sim.send(&ping).await.unwrap();
+        assert_eq!(resp.error.as_deref(), Some("brownout"));
+        assert!(sim.send(&ping).await.unwrap().ok);
+
+        sim.set_connected(false);
+        assert!(!sim.is_connected());
+        assert!(matches!(
+            sim.send(&ping).await,
+            Err(TransportError::Disconnected)
+        ));
+
+        sim.set_connected(true);
+        let resp = sim
+            .send(&ZcCommand::new("spi_transfer", json!({"bytes": [1]})))
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.error.as_deref(),
+            Some("simulated failure: spi_transfer")
+        );
+    }
+
+    #[tokio::test]
+    async fn watches_push_events() {
+        let sim = SimulatedTransport::new(&VirtualDeviceConfig {
+            adc: vec![WaveformConfig::Constant { value: 100 }],
+            ..VirtualDeviceConfig::default()
+        });
+        let (tx, mut rx) = mpsc::unbounded_channel::<DeviceEvent>();
+        sim.set_event_sink(EventSink::new("sim0", tx));
+
+        sim.send(&ZcCommand::new(
+            "gpio_watch",
+            json!({"pin": 9, "enabled": true}),
+        ))
+        .await
+        .unwrap();
+        sim.set_pin(9, 1);
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.device, "sim0");
+        assert_eq!(event.event.signal(), "pin_9");
+        assert_eq!(event.event.payload(), "1");
+
+        sim.send(&ZcCommand::new(
+            "adc_watch",
+            json!({"channel": 0, "threshold": 2000}),
+        ))
+        .await
+        .unwrap();
+        sim.set_waveform(0, WaveformConfig::Constant { value: 3000 });
+        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.event.signal(), "adc_0");
+        assert_eq!(event.event.data["direction"], "rising");
+    }
+}
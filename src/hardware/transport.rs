@@ -5,7 +5,10 @@
 //! - `SWDTransport` — memory read/write via probe-rs (Phase 7)
 //! - `UF2Transport` — firmware flashing via UF2 mass storage (Phase 6)
 //! - `NativeTransport` — direct Linux GPIO/I2C/SPI via rppal/sysfs (later)
+//! - `network::NetworkTransport` — the same JSON protocol over TCP or WebSocket (Wi-Fi boards)
+//! - `simulator::SimulatedTransport` — an in-process virtual board for tests
 
+use super::events::EventSink;
 use super::protocol::{ZcCommand, ZcResponse};
 use async_trait::async_trait;
 use thiserror::Error;
@@ -48,6 +51,10 @@ pub enum TransportKind {
     Uf2,
     /// Direct Linux GPIO/I2C/SPI (rppal, sysfs).
     Native,
+    /// Newline-delimited JSON over TCP or WebSocket.
+    Network,
+    /// In-process virtual board (no hardware).
+    Simulated,
 }
 
 impl std::fmt::Display for TransportKind {
@@ -57,6 +64,8 @@ impl std::fmt::Display for TransportKind {
             Self::Swd => write!(f, "swd"),
             Self::Uf2 => write!(f, "uf2"),
             Self::Native => write!(f, "native"),
+            Self::Network => write!(f, "network"),
+            Self::Simulated => write!(f, "simulated"),
         }
     }
 }
@@ -75,6 +84,11 @@ pub trait Transport: Send + Sync {
 
     /// Whether the transport is currently connected to a device.
     fn is_connected(&self) -> bool;
+
+    /// Where to publish unsolicited event frames read from the device.
+    ///
+    /// The default drops them — transports that can receive events override this.
+    fn set_event_sink(&self, _sink: EventSink) {}
 }
 
 #[cfg(test)]
@@ -87,6 +101,8 @@ mod tests {
         assert_eq!(TransportKind::Swd.to_string(), "swd");
         assert_eq!(TransportKind::Uf2.to_string(), "uf2");
         assert_eq!(TransportKind::Native.to_string(), "native");
+        assert_eq!(TransportKind::Network.to_string(), "network");
+        assert_eq!(TransportKind::Simulated.to_string(), "simulated");
     }
 
     #[test]
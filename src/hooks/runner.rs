@@ -30,7 +30,8 @@ impl HookRunner {
         }
     }
 
-    /// Build a hook runner from configuration, registering enabled built-in hooks.
+    /// Build a hook runner from configuration, registering enabled built-in hooks
+    /// and the hooks of loaded WASM component and process plugins.
     ///
     /// Returns `None` if hooks are disabled in config.
     pub fn from_config(config: &HooksConfig) -> Option<Self> {
@@ -47,6 +48,11 @@ impl HookRunner {
         if config.builtin.session_memory {
             runner.register(Box::new(super::builtin::SessionMemoryHook));
         }
+        for backend in crate::plugins::runtime::plugin_backends() {
+            for handler in crate::plugins::host::plugin_hooks(&backend) {
+                runner.register(handler);
+            }
+        }
         Some(runner)
     }
 
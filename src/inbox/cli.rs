@@ -0,0 +1,109 @@
+use super::{HumanDecision, HumanTask};
+use crate::config::Config;
+use anyhow::{Context, Result};
+
+/// Handle `zeroclaw inbox <subcommand>` CLI commands.
+pub fn handle_command(command: crate::InboxCommands, config: &Config) -> Result<()> {
+    let inbox = super::open(config)?;
+    match command {
+        crate::InboxCommands::List { all, limit, json } => {
+            let tasks = inbox.list(all, limit)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&tasks)?);
+            } else if tasks.is_empty() {
+                println!("No pending tasks.");
+            } else {
+                for task in &tasks {
+                    print_row(task);
+                }
+            }
+            Ok(())
+        }
+        crate::InboxCommands::Show { id } => {
+            let task = inbox
+                .get(&id)?
+                .with_context(|| format!("Inbox task {id} not found"))?;
+            print_task(&task);
+            Ok(())
+        }
+        crate::InboxCommands::Approve { id, comment } => {
+            resolve(&inbox, &id, HumanDecision::Approve, comment.as_deref())
+        }
+        crate::InboxCommands::Reject { id, reason } => {
+            resolve(&inbox, &id, HumanDecision::Reject, reason.as_deref())
+        }
+        crate::InboxCommands::Answer { id, answer } => {
+            resolve(&inbox, &id, HumanDecision::Answer, Some(&answer.join(" ")))
+        }
+        crate::InboxCommands::Cancel { id } => {
+            let task = inbox.cancel(&id, &cli_user())?;
+            println!("Cancelled {}: {}", task.id, task.title);
+            Ok(())
+        }
+    }
+}
+
+fn resolve(
+    inbox: &super::HumanInbox,
+    id: &str,
+    decision: HumanDecision,
+    response: Option<&str>,
+) -> Result<()> {
+    let task = inbox.resolve(id, decision, response, &cli_user(), "cli")?;
+    println!("{} {}: {}", task.id, task.status, task.title);
+    Ok(())
+}
+
+fn cli_user() -> String {
+    std::env::var("USER")
+        .ok()
+        .filter(|user| !user.trim().is_empty())
+        .unwrap_or_else(|| "cli".to_string())
+}
+
+fn print_row(task: &HumanTask) {
+    let due = task.deadline.map_or_else(
+        || "no deadline".to_string(),
+        |deadline| format!("due {}", deadline.format("%Y-%m-%d %H:%M")),
+    );
+    println!(
+        "{}  {:<16}  {:<9}  {:<24}  {due}  {}",
+        task.id,
+        task.kind.as_str(),
+        task.status.as_str(),
+        task.current_assignee().unwrap_or("-"),
+        task.title
+    );
+}
+
+fn print_task(task: &HumanTask) {
+    println!("{} ({}) — {}", task.id, task.kind.as_str(), task.title);
+    println!("  Status:     {}", task.status);
+    println!("  Assignee:   {}", task.current_assignee().unwrap_or("-"));
+    if let Some(to) = &task.escalate_to {
+        let state = if task.escalated { "escalated" } else { "at" };
+        let at = task
+            .escalate_at
+            .map_or_else(String::new, |at| format!(" {}", at.to_rfc3339()));
+        println!("  Escalation: {to} ({state}{at})");
+    }
+    println!("  Waiting:    {}", task.waiter.label());
+    println!("  Created:    {}", task.created_at.to_rfc3339());
+    if let Some(deadline) = task.deadline {
+        println!("  Deadline:   {}", deadline.to_rfc3339());
+    }
+    if let Some(resolved_at) = task.resolved_at {
+        println!(
+            "  Resolved:   {} by {} via {}",
+            resolved_at.to_rfc3339(),
+            task.resolved_by.as_deref().unwrap_or("-"),
+            task.resolved_via.as_deref().unwrap_or("-")
+        );
+    }
+    if let Some(response) = &task.response {
+        println!("  Response:   {response}");
+    }
+    if !task.body.trim().is_empty() {
+        println!("\n{}", task.body.trim());
+    }
+}
@@ -0,0 +1,279 @@
+//! Human-in-the-loop task inbox.
+//!
+//! Tool approvals, workflow `user_confirm` steps, questions from agents and
+//! draft reviews become [`HumanTask`]s in one persistent inbox
+//! (`{workspace}/inbox/tasks.db`). Tasks carry an assignee, a deadline,
+//! reminders and escalation rules from `[inbox]`. They can be resolved from
+//! any linked channel (`/inbox-approve`, `/inbox-answer`, ...), the gateway
+//! (`/api/inbox`) or `zeroclaw inbox`, and whoever waits on a task — an
+//! agent turn, a cron job or a workflow run — picks the outcome up from here.
+
+pub mod cli;
+mod store;
+mod types;
+
+#[allow(unused_imports)]
+pub use store::HumanInbox;
+#[allow(unused_imports)]
+pub use types::{
+    HumanDecision, HumanTask, HumanTaskKind, HumanTaskStatus, InboxEvent, InboxNotice,
+    NewHumanTask, TaskWaiter,
+};
+
+use crate::approval::{ApprovalResponse, PendingNonCliApprovalRequest};
+use crate::config::schema::InboxConfig;
+use crate::config::Config;
+use crate::observability::runtime_trace;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+const NOTIFIER_COMPONENT: &str = "inbox";
+
+static INBOX: LazyLock<RwLock<Option<Arc<HumanInbox>>>> = LazyLock::new(|| RwLock::new(None));
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("inbox").join("tasks.db")
+}
+
+/// Open the workspace inbox.
+pub fn open(config: &Config) -> Result<HumanInbox> {
+    HumanInbox::open(&db_path(&config.workspace_dir), config.inbox.clone())
+}
+
+/// Open the process-wide inbox (or clear it when the inbox is disabled).
+pub fn init_from_config(config: &InboxConfig, workspace_dir: &Path) {
+    let inbox = if config.enabled {
+        match HumanInbox::open(&db_path(workspace_dir), config.clone()) {
+            Ok(inbox) => Some(Arc::new(inbox)),
+            Err(error) => {
+                tracing::warn!("Human task inbox disabled: failed to open storage: {error}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut guard = INBOX.write().unwrap_or_else(|e| e.into_inner());
+    *guard = inbox;
+}
+
+/// The process-wide inbox, if enabled.
+pub fn global() -> Option<Arc<HumanInbox>> {
+    INBOX.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// ── Tool approvals ───────────────────────────────────────────────
+
+/// Mirror a pending non-CLI tool approval into the inbox so it can also be
+/// answered from the CLI or dashboard. The requester was already prompted
+/// in their chat, so no separate notification goes out.
+pub fn mirror_tool_approval(request: &PendingNonCliApprovalRequest, arguments: &serde_json::Value) {
+    let Some(inbox) = global() else {
+        return;
+    };
+    let deadline_secs = chrono::DateTime::parse_from_rfc3339(&request.expires_at)
+        .ok()
+        .and_then(|expires| {
+            u64::try_from((expires.with_timezone(&Utc) - Utc::now()).num_seconds()).ok()
+        })
+        .filter(|secs| *secs > 0);
+    let task = NewHumanTask::new(
+        HumanTaskKind::ToolApproval,
+        format!("Approve `{}`", request.tool_name),
+    )
+    .with_body(crate::util::truncate_with_ellipsis(
+        &arguments.to_string(),
+        1000,
+    ))
+    .with_assignee(Some(format!(
+        "{}:{}",
+        request.requested_channel, request.requested_reply_target
+    )))
+    .with_waiter(TaskWaiter::Agent {
+        channel: request.requested_channel.clone(),
+        user: request.requested_by.clone(),
+    })
+    .with_external_ref(request.request_id.clone())
+    .with_created_by(format!(
+        "{}:{}",
+        request.requested_channel, request.requested_by
+    ))
+    .with_deadline_secs(deadline_secs)
+    .already_notified();
+    if let Err(error) = inbox.create(task) {
+        tracing::warn!(
+            "Failed to mirror approval {} into the inbox: {error}",
+            request.request_id
+        );
+    }
+}
+
+/// The decision taken in the inbox on a mirrored approval, once there is one.
+pub fn approval_decision(request_id: &str) -> Option<ApprovalResponse> {
+    let task = global()?.find_by_ref(request_id).ok().flatten()?;
+    match task.status {
+        HumanTaskStatus::Pending => None,
+        HumanTaskStatus::Approved => Some(ApprovalResponse::Yes),
+        _ => Some(ApprovalResponse::No),
+    }
+}
+
+/// Close the mirrored task of an approval that was decided elsewhere.
+pub fn settle_approval(request_id: &str, decision: ApprovalResponse, via: &str) {
+    let Some(inbox) = global() else {
+        return;
+    };
+    let Ok(Some(task)) = inbox.find_by_ref(request_id) else {
+        return;
+    };
+    if !task.is_open() {
+        return;
+    }
+    let decision = match decision {
+        ApprovalResponse::Yes | ApprovalResponse::Always => HumanDecision::Approve,
+        ApprovalResponse::No => HumanDecision::Reject,
+    };
+    if let Err(error) = inbox.resolve(&task.id, decision, None, "approval", via) {
+        tracing::debug!("Failed to settle inbox task {}: {error}", task.id);
+    }
+}
+
+// ── Notifications ────────────────────────────────────────────────
+
+/// Daemon component: sweep the inbox for due notifications, reminders,
+/// escalations and expiries, deliver them to channel-bound assignees and
+/// reschedule cron jobs whose tasks were resolved.
+pub async fn run_notifier(config: Config) -> Result<()> {
+    let inbox = match global() {
+        Some(inbox) => inbox,
+        None => Arc::new(open(&config)?),
+    };
+    let poll = std::time::Duration::from_secs(config.inbox.poll_secs.max(5));
+    let mut interval = tokio::time::interval(poll);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    crate::health::mark_component_ok(NOTIFIER_COMPONENT);
+    loop {
+        interval.tick().await;
+        match notify_due(&config, &inbox).await {
+            Ok(()) => crate::health::mark_component_ok(NOTIFIER_COMPONENT),
+            Err(error) => {
+                crate::health::mark_component_error(NOTIFIER_COMPONENT, error.to_string());
+                tracing::warn!("Inbox sweep failed: {error}");
+            }
+        }
+    }
+}
+
+async fn notify_due(config: &Config, inbox: &HumanInbox) -> Result<()> {
+    for notice in inbox.sweep(Utc::now())? {
+        let task = &notice.task;
+        runtime_trace::record_event(
+            "inbox_task_notice",
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            Some(task.title.as_str()),
+            serde_json::json!({
+                "task_id": task.id,
+                "event": format!("{:?}", notice.event).to_ascii_lowercase(),
+                "assignee": task.current_assignee(),
+            }),
+        );
+        let Some((channel, target)) = task.current_assignee().and_then(|who| who.split_once(':'))
+        else {
+            continue;
+        };
+        if let Err(error) = crate::cron::scheduler::deliver_announcement(
+            config,
+            channel,
+            target,
+            &notice_message(&notice),
+        )
+        .await
+        {
+            tracing::warn!(
+                "Failed to notify {channel}:{target} about {}: {error}",
+                task.id
+            );
+        }
+    }
+
+    for job_id in inbox.take_cron_resumptions()? {
+        crate::cron::schedule_now(config, &job_id)
+            .with_context(|| format!("Failed to resume cron job {job_id}"))?;
+    }
+    Ok(())
+}
+
+fn notice_message(notice: &InboxNotice) -> String {
+    let task = &notice.task;
+    let mut message = match notice.event {
+        InboxEvent::Created => format!("📥 New {} task `{}`", kind_label(task.kind), task.id),
+        InboxEvent::Reminder => format!("⏰ Reminder: task `{}` is still waiting", task.id),
+        InboxEvent::Escalated => format!("⬆️ Escalated to you: task `{}`", task.id),
+        InboxEvent::Expired => {
+            return format!(
+                "⌛ Task `{}` expired without a response: {}",
+                task.id, task.title
+            )
+        }
+    };
+    let _ = write!(message, "\n{}", task.title);
+    if !task.body.trim().is_empty() {
+        let _ = write!(
+            message,
+            "\n\n{}",
+            crate::util::truncate_with_ellipsis(task.body.trim(), 800)
+        );
+    }
+    if let Some(deadline) = task.deadline {
+        let _ = write!(
+            message,
+            "\n\nDue: {}",
+            deadline.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+    let _ = write!(message, "\n{}", reply_hint(task));
+    message
+}
+
+/// How to resolve `task` from a chat.
+pub fn reply_hint(task: &HumanTask) -> String {
+    if task.kind == HumanTaskKind::Question {
+        format!("Reply with `/inbox-answer {} <answer>`.", task.id)
+    } else {
+        format!(
+            "Reply with `/inbox-approve {0} [comment]` or `/inbox-reject {0} [reason]`.",
+            task.id
+        )
+    }
+}
+
+fn kind_label(kind: HumanTaskKind) -> &'static str {
+    match kind {
+        HumanTaskKind::ToolApproval => "tool approval",
+        HumanTaskKind::WorkflowConfirm => "workflow confirmation",
+        HumanTaskKind::Question => "question",
+        HumanTaskKind::Review => "review",
+    }
+}
+
+/// Prompt section with the outcomes of tasks a cron job was waiting on.
+pub fn cron_resume_context(job_id: &str) -> Option<String> {
+    let tasks = global()?.take_resolved_for_cron(job_id).ok()?;
+    if tasks.is_empty() {
+        return None;
+    }
+    let mut context = String::from("[Resolved inbox tasks]\n");
+    for task in tasks {
+        let _ = writeln!(context, "- {}", task.outcome_summary());
+    }
+    Some(context)
+}
@@ -0,0 +1,497 @@
+use super::types::{
+    HumanDecision, HumanTask, HumanTaskKind, HumanTaskStatus, InboxEvent, InboxNotice,
+    NewHumanTask, TaskWaiter,
+};
+use crate::config::InboxConfig;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// How often [`HumanInbox::wait`] re-reads a task.
+const WAIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// SQLite-backed store of human tasks (`{workspace}/inbox/tasks.db`).
+///
+/// Each row keeps the task as JSON next to the columns it is looked up by,
+/// so the CLI, gateway and daemon can share the file across processes.
+pub struct HumanInbox {
+    conn: Mutex<Connection>,
+    config: InboxConfig,
+}
+
+impl std::fmt::Debug for HumanInbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HumanInbox")
+            .field("path", &self.conn.lock().path().map(str::to_string))
+            .finish_non_exhaustive()
+    }
+}
+
+impl HumanInbox {
+    pub fn open(path: &Path, config: InboxConfig) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create inbox directory: {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open inbox DB: {}", path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA busy_timeout = 5000;
+             CREATE TABLE IF NOT EXISTS human_tasks (
+                 id           TEXT PRIMARY KEY,
+                 status       TEXT NOT NULL,
+                 external_ref TEXT,
+                 cron_job     TEXT,
+                 created_at   TEXT NOT NULL,
+                 task         TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_human_tasks_status ON human_tasks(status);
+             CREATE INDEX IF NOT EXISTS idx_human_tasks_ref ON human_tasks(external_ref);
+             CREATE INDEX IF NOT EXISTS idx_human_tasks_cron ON human_tasks(cron_job);",
+        )
+        .context("Failed to initialize inbox schema")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            config,
+        })
+    }
+
+    /// Add a task, filling in the configured assignee, deadline, reminder
+    /// and escalation defaults.
+    pub fn create(&self, new: NewHumanTask) -> Result<HumanTask> {
+        if new.title.trim().is_empty() {
+            bail!("Inbox task title must not be empty");
+        }
+        let now = Utc::now();
+        let deadline_secs = new
+            .deadline_secs
+            .unwrap_or(self.config.default_deadline_secs);
+        let escalation = self.config.escalation.iter().find(|rule| {
+            rule.kinds.is_empty()
+                || rule
+                    .kinds
+                    .iter()
+                    .any(|kind| HumanTaskKind::from_name(kind) == Some(new.kind))
+        });
+        let task = HumanTask {
+            id: format!("hit-{}", &Uuid::new_v4().simple().to_string()[..8]),
+            kind: new.kind,
+            title: new.title.trim().to_string(),
+            body: new.body,
+            assignee: new
+                .assignee
+                .or_else(|| self.config.default_assignee.clone()),
+            escalate_to: escalation.map(|rule| rule.to.clone()),
+            escalate_at: escalation.map(|rule| now + secs(rule.after_secs)),
+            escalated: false,
+            status: HumanTaskStatus::Pending,
+            waiter: new.waiter,
+            external_ref: new.external_ref,
+            created_by: new.created_by,
+            created_at: now,
+            deadline: (deadline_secs > 0).then(|| now + secs(deadline_secs)),
+            next_reminder_at: (self.config.reminder_interval_secs > 0)
+                .then(|| now + secs(self.config.reminder_interval_secs)),
+            reminders_sent: 0,
+            notified: new.notified,
+            response: None,
+            resolved_by: None,
+            resolved_via: None,
+            resolved_at: None,
+            resume_requested: false,
+            consumed: false,
+        };
+        self.conn
+            .lock()
+            .execute(
+                "INSERT INTO human_tasks (id, status, external_ref, cron_job, created_at, task)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    task.id,
+                    task.status.as_str(),
+                    task.external_ref,
+                    cron_job(&task),
+                    task.created_at.to_rfc3339(),
+                    serde_json::to_string(&task)?,
+                ],
+            )
+            .context("Failed to insert inbox task")?;
+        Ok(task)
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<HumanTask>> {
+        self.query_one("SELECT task FROM human_tasks WHERE id = ?1", id)
+    }
+
+    /// The newest task mirroring `external_ref`.
+    pub fn find_by_ref(&self, external_ref: &str) -> Result<Option<HumanTask>> {
+        self.query_one(
+            "SELECT task FROM human_tasks WHERE external_ref = ?1
+             ORDER BY created_at DESC LIMIT 1",
+            external_ref,
+        )
+    }
+
+    /// Tasks newest first; only pending ones unless `include_closed`.
+    pub fn list(&self, include_closed: bool, limit: usize) -> Result<Vec<HumanTask>> {
+        let sql = if include_closed {
+            "SELECT task FROM human_tasks ORDER BY created_at DESC LIMIT ?1"
+        } else {
+            "SELECT task FROM human_tasks WHERE status = 'pending'
+             ORDER BY created_at DESC LIMIT ?1"
+        };
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params![i64::try_from(limit).unwrap_or(i64::MAX)], |row| {
+            row.get::<_, String>(0)
+        })?;
+        rows.map(|row| parse_task(&row?)).collect()
+    }
+
+    /// Record a human's response. Questions take an answer; every other
+    /// kind is approved or rejected, with `response` as optional comments.
+    pub fn resolve(
+        &self,
+        id: &str,
+        decision: HumanDecision,
+        response: Option<&str>,
+        resolved_by: &str,
+        via: &str,
+    ) -> Result<HumanTask> {
+        let mut task = self.open_task(id)?;
+        let response = response.map(str::trim).filter(|r| !r.is_empty());
+        task.status = match (task.kind, decision) {
+            (_, HumanDecision::Reject) => HumanTaskStatus::Rejected,
+            (HumanTaskKind::Question, HumanDecision::Answer | HumanDecision::Approve) => {
+                if response.is_none() {
+                    bail!("Task {id} is a question; an answer is required");
+                }
+                HumanTaskStatus::Answered
+            }
+            (_, HumanDecision::Approve) => HumanTaskStatus::Approved,
+            (kind, HumanDecision::Answer) => {
+                bail!(
+                    "Task {id} is a {} task; approve or reject it",
+                    kind.as_str()
+                )
+            }
+        };
+        task.response = response.map(str::to_string);
+        task.resolved_by = Some(resolved_by.to_string());
+        task.resolved_via = Some(via.to_string());
+        task.resolved_at = Some(Utc::now());
+        self.save(&task)?;
+        Ok(task)
+    }
+
+    pub fn cancel(&self, id: &str, cancelled_by: &str) -> Result<HumanTask> {
+        let mut task = self.open_task(id)?;
+        task.status = HumanTaskStatus::Cancelled;
+        task.resolved_by = Some(cancelled_by.to_string());
+        task.resolved_at = Some(Utc::now());
+        self.save(&task)?;
+        Ok(task)
+    }
+
+    /// Advance every pending task to `now`: expire those past their
+    /// deadline, escalate, and schedule first notifications and reminders.
+    /// Returns what the assignees should be told.
+    pub fn sweep(&self, now: DateTime<Utc>) -> Result<Vec<InboxNotice>> {
+        let mut notices = Vec::new();
+        for mut task in self.list(false, usize::MAX)? {
+            let event = if task.deadline.is_some_and(|deadline| deadline <= now) {
+                task.status = HumanTaskStatus::Expired;
+                task.resolved_at = Some(now);
+                Some(InboxEvent::Expired)
+            } else if !task.escalated && task.escalate_at.is_some_and(|at| at <= now) {
+                task.escalated = true;
+                task.notified = true;
+                task.next_reminder_at = self.next_reminder(now);
+                Some(InboxEvent::Escalated)
+            } else if !task.notified {
+                task.notified = true;
+                Some(InboxEvent::Created)
+            } else if task.reminders_sent < self.config.max_reminders
+                && task.next_reminder_at.is_some_and(|at| at <= now)
+            {
+                task.reminders_sent += 1;
+                task.next_reminder_at = self.next_reminder(now);
+                Some(InboxEvent::Reminder)
+            } else {
+                None
+            };
+            if let Some(event) = event {
+                self.save(&task)?;
+                notices.push(InboxNotice { event, task });
+            }
+        }
+        Ok(notices)
+    }
+
+    /// Expire `id` if its deadline has passed; returns the current task.
+    fn expire_if_due(&self, id: &str, now: DateTime<Utc>) -> Result<HumanTask> {
+        let mut task = self
+            .get(id)?
+            .with_context(|| format!("Inbox task {id} not found"))?;
+        if task.is_open() && task.deadline.is_some_and(|deadline| deadline <= now) {
+            task.status = HumanTaskStatus::Expired;
+            task.resolved_at = Some(now);
+            self.save(&task)?;
+        }
+        Ok(task)
+    }
+
+    /// Wait for `id` to leave the pending state. Returns the task as it
+    /// stands when it is resolved, `max_wait` elapses or `cancel` fires.
+    pub async fn wait(
+        &self,
+        id: &str,
+        max_wait: Option<std::time::Duration>,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<HumanTask> {
+        let started = std::time::Instant::now();
+        loop {
+            let task = self.expire_if_due(id, Utc::now())?;
+            if !task.is_open()
+                || max_wait.is_some_and(|max| started.elapsed() >= max)
+                || cancel.is_some_and(CancellationToken::is_cancelled)
+            {
+                return Ok(task);
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Cron jobs waiting on resolved tasks that have not been asked to run
+    /// again yet. Marks those tasks so each job is scheduled once.
+    pub fn take_cron_resumptions(&self) -> Result<Vec<String>> {
+        let tasks = self.query_all(
+            "SELECT task FROM human_tasks WHERE cron_job IS NOT NULL AND status != 'pending'",
+        )?;
+        let mut jobs = Vec::new();
+        for mut task in tasks {
+            if task.resume_requested || task.consumed {
+                continue;
+            }
+            task.resume_requested = true;
+            self.save(&task)?;
+            if let Some(job_id) = cron_job(&task) {
+                if !jobs.contains(&job_id) {
+                    jobs.push(job_id);
+                }
+            }
+        }
+        Ok(jobs)
+    }
+
+    /// Resolved tasks a cron job was waiting on, marked as consumed.
+    pub fn take_resolved_for_cron(&self, job_id: &str) -> Result<Vec<HumanTask>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT task FROM human_tasks WHERE cron_job = ?1 AND status != 'pending'
+             ORDER BY created_at",
+        )?;
+        let rows: Vec<String> = stmt
+            .query_map(params![job_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        drop(stmt);
+        drop(conn);
+
+        let mut taken = Vec::new();
+        for row in rows {
+            let mut task = parse_task(&row)?;
+            if task.consumed {
+                continue;
+            }
+            task.consumed = true;
+            self.save(&task)?;
+            taken.push(task);
+        }
+        Ok(taken)
+    }
+
+    fn next_reminder(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (self.config.reminder_interval_secs > 0)
+            .then(|| now + secs(self.config.reminder_interval_secs))
+    }
+
+    fn open_task(&self, id: &str) -> Result<HumanTask> {
+        let task = self.expire_if_due(id, Utc::now())?;
+        if !task.is_open() {
+            bail!("Inbox task {id} is already {}", task.status);
+        }
+        Ok(task)
+    }
+
+    fn save(&self, task: &HumanTask) -> Result<()> {
+        self.conn
+            .lock()
+            .execute(
+                "UPDATE human_tasks SET status = ?2, task = ?3 WHERE id = ?1",
+                params![task.id, task.status.as_str(), serde_json::to_string(task)?],
+            )
+            .context("Failed to update inbox task")?;
+        Ok(())
+    }
+
+    fn query_one(&self, sql: &str, arg: &str) -> Result<Option<HumanTask>> {
+        let row: Option<String> = self
+            .conn
+            .lock()
+            .query_row(sql, params![arg], |row| row.get(0))
+            .optional()?;
+        row.as_deref().map(parse_task).transpose()
+    }
+
+    fn query_all(&self, sql: &str) -> Result<Vec<HumanTask>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.map(|row| parse_task(&row?)).collect()
+    }
+}
+
+fn parse_task(json: &str) -> Result<HumanTask> {
+    serde_json::from_str(json).context("Corrupt inbox task")
+}
+
+fn cron_job(task: &HumanTask) -> Option<String> {
+    match &task.waiter {
+        TaskWaiter::Cron { job_id } => Some(job_id.clone()),
+        _ => None,
+    }
+}
+
+fn secs(value: u64) -> Duration {
+    Duration::seconds(i64::try_from(value).unwrap_or(i64::MAX / 1000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::InboxEscalationRule;
+    use tempfile::TempDir;
+
+    fn inbox(tmp: &TempDir, config: InboxConfig) -> HumanInbox {
+        HumanInbox::open(&tmp.path().join("tasks.db"), config).unwrap()
+    }
+
+    #[test]
+    fn question_requires_an_answer_and_resolves_once() {
+        let tmp = TempDir::new().unwrap();
+        let inbox = inbox(&tmp, InboxConfig::default());
+        let task = inbox
+            .create(NewHumanTask::new(HumanTaskKind::Question, "Which region?"))
+            .unwrap();
+        assert!(task.deadline.is_some());
+
+        assert!(inbox
+            .resolve(&task.id, HumanDecision::Answer, None, "alice", "cli")
+            .is_err());
+        let resolved = inbox
+            .resolve(
+                &task.id,
+                HumanDecision::Answer,
+                Some("eu-west"),
+                "alice",
+                "cli",
+            )
+            .unwrap();
+        assert_eq!(resolved.status, HumanTaskStatus::Answered);
+        assert_eq!(resolved.response.as_deref(), Some("eu-west"));
+
+        let err = inbox
+            .resolve(&task.id, HumanDecision::Reject, None, "bob", "cli")
+            .unwrap_err();
+        assert!(err.to_string().contains("already answered"));
+        assert!(inbox.list(false, 10).unwrap().is_empty());
+        assert_eq!(inbox.list(true, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn sweep_notifies_escalates_reminds_and_expires() {
+        let tmp = TempDir::new().unwrap();
+        let config = InboxConfig {
+            default_assignee: Some("telegram:100".into()),
+            reminder_interval_secs: 60,
+            max_reminders: 1,
+            escalation: vec![InboxEscalationRule {
+                kinds: vec!["review".into()],
+                after_secs: 120,
+                to: "telegram:200".into(),
+            }],
+            ..InboxConfig::default()
+        };
+        let inbox = inbox(&tmp, config);
+        let task = inbox
+            .create(
+                NewHumanTask::new(HumanTaskKind::Review, "Review release notes")
+                    .with_deadline_secs(Some(600)),
+            )
+            .unwrap();
+        let start = task.created_at;
+        let events = |at: i64| -> Vec<InboxEvent> {
+            inbox
+                .sweep(start + Duration::seconds(at))
+                .unwrap()
+                .into_iter()
+                .map(|notice| notice.event)
+                .collect()
+        };
+
+        assert_eq!(events(1), vec![InboxEvent::Created]);
+        assert!(events(30).is_empty());
+        assert_eq!(events(61), vec![InboxEvent::Reminder]);
+        assert!(events(100).is_empty(), "reminders are capped");
+        assert_eq!(events(121), vec![InboxEvent::Escalated]);
+        let escalated = inbox.get(&task.id).unwrap().unwrap();
+        assert_eq!(escalated.current_assignee(), Some("telegram:200"));
+        assert!(escalated.answerable_by("telegram", "200", "200"));
+        assert!(!escalated.answerable_by("telegram", "300", "300"));
+        assert_eq!(events(601), vec![InboxEvent::Expired]);
+        assert_eq!(
+            inbox.get(&task.id).unwrap().unwrap().status,
+            HumanTaskStatus::Expired
+        );
+    }
+
+    #[test]
+    fn cron_waiters_are_resumed_once_with_the_outcome() {
+        let tmp = TempDir::new().unwrap();
+        let inbox = inbox(&tmp, InboxConfig::default());
+        let task = inbox
+            .create(
+                NewHumanTask::new(HumanTaskKind::Question, "Renew the domain?").with_waiter(
+                    TaskWaiter::Cron {
+                        job_id: "job-1".into(),
+                    },
+                ),
+            )
+            .unwrap();
+        assert!(inbox.take_cron_resumptions().unwrap().is_empty());
+
+        inbox
+            .resolve(
+                &task.id,
+                HumanDecision::Answer,
+                Some("yes"),
+                "alice",
+                "telegram",
+            )
+            .unwrap();
+        assert_eq!(inbox.take_cron_resumptions().unwrap(), vec!["job-1"]);
+        assert!(inbox.take_cron_resumptions().unwrap().is_empty());
+
+        let taken = inbox.take_resolved_for_cron("job-1").unwrap();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].response.as_deref(), Some("yes"));
+        assert!(inbox.take_resolved_for_cron("job-1").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a human is asked to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HumanTaskKind {
+    /// Approve or deny a supervised tool call.
+    ToolApproval,
+    /// Confirm a workflow `user_confirm` step.
+    WorkflowConfirm,
+    /// Answer a question from an agent.
+    Question,
+    /// Approve or reject a draft, optionally with comments.
+    Review,
+}
+
+impl HumanTaskKind {
+    pub const ALL: [Self; 4] = [
+        Self::ToolApproval,
+        Self::WorkflowConfirm,
+        Self::Question,
+        Self::Review,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ToolApproval => "tool_approval",
+            Self::WorkflowConfirm => "workflow_confirm",
+            Self::Question => "question",
+            Self::Review => "review",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HumanTaskStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Answered,
+    /// The deadline passed without a response.
+    Expired,
+    Cancelled,
+}
+
+impl HumanTaskStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Answered => "answered",
+            Self::Expired => "expired",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::fmt::Display for HumanTaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A human's response to a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HumanDecision {
+    Approve,
+    Reject,
+    /// Answer a question; the response text is required.
+    Answer,
+}
+
+impl HumanDecision {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "approve" | "approved" | "yes" => Some(Self::Approve),
+            "reject" | "rejected" | "deny" | "no" => Some(Self::Reject),
+            "answer" => Some(Self::Answer),
+            _ => None,
+        }
+    }
+}
+
+/// Who resumes once the task is resolved.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskWaiter {
+    #[default]
+    None,
+    /// An agent turn on a channel, polling the task.
+    Agent { channel: String, user: String },
+    /// A cron job, which runs again with the outcome in its prompt.
+    Cron { job_id: String },
+    /// A workflow run blocked on a `user_confirm` step.
+    Workflow { workflow: String, step: String },
+}
+
+impl TaskWaiter {
+    pub fn label(&self) -> String {
+        match self {
+            Self::None => "nobody".to_string(),
+            Self::Agent { channel, user } => format!("agent ({channel}:{user})"),
+            Self::Cron { job_id } => format!("cron job {job_id}"),
+            Self::Workflow { workflow, step } => format!("workflow {workflow} step {step}"),
+        }
+    }
+}
+
+/// A request for human input, persisted in the inbox.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HumanTask {
+    pub id: String,
+    pub kind: HumanTaskKind,
+    pub title: String,
+    /// Details: tool arguments, the draft under review, context for a question.
+    #[serde(default)]
+    pub body: String,
+    /// `channel:recipient` (notified on that channel) or a free-form name.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Escalation target; takes over as assignee once `escalate_at` passes.
+    #[serde(default)]
+    pub escalate_to: Option<String>,
+    #[serde(default)]
+    pub escalate_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub escalated: bool,
+    pub status: HumanTaskStatus,
+    #[serde(default)]
+    pub waiter: TaskWaiter,
+    /// ID of the record this task mirrors, e.g. a pending approval request.
+    #[serde(default)]
+    pub external_ref: Option<String>,
+    #[serde(default)]
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub next_reminder_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub reminders_sent: u32,
+    /// Whether the assignee has been told about the task.
+    #[serde(default)]
+    pub notified: bool,
+    #[serde(default)]
+    pub response: Option<String>,
+    #[serde(default)]
+    pub resolved_by: Option<String>,
+    /// Where the response came from: a channel name, `cli` or `dashboard`.
+    #[serde(default)]
+    pub resolved_via: Option<String>,
+    #[serde(default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// A cron waiter has been scheduled to run again.
+    #[serde(default)]
+    pub resume_requested: bool,
+    /// The waiter has picked up the outcome.
+    #[serde(default)]
+    pub consumed: bool,
+}
+
+impl HumanTask {
+    pub fn is_open(&self) -> bool {
+        self.status == HumanTaskStatus::Pending
+    }
+
+    /// The assignee currently responsible, after any escalation.
+    pub fn current_assignee(&self) -> Option<&str> {
+        if self.escalated {
+            self.escalate_to.as_deref().or(self.assignee.as_deref())
+        } else {
+            self.assignee.as_deref()
+        }
+    }
+
+    /// Whether `sender` on `channel` may resolve this task. Tasks bound to
+    /// a `channel:recipient` assignee (or escalation target) only accept
+    /// that recipient; all others accept anyone on a linked channel.
+    pub fn answerable_by(&self, channel: &str, sender: &str, reply_target: &str) -> bool {
+        let bound: Vec<&str> = [self.assignee.as_deref(), self.escalate_to.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|who| who.contains(':'))
+            .collect();
+        if bound.is_empty() {
+            return true;
+        }
+        bound.iter().any(|who| {
+            who.split_once(':').is_some_and(|(ch, recipient)| {
+                ch.eq_ignore_ascii_case(channel)
+                    && (recipient == sender || recipient == reply_target)
+            })
+        })
+    }
+
+    /// One-line outcome suitable for a prompt or chat message.
+    pub fn outcome_summary(&self) -> String {
+        let mut summary = format!("[{}] {}: {}", self.id, self.title, self.status);
+        if let Some(by) = &self.resolved_by {
+            summary.push_str(" by ");
+            summary.push_str(by);
+        }
+        if let Some(response) = self.response.as_deref().filter(|r| !r.trim().is_empty()) {
+            summary.push_str(" — ");
+            summary.push_str(response);
+        }
+        summary
+    }
+}
+
+/// Parameters for a new task; unset fields fall back to `[inbox]` defaults.
+#[derive(Debug, Clone)]
+pub struct NewHumanTask {
+    pub kind: HumanTaskKind,
+    pub title: String,
+    pub body: String,
+    pub assignee: Option<String>,
+    pub waiter: TaskWaiter,
+    pub external_ref: Option<String>,
+    pub created_by: Option<String>,
+    /// Seconds until the task expires; `Some(0)` means no deadline.
+    pub deadline_secs: Option<u64>,
+    /// The assignee was already prompted elsewhere (e.g. an approval prompt).
+    pub notified: bool,
+}
+
+impl NewHumanTask {
+    pub fn new(kind: HumanTaskKind, title: impl Into<String>) -> Self {
+        Self {
+            kind,
+            title: title.into(),
+            body: String::new(),
+            assignee: None,
+            waiter: TaskWaiter::None,
+            external_ref: None,
+            created_by: None,
+            deadline_secs: None,
+            notified: false,
+        }
+    }
+
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub fn with_assignee(mut self, assignee: Option<String>) -> Self {
+        self.assignee = assignee.filter(|a| !a.trim().is_empty());
+        self
+    }
+
+    pub fn with_waiter(mut self, waiter: TaskWaiter) -> Self {
+        self.waiter = waiter;
+        self
+    }
+
+    pub fn with_external_ref(mut self, external_ref: impl Into<String>) -> Self {
+        self.external_ref = Some(external_ref.into());
+        self
+    }
+
+    pub fn with_created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.created_by = Some(created_by.into());
+        self
+    }
+
+    pub fn with_deadline_secs(mut self, deadline_secs: Option<u64>) -> Self {
+        self.deadline_secs = deadline_secs;
+        self
+    }
+
+    pub fn already_notified(mut self) -> Self {
+        self.notified = true;
+        self
+    }
+}
+
+/// Something the notifier should tell an assignee about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboxEvent {
+    Created,
+    Reminder,
+    Escalated,
+    Expired,
+}
+
+#[derive(Debug, Clone)]
+pub struct InboxNotice {
+    pub event: InboxEvent,
+    pub task: HumanTask,
+}
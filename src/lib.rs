@@ -47,6 +47,7 @@ pub(crate) mod auth;
 pub mod billing;
 pub mod categories;
 pub mod channels;
+pub(crate) mod checkpoint;
 pub mod coding;
 pub mod config;
 pub mod coordination;
@@ -67,6 +68,7 @@ pub mod host_probe;
 pub(crate) mod heartbeat;
 pub mod hooks;
 pub(crate) mod identity;
+pub(crate) mod inbox;
 // Intentionally unused re-export — public API surface for plugin authors.
 pub(crate) mod integrations;
 // `local_llm` (on-device Gemma 4 fallback: daemon health, model pull, config).
@@ -95,6 +97,7 @@ pub(crate) mod storage;
 pub mod sync;
 pub mod task_category;
 pub mod telemetry;
+pub(crate) mod tenancy;
 pub mod tools;
 pub(crate) mod tunnel;
 pub mod update;
@@ -211,6 +214,18 @@ pub enum SkillCommands {
         /// Skill name to remove
         name: String,
     },
+    /// Accept the current content of an installed skill and record it in skills.lock
+    Approve {
+        /// Installed skill name
+        name: String,
+    },
+    /// Reinstall locked skills from their recorded sources
+    Update {
+        /// Skill to update (defaults to every locked skill)
+        name: Option<String>,
+    },
+    /// Show locked skills whose source has newer content
+    Outdated,
     /// List all available skill templates
     Templates,
 }
@@ -405,6 +420,233 @@ pub enum MemoryCommands {
     },
 }
 
+/// Checkpoint management subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CheckpointCommands {
+    /// List checkpoints, newest first
+    List {
+        /// Maximum number of checkpoints to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Roll back a checkpoint and every newer one
+    Restore {
+        /// Checkpoint ID (from `zeroclaw checkpoint list`)
+        id: String,
+    },
+}
+
+/// Cost ledger subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CostCommands {
+    /// Break spend down by one dimension
+    Report {
+        /// user, channel, session, cron_job, workflow, tool, model or kind
+        #[arg(long, default_value = "channel")]
+        by: String,
+        /// today, week, month or all
+        #[arg(long, default_value = "month")]
+        period: String,
+        /// Report from this date (YYYY-MM-DD) instead of a named period
+        #[arg(long)]
+        since: Option<String>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Human task inbox subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum InboxCommands {
+    /// List pending tasks, newest first
+    List {
+        /// Include resolved, expired and cancelled tasks
+        #[arg(long)]
+        all: bool,
+        /// Maximum number of tasks to display
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        /// Print the tasks as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show one task in full
+    Show {
+        /// Task ID (from `zeroclaw inbox list`)
+        id: String,
+    },
+    /// Approve a tool call, workflow step or draft
+    Approve {
+        /// Task ID
+        id: String,
+        /// Optional comment for the waiting agent
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// Reject a tool call, workflow step, draft or question
+    Reject {
+        /// Task ID
+        id: String,
+        /// Optional reason for the waiting agent
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Answer a question
+    Answer {
+        /// Task ID
+        id: String,
+        /// The answer
+        #[arg(required = true, trailing_var_arg = true)]
+        answer: Vec<String>,
+    },
+    /// Withdraw a task without resolving it
+    Cancel {
+        /// Task ID
+        id: String,
+    },
+}
+
+/// Goal subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GoalCommands {
+    /// List active, pending and blocked goals
+    List {
+        /// Include completed and cancelled goals
+        #[arg(long)]
+        all: bool,
+        /// Print the goals as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a goal with its steps and recent attempts
+    Show {
+        /// Goal ID (from `zeroclaw goals list`)
+        id: String,
+        /// Print the goal as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a goal for the daemon to work on
+    Add {
+        /// What the goal should achieve
+        description: String,
+        /// A step, in order (repeatable)
+        #[arg(long = "step")]
+        steps: Vec<String>,
+        /// Require approval before step N runs, counting from 1 (repeatable)
+        #[arg(long = "checkpoint")]
+        checkpoints: Vec<usize>,
+        /// Priority: low, medium, high or critical
+        #[arg(long, default_value = "medium")]
+        priority: String,
+        /// Who to notify, as channel:recipient (e.g. telegram:12345)
+        #[arg(long)]
+        owner: Option<String>,
+        /// Spend cap for this goal in USD
+        #[arg(long)]
+        budget_usd: Option<f64>,
+        /// Create the goal paused
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Append a step to a goal
+    AddStep {
+        /// Goal ID
+        id: String,
+        /// The step
+        description: String,
+        /// Require approval before the step runs
+        #[arg(long)]
+        checkpoint: bool,
+    },
+    /// Approve a checkpointed step
+    Approve {
+        /// Goal ID
+        id: String,
+        /// Step ID; defaults to the next step waiting for approval
+        step: Option<String>,
+    },
+    /// Stop the daemon from working on a goal
+    Pause {
+        /// Goal ID
+        id: String,
+    },
+    /// Hand a paused or blocked goal back to the daemon
+    Resume {
+        /// Goal ID
+        id: String,
+    },
+    /// Cancel a goal
+    Cancel {
+        /// Goal ID
+        id: String,
+    },
+}
+
+/// Scoped API token subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ApiTokenCommands {
+    /// Create a token and print it once
+    Create {
+        /// Unique token name (e.g. `ci-deploy`)
+        name: String,
+        /// Scopes: chat, memory:read, memory:write, cron:read, cron:write, read, admin
+        #[arg(long = "scope", required = true, value_delimiter = ',')]
+        scopes: Vec<String>,
+        /// Expire the token after this many days
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+        /// Only accept requests from this IP or CIDR range (repeatable)
+        #[arg(long)]
+        allow_ip: Vec<String>,
+        /// Requests per minute (0 = unlimited)
+        #[arg(long, default_value = "0")]
+        rate_limit: u32,
+    },
+    /// List tokens with their scopes and last use
+    List {
+        /// Print the tokens as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Revoke a token by ID or name
+    Revoke {
+        /// Token ID or name
+        token: String,
+    },
+}
+
+/// Tenant (per-user storage) subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TenantCommands {
+    /// List tenants with stored data
+    List {
+        /// Print the tenants as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Copy one tenant's memory, vault, ontology and sessions to a directory
+    Export {
+        /// User ID or username
+        user: String,
+        /// Directory to write the export into (default: `{workspace}/exports`)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Delete one tenant's stored data and user account
+    Delete {
+        /// User ID or username
+        user: String,
+        /// Keep the user account, sessions and channel links
+        #[arg(long)]
+        keep_account: bool,
+        /// Confirm the deletion
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
 /// Vault (second brain) subcommands.
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum VaultCommands {
@@ -64,6 +64,7 @@ mod approval;
 mod auth;
 mod billing;
 mod channels;
+mod checkpoint;
 mod coding;
 mod config;
 mod coordination;
@@ -80,6 +81,7 @@ mod health;
 mod heartbeat;
 mod hooks;
 mod identity;
+mod inbox;
 mod integrations;
 // ── Dual-compile symmetry block ─────────────────────────────────────
 // `lib.rs` and `main.rs` both declare `gateway` / `channels` / etc.,
@@ -137,6 +139,7 @@ mod storage;
 mod sync;
 mod task_category;
 mod telemetry;
+mod tenancy;
 mod tools;
 mod tunnel;
 mod update;
@@ -297,6 +300,19 @@ Examples:
         /// Memory backend (sqlite, markdown, none)
         #[arg(long)]
         memory_backend: Option<String>,
+
+        /// Record provider requests and responses to a cassette file
+        #[arg(long, value_name = "PATH", conflicts_with = "replay")]
+        record: Option<std::path::PathBuf>,
+
+        /// Replay provider responses from a cassette file instead of calling the provider
+        #[arg(long, value_name = "PATH")]
+        replay: Option<std::path::PathBuf>,
+
+        /// Match replayed requests on their conversation only, ignoring the
+        /// system prompt, model and temperature
+        #[arg(long, requires = "replay")]
+        replay_fuzzy: bool,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -584,6 +600,117 @@ Examples:
         memory_command: MemoryCommands,
     },
 
+    /// List and restore checkpoints of files modified by the agent
+    #[command(long_about = "\
+List and restore checkpoints of files modified by the agent.
+
+Before the agent writes, edits, or patches a file, its previous content \
+is saved to a checkpoint under the workspace state dir. All files touched \
+during one turn share a checkpoint. Restoring a checkpoint also rolls back \
+every newer one. Works in directories that are not git repositories.
+
+Examples:
+  zeroclaw checkpoint list
+  zeroclaw checkpoint restore 20260101T120000123Z-1a2b3c4d")]
+    Checkpoint {
+        #[command(subcommand)]
+        checkpoint_command: CheckpointCommands,
+    },
+
+    /// Revert the file changes made by the agent's most recent turn
+    Undo,
+
+    /// Report spend from the cost ledger
+    #[command(long_about = "\
+Report spend from the cost ledger.
+
+Every provider call, embedding request, search API call and TTS/STT minute \
+is recorded with the user, channel, session, cron job, workflow and tool \
+that caused it. Group by any of those, or by model or kind.
+
+Examples:
+  zeroclaw cost report
+  zeroclaw cost report --by user --period today
+  zeroclaw cost report --by tool --since 2026-01-01 --json")]
+    Cost {
+        #[command(subcommand)]
+        cost_command: CostCommands,
+    },
+
+    /// Review and resolve tasks waiting on a human
+    #[command(long_about = "\
+Review and resolve tasks waiting on a human.
+
+Tool approvals, workflow confirmations, agent questions and draft reviews \
+wait in the inbox until someone resolves them here, from a linked channel \
+or from the dashboard. Resolving a task resumes whatever was waiting on it.
+
+Examples:
+  zeroclaw inbox list
+  zeroclaw inbox show hit-1a2b3c4d
+  zeroclaw inbox approve hit-1a2b3c4d --comment \"looks good\"
+  zeroclaw inbox answer hit-1a2b3c4d eu-west-1")]
+    Inbox {
+        #[command(subcommand)]
+        inbox_command: InboxCommands,
+    },
+
+    /// Manage long-running goals advanced by the daemon
+    #[command(long_about = "\
+Manage long-running goals advanced by the daemon.
+
+With [goal_loop] enabled, the daemon works through goal steps on its own \
+within the configured active hours and budgets, reflects on stalled goals \
+and notifies each goal's owner at milestones, stalls and checkpoints.
+
+Examples:
+  zeroclaw goals list
+  zeroclaw goals add \"Publish the Q4 report\" --step \"Collect figures\" --step \"Publish\" --checkpoint 2
+  zeroclaw goals approve goal-1a2b3c4d
+  zeroclaw goals show goal-1a2b3c4d")]
+    Goals {
+        #[command(subcommand)]
+        goal_command: GoalCommands,
+    },
+
+    /// Manage scoped API tokens for gateway automation
+    #[command(long_about = "\
+Manage scoped API tokens for gateway automation.
+
+API tokens are named bearer tokens for CI jobs and scripts. Each token \
+carries scopes (chat, memory:read, memory:write, cron:read, cron:write, \
+read, admin), an optional expiry, an optional IP allowlist and a per-token \
+rate limit. Requests made with a token are recorded in the audit log.
+
+Examples:
+  zeroclaw api-token create ci-chat --scope chat --expires-in-days 90
+  zeroclaw api-token create backup --scope memory:read --allow-ip 10.0.0.0/8
+  zeroclaw api-token list
+  zeroclaw api-token revoke ci-chat")]
+    ApiToken {
+        #[command(subcommand)]
+        api_token_command: ApiTokenCommands,
+    },
+
+    /// Inspect, export or delete per-user storage
+    #[command(long_about = "\
+Inspect, export or delete per-user storage.
+
+With [auth] tenant_isolation = true, each gateway user keeps their own \
+memory, vault, ontology and sessions under {workspace}/tenants/{user_id}/. \
+Export copies one tenant's databases together with a manifest of the \
+account and its channel links; delete removes them and, unless \
+--keep-account is given, the user account as well.
+
+Examples:
+  zeroclaw tenant list
+  zeroclaw tenant export alice --output /backups
+  zeroclaw tenant delete alice --yes")]
+    Tenant {
+        #[command(subcommand)]
+        tenant_command: TenantCommands,
+    },
+
     /// Second-brain vault operations (legal ingest + graph)
     #[command(long_about = "\
 Second-brain (vault) operations.
@@ -650,6 +777,30 @@ Examples:
         #[arg(value_enum)]
         shell: CompletionShell,
     },
+
+    /// Internal launcher used by the seccomp sandbox to confine tool commands
+    #[command(hide = true, name = "sandbox-exec")]
+    SandboxExec {
+        /// Syscall profile: strict, standard, or permissive
+        #[arg(long)]
+        profile: String,
+
+        /// CPU time limit in seconds (0 = unlimited)
+        #[arg(long, default_value_t = 0)]
+        cpu_seconds: u64,
+
+        /// Data segment limit in bytes when no cgroup is used (0 = unlimited)
+        #[arg(long, default_value_t = 0)]
+        memory_bytes: u64,
+
+        /// `cgroup.procs` file of the cgroup to join before exec
+        #[arg(long)]
+        cgroup_procs: Option<std::path::PathBuf>,
+
+        /// Command and arguments to execute
+        #[arg(required = true, last = true)]
+        command: Vec<std::ffi::OsString>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -904,6 +1055,161 @@ enum MemoryCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum CheckpointCommands {
+    /// List checkpoints, newest first
+    List {
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Roll back a checkpoint and every newer one
+    Restore { id: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum CostCommands {
+    /// Break spend down by one dimension
+    Report {
+        #[arg(long, default_value = "channel")]
+        by: String,
+        #[arg(long, default_value = "month")]
+        period: String,
+        #[arg(long)]
+        since: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InboxCommands {
+    /// List pending tasks, newest first
+    List {
+        #[arg(long)]
+        all: bool,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show one task in full
+    Show { id: String },
+    /// Approve a tool call, workflow step or draft
+    Approve {
+        id: String,
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// Reject a tool call, workflow step, draft or question
+    Reject {
+        id: String,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Answer a question
+    Answer {
+        id: String,
+        #[arg(required = true, trailing_var_arg = true)]
+        answer: Vec<String>,
+    },
+    /// Withdraw a task without resolving it
+    Cancel { id: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum GoalCommands {
+    /// List active, pending and blocked goals
+    List {
+        #[arg(long)]
+        all: bool,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a goal with its steps and recent attempts
+    Show {
+        id: String,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a goal for the daemon to work on
+    Add {
+        description: String,
+        #[arg(long = "step")]
+        steps: Vec<String>,
+        #[arg(long = "checkpoint")]
+        checkpoints: Vec<usize>,
+        #[arg(long, default_value = "medium")]
+        priority: String,
+        #[arg(long)]
+        owner: Option<String>,
+        #[arg(long)]
+        budget_usd: Option<f64>,
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Append a step to a goal
+    AddStep {
+        id: String,
+        description: String,
+        #[arg(long)]
+        checkpoint: bool,
+    },
+    /// Approve a checkpointed step
+    Approve { id: String, step: Option<String> },
+    /// Stop the daemon from working on a goal
+    Pause { id: String },
+    /// Hand a paused or blocked goal back to the daemon
+    Resume { id: String },
+    /// Cancel a goal
+    Cancel { id: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum ApiTokenCommands {
+    /// Create a token and print it once
+    Create {
+        name: String,
+        #[arg(long = "scope", required = true, value_delimiter = ',')]
+        scopes: Vec<String>,
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+        #[arg(long)]
+        allow_ip: Vec<String>,
+        #[arg(long, default_value = "0")]
+        rate_limit: u32,
+    },
+    /// List tokens with their scopes and last use
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Revoke a token by ID or name
+    Revoke { token: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum TenantCommands {
+    /// List tenants with stored data
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Copy one tenant's storage to a directory
+    Export {
+        user: String,
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Delete one tenant's stored data and user account
+    Delete {
+        user: String,
+        #[arg(long)]
+        keep_account: bool,
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum VaultCommands {
     /// Legal-domain operations (statute + precedent ingestion, graph stats)
@@ -1041,6 +1347,37 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // The sandbox launcher execs the tool command directly; it must not load
+    // config or write log lines into the command's output.
+    if let Commands::SandboxExec {
+        profile,
+        cpu_seconds,
+        memory_bytes,
+        cgroup_procs,
+        command,
+    } = &cli.command
+    {
+        #[cfg(target_os = "linux")]
+        {
+            let (program, args) = command
+                .split_first()
+                .context("sandbox-exec requires a command")?;
+            return security::seccomp::exec_sandboxed(
+                profile,
+                *cpu_seconds,
+                *memory_bytes,
+                cgroup_procs.as_deref(),
+                program,
+                args,
+            );
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (profile, cpu_seconds, memory_bytes, cgroup_procs, command);
+            bail!("sandbox-exec is only supported on Linux");
+        }
+    }
+
     // Initialize logging - respects RUST_LOG env var, defaults to INFO
     let subscriber = fmt::Subscriber::builder()
         .with_timer(tracing_subscriber::fmt::time::ChronoLocal::rfc_3339())
@@ -1142,6 +1479,8 @@ async fn main() -> Result<()> {
     let mut config = Config::load_or_init().await?;
     config.apply_env_overrides();
     observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
+    cost::init_from_config(&config.cost, &config.workspace_dir);
+    inbox::init_from_config(&config.inbox, &config.workspace_dir);
     if config.security.otp.enabled {
         let config_dir = config
             .config_path
@@ -1157,7 +1496,9 @@ async fn main() -> Result<()> {
     }
 
     match cli.command {
-        Commands::Onboard { .. } | Commands::Completions { .. } => unreachable!(),
+        Commands::Onboard { .. }
+        | Commands::Completions { .. }
+        | Commands::SandboxExec { .. } => unreachable!(),
 
         Commands::Agent {
             message,
@@ -1171,8 +1512,13 @@ async fn main() -> Result<()> {
             max_history_messages,
             compact_context,
             memory_backend,
+            record,
+            replay,
+            replay_fuzzy,
         } => {
-            maybe_auto_bootstrap_local_llm("agent").await;
+            if replay.is_none() {
+                maybe_auto_bootstrap_local_llm("agent").await;
+            }
             if let Some(level) = autonomy_level {
                 config.autonomy.level = level;
             }
@@ -1191,6 +1537,17 @@ async fn main() -> Result<()> {
             if let Some(ref backend) = memory_backend {
                 config.memory.backend = backend.clone();
             }
+            if let Some(path) = record {
+                config.agent.cassette.mode = config::CassetteMode::Record;
+                config.agent.cassette.path = Some(path);
+            }
+            if let Some(path) = replay {
+                config.agent.cassette.mode = config::CassetteMode::Replay;
+                config.agent.cassette.path = Some(path);
+            }
+            if replay_fuzzy {
+                config.agent.cassette.matching = config::CassetteMatch::Fuzzy;
+            }
             // interactive=true only when no --message flag (real REPL session).
             // Single-shot mode (-m) runs non-interactively: no TTY approval prompt,
             // so tools are not denied by a stdin read returning EOF.
@@ -1490,6 +1847,25 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Checkpoint { checkpoint_command } => {
+            checkpoint::cli::handle_command(checkpoint_command, &config)
+        }
+
+        Commands::Undo => checkpoint::cli::handle_undo(&config),
+
+        Commands::Cost { cost_command } => cost::cli::handle_command(cost_command, &config),
+
+        Commands::Inbox { inbox_command } => inbox::cli::handle_command(inbox_command, &config),
+        Commands::Goals { goal_command } => goals::cli::handle_command(goal_command, &config).await,
+
+        Commands::ApiToken { api_token_command } => {
+            security::api_tokens::handle_command(api_token_command, &config)
+        }
+
+        Commands::Tenant { tenant_command } => {
+            tenancy::cli::handle_command(tenant_command, &config)
+        }
+
         Commands::Vault { vault_command } => match vault_command {
             VaultCommands::Legal { legal_command } => match legal_command {
                 VaultLegalCommands::Ingest { path, dry_run } => {
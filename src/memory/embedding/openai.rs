@@ -139,6 +139,16 @@ impl EmbeddingProvider for OpenAiEmbedding {
             embeddings.push(vec);
         }
 
+        let tokens = json
+            .pointer("/usage/prompt_tokens")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_else(|| texts.iter().map(|t| t.len() as u64 / 4).sum());
+        crate::cost::record_metered(
+            crate::cost::CostKind::Embedding,
+            &format!("{}/{}", self.provider_family, self.model),
+            tokens as f64,
+        );
+
         Ok(embeddings)
     }
 }
@@ -6,6 +6,7 @@ pub mod noop;
 pub mod otel;
 pub mod prometheus;
 pub mod runtime_trace;
+pub mod trace_context;
 pub mod traits;
 pub mod verbose;
 
@@ -25,6 +26,7 @@ pub use verbose::VerboseObserver;
 use crate::config::schema::CostConfig;
 use crate::config::ObservabilityConfig;
 use crate::cost::CostTracker;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Factory: create the right observer from config
@@ -55,6 +57,44 @@ pub fn create_observer_with_cost_tracking(
     }
 }
 
+/// Create the observer stack for an entry point (CLI, channels, daemon,
+/// gateway). When `[cost]` is enabled, token usage is recorded on the shared
+/// ledger so budgets and `zeroclaw cost report` see every turn.
+pub fn create_observer_with_ledger(
+    config: &ObservabilityConfig,
+    cost_config: &CostConfig,
+    workspace_dir: &Path,
+) -> Box<dyn Observer> {
+    create_observer_with_cost_tracking(
+        config,
+        ledger_tracker(cost_config, workspace_dir),
+        cost_config,
+    )
+}
+
+/// A bare cost observer on the shared ledger, for nested loops (delegate
+/// sub-agents, team roles) that are not handed their parent's observer.
+pub fn create_ledger_cost_observer(
+    cost_config: &CostConfig,
+    workspace_dir: &Path,
+) -> Option<CostObserver> {
+    ledger_tracker(cost_config, workspace_dir)
+        .map(|tracker| CostObserver::new(tracker, cost_config.prices.clone()))
+}
+
+fn ledger_tracker(cost_config: &CostConfig, workspace_dir: &Path) -> Option<Arc<CostTracker>> {
+    if !cost_config.enabled {
+        return None;
+    }
+    match crate::cost::shared_tracker(cost_config, workspace_dir) {
+        Ok(tracker) => Some(tracker),
+        Err(error) => {
+            tracing::warn!("Cost tracking disabled for this observer: {error}");
+            None
+        }
+    }
+}
+
 fn create_observer_internal(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
@@ -1,14 +1,18 @@
+use super::trace_context::{self, SpanKind};
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use opentelemetry::metrics::{Counter, Gauge, Histogram};
-use opentelemetry::trace::{Span, SpanKind, Status, Tracer};
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
 use std::any::Any;
-use std::time::SystemTime;
 
 /// OpenTelemetry-backed observer — exports traces and metrics via OTLP.
+///
+/// Spans are not built from observer events: turns, LLM requests and tool
+/// calls are traced where they run via [`trace_context`], so they nest under
+/// one another. This observer installs the tracer provider they export to
+/// and records the metrics.
 pub struct OtelObserver {
     tracer_provider: SdkTracerProvider,
     meter_provider: SdkMeterProvider,
@@ -174,8 +178,6 @@ impl OtelObserver {
 
 impl Observer for OtelObserver {
     fn record_event(&self, event: &ObserverEvent) {
-        let tracer = global::tracer("zeroclaw");
-
         match event {
             ObserverEvent::AgentStart { provider, model } => {
                 self.agent_starts.add(
@@ -206,60 +208,15 @@ impl Observer for OtelObserver {
                 ];
                 self.llm_calls.add(1, &attrs);
                 self.llm_duration.record(secs, &attrs);
-
-                // Create a completed span for visibility in trace backends.
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("llm.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("provider", provider.clone()),
-                            KeyValue::new("model", model.clone()),
-                            KeyValue::new("success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                if *success {
-                    span.set_status(Status::Ok);
-                } else {
-                    span.set_status(Status::error(""));
-                }
-                span.end();
             }
             ObserverEvent::AgentEnd {
                 provider,
                 model,
                 duration,
-                tokens_used,
-                cost_usd,
+                tokens_used: _,
+                cost_usd: _,
             } => {
                 let secs = duration.as_secs_f64();
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-
-                // Create a completed span with correct timing
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("agent.invocation")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("provider", provider.clone()),
-                            KeyValue::new("model", model.clone()),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                if let Some(t) = tokens_used {
-                    span.set_attribute(KeyValue::new("tokens_used", *t as i64));
-                }
-                if let Some(c) = cost_usd {
-                    span.set_attribute(KeyValue::new("cost_usd", *c));
-                }
-                span.end();
-
                 self.agent_duration.record(
                     secs,
                     &[
@@ -276,29 +233,6 @@ impl Observer for OtelObserver {
                 success,
             } => {
                 let secs = duration.as_secs_f64();
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-
-                let status = if *success {
-                    Status::Ok
-                } else {
-                    Status::error("")
-                };
-
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("tool.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("tool.name", tool.clone()),
-                            KeyValue::new("tool.success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                span.set_status(status);
-                span.end();
-
                 let attrs = [
                     KeyValue::new("tool", tool.clone()),
                     KeyValue::new("success", success.to_string()),
@@ -320,16 +254,11 @@ impl Observer for OtelObserver {
                 self.heartbeat_ticks.add(1, &[]);
             }
             ObserverEvent::Error { component, message } => {
-                // Create an error span for visibility in trace backends
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("error")
-                        .with_kind(SpanKind::Internal)
-                        .with_attributes(vec![
-                            KeyValue::new("component", component.clone()),
-                            KeyValue::new("error.message", message.clone()),
-                        ]),
-                );
-                span.set_status(Status::error(message.clone()));
+                // Error spans land inside whatever turn reported them.
+                let mut span = trace_context::Span::start("error", SpanKind::Internal);
+                span.set_attribute("component", component.clone());
+                span.set_attribute("error.message", message.clone());
+                span.set_error(message.clone());
                 span.end();
 
                 self.errors
@@ -0,0 +1,413 @@
+//! W3C trace context and hierarchical spans for agent turns.
+//!
+//! Observer events are flat, so they cannot express that a tool call
+//! happened inside a particular LLM turn. This module carries the active
+//! span in a task-local so nested work (turn → LLM request → tool call →
+//! delegated sub-agent → MCP call) is parented correctly, and outbound HTTP
+//! can forward it as a `traceparent` header.
+//!
+//! Spans are always tracked; they are only exported when the crate is built
+//! with `observability-otel` and an [`super::OtelObserver`] has installed the
+//! global tracer provider. Without it, ending a span is a no-op.
+
+use std::future::Future;
+use std::time::SystemTime;
+
+/// Header used to propagate trace context on outbound requests.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+tokio::task_local! {
+    static CURRENT_TRACE_CONTEXT: TraceContext;
+}
+
+/// Identifies one span within a trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+}
+
+impl TraceContext {
+    /// Start a new trace.
+    pub fn root() -> Self {
+        Self {
+            trace_id: non_zero_u128(),
+            span_id: non_zero_u64(),
+        }
+    }
+
+    /// A new span id within the same trace.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: non_zero_u64(),
+        }
+    }
+
+    /// The context of the enclosing span, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_TRACE_CONTEXT.try_with(|cx| *cx).ok()
+    }
+
+    /// Parse a W3C `traceparent` header (`00-<trace>-<span>-<flags>`).
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next()?;
+        let trace = parts.next()?;
+        let span = parts.next()?;
+        let flags = parts.next()?;
+        if version.len() != 2
+            || version == "ff"
+            || trace.len() != 32
+            || span.len() != 16
+            || flags.len() != 2
+        {
+            return None;
+        }
+        // Version 00 forbids trailing fields; later versions may add them.
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+        u8::from_str_radix(version, 16).ok()?;
+        u8::from_str_radix(flags, 16).ok()?;
+        let trace_id = u128::from_str_radix(trace, 16).ok()?;
+        let span_id = u64::from_str_radix(span, 16).ok()?;
+        (trace_id != 0 && span_id != 0).then_some(Self { trace_id, span_id })
+    }
+
+    /// Render as a W3C `traceparent` header value.
+    pub fn traceparent(&self) -> String {
+        format!("00-{:032x}-{:016x}-01", self.trace_id, self.span_id)
+    }
+
+    /// Hex trace id, as shown by trace backends.
+    pub fn trace_id_hex(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+}
+
+fn non_zero_u128() -> u128 {
+    loop {
+        let id = rand::random::<u128>();
+        if id != 0 {
+            return id;
+        }
+    }
+}
+
+fn non_zero_u64() -> u64 {
+    loop {
+        let id = rand::random::<u64>();
+        if id != 0 {
+            return id;
+        }
+    }
+}
+
+/// Run `future` with `context` as the current trace context.
+pub async fn scope<F: Future>(context: TraceContext, future: F) -> F::Output {
+    CURRENT_TRACE_CONTEXT.scope(context, future).await
+}
+
+/// Propagation headers for the current span, for chaining onto an outbound
+/// request with `.headers(trace_context::headers())`. Empty outside a span.
+pub fn headers() -> reqwest::header::HeaderMap {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(cx) = TraceContext::current() {
+        if let Ok(value) = reqwest::header::HeaderValue::from_str(&cx.traceparent()) {
+            headers.insert(TRACEPARENT_HEADER, value);
+        }
+    }
+    headers
+}
+
+/// Relationship of a span to the remote side, mirroring OTel span kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    Internal,
+    Client,
+    Server,
+}
+
+/// Attribute value recorded on a [`Span`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    StringArray(Vec<String>),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for AttributeValue {
+    fn from(value: u64) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Vec<String>> for AttributeValue {
+    fn from(value: Vec<String>) -> Self {
+        Self::StringArray(value)
+    }
+}
+
+/// An in-flight span. Exported when ended or dropped, so early returns
+/// still close it.
+#[derive(Debug)]
+pub struct Span {
+    name: String,
+    kind: SpanKind,
+    context: TraceContext,
+    parent_span_id: Option<u64>,
+    start_time: SystemTime,
+    attributes: Vec<(&'static str, AttributeValue)>,
+    error: Option<String>,
+}
+
+impl Span {
+    /// Start a span under the current one, or a new trace if there is none.
+    pub fn start(name: impl Into<String>, kind: SpanKind) -> Self {
+        Self::start_with_parent(name, kind, TraceContext::current())
+    }
+
+    /// Start a span under an explicit parent, such as one received in an
+    /// inbound `traceparent` header.
+    pub fn start_with_parent(
+        name: impl Into<String>,
+        kind: SpanKind,
+        parent: Option<TraceContext>,
+    ) -> Self {
+        let (context, parent_span_id) = match parent {
+            Some(parent) => (parent.child(), Some(parent.span_id)),
+            None => (TraceContext::root(), None),
+        };
+        Self {
+            name: name.into(),
+            kind,
+            context,
+            parent_span_id,
+            start_time: SystemTime::now(),
+            attributes: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn context(&self) -> TraceContext {
+        self.context
+    }
+
+    pub fn parent_span_id(&self) -> Option<u64> {
+        self.parent_span_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&AttributeValue> {
+        self.attributes
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn set_attribute(&mut self, key: &'static str, value: impl Into<AttributeValue>) {
+        self.attributes.push((key, value.into()));
+    }
+
+    /// Mark the span as failed.
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        self.error = Some(message.into());
+    }
+
+    /// Run `future` with this span as the current trace context.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        scope(self.context, future).await
+    }
+
+    /// Finish the span now.
+    pub fn end(self) {}
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        #[cfg(feature = "observability-otel")]
+        export(self);
+    }
+}
+
+#[cfg(feature = "observability-otel")]
+fn export(span: &mut Span) {
+    use opentelemetry::trace::{
+        Span as _, SpanBuilder, SpanContext, SpanId, Status, TraceContextExt, TraceFlags, TraceId,
+        TraceState, Tracer,
+    };
+    use opentelemetry::{Array, KeyValue, StringValue, Value};
+
+    let attributes = std::mem::take(&mut span.attributes)
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                AttributeValue::String(s) => Value::from(s),
+                AttributeValue::Int(i) => Value::from(i),
+                AttributeValue::Float(f) => Value::from(f),
+                AttributeValue::Bool(b) => Value::from(b),
+                AttributeValue::StringArray(items) => Value::Array(Array::String(
+                    items.into_iter().map(StringValue::from).collect(),
+                )),
+            };
+            KeyValue::new(key, value)
+        })
+        .collect::<Vec<_>>();
+
+    let kind = match span.kind {
+        SpanKind::Internal => opentelemetry::trace::SpanKind::Internal,
+        SpanKind::Client => opentelemetry::trace::SpanKind::Client,
+        SpanKind::Server => opentelemetry::trace::SpanKind::Server,
+    };
+    let trace_id = TraceId::from(span.context.trace_id);
+    let builder = SpanBuilder::from_name(std::mem::take(&mut span.name))
+        .with_kind(kind)
+        .with_trace_id(trace_id)
+        .with_span_id(SpanId::from(span.context.span_id))
+        .with_start_time(span.start_time)
+        .with_attributes(attributes);
+    // The parent may have been started by us or by a remote caller; either
+    // way only its ids matter, so present it as a remote span context.
+    let parent = match span.parent_span_id {
+        Some(parent_id) => {
+            opentelemetry::Context::new().with_remote_span_context(SpanContext::new(
+                trace_id,
+                SpanId::from(parent_id),
+                TraceFlags::SAMPLED,
+                true,
+                TraceState::default(),
+            ))
+        }
+        None => opentelemetry::Context::new(),
+    };
+
+    let mut exported =
+        opentelemetry::global::tracer("zeroclaw").build_with_context(builder, &parent);
+    match span.error.take() {
+        Some(message) => exported.set_status(Status::error(message)),
+        None => exported.set_status(Status::Ok),
+    }
+    exported.end_with_timestamp(SystemTime::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traceparent_round_trips() {
+        let cx = TraceContext::root();
+        let header = cx.traceparent();
+        assert_eq!(header.len(), 55);
+        assert!(header.starts_with("00-"));
+        assert!(header.ends_with("-01"));
+        assert_eq!(TraceContext::from_traceparent(&header), Some(cx));
+    }
+
+    #[test]
+    fn traceparent_rejects_malformed_values() {
+        let valid = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        assert!(TraceContext::from_traceparent(valid).is_some());
+        for bad in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-zzf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        ] {
+            assert_eq!(TraceContext::from_traceparent(bad), None, "{bad}");
+        }
+    }
+
+    #[tokio::test]
+    async fn nested_spans_share_trace_and_link_parents() {
+        assert!(TraceContext::current().is_none());
+        let turn = Span::start("turn", SpanKind::Internal);
+        assert_eq!(turn.parent_span_id(), None);
+
+        let (llm, tool) = turn
+            .scope(async {
+                let llm = Span::start("chat", SpanKind::Client);
+                let tool = llm
+                    .scope(async { Span::start("execute_tool shell", SpanKind::Internal) })
+                    .await;
+                (llm, tool)
+            })
+            .await;
+
+        assert_eq!(llm.context().trace_id, turn.context().trace_id);
+        assert_eq!(llm.parent_span_id(), Some(turn.context().span_id));
+        assert_eq!(tool.context().trace_id, turn.context().trace_id);
+        assert_eq!(tool.parent_span_id(), Some(llm.context().span_id));
+        assert!(TraceContext::current().is_none());
+    }
+
+    #[tokio::test]
+    async fn headers_carry_traceparent_only_inside_a_span() {
+        assert!(headers().is_empty());
+
+        let span = Span::start("call", SpanKind::Client);
+        let headers = span.scope(async { headers() }).await;
+        let header = headers[TRACEPARENT_HEADER].to_str().unwrap();
+        assert_eq!(TraceContext::from_traceparent(header), Some(span.context()));
+    }
+
+    #[test]
+    fn remote_parent_is_honoured() {
+        let remote = TraceContext::from_traceparent(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        );
+        let mut span = Span::start_with_parent("ws.chat", SpanKind::Server, remote);
+        span.set_attribute("gen_ai.usage.input_tokens", 12_u64);
+        span.set_attribute("gen_ai.usage.input_tokens", 15_u64);
+        assert_eq!(
+            span.context().trace_id_hex(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(span.parent_span_id(), Some(0x00f0_67aa_0ba9_02b7));
+        assert_eq!(
+            span.attribute("gen_ai.usage.input_tokens"),
+            Some(&AttributeValue::Int(15))
+        );
+    }
+}
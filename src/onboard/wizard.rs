@@ -204,6 +204,8 @@ pub async fn run_wizard_with_migration(
         reliability: crate::config::ReliabilityConfig::default(),
         scheduler: crate::config::schema::SchedulerConfig::default(),
         coordination: crate::config::CoordinationConfig::default(),
+        teams: std::collections::HashMap::new(),
+        inbox: crate::config::InboxConfig::default(),
         agent: crate::config::schema::AgentConfig::default(),
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
@@ -211,6 +213,7 @@ pub async fn run_wizard_with_migration(
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
         goal_loop: crate::config::schema::GoalLoopConfig::default(),
+        webhooks: crate::config::schema::WebhooksConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
@@ -744,6 +747,8 @@ async fn run_quick_setup_with_home(
         reliability: crate::config::ReliabilityConfig::default(),
         scheduler: crate::config::schema::SchedulerConfig::default(),
         coordination: crate::config::CoordinationConfig::default(),
+        teams: std::collections::HashMap::new(),
+        inbox: crate::config::InboxConfig::default(),
         agent: crate::config::schema::AgentConfig::default(),
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
@@ -751,6 +756,7 @@ async fn run_quick_setup_with_home(
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
         goal_loop: crate::config::schema::GoalLoopConfig::default(),
+        webhooks: crate::config::schema::WebhooksConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
@@ -18,7 +18,6 @@ use std::sync::Arc;
 /// When `sync` is `None`, the repo operates in local-only mode.
 pub struct OntologyRepo {
     conn: Arc<Mutex<Connection>>,
-    #[allow(dead_code)]
     db_path: PathBuf,
     /// Optional sync engine for cross-device replication.
     sync: Option<Arc<parking_lot::Mutex<crate::memory::sync::SyncEngine>>>,
@@ -69,6 +68,20 @@ impl OntologyRepo {
         })
     }
 
+    /// The current tenant's connection when the repo was opened on a
+    /// workspace and the task runs inside [`crate::tenancy::scope`],
+    /// otherwise the repo's own.
+    fn connection(&self) -> anyhow::Result<Arc<Mutex<Connection>>> {
+        let Some(workspace) = self.db_path.parent().and_then(Path::parent) else {
+            return Ok(Arc::clone(&self.conn));
+        };
+        let dir = crate::tenancy::workspace_dir(workspace);
+        if dir == workspace {
+            return Ok(Arc::clone(&self.conn));
+        }
+        crate::tenancy::cached("ontology", &dir, || Ok(Self::open(&dir)?.conn))
+    }
+
     /// Attach a sync engine for cross-device replication.
     ///
     /// After this call, every CUD operation (create/update object, create
@@ -78,6 +91,15 @@ impl OntologyRepo {
         self.sync = Some(sync);
     }
 
+    /// The sync engine, unless the task runs for a tenant: cross-device sync
+    /// replicates the shared workspace only, so tenant writes stay local.
+    fn shared_sync(&self) -> Option<&Arc<parking_lot::Mutex<crate::memory::sync::SyncEngine>>> {
+        if crate::tenancy::current().is_some() {
+            return None;
+        }
+        self.sync.as_ref()
+    }
+
     /// Record an object upsert delta in the sync engine (best-effort).
     fn sync_object(
         &self,
@@ -87,7 +109,7 @@ impl OntologyRepo {
         properties: &serde_json::Value,
         owner_user_id: &str,
     ) {
-        if let Some(ref sync) = self.sync {
+        if let Some(sync) = self.shared_sync() {
             let props_json = serde_json::to_string(properties).unwrap_or_default();
             sync.lock().record_ontology_object(
                 object_id,
@@ -107,7 +129,7 @@ impl OntologyRepo {
         to_object_id: i64,
         properties: Option<&serde_json::Value>,
     ) {
-        if let Some(ref sync) = self.sync {
+        if let Some(sync) = self.shared_sync() {
             let props_json = properties.map(|p| serde_json::to_string(p).unwrap_or_default());
             sync.lock().record_ontology_link(
                 link_type_name,
@@ -138,7 +160,7 @@ impl OntologyRepo {
         location: Option<&str>,
         status: &str,
     ) {
-        if let Some(ref sync) = self.sync {
+        if let Some(sync) = self.shared_sync() {
             let params_json = serde_json::to_string(params).unwrap_or_default();
             let result_json = result.map(|r| serde_json::to_string(r).unwrap_or_default());
             sync.lock().record_ontology_action(
@@ -164,7 +186,8 @@ impl OntologyRepo {
 
     /// Resolve an object type name to its ID.
     pub fn object_type_id(&self, name: &str) -> anyhow::Result<i64> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt =
             conn.prepare_cached("SELECT id FROM ontology_object_types WHERE name = ?1")?;
         let id = stmt
@@ -175,7 +198,8 @@ impl OntologyRepo {
 
     /// Resolve an object type ID to its name.
     pub fn object_type_name(&self, id: i64) -> anyhow::Result<String> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt =
             conn.prepare_cached("SELECT name FROM ontology_object_types WHERE id = ?1")?;
         let name = stmt
@@ -186,7 +210,8 @@ impl OntologyRepo {
 
     /// Resolve a link type name to its ID.
     pub fn link_type_id(&self, name: &str) -> anyhow::Result<i64> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare_cached("SELECT id FROM ontology_link_types WHERE name = ?1")?;
         let id = stmt
             .query_row(params![name], |r| r.get(0))
@@ -196,7 +221,8 @@ impl OntologyRepo {
 
     /// Resolve an action type name to its ID.
     pub fn action_type_id(&self, name: &str) -> anyhow::Result<i64> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt =
             conn.prepare_cached("SELECT id FROM ontology_action_types WHERE name = ?1")?;
         let id = stmt
@@ -207,7 +233,8 @@ impl OntologyRepo {
 
     /// Resolve an action type ID to its name.
     pub fn action_type_name(&self, id: i64) -> anyhow::Result<String> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt =
             conn.prepare_cached("SELECT name FROM ontology_action_types WHERE id = ?1")?;
         let name = stmt
@@ -233,7 +260,8 @@ impl OntologyRepo {
         let type_id = self.object_type_id(type_name)?;
         let now = now_millis();
         let props_str = serde_json::to_string(properties)?;
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.execute(
             "INSERT INTO ontology_objects (type_id, title, properties, owner_user_id, created_at, updated_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
@@ -250,7 +278,8 @@ impl OntologyRepo {
     /// Callers operating on behalf of an external user should prefer
     /// [`get_object_for_owner`] to enforce ownership isolation.
     pub fn get_object(&self, id: i64) -> anyhow::Result<Option<OntologyObject>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.query_row(
             "SELECT id, type_id, title, properties, owner_user_id, created_at, updated_at, themes
              FROM ontology_objects WHERE id = ?1",
@@ -281,7 +310,8 @@ impl OntologyRepo {
         id: i64,
         owner_user_id: &str,
     ) -> anyhow::Result<Option<OntologyObject>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.query_row(
             "SELECT id, type_id, title, properties, owner_user_id, created_at, updated_at, themes
              FROM ontology_objects WHERE id = ?1 AND owner_user_id = ?2",
@@ -315,7 +345,8 @@ impl OntologyRepo {
         properties: Option<&serde_json::Value>,
     ) -> anyhow::Result<()> {
         let now = now_millis();
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         if let Some(props) = properties {
             let props_str = serde_json::to_string(props)?;
             conn.execute(
@@ -343,7 +374,8 @@ impl OntologyRepo {
         properties: Option<&serde_json::Value>,
     ) -> anyhow::Result<()> {
         let now = now_millis();
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let affected = if let Some(props) = properties {
             let props_str = serde_json::to_string(props)?;
             conn.execute(
@@ -368,7 +400,7 @@ impl OntologyRepo {
         // Sync the updated state. We need to read back the object to get
         // the full state including type_name. Best-effort — if read fails
         // we skip sync rather than fail the update.
-        if self.sync.is_some() {
+        if self.shared_sync().is_some() {
             if let Ok(Some(obj)) = self.get_object_for_owner(id, owner_user_id) {
                 // Resolve type name for the sync delta.
                 let type_name = self
@@ -394,7 +426,8 @@ impl OntologyRepo {
         query: &str,
         limit: usize,
     ) -> anyhow::Result<Vec<OntologyObject>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut results = Vec::new();
 
         // Resolve optional type filter to a type_id (using parameter binding, never format!).
@@ -531,7 +564,8 @@ impl OntologyRepo {
         let link_type_id = self.link_type_id(link_type_name)?;
         let now = now_millis();
         let props_str = properties.map(|p| serde_json::to_string(p).unwrap_or_default());
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let affected = conn.execute(
             "INSERT OR IGNORE INTO ontology_links (link_type_id, from_object_id, to_object_id, properties, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5)",
@@ -565,7 +599,8 @@ impl OntologyRepo {
         object_id: i64,
         owner_user_id: &str,
     ) -> anyhow::Result<Vec<OntologyLink>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare_cached(
             "SELECT l.id, l.link_type_id, l.from_object_id, l.to_object_id, l.properties, l.created_at
              FROM ontology_links l
@@ -592,7 +627,8 @@ impl OntologyRepo {
         object_id: i64,
         owner_user_id: &str,
     ) -> anyhow::Result<Vec<OntologyLink>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare_cached(
             "SELECT l.id, l.link_type_id, l.from_object_id, l.to_object_id, l.properties, l.created_at
              FROM ontology_links l
@@ -683,7 +719,8 @@ impl OntologyRepo {
             timesync::now_triple(home_timezone)
         };
 
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.execute(
             "INSERT INTO ontology_actions
              (action_type_id, actor_user_id, actor_kind, primary_object_id,
@@ -744,14 +781,15 @@ impl OntologyRepo {
     ) -> anyhow::Result<()> {
         let now = now_millis();
         let result_str = serde_json::to_string(result)?;
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.execute(
             "UPDATE ontology_actions SET result = ?2, status = 'success', updated_at = ?3 WHERE id = ?1",
             params![action_id, result_str, now],
         )?;
 
         // Re-read the action to get full context for sync delta.
-        if self.sync.is_some() {
+        if self.shared_sync().is_some() {
             #[allow(clippy::type_complexity)]
             let action_opt: Option<(
                 String,
@@ -830,7 +868,8 @@ impl OntologyRepo {
         let now = now_millis();
         let result_str =
             serde_json::to_string(&serde_json::json!({"success": false, "error": error}))?;
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.execute(
             "UPDATE ontology_actions SET result = ?2, status = 'error', error_message = ?3, updated_at = ?4 WHERE id = ?1",
             params![action_id, result_str, error, now],
@@ -845,7 +884,8 @@ impl OntologyRepo {
         channel: Option<&str>,
         limit: usize,
     ) -> anyhow::Result<Vec<OntologyAction>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let (sql, limit_val) = if channel.is_some() {
             (
                 "SELECT id, action_type_id, actor_user_id, actor_kind,
@@ -937,7 +977,8 @@ impl OntologyRepo {
         let type_id = self.object_type_id(type_name)?;
         let now = now_millis();
         let props_str = serde_json::to_string(default_properties)?;
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
 
         // Atomic upsert: the unique index on (type_id, title, owner_user_id)
         // doesn't exist by default, so we fall back to a safe pattern:
@@ -1143,7 +1184,8 @@ impl OntologyRepo {
     /// strongly are these two things related?". Direction is dropped (the
     /// algorithm operates on an undirected projection).
     pub fn load_graph_view(&self) -> anyhow::Result<super::community::GraphView> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare("SELECT id, title FROM ontology_objects")?;
         let nodes: Vec<super::community::GraphNode> = stmt
             .query_map([], |row| {
@@ -1191,7 +1233,8 @@ impl OntologyRepo {
         assignment: &super::community::CommunityAssignment,
         mut summarise: impl FnMut(u32, &[i64]) -> (String, Vec<String>),
     ) -> anyhow::Result<usize> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let tx = conn.unchecked_transaction()?;
         tx.execute("DELETE FROM ontology_communities WHERE level = 0", [])?;
         let mut written = 0usize;
@@ -1221,7 +1264,8 @@ impl OntologyRepo {
     pub fn list_communities_needing_summary(
         &self,
     ) -> anyhow::Result<Vec<(u32, u32, Vec<i64>)>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare(
             "SELECT community_id, level, object_ids
                FROM ontology_communities
@@ -1260,7 +1304,8 @@ impl OntologyRepo {
         keywords: &[String],
     ) -> anyhow::Result<()> {
         let keywords_json = serde_json::to_string(keywords)?;
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.execute(
             "UPDATE ontology_communities
                 SET summary = ?1, keywords = ?2
@@ -1277,7 +1322,8 @@ impl OntologyRepo {
     pub fn list_communities_needing_embedding(
         &self,
     ) -> anyhow::Result<Vec<(u32, u32, String)>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare(
             "SELECT community_id, level, summary
                FROM ontology_communities
@@ -1311,7 +1357,8 @@ impl OntologyRepo {
         for f in embedding {
             bytes.extend_from_slice(&f.to_le_bytes());
         }
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         conn.execute(
             "UPDATE ontology_communities
                 SET summary_embedding = ?1
@@ -1328,7 +1375,8 @@ impl OntologyRepo {
     pub fn list_communities_level_zero(
         &self,
     ) -> anyhow::Result<Vec<super::community::CommunitySummary>> {
-        let conn = self.conn.lock();
+        let handle = self.connection()?;
+        let conn = handle.lock();
         let mut stmt = conn.prepare(
             "SELECT community_id, level, summary, object_ids, keywords, summary_embedding
                FROM ontology_communities
@@ -1400,6 +1448,31 @@ mod community_tests {
             .unwrap()
     }
 
+    #[tokio::test]
+    async fn tenant_writes_record_no_sync_deltas() {
+        let (_tmp, mut r) = repo();
+        let sync_dir = TempDir::new().unwrap();
+        let engine = Arc::new(parking_lot::Mutex::new(
+            crate::memory::sync::SyncEngine::new(sync_dir.path(), true).unwrap(),
+        ));
+        r.set_sync(Arc::clone(&engine));
+
+        let create = |title: &str| {
+            r.create_object("Contact", Some(title), &serde_json::json!({}), "u1")
+                .unwrap()
+        };
+        crate::tenancy::scope(Some("acme".into()), async {
+            let a = create("Alice");
+            let b = create("Bob");
+            r.create_link("knows", a, b, None).unwrap();
+        })
+        .await;
+        assert_eq!(engine.lock().journal_len(), 0);
+
+        create("Carol");
+        assert_eq!(engine.lock().journal_len(), 1);
+    }
+
     #[test]
     fn load_graph_view_returns_nodes_and_collapsed_edges() {
         let (_tmp, r) = repo();
@@ -46,16 +46,25 @@ async fn send_request(port: &mut SerialStream, cmd: &str, args: Value) -> anyhow
     port.write_all(line.as_bytes()).await?;
     port.flush().await?;
 
-    let mut buf = Vec::new();
-    let mut b = [0u8; 1];
-    while port.read_exact(&mut b).await.is_ok() {
-        if b[0] == b'\n' {
-            break;
+    // Skip unsolicited `{"event":…}` frames pushed by newer firmware; only
+    // the `hardware` device stack routes those to the dispatch router.
+    let resp: Value = loop {
+        let mut buf = Vec::new();
+        let mut b = [0u8; 1];
+        while port.read_exact(&mut b).await.is_ok() {
+            if b[0] == b'\n' {
+                break;
+            }
+            buf.push(b[0]);
         }
-        buf.push(b[0]);
-    }
-    let line_str = String::from_utf8_lossy(&buf);
-    let resp: Value = serde_json::from_str(line_str.trim())?;
+        let line_str = String::from_utf8_lossy(&buf);
+        let frame: Value = serde_json::from_str(line_str.trim())?;
+        if frame.get("event").is_some() && frame.get("id").is_none() {
+            tracing::debug!(event = %frame["event"], "serial peripheral: skipping event frame");
+            continue;
+        }
+        break frame;
+    };
     let resp_id = resp["id"].as_str().unwrap_or("");
     if resp_id != id_str {
         anyhow::bail!("Response id mismatch: expected {}, got {}", id_str, resp_id);
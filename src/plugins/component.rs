@@ -0,0 +1,724 @@
+//! WASM component plugins — manifests whose `module_path` names a component.
+//!
+//! A discovered `zeroclaw.plugin.toml` with `module_path = "plugin.wasm"` is
+//! loaded as a WebAssembly component built against
+//! `wit/zeroclaw/plugin/v1/plugin.wit`. The component exports `register`,
+//! during which it calls the host's `register-tool` / `register-hook`, then
+//! serves `execute-tool` and `handle-hook` calls for what it registered.
+//!
+//! # Capability grants
+//!
+//! Registrations go through [`Registrar`], so the manifest's `capabilities`
+//! are the grant set (see [`super::host`]).
+//!
+//! Components are instantiated without WASI: the `zeroclaw:plugin/host`
+//! interface is the only thing they can import, so they have no filesystem,
+//! network, clock or environment access. Each call is capped at
+//! [`COMPONENT_TIMEOUT_SECS`] via epoch interruption.
+//!
+//! # Hot reload
+//!
+//! When `[plugins] hot_reload` is on, calls check (at most once per second)
+//! whether the component file changed and, if so, instantiate and register
+//! the new build before serving the call. Tools and hooks exposed to the
+//! agent are fixed at startup; a reloaded build that drops a tool makes that
+//! tool fail, and newly registered tools appear on the next start. A failed
+//! reload keeps the previous instance.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::path::{Component as PathComponent, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{info, warn};
+
+use crate::tools::traits::ToolResult;
+
+use super::host::{HookOutcome, HookPoint, PluginBackend, Registrar, Registration};
+use super::manifest::PluginManifest;
+
+/// Wall-clock limit for a single `register`, tool or hook call.
+pub const COMPONENT_TIMEOUT_SECS: u64 = 30;
+
+/// Minimum time between two checks of the component file for changes.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Whether a manifest describes a WASM component plugin.
+pub fn is_component_manifest(manifest: &PluginManifest) -> bool {
+    manifest.module_path.trim().ends_with(".wasm")
+}
+
+/// A live component instance. Calls are serialized by [`ComponentPlugin`].
+pub trait ComponentInstance: Send {
+    fn execute_tool(&mut self, name: &str, args_json: &str) -> Result<ToolResult>;
+    fn handle_hook(&mut self, point: HookPoint, payload_json: &str) -> Result<HookOutcome>;
+}
+
+/// Compiles, instantiates and registers a component file.
+pub trait ComponentLoader: Send + Sync {
+    fn instantiate(
+        &self,
+        path: &Path,
+        registrar: Registrar,
+        config_json: &str,
+    ) -> Result<(Box<dyn ComponentInstance>, Registration)>;
+}
+
+type FileStamp = (SystemTime, u64);
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+struct LoadedComponent {
+    instance: Box<dyn ComponentInstance>,
+    registration: Registration,
+    stamp: Option<FileStamp>,
+    checked_at: Instant,
+    /// Set after a trap; the next call re-instantiates the component.
+    poisoned: bool,
+}
+
+/// A loaded component plugin, shared by the tools and hooks it registered.
+pub struct ComponentPlugin {
+    manifest: PluginManifest,
+    module_path: PathBuf,
+    config_json: String,
+    hot_reload: bool,
+    reload_interval: Duration,
+    loader: Box<dyn ComponentLoader>,
+    state: Mutex<LoadedComponent>,
+}
+
+impl ComponentPlugin {
+    /// Load the component named by `manifest.module_path` inside `dir`.
+    pub fn load(
+        manifest: &PluginManifest,
+        dir: &Path,
+        config: &Value,
+        hot_reload: bool,
+    ) -> Result<Arc<Self>> {
+        let loader = wasm::loader(&manifest.id)?;
+        Self::with_loader(manifest, dir, config, hot_reload, loader)
+    }
+
+    pub fn with_loader(
+        manifest: &PluginManifest,
+        dir: &Path,
+        config: &Value,
+        hot_reload: bool,
+        loader: Box<dyn ComponentLoader>,
+    ) -> Result<Arc<Self>> {
+        let module_path = resolve_module_path(dir, &manifest.module_path)?;
+        let config_json = serde_json::to_string(config)?;
+        let stamp = file_stamp(&module_path);
+        let (instance, registration) = loader
+            .instantiate(&module_path, Registrar::new(manifest), &config_json)
+            .with_context(|| format!("failed to load plugin component '{}'", manifest.id))?;
+        Ok(Arc::new(Self {
+            manifest: manifest.clone(),
+            module_path,
+            config_json,
+            hot_reload,
+            reload_interval: RELOAD_CHECK_INTERVAL,
+            loader,
+            state: Mutex::new(LoadedComponent {
+                instance,
+                registration,
+                stamp,
+                checked_at: Instant::now(),
+                poisoned: false,
+            }),
+        }))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.manifest.id
+    }
+
+    /// Re-instantiate the component if it trapped or its file changed.
+    fn refresh(&self, state: &mut LoadedComponent) {
+        if !state.poisoned {
+            if !self.hot_reload || state.checked_at.elapsed() < self.reload_interval {
+                return;
+            }
+            state.checked_at = Instant::now();
+            let stamp = file_stamp(&self.module_path);
+            if stamp.is_none() || stamp == state.stamp {
+                return;
+            }
+        }
+
+        let stamp = file_stamp(&self.module_path);
+        match self.loader.instantiate(
+            &self.module_path,
+            Registrar::new(&self.manifest),
+            &self.config_json,
+        ) {
+            Ok((instance, registration)) => {
+                info!(
+                    plugin = %self.manifest.id,
+                    restarted = state.poisoned,
+                    tools = registration.tools.len(),
+                    hooks = registration.hooks.len(),
+                    "plugin component reloaded"
+                );
+                *state = LoadedComponent {
+                    instance,
+                    registration,
+                    stamp,
+                    checked_at: Instant::now(),
+                    poisoned: false,
+                };
+            }
+            Err(error) => {
+                // Don't retry the same broken build on every call.
+                state.stamp = stamp;
+                warn!(
+                    plugin = %self.manifest.id,
+                    error = %format!("{error:#}"),
+                    "plugin component reload failed; keeping the previous instance"
+                );
+            }
+        }
+    }
+
+    fn run_tool(&self, name: &str, args: &Value) -> Result<ToolResult> {
+        let mut state = self.state.lock();
+        self.refresh(&mut state);
+        if state.poisoned {
+            bail!(
+                "plugin '{}' is unavailable after a failed call",
+                self.manifest.id
+            );
+        }
+        if !state.registration.has_tool(name) {
+            bail!(
+                "tool '{name}' is no longer registered by plugin '{}'",
+                self.manifest.id
+            );
+        }
+        let result = state.instance.execute_tool(name, &args.to_string());
+        if result.is_err() {
+            state.poisoned = true;
+        }
+        result
+    }
+
+    fn run_hook(&self, point: HookPoint, payload: &Value) -> Option<HookOutcome> {
+        let mut state = self.state.lock();
+        self.refresh(&mut state);
+        if state.poisoned || !state.registration.has_hook(point) {
+            return None;
+        }
+        match state.instance.handle_hook(point, &payload.to_string()) {
+            Ok(outcome) => Some(outcome),
+            Err(error) => {
+                state.poisoned = true;
+                warn!(
+                    plugin = %self.manifest.id,
+                    hook = point.handler_name(),
+                    error = %format!("{error:#}"),
+                    "plugin hook failed; continuing without it"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// `module_path` must be a relative path that stays inside the plugin directory.
+fn resolve_module_path(dir: &Path, module_path: &str) -> Result<PathBuf> {
+    let relative = Path::new(module_path.trim());
+    if !relative
+        .components()
+        .all(|part| matches!(part, PathComponent::Normal(_) | PathComponent::CurDir))
+    {
+        bail!("plugin module_path '{module_path}' must be relative to the plugin directory");
+    }
+    Ok(dir.join(relative))
+}
+
+#[async_trait]
+impl PluginBackend for ComponentPlugin {
+    fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    fn registration(&self) -> Registration {
+        self.state.lock().registration.clone()
+    }
+
+    async fn call_tool(self: Arc<Self>, name: String, args: Value) -> Result<ToolResult> {
+        tokio::task::spawn_blocking(move || {
+            self.run_tool(&name, &args)
+                .with_context(|| format!("plugin tool '{name}' ({}) execution failed", self.id()))
+        })
+        .await
+        .context("plugin component task panicked")?
+    }
+
+    async fn call_hook(self: Arc<Self>, point: HookPoint, payload: Value) -> Option<HookOutcome> {
+        tokio::task::spawn_blocking(move || self.run_hook(point, &payload))
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+// ─── wasmtime-backed loader ───────────────────────────────────────────────────
+
+#[cfg(all(
+    feature = "wasm-tools",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+mod wasm {
+    use super::{
+        ComponentInstance, ComponentLoader, HookOutcome, HookPoint, Registrar, Registration,
+        ToolResult, COMPONENT_TIMEOUT_SECS,
+    };
+    use crate::plugins::manifest::PluginToolManifest;
+    use crate::plugins::traits::PluginLogger;
+    use anyhow::{bail, Context, Result};
+    use std::path::Path;
+    use wasmtime::component::{Component, HasSelf, Linker};
+    use wasmtime::{Config as WtConfig, Engine, Store};
+
+    mod bindings {
+        wasmtime::component::bindgen!({
+            path: "wit/zeroclaw/plugin/v1",
+            world: "plugin",
+        });
+    }
+
+    use bindings::exports::zeroclaw::plugin::guest::HookOutcomeKind;
+    use bindings::zeroclaw::plugin::host::{self, HookPoint as WitHookPoint, LogLevel, ToolSpec};
+
+    struct HostState {
+        logger: PluginLogger,
+        /// Present only while the component's `register` export runs.
+        registrar: Option<Registrar>,
+    }
+
+    impl HostState {
+        fn registrar(&mut self) -> Result<&mut Registrar, String> {
+            self.registrar
+                .as_mut()
+                .ok_or_else(|| "registration is only allowed during register()".to_string())
+        }
+    }
+
+    impl host::Host for HostState {
+        fn log(&mut self, level: LogLevel, message: String) {
+            match level {
+                LogLevel::Debug => self.logger.debug(&message),
+                LogLevel::Info => self.logger.info(&message),
+                LogLevel::Warn => self.logger.warn(&message),
+                LogLevel::Error => self.logger.error(&message),
+            }
+        }
+
+        fn register_tool(&mut self, spec: ToolSpec) -> Result<(), String> {
+            let parameters = serde_json::from_str(&spec.parameters_json)
+                .map_err(|e| format!("invalid parameters-json for tool '{}': {e}", spec.name))?;
+            self.registrar()?.register_tool(PluginToolManifest {
+                name: spec.name,
+                description: spec.description,
+                parameters,
+            })
+        }
+
+        fn register_hook(&mut self, point: WitHookPoint, priority: i32) -> Result<(), String> {
+            self.registrar()?.register_hook(from_wit(point), priority)
+        }
+    }
+
+    fn from_wit(point: WitHookPoint) -> HookPoint {
+        match point {
+            WitHookPoint::GatewayStart => HookPoint::GatewayStart,
+            WitHookPoint::GatewayStop => HookPoint::GatewayStop,
+            WitHookPoint::SessionStart => HookPoint::SessionStart,
+            WitHookPoint::SessionEnd => HookPoint::SessionEnd,
+            WitHookPoint::LlmInput => HookPoint::LlmInput,
+            WitHookPoint::LlmOutput => HookPoint::LlmOutput,
+            WitHookPoint::AfterToolCall => HookPoint::AfterToolCall,
+            WitHookPoint::MessageSent => HookPoint::MessageSent,
+            WitHookPoint::HeartbeatTick => HookPoint::HeartbeatTick,
+            WitHookPoint::BeforeModelResolve => HookPoint::BeforeModelResolve,
+            WitHookPoint::BeforePromptBuild => HookPoint::BeforePromptBuild,
+            WitHookPoint::BeforeLlmCall => HookPoint::BeforeLlmCall,
+            WitHookPoint::BeforeToolCall => HookPoint::BeforeToolCall,
+            WitHookPoint::BeforeCompaction => HookPoint::BeforeCompaction,
+            WitHookPoint::AfterCompaction => HookPoint::AfterCompaction,
+            WitHookPoint::ToolResultPersist => HookPoint::ToolResultPersist,
+            WitHookPoint::MessageReceived => HookPoint::MessageReceived,
+            WitHookPoint::MessageSending => HookPoint::MessageSending,
+        }
+    }
+
+    fn to_wit(point: HookPoint) -> WitHookPoint {
+        match point {
+            HookPoint::GatewayStart => WitHookPoint::GatewayStart,
+            HookPoint::GatewayStop => WitHookPoint::GatewayStop,
+            HookPoint::SessionStart => WitHookPoint::SessionStart,
+            HookPoint::SessionEnd => WitHookPoint::SessionEnd,
+            HookPoint::LlmInput => WitHookPoint::LlmInput,
+            HookPoint::LlmOutput => WitHookPoint::LlmOutput,
+            HookPoint::AfterToolCall => WitHookPoint::AfterToolCall,
+            HookPoint::MessageSent => WitHookPoint::MessageSent,
+            HookPoint::HeartbeatTick => WitHookPoint::HeartbeatTick,
+            HookPoint::BeforeModelResolve => WitHookPoint::BeforeModelResolve,
+            HookPoint::BeforePromptBuild => WitHookPoint::BeforePromptBuild,
+            HookPoint::BeforeLlmCall => WitHookPoint::BeforeLlmCall,
+            HookPoint::BeforeToolCall => WitHookPoint::BeforeToolCall,
+            HookPoint::BeforeCompaction => WitHookPoint::BeforeCompaction,
+            HookPoint::AfterCompaction => WitHookPoint::AfterCompaction,
+            HookPoint::ToolResultPersist => WitHookPoint::ToolResultPersist,
+            HookPoint::MessageReceived => WitHookPoint::MessageReceived,
+            HookPoint::MessageSending => WitHookPoint::MessageSending,
+        }
+    }
+
+    /// Run `call` with an epoch deadline, ticking the engine at 1 Hz meanwhile.
+    ///
+    /// Each plugin has its own engine and calls are serialized per plugin, so
+    /// only one ticker advances a given engine at a time.
+    fn with_deadline<R>(
+        engine: &Engine,
+        store: &mut Store<HostState>,
+        call: impl FnOnce(&mut Store<HostState>) -> wasmtime::Result<R>,
+    ) -> Result<R> {
+        store.set_epoch_deadline(COMPONENT_TIMEOUT_SECS);
+        let ticker_engine = engine.clone();
+        let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+        let ticker = std::thread::spawn(move || {
+            while stop_rx
+                .recv_timeout(std::time::Duration::from_secs(1))
+                .is_err()
+            {
+                ticker_engine.increment_epoch();
+            }
+        });
+        let result = call(store);
+        let _ = stop_tx.send(());
+        let _ = ticker.join();
+        result.context("plugin component trapped or timed out")
+    }
+
+    struct WasmComponentLoader {
+        engine: Engine,
+        logger: PluginLogger,
+    }
+
+    impl ComponentLoader for WasmComponentLoader {
+        fn instantiate(
+            &self,
+            path: &Path,
+            registrar: Registrar,
+            config_json: &str,
+        ) -> Result<(Box<dyn ComponentInstance>, Registration)> {
+            let component = Component::from_file(&self.engine, path)
+                .with_context(|| format!("cannot compile WASM component: {}", path.display()))?;
+
+            let mut linker: Linker<HostState> = Linker::new(&self.engine);
+            bindings::Plugin::add_to_linker::<_, HasSelf<_>>(&mut linker, |state| state)
+                .context("failed to link the zeroclaw:plugin host interface")?;
+
+            let mut store = Store::new(
+                &self.engine,
+                HostState {
+                    logger: self.logger.clone(),
+                    registrar: Some(registrar),
+                },
+            );
+            let plugin = with_deadline(&self.engine, &mut store, |store| {
+                bindings::Plugin::instantiate(store, &component, &linker)
+            })
+            .with_context(|| {
+                format!(
+                    "cannot instantiate {} (components may only import zeroclaw:plugin/host)",
+                    path.display()
+                )
+            })?;
+
+            let registered = with_deadline(&self.engine, &mut store, |store| {
+                plugin
+                    .zeroclaw_plugin_guest()
+                    .call_register(store, config_json)
+            })?;
+            let registration = store
+                .data_mut()
+                .registrar
+                .take()
+                .map(Registrar::finish)
+                .unwrap_or_default();
+            if let Err(message) = registered {
+                bail!("register() returned error: {message}");
+            }
+
+            Ok((
+                Box::new(WasmComponentInstance {
+                    engine: self.engine.clone(),
+                    store,
+                    plugin,
+                }),
+                registration,
+            ))
+        }
+    }
+
+    struct WasmComponentInstance {
+        engine: Engine,
+        store: Store<HostState>,
+        plugin: bindings::Plugin,
+    }
+
+    impl ComponentInstance for WasmComponentInstance {
+        fn execute_tool(&mut self, name: &str, args_json: &str) -> Result<ToolResult> {
+            let plugin = &self.plugin;
+            let result = with_deadline(&self.engine, &mut self.store, |store| {
+                plugin
+                    .zeroclaw_plugin_guest()
+                    .call_execute_tool(store, name, args_json)
+            })?;
+            Ok(ToolResult {
+                success: result.success,
+                output: result.output,
+                error: result.error,
+            })
+        }
+
+        fn handle_hook(&mut self, point: HookPoint, payload_json: &str) -> Result<HookOutcome> {
+            let plugin = &self.plugin;
+            let outcome = with_deadline(&self.engine, &mut self.store, |store| {
+                plugin
+                    .zeroclaw_plugin_guest()
+                    .call_handle_hook(store, to_wit(point), payload_json)
+            })?;
+            Ok(match outcome.kind {
+                HookOutcomeKind::Continue => HookOutcome::Continue(outcome.payload_json),
+                HookOutcomeKind::Cancel => HookOutcome::Cancel(
+                    outcome
+                        .reason
+                        .unwrap_or_else(|| "cancelled by plugin".to_string()),
+                ),
+            })
+        }
+    }
+
+    pub(super) fn loader(plugin_id: &str) -> Result<Box<dyn ComponentLoader>> {
+        let mut cfg = WtConfig::new();
+        cfg.wasm_component_model(true);
+        cfg.epoch_interruption(true);
+        let engine = Engine::new(&cfg).context("failed to create WASM engine")?;
+        Ok(Box::new(WasmComponentLoader {
+            engine,
+            logger: PluginLogger::new(plugin_id),
+        }))
+    }
+}
+
+#[cfg(any(
+    not(feature = "wasm-tools"),
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows"))
+))]
+mod wasm {
+    use super::ComponentLoader;
+
+    pub(super) fn loader(_plugin_id: &str) -> anyhow::Result<Box<dyn ComponentLoader>> {
+        if cfg!(feature = "wasm-tools") {
+            anyhow::bail!("WASM component plugins are not supported on this target")
+        }
+        anyhow::bail!(
+            "WASM component plugins require the `wasm-tools` feature \
+             (rebuild with `--features wasm-tools`)"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::HookResult;
+    use crate::plugins::host::{plugin_hooks, plugin_tools};
+    use crate::plugins::manifest::PluginToolManifest;
+    use crate::plugins::traits::PluginCapability;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Treats the "component" file as a list of tool names; each instance
+    /// echoes the file contents it was built from.
+    struct ScriptLoader {
+        instantiations: Arc<AtomicUsize>,
+    }
+
+    struct ScriptInstance {
+        build: String,
+    }
+
+    impl ComponentInstance for ScriptInstance {
+        fn execute_tool(&mut self, name: &str, args_json: &str) -> Result<ToolResult> {
+            if name == "trap" {
+                bail!("unreachable executed");
+            }
+            Ok(ToolResult {
+                success: true,
+                output: format!("{}|{name}|{args_json}", self.build),
+                error: None,
+            })
+        }
+
+        fn handle_hook(&mut self, _point: HookPoint, payload_json: &str) -> Result<HookOutcome> {
+            let mut payload: Value = serde_json::from_str(payload_json)?;
+            if payload["name"] == "shell" {
+                return Ok(HookOutcome::Cancel("shell is blocked".into()));
+            }
+            payload["args"]["checked"] = Value::Bool(true);
+            Ok(HookOutcome::Continue(Some(payload.to_string())))
+        }
+    }
+
+    impl ComponentLoader for ScriptLoader {
+        fn instantiate(
+            &self,
+            path: &Path,
+            mut registrar: Registrar,
+            _config_json: &str,
+        ) -> Result<(Box<dyn ComponentInstance>, Registration)> {
+            self.instantiations.fetch_add(1, Ordering::SeqCst);
+            let build = std::fs::read_to_string(path)?;
+            for line in build.lines() {
+                if let Some(point) = line.strip_prefix("hook:") {
+                    assert_eq!(point, "before-tool-call");
+                    registrar
+                        .register_hook(HookPoint::BeforeToolCall, 5)
+                        .map_err(anyhow::Error::msg)?;
+                } else {
+                    registrar
+                        .register_tool(PluginToolManifest {
+                            name: line.to_string(),
+                            description: format!("{line} tool"),
+                            parameters: serde_json::json!({ "type": "object" }),
+                        })
+                        .map_err(anyhow::Error::msg)?;
+                }
+            }
+            Ok((Box::new(ScriptInstance { build }), registrar.finish()))
+        }
+    }
+
+    fn manifest(capabilities: Vec<PluginCapability>) -> PluginManifest {
+        PluginManifest {
+            id: "scripted".into(),
+            capabilities,
+            module_path: "plugin.wasm".into(),
+            ..PluginManifest::default()
+        }
+    }
+
+    fn load(
+        dir: &Path,
+        manifest: &PluginManifest,
+        build: &str,
+    ) -> (Result<Arc<ComponentPlugin>>, Arc<AtomicUsize>) {
+        std::fs::write(dir.join("plugin.wasm"), build).unwrap();
+        let instantiations = Arc::new(AtomicUsize::new(0));
+        let loader = Box::new(ScriptLoader {
+            instantiations: Arc::clone(&instantiations),
+        });
+        let plugin =
+            ComponentPlugin::with_loader(manifest, dir, &serde_json::json!({}), true, loader);
+        (plugin, instantiations)
+    }
+
+    #[test]
+    fn module_path_must_stay_inside_plugin_dir() {
+        let dir = Path::new("/plugins/demo");
+        assert_eq!(
+            resolve_module_path(dir, "build/plugin.wasm").unwrap(),
+            dir.join("build/plugin.wasm")
+        );
+        assert!(resolve_module_path(dir, "../other/plugin.wasm").is_err());
+        assert!(resolve_module_path(dir, "/tmp/plugin.wasm").is_err());
+    }
+
+    #[test]
+    fn ungranted_registration_fails_the_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let (plugin, _) = load(dir.path(), &manifest(vec![]), "lookup\n");
+        let error = format!("{:#}", plugin.err().expect("load should fail"));
+        assert!(
+            error.contains("not granted the Tools capability"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn tools_call_into_the_component_and_reload_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let (plugin, instantiations) = load(
+            dir.path(),
+            &manifest(vec![PluginCapability::Tools]),
+            "lookup\ntrap",
+        );
+        let mut plugin = plugin.unwrap();
+        Arc::get_mut(&mut plugin).unwrap().reload_interval = Duration::ZERO;
+
+        let backend: Arc<dyn PluginBackend> = plugin;
+        let tools = plugin_tools(&backend);
+        assert_eq!(tools.len(), 2);
+        let result = tools[0]
+            .execute(serde_json::json!({ "q": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "lookup\ntrap|lookup|{\"q\":1}");
+
+        // A trap poisons the instance; the next call gets a fresh one.
+        assert!(tools[1].execute(serde_json::json!({})).await.is_err());
+        assert!(tools[0].execute(serde_json::json!({})).await.is_ok());
+        assert_eq!(instantiations.load(Ordering::SeqCst), 2);
+
+        // A new build without `lookup` is picked up and the stale tool fails.
+        std::fs::write(dir.path().join("plugin.wasm"), "other-build-without-it").unwrap();
+        let error = tools[0].execute(serde_json::json!({})).await.unwrap_err();
+        assert!(format!("{error:#}").contains("no longer registered"));
+        assert_eq!(instantiations.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn hooks_can_rewrite_or_cancel() {
+        let dir = tempfile::tempdir().unwrap();
+        let (plugin, _) = load(
+            dir.path(),
+            &manifest(vec![PluginCapability::Hooks]),
+            "hook:before-tool-call",
+        );
+        let backend: Arc<dyn PluginBackend> = plugin.unwrap();
+        let hooks = plugin_hooks(&backend);
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name(), "scripted:before_tool_call");
+        assert_eq!(hooks[0].priority(), 5);
+
+        match hooks[0]
+            .before_tool_call("file_read".into(), serde_json::json!({ "path": "a" }))
+            .await
+        {
+            HookResult::Continue((name, args)) => {
+                assert_eq!(name, "file_read");
+                assert_eq!(args["checked"], true);
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+        assert!(hooks[0]
+            .before_tool_call("shell".into(), serde_json::json!({}))
+            .await
+            .is_cancel());
+        // Points the component did not register pass through untouched.
+        match hooks[0].before_prompt_build("prompt".into()).await {
+            HookResult::Continue(prompt) => assert_eq!(prompt, "prompt"),
+            HookResult::Cancel(_) => panic!("unexpected cancel"),
+        }
+    }
+}
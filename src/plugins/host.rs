@@ -0,0 +1,681 @@
+//! Host side shared by plugins that run outside the binary.
+//!
+//! WASM component plugins and JSON-RPC process plugins both register tools
+//! and hooks at load time through a [`Registrar`], which enforces the grants
+//! in the manifest's `capabilities`:
+//! - `Tools` allows registering tools; if the manifest declares `[[tools]]`,
+//!   only those names may be registered.
+//! - `Hooks` allows registering hooks.
+//! - `ModifyToolResults` is additionally required for `tool_result_persist`.
+//!
+//! The agent sees [`PluginBackend`] registrations through thin proxies that
+//! forward each call; hook payloads are JSON objects named after the
+//! `HookHandler` arguments, e.g. `{"name": "shell", "args": {...}}` for
+//! `before_tool_call`. Modifying hooks may answer with a replacement payload
+//! of the same shape, or cancel.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::channels::traits::ChannelMessage;
+use crate::hooks::{HookHandler, HookResult};
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::tools::traits::{Tool, ToolResult};
+
+use super::manifest::{PluginManifest, PluginToolManifest};
+use super::traits::PluginCapability;
+
+/// Lifecycle point a plugin can register a hook for — one per `HookHandler` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookPoint {
+    GatewayStart,
+    GatewayStop,
+    SessionStart,
+    SessionEnd,
+    LlmInput,
+    LlmOutput,
+    AfterToolCall,
+    MessageSent,
+    HeartbeatTick,
+    BeforeModelResolve,
+    BeforePromptBuild,
+    BeforeLlmCall,
+    BeforeToolCall,
+    BeforeCompaction,
+    AfterCompaction,
+    ToolResultPersist,
+    MessageReceived,
+    MessageSending,
+}
+
+impl HookPoint {
+    pub const ALL: [Self; 18] = [
+        Self::GatewayStart,
+        Self::GatewayStop,
+        Self::SessionStart,
+        Self::SessionEnd,
+        Self::LlmInput,
+        Self::LlmOutput,
+        Self::AfterToolCall,
+        Self::MessageSent,
+        Self::HeartbeatTick,
+        Self::BeforeModelResolve,
+        Self::BeforePromptBuild,
+        Self::BeforeLlmCall,
+        Self::BeforeToolCall,
+        Self::BeforeCompaction,
+        Self::AfterCompaction,
+        Self::ToolResultPersist,
+        Self::MessageReceived,
+        Self::MessageSending,
+    ];
+
+    /// Name of the corresponding `HookHandler` method.
+    pub fn handler_name(self) -> &'static str {
+        match self {
+            Self::GatewayStart => "on_gateway_start",
+            Self::GatewayStop => "on_gateway_stop",
+            Self::SessionStart => "on_session_start",
+            Self::SessionEnd => "on_session_end",
+            Self::LlmInput => "on_llm_input",
+            Self::LlmOutput => "on_llm_output",
+            Self::AfterToolCall => "on_after_tool_call",
+            Self::MessageSent => "on_message_sent",
+            Self::HeartbeatTick => "on_heartbeat_tick",
+            Self::BeforeModelResolve => "before_model_resolve",
+            Self::BeforePromptBuild => "before_prompt_build",
+            Self::BeforeLlmCall => "before_llm_call",
+            Self::BeforeToolCall => "before_tool_call",
+            Self::BeforeCompaction => "before_compaction",
+            Self::AfterCompaction => "after_compaction",
+            Self::ToolResultPersist => "tool_result_persist",
+            Self::MessageReceived => "on_message_received",
+            Self::MessageSending => "on_message_sending",
+        }
+    }
+
+    pub fn from_handler_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|point| point.handler_name() == name)
+    }
+}
+
+/// A plugin's answer to a hook call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookOutcome {
+    /// Continue, optionally with a replacement payload (JSON).
+    Continue(Option<String>),
+    Cancel(String),
+}
+
+/// What a plugin registered while loading.
+#[derive(Debug, Clone, Default)]
+pub struct Registration {
+    pub tools: Vec<PluginToolManifest>,
+    pub hooks: Vec<(HookPoint, i32)>,
+    /// Background services the host starts and stops (process plugins only).
+    pub services: Vec<String>,
+}
+
+impl Registration {
+    pub fn has_tool(&self, name: &str) -> bool {
+        self.tools.iter().any(|tool| tool.name == name)
+    }
+
+    pub fn has_hook(&self, point: HookPoint) -> bool {
+        self.hooks.iter().any(|(p, _)| *p == point)
+    }
+}
+
+/// Collects registrations, enforcing the manifest's capability grants.
+pub struct Registrar {
+    plugin_id: String,
+    grants: Vec<PluginCapability>,
+    declared_tools: Vec<String>,
+    registration: Registration,
+}
+
+impl Registrar {
+    pub fn new(manifest: &PluginManifest) -> Self {
+        Self {
+            plugin_id: manifest.id.clone(),
+            grants: manifest.capabilities.clone(),
+            declared_tools: manifest
+                .tools
+                .iter()
+                .map(|tool| tool.name.clone())
+                .collect(),
+            registration: Registration::default(),
+        }
+    }
+
+    pub fn register_tool(&mut self, spec: PluginToolManifest) -> Result<(), String> {
+        if !self.grants.contains(&PluginCapability::Tools) {
+            return Err(format!(
+                "plugin '{}' is not granted the Tools capability",
+                self.plugin_id
+            ));
+        }
+        if spec.name.trim().is_empty() {
+            return Err("tool name cannot be empty".into());
+        }
+        if !self.declared_tools.is_empty() && !self.declared_tools.contains(&spec.name) {
+            return Err(format!(
+                "tool '{}' is not declared in the manifest of plugin '{}'",
+                spec.name, self.plugin_id
+            ));
+        }
+        if self.registration.has_tool(&spec.name) {
+            return Err(format!("tool '{}' is already registered", spec.name));
+        }
+        self.registration.tools.push(spec);
+        Ok(())
+    }
+
+    pub fn register_hook(&mut self, point: HookPoint, priority: i32) -> Result<(), String> {
+        if !self.grants.contains(&PluginCapability::Hooks) {
+            return Err(format!(
+                "plugin '{}' is not granted the Hooks capability",
+                self.plugin_id
+            ));
+        }
+        if point == HookPoint::ToolResultPersist
+            && !self.grants.contains(&PluginCapability::ModifyToolResults)
+        {
+            return Err(format!(
+                "plugin '{}' needs the ModifyToolResults capability for tool_result_persist",
+                self.plugin_id
+            ));
+        }
+        if self.registration.has_hook(point) {
+            return Err(format!(
+                "hook '{}' is already registered",
+                point.handler_name()
+            ));
+        }
+        self.registration.hooks.push((point, priority));
+        Ok(())
+    }
+
+    pub fn register_service(&mut self, name: &str) -> Result<(), String> {
+        if name.trim().is_empty() {
+            return Err("service name cannot be empty".into());
+        }
+        if self.registration.services.iter().any(|s| s == name) {
+            return Err(format!("service '{name}' is already registered"));
+        }
+        self.registration.services.push(name.to_string());
+        Ok(())
+    }
+
+    pub fn finish(self) -> Registration {
+        self.registration
+    }
+}
+
+/// A loaded plugin that serves tool and hook calls for what it registered.
+#[async_trait]
+pub trait PluginBackend: Send + Sync {
+    fn manifest(&self) -> &PluginManifest;
+
+    /// What the plugin currently has registered.
+    fn registration(&self) -> Registration;
+
+    async fn call_tool(self: Arc<Self>, name: String, args: Value) -> Result<ToolResult>;
+
+    /// `None` when the plugin did not answer; the hook is then skipped.
+    async fn call_hook(self: Arc<Self>, point: HookPoint, payload: Value) -> Option<HookOutcome>;
+}
+
+/// Tool proxies for everything `backend` registered.
+pub fn plugin_tools(backend: &Arc<dyn PluginBackend>) -> Vec<Box<dyn Tool>> {
+    backend
+        .registration()
+        .tools
+        .into_iter()
+        .map(|spec| {
+            Box::new(PluginTool {
+                backend: Arc::clone(backend),
+                spec,
+            }) as Box<dyn Tool>
+        })
+        .collect()
+}
+
+/// Hook proxies for everything `backend` registered.
+pub fn plugin_hooks(backend: &Arc<dyn PluginBackend>) -> Vec<Box<dyn HookHandler>> {
+    backend
+        .registration()
+        .hooks
+        .into_iter()
+        .map(|(point, priority)| {
+            Box::new(PluginHook {
+                backend: Arc::clone(backend),
+                name: format!("{}:{}", backend.manifest().id, point.handler_name()),
+                point,
+                priority,
+            }) as Box<dyn HookHandler>
+        })
+        .collect()
+}
+
+struct PluginTool {
+    backend: Arc<dyn PluginBackend>,
+    spec: PluginToolManifest,
+}
+
+#[async_trait]
+impl Tool for PluginTool {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn description(&self) -> &str {
+        &self.spec.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.spec.parameters.clone()
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolResult> {
+        Arc::clone(&self.backend)
+            .call_tool(self.spec.name.clone(), args)
+            .await
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModelChoice {
+    provider: String,
+    model: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PromptPayload {
+    prompt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LlmCallPayload {
+    messages: Vec<ChatMessage>,
+    model: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ToolCallPayload {
+    name: String,
+    args: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CompactionPayload {
+    messages: Vec<ChatMessage>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SummaryPayload {
+    summary: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ToolResultPayload {
+    tool: String,
+    result: ToolResult,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IncomingMessage {
+    id: String,
+    sender: String,
+    reply_target: String,
+    content: String,
+    channel: String,
+    timestamp: u64,
+    thread_ts: Option<String>,
+    silent: bool,
+}
+
+impl From<ChannelMessage> for IncomingMessage {
+    fn from(message: ChannelMessage) -> Self {
+        Self {
+            id: message.id,
+            sender: message.sender,
+            reply_target: message.reply_target,
+            content: message.content,
+            channel: message.channel,
+            timestamp: message.timestamp,
+            thread_ts: message.thread_ts,
+            silent: message.silent,
+        }
+    }
+}
+
+impl From<IncomingMessage> for ChannelMessage {
+    fn from(message: IncomingMessage) -> Self {
+        Self {
+            id: message.id,
+            sender: message.sender,
+            reply_target: message.reply_target,
+            content: message.content,
+            channel: message.channel,
+            timestamp: message.timestamp,
+            thread_ts: message.thread_ts,
+            silent: message.silent,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct OutgoingMessage {
+    channel: String,
+    recipient: String,
+    content: String,
+}
+
+struct PluginHook {
+    backend: Arc<dyn PluginBackend>,
+    name: String,
+    point: HookPoint,
+    priority: i32,
+}
+
+impl PluginHook {
+    async fn call(&self, point: HookPoint, payload: Value) -> Option<HookOutcome> {
+        if point != self.point {
+            return None;
+        }
+        Arc::clone(&self.backend).call_hook(point, payload).await
+    }
+
+    async fn notify(&self, point: HookPoint, payload: Value) {
+        let _ = self.call(point, payload).await;
+    }
+
+    async fn modify<T: Serialize + DeserializeOwned>(
+        &self,
+        point: HookPoint,
+        input: T,
+    ) -> HookResult<T> {
+        if point != self.point {
+            return HookResult::Continue(input);
+        }
+        let payload = match serde_json::to_value(&input) {
+            Ok(payload) => payload,
+            Err(_) => return HookResult::Continue(input),
+        };
+        match self.call(point, payload).await {
+            Some(HookOutcome::Cancel(reason)) => HookResult::Cancel(reason),
+            Some(HookOutcome::Continue(Some(replacement))) => {
+                match serde_json::from_str(&replacement) {
+                    Ok(next) => HookResult::Continue(next),
+                    Err(error) => {
+                        warn!(
+                            hook = %self.name,
+                            error = %error,
+                            "plugin hook returned a malformed payload; ignoring it"
+                        );
+                        HookResult::Continue(input)
+                    }
+                }
+            }
+            Some(HookOutcome::Continue(None)) | None => HookResult::Continue(input),
+        }
+    }
+}
+
+#[async_trait]
+impl HookHandler for PluginHook {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn capabilities(&self) -> &[PluginCapability] {
+        &self.backend.manifest().capabilities
+    }
+
+    async fn on_gateway_start(&self, host: &str, port: u16) {
+        let payload = serde_json::json!({ "host": host, "port": port });
+        self.notify(HookPoint::GatewayStart, payload).await;
+    }
+
+    async fn on_gateway_stop(&self) {
+        self.notify(HookPoint::GatewayStop, serde_json::json!({}))
+            .await;
+    }
+
+    async fn on_session_start(&self, session_id: &str, channel: &str) {
+        let payload = serde_json::json!({ "session_id": session_id, "channel": channel });
+        self.notify(HookPoint::SessionStart, payload).await;
+    }
+
+    async fn on_session_end(&self, session_id: &str, channel: &str) {
+        let payload = serde_json::json!({ "session_id": session_id, "channel": channel });
+        self.notify(HookPoint::SessionEnd, payload).await;
+    }
+
+    async fn on_llm_input(&self, messages: &[ChatMessage], model: &str) {
+        let payload = serde_json::json!({ "messages": messages, "model": model });
+        self.notify(HookPoint::LlmInput, payload).await;
+    }
+
+    async fn on_llm_output(&self, response: &ChatResponse) {
+        let payload = serde_json::json!({
+            "text": response.text,
+            "tool_calls": response.tool_calls,
+            "reasoning_content": response.reasoning_content,
+            "usage": response.usage.as_ref().map(|usage| serde_json::json!({
+                "input_tokens": usage.input_tokens,
+                "output_tokens": usage.output_tokens,
+            })),
+        });
+        self.notify(HookPoint::LlmOutput, payload).await;
+    }
+
+    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, duration: Duration) {
+        let payload = serde_json::json!({
+            "tool": tool,
+            "result": result,
+            "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        });
+        self.notify(HookPoint::AfterToolCall, payload).await;
+    }
+
+    async fn on_message_sent(&self, channel: &str, recipient: &str, content: &str) {
+        let payload = serde_json::json!({
+            "channel": channel,
+            "recipient": recipient,
+            "content": content,
+        });
+        self.notify(HookPoint::MessageSent, payload).await;
+    }
+
+    async fn on_heartbeat_tick(&self) {
+        self.notify(HookPoint::HeartbeatTick, serde_json::json!({}))
+            .await;
+    }
+
+    async fn before_model_resolve(
+        &self,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        match self
+            .modify(
+                HookPoint::BeforeModelResolve,
+                ModelChoice { provider, model },
+            )
+            .await
+        {
+            HookResult::Continue(choice) => HookResult::Continue((choice.provider, choice.model)),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn before_prompt_build(&self, prompt: String) -> HookResult<String> {
+        match self
+            .modify(HookPoint::BeforePromptBuild, PromptPayload { prompt })
+            .await
+        {
+            HookResult::Continue(payload) => HookResult::Continue(payload.prompt),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn before_llm_call(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        match self
+            .modify(HookPoint::BeforeLlmCall, LlmCallPayload { messages, model })
+            .await
+        {
+            HookResult::Continue(payload) => {
+                HookResult::Continue((payload.messages, payload.model))
+            }
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        match self
+            .modify(HookPoint::BeforeToolCall, ToolCallPayload { name, args })
+            .await
+        {
+            HookResult::Continue(payload) => HookResult::Continue((payload.name, payload.args)),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn before_compaction(&self, messages: Vec<ChatMessage>) -> HookResult<Vec<ChatMessage>> {
+        match self
+            .modify(HookPoint::BeforeCompaction, CompactionPayload { messages })
+            .await
+        {
+            HookResult::Continue(payload) => HookResult::Continue(payload.messages),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn after_compaction(&self, summary: String) -> HookResult<String> {
+        match self
+            .modify(HookPoint::AfterCompaction, SummaryPayload { summary })
+            .await
+        {
+            HookResult::Continue(payload) => HookResult::Continue(payload.summary),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn tool_result_persist(
+        &self,
+        tool: String,
+        result: ToolResult,
+    ) -> HookResult<ToolResult> {
+        match self
+            .modify(
+                HookPoint::ToolResultPersist,
+                ToolResultPayload { tool, result },
+            )
+            .await
+        {
+            HookResult::Continue(payload) => HookResult::Continue(payload.result),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn on_message_received(&self, message: ChannelMessage) -> HookResult<ChannelMessage> {
+        match self
+            .modify(HookPoint::MessageReceived, IncomingMessage::from(message))
+            .await
+        {
+            HookResult::Continue(message) => HookResult::Continue(message.into()),
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        let message = OutgoingMessage {
+            channel,
+            recipient,
+            content,
+        };
+        match self.modify(HookPoint::MessageSending, message).await {
+            HookResult::Continue(message) => {
+                HookResult::Continue((message.channel, message.recipient, message.content))
+            }
+            HookResult::Cancel(reason) => HookResult::Cancel(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str) -> PluginToolManifest {
+        PluginToolManifest {
+            name: name.into(),
+            description: "d".into(),
+            parameters: serde_json::json!({}),
+        }
+    }
+
+    fn manifest(capabilities: Vec<PluginCapability>) -> PluginManifest {
+        PluginManifest {
+            id: "granted".into(),
+            capabilities,
+            ..PluginManifest::default()
+        }
+    }
+
+    #[test]
+    fn registrar_enforces_manifest_grants() {
+        let mut declared = manifest(vec![PluginCapability::Tools, PluginCapability::Hooks]);
+        declared.tools.push(spec("lookup"));
+        let mut registrar = Registrar::new(&declared);
+        assert!(registrar.register_tool(spec("lookup")).is_ok());
+        assert!(registrar.register_tool(spec("lookup")).is_err());
+        assert!(registrar.register_tool(spec("undeclared")).is_err());
+        assert!(registrar
+            .register_hook(HookPoint::BeforeToolCall, 0)
+            .is_ok());
+        assert!(registrar
+            .register_hook(HookPoint::ToolResultPersist, 0)
+            .unwrap_err()
+            .contains("ModifyToolResults"));
+
+        let mut ungranted = Registrar::new(&manifest(vec![]));
+        assert!(ungranted.register_tool(spec("lookup")).is_err());
+        assert!(ungranted.register_hook(HookPoint::SessionStart, 0).is_err());
+        assert!(ungranted.register_service("poller").is_ok());
+    }
+
+    #[test]
+    fn hook_points_round_trip_through_handler_names() {
+        for point in HookPoint::ALL {
+            assert_eq!(
+                HookPoint::from_handler_name(point.handler_name()),
+                Some(point)
+            );
+        }
+        assert_eq!(HookPoint::from_handler_name("on_tool_call"), None);
+    }
+}
@@ -7,12 +7,17 @@
 use std::collections::HashSet;
 use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use tracing::{info, warn};
 
 use crate::config::PluginsConfig;
+use crate::security::Sandbox;
 
+use super::component::{is_component_manifest, ComponentPlugin};
 use super::discovery::discover_plugins;
+use super::host::{plugin_hooks, plugin_tools, PluginBackend};
+use super::process::{is_process_manifest, ProcessPlugin};
 use super::registry::{
     DiagnosticLevel, PluginDiagnostic, PluginHookRegistration, PluginOrigin, PluginRecord,
     PluginRegistry, PluginStatus, PluginToolRegistration,
@@ -67,11 +72,13 @@ fn run_register(
 /// Load all plugins: discover → filter → register → collect into registry.
 ///
 /// `builtin_plugins` are compiled-in plugins (like OpenClaw's bundled extensions).
-/// They are registered first, then discovered plugins from disk.
+/// They are registered first, then discovered plugins from disk. Process
+/// plugins are spawned inside `sandbox`.
 pub fn load_plugins(
     cfg: &PluginsConfig,
     workspace_dir: Option<&std::path::Path>,
     builtin_plugins: Vec<Box<dyn Plugin>>,
+    sandbox: Arc<dyn Sandbox>,
 ) -> PluginRegistry {
     let mut registry = PluginRegistry::new();
 
@@ -205,14 +212,88 @@ pub fn load_plugins(
                     status: PluginStatus::Disabled,
                 });
             }
+            Ok(())
+                if is_process_manifest(&discovered.manifest)
+                    || is_component_manifest(&discovered.manifest) =>
+            {
+                let plugin_config = cfg
+                    .entries
+                    .get(&id)
+                    .map(|e| e.config.clone())
+                    .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+                let loaded: anyhow::Result<Arc<dyn PluginBackend>> =
+                    if is_process_manifest(&discovered.manifest) {
+                        ProcessPlugin::load(
+                            &discovered.manifest,
+                            &discovered.dir,
+                            &plugin_config,
+                            Arc::clone(&sandbox),
+                        )
+                        .map(|plugin| plugin as Arc<dyn PluginBackend>)
+                    } else {
+                        ComponentPlugin::load(
+                            &discovered.manifest,
+                            &discovered.dir,
+                            &plugin_config,
+                            cfg.hot_reload,
+                        )
+                        .map(|plugin| plugin as Arc<dyn PluginBackend>)
+                    };
+                let status = match loaded {
+                    Ok(backend) => {
+                        let tools = plugin_tools(&backend);
+                        let hooks = plugin_hooks(&backend);
+                        info!(
+                            plugin = %id,
+                            tools = tools.len(),
+                            hooks = hooks.len(),
+                            "plugin registered"
+                        );
+                        for tool in tools {
+                            registry.tools.push(PluginToolRegistration {
+                                plugin_id: id.clone(),
+                                tool,
+                            });
+                        }
+                        for handler in hooks {
+                            registry.hooks.push(PluginHookRegistration {
+                                plugin_id: id.clone(),
+                                handler,
+                            });
+                        }
+                        registry.backends.push(backend);
+                        PluginStatus::Active
+                    }
+                    Err(err) => {
+                        let message = format!("{err:#}");
+                        warn!(plugin = %id, error = %message, "plugin failed to load");
+                        registry.push_diagnostic(PluginDiagnostic {
+                            level: DiagnosticLevel::Error,
+                            plugin_id: Some(id.clone()),
+                            source: Some(discovered.dir.display().to_string()),
+                            message: message.clone(),
+                        });
+                        PluginStatus::Error(message)
+                    }
+                };
+                registry.plugins.push(PluginRecord {
+                    id: id.clone(),
+                    name: discovered.manifest.name,
+                    version: discovered.manifest.version,
+                    description: discovered.manifest.description,
+                    source: discovered.dir.display().to_string(),
+                    origin: discovered.origin,
+                    status,
+                });
+                loaded_ids.insert(id);
+            }
             Ok(()) => {
-                // Disk-discovered plugins are manifest-only for now.
-                // Dynamic loading (libloading / WASM) is a future extension point.
+                // Manifests without a component or process have no entry point.
                 warn!(
                     plugin = %id,
                     path = %discovered.dir.display(),
-                    "discovered plugin has no compiled entry point; \
-                     register as builtin or wait for dynamic loading support"
+                    "discovered plugin has no entry point; \
+                     set `module_path` or `[process]`, or register it as builtin"
                 );
                 registry.plugins.push(PluginRecord {
                     id: id.clone(),
@@ -222,7 +303,7 @@ pub fn load_plugins(
                     source: discovered.dir.display().to_string(),
                     origin: discovered.origin,
                     status: PluginStatus::Error(
-                        "dynamic loading not yet supported; register as builtin".into(),
+                        "no WASM component or [process] entry point; register as builtin".into(),
                     ),
                 });
                 loaded_ids.insert(id);
@@ -243,6 +324,7 @@ mod tests {
     use crate::config::PluginsConfig;
     use crate::plugins::manifest::PluginManifest;
     use crate::plugins::traits::{Plugin, PluginApi};
+    use crate::security::NoopSandbox;
 
     struct OkPlugin {
         manifest: PluginManifest,
@@ -295,6 +377,7 @@ mod tests {
             wit_packages: vec![],
             tools: vec![],
             providers: vec![],
+            process: None,
         }
     }
 
@@ -311,7 +394,7 @@ mod tests {
             enabled: false,
             ..Default::default()
         };
-        let reg = load_plugins(&cfg, None, vec![]);
+        let reg = load_plugins(&cfg, None, vec![], Arc::new(NoopSandbox));
         assert_eq!(reg.active_count(), 0);
         assert!(reg
             .diagnostics
@@ -325,7 +408,7 @@ mod tests {
         let plugin: Box<dyn Plugin> = Box::new(OkPlugin {
             manifest: make_manifest("ok"),
         });
-        let reg = load_plugins(&cfg, None, vec![plugin]);
+        let reg = load_plugins(&cfg, None, vec![plugin], Arc::new(NoopSandbox));
         assert_eq!(reg.active_count(), 1);
         assert_eq!(reg.plugins[0].status, PluginStatus::Active);
     }
@@ -336,7 +419,7 @@ mod tests {
         let plugin: Box<dyn Plugin> = Box::new(PanicPlugin {
             manifest: make_manifest("panicky"),
         });
-        let reg = load_plugins(&cfg, None, vec![plugin]);
+        let reg = load_plugins(&cfg, None, vec![plugin], Arc::new(NoopSandbox));
         assert_eq!(reg.active_count(), 0);
         match &reg.plugins[0].status {
             PluginStatus::Error(msg) => assert!(msg.contains("panic")),
@@ -350,7 +433,7 @@ mod tests {
         let plugin: Box<dyn Plugin> = Box::new(ErrorPlugin {
             manifest: make_manifest("erroring"),
         });
-        let reg = load_plugins(&cfg, None, vec![plugin]);
+        let reg = load_plugins(&cfg, None, vec![plugin], Arc::new(NoopSandbox));
         assert_eq!(reg.active_count(), 0);
         match &reg.plugins[0].status {
             PluginStatus::Error(msg) => assert!(msg.contains("error")),
@@ -368,7 +451,7 @@ mod tests {
         let plugin: Box<dyn Plugin> = Box::new(OkPlugin {
             manifest: make_manifest("blocked"),
         });
-        let reg = load_plugins(&cfg, None, vec![plugin]);
+        let reg = load_plugins(&cfg, None, vec![plugin], Arc::new(NoopSandbox));
         assert_eq!(reg.active_count(), 0);
         assert_eq!(reg.plugins[0].status, PluginStatus::Disabled);
     }
@@ -386,10 +469,80 @@ mod tests {
         let blocked: Box<dyn Plugin> = Box::new(OkPlugin {
             manifest: make_manifest("not-allowed"),
         });
-        let reg = load_plugins(&cfg, None, vec![allowed, blocked]);
+        let reg = load_plugins(&cfg, None, vec![allowed, blocked], Arc::new(NoopSandbox));
         assert_eq!(reg.active_count(), 1);
         assert_eq!(reg.plugins[0].id, "allowed");
         assert_eq!(reg.plugins[0].status, PluginStatus::Active);
         assert_eq!(reg.plugins[1].status, PluginStatus::Disabled);
     }
+
+    #[cfg(not(feature = "wasm-tools"))]
+    #[test]
+    fn component_plugin_without_runtime_is_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("component-demo");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(crate::plugins::PLUGIN_MANIFEST_FILENAME),
+            r#"
+id = "component-demo"
+module_path = "plugin.wasm"
+capabilities = ["Tools"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("plugin.wasm"), b"\0asm").unwrap();
+
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![tmp.path().display().to_string()],
+            ..Default::default()
+        };
+        let reg = load_plugins(&cfg, None, vec![], Arc::new(NoopSandbox));
+        let record = reg
+            .plugins
+            .iter()
+            .find(|p| p.id == "component-demo")
+            .expect("component plugin should be discovered");
+        match &record.status {
+            PluginStatus::Error(msg) => assert!(msg.contains("wasm-tools"), "{msg}"),
+            other => panic!("expected Error, got {other:?}"),
+        }
+        assert!(reg.backends.is_empty());
+    }
+
+    #[test]
+    fn process_plugin_spawn_failure_is_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("process-demo");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(crate::plugins::PLUGIN_MANIFEST_FILENAME),
+            r#"
+id = "process-demo"
+capabilities = ["Tools"]
+
+[process]
+command = "./missing-plugin-binary"
+"#,
+        )
+        .unwrap();
+
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![tmp.path().display().to_string()],
+            ..Default::default()
+        };
+        let reg = load_plugins(&cfg, None, vec![], Arc::new(NoopSandbox));
+        let record = reg
+            .plugins
+            .iter()
+            .find(|p| p.id == "process-demo")
+            .expect("process plugin should be discovered");
+        match &record.status {
+            PluginStatus::Error(msg) => assert!(msg.contains("failed to spawn"), "{msg}"),
+            other => panic!("expected Error, got {other:?}"),
+        }
+        assert!(reg.backends.is_empty());
+    }
 }
@@ -11,8 +11,12 @@ use std::path::Path;
 use super::traits::PluginCapability;
 
 const SUPPORTED_WIT_MAJOR: u64 = 1;
-const SUPPORTED_WIT_PACKAGES: [&str; 3] =
-    ["zeroclaw:hooks", "zeroclaw:tools", "zeroclaw:providers"];
+const SUPPORTED_WIT_PACKAGES: [&str; 4] = [
+    "zeroclaw:hooks",
+    "zeroclaw:tools",
+    "zeroclaw:providers",
+    "zeroclaw:plugin",
+];
 
 /// Filename plugins must use for their manifest.
 pub const PLUGIN_MANIFEST_FILENAME: &str = "zeroclaw.plugin.toml";
@@ -48,7 +52,7 @@ pub struct PluginManifest {
     /// Declared capability set for this plugin.
     #[serde(default)]
     pub capabilities: Vec<PluginCapability>,
-    /// Optional module path used by WASM-oriented plugin runtimes.
+    /// Path of the plugin's WASM component, relative to the plugin directory.
     #[serde(default)]
     pub module_path: String,
     /// Declared WIT package contracts the plugin expects.
@@ -60,6 +64,48 @@ pub struct PluginManifest {
     /// Manifest-declared providers (runtime placeholder wiring for now).
     #[serde(default)]
     pub providers: Vec<String>,
+    /// Executable spoken to over JSON-RPC stdio, for plugins written in other
+    /// languages. Used instead of `module_path`.
+    #[serde(default)]
+    pub process: Option<ProcessManifest>,
+}
+
+/// When a process plugin is restarted after its process exits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    Never,
+    #[default]
+    OnFailure,
+    Always,
+}
+
+/// The `[process]` table of a process plugin manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessManifest {
+    /// Executable to spawn; paths containing `/` are relative to the plugin directory.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Host environment variables passed through to the process.
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Restarts allowed before the plugin is given up on.
+    #[serde(default = "default_process_max_restarts")]
+    pub max_restarts: u32,
+    /// Limit for a single request to the process.
+    #[serde(default = "default_process_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_process_max_restarts() -> u32 {
+    3
+}
+
+fn default_process_timeout_secs() -> u64 {
+    30
 }
 
 /// Result of attempting to load a manifest from a directory.
@@ -143,8 +189,17 @@ pub fn validate_manifest(manifest: &PluginManifest) -> anyhow::Result<()> {
             anyhow::bail!("plugin version cannot be empty");
         }
     }
-    if manifest.module_path.trim().is_empty() {
-        anyhow::bail!("plugin module_path cannot be empty");
+    match &manifest.process {
+        Some(process) => {
+            if process.command.trim().is_empty() {
+                anyhow::bail!("plugin process command cannot be empty");
+            }
+        }
+        None => {
+            if manifest.module_path.trim().is_empty() {
+                anyhow::bail!("plugin module_path cannot be empty");
+            }
+        }
     }
     for wit_pkg in &manifest.wit_packages {
         let (package, major) = parse_wit_package_version(wit_pkg)?;
@@ -274,6 +329,7 @@ id = "  "
             wit_packages: vec!["zeroclaw:hooks@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            process: None,
         };
         assert!(valid.is_valid());
     }
@@ -291,7 +347,32 @@ id = "  "
             wit_packages: vec!["zeroclaw:unknown@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            process: None,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
+
+    #[test]
+    fn process_manifest_replaces_module_path() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+id = "py-demo"
+
+[process]
+command = "./plugin.py"
+args = ["--stdio"]
+restart = "always"
+"#,
+        )
+        .unwrap();
+        let process = manifest.process.as_ref().unwrap();
+        assert_eq!(process.restart, RestartPolicy::Always);
+        assert_eq!(process.max_restarts, 3);
+        assert_eq!(process.timeout_secs, 30);
+        assert!(manifest.is_valid());
+
+        let mut empty = manifest.clone();
+        empty.process.as_mut().unwrap().command = " ".into();
+        assert!(validate_manifest(&empty).is_err());
+    }
 }
@@ -10,6 +10,12 @@
 //!   tools, hooks, and services without knowing the host internals
 //! - **Error isolation**: panics inside plugin `register()` are caught and
 //!   recorded as diagnostics rather than crashing the host
+//! - **Components**: discovered manifests whose `module_path` names a `.wasm`
+//!   component register tools and hooks through `wit/zeroclaw/plugin`, limited
+//!   to the capabilities the manifest grants, and reload when the file changes
+//! - **Processes**: manifests with a `[process]` table name an executable
+//!   (Python, TypeScript, ...) that is spawned inside the configured sandbox
+//!   and speaks JSON-RPC over stdio, restarted per its restart policy
 //!
 //! # Quick start
 //!
@@ -37,20 +43,29 @@
 //! enabled = true
 //! ```
 
+pub mod component;
 pub mod discovery;
+pub mod host;
 pub mod loader;
 pub mod manifest;
+pub mod process;
 pub mod registry;
 pub mod runtime;
 pub mod traits;
 
+#[allow(unused_imports)]
+pub use component::ComponentPlugin;
 #[allow(unused_imports)]
 pub use discovery::discover_plugins;
 #[allow(unused_imports)]
+pub use host::PluginBackend;
+#[allow(unused_imports)]
 pub use loader::load_plugins;
 #[allow(unused_imports)]
 pub use manifest::{PluginManifest, PLUGIN_MANIFEST_FILENAME};
 #[allow(unused_imports)]
+pub use process::ProcessPlugin;
+#[allow(unused_imports)]
 pub use registry::{
     DiagnosticLevel, PluginDiagnostic, PluginHookRegistration, PluginOrigin, PluginRecord,
     PluginRegistry, PluginStatus, PluginToolRegistration,
@@ -75,6 +90,7 @@ mod tests {
             wit_packages: vec![],
             tools: vec![],
             providers: vec![],
+            process: None,
         };
         assert_eq!(PLUGIN_MANIFEST_FILENAME, "zeroclaw.plugin.toml");
     }
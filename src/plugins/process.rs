@@ -0,0 +1,878 @@
+//! Process plugins — manifests with a `[process]` table.
+//!
+//! Plugins written in Python, TypeScript or any other language ship an
+//! executable that ZeroClaw spawns inside the configured [`Sandbox`] and
+//! talks to over newline-delimited JSON-RPC 2.0 on stdin/stdout. Stderr is
+//! forwarded to the log.
+//!
+//! Host → plugin requests:
+//! - `initialize {plugin_id, config, capabilities, protocol_version}` — the
+//!   plugin registers what it provides before answering.
+//! - `service.start {name}` for each registered service, after `initialize`.
+//! - `tool.execute {name, args}` → `{success, output, error?}`.
+//! - `hook.handle {point, payload}` → `{action: "continue" | "cancel",
+//!   payload?, reason?}`, where `point` is the `HookHandler` method name.
+//!
+//! Notifications `service.stop {name}` and `shutdown` are sent on unload.
+//!
+//! Plugin → host requests, valid only while `initialize` runs:
+//! `register_tool {name, description, parameters?}`,
+//! `register_hook {point, priority?}` and `register_service {name}`; grants
+//! are enforced as for components (see [`super::host`]). `log {level,
+//! message}` is accepted at any time.
+//!
+//! # Crash isolation
+//!
+//! A process that exits, hangs past `timeout_secs` or writes garbage only
+//! fails the calls in flight. The `restart` policy (`never`, `on-failure`,
+//! `always`) decides whether it is respawned and re-initialized, with
+//! exponential backoff and at most `max_restarts` consecutive attempts.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Weak};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+use crate::security::Sandbox;
+use crate::tools::traits::ToolResult;
+
+use super::host::{HookOutcome, HookPoint, PluginBackend, Registrar, Registration};
+use super::manifest::{PluginManifest, PluginToolManifest, ProcessManifest, RestartPolicy};
+use super::traits::PluginLogger;
+
+/// Version of the JSON-RPC protocol sent in `initialize`.
+pub const PROCESS_PROTOCOL_VERSION: u32 = 1;
+
+/// Host variables every plugin process receives; `[process] env` adds more.
+const BASE_ENV: [&str; 5] = ["PATH", "HOME", "LANG", "LC_ALL", "TMPDIR"];
+
+/// How long an exiting process gets before it is killed.
+const EXIT_GRACE: Duration = Duration::from_secs(2);
+
+/// A process that ran this long resets the consecutive restart count.
+const STABLE_RUN: Duration = Duration::from_secs(60);
+
+const RESTART_BASE_DELAY: Duration = Duration::from_millis(500);
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// Whether a manifest describes a process plugin.
+pub fn is_process_manifest(manifest: &PluginManifest) -> bool {
+    manifest.process.is_some()
+}
+
+fn restart_delay(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RESTART_BASE_DELAY
+        .saturating_mul(factor)
+        .min(RESTART_MAX_DELAY)
+}
+
+/// Commands containing `/` are resolved against the plugin directory;
+/// bare names are looked up on `PATH`.
+fn resolve_command(dir: &Path, command: &str) -> PathBuf {
+    let command = Path::new(command.trim());
+    if command.is_relative() && command.components().count() > 1 {
+        dir.join(command)
+    } else {
+        command.to_path_buf()
+    }
+}
+
+type Reply = std::result::Result<Value, String>;
+type CallError = (i64, String);
+
+/// One spawned process and its JSON-RPC channel.
+struct Connection {
+    child: Mutex<Child>,
+    stdin: Mutex<Option<ChildStdin>>,
+    pending: Mutex<HashMap<u64, mpsc::Sender<Reply>>>,
+    next_id: AtomicU64,
+    alive: AtomicBool,
+    /// Present only while the plugin's `initialize` runs.
+    registrar: Mutex<Option<Registrar>>,
+    logger: PluginLogger,
+    started: Instant,
+}
+
+impl Connection {
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    fn send(&self, message: &Value) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        let mut stdin = self.stdin.lock();
+        let pipe = stdin.as_mut().context("plugin process stdin is closed")?;
+        pipe.write_all(line.as_bytes())
+            .and_then(|()| pipe.flush())
+            .context("failed to write to plugin process")
+    }
+
+    fn notify(&self, method: &str, params: Value) -> Result<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    fn request(&self, method: &str, params: Value, timeout: Duration) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel();
+        {
+            let mut pending = self.pending.lock();
+            if !self.is_alive() {
+                bail!("plugin process is not running");
+            }
+            pending.insert(id, tx);
+        }
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = self.send(&message) {
+            self.pending.lock().remove(&id);
+            return Err(err);
+        }
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(message)) => bail!("{method} failed: {message}"),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                self.pending.lock().remove(&id);
+                // A hung plugin is treated like a crashed one.
+                self.kill();
+                bail!(
+                    "{method} timed out after {}s; stopping the plugin process",
+                    timeout.as_secs()
+                )
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                bail!("plugin process exited during {method}")
+            }
+        }
+    }
+
+    /// Mark the connection dead and fail every request still waiting.
+    fn fail_pending(&self) {
+        let mut pending = self.pending.lock();
+        self.alive.store(false, Ordering::SeqCst);
+        for (_, tx) in pending.drain() {
+            let _ = tx.send(Err("plugin process exited".into()));
+        }
+    }
+
+    fn kill(&self) {
+        let _ = self.child.lock().kill();
+    }
+
+    fn close_stdin(&self) {
+        self.stdin.lock().take();
+    }
+
+    /// Wait up to `grace` for the process to exit, then kill it.
+    fn reap(&self, grace: Duration) -> Option<ExitStatus> {
+        let deadline = Instant::now() + grace;
+        let mut child = self.child.lock();
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => return Some(status),
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                Ok(None) => {
+                    let _ = child.kill();
+                    return child.wait().ok();
+                }
+                Err(_) => return None,
+            }
+        }
+    }
+
+    fn dispatch(&self, message: &Value) {
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id");
+        match (method, id) {
+            (Some(method), id) => {
+                let params = message.get("params").cloned().unwrap_or(Value::Null);
+                let result = self.handle_call(method, params);
+                if let Some(id) = id {
+                    let reply = match result {
+                        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                        Err((code, message)) => json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": code, "message": message },
+                        }),
+                    };
+                    let _ = self.send(&reply);
+                }
+            }
+            (None, Some(id)) => {
+                let Some(tx) = id.as_u64().and_then(|id| self.pending.lock().remove(&id)) else {
+                    self.logger
+                        .warn(&format!("ignoring response to unknown request {id}"));
+                    return;
+                };
+                let reply = match message.get("error") {
+                    Some(error) => Err(error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("request failed")
+                        .to_string()),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = tx.send(reply);
+            }
+            (None, None) => self
+                .logger
+                .warn("ignoring JSON-RPC message without method or id"),
+        }
+    }
+
+    fn handle_call(&self, method: &str, params: Value) -> std::result::Result<Value, CallError> {
+        #[derive(Deserialize)]
+        struct LogParams {
+            #[serde(default)]
+            level: String,
+            message: String,
+        }
+
+        #[derive(Deserialize)]
+        struct HookParams {
+            point: String,
+            #[serde(default)]
+            priority: i32,
+        }
+
+        #[derive(Deserialize)]
+        struct ServiceParams {
+            name: String,
+        }
+
+        match method {
+            "log" => {
+                let params: LogParams = parse_params(params)?;
+                match params.level.as_str() {
+                    "debug" => self.logger.debug(&params.message),
+                    "warn" | "warning" => self.logger.warn(&params.message),
+                    "error" => self.logger.error(&params.message),
+                    _ => self.logger.info(&params.message),
+                }
+                Ok(Value::Null)
+            }
+            "register_tool" => {
+                let spec: PluginToolManifest = parse_params(params)?;
+                self.with_registrar(|registrar| registrar.register_tool(spec))
+            }
+            "register_hook" => {
+                let params: HookParams = parse_params(params)?;
+                let point = HookPoint::from_handler_name(&params.point).ok_or_else(|| {
+                    (
+                        INVALID_PARAMS,
+                        format!("unknown hook point '{}'", params.point),
+                    )
+                })?;
+                self.with_registrar(|registrar| registrar.register_hook(point, params.priority))
+            }
+            "register_service" => {
+                let params: ServiceParams = parse_params(params)?;
+                self.with_registrar(|registrar| registrar.register_service(&params.name))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method '{method}'"))),
+        }
+    }
+
+    fn with_registrar(
+        &self,
+        register: impl FnOnce(&mut Registrar) -> std::result::Result<(), String>,
+    ) -> std::result::Result<Value, CallError> {
+        let mut registrar = self.registrar.lock();
+        let registrar = registrar.as_mut().ok_or_else(|| {
+            (
+                SERVER_ERROR,
+                "registration is only allowed during initialize".to_string(),
+            )
+        })?;
+        register(registrar)
+            .map(|()| Value::Null)
+            .map_err(|message| (SERVER_ERROR, message))
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> std::result::Result<T, CallError> {
+    serde_json::from_value(params).map_err(|err| (INVALID_PARAMS, err.to_string()))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum HookAction {
+    #[default]
+    Continue,
+    Cancel,
+}
+
+#[derive(Debug, Deserialize)]
+struct HookReply {
+    #[serde(default)]
+    action: HookAction,
+    #[serde(default)]
+    payload: Option<Value>,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+struct ProcessState {
+    conn: Option<Arc<Connection>>,
+    registration: Registration,
+    restarts: u32,
+}
+
+/// A running process plugin, shared by the tools and hooks it registered.
+pub struct ProcessPlugin {
+    manifest: PluginManifest,
+    process: ProcessManifest,
+    dir: PathBuf,
+    config: Value,
+    sandbox: Arc<dyn Sandbox>,
+    logger: PluginLogger,
+    this: Weak<Self>,
+    stopped: AtomicBool,
+    state: Mutex<ProcessState>,
+}
+
+impl ProcessPlugin {
+    /// Spawn the manifest's `[process]` inside `sandbox` and initialize it.
+    pub fn load(
+        manifest: &PluginManifest,
+        dir: &Path,
+        config: &Value,
+        sandbox: Arc<dyn Sandbox>,
+    ) -> Result<Arc<Self>> {
+        let process = manifest
+            .process
+            .clone()
+            .context("plugin manifest has no [process] table")?;
+        let plugin = Arc::new_cyclic(|this| Self {
+            manifest: manifest.clone(),
+            process,
+            dir: dir.to_path_buf(),
+            config: config.clone(),
+            sandbox,
+            logger: PluginLogger::new(&manifest.id),
+            this: this.clone(),
+            stopped: AtomicBool::new(false),
+            state: Mutex::new(ProcessState {
+                conn: None,
+                registration: Registration::default(),
+                restarts: 0,
+            }),
+        });
+        plugin
+            .start()
+            .with_context(|| format!("failed to start plugin process '{}'", manifest.id))?;
+        Ok(plugin)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.manifest.id
+    }
+
+    /// Whether the process is currently up.
+    pub fn is_running(&self) -> bool {
+        self.state
+            .lock()
+            .conn
+            .as_ref()
+            .is_some_and(|conn| conn.is_alive())
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.process.timeout_secs.max(1))
+    }
+
+    fn spawn(&self) -> Result<Arc<Connection>> {
+        let mut cmd = Command::new(resolve_command(&self.dir, &self.process.command));
+        cmd.args(&self.process.args);
+
+        // Wrap before the environment is reset so wrapper launchers start
+        // with the same scrubbed environment the plugin would have had.
+        self.sandbox
+            .wrap_command(&mut cmd)
+            .with_context(|| format!("failed to apply {} sandbox", self.sandbox.name()))?;
+
+        cmd.current_dir(&self.dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .env_clear();
+        for var in BASE_ENV
+            .iter()
+            .copied()
+            .chain(self.process.env.iter().map(String::as_str))
+        {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+        cmd.env("ZEROCLAW_PLUGIN_ID", &self.manifest.id);
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("failed to spawn '{}'", self.process.command))?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().context("plugin stdout is not piped")?;
+        let stderr = child.stderr.take().context("plugin stderr is not piped")?;
+
+        let conn = Arc::new(Connection {
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            alive: AtomicBool::new(true),
+            registrar: Mutex::new(Some(Registrar::new(&self.manifest))),
+            logger: self.logger.clone(),
+            started: Instant::now(),
+        });
+
+        let reader_conn = Arc::clone(&conn);
+        let plugin = self.this.clone();
+        std::thread::Builder::new()
+            .name(format!("plugin-{}-rpc", self.manifest.id))
+            .spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    let Ok(line) = line else { break };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str::<Value>(&line) {
+                        Ok(message) => reader_conn.dispatch(&message),
+                        Err(_) => reader_conn
+                            .logger
+                            .warn(&format!("ignoring non-JSON-RPC output: {line}")),
+                    }
+                }
+                reader_conn.fail_pending();
+                if let Some(plugin) = plugin.upgrade() {
+                    plugin.handle_exit(&reader_conn);
+                }
+            })
+            .context("failed to start plugin reader thread")?;
+
+        let logger = self.logger.clone();
+        std::thread::Builder::new()
+            .name(format!("plugin-{}-stderr", self.manifest.id))
+            .spawn(move || {
+                for line in BufReader::new(stderr).lines() {
+                    let Ok(line) = line else { break };
+                    logger.info(&line);
+                }
+            })
+            .context("failed to start plugin stderr thread")?;
+
+        Ok(conn)
+    }
+
+    /// Run `initialize` and start registered services.
+    fn initialize(&self, conn: &Connection) -> Result<Registration> {
+        let params = json!({
+            "plugin_id": self.manifest.id,
+            "config": self.config,
+            "capabilities": self.manifest.capabilities,
+            "protocol_version": PROCESS_PROTOCOL_VERSION,
+        });
+        let initialized = conn.request("initialize", params, self.timeout());
+        let registration = conn
+            .registrar
+            .lock()
+            .take()
+            .map(Registrar::finish)
+            .unwrap_or_default();
+        initialized?;
+        for service in &registration.services {
+            conn.request("service.start", json!({ "name": service }), self.timeout())
+                .with_context(|| format!("service '{service}' failed to start"))?;
+        }
+        Ok(registration)
+    }
+
+    fn start(&self) -> Result<()> {
+        let conn = self.spawn()?;
+        match self.initialize(&conn) {
+            Ok(registration) => {
+                let mut state = self.state.lock();
+                // The reader only acts on installed connections, so a process
+                // that died before this point must be caught here.
+                if !conn.is_alive() {
+                    bail!("plugin process exited during startup");
+                }
+                info!(
+                    plugin = %self.manifest.id,
+                    tools = registration.tools.len(),
+                    hooks = registration.hooks.len(),
+                    services = registration.services.len(),
+                    "plugin process initialized"
+                );
+                state.conn = Some(conn);
+                state.registration = registration;
+                Ok(())
+            }
+            Err(err) => {
+                conn.kill();
+                Err(err)
+            }
+        }
+    }
+
+    /// Called from the reader thread once a process's stdout closes.
+    fn handle_exit(&self, conn: &Arc<Connection>) {
+        {
+            let mut state = self.state.lock();
+            if !state
+                .conn
+                .as_ref()
+                .is_some_and(|current| Arc::ptr_eq(current, conn))
+            {
+                return;
+            }
+            state.conn = None;
+            if conn.started.elapsed() >= STABLE_RUN {
+                state.restarts = 0;
+            }
+        }
+        let status = conn.reap(EXIT_GRACE);
+        if self.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        if let Some(violation) = status.and_then(|status| self.sandbox.describe_violation(status)) {
+            warn!(plugin = %self.manifest.id, violation = %violation, "plugin sandbox violation");
+        }
+        let failed = !status.is_some_and(|status| status.success());
+        warn!(plugin = %self.manifest.id, status = ?status, "plugin process exited");
+
+        let restart = match self.process.restart {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => failed,
+            RestartPolicy::Always => true,
+        };
+        if !restart {
+            return;
+        }
+        loop {
+            let attempt = {
+                let mut state = self.state.lock();
+                if state.restarts >= self.process.max_restarts {
+                    error!(
+                        plugin = %self.manifest.id,
+                        restarts = state.restarts,
+                        "plugin process keeps exiting; giving up"
+                    );
+                    return;
+                }
+                state.restarts += 1;
+                state.restarts
+            };
+            std::thread::sleep(restart_delay(attempt));
+            if self.stopped.load(Ordering::SeqCst) {
+                return;
+            }
+            match self.start() {
+                Ok(()) => {
+                    info!(plugin = %self.manifest.id, attempt, "plugin process restarted");
+                    return;
+                }
+                Err(err) => warn!(
+                    plugin = %self.manifest.id,
+                    attempt,
+                    error = %format!("{err:#}"),
+                    "plugin process restart failed"
+                ),
+            }
+        }
+    }
+
+    fn connection(&self) -> Result<(Arc<Connection>, Registration)> {
+        let state = self.state.lock();
+        let conn = state
+            .conn
+            .clone()
+            .with_context(|| format!("plugin '{}' is not running", self.manifest.id))?;
+        Ok((conn, state.registration.clone()))
+    }
+
+    fn run_tool(&self, name: &str, args: Value) -> Result<ToolResult> {
+        let (conn, registration) = self.connection()?;
+        if !registration.has_tool(name) {
+            bail!(
+                "tool '{name}' is no longer registered by plugin '{}'",
+                self.manifest.id
+            );
+        }
+        let result = conn.request(
+            "tool.execute",
+            json!({ "name": name, "args": args }),
+            self.timeout(),
+        )?;
+        serde_json::from_value(result).context("plugin returned a malformed tool result")
+    }
+
+    fn run_hook(&self, point: HookPoint, payload: Value) -> Option<HookOutcome> {
+        let (conn, registration) = self.connection().ok()?;
+        if !registration.has_hook(point) {
+            return None;
+        }
+        let reply = conn
+            .request(
+                "hook.handle",
+                json!({ "point": point.handler_name(), "payload": payload }),
+                self.timeout(),
+            )
+            .and_then(|reply| {
+                serde_json::from_value::<HookReply>(reply).context("malformed hook reply")
+            });
+        match reply {
+            Ok(reply) => Some(match reply.action {
+                HookAction::Continue => {
+                    HookOutcome::Continue(reply.payload.map(|payload| payload.to_string()))
+                }
+                HookAction::Cancel => HookOutcome::Cancel(
+                    reply
+                        .reason
+                        .unwrap_or_else(|| "cancelled by plugin".to_string()),
+                ),
+            }),
+            Err(err) => {
+                warn!(
+                    plugin = %self.manifest.id,
+                    hook = point.handler_name(),
+                    error = %format!("{err:#}"),
+                    "plugin hook failed; continuing without it"
+                );
+                None
+            }
+        }
+    }
+}
+
+impl Drop for ProcessPlugin {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let state = self.state.get_mut();
+        let Some(conn) = state.conn.take() else {
+            return;
+        };
+        for service in &state.registration.services {
+            let _ = conn.notify("service.stop", json!({ "name": service }));
+        }
+        let _ = conn.notify("shutdown", json!({}));
+        conn.close_stdin();
+        let _ = std::thread::Builder::new()
+            .name(format!("plugin-{}-exit", self.manifest.id))
+            .spawn(move || conn.reap(EXIT_GRACE));
+    }
+}
+
+#[async_trait]
+impl PluginBackend for ProcessPlugin {
+    fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    fn registration(&self) -> Registration {
+        self.state.lock().registration.clone()
+    }
+
+    async fn call_tool(self: Arc<Self>, name: String, args: Value) -> Result<ToolResult> {
+        tokio::task::spawn_blocking(move || {
+            self.run_tool(&name, args)
+                .with_context(|| format!("plugin tool '{name}' ({}) execution failed", self.id()))
+        })
+        .await
+        .context("plugin process task panicked")?
+    }
+
+    async fn call_hook(self: Arc<Self>, point: HookPoint, payload: Value) -> Option<HookOutcome> {
+        tokio::task::spawn_blocking(move || self.run_hook(point, payload))
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::hooks::HookResult;
+    use crate::plugins::host::{plugin_hooks, plugin_tools};
+    use crate::plugins::traits::PluginCapability;
+    use crate::security::NoopSandbox;
+
+    /// Answers the host's requests; tool args containing `crash` make it exit.
+    const SCRIPT: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+  case "$line" in
+    *'"method":"initialize"'*)
+      echo '{"jsonrpc":"2.0","id":"r1","method":"register_tool","params":{"name":"echo","description":"Echo"}}'
+      echo '{"jsonrpc":"2.0","id":"r2","method":"register_hook","params":{"point":"before_tool_call","priority":7}}'
+      echo '{"jsonrpc":"2.0","id":"r3","method":"register_service","params":{"name":"poller"}}'
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{}}" ;;
+    *'"method":"service.start"'*)
+      echo start >> services.log
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":null}" ;;
+    *'"method":"service.stop"'*)
+      echo stop >> services.log ;;
+    *'"method":"shutdown"'*)
+      exit 0 ;;
+    *'"method":"tool.execute"'*)
+      case "$line" in *crash*) exit 3 ;; esac
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"success\":true,\"output\":\"sandboxed=$SANDBOXED\"}}" ;;
+    *'"method":"hook.handle"'*)
+      case "$line" in
+        *'"shell"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"action\":\"cancel\",\"reason\":\"shell is blocked\"}}" ;;
+        *) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"action\":\"continue\",\"payload\":{\"name\":\"file_read\",\"args\":{\"checked\":true}}}}" ;;
+      esac ;;
+  esac
+done
+"#;
+
+    /// Launches the plugin through `env`, the way wrapper sandboxes do.
+    struct EnvSandbox;
+
+    impl Sandbox for EnvSandbox {
+        fn wrap_command(&self, cmd: &mut Command) -> std::io::Result<()> {
+            let mut wrapped = Command::new("env");
+            wrapped.arg("SANDBOXED=yes").arg(cmd.get_program());
+            wrapped.args(cmd.get_args());
+            *cmd = wrapped;
+            Ok(())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            "env"
+        }
+
+        fn description(&self) -> &str {
+            "test wrapper"
+        }
+    }
+
+    fn manifest(capabilities: Vec<PluginCapability>) -> PluginManifest {
+        PluginManifest {
+            id: "scripted".into(),
+            capabilities,
+            process: Some(ProcessManifest {
+                command: "sh".into(),
+                args: vec!["plugin.sh".into()],
+                env: vec![],
+                restart: RestartPolicy::OnFailure,
+                max_restarts: 3,
+                timeout_secs: 10,
+            }),
+            ..PluginManifest::default()
+        }
+    }
+
+    fn load(
+        dir: &Path,
+        manifest: &PluginManifest,
+        sandbox: Arc<dyn Sandbox>,
+    ) -> Arc<dyn PluginBackend> {
+        std::fs::write(dir.join("plugin.sh"), SCRIPT).unwrap();
+        ProcessPlugin::load(manifest, dir, &json!({}), sandbox).unwrap()
+    }
+
+    #[test]
+    fn relative_commands_resolve_inside_the_plugin_dir() {
+        let dir = Path::new("/plugins/demo");
+        assert_eq!(resolve_command(dir, "./plugin.py"), dir.join("./plugin.py"));
+        assert_eq!(resolve_command(dir, "node"), PathBuf::from("node"));
+        assert_eq!(restart_delay(1), RESTART_BASE_DELAY);
+        assert_eq!(restart_delay(20), RESTART_MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn tools_run_in_the_sandbox_and_restart_after_a_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = load(
+            dir.path(),
+            &manifest(vec![PluginCapability::Tools, PluginCapability::Hooks]),
+            Arc::new(EnvSandbox),
+        );
+        let registration = backend.registration();
+        assert_eq!(registration.services, vec!["poller".to_string()]);
+
+        let tools = plugin_tools(&backend);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "echo");
+        let result = tools[0].execute(json!({})).await.unwrap();
+        assert_eq!(result.output, "sandboxed=yes");
+
+        assert!(tools[0].execute(json!({ "crash": true })).await.is_err());
+        let mut restarted = None;
+        for _ in 0..100 {
+            if let Ok(result) = tools[0].execute(json!({})).await {
+                restarted = Some(result);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(restarted.unwrap().output, "sandboxed=yes");
+
+        drop(tools);
+        drop(backend);
+        let log = dir.path().join("services.log");
+        for _ in 0..100 {
+            if std::fs::read_to_string(&log).unwrap_or_default() == "start\nstart\nstop\n" {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!(
+            "unexpected service log: {:?}",
+            std::fs::read_to_string(&log)
+        );
+    }
+
+    #[tokio::test]
+    async fn hooks_follow_grants_and_can_cancel() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = load(
+            dir.path(),
+            &manifest(vec![PluginCapability::Hooks]),
+            Arc::new(NoopSandbox),
+        );
+        // `register_tool` was refused: Tools is not granted.
+        assert!(plugin_tools(&backend).is_empty());
+
+        let hooks = plugin_hooks(&backend);
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name(), "scripted:before_tool_call");
+        assert_eq!(hooks[0].priority(), 7);
+        match hooks[0]
+            .before_tool_call("file_read".into(), json!({ "path": "a" }))
+            .await
+        {
+            HookResult::Continue((name, args)) => {
+                assert_eq!(name, "file_read");
+                assert_eq!(args["checked"], true);
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+        match hooks[0].before_tool_call("shell".into(), json!({})).await {
+            HookResult::Cancel(reason) => assert_eq!(reason, "shell is blocked"),
+            HookResult::Continue(_) => panic!("expected cancel"),
+        }
+    }
+}
@@ -3,10 +3,12 @@
 //! Mirrors OpenClaw's `PluginRegistry` / `createPluginRegistry()`.
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use crate::hooks::HookHandler;
 use crate::tools::traits::Tool;
 
+use super::host::PluginBackend;
 use super::manifest::{PluginManifest, PluginToolManifest};
 
 /// Status of a loaded plugin.
@@ -78,6 +80,9 @@ pub struct PluginRegistry {
     pub plugins: Vec<PluginRecord>,
     pub tools: Vec<PluginToolRegistration>,
     pub hooks: Vec<PluginHookRegistration>,
+    /// Loaded component and process plugins; their tools and hooks are also
+    /// in `tools` / `hooks`, but unlike those they survive a registry clone.
+    pub backends: Vec<Arc<dyn PluginBackend>>,
     pub diagnostics: Vec<PluginDiagnostic>,
     manifests: HashMap<String, PluginManifest>,
     manifest_tools: Vec<PluginToolManifest>,
@@ -90,6 +95,7 @@ impl PluginRegistry {
             plugins: Vec::new(),
             tools: Vec::new(),
             hooks: Vec::new(),
+            backends: Vec::new(),
             diagnostics: Vec::new(),
             manifests: HashMap::new(),
             manifest_tools: Vec::new(),
@@ -164,6 +170,7 @@ impl Clone for PluginRegistry {
             // need manifest-derived indexes for routing checks.
             tools: Vec::new(),
             hooks: Vec::new(),
+            backends: self.backends.clone(),
             diagnostics: self.diagnostics.clone(),
             manifests: self.manifests.clone(),
             manifest_tools: self.manifest_tools.clone(),
@@ -195,6 +202,7 @@ mod tests {
                 }),
             }],
             providers: vec![provider.to_string()],
+            process: None,
         }
     }
 
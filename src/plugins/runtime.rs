@@ -1,10 +1,12 @@
 use anyhow::{Context, Result};
 use std::path::Path;
-use std::sync::{OnceLock, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 
+use super::host::PluginBackend;
 use super::manifest::PluginManifest;
 use super::registry::PluginRegistry;
-use crate::config::PluginsConfig;
+use crate::config::{PluginsConfig, SecurityConfig};
+use crate::security::AutonomyLevel;
 
 #[derive(Debug, Default)]
 pub struct PluginRuntime;
@@ -75,12 +77,23 @@ fn init_fingerprint_cell() -> &'static RwLock<Option<String>> {
     CELL.get_or_init(|| RwLock::new(None))
 }
 
-fn config_fingerprint(config: &PluginsConfig) -> String {
-    serde_json::to_string(config).unwrap_or_else(|_| "<serialize-error>".to_string())
+fn config_fingerprint(
+    config: &PluginsConfig,
+    security: &SecurityConfig,
+    autonomy: AutonomyLevel,
+) -> String {
+    // Process plugins run inside the configured sandbox, whose seccomp profile
+    // follows the autonomy level, so a change to either must respawn them too.
+    serde_json::to_string(&(config, &security.sandbox, autonomy))
+        .unwrap_or_else(|_| "<serialize-error>".to_string())
 }
 
-pub fn initialize_from_config(config: &PluginsConfig) -> Result<()> {
-    let fingerprint = config_fingerprint(config);
+pub fn initialize_from_config(
+    config: &PluginsConfig,
+    security: &SecurityConfig,
+    autonomy: AutonomyLevel,
+) -> Result<()> {
+    let fingerprint = config_fingerprint(config, security, autonomy);
     {
         let guard = init_fingerprint_cell()
             .read()
@@ -94,7 +107,15 @@ pub fn initialize_from_config(config: &PluginsConfig) -> Result<()> {
     }
 
     let runtime = PluginRuntime::new();
-    let registry = runtime.load_registry_from_config(config)?;
+    let mut registry = runtime.load_registry_from_config(config)?;
+    if config.enabled {
+        // Component and process plugins from the extension directories.
+        let sandbox = crate::security::create_sandbox(security, autonomy);
+        let loaded = super::loader::load_plugins(config, None, Vec::new(), sandbox);
+        registry.backends = loaded.backends;
+        registry.plugins.extend(loaded.plugins);
+        registry.diagnostics.extend(loaded.diagnostics);
+    }
     {
         let mut guard = registry_cell()
             .write()
@@ -118,6 +139,15 @@ pub fn current_registry() -> PluginRegistry {
         .clone()
 }
 
+/// Component and process plugins loaded by the last [`initialize_from_config`].
+pub fn plugin_backends() -> Vec<Arc<dyn PluginBackend>> {
+    registry_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .backends
+        .clone()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,7 +221,12 @@ description = "{tool} description"
             load_paths: vec![dir_a.path().to_string_lossy().to_string()],
             ..PluginsConfig::default()
         };
-        initialize_from_config(&cfg_a).expect("first initialization should succeed");
+        initialize_from_config(
+            &cfg_a,
+            &SecurityConfig::default(),
+            AutonomyLevel::Supervised,
+        )
+        .expect("first initialization should succeed");
         let reg_a = current_registry();
         assert!(reg_a.has_provider("reload-provider-a-for-runtime-test"));
 
@@ -200,7 +235,12 @@ description = "{tool} description"
             load_paths: vec![dir_b.path().to_string_lossy().to_string()],
             ..PluginsConfig::default()
         };
-        initialize_from_config(&cfg_b).expect("second initialization should succeed");
+        initialize_from_config(
+            &cfg_b,
+            &SecurityConfig::default(),
+            AutonomyLevel::Supervised,
+        )
+        .expect("second initialization should succeed");
         let reg_b = current_registry();
         assert!(reg_b.has_provider("reload-provider-b-for-runtime-test"));
         assert!(!reg_b.has_provider("reload-provider-a-for-runtime-test"));
@@ -136,6 +136,7 @@ mod tests {
                 wit_packages: vec![],
                 tools: vec![],
                 providers: vec![],
+                process: None,
             },
         };
         let mut api = PluginApi {
@@ -496,6 +496,7 @@ impl Provider for AnthropicProvider {
         let mut request = self
             .http_client()
             .post(format!("{}/v1/messages", self.base_url))
+            .headers(crate::observability::trace_context::headers())
             .header("anthropic-version", "2023-06-01")
             .header("content-type", "application/json")
             .json(&request);
@@ -545,6 +546,7 @@ impl Provider for AnthropicProvider {
         let req = self
             .http_client()
             .post(format!("{}/v1/messages", self.base_url))
+            .headers(crate::observability::trace_context::headers())
             .header("anthropic-version", "2023-06-01")
             .header("content-type", "application/json")
             .json(&native_request);
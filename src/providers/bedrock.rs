@@ -1054,6 +1054,7 @@ impl BedrockProvider {
         let mut request = self
             .http_client()
             .post(&url)
+            .headers(crate::observability::trace_context::headers())
             .header("content-type", "application/json")
             .header("x-amz-date", &amz_date)
             .header("authorization", &authorization);
@@ -1110,6 +1111,7 @@ impl BedrockProvider {
         let mut request = self
             .http_client()
             .post(&url)
+            .headers(crate::observability::trace_context::headers())
             .header("content-type", "application/json")
             .header("x-amz-date", &amz_date)
             .header("authorization", &authorization);
@@ -0,0 +1,1146 @@
+//! Record-and-replay provider cassettes.
+//!
+//! [`RecordingProvider`] wraps a real provider and writes every request and
+//! its outcome — text, tool calls, streamed chunks or the error — to a JSON
+//! cassette, scrubbing secrets on the way. [`ReplayProvider`] serves a
+//! cassette back without touching the network, so agent tests and user bug
+//! reports can be re-run deterministically.
+
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamError,
+    StreamOptions, StreamResult, TokenUsage, ToolCall, ToolsPayload,
+};
+use super::Provider;
+use crate::config::CassetteMatch;
+use crate::tools::ToolSpec;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Cassette format version written by [`RecordingProvider`].
+pub const CASSETTE_VERSION: u32 = 1;
+
+/// A recorded provider session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    pub version: u32,
+    /// Capabilities of the recorded provider, so replay takes the same
+    /// native-tool / streaming code paths.
+    #[serde(default)]
+    pub capabilities: RecordedCapabilities,
+    #[serde(default)]
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn new(capabilities: RecordedCapabilities) -> Self {
+        Self {
+            version: CASSETTE_VERSION,
+            capabilities,
+            interactions: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read cassette {}", path.display()))?;
+        let cassette: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse cassette {}", path.display()))?;
+        if cassette.version > CASSETTE_VERSION {
+            bail!(
+                "cassette {} has version {}, this build reads up to {CASSETTE_VERSION}",
+                path.display(),
+                cassette.version
+            );
+        }
+        Ok(cassette)
+    }
+
+    /// Write the cassette, replacing `path` atomically.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("failed to write cassette {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedCapabilities {
+    #[serde(default)]
+    pub native_tool_calling: bool,
+    #[serde(default)]
+    pub vision: bool,
+    #[serde(default)]
+    pub streaming: bool,
+}
+
+/// One request and what the provider did with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub outcome: RecordedOutcome,
+}
+
+/// Which provider entry point served the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestKind {
+    ChatWithSystem,
+    ChatWithHistory,
+    Chat,
+    ChatWithTools,
+    Stream,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub kind: RequestKind,
+    pub model: String,
+    pub temperature: f64,
+    pub messages: Vec<ChatMessage>,
+    /// Names of the tools offered with the request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedOutcome {
+    Response(RecordedResponse),
+    Stream { chunks: Vec<RecordedChunk> },
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_content: Option<String>,
+}
+
+impl RecordedResponse {
+    fn capture(response: &ChatResponse) -> Self {
+        let usage = response.usage.as_ref();
+        Self {
+            text: response.text.as_deref().map(scrub),
+            tool_calls: response
+                .tool_calls
+                .iter()
+                .map(|call| ToolCall {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    arguments: scrub(&call.arguments),
+                })
+                .collect(),
+            input_tokens: usage.and_then(|u| u.input_tokens),
+            output_tokens: usage.and_then(|u| u.output_tokens),
+            reasoning_content: response.reasoning_content.as_deref().map(scrub),
+        }
+    }
+
+    fn to_chat_response(&self) -> ChatResponse {
+        let usage =
+            (self.input_tokens.is_some() || self.output_tokens.is_some()).then(|| TokenUsage {
+                input_tokens: self.input_tokens,
+                output_tokens: self.output_tokens,
+            });
+        ChatResponse {
+            text: self.text.clone(),
+            tool_calls: self.tool_calls.clone(),
+            usage,
+            reasoning_content: self.reasoning_content.clone(),
+            quota_metadata: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedChunk {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub delta: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_final: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Remove credentials from anything written to a cassette.
+fn scrub(text: &str) -> String {
+    crate::agent::loop_::scrub_credentials(&super::scrub_secret_patterns(text))
+}
+
+fn scrub_messages(messages: &[ChatMessage]) -> Vec<ChatMessage> {
+    messages
+        .iter()
+        .map(|m| ChatMessage {
+            role: m.role.clone(),
+            content: scrub(&m.content),
+        })
+        .collect()
+}
+
+fn system_and_user(system_prompt: Option<&str>, message: &str) -> Vec<ChatMessage> {
+    system_prompt
+        .map(ChatMessage::system)
+        .into_iter()
+        .chain(std::iter::once(ChatMessage::user(message)))
+        .collect()
+}
+
+fn spec_names(tools: Option<&[ToolSpec]>) -> Vec<String> {
+    tools
+        .unwrap_or_default()
+        .iter()
+        .map(|t| t.name.clone())
+        .collect()
+}
+
+/// Tool names from provider-native JSON (`{"function": {"name"}}` or `{"name"}`).
+fn json_tool_names(tools: &[serde_json::Value]) -> Vec<String> {
+    tools
+        .iter()
+        .filter_map(|t| {
+            t.pointer("/function/name")
+                .or_else(|| t.get("name"))
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+fn request(
+    kind: RequestKind,
+    model: &str,
+    temperature: f64,
+    messages: &[ChatMessage],
+    tools: Vec<String>,
+) -> RecordedRequest {
+    RecordedRequest {
+        kind,
+        model: model.to_string(),
+        temperature,
+        messages: scrub_messages(messages),
+        tools,
+    }
+}
+
+/// Shared between the provider and any in-flight recorded streams.
+struct Recorder {
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+}
+
+impl Recorder {
+    fn push(&self, request: RecordedRequest, outcome: RecordedOutcome) {
+        let mut cassette = self.cassette.lock();
+        cassette.interactions.push(Interaction { request, outcome });
+        // Saved after every interaction so a crashed session still leaves
+        // a usable cassette behind.
+        if let Err(error) = cassette.save(&self.path) {
+            tracing::warn!("failed to save provider cassette: {error:#}");
+        }
+    }
+
+    fn push_result(&self, request: RecordedRequest, result: &Result<ChatResponse>) {
+        let outcome = match result {
+            Ok(response) => RecordedOutcome::Response(RecordedResponse::capture(response)),
+            Err(error) => RecordedOutcome::Error {
+                message: scrub(&format!("{error:#}")),
+            },
+        };
+        self.push(request, outcome);
+    }
+
+    fn push_text(&self, request: RecordedRequest, result: &Result<String>) {
+        let outcome = match result {
+            Ok(text) => RecordedOutcome::Response(RecordedResponse {
+                text: Some(scrub(text)),
+                ..RecordedResponse::default()
+            }),
+            Err(error) => RecordedOutcome::Error {
+                message: scrub(&format!("{error:#}")),
+            },
+        };
+        self.push(request, outcome);
+    }
+}
+
+/// Records every call to the wrapped provider into a cassette file.
+pub struct RecordingProvider {
+    inner: Box<dyn Provider>,
+    recorder: Arc<Recorder>,
+}
+
+impl RecordingProvider {
+    /// Start a new cassette at `path`, replacing any existing file once the
+    /// first interaction is recorded.
+    pub fn new(inner: Box<dyn Provider>, path: impl Into<PathBuf>) -> Self {
+        let cassette = Cassette::new(RecordedCapabilities {
+            native_tool_calling: inner.supports_native_tools(),
+            vision: inner.supports_vision(),
+            streaming: inner.supports_streaming(),
+        });
+        Self {
+            inner,
+            recorder: Arc::new(Recorder {
+                path: path.into(),
+                cassette: Mutex::new(cassette),
+            }),
+        }
+    }
+
+    /// Snapshot of everything recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.recorder.cassette.lock().clone()
+    }
+
+    fn record_stream(
+        &self,
+        request: RecordedRequest,
+        inner: stream::BoxStream<'static, StreamResult<StreamChunk>>,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let chunks = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&chunks);
+        let recorder = Arc::clone(&self.recorder);
+        let finish = stream::once(async move {
+            let chunks = std::mem::take(&mut *chunks.lock());
+            recorder.push(request, RecordedOutcome::Stream { chunks });
+        })
+        .filter_map(|()| async { None::<StreamResult<StreamChunk>> });
+
+        inner
+            .inspect(move |chunk| {
+                seen.lock().push(match chunk {
+                    Ok(chunk) => RecordedChunk {
+                        delta: scrub(&chunk.delta),
+                        is_final: chunk.is_final,
+                        error: None,
+                    },
+                    Err(error) => RecordedChunk {
+                        error: Some(scrub(&error.to_string())),
+                        ..RecordedChunk::default()
+                    },
+                });
+            })
+            .chain(finish)
+            .boxed()
+    }
+}
+
+#[async_trait]
+impl Provider for RecordingProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        let result = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await;
+        let messages = system_and_user(system_prompt, message);
+        self.recorder.push_text(
+            request(
+                RequestKind::ChatWithSystem,
+                model,
+                temperature,
+                &messages,
+                Vec::new(),
+            ),
+            &result,
+        );
+        result
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        let result = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await;
+        self.recorder.push_text(
+            request(
+                RequestKind::ChatWithHistory,
+                model,
+                temperature,
+                messages,
+                Vec::new(),
+            ),
+            &result,
+        );
+        result
+    }
+
+    async fn chat(
+        &self,
+        chat_request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        let result = self.inner.chat(chat_request, model, temperature).await;
+        self.recorder.push_result(
+            request(
+                RequestKind::Chat,
+                model,
+                temperature,
+                chat_request.messages,
+                spec_names(chat_request.tools),
+            ),
+            &result,
+        );
+        result
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        let result = self
+            .inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await;
+        self.recorder.push_result(
+            request(
+                RequestKind::ChatWithTools,
+                model,
+                temperature,
+                messages,
+                json_tool_names(tools),
+            ),
+            &result,
+        );
+        result
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let inner =
+            self.inner
+                .stream_chat_with_system(system_prompt, message, model, temperature, options);
+        let messages = system_and_user(system_prompt, message);
+        self.record_stream(
+            request(
+                RequestKind::Stream,
+                model,
+                temperature,
+                &messages,
+                Vec::new(),
+            ),
+            inner,
+        )
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let inner = self
+            .inner
+            .stream_chat_with_history(messages, model, temperature, options);
+        self.record_stream(
+            request(
+                RequestKind::Stream,
+                model,
+                temperature,
+                messages,
+                Vec::new(),
+            ),
+            inner,
+        )
+    }
+}
+
+/// Serves a recorded cassette back in place of a real provider.
+pub struct ReplayProvider {
+    cassette: Cassette,
+    matching: CassetteMatch,
+    used: Mutex<Vec<bool>>,
+}
+
+impl ReplayProvider {
+    pub fn new(cassette: Cassette, matching: CassetteMatch) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        Self {
+            cassette,
+            matching,
+            used: Mutex::new(used),
+        }
+    }
+
+    pub fn from_file(path: &Path, matching: CassetteMatch) -> Result<Self> {
+        Ok(Self::new(Cassette::load(path)?, matching))
+    }
+
+    /// Recorded interactions that have not been served yet.
+    pub fn remaining(&self) -> usize {
+        self.used.lock().iter().filter(|used| !**used).count()
+    }
+
+    fn take(&self, request: &RecordedRequest) -> Result<&RecordedOutcome> {
+        let mut used = self.used.lock();
+        let index = match self.matching {
+            CassetteMatch::Strict => {
+                let Some(next) = used.iter().position(|used| !used) else {
+                    bail!(
+                        "cassette exhausted: no recorded interaction left for {:?} request",
+                        request.kind
+                    );
+                };
+                if let Some(difference) =
+                    strict_difference(&self.cassette.interactions[next].request, request)
+                {
+                    bail!("cassette mismatch at interaction {next}: {difference}");
+                }
+                next
+            }
+            CassetteMatch::Fuzzy => {
+                let unused = || {
+                    self.cassette
+                        .interactions
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, interaction)| {
+                            !used[*i] && interaction.request.kind == request.kind
+                        })
+                };
+                let wanted = conversation(&request.messages);
+                let matched = unused()
+                    .find(|(_, interaction)| conversation(&interaction.request.messages) == wanted)
+                    .map(|(i, _)| i);
+                match matched.or_else(|| unused().next().map(|(i, _)| i)) {
+                    Some(i) => {
+                        if matched.is_none() {
+                            tracing::warn!(
+                                interaction = i,
+                                "cassette: no recorded {:?} request matches, replaying the next one in order",
+                                request.kind
+                            );
+                        }
+                        i
+                    }
+                    None => bail!(
+                        "cassette exhausted: no recorded interaction left for {:?} request",
+                        request.kind
+                    ),
+                }
+            }
+        };
+        used[index] = true;
+        Ok(&self.cassette.interactions[index].outcome)
+    }
+
+    fn take_response(&self, request: &RecordedRequest) -> Result<ChatResponse> {
+        match self.take(request)? {
+            RecordedOutcome::Response(response) => Ok(response.to_chat_response()),
+            RecordedOutcome::Error { message } => Err(anyhow::anyhow!(message.clone())),
+            RecordedOutcome::Stream { .. } => {
+                bail!("cassette recorded a stream where a response was requested")
+            }
+        }
+    }
+
+    fn take_text(&self, request: &RecordedRequest) -> Result<String> {
+        self.take_response(request)
+            .map(|response| response.text.unwrap_or_default())
+    }
+
+    fn take_stream(
+        &self,
+        request: &RecordedRequest,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let chunks: Vec<StreamResult<StreamChunk>> = match self.take(request) {
+            Ok(RecordedOutcome::Stream { chunks }) => chunks
+                .iter()
+                .map(|chunk| match &chunk.error {
+                    Some(error) => Err(StreamError::Provider(error.clone())),
+                    None => Ok(StreamChunk {
+                        delta: chunk.delta.clone(),
+                        is_final: chunk.is_final,
+                        token_count: 0,
+                    }),
+                })
+                .collect(),
+            Ok(RecordedOutcome::Error { message }) => {
+                vec![Err(StreamError::Provider(message.clone()))]
+            }
+            Ok(RecordedOutcome::Response(_)) => vec![Err(StreamError::Provider(
+                "cassette recorded a response where a stream was requested".into(),
+            ))],
+            Err(error) => vec![Err(StreamError::Provider(error.to_string()))],
+        };
+        stream::iter(chunks).boxed()
+    }
+}
+
+/// Describe the first way `actual` differs from `recorded`, if any.
+fn strict_difference(recorded: &RecordedRequest, actual: &RecordedRequest) -> Option<String> {
+    if recorded.kind != actual.kind {
+        return Some(format!(
+            "expected a {:?} request, got {:?}",
+            recorded.kind, actual.kind
+        ));
+    }
+    if recorded.model != actual.model {
+        return Some(format!(
+            "expected model {:?}, got {:?}",
+            recorded.model, actual.model
+        ));
+    }
+    if (recorded.temperature - actual.temperature).abs() > f64::EPSILON {
+        return Some(format!(
+            "expected temperature {}, got {}",
+            recorded.temperature, actual.temperature
+        ));
+    }
+    if recorded.tools != actual.tools {
+        return Some(format!(
+            "expected tools {:?}, got {:?}",
+            recorded.tools, actual.tools
+        ));
+    }
+    if recorded.messages.len() != actual.messages.len() {
+        return Some(format!(
+            "expected {} messages, got {}",
+            recorded.messages.len(),
+            actual.messages.len()
+        ));
+    }
+    recorded
+        .messages
+        .iter()
+        .zip(&actual.messages)
+        .position(|(r, a)| r.role != a.role || r.content != a.content)
+        .map(|i| {
+            format!(
+                "message {i} ({}) differs: expected {:?}, got {:?}",
+                actual.messages[i].role,
+                crate::util::truncate_with_ellipsis(&recorded.messages[i].content, 120),
+                crate::util::truncate_with_ellipsis(&actual.messages[i].content, 120),
+            )
+        })
+}
+
+/// Non-system messages with whitespace collapsed, for fuzzy matching.
+fn conversation(messages: &[ChatMessage]) -> Vec<(&str, String)> {
+    messages
+        .iter()
+        .filter(|m| m.role != "system")
+        .map(|m| {
+            (
+                m.role.as_str(),
+                m.content.split_whitespace().collect::<Vec<_>>().join(" "),
+            )
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: self.cassette.capabilities.native_tool_calling,
+            vision: self.cassette.capabilities.vision,
+        }
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.cassette.capabilities.streaming
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        let messages = system_and_user(system_prompt, message);
+        self.take_text(&request(
+            RequestKind::ChatWithSystem,
+            model,
+            temperature,
+            &messages,
+            Vec::new(),
+        ))
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        self.take_text(&request(
+            RequestKind::ChatWithHistory,
+            model,
+            temperature,
+            messages,
+            Vec::new(),
+        ))
+    }
+
+    async fn chat(
+        &self,
+        chat_request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        self.take_response(&request(
+            RequestKind::Chat,
+            model,
+            temperature,
+            chat_request.messages,
+            spec_names(chat_request.tools),
+        ))
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        self.take_response(&request(
+            RequestKind::ChatWithTools,
+            model,
+            temperature,
+            messages,
+            json_tool_names(tools),
+        ))
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let messages = system_and_user(system_prompt, message);
+        self.take_stream(&request(
+            RequestKind::Stream,
+            model,
+            temperature,
+            &messages,
+            Vec::new(),
+        ))
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.take_stream(&request(
+            RequestKind::Stream,
+            model,
+            temperature,
+            messages,
+            Vec::new(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Provider that answers from a script and fails when it runs out.
+    struct ScriptedProvider {
+        responses: Mutex<Vec<ChatResponse>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(responses: Vec<ChatResponse>) -> Self {
+            Self {
+                responses: Mutex::new(responses),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+            }
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            Ok(format!("echo: {message}"))
+        }
+
+        async fn chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<ChatResponse> {
+            let mut responses = self.responses.lock();
+            if responses.is_empty() {
+                bail!("upstream 500 with key sk-live-abcdef1234567890");
+            }
+            Ok(responses.remove(0))
+        }
+
+        fn stream_chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+            _options: StreamOptions,
+        ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+            stream::iter(vec![
+                Ok(StreamChunk::delta("Hel")),
+                Ok(StreamChunk::delta("lo")),
+                Ok(StreamChunk::final_chunk()),
+            ])
+            .boxed()
+        }
+    }
+
+    fn tool_call_response() -> ChatResponse {
+        ChatResponse {
+            text: Some(String::new()),
+            tool_calls: vec![ToolCall {
+                id: "call_1".into(),
+                name: "shell".into(),
+                arguments: r#"{"command":"ls"}"#.into(),
+            }],
+            usage: Some(TokenUsage {
+                input_tokens: Some(120),
+                output_tokens: Some(8),
+            }),
+            reasoning_content: None,
+            quota_metadata: None,
+        }
+    }
+
+    fn text_response(text: &str) -> ChatResponse {
+        ChatResponse {
+            text: Some(text.into()),
+            tool_calls: Vec::new(),
+            usage: None,
+            reasoning_content: None,
+            quota_metadata: None,
+        }
+    }
+
+    fn shell_spec() -> Vec<ToolSpec> {
+        vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }]
+    }
+
+    async fn record_session(path: &Path) -> Cassette {
+        let recording = RecordingProvider::new(
+            Box::new(ScriptedProvider::new(vec![
+                tool_call_response(),
+                text_response("Listed the files."),
+            ])),
+            path,
+        );
+        let tools = shell_spec();
+        let mut messages = vec![
+            ChatMessage::system("You are helpful. Today is Monday."),
+            ChatMessage::user("list files"),
+        ];
+        let first = recording
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: Some(&tools),
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.tool_calls[0].name, "shell");
+        messages.push(ChatMessage::assistant("calling shell"));
+        messages.push(ChatMessage::tool("a.txt b.txt"));
+        recording
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: Some(&tools),
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap();
+        let chunks: Vec<_> = recording
+            .stream_chat_with_system(
+                None,
+                "stream please",
+                "model-a",
+                0.2,
+                StreamOptions::new(true),
+            )
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 3);
+        let failed = recording
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                "model-a",
+                0.2,
+            )
+            .await;
+        assert!(failed.is_err());
+        recording.cassette()
+    }
+
+    #[tokio::test]
+    async fn records_responses_streams_and_errors_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let cassette = record_session(&path).await;
+
+        assert_eq!(cassette.interactions.len(), 4);
+        assert!(cassette.capabilities.native_tool_calling);
+        assert!(cassette.capabilities.streaming);
+        assert_eq!(cassette.interactions[0].request.tools, vec!["shell"]);
+        assert!(matches!(
+            &cassette.interactions[2].outcome,
+            RecordedOutcome::Stream { chunks } if chunks.len() == 3 && chunks[2].is_final
+        ));
+
+        let on_disk = Cassette::load(&path).unwrap();
+        assert_eq!(on_disk.interactions.len(), 4);
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("sk-live-abcdef1234567890"), "{raw}");
+        assert!(raw.contains("[REDACTED]"));
+    }
+
+    #[tokio::test]
+    async fn strict_replay_reproduces_the_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = record_session(&dir.path().join("session.json")).await;
+        let replay = ReplayProvider::new(cassette, CassetteMatch::Strict);
+        assert!(replay.supports_native_tools());
+
+        let tools = shell_spec();
+        let mut messages = vec![
+            ChatMessage::system("You are helpful. Today is Monday."),
+            ChatMessage::user("list files"),
+        ];
+        let first = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: Some(&tools),
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(first.usage.unwrap().input_tokens, Some(120));
+
+        messages.push(ChatMessage::assistant("calling shell"));
+        messages.push(ChatMessage::tool("a.txt b.txt"));
+        let second = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: Some(&tools),
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.text.as_deref(), Some("Listed the files."));
+
+        let streamed: Vec<String> = replay
+            .stream_chat_with_system(
+                None,
+                "stream please",
+                "model-a",
+                0.2,
+                StreamOptions::new(true),
+            )
+            .map(|chunk| chunk.unwrap().delta)
+            .collect()
+            .await;
+        assert_eq!(streamed.concat(), "Hello");
+
+        let err = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("upstream 500"), "{err}");
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn strict_replay_reports_the_first_difference() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = record_session(&dir.path().join("session.json")).await;
+        let replay = ReplayProvider::new(cassette, CassetteMatch::Strict);
+
+        let tools = shell_spec();
+        let messages = vec![
+            ChatMessage::system("You are helpful. Today is Tuesday."),
+            ChatMessage::user("list files"),
+        ];
+        let err = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: Some(&tools),
+                },
+                "model-a",
+                0.2,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("interaction 0"), "{err}");
+        assert!(err.contains("message 0 (system) differs"), "{err}");
+        assert_eq!(replay.remaining(), 4);
+    }
+
+    #[tokio::test]
+    async fn fuzzy_replay_ignores_system_prompt_model_and_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = record_session(&dir.path().join("session.json")).await;
+        let replay = ReplayProvider::new(cassette, CassetteMatch::Fuzzy);
+
+        let tools = shell_spec();
+        let messages = vec![
+            ChatMessage::system("A different prompt built by a newer release."),
+            ChatMessage::user("list files"),
+            ChatMessage::assistant("calling   shell"),
+            ChatMessage::tool("a.txt b.txt"),
+        ];
+        // The follow-up request is matched first by its conversation.
+        let second = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: Some(&tools),
+                },
+                "model-b",
+                0.9,
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.text.as_deref(), Some("Listed the files."));
+
+        let first = replay
+            .chat(
+                ChatRequest {
+                    messages: &messages[..2],
+                    tools: Some(&tools),
+                },
+                "model-b",
+                0.9,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.tool_calls.len(), 1);
+        assert_eq!(replay.remaining(), 2);
+    }
+
+    #[tokio::test]
+    async fn replay_fails_once_the_cassette_is_exhausted() {
+        let replay = ReplayProvider::new(
+            Cassette::new(RecordedCapabilities::default()),
+            CassetteMatch::Fuzzy,
+        );
+        let err = replay
+            .chat_with_system(None, "hello", "model-a", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cassette exhausted"), "{err}");
+    }
+}
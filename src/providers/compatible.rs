@@ -1295,7 +1295,13 @@ impl OpenAiCompatibleProvider {
         let url = self.responses_url();
 
         let response = self
-            .apply_auth_header(self.http_client().post(&url).json(&request), credential)
+            .apply_auth_header(
+                self.http_client()
+                    .post(&url)
+                    .headers(crate::observability::trace_context::headers())
+                    .json(&request),
+                credential,
+            )
             .send()
             .await?;
 
@@ -1685,7 +1691,13 @@ impl Provider for OpenAiCompatibleProvider {
         }
 
         let response = match self
-            .apply_auth_header(self.http_client().post(&url).json(&request), credential)
+            .apply_auth_header(
+                self.http_client()
+                    .post(&url)
+                    .headers(crate::observability::trace_context::headers())
+                    .json(&request),
+                credential,
+            )
             .send()
             .await
         {
@@ -1802,7 +1814,13 @@ impl Provider for OpenAiCompatibleProvider {
 
         let url = self.chat_completions_url();
         let response = match self
-            .apply_auth_header(self.http_client().post(&url).json(&request), credential)
+            .apply_auth_header(
+                self.http_client()
+                    .post(&url)
+                    .headers(crate::observability::trace_context::headers())
+                    .json(&request),
+                credential,
+            )
             .send()
             .await
         {
@@ -1932,7 +1950,13 @@ impl Provider for OpenAiCompatibleProvider {
 
         let url = self.chat_completions_url();
         let response = match self
-            .apply_auth_header(self.http_client().post(&url).json(&request), credential)
+            .apply_auth_header(
+                self.http_client()
+                    .post(&url)
+                    .headers(crate::observability::trace_context::headers())
+                    .json(&request),
+                credential,
+            )
             .send()
             .await
         {
@@ -2055,7 +2079,10 @@ impl Provider for OpenAiCompatibleProvider {
         let url = self.chat_completions_url();
         let response = match self
             .apply_auth_header(
-                self.http_client().post(&url).json(&native_request),
+                self.http_client()
+                    .post(&url)
+                    .headers(crate::observability::trace_context::headers())
+                    .json(&native_request),
                 credential,
             )
             .send()
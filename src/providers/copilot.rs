@@ -373,6 +373,7 @@ impl CopilotProvider {
         let mut req = self
             .http_client()
             .post(&url)
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {token}"))
             .json(&request);
 
@@ -883,7 +883,11 @@ impl GeminiProvider {
         project: Option<&str>,
         oauth_token: Option<&str>,
     ) -> reqwest::RequestBuilder {
-        let req = self.http_client().post(url).json(request);
+        let req = self
+            .http_client()
+            .post(url)
+            .headers(crate::observability::trace_context::headers())
+            .json(request);
         match auth {
             GeminiAuth::OAuthToken(_) | GeminiAuth::ManagedOAuth => {
                 let token = oauth_token.unwrap_or_default();
@@ -905,6 +909,7 @@ impl GeminiProvider {
                 };
                 self.http_client()
                     .post(url)
+                    .headers(crate::observability::trace_context::headers())
                     .json(&internal_request)
                     .bearer_auth(token)
             }
@@ -19,6 +19,7 @@
 pub mod anthropic;
 pub mod backoff;
 pub mod bedrock;
+pub mod cassette;
 pub mod compatible;
 pub mod copilot;
 pub mod cursor;
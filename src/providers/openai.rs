@@ -348,6 +348,7 @@ impl Provider for OpenAiProvider {
         let response = self
             .http_client()
             .post(format!("{}/chat/completions", self.base_url))
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .json(&request)
             .send()
@@ -391,6 +392,7 @@ impl Provider for OpenAiProvider {
         let response = self
             .http_client()
             .post(format!("{}/chat/completions", self.base_url))
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .json(&native_request)
             .send()
@@ -461,6 +463,7 @@ impl Provider for OpenAiProvider {
         let response = self
             .http_client()
             .post(format!("{}/chat/completions", self.base_url))
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .json(&native_request)
             .send()
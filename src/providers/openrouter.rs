@@ -368,6 +368,7 @@ impl Provider for OpenRouterProvider {
         let response = self
             .http_client()
             .post("https://openrouter.ai/api/v1/chat/completions")
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -419,6 +420,7 @@ impl Provider for OpenRouterProvider {
         let response = self
             .http_client()
             .post("https://openrouter.ai/api/v1/chat/completions")
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -468,6 +470,7 @@ impl Provider for OpenRouterProvider {
         let response = self
             .http_client()
             .post("https://openrouter.ai/api/v1/chat/completions")
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -563,6 +566,7 @@ impl Provider for OpenRouterProvider {
         let response = self
             .http_client()
             .post("https://openrouter.ai/api/v1/chat/completions")
+            .headers(crate::observability::trace_context::headers())
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
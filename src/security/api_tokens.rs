@@ -0,0 +1,550 @@
+//! Named, scoped API tokens for gateway automation.
+//!
+//! Pairing tokens grant full access to every authenticated route. API tokens
+//! are meant for CI jobs and scripts instead: each one has a name, a set of
+//! [`ApiScope`]s, an optional expiry, an optional IP allowlist and a
+//! per-token rate limit. Tokens are shown once at creation and stored only
+//! as SHA-256 hashes in `{workspace}/api_tokens.db`.
+//!
+//! Token format: `zc_<id>_<secret>`. The prefix lets the gateway tell API
+//! tokens apart from pairing and session tokens without a lookup.
+
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Prefix shared by every API token.
+pub const API_TOKEN_PREFIX: &str = "zc_";
+
+/// File name of the token database inside the workspace.
+pub const API_TOKEN_DB: &str = "api_tokens.db";
+
+/// Length of the public token ID (hex characters).
+const TOKEN_ID_LEN: usize = 12;
+
+/// What an API token may do.
+///
+/// `admin` implies every other scope, `read` covers read-only status and
+/// dashboard routes, and a `:write` scope implies the matching `:read`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiScope {
+    #[serde(rename = "chat")]
+    Chat,
+    #[serde(rename = "memory:read")]
+    MemoryRead,
+    #[serde(rename = "memory:write")]
+    MemoryWrite,
+    #[serde(rename = "cron:read")]
+    CronRead,
+    #[serde(rename = "cron:write")]
+    CronWrite,
+    #[serde(rename = "read")]
+    Read,
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl ApiScope {
+    pub const ALL: [Self; 7] = [
+        Self::Chat,
+        Self::MemoryRead,
+        Self::MemoryWrite,
+        Self::CronRead,
+        Self::CronWrite,
+        Self::Read,
+        Self::Admin,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Chat => "chat",
+            Self::MemoryRead => "memory:read",
+            Self::MemoryWrite => "memory:write",
+            Self::CronRead => "cron:read",
+            Self::CronWrite => "cron:write",
+            Self::Read => "read",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Whether holding `self` satisfies a route that requires `required`.
+    pub fn grants(self, required: Self) -> bool {
+        self == required
+            || self == Self::Admin
+            || matches!(
+                (self, required),
+                (Self::MemoryWrite, Self::MemoryRead) | (Self::CronWrite, Self::CronRead)
+            )
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == raw)
+            .with_context(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|s| s.as_str()).collect();
+                format!(
+                    "Unknown scope '{raw}'. Expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Parameters for [`ApiTokenStore::create`].
+#[derive(Debug, Clone, Default)]
+pub struct NewApiToken {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    /// Unix timestamp after which the token stops working.
+    pub expires_at: Option<i64>,
+    /// IPs or CIDR ranges the token may be used from. Empty allows any.
+    pub ip_allowlist: Vec<String>,
+    /// Requests per minute. `0` means unlimited.
+    pub rate_limit_per_minute: u32,
+}
+
+/// A stored API token. Never contains the secret.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    pub ip_allowlist: Vec<String>,
+    pub rate_limit_per_minute: u32,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+    pub last_used_at: Option<i64>,
+    pub last_used_ip: Option<String>,
+    pub request_count: u64,
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiToken {
+    /// Not revoked and not expired at `now`.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires| now < expires)
+    }
+
+    pub fn has_scope(&self, required: ApiScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+
+    /// Whether a request from `ip` is allowed. An unknown client address
+    /// only passes when the token has no allowlist.
+    pub fn allows_ip(&self, ip: Option<IpAddr>) -> bool {
+        if self.ip_allowlist.is_empty() {
+            return true;
+        }
+        let Some(ip) = ip else {
+            return false;
+        };
+        self.ip_allowlist
+            .iter()
+            .filter_map(|entry| parse_allowlist_entry(entry).ok())
+            .any(|cidr| crate::tools::url_validation::cidr_contains_ip(cidr, ip))
+    }
+
+    /// Lifecycle label for listings.
+    pub fn status(&self, now: i64) -> &'static str {
+        if self.revoked_at.is_some() {
+            "revoked"
+        } else if self.is_active(now) {
+            "active"
+        } else {
+            "expired"
+        }
+    }
+}
+
+/// Accepts a bare IP (a single-host range) or CIDR notation.
+fn parse_allowlist_entry(raw: &str) -> Result<(IpAddr, u8)> {
+    let raw = raw.trim();
+    if raw.contains('/') {
+        return crate::tools::url_validation::parse_cidr(raw);
+    }
+    let ip: IpAddr = raw
+        .parse()
+        .with_context(|| format!("Invalid IP address '{raw}'"))?;
+    let prefix = if ip.is_ipv4() { 32 } else { 128 };
+    Ok((ip, prefix))
+}
+
+/// Thread-safe SQLite-backed store for API tokens.
+pub struct ApiTokenStore {
+    conn: Mutex<Connection>,
+}
+
+impl ApiTokenStore {
+    /// Open (or create) the token database in `workspace_dir`.
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(workspace_dir)?;
+        let path = workspace_dir.join(API_TOKEN_DB);
+        let conn = Connection::open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")?;
+        Self::init_tables(&conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn init_tables(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS api_tokens (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                token_hash TEXT NOT NULL UNIQUE,
+                scopes TEXT NOT NULL,
+                ip_allowlist TEXT NOT NULL DEFAULT '[]',
+                rate_limit_per_minute INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                expires_at INTEGER,
+                last_used_at INTEGER,
+                last_used_ip TEXT,
+                request_count INTEGER NOT NULL DEFAULT 0,
+                revoked_at INTEGER
+            );",
+        )?;
+        Ok(())
+    }
+
+    /// Create a token. Returns the stored record and the plaintext token,
+    /// which is not recoverable afterwards.
+    pub fn create(&self, spec: NewApiToken) -> Result<(ApiToken, String)> {
+        let name = spec.name.trim();
+        if name.is_empty() {
+            bail!("Token name must not be empty");
+        }
+        if spec.scopes.is_empty() {
+            bail!("A token needs at least one scope");
+        }
+        for entry in &spec.ip_allowlist {
+            parse_allowlist_entry(entry)
+                .with_context(|| format!("Invalid IP allowlist entry '{entry}'"))?;
+        }
+
+        let mut scopes = spec.scopes;
+        scopes.sort_by_key(|scope| scope.as_str());
+        scopes.dedup();
+
+        let random = uuid::Uuid::new_v4().simple().to_string();
+        let id = random[..TOKEN_ID_LEN].to_string();
+        let secret = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        let token = format!("{API_TOKEN_PREFIX}{id}_{secret}");
+        let created_at = chrono::Utc::now().timestamp();
+
+        let conn = self.conn.lock();
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM api_tokens WHERE name = ?1)",
+            params![name],
+            |row| row.get(0),
+        )?;
+        if exists {
+            bail!("An API token named '{name}' already exists");
+        }
+        conn.execute(
+            "INSERT INTO api_tokens
+                (id, name, token_hash, scopes, ip_allowlist, rate_limit_per_minute, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                id,
+                name,
+                hash_token(&token),
+                serde_json::to_string(&scopes)?,
+                serde_json::to_string(&spec.ip_allowlist)?,
+                spec.rate_limit_per_minute,
+                created_at,
+                spec.expires_at,
+            ],
+        )?;
+
+        let record = ApiToken {
+            id,
+            name: name.to_string(),
+            scopes,
+            ip_allowlist: spec.ip_allowlist,
+            rate_limit_per_minute: spec.rate_limit_per_minute,
+            created_at,
+            expires_at: spec.expires_at,
+            last_used_at: None,
+            last_used_ip: None,
+            request_count: 0,
+            revoked_at: None,
+        };
+        Ok((record, token))
+    }
+
+    /// All tokens, including revoked and expired ones, oldest first.
+    pub fn list(&self) -> Result<Vec<ApiToken>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!("{SELECT_TOKEN} ORDER BY created_at, name"))?;
+        let rows = stmt.query_map([], row_to_token)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    }
+
+    /// Revoke a token by ID or name. Returns `false` when no active token
+    /// matched.
+    pub fn revoke(&self, id_or_name: &str) -> Result<bool> {
+        let conn = self.conn.lock();
+        let changed = conn.execute(
+            "UPDATE api_tokens SET revoked_at = ?1
+             WHERE (id = ?2 OR name = ?2) AND revoked_at IS NULL",
+            params![chrono::Utc::now().timestamp(), id_or_name.trim()],
+        )?;
+        Ok(changed > 0)
+    }
+
+    /// Look up the token a client presented. Returns the record whether or
+    /// not it is still active; callers check [`ApiToken::is_active`].
+    pub fn find(&self, token: &str) -> Result<Option<ApiToken>> {
+        if !token.starts_with(API_TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let conn = self.conn.lock();
+        conn.query_row(
+            &format!("{SELECT_TOKEN} WHERE token_hash = ?1"),
+            params![hash_token(token)],
+            row_to_token,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Record a request made with token `id`.
+    pub fn record_use(&self, id: &str, ip: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "UPDATE api_tokens
+             SET last_used_at = ?1, last_used_ip = ?2, request_count = request_count + 1
+             WHERE id = ?3",
+            params![chrono::Utc::now().timestamp(), ip, id],
+        )?;
+        Ok(())
+    }
+}
+
+const SELECT_TOKEN: &str = "SELECT id, name, scopes, ip_allowlist, rate_limit_per_minute,
+        created_at, expires_at, last_used_at, last_used_ip, request_count, revoked_at
+     FROM api_tokens";
+
+fn row_to_token(row: &rusqlite::Row<'_>) -> rusqlite::Result<ApiToken> {
+    let scopes: String = row.get(2)?;
+    let ip_allowlist: String = row.get(3)?;
+    let request_count: i64 = row.get(9)?;
+    Ok(ApiToken {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        scopes: serde_json::from_str(&scopes).unwrap_or_default(),
+        ip_allowlist: serde_json::from_str(&ip_allowlist).unwrap_or_default(),
+        rate_limit_per_minute: row.get(4)?,
+        created_at: row.get(5)?,
+        expires_at: row.get(6)?,
+        last_used_at: row.get(7)?,
+        last_used_ip: row.get(8)?,
+        request_count: u64::try_from(request_count).unwrap_or(0),
+        revoked_at: row.get(10)?,
+    })
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Handle `zeroclaw api-token <subcommand>` CLI commands.
+pub fn handle_command(
+    command: crate::ApiTokenCommands,
+    config: &crate::config::Config,
+) -> Result<()> {
+    let store = ApiTokenStore::open(&config.workspace_dir)?;
+    match command {
+        crate::ApiTokenCommands::Create {
+            name,
+            scopes,
+            expires_in_days,
+            allow_ip,
+            rate_limit,
+        } => {
+            let scopes = scopes
+                .iter()
+                .flat_map(|raw| raw.split(','))
+                .filter(|raw| !raw.trim().is_empty())
+                .map(ApiScope::from_str)
+                .collect::<Result<Vec<_>>>()?;
+            let expires_at = expires_in_days
+                .map(|days| chrono::Utc::now().timestamp() + i64::from(days) * 86_400);
+            let (record, token) = store.create(NewApiToken {
+                name,
+                scopes,
+                expires_at,
+                ip_allowlist: allow_ip,
+                rate_limit_per_minute: rate_limit,
+            })?;
+            println!("Created API token '{}' (id {}).", record.name, record.id);
+            println!("Scopes: {}", join_scopes(&record.scopes));
+            println!();
+            println!("  {token}");
+            println!();
+            println!("Store it now — it will not be shown again.");
+            Ok(())
+        }
+        crate::ApiTokenCommands::List { json } => {
+            let tokens = store.list()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&tokens)?);
+                return Ok(());
+            }
+            if tokens.is_empty() {
+                println!("No API tokens. Create one with `zeroclaw api-token create`.");
+                return Ok(());
+            }
+            let now = chrono::Utc::now().timestamp();
+            println!(
+                "{:<12}  {:<20}  {:<8}  {:<28}  {:<16}  {:>8}",
+                "id", "name", "status", "scopes", "last used", "requests"
+            );
+            for token in &tokens {
+                println!(
+                    "{:<12}  {:<20}  {:<8}  {:<28}  {:<16}  {:>8}",
+                    token.id,
+                    token.name,
+                    token.status(now),
+                    join_scopes(&token.scopes),
+                    token
+                        .last_used_at
+                        .map_or_else(|| "never".to_string(), format_timestamp),
+                    token.request_count
+                );
+            }
+            Ok(())
+        }
+        crate::ApiTokenCommands::Revoke { token } => {
+            if store.revoke(&token)? {
+                println!("Revoked API token '{token}'.");
+                Ok(())
+            } else {
+                bail!("No active API token with ID or name '{token}'")
+            }
+        }
+    }
+}
+
+fn join_scopes(scopes: &[ApiScope]) -> String {
+    scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_timestamp(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0).map_or_else(
+        || secs.to_string(),
+        |t| t.format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn spec(name: &str, scopes: &[ApiScope]) -> NewApiToken {
+        NewApiToken {
+            name: name.to_string(),
+            scopes: scopes.to_vec(),
+            ..NewApiToken::default()
+        }
+    }
+
+    #[test]
+    fn scopes_parse_and_imply_narrower_scopes() {
+        assert_eq!(
+            "memory:write".parse::<ApiScope>().unwrap(),
+            ApiScope::MemoryWrite
+        );
+        assert!("memory".parse::<ApiScope>().is_err());
+
+        assert!(ApiScope::MemoryWrite.grants(ApiScope::MemoryRead));
+        assert!(!ApiScope::MemoryRead.grants(ApiScope::MemoryWrite));
+        assert!(ApiScope::CronWrite.grants(ApiScope::CronRead));
+        assert!(!ApiScope::Read.grants(ApiScope::CronRead));
+        assert!(ApiScope::ALL.iter().all(|s| ApiScope::Admin.grants(*s)));
+    }
+
+    #[test]
+    fn tokens_are_hashed_and_revocable() {
+        let tmp = TempDir::new().unwrap();
+        let store = ApiTokenStore::open(tmp.path()).unwrap();
+        let (record, token) = store
+            .create(spec("ci", &[ApiScope::Chat, ApiScope::MemoryRead]))
+            .unwrap();
+        assert!(token.starts_with(&format!("{API_TOKEN_PREFIX}{}_", record.id)));
+        assert!(store.create(spec("ci", &[ApiScope::Chat])).is_err());
+
+        let found = store.find(&token).unwrap().unwrap();
+        assert_eq!(found, record);
+        assert!(store.find("zc_unknown_secret").unwrap().is_none());
+        assert!(store.find("pairing-token").unwrap().is_none());
+
+        store.record_use(&record.id, Some("10.0.0.5")).unwrap();
+        let used = store.find(&token).unwrap().unwrap();
+        assert_eq!(used.request_count, 1);
+        assert_eq!(used.last_used_ip.as_deref(), Some("10.0.0.5"));
+
+        assert!(store.revoke("ci").unwrap());
+        assert!(!store.revoke("ci").unwrap());
+        let revoked = store.find(&token).unwrap().unwrap();
+        assert!(!revoked.is_active(chrono::Utc::now().timestamp()));
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn expiry_and_ip_allowlist_are_enforced() {
+        let tmp = TempDir::new().unwrap();
+        let store = ApiTokenStore::open(tmp.path()).unwrap();
+        let (token, _) = store
+            .create(NewApiToken {
+                expires_at: Some(1_000),
+                ip_allowlist: vec!["10.0.0.0/8".into(), "192.168.1.7".into()],
+                ..spec("deploy", &[ApiScope::CronWrite])
+            })
+            .unwrap();
+        assert!(token.is_active(999));
+        assert!(!token.is_active(1_000));
+        assert_eq!(token.status(1_000), "expired");
+
+        assert!(token.allows_ip("10.2.3.4".parse().ok()));
+        assert!(token.allows_ip("192.168.1.7".parse().ok()));
+        assert!(!token.allows_ip("192.168.1.8".parse().ok()));
+        assert!(!token.allows_ip(None));
+
+        let bad = NewApiToken {
+            ip_allowlist: vec!["10.0.0.0/40".into()],
+            ..spec("bad", &[ApiScope::Read])
+        };
+        assert!(store.create(bad).is_err());
+    }
+}
@@ -21,6 +21,8 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    /// Gateway request made with a scoped API token
+    ApiRequest,
 }
 
 /// Actor information (who performed the action)
@@ -2,10 +2,14 @@
 
 use crate::config::{SandboxBackend, SecurityConfig};
 use crate::security::traits::Sandbox;
+use crate::security::AutonomyLevel;
 use std::sync::Arc;
 
-/// Create a sandbox based on auto-detection or explicit config
-pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
+/// Create a sandbox based on auto-detection or explicit config.
+///
+/// `level` selects the seccomp profile whenever the seccomp backend is used,
+/// whether requested explicitly or picked by auto-detection.
+pub fn create_sandbox(config: &SecurityConfig, level: AutonomyLevel) -> Arc<dyn Sandbox> {
     let backend = &config.sandbox.backend;
 
     // If explicitly disabled, return noop
@@ -64,15 +68,66 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
             tracing::warn!("Docker requested but not available, falling back to application-layer");
             Arc::new(super::traits::NoopSandbox)
         }
+        SandboxBackend::Seccomp => {
+            #[cfg(target_os = "linux")]
+            {
+                if let Ok(sandbox) = super::seccomp::SeccompSandbox::from_config(config, level) {
+                    return Arc::new(sandbox);
+                }
+            }
+            #[cfg(not(target_os = "linux"))]
+            let _ = level;
+            tracing::warn!("Seccomp requested but not available, falling back to application-layer");
+            Arc::new(super::traits::NoopSandbox)
+        }
         SandboxBackend::Auto | SandboxBackend::None => {
             // Auto-detect best available
-            detect_best_sandbox()
+            detect_best_sandbox(config, level)
         }
     }
 }
 
+/// Create the sandbox applied to shell and process tool children.
+///
+/// Only the built-in seccomp backend hooks into the child directly, so this
+/// returns `None` unless `backend = "seccomp"` is selected; the seccomp
+/// profile follows the agent's autonomy level.
+pub fn create_command_sandbox(
+    config: &SecurityConfig,
+    level: AutonomyLevel,
+) -> Option<Arc<dyn Sandbox>> {
+    if !matches!(config.sandbox.backend, SandboxBackend::Seccomp)
+        || config.sandbox.enabled == Some(false)
+    {
+        return None;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        match super::seccomp::SeccompSandbox::from_config(config, level) {
+            Ok(sandbox) => {
+                tracing::info!(
+                    profile = ?sandbox.profile(),
+                    cgroup = sandbox.uses_cgroup(),
+                    "Seccomp sandbox enabled for shell/process tools"
+                );
+                return Some(Arc::new(sandbox));
+            }
+            Err(error) => {
+                tracing::warn!("Seccomp sandbox unavailable for shell/process tools: {error}");
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = level;
+        tracing::warn!("Seccomp sandbox requested but only supported on Linux");
+    }
+    None
+}
+
 /// Auto-detect the best available sandbox
-fn detect_best_sandbox() -> Arc<dyn Sandbox> {
+fn detect_best_sandbox(config: &SecurityConfig, level: AutonomyLevel) -> Arc<dyn Sandbox> {
     #[cfg(target_os = "linux")]
     {
         // Try Landlock first (native, no dependencies)
@@ -89,7 +144,15 @@ fn detect_best_sandbox() -> Arc<dyn Sandbox> {
             tracing::info!("Firejail sandbox enabled");
             return Arc::new(sandbox);
         }
+
+        // Built-in seccomp filter needs no external tools
+        if let Ok(sandbox) = super::seccomp::SeccompSandbox::from_config(config, level) {
+            tracing::info!(profile = ?sandbox.profile(), "Seccomp sandbox enabled");
+            return Arc::new(sandbox);
+        }
     }
+    #[cfg(not(target_os = "linux"))]
+    let _ = (config, level);
 
     #[cfg(target_os = "macos")]
     {
@@ -121,7 +184,9 @@ mod tests {
 
     #[test]
     fn detect_best_sandbox_returns_something() {
-        let sandbox = detect_best_sandbox();
+        let mut config = SecurityConfig::default();
+        config.sandbox.seccomp.use_cgroup = false;
+        let sandbox = detect_best_sandbox(&config, AutonomyLevel::Supervised);
         // Should always return at least NoopSandbox
         assert!(sandbox.is_available());
     }
@@ -133,24 +198,77 @@ mod tests {
                 enabled: Some(false),
                 backend: SandboxBackend::None,
                 firejail_args: Vec::new(),
+                ..Default::default()
             },
             ..Default::default()
         };
-        let sandbox = create_sandbox(&config);
+        let sandbox = create_sandbox(&config, AutonomyLevel::Supervised);
         assert_eq!(sandbox.name(), "none");
     }
 
+    #[test]
+    fn command_sandbox_requires_seccomp_backend() {
+        let config = SecurityConfig::default();
+        assert!(create_command_sandbox(&config, AutonomyLevel::Supervised).is_none());
+
+        let mut config = SecurityConfig::default();
+        config.sandbox.backend = SandboxBackend::Seccomp;
+        config.sandbox.enabled = Some(false);
+        assert!(create_command_sandbox(&config, AutonomyLevel::Supervised).is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn seccomp_backend_uses_configured_autonomy_profile() {
+        let mut config = SecurityConfig::default();
+        config.sandbox.backend = SandboxBackend::Seccomp;
+        config.sandbox.seccomp.use_cgroup = false;
+        let sandbox = create_sandbox(&config, AutonomyLevel::ReadOnly);
+        if sandbox.name() != "seccomp" {
+            return; // seccomp unavailable on this host
+        }
+        let mut cmd = std::process::Command::new("true");
+        sandbox.wrap_command(&mut cmd).unwrap();
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let profile = args.iter().position(|a| a == "--profile").unwrap() + 1;
+        assert_eq!(args[profile], "strict");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn auto_detected_seccomp_uses_autonomy_profile() {
+        let mut config = SecurityConfig::default();
+        config.sandbox.seccomp.use_cgroup = false;
+        let sandbox = detect_best_sandbox(&config, AutonomyLevel::ReadOnly);
+        if sandbox.name() != "seccomp" {
+            return; // another backend won, or seccomp is unavailable here
+        }
+        let mut cmd = std::process::Command::new("true");
+        sandbox.wrap_command(&mut cmd).unwrap();
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let profile = args.iter().position(|a| a == "--profile").unwrap() + 1;
+        assert_eq!(args[profile], "strict");
+    }
+
     #[test]
     fn auto_mode_detects_something() {
-        let config = SecurityConfig {
+        let mut config = SecurityConfig {
             sandbox: SandboxConfig {
                 enabled: None, // Auto-detect
                 backend: SandboxBackend::Auto,
                 firejail_args: Vec::new(),
+                ..Default::default()
             },
             ..Default::default()
         };
-        let sandbox = create_sandbox(&config);
+        config.sandbox.seccomp.use_cgroup = false;
+        let sandbox = create_sandbox(&config, AutonomyLevel::Supervised);
         // Should return some sandbox (at least NoopSandbox)
         assert!(sandbox.is_available());
     }
@@ -251,6 +251,41 @@ impl EstopManager {
     }
 }
 
+/// Interval at which [`spawn_turn_cancellation_watcher`] re-reads the state file.
+const ESTOP_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Cancel this process's in-flight agent turns whenever `kill_all` is engaged
+/// in the persisted state, including when a separate `zeroclaw estop`
+/// invocation engaged it. Turns started while the stop stays engaged are
+/// cancelled on the next poll.
+pub fn spawn_turn_cancellation_watcher(
+    config: &EstopConfig,
+    config_dir: &Path,
+) -> tokio::task::JoinHandle<()> {
+    let state_path = resolve_state_file_path(config_dir, &config.state_file);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ESTOP_WATCH_INTERVAL);
+        loop {
+            interval.tick().await;
+            if persisted_kill_all(&state_path) {
+                let stopped = crate::agent::turn_control::cancel_all();
+                if stopped > 0 {
+                    tracing::warn!(stopped, "Estop kill-all engaged; cancelled in-flight agent turns");
+                }
+            }
+        }
+    })
+}
+
+/// Whether the state file at `state_path` has `kill_all` engaged. A missing
+/// file means no stop; an unparsable one fails closed like [`EstopManager::load`].
+fn persisted_kill_all(state_path: &Path) -> bool {
+    match fs::read_to_string(state_path) {
+        Ok(raw) => serde_json::from_str::<EstopState>(&raw).map_or(true, |state| state.kill_all),
+        Err(_) => false,
+    }
+}
+
 pub fn resolve_state_file_path(config_dir: &Path, state_file: &str) -> PathBuf {
     let expanded = shellexpand::tilde(state_file).into_owned();
     let path = PathBuf::from(expanded);
@@ -313,6 +348,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn persisted_kill_all_tracks_state_file() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("estop-state.json");
+        assert!(!persisted_kill_all(&state_path));
+
+        let cfg = estop_config(&state_path);
+        let mut manager = EstopManager::load(&cfg, dir.path()).unwrap();
+        manager.engage(EstopLevel::NetworkKill).unwrap();
+        assert!(!persisted_kill_all(&state_path));
+
+        manager.engage(EstopLevel::KillAll).unwrap();
+        assert!(persisted_kill_all(&state_path));
+
+        fs::write(&state_path, "{not-json").unwrap();
+        assert!(persisted_kill_all(&state_path));
+    }
+
     #[test]
     fn estop_levels_compose_and_resume() {
         let dir = tempdir().unwrap();
@@ -8,9 +8,9 @@
 //!
 //! OS-level isolation is provided through the [`Sandbox`] trait defined in
 //! [`traits`], with pluggable backends including Docker, Firejail, Bubblewrap,
-//! and Landlock. The [`create_sandbox`] function selects the best available
-//! backend at runtime. An [`AuditLogger`] records security-relevant events for
-//! forensic review.
+//! Landlock, and a built-in seccomp-bpf filter. The [`create_sandbox`] function
+//! selects the best available backend at runtime. An [`AuditLogger`] records
+//! security-relevant events for forensic review.
 //!
 //! # Extension
 //!
@@ -18,6 +18,7 @@
 //! register it in [`detect::create_sandbox`]. See `AGENTS.md` §7.5 for security
 //! change guidelines.
 
+pub mod api_tokens;
 pub mod audit;
 #[cfg(feature = "sandbox-bubblewrap")]
 pub mod bubblewrap;
@@ -46,6 +47,8 @@ pub mod prompt_guard;
 pub mod rate_limiter;
 pub mod remote_wipe;
 pub mod roles;
+#[cfg(target_os = "linux")]
+pub mod seccomp;
 pub mod secrets;
 pub mod sensitive_paths;
 pub mod syscall_anomaly;
@@ -125,6 +125,12 @@ impl RoleRegistry {
         Ok(registry)
     }
 
+    #[must_use]
+    pub fn contains(&self, role_name: &str) -> bool {
+        self.roles
+            .contains_key(&role_name.trim().to_ascii_lowercase())
+    }
+
     #[must_use]
     pub fn resolve_tool_access(
         &self,
@@ -172,6 +178,41 @@ impl RoleRegistry {
         }
     }
 
+    /// Whether `role_name` may call `tool_name` on a path that cannot ask
+    /// for a TOTP code, such as a dashboard chat turn: the tool must be
+    /// allowed and not gated behind TOTP.
+    #[must_use]
+    pub fn allows_without_totp(
+        &self,
+        role_name: &str,
+        tool_name: &str,
+        global_gated_actions: &[String],
+    ) -> bool {
+        let access = self.resolve_tool_access(role_name, tool_name, global_gated_actions);
+        access.allowed && !access.requires_totp
+    }
+
+    /// Whether `role_name` is `owner` or `admin`, or inherits from one of
+    /// them. Administrative gateway routes require such a role.
+    #[must_use]
+    pub fn is_admin(&self, role_name: &str) -> bool {
+        let mut name = role_name.trim().to_ascii_lowercase();
+        let mut seen = Vec::new();
+        loop {
+            if name == "owner" || name == "admin" {
+                return true;
+            }
+            let Some(parent) = self.roles.get(&name).and_then(|role| role.inherits.clone()) else {
+                return false;
+            };
+            if seen.contains(&parent) {
+                return false;
+            }
+            seen.push(parent.clone());
+            name = parent;
+        }
+    }
+
     fn resolve_allow_decision(
         &self,
         role: &RoleDefinition,
@@ -352,6 +393,29 @@ mod tests {
         assert!(!memory_forget.allowed);
     }
 
+    #[test]
+    fn admin_roles_include_descendants_of_owner_and_admin() {
+        let registry = RoleRegistry::from_config(&[SecurityRoleConfig {
+            name: "platform".to_string(),
+            inherits: Some("admin".to_string()),
+            ..SecurityRoleConfig::default()
+        }])
+        .expect("registry from config");
+
+        assert!(registry.is_admin("Owner"));
+        assert!(registry.is_admin("platform"));
+        assert!(!registry.is_admin("operator"));
+        assert!(!registry.is_admin("unknown"));
+    }
+
+    #[test]
+    fn totp_gated_tools_are_not_allowed_without_totp() {
+        let registry = RoleRegistry::built_in();
+        assert!(registry.allows_without_totp("operator", "file_read", &[]));
+        assert!(!registry.allows_without_totp("operator", "shell", &[]));
+        assert!(!registry.allows_without_totp("viewer", "shell", &[]));
+    }
+
     #[test]
     fn inheritance_cycle_is_rejected() {
         let result = RoleRegistry::from_config(&[
@@ -0,0 +1,730 @@
+//! Built-in seccomp-bpf sandbox (Linux, no external tools)
+//!
+//! Runs tool commands through a small launcher — the `zeroclaw` binary
+//! re-executed as the hidden `sandbox-exec` subcommand — which confines
+//! itself before `exec`ing the real command: it joins a cgroup v2 child group
+//! (memory and process-count limits) when the agent's own cgroup is
+//! delegated, sets CPU time (and memory, when no cgroup is available) through
+//! `setrlimit`, and installs a syscall allowlist. A disallowed syscall kills
+//! the child with `SIGSYS`, which [`Sandbox::describe_violation`] reports so
+//! the shell and process tools can feed it into the syscall anomaly detector.
+
+use crate::config::{ResourceLimitsConfig, SeccompProfile, SecurityConfig};
+use crate::security::traits::Sandbox;
+use crate::security::AutonomyLevel;
+use rustix::process::{getrlimit, setrlimit, Resource, Rlimit};
+use seccompiler::{
+    BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter,
+    SeccompRule, TargetArch,
+};
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Hidden CLI subcommand that applies the sandbox and execs the tool command.
+pub const LAUNCHER_SUBCOMMAND: &str = "sandbox-exec";
+
+#[cfg(target_arch = "x86_64")]
+const TARGET_ARCH: Option<TargetArch> = Some(TargetArch::x86_64);
+#[cfg(target_arch = "aarch64")]
+const TARGET_ARCH: Option<TargetArch> = Some(TargetArch::aarch64);
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const TARGET_ARCH: Option<TargetArch> = None;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const BASE_SYSCALLS: &[libc::c_long] = &[
+    // I/O and file descriptors
+    libc::SYS_read,
+    libc::SYS_write,
+    libc::SYS_readv,
+    libc::SYS_writev,
+    libc::SYS_pread64,
+    libc::SYS_pwrite64,
+    libc::SYS_close,
+    libc::SYS_close_range,
+    libc::SYS_lseek,
+    libc::SYS_dup,
+    libc::SYS_dup3,
+    libc::SYS_fcntl,
+    libc::SYS_ioctl,
+    libc::SYS_flock,
+    libc::SYS_fsync,
+    libc::SYS_fdatasync,
+    libc::SYS_sync,
+    libc::SYS_sync_file_range,
+    libc::SYS_pipe2,
+    libc::SYS_splice,
+    libc::SYS_tee,
+    libc::SYS_copy_file_range,
+    libc::SYS_pselect6,
+    libc::SYS_ppoll,
+    libc::SYS_epoll_create1,
+    libc::SYS_epoll_ctl,
+    libc::SYS_epoll_pwait,
+    libc::SYS_eventfd2,
+    libc::SYS_signalfd4,
+    libc::SYS_timerfd_create,
+    libc::SYS_timerfd_settime,
+    libc::SYS_timerfd_gettime,
+    libc::SYS_inotify_init1,
+    libc::SYS_inotify_add_watch,
+    libc::SYS_inotify_rm_watch,
+    // Filesystem
+    libc::SYS_openat,
+    libc::SYS_openat2,
+    libc::SYS_newfstatat,
+    libc::SYS_fstat,
+    libc::SYS_statx,
+    libc::SYS_statfs,
+    libc::SYS_fstatfs,
+    libc::SYS_getdents64,
+    libc::SYS_getcwd,
+    libc::SYS_chdir,
+    libc::SYS_fchdir,
+    libc::SYS_mkdirat,
+    libc::SYS_mknodat,
+    libc::SYS_unlinkat,
+    libc::SYS_renameat,
+    libc::SYS_renameat2,
+    libc::SYS_linkat,
+    libc::SYS_symlinkat,
+    libc::SYS_readlinkat,
+    libc::SYS_faccessat,
+    libc::SYS_faccessat2,
+    libc::SYS_fchmod,
+    libc::SYS_fchmodat,
+    libc::SYS_fchown,
+    libc::SYS_fchownat,
+    libc::SYS_truncate,
+    libc::SYS_ftruncate,
+    libc::SYS_fallocate,
+    libc::SYS_utimensat,
+    libc::SYS_umask,
+    libc::SYS_getxattr,
+    libc::SYS_lgetxattr,
+    libc::SYS_fgetxattr,
+    libc::SYS_listxattr,
+    libc::SYS_llistxattr,
+    libc::SYS_flistxattr,
+    // Memory
+    libc::SYS_brk,
+    libc::SYS_mmap,
+    libc::SYS_munmap,
+    libc::SYS_mremap,
+    libc::SYS_mprotect,
+    libc::SYS_msync,
+    libc::SYS_mincore,
+    libc::SYS_madvise,
+    libc::SYS_memfd_create,
+    libc::SYS_membarrier,
+    // Processes, threads and signals
+    libc::SYS_execve,
+    libc::SYS_execveat,
+    libc::SYS_clone,
+    libc::SYS_clone3,
+    libc::SYS_exit,
+    libc::SYS_exit_group,
+    libc::SYS_wait4,
+    libc::SYS_waitid,
+    libc::SYS_kill,
+    libc::SYS_tkill,
+    libc::SYS_tgkill,
+    libc::SYS_rt_sigaction,
+    libc::SYS_rt_sigprocmask,
+    libc::SYS_rt_sigreturn,
+    libc::SYS_rt_sigpending,
+    libc::SYS_rt_sigtimedwait,
+    libc::SYS_rt_sigsuspend,
+    libc::SYS_rt_sigqueueinfo,
+    libc::SYS_sigaltstack,
+    libc::SYS_futex,
+    libc::SYS_set_tid_address,
+    libc::SYS_set_robust_list,
+    libc::SYS_get_robust_list,
+    libc::SYS_rseq,
+    libc::SYS_sched_yield,
+    libc::SYS_sched_getaffinity,
+    libc::SYS_sched_getparam,
+    libc::SYS_sched_getscheduler,
+    libc::SYS_prctl,
+    libc::SYS_prlimit64,
+    libc::SYS_getrlimit,
+    libc::SYS_setrlimit,
+    libc::SYS_getrusage,
+    libc::SYS_getpriority,
+    libc::SYS_setpriority,
+    libc::SYS_setpgid,
+    libc::SYS_getpgid,
+    libc::SYS_setsid,
+    libc::SYS_getsid,
+    libc::SYS_getpid,
+    libc::SYS_getppid,
+    libc::SYS_gettid,
+    libc::SYS_getuid,
+    libc::SYS_geteuid,
+    libc::SYS_getgid,
+    libc::SYS_getegid,
+    libc::SYS_getgroups,
+    libc::SYS_getresuid,
+    libc::SYS_getresgid,
+    libc::SYS_capget,
+    // Time and system info
+    libc::SYS_nanosleep,
+    libc::SYS_clock_nanosleep,
+    libc::SYS_clock_gettime,
+    libc::SYS_clock_getres,
+    libc::SYS_gettimeofday,
+    libc::SYS_getitimer,
+    libc::SYS_setitimer,
+    libc::SYS_times,
+    libc::SYS_uname,
+    libc::SYS_sysinfo,
+    libc::SYS_getcpu,
+    libc::SYS_getrandom,
+    // Socket operations (creation is gated by `socket` below)
+    libc::SYS_connect,
+    libc::SYS_accept,
+    libc::SYS_accept4,
+    libc::SYS_bind,
+    libc::SYS_listen,
+    libc::SYS_shutdown,
+    libc::SYS_sendto,
+    libc::SYS_recvfrom,
+    libc::SYS_sendmsg,
+    libc::SYS_recvmsg,
+    libc::SYS_sendmmsg,
+    libc::SYS_recvmmsg,
+    libc::SYS_getsockname,
+    libc::SYS_getpeername,
+    libc::SYS_socketpair,
+    libc::SYS_setsockopt,
+    libc::SYS_getsockopt,
+];
+
+/// Legacy syscalls that only exist on x86_64 (aarch64 uses the `*at` forms).
+#[cfg(target_arch = "x86_64")]
+const ARCH_SYSCALLS: &[libc::c_long] = &[
+    libc::SYS_open,
+    libc::SYS_creat,
+    libc::SYS_stat,
+    libc::SYS_lstat,
+    libc::SYS_access,
+    libc::SYS_getdents,
+    libc::SYS_mkdir,
+    libc::SYS_rmdir,
+    libc::SYS_rename,
+    libc::SYS_link,
+    libc::SYS_unlink,
+    libc::SYS_symlink,
+    libc::SYS_readlink,
+    libc::SYS_chmod,
+    libc::SYS_chown,
+    libc::SYS_lchown,
+    libc::SYS_utimes,
+    libc::SYS_futimesat,
+    libc::SYS_pipe,
+    libc::SYS_dup2,
+    libc::SYS_poll,
+    libc::SYS_select,
+    libc::SYS_epoll_create,
+    libc::SYS_epoll_wait,
+    libc::SYS_eventfd,
+    libc::SYS_signalfd,
+    libc::SYS_inotify_init,
+    libc::SYS_sendfile,
+    libc::SYS_fadvise64,
+    libc::SYS_fork,
+    libc::SYS_vfork,
+    libc::SYS_getpgrp,
+    libc::SYS_alarm,
+    libc::SYS_pause,
+    libc::SYS_time,
+    libc::SYS_arch_prctl,
+];
+#[cfg(target_arch = "aarch64")]
+const ARCH_SYSCALLS: &[libc::c_long] = &[];
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const BASE_SYSCALLS: &[libc::c_long] = &[];
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const ARCH_SYSCALLS: &[libc::c_long] = &[];
+
+/// Built-in seccomp-bpf sandbox backend for Linux
+pub struct SeccompSandbox {
+    profile: SeccompProfile,
+    launcher: PathBuf,
+    cpu_seconds: u64,
+    memory_bytes: u64,
+    cgroup: Option<PathBuf>,
+}
+
+impl SeccompSandbox {
+    /// Create a seccomp sandbox with the given profile and resource limits.
+    ///
+    /// Limits of `0` are treated as unlimited. When `use_cgroup` is set and
+    /// the agent's cgroup accepts a child group with the memory and pids
+    /// controllers, those limits are enforced there; otherwise a warning is
+    /// logged, memory falls back to `RLIMIT_DATA` and the process-count limit
+    /// is not enforced.
+    pub fn new(
+        profile: SeccompProfile,
+        limits: &ResourceLimitsConfig,
+        use_cgroup: bool,
+    ) -> std::io::Result<Self> {
+        if !Self::is_supported() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "seccomp filtering is not available on this kernel or architecture",
+            ));
+        }
+        // Fail early rather than on the first tool call.
+        build_filter(profile)?;
+
+        let memory_bytes = u64::from(limits.max_memory_mb).saturating_mul(1024 * 1024);
+        let cgroup = if use_cgroup {
+            match create_cgroup(memory_bytes, limits.max_subprocesses) {
+                Ok(dir) => Some(dir),
+                Err(reason) => {
+                    tracing::warn!(
+                        "cgroup v2 limits unavailable ({reason}); tool memory falls back to \
+                         RLIMIT_DATA and max_subprocesses is not enforced"
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            profile,
+            launcher: std::env::current_exe()?,
+            cpu_seconds: limits.max_cpu_time_seconds,
+            memory_bytes,
+            cgroup,
+        })
+    }
+
+    /// Create a sandbox from `[security]` config for the given autonomy level.
+    pub fn from_config(config: &SecurityConfig, level: AutonomyLevel) -> std::io::Result<Self> {
+        let seccomp = &config.sandbox.seccomp;
+        Self::new(
+            seccomp.profile_for(level),
+            &config.resources,
+            seccomp.use_cgroup,
+        )
+    }
+
+    /// Use `launcher` instead of the current executable to run commands.
+    #[must_use]
+    pub fn with_launcher(mut self, launcher: impl Into<PathBuf>) -> Self {
+        self.launcher = launcher.into();
+        self
+    }
+
+    /// Profile this sandbox applies.
+    pub fn profile(&self) -> SeccompProfile {
+        self.profile
+    }
+
+    /// Whether limits are enforced through a cgroup v2 child group.
+    pub fn uses_cgroup(&self) -> bool {
+        self.cgroup.is_some()
+    }
+
+    fn is_supported() -> bool {
+        TARGET_ARCH.is_some()
+            && std::fs::read_to_string("/proc/self/status")
+                .map(|status| status.lines().any(|line| line.starts_with("Seccomp:")))
+                .unwrap_or(false)
+    }
+}
+
+impl Drop for SeccompSandbox {
+    fn drop(&mut self) {
+        // Fails harmlessly while children are still alive.
+        if let Some(dir) = &self.cgroup {
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
+impl Sandbox for SeccompSandbox {
+    fn wrap_command(&self, cmd: &mut Command) -> std::io::Result<()> {
+        let mut launcher = Command::new(&self.launcher);
+        launcher
+            .arg(LAUNCHER_SUBCOMMAND)
+            .arg("--profile")
+            .arg(profile_name(self.profile))
+            .arg("--cpu-seconds")
+            .arg(self.cpu_seconds.to_string());
+        match &self.cgroup {
+            Some(dir) => {
+                launcher.arg("--cgroup-procs").arg(dir.join("cgroup.procs"));
+            }
+            None => {
+                launcher
+                    .arg("--memory-bytes")
+                    .arg(self.memory_bytes.to_string());
+            }
+        }
+        launcher.arg("--").arg(cmd.get_program()).args(cmd.get_args());
+
+        if let Some(dir) = cmd.get_current_dir() {
+            launcher.current_dir(dir);
+        }
+        for (key, value) in cmd.get_envs() {
+            match value {
+                Some(value) => launcher.env(key, value),
+                None => launcher.env_remove(key),
+            };
+        }
+
+        *cmd = launcher;
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        Self::is_supported()
+    }
+
+    fn name(&self) -> &str {
+        "seccomp"
+    }
+
+    fn description(&self) -> &str {
+        "Built-in seccomp-bpf syscall allowlist with cgroup v2 / rlimit resource limits (Linux)"
+    }
+
+    fn describe_violation(&self, status: ExitStatus) -> Option<String> {
+        (self.profile != SeccompProfile::Permissive && status.signal() == Some(libc::SIGSYS)).then(
+            || {
+                format!(
+                    "seccomp: bad system call (SIGSYS) denied by the {} syscall profile; process killed",
+                    profile_name(self.profile)
+                )
+            },
+        )
+    }
+}
+
+/// Confine the current process and replace it with `program`.
+///
+/// Entry point of the `sandbox-exec` launcher. Only returns on failure.
+pub fn exec_sandboxed(
+    profile: &str,
+    cpu_seconds: u64,
+    memory_bytes: u64,
+    cgroup_procs: Option<&Path>,
+    program: &OsStr,
+    args: &[OsString],
+) -> anyhow::Result<()> {
+    let profile = parse_profile(profile)
+        .ok_or_else(|| anyhow::anyhow!("unknown seccomp profile: {profile}"))?;
+    // Compile before confining so a bad filter cannot leave a half-applied sandbox.
+    let filter = build_filter(profile)?;
+
+    if let Some(procs) = cgroup_procs {
+        std::fs::write(procs, std::process::id().to_string())
+            .map_err(|e| anyhow::anyhow!("cannot join cgroup {}: {e}", procs.display()))?;
+    }
+    limit_resource(Resource::Cpu, cpu_seconds)?;
+    limit_resource(Resource::Data, memory_bytes)?;
+    if let Some(filter) = &filter {
+        seccompiler::apply_filter(filter)?;
+    }
+
+    let error = Command::new(program).args(args).exec();
+    anyhow::bail!("failed to execute {}: {error}", program.to_string_lossy())
+}
+
+fn profile_name(profile: SeccompProfile) -> &'static str {
+    match profile {
+        SeccompProfile::Strict => "strict",
+        SeccompProfile::Standard => "standard",
+        SeccompProfile::Permissive => "permissive",
+    }
+}
+
+fn parse_profile(name: &str) -> Option<SeccompProfile> {
+    match name {
+        "strict" => Some(SeccompProfile::Strict),
+        "standard" => Some(SeccompProfile::Standard),
+        "permissive" => Some(SeccompProfile::Permissive),
+        _ => None,
+    }
+}
+
+/// Build the BPF program for `profile`, or `None` when it does not filter.
+fn build_filter(profile: SeccompProfile) -> std::io::Result<Option<BpfProgram>> {
+    if profile == SeccompProfile::Permissive {
+        return Ok(None);
+    }
+    let arch = TARGET_ARCH.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "seccomp filtering is not supported on this architecture",
+        )
+    })?;
+
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BASE_SYSCALLS
+        .iter()
+        .chain(ARCH_SYSCALLS)
+        .map(|&nr| (nr, Vec::new()))
+        .collect();
+
+    // `strict` only lets commands create AF_UNIX sockets.
+    let socket_rules = if profile == SeccompProfile::Strict {
+        let af_unix = SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Eq,
+            libc::AF_UNIX as u64,
+        )
+        .and_then(|condition| SeccompRule::new(vec![condition]))
+        .map_err(std::io::Error::other)?;
+        vec![af_unix]
+    } else {
+        Vec::new()
+    };
+    rules.insert(libc::SYS_socket, socket_rules);
+
+    let filter = SeccompFilter::new(
+        rules,
+        SeccompAction::KillProcess,
+        SeccompAction::Allow,
+        arch,
+    )
+    .map_err(std::io::Error::other)?;
+    BpfProgram::try_from(filter)
+        .map(Some)
+        .map_err(std::io::Error::other)
+}
+
+/// Lower both the soft and hard limit of `resource`; `0` leaves it unchanged.
+fn limit_resource(resource: Resource, value: u64) -> std::io::Result<()> {
+    if value == 0 {
+        return Ok(());
+    }
+    let value = getrlimit(resource).maximum.map_or(value, |max| value.min(max));
+    setrlimit(
+        resource,
+        Rlimit {
+            current: Some(value),
+            maximum: Some(value),
+        },
+    )
+    .map_err(std::io::Error::from)
+}
+
+/// Relative cgroup v2 path of the current process (the `0::` entry).
+fn own_cgroup_path() -> Option<String> {
+    let raw = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    raw.lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| path.trim().trim_start_matches('/').to_string())
+}
+
+/// Enable the memory and pids controllers for children of `base`.
+///
+/// cgroup v2 only lets a group hand controllers to its children while it has
+/// no processes of its own (the root group excepted), so this fails unless
+/// the agent runs in a delegated subtree, e.g. a systemd unit with
+/// `Delegate=yes` that moved the agent into a leaf group.
+fn enable_subtree_controllers(base: &Path) -> Result<(), String> {
+    let control = base.join("cgroup.subtree_control");
+    let enabled = std::fs::read_to_string(&control).unwrap_or_default();
+    let missing: Vec<&str> = ["memory", "pids"]
+        .into_iter()
+        .filter(|name| !enabled.split_whitespace().any(|c| c == *name))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let available = std::fs::read_to_string(base.join("cgroup.controllers")).unwrap_or_default();
+    if let Some(name) = missing
+        .iter()
+        .find(|name| !available.split_whitespace().any(|c| c == **name))
+    {
+        return Err(format!(
+            "the {name} controller is not delegated to {}",
+            base.display()
+        ));
+    }
+
+    let request: Vec<String> = missing.iter().map(|name| format!("+{name}")).collect();
+    std::fs::write(&control, request.join(" ")).map_err(|error| {
+        format!(
+            "cannot enable {} in {}: {error}",
+            missing.join(" and "),
+            control.display()
+        )
+    })
+}
+
+/// Create the cgroup v2 child group shared by every child this sandbox spawns.
+///
+/// Returns why the group cannot be used when it cannot.
+fn create_cgroup(memory_bytes: u64, max_pids: u32) -> Result<PathBuf, String> {
+    let own = own_cgroup_path().ok_or("no cgroup v2 entry in /proc/self/cgroup")?;
+    let base = Path::new(CGROUP_ROOT).join(own);
+    if !base.join("cgroup.controllers").exists() {
+        return Err(format!("{} is not a cgroup v2 group", base.display()));
+    }
+    enable_subtree_controllers(&base)?;
+
+    let dir = base.join(format!("zeroclaw-tools-{}", std::process::id()));
+    if let Err(error) = std::fs::create_dir(&dir) {
+        if error.kind() != std::io::ErrorKind::AlreadyExists {
+            return Err(format!("cannot create {}: {error}", dir.display()));
+        }
+    }
+
+    let memory = if memory_bytes == 0 {
+        "max".to_string()
+    } else {
+        memory_bytes.to_string()
+    };
+    let pids = if max_pids == 0 {
+        "max".to_string()
+    } else {
+        max_pids.to_string()
+    };
+    let applied = std::fs::write(dir.join("memory.max"), memory)
+        .and_then(|()| std::fs::write(dir.join("pids.max"), pids));
+    if let Err(error) = applied {
+        let _ = std::fs::remove_dir(&dir);
+        return Err(format!(
+            "{} does not accept memory/pids limits: {error}",
+            dir.display()
+        ));
+    }
+
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> ResourceLimitsConfig {
+        ResourceLimitsConfig::default()
+    }
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn permissive_profile_installs_no_filter() {
+        assert!(build_filter(SeccompProfile::Permissive).unwrap().is_none());
+    }
+
+    #[test]
+    fn strict_filter_inspects_socket_family() {
+        if TARGET_ARCH.is_none() {
+            return;
+        }
+        let strict = build_filter(SeccompProfile::Strict).unwrap().unwrap();
+        let standard = build_filter(SeccompProfile::Standard).unwrap().unwrap();
+        // The AF_UNIX argument check adds instructions on top of the allowlist.
+        assert!(strict.len() > standard.len());
+        assert!(
+            strict.iter().any(|insn| insn.k == libc::AF_UNIX as u32),
+            "strict filter must compare the socket family"
+        );
+    }
+
+    #[test]
+    fn profile_names_round_trip() {
+        for profile in [
+            SeccompProfile::Strict,
+            SeccompProfile::Standard,
+            SeccompProfile::Permissive,
+        ] {
+            assert_eq!(parse_profile(profile_name(profile)), Some(profile));
+        }
+        assert_eq!(parse_profile("lenient"), None);
+    }
+
+    #[test]
+    fn wrap_command_routes_through_launcher() {
+        let Ok(sandbox) = SeccompSandbox::new(SeccompProfile::Strict, &limits(), false) else {
+            return;
+        };
+        let sandbox = sandbox.with_launcher("/opt/zeroclaw");
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("echo --profile")
+            .current_dir("/tmp")
+            .env("ZEROCLAW_MARK", "1")
+            .env_remove("HOME");
+        sandbox.wrap_command(&mut cmd).unwrap();
+
+        assert_eq!(cmd.get_program(), "/opt/zeroclaw");
+        let args = args(&cmd);
+        assert_eq!(&args[..3], [LAUNCHER_SUBCOMMAND, "--profile", "strict"]);
+        assert!(args.contains(&"--memory-bytes".to_string()));
+        let separator = args.iter().position(|arg| arg == "--").unwrap();
+        assert_eq!(&args[separator + 1..], ["sh", "-c", "echo --profile"]);
+        assert_eq!(cmd.get_current_dir(), Some(Path::new("/tmp")));
+        let envs: Vec<_> = cmd.get_envs().collect();
+        assert!(envs.contains(&(OsStr::new("ZEROCLAW_MARK"), Some(OsStr::new("1")))));
+        assert!(envs.contains(&(OsStr::new("HOME"), None)));
+    }
+
+    #[test]
+    fn describe_violation_reports_sigsys_only() {
+        let Ok(sandbox) = SeccompSandbox::new(SeccompProfile::Standard, &limits(), false) else {
+            return;
+        };
+        let killed = ExitStatus::from_raw(libc::SIGSYS);
+        let report = sandbox.describe_violation(killed).unwrap();
+        assert!(report.contains("seccomp"));
+        assert!(report.contains("standard"));
+        assert!(sandbox.describe_violation(ExitStatus::from_raw(0)).is_none());
+
+        let permissive =
+            SeccompSandbox::new(SeccompProfile::Permissive, &limits(), false).unwrap();
+        assert!(permissive.describe_violation(killed).is_none());
+    }
+
+    #[test]
+    fn subtree_controllers_are_requested_only_when_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let control = dir.path().join("cgroup.subtree_control");
+        std::fs::write(dir.path().join("cgroup.controllers"), "cpu memory pids").unwrap();
+        std::fs::write(&control, "pids").unwrap();
+        enable_subtree_controllers(dir.path()).unwrap();
+        assert_eq!(std::fs::read_to_string(&control).unwrap(), "+memory");
+
+        std::fs::write(&control, "memory pids").unwrap();
+        enable_subtree_controllers(dir.path()).unwrap();
+        assert_eq!(std::fs::read_to_string(&control).unwrap(), "memory pids");
+
+        std::fs::write(dir.path().join("cgroup.controllers"), "cpu").unwrap();
+        std::fs::write(&control, "").unwrap();
+        let error = enable_subtree_controllers(dir.path()).unwrap_err();
+        assert!(error.contains("memory controller is not delegated"), "{error}");
+    }
+
+    #[test]
+    fn from_config_selects_profile_by_autonomy() {
+        let mut config = SecurityConfig::default();
+        config.sandbox.seccomp.use_cgroup = false;
+        let Ok(read_only) = SeccompSandbox::from_config(&config, AutonomyLevel::ReadOnly) else {
+            return;
+        };
+        assert_eq!(read_only.profile(), SeccompProfile::Strict);
+        assert!(!read_only.uses_cgroup());
+        let full = SeccompSandbox::from_config(&config, AutonomyLevel::Full).unwrap();
+        assert_eq!(full.profile(), SeccompProfile::Permissive);
+    }
+}
@@ -49,6 +49,16 @@ pub trait Sandbox: Send + Sync {
     /// Displayed in status output and health checks so operators can verify
     /// the active security posture.
     fn description(&self) -> &str;
+
+    /// Describe a sandbox violation inferred from a child's exit status.
+    ///
+    /// Backends that enforce policy inside the kernel (e.g. a seccomp filter
+    /// killing the child with `SIGSYS`) return a telemetry line that callers
+    /// feed into [`SyscallAnomalyDetector`](crate::security::SyscallAnomalyDetector).
+    /// The default reports nothing.
+    fn describe_violation(&self, _status: std::process::ExitStatus) -> Option<String> {
+        None
+    }
 }
 
 /// No-op sandbox that provides no additional OS-level isolation.
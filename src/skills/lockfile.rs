@@ -0,0 +1,590 @@
+//! `skills.lock`: provenance and approved content for installed workspace skills.
+//!
+//! Every install records where a skill came from, its version and a hash of
+//! its content. Workspace skills that are not in the lock, or whose content no
+//! longer matches it, are refused at load time until they are approved with
+//! `zeroclaw skills approve`.
+//!
+//! Lockfiles older than version 2 (or none at all) predate that rule: the
+//! first load locks every skill already installed, once, and rewrites the
+//! lockfile at the current version.
+//!
+//! A skill may also ship a `SKILL.sig` file carrying an Ed25519 signature over
+//! its content hash. Signatures are checked against `[skills].trusted_publishers`.
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Lockfile name, stored next to the `skills/` directory in the workspace.
+pub const LOCKFILE_NAME: &str = "skills.lock";
+
+/// Detached signature file inside a skill directory. Excluded from the content hash.
+pub const SIGNATURE_FILE: &str = "SKILL.sig";
+
+/// Version 2 refuses unlocked skills; see [`SkillLock::load`].
+const LOCKFILE_VERSION: u32 = 2;
+const INTEGRITY_PREFIX: &str = "sha256-";
+
+/// Where a locked skill was installed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    /// Local skill directory, copied into the workspace.
+    Local,
+    /// Local `.zip` archive.
+    LocalZip,
+    /// Git remote, cloned at `revision`.
+    Git,
+    /// ZeroMarket (or compatible) registry package.
+    Registry,
+    /// HTTPS zip archive.
+    ZipUrl,
+    /// ClawhHub skill.
+    Clawhub,
+    /// Created directly in the workspace; there is no upstream to update from.
+    Workspace,
+}
+
+impl SourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::LocalZip => "local_zip",
+            Self::Git => "git",
+            Self::Registry => "registry",
+            Self::ZipUrl => "zip_url",
+            Self::Clawhub => "clawhub",
+            Self::Workspace => "workspace",
+        }
+    }
+}
+
+/// One approved skill in `skills.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Source string as passed to `zeroclaw skills install`.
+    /// Local paths are stored canonicalized.
+    pub source: String,
+    pub kind: SourceKind,
+    pub version: String,
+    /// `sha256-<hex>` over the skill's files, excluding `SKILL.sig`.
+    pub integrity: String,
+    /// Commit the skill was installed from (git sources only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// Publisher whose signature was verified when the skill was approved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    pub approved_at: DateTime<Utc>,
+}
+
+/// Parsed `skills.lock`, keyed by installed skill directory name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillLock {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+impl Default for SkillLock {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+impl SkillLock {
+    pub fn path(workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join(LOCKFILE_NAME)
+    }
+
+    /// Read the workspace lockfile, migrating it first if it predates
+    /// version 2 (a missing file counts as version 0).
+    pub fn load(workspace_dir: &Path) -> Result<Self> {
+        let path = Self::path(workspace_dir);
+        let mut lock: Self = match std::fs::read_to_string(&path) {
+            Ok(raw) => toml::from_str(&raw)
+                .with_context(|| format!("failed to parse {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Self {
+                version: 0,
+                skills: BTreeMap::new(),
+            },
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", path.display()))
+            }
+        };
+        if lock.version > LOCKFILE_VERSION {
+            bail!(
+                "{} has version {}, this build understands up to {LOCKFILE_VERSION}",
+                path.display(),
+                lock.version
+            );
+        }
+        if lock.version < LOCKFILE_VERSION {
+            lock.migrate(workspace_dir)?;
+        }
+        Ok(lock)
+    }
+
+    /// One-time migration to version 2: lock every skill installed before
+    /// unlocked skills were refused, as approved in its current state. The
+    /// lockfile is then written at version 2 so this never runs again.
+    fn migrate(&mut self, workspace_dir: &Path) -> Result<()> {
+        let skills_dir = workspace_dir.join("skills");
+        let entries = match std::fs::read_dir(&skills_dir) {
+            Ok(entries) => entries.flatten().collect(),
+            Err(_) => Vec::new(),
+        };
+        for entry in entries {
+            let path = entry.path();
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if !path.is_dir() || self.skills.contains_key(&name) {
+                continue;
+            }
+            let integrity = match content_hash(&path) {
+                Ok(integrity) => integrity,
+                Err(err) => {
+                    tracing::warn!("not locking skill '{name}' during migration: {err:#}");
+                    continue;
+                }
+            };
+            tracing::info!("{LOCKFILE_NAME}: locking previously installed skill '{name}'");
+            self.skills.insert(
+                name,
+                LockedSkill {
+                    source: path.display().to_string(),
+                    kind: SourceKind::Workspace,
+                    version: super::installed_skill_version(&path),
+                    integrity,
+                    revision: None,
+                    publisher: None,
+                    approved_at: Utc::now(),
+                },
+            );
+        }
+        self.version = LOCKFILE_VERSION;
+        if workspace_dir.is_dir() {
+            self.save(workspace_dir)?;
+        }
+        Ok(())
+    }
+
+    /// Write the lockfile atomically (temp file + rename).
+    pub fn save(&self, workspace_dir: &Path) -> Result<()> {
+        let path = Self::path(workspace_dir);
+        let body = format!(
+            "# Generated by zeroclaw. Records the source and approved content of each skill.\n{}",
+            toml::to_string_pretty(self).context("failed to serialize skills.lock")?
+        );
+        let tmp = path.with_extension("lock.tmp");
+        std::fs::write(&tmp, body).with_context(|| format!("failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Signature policy derived from `[skills]` config.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrustPolicy<'a> {
+    /// Publisher name → base64 Ed25519 public key.
+    pub trusted_publishers: Option<&'a HashMap<String, String>>,
+    /// Refuse skills without a valid signature.
+    pub require_signatures: bool,
+}
+
+impl<'a> TrustPolicy<'a> {
+    pub fn from_config(config: &'a crate::config::SkillsConfig) -> Self {
+        Self {
+            trusted_publishers: Some(&config.trusted_publishers),
+            require_signatures: config.require_signatures,
+        }
+    }
+}
+
+/// Contents of `SKILL.sig`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillSignature {
+    pub publisher: String,
+    #[serde(default = "default_signature_algorithm")]
+    pub algorithm: String,
+    /// Base64 Ed25519 signature over the skill's `sha256-<hex>` integrity string.
+    pub signature: String,
+}
+
+fn default_signature_algorithm() -> String {
+    "ed25519".to_string()
+}
+
+/// Hash every regular file under `skill_dir` (sorted by relative path) into a
+/// `sha256-<hex>` integrity string. `.git` and the root `SKILL.sig` are skipped.
+pub fn content_hash(skill_dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(skill_dir, skill_dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in &files {
+        let bytes = std::fs::read(skill_dir.join(relative))
+            .with_context(|| format!("failed to read {}", skill_dir.join(relative).display()))?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(&bytes);
+    }
+    Ok(format!(
+        "{INTEGRITY_PREFIX}{}",
+        hex::encode(hasher.finalize())
+    ))
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if file_type.is_symlink() {
+            bail!("symlink inside skill: {}", path.display());
+        }
+        if file_type.is_dir() {
+            if relative != ".git" {
+                collect_files(root, &path, out)?;
+            }
+        } else if file_type.is_file() && relative != SIGNATURE_FILE {
+            out.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Check `SKILL.sig` against the trusted publisher keys.
+///
+/// Returns the verified publisher, or `None` for an unsigned skill when
+/// signatures are not required. A signature that is present but cannot be
+/// verified is always an error.
+pub fn verify_signature(
+    skill_dir: &Path,
+    integrity: &str,
+    policy: TrustPolicy<'_>,
+) -> Result<Option<String>> {
+    let sig_path = skill_dir.join(SIGNATURE_FILE);
+    let raw = match std::fs::read_to_string(&sig_path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            if policy.require_signatures {
+                bail!("skill is unsigned and [skills].require_signatures is enabled");
+            }
+            return Ok(None);
+        }
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", sig_path.display()))
+        }
+    };
+
+    let sig: SkillSignature =
+        toml::from_str(&raw).with_context(|| format!("failed to parse {}", sig_path.display()))?;
+    if !sig.algorithm.eq_ignore_ascii_case("ed25519") {
+        bail!("unsupported signature algorithm '{}'", sig.algorithm);
+    }
+    let Some(key) = policy
+        .trusted_publishers
+        .and_then(|keys| keys.get(&sig.publisher))
+    else {
+        bail!(
+            "skill is signed by '{}', which is not listed in [skills].trusted_publishers",
+            sig.publisher
+        );
+    };
+
+    let engine = base64::engine::general_purpose::STANDARD;
+    let key = engine
+        .decode(key.trim())
+        .with_context(|| format!("invalid public key for publisher '{}'", sig.publisher))?;
+    let signature = engine
+        .decode(sig.signature.trim())
+        .context("SKILL.sig signature is not valid base64")?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, key)
+        .verify(integrity.as_bytes(), &signature)
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "signature from '{}' does not match the skill content",
+                sig.publisher
+            )
+        })?;
+    Ok(Some(sig.publisher))
+}
+
+/// Hash a skill and verify its signature, returning `(integrity, publisher)`.
+pub fn inspect(skill_dir: &Path, policy: TrustPolicy<'_>) -> Result<(String, Option<String>)> {
+    let integrity = content_hash(skill_dir)?;
+    let publisher = verify_signature(skill_dir, &integrity, policy)?;
+    Ok((integrity, publisher))
+}
+
+/// Load-time check of workspace skills against `skills.lock`.
+pub struct IntegrityCheck<'a> {
+    pub lock: &'a SkillLock,
+    pub policy: TrustPolicy<'a>,
+}
+
+impl IntegrityCheck<'_> {
+    /// Verify the skill installed as `name`: it must be locked, match the
+    /// approved hash and carry any signature it was approved with.
+    pub fn verify(&self, name: &str, skill_dir: &Path) -> Result<()> {
+        let Some(locked) = self.lock.skills.get(name) else {
+            bail!(
+                "not recorded in {LOCKFILE_NAME}; review the skill and run \
+                 `zeroclaw skills approve {name}`"
+            );
+        };
+
+        let (integrity, publisher) = inspect(skill_dir, self.policy)?;
+        if integrity != locked.integrity {
+            bail!(
+                "content changed since it was approved ({LOCKFILE_NAME} has {}, found {integrity}); \
+                 review the changes and run `zeroclaw skills approve {name}`",
+                locked.integrity
+            );
+        }
+        if locked.publisher.is_some() && publisher != locked.publisher {
+            bail!(
+                "skill was approved with a signature from '{}' but is no longer signed by it",
+                locked.publisher.as_deref().unwrap_or_default()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn write_skill(dir: &Path) {
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        std::fs::write(
+            dir.join("SKILL.toml"),
+            "[skill]\nname = \"weather\"\ndescription = \"Forecasts\"\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("docs/usage.md"), "# Usage\n").unwrap();
+    }
+
+    fn keypair() -> Ed25519KeyPair {
+        Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap()
+    }
+
+    fn sign(dir: &Path, publisher: &str, key: &Ed25519KeyPair) {
+        let integrity = content_hash(dir).unwrap();
+        let signature = base64::engine::general_purpose::STANDARD
+            .encode(key.sign(integrity.as_bytes()).as_ref());
+        std::fs::write(
+            dir.join(SIGNATURE_FILE),
+            format!("publisher = \"{publisher}\"\nsignature = \"{signature}\"\n"),
+        )
+        .unwrap();
+    }
+
+    fn trusted(publisher: &str, key: &Ed25519KeyPair) -> HashMap<String, String> {
+        HashMap::from([(
+            publisher.to_string(),
+            base64::engine::general_purpose::STANDARD.encode(key.public_key().as_ref()),
+        )])
+    }
+
+    fn locked(integrity: String, publisher: Option<&str>) -> LockedSkill {
+        LockedSkill {
+            source: "/src/weather".into(),
+            kind: SourceKind::Local,
+            version: "1.0.0".into(),
+            integrity,
+            revision: None,
+            publisher: publisher.map(str::to_string),
+            approved_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn content_hash_is_stable_and_ignores_signature_and_git() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("weather");
+        write_skill(&dir);
+        let before = content_hash(&dir).unwrap();
+        assert!(before.starts_with("sha256-"));
+
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        std::fs::write(dir.join(SIGNATURE_FILE), "publisher = \"x\"\n").unwrap();
+        assert_eq!(content_hash(&dir).unwrap(), before);
+
+        std::fs::write(dir.join("docs/usage.md"), "# Usage\nrm -rf\n").unwrap();
+        assert_ne!(content_hash(&dir).unwrap(), before);
+    }
+
+    #[test]
+    fn lockfile_roundtrips_and_missing_file_is_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(SkillLock::load(tmp.path()).unwrap().skills.is_empty());
+
+        let mut lock = SkillLock::default();
+        let mut entry = locked("sha256-abc".into(), Some("acme"));
+        entry.kind = SourceKind::Git;
+        entry.revision = Some("0123abcd".into());
+        lock.skills.insert("weather".into(), entry);
+        lock.save(tmp.path()).unwrap();
+
+        let raw = std::fs::read_to_string(tmp.path().join(LOCKFILE_NAME)).unwrap();
+        assert!(raw.contains("[skills.weather]"));
+        assert!(raw.contains("kind = \"git\""));
+        assert_eq!(SkillLock::load(tmp.path()).unwrap(), lock);
+    }
+
+    #[test]
+    fn modified_locked_skill_is_refused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("weather");
+        write_skill(&dir);
+
+        let mut lock = SkillLock::default();
+        lock.skills
+            .insert("weather".into(), locked(content_hash(&dir).unwrap(), None));
+        let check = IntegrityCheck {
+            lock: &lock,
+            policy: TrustPolicy::default(),
+        };
+        check.verify("weather", &dir).unwrap();
+
+        std::fs::write(dir.join("docs/extra.md"), "new instructions\n").unwrap();
+        let err = check.verify("weather", &dir).unwrap_err().to_string();
+        assert!(err.contains("zeroclaw skills approve weather"), "{err}");
+    }
+
+    #[test]
+    fn unlocked_skill_is_refused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("weather");
+        write_skill(&dir);
+        let lock = SkillLock::default();
+        let check = IntegrityCheck {
+            lock: &lock,
+            policy: TrustPolicy::default(),
+        };
+        let err = check.verify("weather", &dir).unwrap_err().to_string();
+        assert!(err.contains("not recorded in skills.lock"), "{err}");
+        assert!(err.contains("zeroclaw skills approve weather"), "{err}");
+    }
+
+    #[test]
+    fn migration_locks_previously_installed_skills_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let skills = tmp.path().join("skills");
+        write_skill(&skills.join("weather"));
+
+        let lock = SkillLock::load(tmp.path()).unwrap();
+        assert_eq!(lock.version, LOCKFILE_VERSION);
+        let entry = &lock.skills["weather"];
+        assert_eq!(entry.kind, SourceKind::Workspace);
+        assert_eq!(entry.version, "1.0.0");
+        assert_eq!(
+            entry.integrity,
+            content_hash(&skills.join("weather")).unwrap()
+        );
+
+        write_skill(&skills.join("dropped"));
+        let lock = SkillLock::load(tmp.path()).unwrap();
+        assert!(!lock.skills.contains_key("dropped"));
+    }
+
+    #[test]
+    fn migration_upgrades_version_one_lockfiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let skills = tmp.path().join("skills");
+        write_skill(&skills.join("weather"));
+        write_skill(&skills.join("legacy"));
+
+        let mut lock = SkillLock {
+            version: 1,
+            skills: BTreeMap::new(),
+        };
+        lock.skills
+            .insert("weather".into(), locked("sha256-abc".into(), Some("acme")));
+        lock.save(tmp.path()).unwrap();
+
+        let migrated = SkillLock::load(tmp.path()).unwrap();
+        assert_eq!(migrated.skills["weather"], lock.skills["weather"]);
+        assert_eq!(migrated.skills["legacy"].kind, SourceKind::Workspace);
+        let raw = std::fs::read_to_string(tmp.path().join(LOCKFILE_NAME)).unwrap();
+        assert!(raw.contains("version = 2"), "{raw}");
+    }
+
+    #[test]
+    fn signature_is_verified_against_trusted_publishers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("weather");
+        write_skill(&dir);
+        let key = keypair();
+        sign(&dir, "acme", &key);
+
+        let keys = trusted("acme", &key);
+        let policy = TrustPolicy {
+            trusted_publishers: Some(&keys),
+            require_signatures: true,
+        };
+        let (_, publisher) = inspect(&dir, policy).unwrap();
+        assert_eq!(publisher.as_deref(), Some("acme"));
+
+        let err = inspect(&dir, TrustPolicy::default()).unwrap_err();
+        assert!(err.to_string().contains("not listed"), "{err}");
+
+        std::fs::write(dir.join("docs/usage.md"), "tampered\n").unwrap();
+        let err = inspect(&dir, policy).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+    }
+
+    #[test]
+    fn unsigned_skill_is_refused_when_signatures_are_required() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("weather");
+        write_skill(&dir);
+        let mut lock = SkillLock::default();
+        lock.skills
+            .insert("weather".into(), locked(content_hash(&dir).unwrap(), None));
+
+        let lenient = IntegrityCheck {
+            lock: &lock,
+            policy: TrustPolicy::default(),
+        };
+        lenient.verify("weather", &dir).unwrap();
+
+        let keys = HashMap::new();
+        let strict = IntegrityCheck {
+            lock: &lock,
+            policy: TrustPolicy {
+                trusted_publishers: Some(&keys),
+                require_signatures: true,
+            },
+        };
+        let err = strict.verify("weather", &dir).unwrap_err();
+        assert!(err.to_string().contains("unsigned"), "{err}");
+    }
+}
@@ -0,0 +1,247 @@
+//! Capabilities a skill declares in the `[permissions]` table of `SKILL.toml`.
+//!
+//! Skill tools run under a [`SecurityPolicy`] derived from the global one and
+//! narrowed to what the skill declared: an action must be allowed by the
+//! global policy *and* covered by the skill's declaration.
+//!
+//! `domains` is a check on the rendered command text, not a network sandbox:
+//! only literal `http(s)://` URLs are compared against it. Hosts a script
+//! builds or reads at runtime, bare hostnames (`curl example.com`) and
+//! non-HTTP clients are not seen, so a declared command that can reach the
+//! network (an interpreter, `curl`) can reach any host.
+//!
+//! ```toml
+//! [permissions]
+//! commands = ["python3"]
+//! domains = ["api.weather.gov"]
+//! paths = ["~/weather-cache"]
+//! secrets = ["WEATHER_API_KEY"]
+//! ```
+
+use crate::security::SecurityPolicy;
+use crate::skills::SkillTool;
+use crate::tools::url_validation::{
+    extract_host, host_matches_allowlist, normalize_allowed_domains, UrlSchemePolicy,
+};
+use parking_lot::RwLock;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static URL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\bhttps?://[^\s'"<>]+"#).expect("url regex compilation failed")
+});
+
+/// Capabilities requested by a skill.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillPermissions {
+    /// Executables the skill's shell tools may run (names or exact paths).
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Hosts allowed in literal `http(s)://` URLs of a rendered command
+    /// (`example.com` also covers subdomains). Does not restrict traffic the
+    /// command itself opens; see the module docs.
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Filesystem paths outside the skill directory the skill may touch.
+    /// Relative paths are resolved against the workspace.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Environment variables passed through to the skill's commands.
+    #[serde(default)]
+    pub secrets: Vec<String>,
+}
+
+impl SkillPermissions {
+    /// Permissions implied by a manifest without a `[permissions]` table:
+    /// only the executables its tool templates invoke.
+    pub fn implied_by(tools: &[SkillTool]) -> Self {
+        let mut commands: Vec<String> = tools
+            .iter()
+            .flat_map(|tool| template_executables(&tool.command))
+            .collect();
+        commands.sort();
+        commands.dedup();
+        Self {
+            commands,
+            ..Self::default()
+        }
+    }
+
+    /// Lines describing the declaration, for install and list output.
+    pub fn summary_lines(&self) -> Vec<String> {
+        [
+            ("commands", &self.commands),
+            ("domains", &self.domains),
+            ("paths", &self.paths),
+            ("secrets", &self.secrets),
+        ]
+        .into_iter()
+        .filter(|(_, values)| !values.is_empty())
+        .map(|(label, values)| format!("{label}: {}", values.join(", ")))
+        .collect()
+    }
+
+    /// Whether `executable` (as written in a command template) is declared.
+    pub fn declares_command(&self, executable: &str) -> bool {
+        let base = executable.rsplit('/').next().unwrap_or(executable);
+        self.commands
+            .iter()
+            .any(|declared| declared == executable || declared == base)
+    }
+
+    /// Policy the skill's tools are checked against, in addition to `base`.
+    ///
+    /// The skill directory acts as its workspace. Only declared commands are
+    /// allowlisted, only declared paths are reachable outside the skill
+    /// directory, and only declared secrets are passed through.
+    pub fn derive_policy(&self, base: &SecurityPolicy, skill_dir: Option<&Path>) -> SecurityPolicy {
+        let mut roots: Vec<PathBuf> = self
+            .paths
+            .iter()
+            .map(|raw| {
+                let expanded = PathBuf::from(shellexpand::tilde(raw.trim()).as_ref());
+                if expanded.is_absolute() {
+                    expanded
+                } else {
+                    base.workspace_dir.join(expanded)
+                }
+            })
+            .collect();
+        if let Some(dir) = skill_dir {
+            roots.push(dir.to_path_buf());
+        }
+
+        SecurityPolicy {
+            workspace_dir: skill_dir.map_or_else(|| base.workspace_dir.clone(), Path::to_path_buf),
+            workspace_only: true,
+            allowed_commands: self.commands.clone(),
+            allowed_roots: RwLock::new(roots),
+            shell_env_passthrough: self.secrets.clone(),
+            ..base.clone()
+        }
+    }
+
+    /// First host of a literal `http(s)://` URL in `command` that is not
+    /// declared in `domains`. Anything else the command may connect to is
+    /// not inspected.
+    pub fn undeclared_host(&self, command: &str) -> Option<String> {
+        let allowed = normalize_allowed_domains(self.domains.clone());
+        URL_REGEX.find_iter(command).find_map(|url| {
+            let url = url.as_str().to_ascii_lowercase();
+            match extract_host(&url, UrlSchemePolicy::HttpOrHttps, "skill tools") {
+                Ok(host) if host_matches_allowlist(&host, &allowed) => None,
+                Ok(host) => Some(host),
+                Err(_) => Some(url),
+            }
+        })
+    }
+}
+
+/// Executables invoked by a command template: the first word of each
+/// `|`, `;` or `&`-separated segment, after any `VAR=value` assignments.
+pub fn template_executables(command: &str) -> Vec<String> {
+    command
+        .split(['|', ';', '&', '\n'])
+        .filter_map(|segment| {
+            segment
+                .split_whitespace()
+                .find(|word| !is_env_assignment(word))
+                .filter(|word| !word.starts_with('{'))
+                .map(|word| word.trim_matches(|c| c == '\'' || c == '"').to_string())
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn is_env_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use std::collections::HashMap;
+
+    fn shell_tool(command: &str) -> SkillTool {
+        SkillTool {
+            name: "run".into(),
+            description: "Run".into(),
+            kind: "shell".into(),
+            command: command.into(),
+            args: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn template_executables_cover_every_segment() {
+        assert_eq!(
+            template_executables("LANG=C python3 fetch.py {city} | jq .temp && echo done"),
+            vec!["python3", "jq", "echo"]
+        );
+        let implied =
+            SkillPermissions::implied_by(&[shell_tool("echo {x}"), shell_tool("echo hi")]);
+        assert_eq!(implied.commands, vec!["echo"]);
+        assert!(implied.domains.is_empty());
+    }
+
+    #[test]
+    fn derived_policy_only_allows_declared_commands_and_paths() {
+        let workspace = tempfile::tempdir().unwrap();
+        let base = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: workspace.path().to_path_buf(),
+            allowed_commands: vec!["echo".into(), "cat".into(), "ls".into()],
+            forbidden_paths: vec![],
+            ..SecurityPolicy::default()
+        };
+        let skill_dir = workspace.path().join("skills/weather");
+        let permissions = SkillPermissions {
+            commands: vec!["echo".into(), "ls".into()],
+            paths: vec!["cache".into()],
+            secrets: vec!["WEATHER_API_KEY".into()],
+            ..SkillPermissions::default()
+        };
+        let policy = permissions.derive_policy(&base, Some(&skill_dir));
+
+        assert!(policy.validate_command_execution("echo hi", false).is_ok());
+        assert!(policy
+            .validate_command_execution("cat notes.txt", false)
+            .is_err());
+
+        let cache = workspace.path().join("cache/today.json");
+        let secret = workspace.path().join("secrets.txt");
+        assert!(policy.is_path_allowed(&cache.display().to_string()));
+        assert!(!policy.is_path_allowed(&secret.display().to_string()));
+        assert!(policy.is_path_allowed(&skill_dir.join("data.txt").display().to_string()));
+        assert_eq!(policy.shell_env_passthrough, vec!["WEATHER_API_KEY"]);
+    }
+
+    #[test]
+    fn undeclared_hosts_are_reported() {
+        let permissions = SkillPermissions {
+            domains: vec!["weather.gov".into()],
+            ..SkillPermissions::default()
+        };
+        assert_eq!(
+            permissions.undeclared_host("echo https://api.weather.gov/points/1,2"),
+            None
+        );
+        assert_eq!(
+            permissions
+                .undeclared_host("echo 'https://api.weather.gov' 'http://evil.example/x?d=1'")
+                .as_deref(),
+            Some("evil.example")
+        );
+        assert_eq!(
+            SkillPermissions::default().undeclared_host("echo HTTPS://Example.com"),
+            Some("example.com".into())
+        );
+        // Only literal URLs are checked; bare hosts pass.
+        assert_eq!(permissions.undeclared_host("curl -s evil.example/x"), None);
+    }
+}
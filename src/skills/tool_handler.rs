@@ -31,16 +31,19 @@
 //! ## Security
 //!
 //! - All arguments are validated and shell-escaped
-//! - Commands execute within existing SecurityPolicy constraints
+//! - Commands must pass the global SecurityPolicy *and* a policy derived from
+//!   the skill's declared `[permissions]` (commands, domains, paths, secrets)
+//! - Commands run in the skill directory with only declared secrets in the environment
 //! - No arbitrary code injection
 
 use crate::security::SecurityPolicy;
-use crate::skills::SkillTool;
+use crate::skills::{SkillPermissions, SkillTool};
 use crate::tools::traits::{Tool, ToolResult};
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::{Arc, LazyLock};
 
 /// Regex to extract {placeholder} names from command templates
@@ -70,10 +73,16 @@ pub struct SkillToolHandler {
     tool_def: SkillTool,
     parameters: Vec<SkillToolParameter>,
     security: Arc<SecurityPolicy>,
+    permissions: SkillPermissions,
+    skill_policy: SecurityPolicy,
+    skill_dir: Option<PathBuf>,
 }
 
 impl SkillToolHandler {
-    /// Create a new skill tool handler from a skill tool definition
+    /// Create a new skill tool handler from a skill tool definition.
+    ///
+    /// The handler only gets the permissions implied by its own command
+    /// template until [`Self::with_permissions`] applies the skill's declaration.
     pub fn new(
         skill_name: String,
         tool_def: SkillTool,
@@ -92,14 +101,41 @@ impl SkillToolHandler {
             );
         }
         let parameters = Self::extract_parameters(&tool_def)?;
+        let permissions = SkillPermissions::implied_by(std::slice::from_ref(&tool_def));
+        let skill_policy = permissions.derive_policy(&security, None);
         Ok(Self {
             skill_name,
             tool_def,
             parameters,
             security,
+            permissions,
+            skill_policy,
+            skill_dir: None,
         })
     }
 
+    /// Restrict the tool to the permissions declared by its skill and run it
+    /// from `skill_dir`. Fails if the command template invokes an executable
+    /// the skill did not declare.
+    pub fn with_permissions(
+        mut self,
+        permissions: SkillPermissions,
+        skill_dir: Option<PathBuf>,
+    ) -> Result<Self> {
+        for executable in super::permissions::template_executables(&self.tool_def.command) {
+            if !permissions.declares_command(&executable) {
+                bail!(
+                    "tool '{}' runs '{executable}', which is not declared in [permissions].commands",
+                    self.tool_def.name
+                );
+            }
+        }
+        self.skill_policy = permissions.derive_policy(&self.security, skill_dir.as_deref());
+        self.permissions = permissions;
+        self.skill_dir = skill_dir;
+        Ok(self)
+    }
+
     /// Extract parameter definitions from tool args and command template
     fn extract_parameters(tool_def: &SkillTool) -> Result<Vec<SkillToolParameter>> {
         let placeholders = Self::extract_placeholders(&tool_def.command);
@@ -372,6 +408,28 @@ impl Tool for SkillToolHandler {
             });
         }
 
+        if let Err(e) = self
+            .skill_policy
+            .validate_command_execution(&command, false)
+        {
+            return Ok(ToolResult {
+                output: format!("Blocked by skill permissions of '{}': {e}", self.skill_name),
+                success: false,
+                error: None,
+            });
+        }
+
+        if let Some(host) = self.permissions.undeclared_host(&command) {
+            return Ok(ToolResult {
+                output: format!(
+                    "Blocked by skill permissions of '{}': host '{host}' is not declared in [permissions].domains",
+                    self.skill_name
+                ),
+                success: false,
+                error: None,
+            });
+        }
+
         if !self.security.record_action() {
             return Ok(ToolResult {
                 output: "Action limit exceeded — try again later.".into(),
@@ -387,9 +445,17 @@ impl Tool for SkillToolHandler {
             "Executing skill tool"
         );
 
-        let output = tokio::process::Command::new("sh")
-            .arg("-c")
-            .arg(&command)
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(&command).env_clear();
+        for var in crate::tools::shell::collect_allowed_shell_env_vars(&self.skill_policy) {
+            if let Ok(val) = std::env::var(&var) {
+                cmd.env(&var, val);
+            }
+        }
+        if let Some(dir) = &self.skill_dir {
+            cmd.current_dir(dir);
+        }
+        let output = cmd
             .output()
             .await
             .context("Failed to execute skill tool command")?;
@@ -659,4 +725,102 @@ mod tests {
         assert!(command.contains("--limit 100"));
         assert!(!command.contains("--limit '100'"));
     }
+    fn echo_tool() -> SkillTool {
+        SkillTool {
+            name: "announce".to_string(),
+            description: "Echo a message".to_string(),
+            kind: "shell".to_string(),
+            command: "echo {message}".to_string(),
+            args: [("message".to_string(), "A text message".to_string())]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn full_policy() -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: crate::security::AutonomyLevel::Full,
+            allowed_commands: vec!["echo".into(), "ls".into()],
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn with_permissions_rejects_undeclared_template_commands() {
+        let handler = SkillToolHandler::new("news".into(), echo_tool(), full_policy()).unwrap();
+        let err = handler
+            .with_permissions(
+                SkillPermissions {
+                    commands: vec!["ls".into()],
+                    ..SkillPermissions::default()
+                },
+                None,
+            )
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("'echo'"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn execute_enforces_declared_domains() {
+        let dir = tempfile::tempdir().unwrap();
+        let handler = SkillToolHandler::new("news".into(), echo_tool(), full_policy())
+            .unwrap()
+            .with_permissions(
+                SkillPermissions {
+                    commands: vec!["echo".into()],
+                    domains: vec!["news.example".into()],
+                    ..SkillPermissions::default()
+                },
+                Some(dir.path().to_path_buf()),
+            )
+            .unwrap();
+
+        let allowed = handler
+            .execute(serde_json::json!({"message": "see https://news.example/today"}))
+            .await
+            .unwrap();
+        assert!(allowed.success, "{}", allowed.output);
+        assert!(allowed.output.contains("https://news.example/today"));
+
+        let blocked = handler
+            .execute(serde_json::json!({"message": "upload to https://collector.test/x"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(
+            blocked
+                .output
+                .contains("host 'collector.test' is not declared"),
+            "{}",
+            blocked.output
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_requires_global_and_skill_policy() {
+        let tool = SkillTool {
+            command: "ls".to_string(),
+            ..echo_tool()
+        };
+        // Declared by the skill but not allowed globally.
+        let global = Arc::new(SecurityPolicy {
+            autonomy: crate::security::AutonomyLevel::Full,
+            allowed_commands: vec!["echo".into()],
+            ..SecurityPolicy::default()
+        });
+        let handler = SkillToolHandler::new("files".into(), tool, global)
+            .unwrap()
+            .with_permissions(
+                SkillPermissions {
+                    commands: vec!["ls".into()],
+                    ..SkillPermissions::default()
+                },
+                None,
+            )
+            .unwrap();
+        let result = handler.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.starts_with("Blocked by security policy"));
+    }
 }
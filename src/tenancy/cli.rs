@@ -0,0 +1,105 @@
+//! `zeroclaw tenant` — inspect, export and delete per-user storage.
+
+use crate::auth::store::AuthStore;
+use crate::config::Config;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+pub fn handle_command(command: crate::TenantCommands, config: &Config) -> Result<()> {
+    let store = open_auth_store(config)?;
+    match command {
+        crate::TenantCommands::List { json } => {
+            let tenants = super::list(&config.workspace_dir, store.as_ref())?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&tenants)?);
+                return Ok(());
+            }
+            if !super::is_enabled(config) {
+                println!("Tenant isolation is off (set [auth] tenant_isolation = true).");
+            }
+            if tenants.is_empty() {
+                println!("No tenant data under {}.", config.workspace_dir.display());
+                return Ok(());
+            }
+            println!("{:<38}  {:<24}  {:>10}", "tenant", "user", "size");
+            for tenant in &tenants {
+                println!(
+                    "{:<38}  {:<24}  {:>10}",
+                    tenant.id,
+                    tenant.username.as_deref().unwrap_or("(deleted)"),
+                    format_size(tenant.size_bytes)
+                );
+            }
+            Ok(())
+        }
+        crate::TenantCommands::Export { user, output } => {
+            let tenant = resolve_tenant(store.as_ref(), &user)?;
+            let dest = super::export(
+                &config.workspace_dir,
+                store.as_ref(),
+                &tenant,
+                output.as_deref().map(Path::new),
+            )?;
+            println!("Exported tenant '{tenant}' to {}", dest.display());
+            Ok(())
+        }
+        crate::TenantCommands::Delete {
+            user,
+            keep_account,
+            yes,
+        } => {
+            let tenant = resolve_tenant(store.as_ref(), &user)?;
+            if !yes {
+                bail!(
+                    "Deleting tenant '{tenant}' removes all of its memory, vault and sessions; \
+                     re-run with --yes to confirm"
+                );
+            }
+            let deletion =
+                super::delete(&config.workspace_dir, store.as_ref(), &tenant, keep_account)?;
+            if !deletion.data_removed && !deletion.account_removed {
+                bail!("Nothing to delete for tenant '{tenant}'");
+            }
+            if deletion.data_removed {
+                println!("Deleted stored data of tenant '{tenant}'.");
+            }
+            if deletion.account_removed {
+                println!("Deleted user account '{tenant}' with its sessions and channel links.");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn open_auth_store(config: &Config) -> Result<Option<AuthStore>> {
+    let db_path = config.workspace_dir.join("auth.db");
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(AuthStore::new(&db_path, None)?))
+}
+
+/// Accept either a user ID or a username.
+fn resolve_tenant(store: Option<&AuthStore>, user: &str) -> Result<String> {
+    if let Some(store) = store {
+        if store.get_user(user)?.is_none() {
+            if let Some(info) = store
+                .list_all_users(0)?
+                .into_iter()
+                .find(|info| info.username == user)
+            {
+                return Ok(info.user_id);
+            }
+        }
+    }
+    Ok(user.to_string())
+}
+
+fn format_size(bytes: u64) -> String {
+    let kib = bytes as f64 / 1024.0;
+    if kib < 1024.0 {
+        format!("{kib:.1} KiB")
+    } else {
+        format!("{:.1} MiB", kib / 1024.0)
+    }
+}
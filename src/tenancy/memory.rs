@@ -0,0 +1,208 @@
+//! Memory backend that routes every call to the current tenant's store.
+
+use crate::config::{Config, MemoryConfig, StorageProviderConfig};
+use crate::memory::sync::{DeltaOperation, EmbeddingBlob, SyncEngine};
+use crate::memory::traits::MemoryConflict;
+use crate::memory::{Memory, MemoryCategory, MemoryEntry};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Wraps the workspace memory opened at startup. Calls made outside a
+/// tenant scope go to it unchanged; calls inside one go to a memory of the
+/// same backend opened under the tenant's directory on first use.
+pub struct TenantMemory {
+    shared: Arc<dyn Memory>,
+    config: MemoryConfig,
+    storage: StorageProviderConfig,
+    workspace_dir: PathBuf,
+    api_key: Option<String>,
+}
+
+impl TenantMemory {
+    pub fn new(shared: Arc<dyn Memory>, config: &Config) -> Self {
+        Self {
+            shared,
+            config: config.memory.clone(),
+            storage: config.storage.provider.config.clone(),
+            workspace_dir: config.workspace_dir.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+
+    fn resolve(&self) -> Result<Arc<dyn Memory>> {
+        let dir = super::workspace_dir(&self.workspace_dir);
+        if dir == self.workspace_dir {
+            return Ok(Arc::clone(&self.shared));
+        }
+        super::cached("memory", &dir, || {
+            crate::memory::create_memory_with_storage(
+                &self.config,
+                Some(&self.storage),
+                &dir,
+                self.api_key.as_deref(),
+            )
+            .map(Arc::from)
+        })
+    }
+}
+
+#[async_trait]
+impl Memory for TenantMemory {
+    fn name(&self) -> &str {
+        self.shared.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        self.resolve()?
+            .store(key, content, category, session_id)
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.resolve()?.recall(query, limit, session_id).await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        self.resolve()?.get(key).await
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.resolve()?.list(category, session_id).await
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        self.resolve()?.forget(key).await
+    }
+
+    async fn count(&self) -> Result<usize> {
+        self.resolve()?.count().await
+    }
+
+    async fn health_check(&self) -> bool {
+        match self.resolve() {
+            Ok(memory) => memory.health_check().await,
+            Err(_) => false,
+        }
+    }
+
+    async fn reindex(
+        &self,
+        progress_callback: Option<Box<dyn Fn(usize, usize) + Send + Sync>>,
+    ) -> Result<usize> {
+        self.resolve()?.reindex(progress_callback).await
+    }
+
+    async fn track_recall(&self, key: &str) -> Result<()> {
+        self.resolve()?.track_recall(key).await
+    }
+
+    async fn hot_memories(&self, limit: usize) -> Result<Vec<MemoryEntry>> {
+        self.resolve()?.hot_memories(limit).await
+    }
+
+    async fn detect_conflict(
+        &self,
+        key: &str,
+        new_content: &str,
+    ) -> Result<Option<MemoryConflict>> {
+        self.resolve()?.detect_conflict(key, new_content).await
+    }
+
+    async fn forget_matching(&self, pattern: &str) -> Result<usize> {
+        self.resolve()?.forget_matching(pattern).await
+    }
+
+    // Cross-device sync replicates the shared workspace only.
+    fn attach_sync_engine(&self, engine: Arc<parking_lot::Mutex<SyncEngine>>) {
+        self.shared.attach_sync_engine(engine);
+    }
+
+    async fn apply_remote_v3_delta(&self, delta: &DeltaOperation) -> Result<bool> {
+        self.shared.apply_remote_v3_delta(delta).await
+    }
+
+    async fn accept_remote_embedding(&self, content: &str, blob: &EmbeddingBlob) -> Result<bool> {
+        self.shared.accept_remote_embedding(content, blob).await
+    }
+
+    async fn current_embedding_blob(&self, content: &str) -> Option<EmbeddingBlob> {
+        self.resolve().ok()?.current_embedding_blob(content).await
+    }
+
+    async fn query_embedding(&self, query: &str) -> Option<Vec<f32>> {
+        self.resolve().ok()?.query_embedding(query).await
+    }
+
+    async fn accept_remote_store_if_newer(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        remote_hlc: &str,
+    ) -> Result<bool> {
+        self.shared
+            .accept_remote_store_if_newer(key, content, category, remote_hlc)
+            .await
+    }
+
+    async fn recall_with_variations(
+        &self,
+        original_query: &str,
+        variations: &[String],
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.resolve()?
+            .recall_with_variations(original_query, variations, limit, session_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tenants_do_not_see_each_others_memories() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        config.memory.backend = "sqlite".into();
+        config.memory.embedding_provider = "none".into();
+        let shared: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&config.memory, tmp.path(), None).unwrap());
+        let memory = TenantMemory::new(shared, &config);
+
+        super::super::scope(Some("alice".into()), async {
+            memory
+                .store("pet", "cat named Miso", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        })
+        .await;
+
+        let bob = super::super::scope(Some("bob".into()), memory.get("pet")).await;
+        assert!(bob.unwrap().is_none());
+        assert!(memory.get("pet").await.unwrap().is_none());
+        let alice = super::super::scope(Some("alice".into()), memory.get("pet")).await;
+        assert_eq!(alice.unwrap().unwrap().content, "cat named Miso");
+        assert!(tmp.path().join("tenants/alice/memory/brain.db").exists());
+    }
+}
@@ -0,0 +1,391 @@
+//! Per-user storage isolation for team deployments.
+//!
+//! With `[auth] tenant_isolation = true`, every authenticated user gets their
+//! own memory, vault, ontology and session databases under
+//! `{workspace}/tenants/{user_id}/` instead of sharing the workspace
+//! `brain.db`. Entry points resolve the tenant — the gateway from the login
+//! session, channels from the sender's linked identity — and run the request
+//! inside [`scope`]. Storage opened per request goes through
+//! [`workspace_dir`]; handles opened once at startup are wrapped in
+//! [`TenantMemory`] / [`TenantSessionManager`], which route every call to the
+//! current tenant. Work outside a scope (the CLI, pairing-token clients,
+//! unlinked channel senders) keeps using the shared workspace.
+
+pub mod cli;
+pub mod memory;
+pub mod sessions;
+
+pub use memory::TenantMemory;
+pub use sessions::TenantSessionManager;
+
+use crate::auth::store::AuthStore;
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Directory under the workspace that holds one subdirectory per tenant.
+pub const TENANTS_DIR: &str = "tenants";
+
+tokio::task_local! {
+    static CURRENT_TENANT: String;
+}
+
+/// Auth store used to resolve channel senders, present while isolation is on.
+static DIRECTORY: LazyLock<RwLock<Option<Arc<AuthStore>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Per-tenant storage handles, keyed by kind and tenant directory.
+type HandleCache = HashMap<(&'static str, PathBuf), Arc<dyn Any + Send + Sync>>;
+static HANDLES: LazyLock<Mutex<HandleCache>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `config` asks for per-user storage. Isolation needs user
+/// accounts, so it is ignored while `[auth]` is disabled.
+pub fn is_enabled(config: &Config) -> bool {
+    config.auth.enabled && config.auth.tenant_isolation
+}
+
+/// Open the auth store used for channel identity lookups (or clear it when
+/// isolation is disabled).
+pub fn init_from_config(config: &Config) {
+    let directory = if is_enabled(config) {
+        let db_path = config.workspace_dir.join("auth.db");
+        match AuthStore::new(&db_path, Some(config.auth.session_ttl_secs))
+            .and_then(|store| store.ensure_channel_links_table().map(|()| store))
+        {
+            Ok(store) => Some(Arc::new(store)),
+            Err(error) => {
+                tracing::warn!("Tenant resolution for channels disabled: {error}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut guard = DIRECTORY.write().unwrap_or_else(|e| e.into_inner());
+    *guard = directory;
+}
+
+/// Run `fut` on behalf of `tenant`. `None` runs it against the shared
+/// workspace.
+pub async fn scope<F: Future>(tenant: Option<String>, fut: F) -> F::Output {
+    match tenant {
+        Some(tenant) => CURRENT_TENANT.scope(tenant, fut).await,
+        None => fut.await,
+    }
+}
+
+/// The tenant the current task runs for, if any.
+pub fn current() -> Option<String> {
+    CURRENT_TENANT.try_with(Clone::clone).ok()
+}
+
+/// Storage root for the current tenant: `base/tenants/{id}` inside a
+/// [`scope`], `base` itself otherwise.
+pub fn workspace_dir(base: &Path) -> PathBuf {
+    match current() {
+        Some(tenant) => base.join(TENANTS_DIR).join(tenant),
+        None => base.to_path_buf(),
+    }
+}
+
+/// Storage root of `tenant`, rejecting IDs that could escape `tenants/`.
+pub fn tenant_dir(base: &Path, tenant: &str) -> Result<PathBuf> {
+    let valid = !tenant.is_empty()
+        && tenant.len() <= 64
+        && tenant
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!("Invalid tenant ID: {tenant:?}");
+    }
+    Ok(base.join(TENANTS_DIR).join(tenant))
+}
+
+/// The user a channel sender is linked to, when isolation is on.
+pub fn tenant_for_channel(channel: &str, sender: &str) -> Option<String> {
+    let directory = DIRECTORY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()?;
+    match directory.find_channel_link(channel, sender) {
+        Ok(user) => user.map(|user| user.id),
+        Err(error) => {
+            tracing::warn!(channel, "Channel identity lookup failed: {error}");
+            None
+        }
+    }
+}
+
+/// The storage handle of `kind` for the tenant rooted at `dir`, opened with
+/// `open` on first use and shared afterwards.
+pub(crate) fn cached<T, F>(kind: &'static str, dir: &Path, open: F) -> Result<T>
+where
+    T: Clone + Send + Sync + 'static,
+    F: FnOnce() -> Result<T>,
+{
+    let mut handles = HANDLES.lock();
+    let key = (kind, dir.to_path_buf());
+    if let Some(handle) = handles.get(&key).and_then(|h| h.downcast_ref::<T>()) {
+        return Ok(handle.clone());
+    }
+    let handle = open()?;
+    handles.insert(key, Arc::new(handle.clone()));
+    Ok(handle)
+}
+
+/// Drop every cached handle for the tenant rooted at `dir`.
+fn evict(dir: &Path) {
+    HANDLES.lock().retain(|(_, path), _| path != dir);
+}
+
+/// Wrap the memory backend opened at startup so tenants get their own.
+/// Only backends that live in the workspace directory can be split per
+/// tenant; remote ones are rejected rather than silently shared.
+pub fn wrap_memory(
+    shared: Arc<dyn crate::memory::Memory>,
+    config: &Config,
+) -> Result<Arc<dyn crate::memory::Memory>> {
+    if !is_enabled(config) {
+        return Ok(shared);
+    }
+    let backend = crate::memory::effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    );
+    match crate::memory::classify_memory_backend(&backend) {
+        crate::memory::MemoryBackendKind::Sqlite
+        | crate::memory::MemoryBackendKind::Lucid
+        | crate::memory::MemoryBackendKind::Markdown
+        | crate::memory::MemoryBackendKind::None => {}
+        _ => bail!(
+            "auth.tenant_isolation requires a workspace-local memory backend \
+             (sqlite, lucid, markdown or none), found '{backend}'"
+        ),
+    }
+    Ok(Arc::new(TenantMemory::new(shared, config)))
+}
+
+/// Wrap the session manager opened at startup so tenants get their own.
+pub fn wrap_session_manager(
+    shared: Option<Arc<dyn crate::agent::session::SessionManager>>,
+    config: &Config,
+) -> Option<Arc<dyn crate::agent::session::SessionManager>> {
+    let shared = shared?;
+    if !is_enabled(config) {
+        return Some(shared);
+    }
+    Some(Arc::new(TenantSessionManager::new(shared, config)))
+}
+
+/// A tenant with data on disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantInfo {
+    pub id: String,
+    /// Username of the owning account, `None` once the account is gone.
+    pub username: Option<String>,
+    pub size_bytes: u64,
+}
+
+/// What [`delete`] removed.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TenantDeletion {
+    pub data_removed: bool,
+    pub account_removed: bool,
+}
+
+/// Tenants with a directory under `{workspace}/tenants/`.
+pub fn list(workspace_dir: &Path, store: Option<&AuthStore>) -> Result<Vec<TenantInfo>> {
+    let root = workspace_dir.join(TENANTS_DIR);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut tenants = Vec::new();
+    for entry in std::fs::read_dir(&root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let id = entry.file_name().to_string_lossy().into_owned();
+        let username = store
+            .and_then(|store| store.get_user(&id).ok().flatten())
+            .map(|user| user.username);
+        tenants.push(TenantInfo {
+            size_bytes: dir_size(&entry.path()),
+            id,
+            username,
+        });
+    }
+    tenants.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(tenants)
+}
+
+/// Copy one tenant's storage to `out_dir` (default `{workspace}/exports`)
+/// and return the export directory. SQLite databases are written with
+/// `VACUUM INTO`, so the copy is consistent even while the gateway runs.
+/// A `tenant.json` manifest records the account and its channel links.
+pub fn export(
+    workspace_dir: &Path,
+    store: Option<&AuthStore>,
+    tenant: &str,
+    out_dir: Option<&Path>,
+) -> Result<PathBuf> {
+    let source = tenant_dir(workspace_dir, tenant)?;
+    if !source.is_dir() {
+        bail!("Tenant '{tenant}' has no stored data");
+    }
+    let out_dir = out_dir.map_or_else(|| workspace_dir.join("exports"), Path::to_path_buf);
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let dest = out_dir.join(format!("tenant-{tenant}-{stamp}"));
+    std::fs::create_dir_all(&dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    copy_tenant_tree(&source, &dest)?;
+
+    let user = store.and_then(|store| store.get_user(tenant).ok().flatten());
+    let links = store
+        .and_then(|store| store.list_user_channels(tenant).ok())
+        .unwrap_or_default();
+    let manifest = serde_json::json!({
+        "tenant": tenant,
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "user": user.map(|user| serde_json::json!({
+            "id": user.id,
+            "username": user.username,
+            "email": user.email,
+            "created_at": user.created_at,
+        })),
+        "channel_links": links.iter().map(|link| serde_json::json!({
+            "channel": link.channel,
+            "platform_uid": link.platform_uid,
+            "linked_at": link.linked_at,
+        })).collect::<Vec<_>>(),
+    });
+    std::fs::write(
+        dest.join("tenant.json"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(dest)
+}
+
+/// Remove one tenant's storage and, unless `keep_account`, the user
+/// account with its sessions, devices and channel links.
+pub fn delete(
+    workspace_dir: &Path,
+    store: Option<&AuthStore>,
+    tenant: &str,
+    keep_account: bool,
+) -> Result<TenantDeletion> {
+    let dir = tenant_dir(workspace_dir, tenant)?;
+    evict(&dir);
+    let mut deletion = TenantDeletion::default();
+    if dir.is_dir() {
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        deletion.data_removed = true;
+    }
+    if !keep_account {
+        if let Some(store) = store {
+            deletion.account_removed = store.delete_user(tenant)?;
+        }
+    }
+    Ok(deletion)
+}
+
+fn copy_tenant_tree(source: &Path, dest: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            copy_tenant_tree(&path, &target)?;
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.ends_with("-wal") || name.ends_with("-shm") || name.ends_with("-journal") {
+            // Folded into the database by VACUUM INTO.
+            continue;
+        }
+        if name.ends_with(".db") {
+            let conn = rusqlite::Connection::open(&path)?;
+            conn.execute("VACUUM INTO ?1", [target.to_string_lossy().as_ref()])
+                .with_context(|| format!("Failed to export {}", path.display()))?;
+        } else {
+            std::fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn workspace_dir_follows_the_current_scope() {
+        let base = Path::new("/srv/zeroclaw");
+        assert_eq!(workspace_dir(base), base);
+        let scoped = scope(Some("alice".into()), async { workspace_dir(base) }).await;
+        assert_eq!(scoped, base.join("tenants").join("alice"));
+        let unscoped = scope(None, async { workspace_dir(base) }).await;
+        assert_eq!(unscoped, base);
+    }
+
+    #[test]
+    fn tenant_dir_rejects_path_escapes() {
+        let base = Path::new("/srv/zeroclaw");
+        assert!(tenant_dir(base, "6f1c-42_ab").is_ok());
+        for bad in ["", "..", "a/b", "a\\b", "../etc"] {
+            assert!(tenant_dir(base, bad).is_err(), "{bad:?} accepted");
+        }
+    }
+
+    #[test]
+    fn export_then_delete_tenant() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tenant_dir(tmp.path(), "alice").unwrap();
+        std::fs::create_dir_all(dir.join("memory")).unwrap();
+        let conn = rusqlite::Connection::open(dir.join("memory").join("brain.db")).unwrap();
+        conn.execute_batch("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('hi');")
+            .unwrap();
+        drop(conn);
+        std::fs::write(dir.join("memory").join("MEMORY.md"), "# notes").unwrap();
+
+        let tenants = list(tmp.path(), None).unwrap();
+        assert_eq!(tenants.len(), 1);
+        assert_eq!(tenants[0].id, "alice");
+
+        let exported = export(tmp.path(), None, "alice", None).unwrap();
+        let copy = rusqlite::Connection::open(exported.join("memory").join("brain.db")).unwrap();
+        let body: String = copy
+            .query_row("SELECT body FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(body, "hi");
+        assert!(exported.join("memory").join("MEMORY.md").exists());
+        assert!(exported.join("tenant.json").exists());
+
+        let deletion = delete(tmp.path(), None, "alice", false).unwrap();
+        assert!(deletion.data_removed);
+        assert!(!dir.exists());
+        assert!(list(tmp.path(), None).unwrap().is_empty());
+    }
+}
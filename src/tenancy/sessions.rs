@@ -0,0 +1,122 @@
+//! Session manager that routes every call to the current tenant's store.
+
+use crate::agent::session::{shared_session_manager, Session, SessionManager};
+use crate::config::{AgentSessionConfig, Config};
+use crate::memory::InteractionCategory;
+use crate::providers::ChatMessage;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Wraps the session manager opened at startup. Calls made inside a tenant
+/// scope go to that tenant's session store; [`Session`] handles returned by
+/// `get_or_create` stay bound to the tenant they were created for.
+pub struct TenantSessionManager {
+    shared: Arc<dyn SessionManager>,
+    config: AgentSessionConfig,
+    workspace_dir: PathBuf,
+}
+
+impl TenantSessionManager {
+    pub fn new(shared: Arc<dyn SessionManager>, config: &Config) -> Self {
+        Self {
+            shared,
+            config: config.agent.session.clone(),
+            workspace_dir: config.workspace_dir.clone(),
+        }
+    }
+
+    fn resolve(&self) -> Result<Arc<dyn SessionManager>> {
+        let dir = super::workspace_dir(&self.workspace_dir);
+        if dir == self.workspace_dir {
+            return Ok(Arc::clone(&self.shared));
+        }
+        shared_session_manager(&self.config, &dir)?
+            .context("Session backend is disabled for tenant storage")
+    }
+}
+
+#[async_trait]
+impl SessionManager for TenantSessionManager {
+    fn clone_arc(&self) -> Arc<dyn SessionManager> {
+        Arc::new(Self {
+            shared: Arc::clone(&self.shared),
+            config: self.config.clone(),
+            workspace_dir: self.workspace_dir.clone(),
+        })
+    }
+
+    async fn ensure_exists(&self, session_id: &str) -> Result<()> {
+        self.resolve()?.ensure_exists(session_id).await
+    }
+
+    async fn get_history(&self, session_id: &str) -> Result<Vec<ChatMessage>> {
+        self.resolve()?.get_history(session_id).await
+    }
+
+    async fn set_history(&self, session_id: &str, history: Vec<ChatMessage>) -> Result<()> {
+        self.resolve()?.set_history(session_id, history).await
+    }
+
+    async fn delete(&self, session_id: &str) -> Result<()> {
+        self.resolve()?.delete(session_id).await
+    }
+
+    async fn cleanup_expired(&self) -> Result<usize> {
+        self.resolve()?.cleanup_expired().await
+    }
+
+    async fn append_turn(
+        &self,
+        session_id: &str,
+        role: &str,
+        content: &str,
+        channel: Option<&str>,
+        sender: Option<&str>,
+    ) -> Result<()> {
+        self.resolve()?
+            .append_turn(session_id, role, content, channel, sender)
+            .await
+    }
+
+    async fn append_turn_with_metadata(
+        &self,
+        session_id: &str,
+        role: &str,
+        content: &str,
+        channel: Option<&str>,
+        sender: Option<&str>,
+        interaction_category: &InteractionCategory,
+        location: Option<&str>,
+        counterpart: Option<&str>,
+    ) -> Result<()> {
+        self.resolve()?
+            .append_turn_with_metadata(
+                session_id,
+                role,
+                content,
+                channel,
+                sender,
+                interaction_category,
+                location,
+                counterpart,
+            )
+            .await
+    }
+
+    async fn recent_turns_for_sender(
+        &self,
+        sender: &str,
+        max_turns: usize,
+        max_age_secs: i64,
+    ) -> Result<Vec<ChatMessage>> {
+        self.resolve()?
+            .recent_turns_for_sender(sender, max_turns, max_age_secs)
+            .await
+    }
+
+    async fn get_or_create(&self, session_id: &str) -> Result<Session> {
+        self.resolve()?.get_or_create(session_id).await
+    }
+}
@@ -20,11 +20,22 @@ use tokio::process::Command;
 /// - It does NOT fetch, pull, or push.
 /// - It does NOT run arbitrary scripts.
 /// - It is intentionally narrow: patch in, apply/check, status/commit out.
-pub struct ApplyPatchTool;
+/// - With a workspace dir set, touched files are checkpointed before applying.
+pub struct ApplyPatchTool {
+    workspace_dir: Option<PathBuf>,
+}
 
 impl ApplyPatchTool {
     pub fn new() -> Self {
-        Self
+        Self {
+            workspace_dir: None,
+        }
+    }
+
+    /// Record checkpoints of patched files in this workspace's store.
+    pub fn with_workspace_dir(mut self, workspace_dir: impl Into<PathBuf>) -> Self {
+        self.workspace_dir = Some(workspace_dir.into());
+        self
     }
 
     fn schema() -> serde_json::Value {
@@ -159,6 +170,12 @@ impl Tool for ApplyPatchTool {
             });
         }
 
+        if let Some(workspace_dir) = &self.workspace_dir {
+            for path in patch_target_paths(&patch) {
+                crate::checkpoint::record_before_write(workspace_dir, &repo_root.join(path));
+            }
+        }
+
         // Apply patch.
         {
             let (code, out, err) = run_cmd(
@@ -292,6 +309,39 @@ impl Tool for ApplyPatchTool {
     }
 }
 
+/// Repo-relative paths a unified diff reads or writes (`a/` / `b/` prefixes
+/// stripped, `/dev/null` skipped).
+fn patch_target_paths(patch: &str) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for line in patch.lines() {
+        let raw = if let Some(rest) = line
+            .strip_prefix("--- ")
+            .or_else(|| line.strip_prefix("+++ "))
+        {
+            let name = rest.split('\t').next().unwrap_or(rest).trim_end();
+            if name == "/dev/null" {
+                continue;
+            }
+            name.strip_prefix("a/")
+                .or_else(|| name.strip_prefix("b/"))
+                .unwrap_or(name)
+        } else if let Some(rest) = line
+            .strip_prefix("rename from ")
+            .or_else(|| line.strip_prefix("rename to "))
+            .or_else(|| line.strip_prefix("copy to "))
+        {
+            rest.trim_end()
+        } else {
+            continue;
+        };
+        let path = PathBuf::from(raw);
+        if path.is_relative() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
 async fn git_repo_root() -> Result<PathBuf> {
     let cwd = std::env::current_dir().context("Failed to read current_dir")?;
     let (code, out, err) = run_cmd(&cwd, "git", &["rev-parse", "--show-toplevel"]).await?;
@@ -335,4 +385,33 @@ mod tests {
         assert!(s["properties"].is_object());
         assert!(s["properties"]["patch"].is_object());
     }
+
+    #[test]
+    fn patch_target_paths_covers_edits_creations_and_renames() {
+        let patch = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/new.txt b/new.txt
+new file mode 100644
+--- /dev/null
++++ b/new.txt\t2026-01-01 00:00:00
+@@ -0,0 +1 @@
++--- not a header
+diff --git a/old_name.rs b/new_name.rs
+similarity index 100%
+rename from old_name.rs
+rename to new_name.rs
+";
+        let paths = patch_target_paths(patch);
+        assert_eq!(
+            paths,
+            ["src/lib.rs", "new.txt", "old_name.rs", "new_name.rs"]
+                .map(PathBuf::from)
+                .to_vec()
+        );
+    }
 }
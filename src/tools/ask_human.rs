@@ -0,0 +1,227 @@
+//! `ask_human` — put a question or a draft in the human task inbox.
+//!
+//! The task is delivered to its assignee's channel and can be resolved from
+//! any linked channel, the dashboard or the CLI; see [`crate::inbox`].
+
+use super::traits::{Tool, ToolContext, ToolResult};
+use crate::inbox::{HumanInbox, HumanTask, HumanTaskKind, NewHumanTask, TaskWaiter};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait for a response when the caller does not say.
+const DEFAULT_WAIT_SECS: u64 = 300;
+/// Longest a single call may block the turn.
+const MAX_WAIT_SECS: u64 = 3600;
+
+pub struct AskHumanTool {
+    inbox: Arc<HumanInbox>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl AskHumanTool {
+    pub fn new(inbox: Arc<HumanInbox>, security: Arc<SecurityPolicy>) -> Self {
+        Self { inbox, security }
+    }
+
+    fn report(task: &HumanTask) -> ToolResult {
+        if task.is_open() {
+            let waiting = match &task.waiter {
+                TaskWaiter::Cron { .. } => {
+                    " This cron job will run again with the outcome once it is resolved."
+                }
+                _ => " Call ask_human again with this task_id to check on it.",
+            };
+            return ToolResult {
+                success: true,
+                output: format!(
+                    "Task {} is still pending (assigned to {}).{waiting}",
+                    task.id,
+                    task.current_assignee().unwrap_or("anyone")
+                ),
+                error: None,
+            };
+        }
+        ToolResult {
+            success: true,
+            output: task.outcome_summary(),
+            error: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AskHumanTool {
+    fn name(&self) -> &str {
+        "ask_human"
+    }
+
+    fn description(&self) -> &str {
+        "Ask a human a question or have them review a draft, through the human task inbox. \
+         The task reaches the assignee on their channel and can be answered from any linked \
+         channel, the dashboard or the CLI. Waits up to wait_secs for the response; pass \
+         task_id to check on an earlier task instead of creating one."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "question": {
+                    "type": "string",
+                    "description": "The question, or what the reviewer should decide"
+                },
+                "kind": {
+                    "type": "string",
+                    "enum": ["question", "review"],
+                    "default": "question",
+                    "description": "question: expects a free-text answer; review: approve or reject the draft"
+                },
+                "details": {
+                    "type": "string",
+                    "description": "Context for the question, or the draft to review"
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Who should answer, as channel:recipient (e.g. telegram:12345); defaults to the configured assignee"
+                },
+                "deadline_secs": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Seconds until the task expires; 0 for no deadline"
+                },
+                "wait_secs": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": MAX_WAIT_SECS,
+                    "default": DEFAULT_WAIT_SECS,
+                    "description": "How long to wait for the response before returning; 0 returns immediately"
+                },
+                "task_id": {
+                    "type": "string",
+                    "description": "Check on an earlier task instead of creating a new one"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.execute_with_context(args, &ToolContext::default())
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        let wait_secs = args
+            .get("wait_secs")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_WAIT_SECS)
+            .min(MAX_WAIT_SECS);
+
+        let task = if let Some(task_id) = args
+            .get("task_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+        {
+            match self.inbox.get(task_id)? {
+                Some(task) => task,
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Unknown inbox task '{task_id}'")),
+                    })
+                }
+            }
+        } else {
+            let Some(question) = args
+                .get("question")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|q| !q.is_empty())
+            else {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Missing 'question' parameter".to_string()),
+                });
+            };
+
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "ask_human")
+            {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+
+            let kind = match args.get("kind").and_then(|v| v.as_str()) {
+                Some("review") => HumanTaskKind::Review,
+                _ => HumanTaskKind::Question,
+            };
+            let attribution = crate::cost::attribution::current();
+            let waiter = match (
+                &attribution.cron_job,
+                &attribution.channel,
+                &attribution.user,
+            ) {
+                (Some(job_id), _, _) => TaskWaiter::Cron {
+                    job_id: job_id.clone(),
+                },
+                (None, Some(channel), Some(user)) => TaskWaiter::Agent {
+                    channel: channel.clone(),
+                    user: user.clone(),
+                },
+                _ => TaskWaiter::None,
+            };
+            let mut new = NewHumanTask::new(kind, question)
+                .with_body(
+                    args.get("details")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default(),
+                )
+                .with_assignee(
+                    args.get("assignee")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                )
+                .with_waiter(waiter)
+                .with_deadline_secs(
+                    args.get("deadline_secs")
+                        .and_then(serde_json::Value::as_u64),
+                );
+            if let (Some(channel), Some(user)) = (&attribution.channel, &attribution.user) {
+                new = new.with_created_by(format!("{channel}:{user}"));
+            }
+            self.inbox.create(new)?
+        };
+
+        // Cron jobs are resumed by the scheduler; do not hold the run open.
+        if !task.is_open() || wait_secs == 0 || matches!(task.waiter, TaskWaiter::Cron { .. }) {
+            return Ok(Self::report(&task));
+        }
+
+        ctx.progress
+            .report(format!("waiting for a human on task {}", task.id));
+        let task = self
+            .inbox
+            .wait(
+                &task.id,
+                Some(Duration::from_secs(wait_secs)),
+                Some(&ctx.cancellation),
+            )
+            .await?;
+        Ok(Self::report(&task))
+    }
+}
@@ -5,7 +5,7 @@
 //! `--features browser-native` and selected through config.
 //! Computer-use (OS-level) actions are supported via an optional sidecar endpoint.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolContext, ToolResult};
 use super::url_validation::{validate_url as validate_network_url, DomainPolicy, UrlSchemePolicy};
 use crate::config::UrlAccessConfig;
 use crate::security::SecurityPolicy;
@@ -1501,6 +1501,25 @@ impl Tool for BrowserTool {
         })
     }
 
+    async fn execute_with_context(
+        &self,
+        args: Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        ctx.progress.report(format!("browser action '{action}'"));
+        tokio::select! {
+            () = ctx.cancelled() => Ok(ToolResult::cancelled(format!(
+                "Browser action '{action}' was interrupted; the page may be in an intermediate state."
+            ))),
+            result = self.execute(args) => result,
+        }
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         // Security checks
         if !self.security.can_act() {
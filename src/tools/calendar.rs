@@ -4,15 +4,22 @@
 //! - **Google Calendar** (REST v3) — primary backend, covers Samsung Calendar
 //! - **Microsoft Outlook** (Graph API) — enterprise/business users
 //! - **KakaoTalk 톡캘린더** (Kakao REST API) — Korean users
+//! - **CalDAV** (Nextcloud, Radicale, iCloud, ...) — self-hosted calendars
+//! - **Local ICS file** — plain `.ics` files, no account needed
 //!
 //! The tools expose a unified interface so the LLM can:
 //! - List upcoming events
 //! - Create new events / reminders
 //! - Search events by keyword or date range
+//! - Update and delete events (CalDAV and ICS backends)
 
 use async_trait::async_trait;
+use chrono_tz::Tz;
 use serde_json::json;
+use std::sync::Arc;
 
+use super::calendar_caldav::CalDavClient;
+use super::calendar_ical::{self, Calendar, Event, EventChanges, EventTime, IcsFile, NewEvent};
 use super::traits::{Tool, ToolResult};
 
 // ═══════════════════════════════════════════════════════════════════
@@ -33,6 +40,24 @@ pub enum CalendarProvider {
         access_token: String,
         calendar_id: Option<String>,
     },
+    /// CalDAV server; `timezone` applies to floating times and new
+    /// recurring events.
+    CalDav {
+        client: Arc<CalDavClient>,
+        timezone: Tz,
+    },
+    /// Local `.ics` file.
+    Ics {
+        file: IcsFile,
+        timezone: Tz,
+    },
+}
+
+impl CalendarProvider {
+    /// Whether events can be updated and deleted by UID.
+    pub fn supports_edits(&self) -> bool {
+        matches!(self, Self::CalDav { .. } | Self::Ics { .. })
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════
@@ -59,7 +84,8 @@ impl Tool for CalendarListEventsTool {
     fn description(&self) -> &str {
         "List upcoming events from the user's calendar. \
          Shows event title, start/end time, location, and description. \
-         Supports Google Calendar, Microsoft Outlook, and KakaoTalk 톡캘린더."
+         Supports Google Calendar, Microsoft Outlook, KakaoTalk 톡캘린더, \
+         CalDAV and local ICS files (recurring events are expanded)."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -211,6 +237,48 @@ impl Tool for CalendarListEventsTool {
                     error: None,
                 })
             }
+            CalendarProvider::CalDav { client, timezone } => {
+                let time_max = now + chrono::Duration::days(days_ahead as i64);
+                let resources = match client.events_between(now, time_max).await {
+                    Ok(resources) => resources,
+                    Err(e) => return Ok(calendar_error("CalDAV", &e)),
+                };
+                let events: Vec<Event> = resources
+                    .iter()
+                    .flat_map(|resource| Calendar::parse(&resource.data).events())
+                    .collect();
+                Ok(ToolResult {
+                    success: true,
+                    output: format_ical_events(
+                        &events,
+                        now,
+                        time_max,
+                        query,
+                        max_results,
+                        *timezone,
+                    ),
+                    error: None,
+                })
+            }
+            CalendarProvider::Ics { file, timezone } => {
+                let time_max = now + chrono::Duration::days(days_ahead as i64);
+                let calendar = match file.load().await {
+                    Ok(calendar) => calendar,
+                    Err(e) => return Ok(calendar_error("ICS", &e)),
+                };
+                Ok(ToolResult {
+                    success: true,
+                    output: format_ical_events(
+                        &calendar.events(),
+                        now,
+                        time_max,
+                        query,
+                        max_results,
+                        *timezone,
+                    ),
+                    error: None,
+                })
+            }
         }
     }
 }
@@ -239,7 +307,8 @@ impl Tool for CalendarCreateEventTool {
     fn description(&self) -> &str {
         "Create a new event or reminder on the user's calendar. \
          Supports setting title, start/end time, location, description, \
-         and reminders. Works with Google Calendar, Outlook, and KakaoTalk 톡캘린더."
+         reminders and recurrence. Works with Google Calendar, Outlook, \
+         KakaoTalk 톡캘린더, CalDAV and local ICS files."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -279,6 +348,10 @@ impl Tool for CalendarCreateEventTool {
                 "timezone": {
                     "type": "string",
                     "description": "Timezone (e.g. 'Asia/Seoul'). Defaults to user's home timezone."
+                },
+                "recurrence": {
+                    "type": "string",
+                    "description": "Optional RFC 5545 recurrence rule, e.g. 'FREQ=WEEKLY;BYDAY=MO,WE;COUNT=10'. Supported by Google, CalDAV and ICS backends."
                 }
             },
             "required": ["title", "start_time"]
@@ -310,6 +383,11 @@ impl Tool for CalendarCreateEventTool {
             .get("timezone")
             .and_then(|v| v.as_str())
             .unwrap_or("Asia/Seoul");
+        let recurrence = args
+            .get("recurrence")
+            .and_then(|v| v.as_str())
+            .map(|rule| rule.trim().trim_start_matches("RRULE:"))
+            .filter(|rule| !rule.is_empty());
 
         let client = crate::config::build_runtime_proxy_client("tool.calendar");
 
@@ -354,6 +432,9 @@ impl Tool for CalendarCreateEventTool {
                 if let Some(desc) = description {
                     body["description"] = json!(desc);
                 }
+                if let Some(rule) = recurrence {
+                    body["recurrence"] = json!([format!("RRULE:{rule}")]);
+                }
 
                 let resp = client
                     .post(&url)
@@ -490,6 +571,268 @@ impl Tool for CalendarCreateEventTool {
                     error: None,
                 })
             }
+            CalendarProvider::CalDav { .. } | CalendarProvider::Ics { .. } => {
+                let default_tz = provider_timezone(&self.provider);
+                let tz = timezone.parse::<Tz>().unwrap_or(default_tz);
+                let new_event = match build_new_event(NewEventArgs {
+                    title,
+                    start_time,
+                    end_time,
+                    all_day,
+                    tz,
+                    location,
+                    description,
+                    recurrence,
+                    reminder_minutes,
+                }) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some(format!("{e:#}")),
+                        })
+                    }
+                };
+                let uid = calendar_ical::new_uid();
+                let saved = match &self.provider {
+                    CalendarProvider::CalDav { client, .. } => {
+                        let mut calendar = Calendar::empty();
+                        calendar.add_event(&uid, &new_event);
+                        client
+                            .create(&uid, calendar.serialize())
+                            .await
+                            .map(|href| format!("CalDAV event created.\nURL: {href}"))
+                    }
+                    CalendarProvider::Ics { file, .. } => ics_modify(file, |existing| {
+                        existing.add_event(&uid, &new_event);
+                        true
+                    })
+                    .await
+                    .map(|_| format!("Event added to {}.", file.path().display())),
+                    _ => unreachable!("matched CalDAV/ICS above"),
+                };
+                match saved {
+                    Ok(header) => Ok(ToolResult {
+                        success: true,
+                        output: format!(
+                            "{header}\nTitle: {title}\nStart: {}\nID: {uid}",
+                            new_event.start.display(tz)
+                        ),
+                        error: None,
+                    }),
+                    Err(e) => Ok(calendar_error(backend_label(&self.provider), &e)),
+                }
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Calendar Update / Delete Event Tools (CalDAV, ICS)
+// ═══════════════════════════════════════════════════════════════════
+
+/// Update an existing event by ID. Recurring events change as a series.
+pub struct CalendarUpdateEventTool {
+    provider: CalendarProvider,
+}
+
+impl CalendarUpdateEventTool {
+    pub fn new(provider: CalendarProvider) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarUpdateEventTool {
+    fn name(&self) -> &str {
+        "calendar_update_event"
+    }
+
+    fn description(&self) -> &str {
+        "Update an existing calendar event by its ID (from calendar_list_events). \
+         Only the given fields change; moving the start keeps the event's duration \
+         unless end_time is also given. Recurring events are updated as a whole series. \
+         Works with CalDAV and local ICS calendars."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "event_id": {
+                    "type": "string",
+                    "description": "Event ID as shown by calendar_list_events"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "New event title"
+                },
+                "start_time": {
+                    "type": "string",
+                    "description": "New start time in ISO 8601 format, or YYYY-MM-DD for all-day events"
+                },
+                "end_time": {
+                    "type": "string",
+                    "description": "New end time in ISO 8601 format, or YYYY-MM-DD for all-day events"
+                },
+                "location": {
+                    "type": "string",
+                    "description": "New event location"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "New event description / notes"
+                }
+            },
+            "required": ["event_id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let event_id = args
+            .get("event_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: event_id"))?;
+        let text = |key: &str| args.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let start_time = args.get("start_time").and_then(|v| v.as_str());
+        let end_time = args.get("end_time").and_then(|v| v.as_str());
+
+        if !self.provider.supports_edits() {
+            return Ok(edits_unsupported(&self.provider));
+        }
+        let default_tz = provider_timezone(&self.provider);
+
+        let result = match &self.provider {
+            CalendarProvider::CalDav { client, .. } => match client.find_event(event_id).await {
+                Ok(Some(resource)) => {
+                    let mut calendar = Calendar::parse(&resource.data);
+                    match event_changes(
+                        &calendar, event_id, &text, start_time, end_time, default_tz,
+                    ) {
+                        Ok(changes) => {
+                            calendar.update_event(event_id, &changes);
+                            client.update(&resource, calendar.serialize()).await
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                Ok(None) => Err(anyhow::anyhow!("No event with ID {event_id}")),
+                Err(e) => Err(e),
+            },
+            CalendarProvider::Ics { file, .. } => {
+                let mut outcome = Ok(());
+                let saved = ics_modify(file, |calendar| {
+                    match event_changes(calendar, event_id, &text, start_time, end_time, default_tz)
+                    {
+                        Ok(changes) => calendar.update_event(event_id, &changes),
+                        Err(e) => {
+                            outcome = Err(e);
+                            false
+                        }
+                    }
+                })
+                .await;
+                outcome.and(saved).and_then(|updated| {
+                    if updated {
+                        Ok(())
+                    } else {
+                        Err(anyhow::anyhow!("No event with ID {event_id}"))
+                    }
+                })
+            }
+            _ => unreachable!("supports_edits checked above"),
+        };
+
+        match result {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Event {event_id} updated."),
+                error: None,
+            }),
+            Err(e) => Ok(calendar_error(backend_label(&self.provider), &e)),
+        }
+    }
+}
+
+/// Delete an event by ID, including all instances of a recurring event.
+pub struct CalendarDeleteEventTool {
+    provider: CalendarProvider,
+}
+
+impl CalendarDeleteEventTool {
+    pub fn new(provider: CalendarProvider) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarDeleteEventTool {
+    fn name(&self) -> &str {
+        "calendar_delete_event"
+    }
+
+    fn description(&self) -> &str {
+        "Delete a calendar event by its ID (from calendar_list_events). \
+         Recurring events are deleted with all their instances. \
+         Works with CalDAV and local ICS calendars."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "event_id": {
+                    "type": "string",
+                    "description": "Event ID as shown by calendar_list_events"
+                }
+            },
+            "required": ["event_id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let event_id = args
+            .get("event_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: event_id"))?;
+
+        let result = match &self.provider {
+            CalendarProvider::CalDav { client, .. } => match client.find_event(event_id).await {
+                Ok(Some(resource)) => {
+                    let mut calendar = Calendar::parse(&resource.data);
+                    calendar.remove_event(event_id);
+                    if calendar.events().is_empty() {
+                        client.delete(&resource).await
+                    } else {
+                        // Other events share this object; keep them.
+                        client.update(&resource, calendar.serialize()).await
+                    }
+                }
+                Ok(None) => Err(anyhow::anyhow!("No event with ID {event_id}")),
+                Err(e) => Err(e),
+            },
+            CalendarProvider::Ics { file, .. } => {
+                ics_modify(file, |calendar| calendar.remove_event(event_id))
+                    .await
+                    .and_then(|removed| {
+                        if removed {
+                            Ok(())
+                        } else {
+                            Err(anyhow::anyhow!("No event with ID {event_id}"))
+                        }
+                    })
+            }
+            _ => return Ok(edits_unsupported(&self.provider)),
+        };
+
+        match result {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Event {event_id} deleted."),
+                error: None,
+            }),
+            Err(e) => Ok(calendar_error(backend_label(&self.provider), &e)),
         }
     }
 }
@@ -599,6 +942,239 @@ fn format_kakao_events(data: &serde_json::Value) -> String {
     out
 }
 
+/// List-tool output for CalDAV / ICS events, including IDs for edits.
+fn format_ical_events(
+    events: &[Event],
+    from: chrono::DateTime<chrono::Utc>,
+    to: chrono::DateTime<chrono::Utc>,
+    query: Option<&str>,
+    max_results: u64,
+    timezone: Tz,
+) -> String {
+    let query = query.map(str::to_lowercase);
+    let occurrences: Vec<_> = calendar_ical::expand(events, from, to, timezone)
+        .into_iter()
+        .filter(|occurrence| {
+            let Some(query) = &query else {
+                return true;
+            };
+            let event = occurrence.event;
+            [
+                Some(&event.summary),
+                event.description.as_ref(),
+                event.location.as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|text| text.to_lowercase().contains(query))
+        })
+        .take(usize::try_from(max_results).unwrap_or(usize::MAX))
+        .collect();
+    if occurrences.is_empty() {
+        return "No upcoming events found.".to_string();
+    }
+
+    use std::fmt::Write as _;
+    let mut out = format!("Found {} upcoming event(s):\n\n", occurrences.len());
+    for (i, occurrence) in occurrences.iter().enumerate() {
+        let event = occurrence.event;
+        let title: &str = if event.summary.is_empty() {
+            "(No title)"
+        } else {
+            &event.summary
+        };
+        let _ = write!(
+            out,
+            "{}. {} ({}~{})",
+            i + 1,
+            title,
+            occurrence.start.display(timezone),
+            occurrence.end.display(timezone)
+        );
+        if let Some(loc) = &event.location {
+            let _ = write!(out, " @ {loc}");
+        }
+        if occurrence.is_recurring() {
+            out.push_str(" [recurring]");
+        }
+        let _ = writeln!(out, " (ID: {})", event.uid);
+    }
+    out
+}
+
+struct NewEventArgs<'a> {
+    title: &'a str,
+    start_time: &'a str,
+    end_time: Option<&'a str>,
+    all_day: bool,
+    tz: Tz,
+    location: Option<&'a str>,
+    description: Option<&'a str>,
+    recurrence: Option<&'a str>,
+    reminder_minutes: u64,
+}
+
+/// Build the VEVENT for a create call. Recurring events keep their wall
+/// time in `tz` (so they follow DST); one-off events are stored in UTC.
+fn build_new_event(args: NewEventArgs<'_>) -> anyhow::Result<NewEvent> {
+    let zone = args.recurrence.map(|_| args.tz);
+    let (start, end) = if args.all_day {
+        let start = EventTime::Date(parse_date_arg(args.start_time)?);
+        let end = match args.end_time {
+            Some(end) => EventTime::Date(parse_date_arg(end)?),
+            None => start,
+        };
+        // DTEND is exclusive for all-day events.
+        let end = if end.naive() <= start.naive() {
+            start.shifted(chrono::Duration::days(1))
+        } else {
+            end
+        };
+        (start, end)
+    } else {
+        let start = parse_time_arg(args.start_time, args.tz, zone)?;
+        let end = match args.end_time {
+            Some(end) => parse_time_arg(end, args.tz, zone)?,
+            None => start.shifted(chrono::Duration::hours(1)),
+        };
+        (start, end)
+    };
+    if let Some(rule) = args.recurrence {
+        calendar_ical::validate_rrule(rule)
+            .map_err(|e| anyhow::anyhow!("Invalid recurrence rule '{rule}': {e}"))?;
+    }
+    Ok(NewEvent {
+        summary: args.title.to_string(),
+        start,
+        end,
+        location: args.location.map(str::to_string),
+        description: args.description.map(str::to_string),
+        rrule: args.recurrence.map(str::to_string),
+        reminder_minutes: Some(args.reminder_minutes),
+    })
+}
+
+fn parse_date_arg(value: &str) -> anyhow::Result<chrono::NaiveDate> {
+    let date = value.get(..10).unwrap_or(value);
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date: {value} (expected YYYY-MM-DD)"))
+}
+
+/// Parse an ISO 8601 tool argument. Values without an offset are read in
+/// `tz`; the result is kept as wall time in `zone` if given, else UTC.
+fn parse_time_arg(value: &str, tz: Tz, zone: Option<Tz>) -> anyhow::Result<EventTime> {
+    use chrono::TimeZone;
+
+    let instant = match chrono::DateTime::parse_from_rfc3339(value) {
+        Ok(dt) => dt.with_timezone(&chrono::Utc),
+        Err(_) => {
+            let naive = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+                .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+                .map_err(|_| anyhow::anyhow!("Invalid time: {value} (expected ISO 8601)"))?;
+            tz.from_local_datetime(&naive)
+                .earliest()
+                .ok_or_else(|| anyhow::anyhow!("{value} does not exist in {tz}"))?
+                .with_timezone(&chrono::Utc)
+        }
+    };
+    Ok(match zone {
+        Some(zone) => EventTime::Local(instant.with_timezone(&zone).naive_local(), Some(zone)),
+        None => EventTime::Utc(instant),
+    })
+}
+
+/// Translate update-tool arguments into changes for the master event `uid`.
+fn event_changes(
+    calendar: &Calendar,
+    uid: &str,
+    text: &dyn Fn(&str) -> Option<String>,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+    default_tz: Tz,
+) -> anyhow::Result<EventChanges> {
+    let events = calendar.events();
+    let Some(existing) = events
+        .iter()
+        .find(|event| event.uid == uid && event.recurrence_id.is_none())
+    else {
+        anyhow::bail!("No event with ID {uid}");
+    };
+    // New times keep the kind of the stored start (all-day, zoned, UTC).
+    let convert = |value: &str| match existing.start {
+        EventTime::Date(_) => parse_date_arg(value).map(EventTime::Date),
+        EventTime::Local(_, Some(zone)) => parse_time_arg(value, zone, Some(zone)),
+        _ => parse_time_arg(value, default_tz, None),
+    };
+
+    let start = start_time.map(convert).transpose()?;
+    let mut end = end_time.map(convert).transpose()?;
+    if let (Some(start), None) = (start, end) {
+        end = Some(start.shifted(existing.duration()));
+    }
+    let changes = EventChanges {
+        summary: text("title"),
+        start,
+        end,
+        location: text("location"),
+        description: text("description"),
+    };
+    if changes.is_empty() {
+        anyhow::bail!("Nothing to update: pass at least one of title, start_time, end_time, location, description");
+    }
+    Ok(changes)
+}
+
+/// Load, edit and save an ICS file; `edit` returns whether it changed.
+async fn ics_modify(
+    file: &IcsFile,
+    edit: impl FnOnce(&mut Calendar) -> bool,
+) -> anyhow::Result<bool> {
+    let mut calendar = file.load().await?;
+    let changed = edit(&mut calendar);
+    if changed {
+        file.save(&calendar).await?;
+    }
+    Ok(changed)
+}
+
+fn provider_timezone(provider: &CalendarProvider) -> Tz {
+    match provider {
+        CalendarProvider::CalDav { timezone, .. } | CalendarProvider::Ics { timezone, .. } => {
+            *timezone
+        }
+        _ => chrono_tz::Asia::Seoul,
+    }
+}
+
+fn backend_label(provider: &CalendarProvider) -> &'static str {
+    match provider {
+        CalendarProvider::Google { .. } => "Google Calendar",
+        CalendarProvider::Outlook { .. } => "Outlook Calendar",
+        CalendarProvider::Kakao { .. } => "KakaoTalk Calendar",
+        CalendarProvider::CalDav { .. } => "CalDAV",
+        CalendarProvider::Ics { .. } => "ICS",
+    }
+}
+
+fn calendar_error(backend: &str, error: &anyhow::Error) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(format!("{backend} error: {error:#}")),
+    }
+}
+
+fn edits_unsupported(provider: &CalendarProvider) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(format!(
+            "Updating and deleting events is not supported for {}; use a CalDAV or ICS calendar.",
+            backend_label(provider)
+        )),
+    }
+}
+
 fn format_default_end_time(start: &str) -> String {
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(start) {
         (dt + chrono::Duration::hours(1)).to_rfc3339()
@@ -707,6 +1283,80 @@ mod tests {
             .is_err());
     }
 
+    #[tokio::test]
+    async fn ics_backend_create_list_update_delete() {
+        let tmp = tempfile::tempdir().unwrap();
+        let provider = CalendarProvider::Ics {
+            file: IcsFile::new(tmp.path().join("team.ics")),
+            timezone: chrono_tz::Europe::Berlin,
+        };
+        let start = (chrono::Utc::now() + chrono::Duration::days(1))
+            .with_timezone(&chrono_tz::Europe::Berlin)
+            .format("%Y-%m-%dT10:00:00")
+            .to_string();
+
+        let created = CalendarCreateEventTool::new(provider.clone())
+            .execute(json!({
+                "title": "Planning",
+                "start_time": start,
+                "timezone": "Europe/Berlin",
+                "recurrence": "FREQ=DAILY;COUNT=3"
+            }))
+            .await
+            .unwrap();
+        assert!(created.success, "{:?}", created.error);
+        let uid = created
+            .output
+            .rsplit("ID: ")
+            .next()
+            .unwrap()
+            .trim()
+            .to_string();
+
+        let list = CalendarListEventsTool::new(provider.clone());
+        let listed = list.execute(json!({})).await.unwrap();
+        assert!(listed.output.starts_with("Found 3 upcoming event(s)"));
+        assert!(listed.output.contains("[recurring]"));
+        assert!(listed.output.contains(&uid));
+
+        let updated = CalendarUpdateEventTool::new(provider.clone())
+            .execute(json!({"event_id": uid, "title": "Sprint planning", "location": "Room 2"}))
+            .await
+            .unwrap();
+        assert!(updated.success, "{:?}", updated.error);
+        let listed = list.execute(json!({"query": "sprint"})).await.unwrap();
+        assert!(listed.output.contains("Sprint planning"));
+        assert!(listed.output.contains("@ Room 2"));
+
+        let delete = CalendarDeleteEventTool::new(provider);
+        assert!(
+            delete
+                .execute(json!({"event_id": uid}))
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(
+            !delete
+                .execute(json!({"event_id": uid}))
+                .await
+                .unwrap()
+                .success
+        );
+        let listed = list.execute(json!({})).await.unwrap();
+        assert_eq!(listed.output, "No upcoming events found.");
+    }
+
+    #[tokio::test]
+    async fn cloud_providers_reject_edits() {
+        let tool = CalendarDeleteEventTool::new(CalendarProvider::Outlook {
+            access_token: "t".into(),
+        });
+        let result = tool.execute(json!({"event_id": "x"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("CalDAV or ICS"));
+    }
+
     #[tokio::test]
     async fn calendar_create_missing_start_fails() {
         let tool = CalendarCreateEventTool::new(CalendarProvider::Kakao {
@@ -0,0 +1,569 @@
+//! CalDAV (RFC 4791) client for the calendar tools.
+//!
+//! Works with Nextcloud, Radicale, iCloud and other CalDAV servers:
+//! - Discovery: `current-user-principal` → `calendar-home-set` → first
+//!   VEVENT calendar (or the one named in config), with `/.well-known/caldav`
+//!   as fallback; a configured collection URL skips discovery
+//! - `REPORT calendar-query` with a time range for listing, and a UID
+//!   `text-match` for lookups
+//! - Create / update / delete guarded by `If-None-Match` / `If-Match` ETags
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use reqwest::{Method, StatusCode, Url};
+
+const PROPFIND_PRINCIPAL: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:current-user-principal/><d:resourcetype/></d:prop></d:propfind>"#;
+
+const PROPFIND_HOME: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><c:calendar-home-set/></d:prop></d:propfind>"#;
+
+const PROPFIND_CALENDARS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><d:resourcetype/><d:displayname/><c:supported-calendar-component-set/></d:prop></d:propfind>"#;
+
+/// One calendar object resource (`.ics`) on the server.
+#[derive(Debug, Clone)]
+pub struct CalendarResource {
+    pub href: Url,
+    pub etag: Option<String>,
+    pub data: String,
+}
+
+/// CalDAV account with a lazily discovered calendar collection.
+pub struct CalDavClient {
+    base_url: String,
+    username: String,
+    password: String,
+    /// Collection URL/path, or the display name of the calendar to use.
+    calendar: Option<String>,
+    collection: Mutex<Option<Url>>,
+}
+
+impl CalDavClient {
+    pub fn new(
+        base_url: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+        calendar: Option<String>,
+    ) -> Self {
+        Self {
+            base_url: base_url.into(),
+            username: username.into(),
+            password: password.into(),
+            calendar: calendar.filter(|calendar| !calendar.trim().is_empty()),
+            collection: Mutex::new(None),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        url: Url,
+        headers: &[(&str, &str)],
+        body: Option<String>,
+    ) -> Result<reqwest::Response> {
+        let method = Method::from_bytes(method.as_bytes())?;
+        let client = crate::config::build_runtime_proxy_client("tool.calendar");
+        let mut request = client
+            .request(method.clone(), url.clone())
+            .basic_auth(&self.username, Some(&self.password));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        request
+            .send()
+            .await
+            .with_context(|| format!("CalDAV {method} {url} failed"))
+    }
+
+    /// PROPFIND / REPORT returning the parsed multistatus body.
+    async fn multistatus(
+        &self,
+        method: &str,
+        url: Url,
+        depth: &str,
+        body: &str,
+    ) -> Result<Vec<DavResponse>> {
+        let resp = self
+            .send(
+                method,
+                url.clone(),
+                &[
+                    ("Depth", depth),
+                    ("Content-Type", "application/xml; charset=utf-8"),
+                ],
+                Some(body.to_string()),
+            )
+            .await?;
+        let status = resp.status();
+        if status != StatusCode::MULTI_STATUS && !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            bail!("CalDAV {method} {url} returned {status}: {text}");
+        }
+        parse_multistatus(&resp.text().await?)
+    }
+
+    /// The calendar collection URL, discovered on first use.
+    pub async fn calendar_url(&self) -> Result<Url> {
+        if let Some(url) = self.collection.lock().clone() {
+            return Ok(url);
+        }
+        let url = self.discover().await?;
+        *self.collection.lock() = Some(url.clone());
+        Ok(url)
+    }
+
+    async fn discover(&self) -> Result<Url> {
+        let base = Url::parse(&self.base_url)
+            .with_context(|| format!("Invalid CalDAV URL: {}", self.base_url))?;
+        if let Some(calendar) = self.calendar.as_deref().filter(|c| c.contains('/')) {
+            return Ok(as_collection(base.join(calendar)?));
+        }
+
+        let mut principal = None;
+        for candidate in [base.clone(), base.join("/.well-known/caldav")?] {
+            let responses = self
+                .multistatus("PROPFIND", candidate.clone(), "0", PROPFIND_PRINCIPAL)
+                .await;
+            let Ok(responses) = responses else {
+                continue;
+            };
+            if self.calendar.is_none() && responses.iter().any(|r| r.is_calendar) {
+                return Ok(as_collection(candidate));
+            }
+            if let Some(href) = responses.iter().find_map(|r| r.principal.clone()) {
+                principal = Some(candidate.join(&href)?);
+                break;
+            }
+        }
+        let principal = principal.unwrap_or_else(|| base.clone());
+
+        let home = self
+            .multistatus("PROPFIND", principal.clone(), "0", PROPFIND_HOME)
+            .await?
+            .into_iter()
+            .find_map(|r| r.calendar_home)
+            .map(|href| principal.join(&href))
+            .transpose()?
+            .unwrap_or(principal);
+
+        let calendars: Vec<DavResponse> = self
+            .multistatus("PROPFIND", home.clone(), "1", PROPFIND_CALENDARS)
+            .await?
+            .into_iter()
+            .filter(|r| {
+                r.is_calendar
+                    && (r.components.is_empty() || r.components.iter().any(|c| c == "VEVENT"))
+            })
+            .collect();
+        let chosen = match &self.calendar {
+            Some(name) => calendars.into_iter().find(|r| {
+                r.display_name.as_deref() == Some(name.as_str())
+                    || r.href.trim_end_matches('/').rsplit('/').next() == Some(name.as_str())
+            }),
+            None => calendars.into_iter().next(),
+        };
+        let Some(chosen) = chosen else {
+            bail!(
+                "No CalDAV event calendar{} found under {home}",
+                self.calendar
+                    .as_deref()
+                    .map(|name| format!(" named '{name}'"))
+                    .unwrap_or_default()
+            );
+        };
+        Ok(as_collection(home.join(&chosen.href)?))
+    }
+
+    async fn query(&self, filter: &str) -> Result<Vec<CalendarResource>> {
+        let collection = self.calendar_url().await?;
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><d:getetag/><c:calendar-data/></d:prop><c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VEVENT">{filter}</c:comp-filter></c:comp-filter></c:filter></c:calendar-query>"#
+        );
+        self.multistatus("REPORT", collection.clone(), "1", &body)
+            .await?
+            .into_iter()
+            .filter_map(|r| {
+                let data = r.calendar_data?;
+                Some(collection.join(&r.href).map(|href| CalendarResource {
+                    href,
+                    etag: r.etag,
+                    data,
+                }))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Calendar objects with an event (or recurring instance) in `[from, to)`.
+    pub async fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CalendarResource>> {
+        self.query(&format!(
+            r#"<c:time-range start="{}" end="{}"/>"#,
+            from.format("%Y%m%dT%H%M%SZ"),
+            to.format("%Y%m%dT%H%M%SZ")
+        ))
+        .await
+    }
+
+    /// The calendar object holding the event with `uid`, if any.
+    pub async fn find_event(&self, uid: &str) -> Result<Option<CalendarResource>> {
+        let filter = format!(
+            r#"<c:prop-filter name="UID"><c:text-match collation="i;octet">{}</c:text-match></c:prop-filter>"#,
+            quick_xml::escape::escape(uid)
+        );
+        Ok(self.query(&filter).await?.into_iter().next())
+    }
+
+    /// Store a new calendar object named after `uid`; fails if it exists.
+    pub async fn create(&self, uid: &str, data: String) -> Result<Url> {
+        let collection = self.calendar_url().await?;
+        let href = collection.join(&format!("{}.ics", urlencoding::encode(uid)))?;
+        self.put(href.clone(), data, ("If-None-Match", "*")).await?;
+        Ok(href)
+    }
+
+    /// Replace `resource` with `data`, only if it is unchanged on the server.
+    pub async fn update(&self, resource: &CalendarResource, data: String) -> Result<()> {
+        let etag = resource.etag.as_deref().unwrap_or("*");
+        self.put(resource.href.clone(), data, ("If-Match", etag))
+            .await
+    }
+
+    async fn put(&self, href: Url, data: String, condition: (&str, &str)) -> Result<()> {
+        let resp = self
+            .send(
+                "PUT",
+                href.clone(),
+                &[("Content-Type", "text/calendar; charset=utf-8"), condition],
+                Some(data),
+            )
+            .await?;
+        check_write(resp, &href).await
+    }
+
+    /// Delete `resource`, only if it is unchanged on the server.
+    pub async fn delete(&self, resource: &CalendarResource) -> Result<()> {
+        let etag = resource.etag.as_deref().unwrap_or("*");
+        let resp = self
+            .send("DELETE", resource.href.clone(), &[("If-Match", etag)], None)
+            .await?;
+        check_write(resp, &resource.href).await
+    }
+}
+
+async fn check_write(resp: reqwest::Response, href: &Url) -> Result<()> {
+    let status = resp.status();
+    if status == StatusCode::PRECONDITION_FAILED {
+        bail!("{href} was changed on the server since it was read (ETag mismatch); list events and retry");
+    }
+    if !status.is_success() {
+        let text = resp.text().await.unwrap_or_default();
+        bail!("CalDAV write to {href} returned {status}: {text}");
+    }
+    Ok(())
+}
+
+/// Collections need a trailing slash so relative joins stay inside them.
+fn as_collection(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Multistatus parsing
+// ═══════════════════════════════════════════════════════════════════
+
+/// The properties of one `<d:response>` the client reads.
+#[derive(Debug, Default, Clone)]
+struct DavResponse {
+    href: String,
+    etag: Option<String>,
+    calendar_data: Option<String>,
+    display_name: Option<String>,
+    principal: Option<String>,
+    calendar_home: Option<String>,
+    is_calendar: bool,
+    components: Vec<String>,
+}
+
+fn local_name(name: &[u8]) -> &[u8] {
+    name.rsplit(|b| *b == b':').next().unwrap_or(name)
+}
+
+fn parse_multistatus(xml: &str) -> Result<Vec<DavResponse>> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Vec<u8>> = Vec::new();
+    let mut responses = Vec::new();
+    let mut current: Option<DavResponse> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = local_name(e.name().as_ref()).to_vec();
+                if name == b"response" {
+                    current = Some(DavResponse::default());
+                }
+                on_element(&stack, &mut current, &e);
+                stack.push(name);
+            }
+            Ok(Event::Empty(e)) => on_element(&stack, &mut current, &e),
+            Ok(Event::End(_)) => {
+                if stack.pop().as_deref() == Some(b"response".as_slice()) {
+                    if let Some(response) = current.take() {
+                        responses.push(response);
+                    }
+                }
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape()?.into_owned();
+                push_text(&stack, current.as_mut(), &text);
+            }
+            Ok(Event::CData(e)) => {
+                let text = String::from_utf8_lossy(&e.into_inner()).into_owned();
+                push_text(&stack, current.as_mut(), &text);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e).context("Invalid CalDAV multistatus response"),
+            _ => {}
+        }
+    }
+    Ok(responses)
+}
+
+fn on_element(
+    stack: &[Vec<u8>],
+    current: &mut Option<DavResponse>,
+    e: &quick_xml::events::BytesStart<'_>,
+) {
+    let Some(response) = current.as_mut() else {
+        return;
+    };
+    let name = local_name(e.name().as_ref()).to_vec();
+    let parent = stack.last().map(Vec::as_slice);
+    if name == b"calendar" && parent == Some(b"resourcetype".as_slice()) {
+        response.is_calendar = true;
+    } else if name == b"comp" {
+        for attr in e.attributes().flatten() {
+            if local_name(attr.key.as_ref()) == b"name" {
+                response
+                    .components
+                    .push(String::from_utf8_lossy(&attr.value).to_ascii_uppercase());
+            }
+        }
+    }
+}
+
+fn push_text(stack: &[Vec<u8>], response: Option<&mut DavResponse>, text: &str) {
+    let Some(response) = response else {
+        return;
+    };
+    let Some((leaf, ancestors)) = stack.split_last() else {
+        return;
+    };
+    let parent = ancestors.last().map(Vec::as_slice);
+    let append = |slot: &mut Option<String>| slot.get_or_insert_with(String::new).push_str(text);
+    match (leaf.as_slice(), parent) {
+        (b"href", Some(b"response")) => response.href.push_str(text.trim()),
+        (b"href", Some(b"current-user-principal")) => append(&mut response.principal),
+        (b"href", Some(b"calendar-home-set")) => append(&mut response.calendar_home),
+        (b"getetag", _) => append(&mut response.etag),
+        (b"calendar-data", _) => append(&mut response.calendar_data),
+        (b"displayname", _) => append(&mut response.display_name),
+        _ => {}
+    }
+    for value in [
+        &mut response.principal,
+        &mut response.calendar_home,
+        &mut response.etag,
+        &mut response.display_name,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let trimmed = value.trim();
+        if trimmed.len() != value.len() {
+            *value = trimmed.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn multistatus(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(207).set_body_raw(
+            format!(
+                r#"<?xml version="1.0"?><d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/">{body}</d:multistatus>"#
+            ),
+            "application/xml",
+        )
+    }
+
+    const EVENT: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:abc@example.com\r\nDTSTART:20260401T090000Z\r\nDTEND:20260401T100000Z\r\nSUMMARY:Review &amp; plan\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+    async fn mount_discovery(server: &MockServer) {
+        Mock::given(method("PROPFIND"))
+            .and(path("/dav/"))
+            .and(header("Depth", "0"))
+            .respond_with(multistatus(
+                "<d:response><d:href>/dav/</d:href><d:propstat><d:prop><d:current-user-principal><d:href>/dav/principals/alice/</d:href></d:current-user-principal><d:resourcetype><d:collection/></d:resourcetype></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+            ))
+            .mount(server)
+            .await;
+        Mock::given(method("PROPFIND"))
+            .and(path("/dav/principals/alice/"))
+            .respond_with(multistatus(
+                "<d:response><d:href>/dav/principals/alice/</d:href><d:propstat><d:prop><c:calendar-home-set><d:href>/dav/calendars/alice/</d:href></c:calendar-home-set></d:prop></d:propstat></d:response>",
+            ))
+            .mount(server)
+            .await;
+        Mock::given(method("PROPFIND"))
+            .and(path("/dav/calendars/alice/"))
+            .and(header("Depth", "1"))
+            .respond_with(multistatus(
+                r#"<d:response><d:href>/dav/calendars/alice/</d:href><d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat></d:response>
+<d:response><d:href>/dav/calendars/alice/tasks/</d:href><d:propstat><d:prop><d:resourcetype><d:collection/><c:calendar/></d:resourcetype><d:displayname>Tasks</d:displayname><c:supported-calendar-component-set><c:comp name="VTODO"/></c:supported-calendar-component-set></d:prop></d:propstat></d:response>
+<d:response><d:href>/dav/calendars/alice/work/</d:href><d:propstat><d:prop><d:resourcetype><d:collection/><c:calendar/></d:resourcetype><d:displayname>Work</d:displayname><c:supported-calendar-component-set><c:comp name="VEVENT"/><c:comp name="VTODO"/></c:supported-calendar-component-set></d:prop></d:propstat></d:response>
+<d:response><d:href>/dav/calendars/alice/personal/</d:href><d:propstat><d:prop><d:resourcetype><d:collection/><c:calendar/></d:resourcetype><d:displayname>Personal</d:displayname></d:prop></d:propstat></d:response>"#,
+            ))
+            .mount(server)
+            .await;
+    }
+
+    fn client(server: &MockServer, calendar: Option<&str>) -> CalDavClient {
+        CalDavClient::new(
+            format!("{}/dav/", server.uri()),
+            "alice",
+            "secret",
+            calendar.map(str::to_string),
+        )
+    }
+
+    #[tokio::test]
+    async fn discovers_first_event_calendar_or_named_one() {
+        let server = MockServer::start().await;
+        mount_discovery(&server).await;
+
+        let url = client(&server, None).calendar_url().await.unwrap();
+        assert_eq!(url.path(), "/dav/calendars/alice/work/");
+
+        let url = client(&server, Some("Personal"))
+            .calendar_url()
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/dav/calendars/alice/personal/");
+
+        let err = client(&server, Some("Tasks"))
+            .calendar_url()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("named 'Tasks'"));
+
+        let url = client(&server, Some("/dav/calendars/alice/other"))
+            .calendar_url()
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/dav/calendars/alice/other/");
+    }
+
+    #[tokio::test]
+    async fn report_lists_events_and_finds_by_uid() {
+        let server = MockServer::start().await;
+        mount_discovery(&server).await;
+        Mock::given(method("REPORT"))
+            .and(path("/dav/calendars/alice/work/"))
+            .and(body_string_contains(r#"<c:time-range start="20260401T000000Z""#))
+            .respond_with(multistatus(&format!(
+                r#"<d:response><d:href>/dav/calendars/alice/work/abc.ics</d:href><d:propstat><d:prop><d:getetag>"e1"</d:getetag><c:calendar-data>{EVENT}</c:calendar-data></d:prop></d:propstat></d:response>"#
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("REPORT"))
+            .and(path("/dav/calendars/alice/work/"))
+            .and(body_string_contains("abc@example.com</c:text-match>"))
+            .respond_with(multistatus(&format!(
+                r#"<d:response><d:href>/dav/calendars/alice/work/abc.ics</d:href><d:propstat><d:prop><d:getetag>"e1"</d:getetag><c:calendar-data><![CDATA[{EVENT}]]></c:calendar-data></d:prop></d:propstat></d:response>"#
+            )))
+            .mount(&server)
+            .await;
+
+        let caldav = client(&server, None);
+        let from = "2026-04-01T00:00:00Z".parse().unwrap();
+        let to = "2026-04-08T00:00:00Z".parse().unwrap();
+        let resources = caldav.events_between(from, to).await.unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].etag.as_deref(), Some("\"e1\""));
+        assert_eq!(
+            resources[0].href.path(),
+            "/dav/calendars/alice/work/abc.ics"
+        );
+        let events = crate::tools::calendar_ical::Calendar::parse(&resources[0].data).events();
+        assert_eq!(events[0].summary, "Review & plan");
+
+        let found = caldav.find_event("abc@example.com").await.unwrap().unwrap();
+        assert!(found.data.contains("UID:abc@example.com"));
+    }
+
+    #[tokio::test]
+    async fn writes_are_guarded_by_etags() {
+        let server = MockServer::start().await;
+        mount_discovery(&server).await;
+        Mock::given(method("PUT"))
+            .and(path("/dav/calendars/alice/work/new%40zeroclaw.ics"))
+            .and(header("If-None-Match", "*"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/dav/calendars/alice/work/abc.ics"))
+            .and(header("If-Match", "\"stale\""))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/dav/calendars/alice/work/abc.ics"))
+            .and(header("If-Match", "\"e1\""))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let caldav = client(&server, None);
+        let href = caldav.create("new@zeroclaw", EVENT.into()).await.unwrap();
+        assert!(href.path().ends_with("/work/new%40zeroclaw.ics"));
+
+        let mut resource = CalendarResource {
+            href: Url::parse(&format!(
+                "{}/dav/calendars/alice/work/abc.ics",
+                server.uri()
+            ))
+            .unwrap(),
+            etag: Some("\"stale\"".into()),
+            data: EVENT.into(),
+        };
+        let err = caldav.update(&resource, EVENT.into()).await.unwrap_err();
+        assert!(err.to_string().contains("ETag mismatch"));
+
+        resource.etag = Some("\"e1\"".into());
+        caldav.delete(&resource).await.unwrap();
+    }
+}
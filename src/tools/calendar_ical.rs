@@ -0,0 +1,1358 @@
+//! iCalendar (RFC 5545) support for the CalDAV and local ICS calendar backends.
+//!
+//! Covers what the calendar tools need and nothing more:
+//! - VEVENT parsing (TZID / UTC / floating / all-day times, DURATION)
+//! - RRULE expansion into concrete occurrences, honouring EXDATE, RDATE and
+//!   RECURRENCE-ID overrides
+//! - Text-level edits (add / update / remove by UID) that keep unknown
+//!   properties, VTIMEZONE and VALARM components intact
+//! - A local `.ics` file store built on top of the above
+
+use anyhow::{bail, Context, Result};
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::path::PathBuf;
+
+/// Upper bound on recurrence periods walked per event, so a malformed or
+/// very old daily rule cannot stall a listing.
+const MAX_RECURRENCE_PERIODS: u32 = 100_000;
+
+const PRODID: &str = "-//ZeroClaw//Calendar//EN";
+
+// ═══════════════════════════════════════════════════════════════════
+// Content lines
+// ═══════════════════════════════════════════════════════════════════
+
+/// One unfolded content line: `NAME;PARAM=VALUE:value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut in_quotes = false;
+        let mut split = None;
+        for (idx, ch) in line.char_indices() {
+            match ch {
+                '"' => in_quotes = !in_quotes,
+                ':' if !in_quotes => {
+                    split = Some(idx);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let split = split?;
+        let (head, value) = (&line[..split], &line[split + 1..]);
+
+        let mut parts = split_unquoted(head, ';').into_iter();
+        let name = parts.next()?.trim().to_ascii_uppercase();
+        if name.is_empty() {
+            return None;
+        }
+        let params = parts
+            .filter_map(|param| {
+                let (key, value) = param.split_once('=')?;
+                Some((
+                    key.trim().to_ascii_uppercase(),
+                    value.trim_matches('"').to_string(),
+                ))
+            })
+            .collect();
+        Some(Self {
+            name,
+            params,
+            value: value.to_string(),
+        })
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn split_unquoted(input: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (idx, ch) in input.char_indices() {
+        if ch == '"' {
+            in_quotes = !in_quotes;
+        } else if ch == separator && !in_quotes {
+            parts.push(&input[start..idx]);
+            start = idx + ch.len_utf8();
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// Join folded lines (CRLF or LF followed by a space or tab).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !raw.is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 sequences.
+fn fold(line: &str, out: &mut String) {
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+pub fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+pub fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Times
+// ═══════════════════════════════════════════════════════════════════
+
+/// A DTSTART / DTEND / EXDATE value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTime {
+    /// All-day (`VALUE=DATE`).
+    Date(NaiveDate),
+    /// Absolute UTC time (`...Z`).
+    Utc(DateTime<Utc>),
+    /// Wall-clock time in a named zone, or floating when the zone is `None`.
+    Local(NaiveDateTime, Option<Tz>),
+}
+
+impl EventTime {
+    /// Parse a single date or date-time value, using `TZID` from `tzid`.
+    pub fn parse(value: &str, tzid: Option<&str>) -> Result<Self> {
+        let value = value.trim();
+        if value.len() == 8 {
+            let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+                .with_context(|| format!("Invalid iCalendar date: {value}"))?;
+            return Ok(Self::Date(date));
+        }
+        if let Some(utc) = value.strip_suffix('Z') {
+            let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+                .with_context(|| format!("Invalid iCalendar date-time: {value}"))?;
+            return Ok(Self::Utc(naive.and_utc()));
+        }
+        let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .with_context(|| format!("Invalid iCalendar date-time: {value}"))?;
+        Ok(Self::Local(naive, tzid.and_then(parse_tzid)))
+    }
+
+    fn from_property(prop: &Property) -> Result<Self> {
+        Self::parse(&prop.value, prop.param("TZID"))
+    }
+
+    /// Wall-clock time in the value's own zone (midnight for dates).
+    pub fn naive(&self) -> NaiveDateTime {
+        match self {
+            Self::Date(date) => date.and_time(NaiveTime::MIN),
+            Self::Utc(dt) => dt.naive_utc(),
+            Self::Local(naive, _) => *naive,
+        }
+    }
+
+    /// Same kind of value at a different wall-clock time.
+    fn with_naive(&self, naive: NaiveDateTime) -> Self {
+        match self {
+            Self::Date(_) => Self::Date(naive.date()),
+            Self::Utc(_) => Self::Utc(naive.and_utc()),
+            Self::Local(_, tz) => Self::Local(naive, *tz),
+        }
+    }
+
+    /// Absolute instant; dates and floating times are read in `default_tz`.
+    pub fn to_utc(&self, default_tz: Tz) -> DateTime<Utc> {
+        match self {
+            Self::Utc(dt) => *dt,
+            Self::Date(_) | Self::Local(_, None) => resolve_local(default_tz, self.naive()),
+            Self::Local(naive, Some(tz)) => resolve_local(*tz, *naive),
+        }
+    }
+
+    /// The same kind of value moved by `by`.
+    pub fn shifted(&self, by: Duration) -> Self {
+        self.with_naive(self.naive() + by)
+    }
+
+    pub fn is_date(&self) -> bool {
+        matches!(self, Self::Date(_))
+    }
+
+    /// Human-readable form: `YYYY-MM-DD` for dates, RFC 3339 otherwise.
+    pub fn display(&self, default_tz: Tz) -> String {
+        match self {
+            Self::Date(date) => date.format("%Y-%m-%d").to_string(),
+            Self::Utc(dt) => dt.with_timezone(&default_tz).to_rfc3339(),
+            Self::Local(_, tz) => {
+                let tz = tz.unwrap_or(default_tz);
+                self.to_utc(default_tz).with_timezone(&tz).to_rfc3339()
+            }
+        }
+    }
+
+    /// Content line for property `name` (e.g. `DTSTART;TZID=Asia/Seoul:...`).
+    pub fn to_line(&self, name: &str) -> String {
+        match self {
+            Self::Date(date) => format!("{name};VALUE=DATE:{}", date.format("%Y%m%d")),
+            Self::Utc(dt) => format!("{name}:{}", dt.format("%Y%m%dT%H%M%SZ")),
+            Self::Local(naive, Some(tz)) => {
+                format!(
+                    "{name};TZID={}:{}",
+                    tz.name(),
+                    naive.format("%Y%m%dT%H%M%S")
+                )
+            }
+            Self::Local(naive, None) => format!("{name}:{}", naive.format("%Y%m%dT%H%M%S")),
+        }
+    }
+}
+
+fn parse_tzid(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim_matches('"');
+    tzid.parse::<Tz>().ok().or_else(|| {
+        // Some clients prefix Olson names (e.g. `/mozilla.org/.../Europe/Berlin`).
+        let mut segments = tzid.rsplit('/');
+        let city = segments.next()?;
+        let region = segments.next()?;
+        format!("{region}/{city}").parse::<Tz>().ok()
+    })
+}
+
+/// Map a wall-clock time to UTC; ambiguous times take the earlier instant
+/// and times in a DST gap are pushed forward by an hour.
+fn resolve_local(tz: Tz, naive: NaiveDateTime) -> DateTime<Utc> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt.with_timezone(&Utc),
+        LocalResult::None => tz
+            .from_local_datetime(&(naive + Duration::hours(1)))
+            .earliest()
+            .map_or_else(|| naive.and_utc(), |dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// Parse an RFC 5545 DURATION such as `PT1H30M`, `P1D` or `-P1W`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for ch in rest.chars() {
+        match ch {
+            '0'..='9' => number.push(ch),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(if negative { -total } else { total })
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Events
+// ═══════════════════════════════════════════════════════════════════
+
+/// The parts of a VEVENT the calendar tools read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: EventTime,
+    pub end: Option<EventTime>,
+    pub rrule: Option<String>,
+    pub exdates: Vec<EventTime>,
+    pub rdates: Vec<EventTime>,
+    /// Set on overrides of a single instance of a recurring event.
+    pub recurrence_id: Option<EventTime>,
+}
+
+impl Event {
+    fn from_properties(props: &[Property]) -> Result<Self> {
+        let find = |name: &str| props.iter().find(|prop| prop.name == name);
+        let text = |name: &str| {
+            find(name)
+                .map(|prop| unescape_text(&prop.value))
+                .filter(|value| !value.is_empty())
+        };
+        let multi = |name: &str| -> Vec<EventTime> {
+            props
+                .iter()
+                .filter(|prop| prop.name == name)
+                .flat_map(|prop| {
+                    prop.value
+                        .split(',')
+                        .filter_map(|value| EventTime::parse(value, prop.param("TZID")).ok())
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        };
+
+        let start = EventTime::from_property(find("DTSTART").context("VEVENT without DTSTART")?)?;
+        let end = match find("DTEND") {
+            Some(prop) => Some(EventTime::from_property(prop)?),
+            None => find("DURATION")
+                .and_then(|prop| parse_duration(&prop.value))
+                .map(|duration| start.with_naive(start.naive() + duration)),
+        };
+
+        Ok(Self {
+            uid: find("UID")
+                .map(|prop| prop.value.clone())
+                .unwrap_or_default(),
+            summary: text("SUMMARY").unwrap_or_default(),
+            description: text("DESCRIPTION"),
+            location: text("LOCATION"),
+            start,
+            end,
+            rrule: find("RRULE").map(|prop| prop.value.clone()),
+            exdates: multi("EXDATE"),
+            rdates: multi("RDATE"),
+            recurrence_id: find("RECURRENCE-ID")
+                .map(EventTime::from_property)
+                .transpose()?,
+        })
+    }
+
+    /// DTEND − DTSTART (one day for all-day events without an end).
+    pub fn duration(&self) -> Duration {
+        match (self.start, self.end) {
+            (EventTime::Date(_), None) => Duration::days(1),
+            (_, None) => Duration::zero(),
+            (EventTime::Date(_), Some(end)) | (_, Some(end @ EventTime::Date(_))) => {
+                end.naive() - self.start.naive()
+            }
+            // DTEND may use another zone than DTSTART.
+            (start, Some(end)) => end.to_utc(Tz::UTC) - start.to_utc(Tz::UTC),
+        }
+    }
+}
+
+/// One concrete instance of an event inside a listing window.
+#[derive(Debug, Clone)]
+pub struct Occurrence<'a> {
+    pub event: &'a Event,
+    pub start: EventTime,
+    pub end: EventTime,
+}
+
+impl Occurrence<'_> {
+    pub fn is_recurring(&self) -> bool {
+        self.event.rrule.is_some() || self.event.recurrence_id.is_some()
+    }
+}
+
+/// Every occurrence of `events` overlapping `[from, to)`, sorted by start.
+pub fn expand(
+    events: &[Event],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    default_tz: Tz,
+) -> Vec<Occurrence<'_>> {
+    let overridden: Vec<(&str, DateTime<Utc>)> = events
+        .iter()
+        .filter_map(|event| {
+            let id = event.recurrence_id?;
+            Some((event.uid.as_str(), id.to_utc(default_tz)))
+        })
+        .collect();
+
+    let mut occurrences = Vec::new();
+    for event in events {
+        let duration = event.duration();
+        let starts = if event.recurrence_id.is_some()
+            || (event.rrule.is_none() && event.rdates.is_empty())
+        {
+            vec![event.start]
+        } else {
+            recurrence_starts(event, to, default_tz)
+        };
+
+        for start in starts {
+            let start_utc = start.to_utc(default_tz);
+            if event.recurrence_id.is_none()
+                && overridden
+                    .iter()
+                    .any(|(uid, at)| *uid == event.uid && *at == start_utc)
+            {
+                continue;
+            }
+            let end = start.with_naive(start.naive() + duration);
+            let end_utc = end.to_utc(default_tz);
+            let overlaps = if end_utc > start_utc {
+                start_utc < to && end_utc > from
+            } else {
+                start_utc < to && start_utc >= from
+            };
+            if overlaps {
+                occurrences.push(Occurrence { event, start, end });
+            }
+        }
+    }
+    occurrences.sort_by_key(|occurrence| occurrence.start.to_utc(default_tz));
+    occurrences
+}
+
+/// Start times of a recurring event up to `until`, minus EXDATEs.
+fn recurrence_starts(event: &Event, until: DateTime<Utc>, default_tz: Tz) -> Vec<EventTime> {
+    let mut starts = match event.rrule.as_deref().map(RecurrenceRule::parse) {
+        Some(Ok(rule)) => rule.expand(event.start, until, default_tz),
+        Some(Err(e)) => {
+            tracing::debug!("Unsupported RRULE on {}: {e:#}", event.uid);
+            vec![event.start]
+        }
+        None => vec![event.start],
+    };
+    starts.extend(event.rdates.iter().copied());
+
+    let excluded: Vec<DateTime<Utc>> = event
+        .exdates
+        .iter()
+        .map(|exdate| exdate.to_utc(default_tz))
+        .collect();
+    starts.retain(|start| !excluded.contains(&start.to_utc(default_tz)));
+    starts.sort_by_key(|start| start.to_utc(default_tz));
+    starts.dedup();
+    starts
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// RRULE
+// ═══════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The RRULE subset the tools expand: DAILY/WEEKLY/MONTHLY/YEARLY with
+/// INTERVAL, COUNT, UNTIL, BYDAY (with ordinals), BYMONTHDAY, BYMONTH, WKST.
+#[derive(Debug, Clone)]
+struct RecurrenceRule {
+    freq: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<EventTime>,
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    week_start: Weekday,
+}
+
+impl RecurrenceRule {
+    fn parse(value: &str) -> Result<Self> {
+        let mut freq = None;
+        let mut rule = Self {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+        for part in value.trim().trim_start_matches("RRULE:").split(';') {
+            let Some((key, val)) = part.split_once('=') else {
+                continue;
+            };
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match val.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => bail!("Unsupported RRULE frequency: {other}"),
+                    });
+                }
+                "INTERVAL" => rule.interval = val.parse::<u32>()?.max(1),
+                "COUNT" => rule.count = Some(val.parse()?),
+                "UNTIL" => rule.until = Some(EventTime::parse(val, None)?),
+                "BYDAY" => {
+                    rule.by_day = val
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<Vec<_>>>()?;
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = val
+                        .split(',')
+                        .map(|day| day.parse::<i32>().map_err(Into::into))
+                        .collect::<Result<Vec<_>>>()?;
+                }
+                "BYMONTH" => {
+                    rule.by_month = val
+                        .split(',')
+                        .map(|month| month.parse::<u32>().map_err(Into::into))
+                        .collect::<Result<Vec<_>>>()?;
+                }
+                "WKST" => rule.week_start = parse_weekday(val)?,
+                "BYSETPOS" | "BYYEARDAY" | "BYWEEKNO" | "BYHOUR" | "BYMINUTE" | "BYSECOND" => {
+                    bail!("Unsupported RRULE part: {key}")
+                }
+                _ => {}
+            }
+        }
+        rule.freq = freq.context("RRULE without FREQ")?;
+        Ok(rule)
+    }
+
+    /// Occurrence starts from `dtstart` (always the first instance) until
+    /// the rule ends or an instance begins after `window_end`.
+    fn expand(
+        &self,
+        dtstart: EventTime,
+        window_end: DateTime<Utc>,
+        default_tz: Tz,
+    ) -> Vec<EventTime> {
+        let start = dtstart.naive();
+        let mut starts = vec![dtstart];
+        let mut emitted = 1u32;
+        if self.count.is_some_and(|count| emitted >= count) {
+            return starts;
+        }
+
+        for period in 0..MAX_RECURRENCE_PERIODS {
+            let mut dates = self.period_dates(start.date(), period.saturating_mul(self.interval));
+            dates.sort_unstable();
+            dates.dedup();
+            for date in dates {
+                let candidate = dtstart.with_naive(date.and_time(start.time()));
+                if candidate.naive() <= start {
+                    continue;
+                }
+                if self.is_past_until(&candidate, default_tz)
+                    || candidate.to_utc(default_tz) >= window_end
+                {
+                    return starts;
+                }
+                starts.push(candidate);
+                emitted += 1;
+                if self.count.is_some_and(|count| emitted >= count) {
+                    return starts;
+                }
+            }
+        }
+        starts
+    }
+
+    fn is_past_until(&self, candidate: &EventTime, default_tz: Tz) -> bool {
+        match self.until {
+            None => false,
+            Some(EventTime::Date(date)) => candidate.naive().date() > date,
+            Some(until) => candidate.to_utc(default_tz) > until.to_utc(default_tz),
+        }
+    }
+
+    /// Candidate dates of the period `offset` intervals after the start.
+    fn period_dates(&self, start: NaiveDate, offset: u32) -> Vec<NaiveDate> {
+        let offset = i64::from(offset);
+        match self.freq {
+            Frequency::Daily => {
+                let Some(date) = start.checked_add_signed(Duration::days(offset)) else {
+                    return Vec::new();
+                };
+                let weekday_ok = self.by_day.is_empty()
+                    || self.by_day.iter().any(|(_, wd)| *wd == date.weekday());
+                let month_day_ok = self.by_month_day.is_empty()
+                    || month_days(date.year(), date.month(), &self.by_month_day, &[], 0)
+                        .contains(&date);
+                if weekday_ok && month_day_ok && self.month_allowed(date.month()) {
+                    vec![date]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let since_week_start = (7 + start.weekday().num_days_from_monday()
+                    - self.week_start.num_days_from_monday())
+                    % 7;
+                let Some(week) = start.checked_add_signed(
+                    Duration::weeks(offset) - Duration::days(i64::from(since_week_start)),
+                ) else {
+                    return Vec::new();
+                };
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, wd)| *wd).collect()
+                };
+                weekdays
+                    .into_iter()
+                    .filter_map(|wd| {
+                        let days = (7 + wd.num_days_from_monday()
+                            - self.week_start.num_days_from_monday())
+                            % 7;
+                        week.checked_add_signed(Duration::days(i64::from(days)))
+                    })
+                    .filter(|date| self.month_allowed(date.month()))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let Some((year, month)) = add_months(start.year(), start.month(), offset) else {
+                    return Vec::new();
+                };
+                if !self.month_allowed(month) {
+                    return Vec::new();
+                }
+                month_days(year, month, &self.by_month_day, &self.by_day, start.day())
+            }
+            Frequency::Yearly => {
+                let Ok(year) = i32::try_from(i64::from(start.year()) + offset) else {
+                    return Vec::new();
+                };
+                if self.by_month.is_empty()
+                    && self.by_month_day.is_empty()
+                    && !self.by_day.is_empty()
+                {
+                    return year_weekdays(year, &self.by_day);
+                }
+                let months = if self.by_month.is_empty() {
+                    vec![start.month()]
+                } else {
+                    self.by_month.clone()
+                };
+                months
+                    .into_iter()
+                    .flat_map(|month| {
+                        month_days(year, month, &self.by_month_day, &self.by_day, start.day())
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn month_allowed(&self, month: u32) -> bool {
+        self.by_month.is_empty() || self.by_month.contains(&month)
+    }
+}
+
+/// Check that `rule` is an RRULE value the expansion understands.
+pub fn validate_rrule(rule: &str) -> Result<()> {
+    RecurrenceRule::parse(rule).map(|_| ())
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday> {
+    Ok(match value.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        other => bail!("Invalid RRULE weekday: {other}"),
+    })
+}
+
+/// `MO`, `2TU`, `-1FR` → (ordinal, weekday).
+fn parse_by_day(value: &str) -> Result<(Option<i32>, Weekday)> {
+    let value = value.trim();
+    let split = value.len().saturating_sub(2);
+    let (ordinal, day) = value.split_at(split);
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        Some(ordinal.trim_start_matches('+').parse::<i32>()?)
+    };
+    Ok((ordinal, parse_weekday(day)?))
+}
+
+fn add_months(year: i32, month: u32, offset: i64) -> Option<(i32, u32)> {
+    let index = i64::from(year) * 12 + i64::from(month) - 1 + offset;
+    let year = i32::try_from(index.div_euclid(12)).ok()?;
+    Some((year, u32::try_from(index.rem_euclid(12)).ok()? + 1))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|date| date.pred_opt())
+        .map_or(28, |date| date.day())
+}
+
+/// Dates in one month matching BYMONTHDAY and/or BYDAY, or `default_day`
+/// when neither is set.
+fn month_days(
+    year: i32,
+    month: u32,
+    by_month_day: &[i32],
+    by_day: &[(Option<i32>, Weekday)],
+    default_day: u32,
+) -> Vec<NaiveDate> {
+    let last = days_in_month(year, month);
+    let all: Vec<NaiveDate> = (1..=last)
+        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .collect();
+
+    let by_month_day: Vec<NaiveDate> = by_month_day
+        .iter()
+        .filter_map(|&day| {
+            let day = if day < 0 {
+                i64::from(last) + 1 + i64::from(day)
+            } else {
+                i64::from(day)
+            };
+            u32::try_from(day)
+                .ok()
+                .and_then(|day| NaiveDate::from_ymd_opt(year, month, day))
+        })
+        .collect();
+    let by_day = select_weekdays(&all, by_day);
+
+    match (by_month_day.is_empty(), by_day.is_empty()) {
+        (false, false) => by_month_day
+            .into_iter()
+            .filter(|date| by_day.contains(date))
+            .collect(),
+        (false, true) => by_month_day,
+        (true, false) => by_day,
+        (true, true) => NaiveDate::from_ymd_opt(year, month, default_day)
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Year-wide BYDAY (e.g. `FREQ=YEARLY;BYDAY=20MO`).
+fn year_weekdays(year: i32, by_day: &[(Option<i32>, Weekday)]) -> Vec<NaiveDate> {
+    let all: Vec<NaiveDate> = (1..=12)
+        .flat_map(|month| {
+            (1..=days_in_month(year, month))
+                .filter_map(move |day| NaiveDate::from_ymd_opt(year, month, day))
+        })
+        .collect();
+    select_weekdays(&all, by_day)
+}
+
+fn select_weekdays(days: &[NaiveDate], by_day: &[(Option<i32>, Weekday)]) -> Vec<NaiveDate> {
+    let mut selected = Vec::new();
+    for (ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = days
+            .iter()
+            .copied()
+            .filter(|date| date.weekday() == *weekday)
+            .collect();
+        match ordinal {
+            None => selected.extend(matching),
+            Some(n) => {
+                let index = if *n > 0 {
+                    usize::try_from(*n - 1).ok()
+                } else {
+                    usize::try_from(-*n)
+                        .ok()
+                        .and_then(|back| matching.len().checked_sub(back))
+                };
+                if let Some(date) = index.and_then(|i| matching.get(i)) {
+                    selected.push(*date);
+                }
+            }
+        }
+    }
+    selected
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Calendar objects
+// ═══════════════════════════════════════════════════════════════════
+
+/// Fields for a new VEVENT.
+#[derive(Debug, Clone)]
+pub struct NewEvent {
+    pub summary: String,
+    pub start: EventTime,
+    pub end: EventTime,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub rrule: Option<String>,
+    pub reminder_minutes: Option<u64>,
+}
+
+/// Field changes for an existing VEVENT; `None` leaves a field untouched.
+#[derive(Debug, Clone, Default)]
+pub struct EventChanges {
+    pub summary: Option<String>,
+    pub start: Option<EventTime>,
+    pub end: Option<EventTime>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+impl EventChanges {
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_none()
+            && self.start.is_none()
+            && self.end.is_none()
+            && self.location.is_none()
+            && self.description.is_none()
+    }
+}
+
+/// A VCALENDAR object held as unfolded content lines, so edits leave
+/// everything the tools do not understand untouched.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    lines: Vec<String>,
+}
+
+impl Calendar {
+    pub fn parse(text: &str) -> Self {
+        Self {
+            lines: unfold(text),
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            lines: vec![
+                "BEGIN:VCALENDAR".into(),
+                "VERSION:2.0".into(),
+                format!("PRODID:{PRODID}"),
+                "CALSCALE:GREGORIAN".into(),
+                "END:VCALENDAR".into(),
+            ],
+        }
+    }
+
+    /// Line ranges (`BEGIN:VEVENT` ..= `END:VEVENT`) of top-level VEVENTs.
+    fn event_ranges(&self) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::new();
+        let mut begin = None;
+        let mut depth = 0usize;
+        for (idx, line) in self.lines.iter().enumerate() {
+            let upper = line.to_ascii_uppercase();
+            if upper.starts_with("BEGIN:") {
+                depth += 1;
+                if depth == 2 && upper == "BEGIN:VEVENT" {
+                    begin = Some(idx);
+                }
+            } else if upper.starts_with("END:") {
+                if depth == 2 && upper == "END:VEVENT" {
+                    if let Some(start) = begin.take() {
+                        ranges.push((start, idx));
+                    }
+                }
+                depth = depth.saturating_sub(1);
+            }
+        }
+        ranges
+    }
+
+    /// Properties of the VEVENT itself, skipping nested VALARMs.
+    fn event_properties(&self, (begin, end): (usize, usize)) -> Vec<(usize, Property)> {
+        let mut props = Vec::new();
+        let mut depth = 0usize;
+        for idx in begin + 1..end {
+            let line = &self.lines[idx];
+            let upper = line.to_ascii_uppercase();
+            if upper.starts_with("BEGIN:") {
+                depth += 1;
+            } else if upper.starts_with("END:") {
+                depth = depth.saturating_sub(1);
+            } else if depth == 0 {
+                if let Some(prop) = Property::parse(line) {
+                    props.push((idx, prop));
+                }
+            }
+        }
+        props
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.event_ranges()
+            .into_iter()
+            .filter_map(|range| {
+                let props: Vec<Property> = self
+                    .event_properties(range)
+                    .into_iter()
+                    .map(|(_, prop)| prop)
+                    .collect();
+                match Event::from_properties(&props) {
+                    Ok(event) => Some(event),
+                    Err(e) => {
+                        tracing::debug!("Skipping unparseable VEVENT: {e:#}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    pub fn contains(&self, uid: &str) -> bool {
+        self.events().iter().any(|event| event.uid == uid)
+    }
+
+    /// Append a new VEVENT with the given UID.
+    pub fn add_event(&mut self, uid: &str, event: &NewEvent) {
+        let now = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut lines = vec![
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{uid}"),
+            format!("DTSTAMP:{now}"),
+            format!("CREATED:{now}"),
+            event.start.to_line("DTSTART"),
+            event.end.to_line("DTEND"),
+            format!("SUMMARY:{}", escape_text(&event.summary)),
+        ];
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(rrule) = &event.rrule {
+            lines.push(format!(
+                "RRULE:{}",
+                rrule.trim().trim_start_matches("RRULE:")
+            ));
+        }
+        if let Some(minutes) = event.reminder_minutes.filter(|minutes| *minutes > 0) {
+            lines.extend([
+                "BEGIN:VALARM".to_string(),
+                "ACTION:DISPLAY".to_string(),
+                format!("DESCRIPTION:{}", escape_text(&event.summary)),
+                format!("TRIGGER:-PT{minutes}M"),
+                "END:VALARM".to_string(),
+            ]);
+        }
+        lines.push("END:VEVENT".to_string());
+
+        let insert_at = self
+            .lines
+            .iter()
+            .rposition(|line| line.eq_ignore_ascii_case("END:VCALENDAR"))
+            .unwrap_or(self.lines.len());
+        self.lines.splice(insert_at..insert_at, lines);
+    }
+
+    /// Apply `changes` to the master VEVENT with `uid` (the whole series for
+    /// recurring events). Returns `false` when no such event exists.
+    pub fn update_event(&mut self, uid: &str, changes: &EventChanges) -> bool {
+        let Some(range) = self.event_ranges().into_iter().find(|&range| {
+            let props = self.event_properties(range);
+            props
+                .iter()
+                .any(|(_, prop)| prop.name == "UID" && prop.value == uid)
+                && !props.iter().any(|(_, prop)| prop.name == "RECURRENCE-ID")
+        }) else {
+            return false;
+        };
+
+        let props = self.event_properties(range);
+        let sequence = props
+            .iter()
+            .find(|(_, prop)| prop.name == "SEQUENCE")
+            .and_then(|(_, prop)| prop.value.trim().parse::<u32>().ok())
+            .unwrap_or(0);
+        let now = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let mut replacements: Vec<(&str, Option<String>)> = vec![
+            ("DTSTAMP", Some(format!("DTSTAMP:{now}"))),
+            ("LAST-MODIFIED", Some(format!("LAST-MODIFIED:{now}"))),
+            ("SEQUENCE", Some(format!("SEQUENCE:{}", sequence + 1))),
+        ];
+        if let Some(summary) = &changes.summary {
+            replacements.push(("SUMMARY", Some(format!("SUMMARY:{}", escape_text(summary)))));
+        }
+        if let Some(location) = &changes.location {
+            replacements.push((
+                "LOCATION",
+                Some(format!("LOCATION:{}", escape_text(location))),
+            ));
+        }
+        if let Some(description) = &changes.description {
+            replacements.push((
+                "DESCRIPTION",
+                Some(format!("DESCRIPTION:{}", escape_text(description))),
+            ));
+        }
+        if let Some(start) = &changes.start {
+            replacements.push(("DTSTART", Some(start.to_line("DTSTART"))));
+        }
+        if let Some(end) = &changes.end {
+            replacements.push(("DTEND", Some(end.to_line("DTEND"))));
+            replacements.push(("DURATION", None));
+        }
+
+        let (begin, mut end) = range;
+        for (name, line) in replacements {
+            let existing: Vec<usize> = self
+                .event_properties((begin, end))
+                .into_iter()
+                .filter(|(_, prop)| prop.name == name)
+                .map(|(idx, _)| idx)
+                .collect();
+            for idx in existing.iter().rev() {
+                self.lines.remove(*idx);
+                end -= 1;
+            }
+            if let Some(line) = line {
+                let at = existing.first().copied().unwrap_or(begin + 1);
+                self.lines.insert(at, line);
+                end += 1;
+            }
+        }
+        true
+    }
+
+    /// Remove every VEVENT with `uid`, including instance overrides.
+    pub fn remove_event(&mut self, uid: &str) -> bool {
+        let ranges: Vec<(usize, usize)> = self
+            .event_ranges()
+            .into_iter()
+            .filter(|&range| {
+                self.event_properties(range)
+                    .iter()
+                    .any(|(_, prop)| prop.name == "UID" && prop.value == uid)
+            })
+            .collect();
+        for &(begin, end) in ranges.iter().rev() {
+            self.lines.drain(begin..=end);
+        }
+        !ranges.is_empty()
+    }
+
+    /// Serialize with CRLF line endings and 75-octet folding.
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+        for line in &self.lines {
+            fold(line, &mut out);
+        }
+        out
+    }
+}
+
+/// New globally unique event UID.
+pub fn new_uid() -> String {
+    format!("{}@zeroclaw", uuid::Uuid::new_v4())
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Local ICS file backend
+// ═══════════════════════════════════════════════════════════════════
+
+/// A plain `.ics` file used as a calendar. Writes are atomic (temp file +
+/// rename); a missing file reads as an empty calendar.
+#[derive(Debug, Clone)]
+pub struct IcsFile {
+    path: PathBuf,
+}
+
+impl IcsFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    pub async fn load(&self) -> Result<Calendar> {
+        match tokio::fs::read_to_string(&self.path).await {
+            Ok(text) => Ok(Calendar::parse(&text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Calendar::empty()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+
+    pub async fn save(&self, calendar: &Calendar) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp = self.path.with_extension("ics.tmp");
+        tokio::fs::write(&tmp, calendar.serialize()).await?;
+        tokio::fs::rename(&tmp, &self.path)
+            .await
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn starts(events: &[Event], from: &str, to: &str) -> Vec<String> {
+        expand(events, utc(from), utc(to), Tz::UTC)
+            .iter()
+            .map(|occurrence| occurrence.start.to_utc(Tz::UTC).to_rfc3339())
+            .collect()
+    }
+
+    const WEEKLY: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+DTSTART;TZID=Europe/Berlin:20260316T090000\r\n\
+DTEND;TZID=Europe/Berlin:20260316T091500\r\n\
+SUMMARY:Standup\\, daily\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=6\r\n\
+EXDATE;TZID=Europe/Berlin:20260318T090000\r\n\
+BEGIN:VALARM\r\n\
+TRIGGER:-PT5M\r\n\
+DESCRIPTION:nested\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+RECURRENCE-ID;TZID=Europe/Berlin:20260323T090000\r\n\
+DTSTART;TZID=Europe/Berlin:20260323T100000\r\n\
+DURATION:PT15M\r\n\
+SUMMARY:Standup (moved)\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn parses_folded_lines_and_escapes() {
+        let calendar = Calendar::parse(
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:1\nDTSTART;VALUE=DATE:20260401\nSUMMARY:Long\n  title\nLOCATION:Room\\; A\nEND:VEVENT\nEND:VCALENDAR\n",
+        );
+        let events = calendar.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Long title");
+        assert_eq!(events[0].location.as_deref(), Some("Room; A"));
+        assert!(events[0].start.is_date());
+    }
+
+    #[test]
+    fn weekly_rule_honours_count_exdate_overrides_and_dst() {
+        let events = Calendar::parse(WEEKLY).events();
+        assert_eq!(events[0].summary, "Standup, daily");
+        assert_eq!(
+            starts(&events, "2026-03-01T00:00:00Z", "2026-05-01T00:00:00Z"),
+            [
+                "2026-03-16T08:00:00+00:00",
+                // 18th excluded, 23rd moved by its override
+                "2026-03-23T09:00:00+00:00",
+                "2026-03-25T08:00:00+00:00",
+                // Berlin switches to CEST on 2026-03-29
+                "2026-03-30T07:00:00+00:00",
+                "2026-04-01T07:00:00+00:00",
+            ]
+        );
+        assert_eq!(
+            starts(&events, "2026-03-24T00:00:00Z", "2026-03-31T00:00:00Z").len(),
+            2
+        );
+    }
+
+    #[test]
+    fn monthly_and_yearly_rules() {
+        let rule = |rrule: &str, dtstart: &str| {
+            let text = format!(
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:r\nDTSTART:{dtstart}\nRRULE:{rrule}\nEND:VEVENT\nEND:VCALENDAR\n"
+            );
+            Calendar::parse(&text).events()
+        };
+
+        let last_friday = rule("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", "20260130T120000Z");
+        assert_eq!(
+            starts(&last_friday, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"),
+            [
+                "2026-01-30T12:00:00+00:00",
+                "2026-02-27T12:00:00+00:00",
+                "2026-03-27T12:00:00+00:00",
+            ]
+        );
+
+        let month_end = rule("FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=-1", "20260131T080000Z");
+        assert_eq!(
+            starts(&month_end, "2026-01-01T00:00:00Z", "2026-05-01T00:00:00Z"),
+            ["2026-01-31T08:00:00+00:00", "2026-03-31T08:00:00+00:00"]
+        );
+
+        let leap_day = rule("FREQ=YEARLY;UNTIL=20290101", "20240229T000000Z");
+        // Years without Feb 29 are skipped rather than shifted.
+        assert_eq!(
+            starts(&leap_day, "2024-01-01T00:00:00Z", "2032-01-01T00:00:00Z"),
+            ["2024-02-29T00:00:00+00:00", "2028-02-29T00:00:00+00:00"]
+        );
+
+        let daily = rule("FREQ=DAILY;UNTIL=20260105T090000Z", "20260101T090000Z");
+        assert_eq!(
+            starts(&daily, "2026-01-03T00:00:00Z", "2026-02-01T00:00:00Z"),
+            [
+                "2026-01-03T09:00:00+00:00",
+                "2026-01-04T09:00:00+00:00",
+                "2026-01-05T09:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_rule_falls_back_to_first_instance() {
+        let text = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:h\nDTSTART:20260101T000000Z\nRRULE:FREQ=HOURLY\nEND:VEVENT\nEND:VCALENDAR\n";
+        let events = Calendar::parse(text).events();
+        assert_eq!(
+            starts(&events, "2025-12-31T00:00:00Z", "2026-01-02T00:00:00Z").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn update_and_remove_keep_other_content() {
+        let mut calendar = Calendar::parse(WEEKLY);
+        let changes = EventChanges {
+            summary: Some("Sync".into()),
+            location: Some("Room 1".into()),
+            ..EventChanges::default()
+        };
+        assert!(calendar.update_event("standup@example.com", &changes));
+        assert!(!calendar.update_event("missing", &changes));
+
+        let events = calendar.events();
+        assert_eq!(events[0].summary, "Sync");
+        assert_eq!(events[0].location.as_deref(), Some("Room 1"));
+        assert_eq!(events[1].summary, "Standup (moved)");
+        let text = calendar.serialize();
+        assert!(text.contains("SEQUENCE:1\r\n"));
+        assert!(text.contains("TRIGGER:-PT5M\r\n"));
+
+        assert!(calendar.remove_event("standup@example.com"));
+        assert!(calendar.events().is_empty());
+        assert!(calendar.serialize().ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn added_events_round_trip_with_folding() {
+        let mut calendar = Calendar::empty();
+        let summary = "회의 ".repeat(30);
+        calendar.add_event(
+            "new@zeroclaw",
+            &NewEvent {
+                summary: summary.clone(),
+                start: EventTime::Local(
+                    NaiveDate::from_ymd_opt(2026, 4, 1)
+                        .unwrap()
+                        .and_hms_opt(9, 0, 0)
+                        .unwrap(),
+                    Some(chrono_tz::Asia::Seoul),
+                ),
+                end: EventTime::Utc(utc("2026-04-01T01:30:00Z")),
+                location: None,
+                description: Some("line one\nline two".into()),
+                rrule: Some("FREQ=DAILY;COUNT=2".into()),
+                reminder_minutes: Some(10),
+            },
+        );
+        let text = calendar.serialize();
+        assert!(text.lines().all(|line| line.len() <= 76));
+
+        let events = Calendar::parse(&text).events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, summary);
+        assert_eq!(events[0].description.as_deref(), Some("line one\nline two"));
+        assert_eq!(
+            starts(&events, "2026-03-31T00:00:00Z", "2026-04-30T00:00:00Z"),
+            ["2026-04-01T00:00:00+00:00", "2026-04-02T00:00:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1W"), Some(Duration::weeks(1)));
+        assert_eq!(parse_duration("-P1DT2H"), Some(-Duration::hours(26)));
+        assert_eq!(parse_duration("1H"), None);
+    }
+
+    #[tokio::test]
+    async fn ics_file_missing_reads_as_empty_and_saves() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = IcsFile::new(tmp.path().join("cal").join("me.ics"));
+        let mut calendar = file.load().await.unwrap();
+        assert!(calendar.events().is_empty());
+        calendar.add_event(
+            "a@zeroclaw",
+            &NewEvent {
+                summary: "Dentist".into(),
+                start: EventTime::Date(NaiveDate::from_ymd_opt(2026, 5, 4).unwrap()),
+                end: EventTime::Date(NaiveDate::from_ymd_opt(2026, 5, 5).unwrap()),
+                location: None,
+                description: None,
+                rrule: None,
+                reminder_minutes: None,
+            },
+        );
+        file.save(&calendar).await.unwrap();
+        assert!(file.load().await.unwrap().contains("a@zeroclaw"));
+    }
+}
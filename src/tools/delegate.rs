@@ -1,7 +1,8 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolContext, ToolResult};
 use crate::agent::loop_::run_tool_call_loop;
 use crate::config::DelegateAgentConfig;
-use crate::coordination::{CoordinationEnvelope, CoordinationPayload, InMemoryMessageBus};
+use crate::coordination::{CoordinationEnvelope, CoordinationPayload, MessageBus};
+use crate::observability::trace_context;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
@@ -40,9 +41,11 @@ pub struct DelegateTool {
     /// Inherited multimodal handling config for sub-agent loops.
     multimodal_config: crate::config::MultimodalConfig,
     /// Optional typed coordination bus used to trace delegate lifecycle events.
-    coordination_bus: Option<InMemoryMessageBus>,
+    coordination_bus: Option<MessageBus>,
     /// Logical lead agent identity used in coordination trace events.
     coordination_lead_agent: String,
+    /// Observer for sub-agent LLM calls, so their spend reaches the cost ledger.
+    observer: Arc<dyn Observer>,
 }
 
 impl DelegateTool {
@@ -76,6 +79,7 @@ impl DelegateTool {
             multimodal_config: crate::config::MultimodalConfig::default(),
             coordination_bus,
             coordination_lead_agent: DEFAULT_COORDINATION_LEAD_AGENT.to_string(),
+            observer: Arc::new(NoopObserver),
         }
     }
 
@@ -115,6 +119,7 @@ impl DelegateTool {
             multimodal_config: crate::config::MultimodalConfig::default(),
             coordination_bus,
             coordination_lead_agent: DEFAULT_COORDINATION_LEAD_AGENT.to_string(),
+            observer: Arc::new(NoopObserver),
         }
     }
 
@@ -131,11 +136,7 @@ impl DelegateTool {
     }
 
     /// Override the coordination bus used for delegate event tracing.
-    pub fn with_coordination_bus(
-        mut self,
-        bus: InMemoryMessageBus,
-        lead_agent: impl Into<String>,
-    ) -> Self {
+    pub fn with_coordination_bus(mut self, bus: MessageBus, lead_agent: impl Into<String>) -> Self {
         let lead_agent = {
             let lead = lead_agent.into();
             if lead.trim().is_empty() {
@@ -156,6 +157,13 @@ impl DelegateTool {
         self
     }
 
+    /// Report sub-agent LLM calls to `observer` (normally the parent's
+    /// cost-tracking observer) instead of discarding them.
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = observer;
+        self
+    }
+
     /// Disable coordination tracing for this tool instance.
     pub fn with_coordination_disabled(mut self) -> Self {
         self.coordination_bus = None;
@@ -163,7 +171,7 @@ impl DelegateTool {
     }
 
     #[cfg(test)]
-    fn coordination_bus_snapshot(&self) -> Option<InMemoryMessageBus> {
+    fn coordination_bus_snapshot(&self) -> Option<MessageBus> {
         self.coordination_bus.clone()
     }
 }
@@ -221,6 +229,15 @@ impl Tool for DelegateTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.execute_with_context(args, &ToolContext::default())
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
         let agent_name = args
             .get("agent")
             .and_then(|v| v.as_str())
@@ -346,16 +363,26 @@ impl Tool for DelegateTool {
 
         let temperature = agent_config.temperature.unwrap_or(0.7);
 
+        let mut agent_span = trace_context::Span::start(
+            format!("invoke_agent {agent_name}"),
+            trace_context::SpanKind::Internal,
+        );
+        agent_span.set_attribute("gen_ai.operation.name", "invoke_agent");
+        agent_span.set_attribute("gen_ai.agent.name", agent_name);
+        agent_span.set_attribute("gen_ai.provider.name", agent_config.provider.clone());
+        agent_span.set_attribute("gen_ai.request.model", agent_config.model.clone());
+
         // Agentic mode: run full tool-call loop with allowlisted tools.
         if agent_config.agentic {
-            let result = self
-                .execute_agentic(
+            let result = agent_span
+                .scope(Box::pin(self.execute_agentic(
                     agent_name,
                     agent_config,
                     &*provider,
                     &full_prompt,
                     temperature,
-                )
+                    ctx,
+                )))
                 .await?;
 
             let summary = if result.success {
@@ -366,6 +393,9 @@ impl Tool for DelegateTool {
                     .as_deref()
                     .unwrap_or("delegate agentic execution failed")
             };
+            if !result.success {
+                agent_span.set_error(summary);
+            }
             self.finish_coordination_trace(
                 agent_name,
                 &coordination_trace,
@@ -376,23 +406,33 @@ impl Tool for DelegateTool {
             return Ok(result);
         }
 
+        ctx.progress
+            .report(format!("waiting for agent '{agent_name}'"));
+
         // Wrap the provider call in a timeout to prevent indefinite blocking
-        let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_TIMEOUT_SECS),
-            provider.chat_with_system(
-                agent_config.system_prompt.as_deref(),
-                &full_prompt,
-                &agent_config.model,
-                temperature,
-            ),
-        )
-        .await;
+        let result = tokio::select! {
+            () = ctx.cancelled() => {
+                let message = format!("Agent '{agent_name}' was cancelled before replying");
+                self.finish_coordination_trace(agent_name, &coordination_trace, false, &message);
+                return Ok(ToolResult::cancelled(String::new()));
+            }
+            result = tokio::time::timeout(
+                Duration::from_secs(DELEGATE_TIMEOUT_SECS),
+                agent_span.scope(self.chat_once(
+                    provider.as_ref(),
+                    agent_config,
+                    &full_prompt,
+                    temperature,
+                )),
+            ) => result,
+        };
 
         let result = match result {
             Ok(inner) => inner,
             Err(_elapsed) => {
                 let timeout_message =
                     format!("Agent '{agent_name}' timed out after {DELEGATE_TIMEOUT_SECS}s");
+                agent_span.set_error(timeout_message.clone());
                 self.finish_coordination_trace(
                     agent_name,
                     &coordination_trace,
@@ -428,6 +468,7 @@ impl Tool for DelegateTool {
             }
             Err(e) => {
                 let failure_message = format!("Agent '{agent_name}' failed: {e}");
+                agent_span.set_error(providers::sanitize_api_error(&failure_message));
                 self.finish_coordination_trace(
                     agent_name,
                     &coordination_trace,
@@ -445,6 +486,52 @@ impl Tool for DelegateTool {
 }
 
 impl DelegateTool {
+    /// One non-agentic provider call, reported to the observer with its
+    /// token usage.
+    async fn chat_once(
+        &self,
+        provider: &dyn Provider,
+        agent_config: &DelegateAgentConfig,
+        prompt: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system_prompt) = agent_config.system_prompt.as_ref() {
+            messages.push(ChatMessage::system(system_prompt.clone()));
+        }
+        messages.push(ChatMessage::user(prompt.to_string()));
+
+        let started_at = std::time::Instant::now();
+        let result = provider
+            .chat(
+                providers::ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                &agent_config.model,
+                temperature,
+            )
+            .await;
+        let usage = result
+            .as_ref()
+            .ok()
+            .and_then(|response| response.usage.clone())
+            .unwrap_or_default();
+        self.observer.record_event(&ObserverEvent::LlmResponse {
+            provider: agent_config.provider.clone(),
+            model: agent_config.model.clone(),
+            duration: started_at.elapsed(),
+            success: result.is_ok(),
+            error_message: result
+                .as_ref()
+                .err()
+                .map(|e| providers::sanitize_api_error(&e.to_string())),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+        });
+        result.map(|response| response.text.unwrap_or_default())
+    }
+
     async fn execute_agentic(
         &self,
         agent_name: &str,
@@ -452,6 +539,7 @@ impl DelegateTool {
         provider: &dyn Provider,
         full_prompt: &str,
         temperature: f64,
+        ctx: &ToolContext,
     ) -> anyhow::Result<ToolResult> {
         if agent_config.allowed_tools.is_empty() {
             return Ok(ToolResult {
@@ -495,7 +583,8 @@ impl DelegateTool {
         }
         history.push(ChatMessage::user(full_prompt.to_string()));
 
-        let noop_observer = NoopObserver;
+        ctx.progress
+            .report(format!("agent '{agent_name}' working"));
 
         let result = tokio::time::timeout(
             Duration::from_secs(DELEGATE_AGENTIC_TIMEOUT_SECS),
@@ -503,7 +592,7 @@ impl DelegateTool {
                 provider,
                 &mut history,
                 &sub_tools,
-                &noop_observer,
+                self.observer.as_ref(),
                 &agent_config.provider,
                 &agent_config.model,
                 temperature,
@@ -512,7 +601,7 @@ impl DelegateTool {
                 "delegate",
                 &self.multimodal_config,
                 agent_config.max_iterations,
-                None,
+                Some(ctx.cancellation.clone()),
                 None,
                 None,
                 &[],
@@ -521,6 +610,9 @@ impl DelegateTool {
         .await;
 
         match result {
+            Ok(Err(e)) if crate::agent::loop_::is_tool_loop_cancelled(&e) => {
+                Ok(ToolResult::cancelled(partial_delegate_output(&history)))
+            }
             Ok(Ok(response)) => {
                 let rendered = if response.trim().is_empty() {
                     "[Empty response]".to_string()
@@ -566,6 +658,7 @@ impl DelegateTool {
             correlation_id: correlation_id.clone(),
             conversation_id: conversation_id.clone(),
             request_message_id: None,
+            request_sequence: None,
         };
 
         let Some(bus) = &self.coordination_bus else {
@@ -597,6 +690,17 @@ impl DelegateTool {
                 "delegate coordination: failed to publish delegate request for '{agent_name}': {error}"
             );
         } else {
+            // This call is the worker: take the request off the agent's inbox
+            // and acknowledge it once the run has finished. A run that never
+            // finishes leaves it to be delivered again.
+            match bus.receive_message(agent_name, &request_message_id) {
+                Ok(received) => {
+                    trace.request_sequence = received.map(|entry| entry.sequence);
+                }
+                Err(error) => tracing::warn!(
+                    "delegate coordination: failed to receive delegate request for '{agent_name}': {error}"
+                ),
+            }
             trace.request_message_id = Some(request_message_id);
         }
 
@@ -637,6 +741,14 @@ impl DelegateTool {
             return;
         };
 
+        if let Some(sequence) = trace.request_sequence {
+            if let Err(error) = bus.ack(agent_name, sequence) {
+                tracing::warn!(
+                    "delegate coordination: failed to acknowledge delegate request for '{agent_name}': {error}"
+                );
+            }
+        }
+
         let detail_preview = text_preview(detail, COORDINATION_PREVIEW_MAX_CHARS);
 
         let mut result = CoordinationEnvelope::new_direct(
@@ -690,17 +802,18 @@ struct CoordinationTrace {
     correlation_id: String,
     conversation_id: String,
     request_message_id: Option<String>,
+    request_sequence: Option<u64>,
 }
 
 fn build_coordination_bus(
     agents: &HashMap<String, DelegateAgentConfig>,
     lead_agent: &str,
-) -> Option<InMemoryMessageBus> {
+) -> Option<MessageBus> {
     if agents.is_empty() {
         return None;
     }
 
-    let bus = InMemoryMessageBus::new();
+    let bus = MessageBus::new();
     if let Err(error) = bus.register_agent(lead_agent.to_string()) {
         tracing::warn!(
             "delegate coordination: failed to register default lead agent '{lead_agent}': {error}"
@@ -734,6 +847,17 @@ fn text_preview(value: &str, max_chars: usize) -> String {
     preview
 }
 
+/// Last assistant or tool message produced by a cancelled agentic
+/// sub-agent run, so the parent turn keeps what the sub-agent got done.
+fn partial_delegate_output(history: &[ChatMessage]) -> String {
+    history
+        .iter()
+        .rev()
+        .find(|msg| msg.role == "assistant" || msg.role == "tool")
+        .map(|msg| msg.content.clone())
+        .unwrap_or_default()
+}
+
 struct ToolArcRef {
     inner: Arc<dyn Tool>,
 }
@@ -761,6 +885,14 @@ impl Tool for ToolArcRef {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        self.inner.execute_with_context(args, ctx).await
+    }
 }
 
 struct NoopObserver;
@@ -964,6 +1096,39 @@ mod tests {
         }
     }
 
+    struct UsageReportingProvider;
+
+    #[async_trait]
+    impl Provider for UsageReportingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("unused".to_string())
+        }
+
+        async fn chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            Ok(ChatResponse {
+                text: Some("ok".to_string()),
+                tool_calls: Vec::new(),
+                usage: Some(providers::traits::TokenUsage {
+                    input_tokens: Some(1000),
+                    output_tokens: Some(500),
+                }),
+                reasoning_content: None,
+                quota_metadata: None,
+            })
+        }
+    }
+
     fn agentic_config(allowed_tools: Vec<String>, max_iterations: usize) -> DelegateAgentConfig {
         DelegateAgentConfig {
             provider: "openrouter".to_string(),
@@ -1303,6 +1468,42 @@ mod tests {
             .contains("no executable tools"));
     }
 
+    #[tokio::test]
+    async fn sub_agent_usage_reaches_the_cost_observer() {
+        let workspace = tempfile::tempdir().unwrap();
+        let tracker = Arc::new(
+            crate::cost::CostTracker::new(
+                crate::config::CostConfig {
+                    enabled: true,
+                    ..Default::default()
+                },
+                workspace.path(),
+            )
+            .unwrap(),
+        );
+        let tool = DelegateTool::new(HashMap::new(), None, test_security()).with_observer(
+            Arc::new(crate::observability::CostObserver::new(
+                tracker.clone(),
+                HashMap::new(),
+            )),
+        );
+
+        let reply = tool
+            .chat_once(
+                &UsageReportingProvider,
+                &agentic_config(Vec::new(), 1),
+                "hi",
+                0.2,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(reply, "ok");
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert!(summary.session_cost_usd > 0.0);
+    }
+
     #[tokio::test]
     async fn execute_agentic_runs_tool_call_loop_with_filtered_tools() {
         let config = agentic_config(vec!["echo_tool".to_string()], 10);
@@ -1315,7 +1516,14 @@ mod tests {
 
         let provider = OneToolThenFinalProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic(
+                "agentic",
+                &config,
+                &provider,
+                "run",
+                0.2,
+                &ToolContext::default(),
+            )
             .await
             .unwrap();
 
@@ -1337,7 +1545,14 @@ mod tests {
 
         let provider = OneToolThenFinalProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic(
+                "agentic",
+                &config,
+                &provider,
+                "run",
+                0.2,
+                &ToolContext::default(),
+            )
             .await
             .unwrap();
 
@@ -1357,7 +1572,14 @@ mod tests {
 
         let provider = InfiniteToolCallProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic(
+                "agentic",
+                &config,
+                &provider,
+                "run",
+                0.2,
+                &ToolContext::default(),
+            )
             .await
             .unwrap();
 
@@ -1377,7 +1599,14 @@ mod tests {
 
         let provider = FailingProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic(
+                "agentic",
+                &config,
+                &provider,
+                "run",
+                0.2,
+                &ToolContext::default(),
+            )
             .await
             .unwrap();
 
@@ -1427,20 +1656,19 @@ mod tests {
             .coordination_bus_snapshot()
             .expect("coordination bus should be initialized");
 
-        let worker_messages = bus
-            .drain_for_agent("broken", 0)
-            .expect("worker inbox should exist");
-        assert_eq!(worker_messages.len(), 1);
-        let correlation_id = worker_messages[0]
-            .envelope
-            .correlation_id
-            .clone()
-            .expect("request should have correlation id");
+        // The run consumed and acknowledged its request, even though it failed.
+        assert_eq!(bus.pending_for_agent("broken").unwrap(), 0);
+        assert_eq!(bus.in_flight_for_agent("broken").unwrap(), 0);
 
         let lead_messages = bus
             .drain_for_agent(DEFAULT_COORDINATION_LEAD_AGENT, 0)
             .expect("lead inbox should exist");
         assert_eq!(lead_messages.len(), 3);
+        let correlation_id = lead_messages[0]
+            .envelope
+            .correlation_id
+            .clone()
+            .expect("state patch should have correlation id");
         assert!(
             lead_messages.iter().any(|entry| matches!(
                 entry.envelope.payload,
@@ -1481,17 +1709,21 @@ mod tests {
             .get("tester")
             .expect("tester config should exist");
 
+        let bus = tool
+            .coordination_bus_snapshot()
+            .expect("coordination bus should be initialized");
+
         let trace = tool.start_coordination_trace(
             "tester",
             "Summarize findings",
             "runbook notes",
             agent_config,
         );
+        assert_eq!(bus.pending_for_agent("tester").unwrap(), 0);
+        assert_eq!(bus.in_flight_for_agent("tester").unwrap(), 1);
         tool.finish_coordination_trace("tester", &trace, true, "done");
+        assert_eq!(bus.in_flight_for_agent("tester").unwrap(), 0);
 
-        let bus = tool
-            .coordination_bus_snapshot()
-            .expect("coordination bus should be initialized");
         let state_key = format!("delegate/{}/state", trace.correlation_id);
         let state_entry = bus
             .context_entry(&state_key)
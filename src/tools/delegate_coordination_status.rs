@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::coordination::{CoordinationPayload, InMemoryMessageBus, SequencedEnvelope};
+use crate::coordination::{CoordinationPayload, MessageBus, SequencedEnvelope};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -18,12 +18,12 @@ const MAX_CONTEXT_OFFSET: usize = 10_000;
 
 /// Read-only runtime observability tool for delegate coordination events.
 pub struct DelegateCoordinationStatusTool {
-    bus: InMemoryMessageBus,
+    bus: MessageBus,
     security: Arc<SecurityPolicy>,
 }
 
 impl DelegateCoordinationStatusTool {
-    pub fn new(bus: InMemoryMessageBus, security: Arc<SecurityPolicy>) -> Self {
+    pub fn new(bus: MessageBus, security: Arc<SecurityPolicy>) -> Self {
         Self { bus, security }
     }
 }
@@ -396,8 +396,8 @@ mod tests {
     use super::*;
     use crate::coordination::{CoordinationEnvelope, CoordinationPayload};
 
-    fn test_bus() -> InMemoryMessageBus {
-        let bus = InMemoryMessageBus::new();
+    fn test_bus() -> MessageBus {
+        let bus = MessageBus::new();
         bus.register_agent("delegate-lead")
             .expect("register lead should succeed");
         bus.register_agent("researcher")
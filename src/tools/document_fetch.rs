@@ -24,7 +24,9 @@ impl DocumentFetchTool {
     }
 
     fn store(&self) -> anyhow::Result<DocumentStore> {
-        let db_path = self.workspace_dir.join("memory").join("brain.db");
+        let db_path = crate::tenancy::workspace_dir(&self.workspace_dir)
+            .join("memory")
+            .join("brain.db");
         DocumentStore::open(&db_path)
     }
 }
@@ -128,7 +130,9 @@ impl DocumentSearchTool {
     }
 
     fn store(&self) -> anyhow::Result<DocumentStore> {
-        let db_path = self.workspace_dir.join("memory").join("brain.db");
+        let db_path = crate::tenancy::workspace_dir(&self.workspace_dir)
+            .join("memory")
+            .join("brain.db");
         DocumentStore::open(&db_path)
     }
 }
@@ -18,7 +18,7 @@
 //! 1. **HTML** — displayed in WYSIWYG editor for user viewing/editing
 //! 2. **Markdown** — fed to the AI for understanding and Q&A
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolContext, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -726,6 +726,15 @@ impl Tool for DocumentPipelineTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.execute_with_context(args, &ToolContext::default())
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
         let file_path = args
             .get("file_path")
             .and_then(|v| v.as_str())
@@ -808,17 +817,34 @@ impl Tool for DocumentPipelineTool {
             .ok();
         let gemini_key: Option<String> = None; // disabled — single route via Upstage only
 
-        let result = match doc_type {
-            DocumentType::DigitalPdf => self.process_digital_pdf(path, gemini_key.as_deref()).await,
-            DocumentType::ImagePdf => {
-                self.process_image_pdf(path, upstage_key.as_deref(), gemini_key.as_deref())
-                    .await
-            }
-            DocumentType::OfficeDocument(ext) => self.process_office_document(path, &ext).await,
-            DocumentType::Unsupported(ext) => Err(anyhow::anyhow!(
-                "Unsupported document format: .{ext}. \
+        let conversion = async {
+            match doc_type {
+                DocumentType::DigitalPdf => {
+                    ctx.progress.report("extracting text from digital PDF");
+                    self.process_digital_pdf(path, gemini_key.as_deref()).await
+                }
+                DocumentType::ImagePdf => {
+                    ctx.progress.report("running OCR on image PDF");
+                    self.process_image_pdf(path, upstage_key.as_deref(), gemini_key.as_deref())
+                        .await
+                }
+                DocumentType::OfficeDocument(ext) => {
+                    ctx.progress.report(format!("converting .{ext} document"));
+                    self.process_office_document(path, &ext).await
+                }
+                DocumentType::Unsupported(ext) => Err(anyhow::anyhow!(
+                    "Unsupported document format: .{ext}. \
                      Supported: PDF, HWP, HWPX, DOC, DOCX, XLS, XLSX, PPT, PPTX"
-            )),
+                )),
+            }
+        };
+        let result = tokio::select! {
+            () = ctx.cancelled() => {
+                return Ok(ToolResult::cancelled(format!(
+                    "Conversion of {file_path} was stopped before it finished; no output was saved."
+                )));
+            }
+            result = conversion => result,
         };
 
         match result {
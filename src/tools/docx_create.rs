@@ -0,0 +1,631 @@
+use super::ooxml::{self, xml_escape, ImageData, Package};
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Maximum number of blocks accepted in a single outline.
+const MAX_BLOCKS: usize = 10_000;
+/// Usable page width (A4 with 1" margins) in pixels, used to fit images.
+const MAX_IMAGE_WIDTH_PX: u32 = 600;
+
+/// Create a Word (.docx) document from a structured outline.
+pub struct DocxCreateTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl DocxCreateTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DocxCreateArgs {
+    output_path: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    blocks: Vec<BlockInput>,
+}
+
+/// A block is either a bare string (plain paragraph) or a typed object.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BlockInput {
+    Text(String),
+    Block(Block),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Block {
+    Heading {
+        text: String,
+        #[serde(default = "default_heading_level")]
+        level: u8,
+    },
+    Paragraph {
+        text: String,
+        #[serde(default)]
+        bold: bool,
+        #[serde(default)]
+        italic: bool,
+        #[serde(default)]
+        align: Option<Align>,
+    },
+    List {
+        items: Vec<String>,
+        #[serde(default)]
+        ordered: bool,
+    },
+    Table {
+        rows: Vec<Vec<Value>>,
+        #[serde(default = "default_true")]
+        header: bool,
+    },
+    Image {
+        path: String,
+        #[serde(default)]
+        width: Option<u32>,
+        #[serde(default)]
+        height: Option<u32>,
+        #[serde(default)]
+        caption: Option<String>,
+    },
+    Equation {
+        text: String,
+    },
+    PageBreak,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Align {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+impl Align {
+    fn as_ooxml(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Center => "center",
+            Self::Right => "right",
+            Self::Justify => "both",
+        }
+    }
+}
+
+fn default_heading_level() -> u8 {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl BlockInput {
+    fn into_block(self) -> Block {
+        match self {
+            Self::Text(text) => Block::Paragraph {
+                text,
+                bold: false,
+                italic: false,
+                align: None,
+            },
+            Self::Block(block) => block,
+        }
+    }
+}
+
+/// Render a JSON table cell as display text.
+pub(super) fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Emit `<w:t>` runs for `text`, turning newlines into line breaks.
+fn push_text_runs(xml: &mut String, text: &str, run_props: &str) {
+    for (i, line) in text.split('\n').enumerate() {
+        let _ = write!(xml, "<w:r>{run_props}");
+        if i > 0 {
+            xml.push_str("<w:br/>");
+        }
+        let _ = write!(
+            xml,
+            r#"<w:t xml:space="preserve">{}</w:t></w:r>"#,
+            xml_escape(line)
+        );
+    }
+}
+
+struct DocxBuilder<'a> {
+    body: String,
+    images: std::slice::Iter<'a, ImageData>,
+    media: Vec<(String, &'a ImageData)>,
+    /// Ordered lists each get their own numbering instance so they restart at 1.
+    ordered_lists: usize,
+    drawing_id: usize,
+}
+
+impl<'a> DocxBuilder<'a> {
+    fn new(images: &'a [ImageData]) -> Self {
+        Self {
+            body: String::new(),
+            images: images.iter(),
+            media: Vec::new(),
+            ordered_lists: 0,
+            drawing_id: 0,
+        }
+    }
+
+    fn paragraph(&mut self, style: Option<&str>, align: Option<Align>, text: &str, rpr: &str) {
+        self.body.push_str("<w:p>");
+        if style.is_some() || align.is_some() {
+            self.body.push_str("<w:pPr>");
+            if let Some(style) = style {
+                let _ = write!(self.body, r#"<w:pStyle w:val="{style}"/>"#);
+            }
+            if let Some(align) = align {
+                let _ = write!(self.body, r#"<w:jc w:val="{}"/>"#, align.as_ooxml());
+            }
+            self.body.push_str("</w:pPr>");
+        }
+        push_text_runs(&mut self.body, text, rpr);
+        self.body.push_str("</w:p>");
+    }
+
+    fn block(&mut self, block: &Block) -> anyhow::Result<()> {
+        match block {
+            Block::Heading { text, level } => {
+                let level = (*level).clamp(1, 6);
+                self.paragraph(Some(&format!("Heading{level}")), None, text, "");
+            }
+            Block::Paragraph {
+                text,
+                bold,
+                italic,
+                align,
+            } => {
+                let mut rpr = String::new();
+                if *bold || *italic {
+                    rpr.push_str("<w:rPr>");
+                    if *bold {
+                        rpr.push_str("<w:b/>");
+                    }
+                    if *italic {
+                        rpr.push_str("<w:i/>");
+                    }
+                    rpr.push_str("</w:rPr>");
+                }
+                self.paragraph(None, *align, text, &rpr);
+            }
+            Block::List { items, ordered } => {
+                let num_id = if *ordered {
+                    self.ordered_lists += 1;
+                    self.ordered_lists + 1
+                } else {
+                    1
+                };
+                for item in items {
+                    let _ = write!(
+                        self.body,
+                        r#"<w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="{num_id}"/></w:numPr></w:pPr>"#
+                    );
+                    push_text_runs(&mut self.body, item, "");
+                    self.body.push_str("</w:p>");
+                }
+            }
+            Block::Table { rows, header } => self.table(rows, *header),
+            Block::Image {
+                width,
+                height,
+                caption,
+                ..
+            } => {
+                let image = self
+                    .images
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("image block without loaded image data"))?;
+                self.image(image, *width, *height);
+                if let Some(caption) = caption {
+                    self.paragraph(Some("Caption"), Some(Align::Center), caption, "");
+                }
+            }
+            Block::Equation { text } => {
+                let _ = write!(
+                    self.body,
+                    r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>{}</m:t></m:r></m:oMath></m:oMathPara></w:p>"#,
+                    xml_escape(text)
+                );
+            }
+            Block::PageBreak => {
+                self.body
+                    .push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#);
+            }
+        }
+        Ok(())
+    }
+
+    fn table(&mut self, rows: &[Vec<Value>], header: bool) {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        // 9026 twips = A4 text width with 1" margins.
+        let col_width = 9026 / columns;
+        self.body.push_str(
+            r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="5000" w:type="pct"/></w:tblPr><w:tblGrid>"#,
+        );
+        for _ in 0..columns {
+            let _ = write!(self.body, r#"<w:gridCol w:w="{col_width}"/>"#);
+        }
+        self.body.push_str("</w:tblGrid>");
+        for (r, row) in rows.iter().enumerate() {
+            let is_header = header && r == 0;
+            self.body.push_str("<w:tr>");
+            if is_header {
+                self.body.push_str("<w:trPr><w:tblHeader/></w:trPr>");
+            }
+            for c in 0..columns {
+                let text = row.get(c).map(cell_text).unwrap_or_default();
+                let _ = write!(
+                    self.body,
+                    r#"<w:tc><w:tcPr><w:tcW w:w="{col_width}" w:type="dxa"/></w:tcPr><w:p>"#
+                );
+                let rpr = if is_header {
+                    "<w:rPr><w:b/></w:rPr>"
+                } else {
+                    ""
+                };
+                push_text_runs(&mut self.body, &text, rpr);
+                self.body.push_str("</w:p></w:tc>");
+            }
+            self.body.push_str("</w:tr>");
+        }
+        self.body.push_str("</w:tbl>");
+        // Word merges adjacent tables; keep an empty paragraph between them.
+        self.body.push_str("<w:p/>");
+    }
+
+    fn image(&mut self, image: &'a ImageData, width: Option<u32>, height: Option<u32>) {
+        self.drawing_id += 1;
+        let id = self.drawing_id;
+        let rel_id = format!("rIdImg{id}");
+        let name = format!("image{id}.{}", image.format.extension());
+        let (cx, cy) = image.extent_emu(width, height, MAX_IMAGE_WIDTH_PX);
+        let _ = write!(
+            self.body,
+            r#"<w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="{cx}" cy="{cy}"/><wp:docPr id="{id}" name="Picture {id}"/><wp:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect="1"/></wp:cNvGraphicFramePr><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="{id}" name="{name}"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:embed="{rel_id}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>"#
+        );
+        self.media.push((name, image));
+    }
+}
+
+/// Build a DOCX package. `images` must hold one entry per image block, in
+/// document order.
+fn build_docx(
+    title: Option<&str>,
+    blocks: &[Block],
+    images: &[ImageData],
+) -> anyhow::Result<Vec<u8>> {
+    let mut builder = DocxBuilder::new(images);
+    if let Some(title) = title {
+        builder.paragraph(Some("Title"), None, title, "");
+    }
+    for block in blocks {
+        builder.block(block)?;
+    }
+
+    let document = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        builder.body
+    );
+
+    let mut rels = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rIdStyles" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rIdNumbering" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>"#,
+    );
+    for (i, (name, _)) in builder.media.iter().enumerate() {
+        let _ = write!(
+            rels,
+            r#"<Relationship Id="rIdImg{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/{name}"/>"#,
+            i + 1
+        );
+    }
+    rels.push_str("</Relationships>");
+
+    let mut numbering = String::from(NUMBERING_HEAD);
+    numbering.push_str(r#"<w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>"#);
+    for n in 0..builder.ordered_lists {
+        let _ = write!(
+            numbering,
+            r#"<w:num w:numId="{}"><w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="1"/></w:lvlOverride></w:num>"#,
+            n + 2
+        );
+    }
+    numbering.push_str("</w:numbering>");
+
+    let mut pkg = Package::new();
+    pkg.add("[Content_Types].xml", CONTENT_TYPES);
+    pkg.add("_rels/.rels", ROOT_RELS);
+    pkg.add("docProps/core.xml", core_properties(title));
+    pkg.add("word/document.xml", document);
+    pkg.add("word/_rels/document.xml.rels", rels);
+    pkg.add("word/styles.xml", STYLES);
+    pkg.add("word/numbering.xml", numbering);
+    for (name, image) in builder.media {
+        pkg.add(format!("word/media/{name}"), image.bytes.clone());
+    }
+    pkg.finish()
+}
+
+pub(super) fn core_properties(title: Option<&str>) -> String {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dc:creator>ZeroClaw</dc:creator><dcterms:created xsi:type="dcterms:W3CDTF">{now}</dcterms:created><dcterms:modified xsi:type="dcterms:W3CDTF">{now}</dcterms:modified></cp:coreProperties>"#,
+        xml_escape(title.unwrap_or_default())
+    )
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Default Extension="jpeg" ContentType="image/jpeg"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:eastAsia="Malgun Gothic" w:cs="Calibri"/><w:sz w:val="22"/><w:szCs w:val="22"/><w:lang w:val="en-US" w:eastAsia="ko-KR"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="160" w:line="259" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style><w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:after="240"/><w:jc w:val="center"/></w:pPr><w:rPr><w:b/><w:sz w:val="48"/><w:szCs w:val="48"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/><w:szCs w:val="36"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="30"/><w:szCs w:val="30"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="80"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/><w:szCs w:val="26"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading4"><w:name w:val="heading 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:outlineLvl w:val="3"/></w:pPr><w:rPr><w:b/><w:i/><w:sz w:val="24"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading5"><w:name w:val="heading 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:outlineLvl w:val="4"/></w:pPr><w:rPr><w:b/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading6"><w:name w:val="heading 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:outlineLvl w:val="5"/></w:pPr><w:rPr><w:i/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:after="60"/><w:ind w:left="720"/><w:contextualSpacing/></w:pPr></w:style><w:style w:type="paragraph" w:styleId="Caption"><w:name w:val="caption"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:rPr><w:i/><w:sz w:val="18"/></w:rPr></w:style><w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style></w:styles>"#;
+
+const NUMBERING_HEAD: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="hybridMultilevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="&#8226;"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum><w:abstractNum w:abstractNumId="1"><w:multiLevelType w:val="hybridMultilevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum>"#;
+
+fn error_result(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for DocxCreateTool {
+    fn name(&self) -> &str {
+        "docx_create"
+    }
+
+    fn safe_for_slm(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &str {
+        "Create a Word (.docx) document in the workspace from a structured outline of \
+         headings, paragraphs, bullet/numbered lists, tables, images, equations and page breaks."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "output_path": {
+                    "type": "string",
+                    "description": "Where to save the .docx file. Relative paths resolve from workspace."
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional document title, rendered in Title style and stored in document properties"
+                },
+                "blocks": {
+                    "type": "array",
+                    "description": "Body content in order. A plain string is a paragraph; objects select a block type.",
+                    "items": {
+                        "oneOf": [
+                            { "type": "string" },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "type": {
+                                        "type": "string",
+                                        "enum": ["heading", "paragraph", "list", "table", "image", "equation", "page_break"]
+                                    },
+                                    "text": { "type": "string", "description": "heading / paragraph / equation text" },
+                                    "level": { "type": "integer", "minimum": 1, "maximum": 6, "description": "heading level" },
+                                    "bold": { "type": "boolean" },
+                                    "italic": { "type": "boolean" },
+                                    "align": { "type": "string", "enum": ["left", "center", "right", "justify"] },
+                                    "items": { "type": "array", "items": { "type": "string" }, "description": "list items" },
+                                    "ordered": { "type": "boolean", "description": "numbered instead of bulleted list" },
+                                    "rows": {
+                                        "type": "array",
+                                        "items": { "type": "array" },
+                                        "description": "table rows (array of cell values)"
+                                    },
+                                    "header": { "type": "boolean", "description": "treat the first table row as a header (default: true)" },
+                                    "path": { "type": "string", "description": "workspace path of a PNG/JPEG image" },
+                                    "width": { "type": "integer", "minimum": 1, "description": "image width in pixels" },
+                                    "height": { "type": "integer", "minimum": 1, "description": "image height in pixels" },
+                                    "caption": { "type": "string" }
+                                },
+                                "required": ["type"]
+                            }
+                        ]
+                    }
+                }
+            },
+            "required": ["output_path", "blocks"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let parsed: DocxCreateArgs = serde_json::from_value(args)
+            .map_err(|e| anyhow::anyhow!("invalid docx_create arguments: {e}"))?;
+        if parsed.blocks.len() > MAX_BLOCKS {
+            return Ok(error_result(format!(
+                "Too many blocks: {} (limit: {MAX_BLOCKS})",
+                parsed.blocks.len()
+            )));
+        }
+
+        let target =
+            match ooxml::resolve_output_path(&self.security, &parsed.output_path, "docx").await {
+                Ok(target) => target,
+                Err(e) => return Ok(error_result(e)),
+            };
+
+        let blocks: Vec<Block> = parsed
+            .blocks
+            .into_iter()
+            .map(BlockInput::into_block)
+            .collect();
+        let mut images = Vec::new();
+        for block in &blocks {
+            if let Block::Image { path, .. } = block {
+                match ooxml::load_image(&self.security, path).await {
+                    Ok(image) => images.push(image),
+                    Err(e) => return Ok(error_result(e)),
+                }
+            }
+        }
+
+        let bytes = build_docx(parsed.title.as_deref(), &blocks, &images)?;
+        if let Err(e) = ooxml::write_output(&self.security, &target, &bytes).await {
+            return Ok(error_result(e));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Wrote DOCX document ({} blocks, {} bytes) to {}",
+                blocks.len(),
+                bytes.len(),
+                parsed.output_path
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use crate::tools::docx_read::extract_docx_text_from_path;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn write_png(path: &std::path::Path, width: u32, height: u32) {
+        image::RgbImage::new(width, height).save(path).unwrap();
+    }
+
+    #[test]
+    fn schema_requires_output_path_and_blocks() {
+        let tool = DocxCreateTool::new(test_security(std::env::temp_dir()));
+        let schema = tool.parameters_schema();
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.iter().any(|v| v == "output_path"));
+        assert!(required.iter().any(|v| v == "blocks"));
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_docx_reader() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_png(&tmp.path().join("chart.png"), 40, 20);
+        let tool = DocxCreateTool::new(test_security(tmp.path().to_path_buf()));
+
+        let result = tool
+            .execute(json!({
+                "output_path": "reports/q3.docx",
+                "title": "Quarterly Report",
+                "blocks": [
+                    { "type": "heading", "text": "Summary", "level": 1 },
+                    "Revenue grew <fast> & steadily.",
+                    { "type": "list", "items": ["First point", "Second point"], "ordered": true },
+                    { "type": "table", "rows": [["Region", "Sales"], ["Seoul", 120], ["Busan", null]] },
+                    { "type": "image", "path": "chart.png", "caption": "Figure 1" },
+                    { "type": "equation", "text": "x = a + b" },
+                    { "type": "page_break" },
+                    { "type": "paragraph", "text": "Closing", "bold": true, "align": "right" }
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let path = tmp.path().join("reports/q3.docx");
+        let text = extract_docx_text_from_path(&path).unwrap();
+        for expected in [
+            "Quarterly Report",
+            "Summary",
+            "Revenue grew <fast> & steadily.",
+            "First point",
+            "Second point",
+            "Region",
+            "Seoul",
+            "120",
+            "Figure 1",
+            "Closing",
+        ] {
+            assert!(text.contains(expected), "missing {expected:?} in {text:?}");
+        }
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert!(archive.by_name("word/media/image1.png").is_ok());
+        assert!(archive.by_name("[Content_Types].xml").is_ok());
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_workspace_and_read_only_mode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tool = DocxCreateTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({ "output_path": "../escape.docx", "blocks": ["x"] }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let read_only = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let result = DocxCreateTool::new(read_only)
+            .execute(json!({ "output_path": "a.docx", "blocks": ["x"] }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(!tmp.path().join("a.docx").exists());
+    }
+
+    #[tokio::test]
+    async fn missing_image_fails_without_writing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tool = DocxCreateTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({
+                "output_path": "a.docx",
+                "blocks": [{ "type": "image", "path": "nope.png" }]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(!tmp.path().join("a.docx").exists());
+    }
+}
@@ -255,6 +255,8 @@ impl Tool for FileEditTool {
 
         let new_content = content.replacen(old_string, new_string, 1);
 
+        crate::checkpoint::record_before_write(&self.security.workspace_dir, &resolved_target);
+
         match tokio::fs::write(&resolved_target, &new_content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
@@ -198,6 +198,8 @@ impl Tool for FileWriteTool {
             });
         }
 
+        crate::checkpoint::record_before_write(&self.security.workspace_dir, &resolved_target);
+
         match tokio::fs::write(&resolved_target, content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
@@ -335,6 +337,41 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_write_records_checkpoint_before_overwrite() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_write_checkpoint");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join("exist.txt"), "old")
+            .await
+            .unwrap();
+
+        let tool = FileWriteTool::new(test_security(dir.clone()));
+        let turn = crate::checkpoint::TurnCheckpoint::new("test");
+        crate::checkpoint::scope(turn.clone(), async {
+            for (path, content) in [("exist.txt", "new"), ("fresh.txt", "fresh")] {
+                let result = tool
+                    .execute(json!({"path": path, "content": content}))
+                    .await
+                    .unwrap();
+                assert!(result.success);
+            }
+        })
+        .await;
+
+        let store = crate::checkpoint::CheckpointStore::for_workspace(&dir);
+        store
+            .restore(turn.id(), &test_security(dir.clone()))
+            .unwrap();
+        let content = tokio::fs::read_to_string(dir.join("exist.txt"))
+            .await
+            .unwrap();
+        assert_eq!(content, "old");
+        assert!(!dir.join("fresh.txt").exists());
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
     #[tokio::test]
     async fn file_write_blocks_path_traversal() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_write_traversal");
@@ -36,7 +36,7 @@ use serde_json::{json, Value};
 use crate::security::SecurityPolicy;
 use crate::services::document_cache::DocumentCache;
 use crate::tools::document_pipeline::DocumentPipelineTool;
-use crate::tools::traits::{Tool, ToolResult};
+use crate::tools::traits::{Tool, ToolContext, ToolResult};
 
 /// File extensions the document pipeline can convert.
 const SUPPORTED_EXTENSIONS: &[&str] = &[
@@ -111,6 +111,9 @@ struct ConvertedReport {
     consent_message: Option<String>,
     /// Total estimated credits to convert ALL pending image PDFs.
     consent_total_estimated_credits: u32,
+    /// True when the turn was cancelled before every file was visited;
+    /// the counts above cover only the files processed until then.
+    cancelled: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -301,6 +304,15 @@ impl Tool for FolderIndexTool {
     }
 
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        self.execute_with_context(args, &ToolContext::default())
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        args: Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
         let parsed: Args = serde_json::from_value(args)
             .map_err(|e| anyhow::anyhow!("invalid folder_index arguments: {e}"))?;
 
@@ -373,7 +385,18 @@ impl Tool for FolderIndexTool {
         let mut failures: Vec<FailureReport> = Vec::new();
         let mut pending_consent: Vec<PendingConsent> = Vec::new();
 
-        for path in &to_process {
+        let mut cancelled = false;
+        for (index, path) in to_process.iter().enumerate() {
+            if ctx.is_cancelled() {
+                cancelled = true;
+                break;
+            }
+            ctx.progress.report_step(
+                format!("indexing {}", path.display()),
+                index,
+                to_process.len(),
+            );
+
             // Cheap stale-check: if a fresh entry already exists, count
             // it as a cache hit and move on.
             match cache.lookup(path).await {
@@ -478,11 +501,16 @@ impl Tool for FolderIndexTool {
             consent_required,
             consent_message,
             consent_total_estimated_credits,
+            cancelled,
         };
 
+        let output = serde_json::to_string_pretty(&report).unwrap_or_default();
+        if cancelled {
+            return Ok(ToolResult::cancelled(output));
+        }
         Ok(ToolResult {
             success: true,
-            output: serde_json::to_string_pretty(&report).unwrap_or_default(),
+            output,
             error: None,
         })
     }
@@ -708,4 +736,29 @@ mod tests {
         assert_eq!(report["pending_consent"].as_array().unwrap().len(), 0);
         assert_eq!(report["consent_total_estimated_credits"], 0);
     }
+
+    #[tokio::test]
+    async fn cancelled_context_returns_partial_report() {
+        let workspace = TempDir::new().unwrap();
+        std::fs::write(workspace.path().join("a.docx"), b"x").unwrap();
+        let mut policy = SecurityPolicy::default();
+        policy.workspace_dir = workspace.path().to_path_buf();
+        let tool = FolderIndexTool::new(workspace.path().to_path_buf(), Arc::new(policy));
+        let ctx = ToolContext::default();
+        ctx.cancellation.cancel();
+
+        let result = tool
+            .execute_with_context(
+                json!({
+                    "folder": workspace.path().canonicalize().unwrap().to_string_lossy(),
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(result.is_cancelled());
+        let report: Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(report["cancelled"], true);
+        assert_eq!(report["converted"], 0);
+    }
 }
@@ -0,0 +1,263 @@
+//! `goals` — manage the long-running goals the daemon works on.
+//!
+//! Agents can read, create and re-plan goals, but cannot approve
+//! checkpointed steps; that is left to a human (see [`crate::goals`]).
+
+use super::traits::{Tool, ToolResult};
+use crate::goals::engine::{GoalEngine, GoalStatus, NewGoal, NewStep, StepStatus};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+pub struct GoalsTool {
+    engine: GoalEngine,
+    security: Arc<SecurityPolicy>,
+}
+
+impl GoalsTool {
+    pub fn new(workspace_dir: &Path, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            engine: GoalEngine::new(workspace_dir),
+            security,
+        }
+    }
+
+    fn failure(error: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+        }
+    }
+
+    async fn mutate(&self, action: &str, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = args
+            .get("goal_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let result = match action {
+            "create" => {
+                let new: NewGoal = match serde_json::from_value(args.clone()) {
+                    Ok(new) => new,
+                    Err(error) => return Ok(Self::failure(format!("Invalid goal: {error}"))),
+                };
+                self.engine.update(|state| state.add_goal(new, &now)).await
+            }
+            "add_step" => {
+                let new = NewStep {
+                    description: args
+                        .get("description")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    checkpoint: args
+                        .get("checkpoint")
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false),
+                };
+                self.engine
+                    .update(|state| {
+                        let goal = state.goal_mut(&id)?;
+                        goal.add_step(new)?;
+                        Ok(goal.clone())
+                    })
+                    .await
+            }
+            _ => {
+                let status = match action {
+                    "pause" => GoalStatus::Pending,
+                    "resume" => GoalStatus::InProgress,
+                    "complete" => GoalStatus::Completed,
+                    _ => GoalStatus::Cancelled,
+                };
+                self.engine
+                    .update(|state| crate::goals::set_status(state, &id, status))
+                    .await
+            }
+        };
+        Ok(match result {
+            Ok(goal) => ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&goal)?,
+                error: None,
+            },
+            Err(error) => Self::failure(error.to_string()),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for GoalsTool {
+    fn name(&self) -> &str {
+        "goals"
+    }
+
+    fn description(&self) -> &str {
+        "Manage long-running goals that the daemon works through step by step. \
+         list/get show goals, their steps and attempt history; create adds a goal \
+         with ordered steps; add_step re-plans a goal; pause, resume, complete and \
+         cancel change its status. Steps marked checkpoint wait for human approval."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "get", "create", "add_step", "pause", "resume", "complete", "cancel"]
+                },
+                "goal_id": {
+                    "type": "string",
+                    "description": "Goal ID (all actions except list and create)"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Goal description (create) or step description (add_step)"
+                },
+                "steps": {
+                    "type": "array",
+                    "description": "Ordered steps for create",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "description": {"type": "string"},
+                            "checkpoint": {"type": "boolean", "default": false}
+                        },
+                        "required": ["description"]
+                    }
+                },
+                "checkpoint": {
+                    "type": "boolean",
+                    "description": "Require human approval before the new step runs (add_step)"
+                },
+                "priority": {
+                    "type": "string",
+                    "enum": ["low", "medium", "high", "critical"]
+                },
+                "owner": {
+                    "type": "string",
+                    "description": "Who to notify, as channel:recipient (e.g. telegram:12345)"
+                },
+                "budget_usd": {
+                    "type": "number",
+                    "description": "Spend cap for the goal in USD"
+                },
+                "paused": {
+                    "type": "boolean",
+                    "description": "Create the goal paused"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        match action {
+            "list" => {
+                let state = self.engine.load_state().await?;
+                let goals: Vec<_> = state
+                    .goals
+                    .iter()
+                    .map(|g| {
+                        json!({
+                            "id": g.id,
+                            "description": g.description,
+                            "status": g.status,
+                            "priority": g.priority,
+                            "steps_done": g
+                                .steps
+                                .iter()
+                                .filter(|s| s.status == StepStatus::Completed)
+                                .count(),
+                            "steps": g.steps.len(),
+                            "spent_usd": g.spent_usd(),
+                        })
+                    })
+                    .collect();
+                Ok(ToolResult {
+                    success: true,
+                    output: serde_json::to_string_pretty(&goals)?,
+                    error: None,
+                })
+            }
+            "get" => {
+                let id = args
+                    .get("goal_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                let state = self.engine.load_state().await?;
+                Ok(match state.goals.iter().find(|g| g.id == id) {
+                    Some(goal) => ToolResult {
+                        success: true,
+                        output: serde_json::to_string_pretty(goal)?,
+                        error: None,
+                    },
+                    None => Self::failure(format!("Goal {id} not found")),
+                })
+            }
+            "create" | "add_step" | "pause" | "resume" | "complete" | "cancel" => {
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "goals")
+                {
+                    return Ok(Self::failure(error));
+                }
+                self.mutate(action, &args).await
+            }
+            other => Ok(Self::failure(format!("Unknown action '{other}'"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn create_then_replan_a_goal() {
+        let tmp = TempDir::new().unwrap();
+        let tool = GoalsTool::new(tmp.path(), Arc::new(SecurityPolicy::default()));
+
+        let created = tool
+            .execute(json!({
+                "action": "create",
+                "description": "Migrate the blog",
+                "priority": "high",
+                "steps": [{"description": "Export posts"}, {"description": "Switch DNS", "checkpoint": true}]
+            }))
+            .await
+            .unwrap();
+        assert!(created.success, "{:?}", created.error);
+        let goal: serde_json::Value = serde_json::from_str(&created.output).unwrap();
+        let id = goal["id"].as_str().unwrap().to_string();
+        assert_eq!(goal["status"], "in_progress");
+        assert!(goal["steps"][1]["checkpoint"].is_object());
+
+        let added = tool
+            .execute(json!({"action": "add_step", "goal_id": id, "description": "Announce"}))
+            .await
+            .unwrap();
+        assert!(added.success);
+
+        let state = GoalEngine::new(tmp.path()).load_state().await.unwrap();
+        assert_eq!(state.goals[0].steps[2].id, "s3");
+
+        let missing = tool
+            .execute(json!({"action": "pause", "goal_id": "goal-nope"}))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+    }
+}
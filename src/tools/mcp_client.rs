@@ -12,6 +12,7 @@ use tokio::sync::Mutex;
 use tokio::time::{timeout, Duration};
 
 use crate::config::schema::McpServerConfig;
+use crate::observability::trace_context::{self, SpanKind};
 use crate::tools::mcp_protocol::{
     JsonRpcRequest, McpToolDef, McpToolsListResult, MCP_PROTOCOL_VERSION,
 };
@@ -155,11 +156,23 @@ impl McpServer {
         arguments: serde_json::Value,
     ) -> Result<serde_json::Value> {
         let mut inner = self.inner.lock().await;
+        let mut span =
+            trace_context::Span::start(format!("tools/call {tool_name}"), SpanKind::Client);
+        span.set_attribute("mcp.method.name", "tools/call");
+        span.set_attribute("gen_ai.tool.name", tool_name);
+        span.set_attribute("zeroclaw.mcp.server", inner.config.name.clone());
+
+        // Stdio servers cannot see HTTP headers, so the trace context also
+        // travels in the request's `_meta`.
         let id = inner.next_id.fetch_add(1, Ordering::Relaxed);
         let req = JsonRpcRequest::new(
             id,
             "tools/call",
-            json!({ "name": tool_name, "arguments": arguments }),
+            json!({
+                "name": tool_name,
+                "arguments": arguments,
+                "_meta": { "traceparent": span.context().traceparent() },
+            }),
         );
 
         // Use per-server tool timeout if configured, otherwise default.
@@ -172,7 +185,7 @@ impl McpServer {
 
         let resp = timeout(
             Duration::from_secs(tool_timeout),
-            inner.transport.send_and_recv(&req),
+            span.scope(inner.transport.send_and_recv(&req)),
         )
         .await
         .map_err(|_| {
@@ -181,15 +194,25 @@ impl McpServer {
                 inner.config.name,
                 tool_timeout
             )
-        })?
-        .with_context(|| {
-            format!(
-                "MCP server `{}` error during tool call `{tool_name}`",
-                inner.config.name
-            )
-        })?;
+        })
+        .and_then(|resp| {
+            resp.with_context(|| {
+                format!(
+                    "MCP server `{}` error during tool call `{tool_name}`",
+                    inner.config.name
+                )
+            })
+        });
+        let resp = match resp {
+            Ok(resp) => resp,
+            Err(e) => {
+                span.set_error(format!("{e:#}"));
+                return Err(e);
+            }
+        };
 
         if let Some(err) = resp.error {
+            span.set_error(err.message.clone());
             bail!("MCP tool `{tool_name}` error {}: {}", err.code, err.message);
         }
         Ok(resp.result.unwrap_or(serde_json::Value::Null))
@@ -10,6 +10,7 @@ use tokio::time::{timeout, Duration};
 use tokio_stream::StreamExt;
 
 use crate::config::schema::{McpServerConfig, McpTransport};
+use crate::observability::trace_context;
 use crate::tools::mcp_protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, INTERNAL_ERROR};
 
 /// Maximum bytes for a single JSON-RPC response.
@@ -171,7 +172,11 @@ impl McpTransportConn for HttpTransport {
     async fn send_and_recv(&mut self, request: &JsonRpcRequest) -> Result<JsonRpcResponse> {
         let body = serde_json::to_string(request)?;
 
-        let mut req = self.client.post(&self.url).body(body);
+        let mut req = self
+            .client
+            .post(&self.url)
+            .headers(trace_context::headers())
+            .body(body);
         for (key, value) in &self.headers {
             req = req.header(key, value);
         }
@@ -676,6 +681,7 @@ impl McpTransportConn for SseTransport {
             let mut req = self
                 .client
                 .post(&url)
+                .headers(trace_context::headers())
                 .timeout(Duration::from_secs(120))
                 .body(body.clone())
                 .header("Content-Type", "application/json");
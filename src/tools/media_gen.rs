@@ -898,6 +898,12 @@ impl Tool for ElevenLabsTtsTool {
             .unwrap_or("unknown")
             .to_string();
 
+        crate::cost::record_metered(
+            crate::cost::CostKind::Tts,
+            "elevenlabs",
+            estimate_speech_minutes(char_count),
+        );
+
         let output = format!(
             "TTS audio generated successfully.\n\
              Voice: {voice_id}\n\
@@ -918,6 +924,12 @@ impl Tool for ElevenLabsTtsTool {
     }
 }
 
+/// Rough spoken length of `char_count` characters (~900 characters per
+/// minute of synthesized speech), for metering TTS usage.
+fn estimate_speech_minutes(char_count: usize) -> f64 {
+    char_count as f64 / 900.0
+}
+
 // ── Tests ────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -17,11 +17,14 @@
 
 pub mod agents_ipc;
 pub mod apply_patch;
+pub mod ask_human;
 pub mod auth_profile;
 pub mod bg_run;
 pub mod browser;
 pub mod browser_open;
 pub mod calendar;
+pub mod calendar_caldav;
+pub mod calendar_ical;
 pub mod channel_ack_config;
 pub mod cli_discovery;
 pub mod composio;
@@ -36,6 +39,7 @@ pub mod cron_update;
 pub mod delegate;
 pub mod delegate_coordination_status;
 pub mod document_pipeline;
+pub mod docx_create;
 pub mod docx_read;
 #[cfg(feature = "channel-lark")]
 pub mod feishu_doc;
@@ -45,6 +49,7 @@ pub mod file_write;
 pub mod folder_index;
 pub mod git_operations;
 pub mod glob_search;
+pub mod goals;
 #[cfg(feature = "hardware")]
 pub mod hardware_board_info;
 #[cfg(feature = "hardware")]
@@ -65,9 +70,11 @@ pub mod memory_observe;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod model_routing_config;
+pub mod ooxml;
 pub mod openclaw_migration;
 pub mod pdf_read;
 pub mod perplexity_search;
+pub mod pptx_create;
 pub mod pptx_read;
 pub mod process;
 pub mod proxy_config;
@@ -87,6 +94,7 @@ pub mod subagent_manage;
 pub mod subagent_registry;
 pub mod subagent_spawn;
 pub mod task_plan;
+pub mod team_run;
 pub mod traits;
 pub mod url_validation;
 pub mod vault_graph;
@@ -97,9 +105,11 @@ pub mod web_fetch;
 pub mod web_search_config;
 pub mod web_search_tool;
 pub mod workspace_folder;
+pub mod xlsx_create;
 pub mod xlsx_read;
 
 pub use apply_patch::ApplyPatchTool;
+pub use ask_human::AskHumanTool;
 #[allow(unused_imports)]
 pub use bg_run::{
     format_bg_result_for_injection, BgJob, BgJobStatus, BgJobStore, BgRunTool, BgStatusTool,
@@ -107,7 +117,10 @@ pub use bg_run::{
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 #[allow(unused_imports)]
-pub use calendar::{CalendarCreateEventTool, CalendarListEventsTool, CalendarProvider};
+pub use calendar::{
+    CalendarCreateEventTool, CalendarDeleteEventTool, CalendarListEventsTool, CalendarProvider,
+    CalendarUpdateEventTool,
+};
 pub use channel_ack_config::ChannelAckConfigTool;
 pub use composio::ComposioTool;
 pub use content_search::ContentSearchTool;
@@ -120,6 +133,7 @@ pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
 pub use delegate_coordination_status::DelegateCoordinationStatusTool;
 pub use document_pipeline::DocumentPipelineTool;
+pub use docx_create::DocxCreateTool;
 pub use docx_read::DocxReadTool;
 #[cfg(feature = "channel-lark")]
 pub use feishu_doc::FeishuDocTool;
@@ -128,6 +142,7 @@ pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
 pub use glob_search::GlobSearchTool;
+pub use goals::GoalsTool;
 #[cfg(feature = "hardware")]
 pub use hardware_board_info::HardwareBoardInfoTool;
 #[cfg(feature = "hardware")]
@@ -151,6 +166,7 @@ pub use model_routing_config::ModelRoutingConfigTool;
 pub use openclaw_migration::OpenClawMigrationTool;
 pub use pdf_read::PdfReadTool;
 pub use perplexity_search::PerplexitySearchTool;
+pub use pptx_create::PptxCreateTool;
 pub use pptx_read::PptxReadTool;
 pub use process::ProcessTool;
 pub use proxy_config::ProxyConfigTool;
@@ -169,9 +185,10 @@ pub use subagent_manage::SubAgentManageTool;
 pub use subagent_registry::SubAgentRegistry;
 pub use subagent_spawn::SubAgentSpawnTool;
 pub use task_plan::TaskPlanTool;
+pub use team_run::TeamRunTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{ToolContext, ToolProgress, ToolProgressSink, ToolResult, ToolSpec};
 pub use vault_graph::{
     LegalApplicableVersionTool, LegalGraphFindTool, LegalGraphNeighborsTool,
     LegalGraphShortestPathTool, LegalGraphSubgraphTool, LegalInferLawAbbreviationTool,
@@ -187,6 +204,7 @@ pub use web_access_config::WebAccessConfigTool;
 pub use web_fetch::WebFetchTool;
 pub use web_search_config::WebSearchConfigTool;
 pub use web_search_tool::WebSearchTool;
+pub use xlsx_create::XlsxCreateTool;
 pub use xlsx_read::XlsxReadTool;
 
 pub use auth_profile::ManageAuthProfileTool;
@@ -229,12 +247,30 @@ impl Tool for ArcDelegatingTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        self.inner.execute_with_context(args, ctx).await
+    }
 }
 
 fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
     tools.into_iter().map(ArcDelegatingTool::boxed).collect()
 }
 
+/// Register the calendar tools a provider supports.
+fn push_calendar_tools(tool_arcs: &mut Vec<Arc<dyn Tool>>, provider: CalendarProvider) {
+    tool_arcs.push(Arc::new(CalendarListEventsTool::new(provider.clone())));
+    if provider.supports_edits() {
+        tool_arcs.push(Arc::new(CalendarUpdateEventTool::new(provider.clone())));
+        tool_arcs.push(Arc::new(CalendarDeleteEventTool::new(provider.clone())));
+    }
+    tool_arcs.push(Arc::new(CalendarCreateEventTool::new(provider)));
+}
+
 /// Add background tool execution capabilities to a tool registry
 pub fn add_bg_tools(tools: Vec<Box<dyn Tool>>) -> (Vec<Box<dyn Tool>>, BgJobStore) {
     let bg_job_store = BgJobStore::new();
@@ -309,7 +345,9 @@ pub fn default_tools_with_runtime(
         tools.push(Box::new(FileReadTool::new(security.clone())));
         tools.push(Box::new(FileWriteTool::new(security.clone())));
         tools.push(Box::new(FileEditTool::new(security.clone())));
-        tools.push(Box::new(ApplyPatchTool::new()));
+        tools.push(Box::new(
+            ApplyPatchTool::new().with_workspace_dir(security.workspace_dir.clone()),
+        ));
         tools.push(Box::new(GlobSearchTool::new(security.clone())));
         tools.push(Box::new(ContentSearchTool::new(security.clone())));
         tools.push(Box::new(workspace_folder::WorkspaceFolderTool::new(
@@ -478,16 +516,24 @@ pub fn all_tools_with_runtime(
     }
 
     if has_shell_access {
-        tool_arcs.push(Arc::new(ShellTool::new_with_syscall_detector(
-            security.clone(),
-            runtime.clone(),
-            Some(syscall_detector.clone()),
-        )));
-        tool_arcs.push(Arc::new(ProcessTool::new_with_syscall_detector(
-            security.clone(),
-            runtime.clone(),
-            Some(syscall_detector),
-        )));
+        let command_sandbox =
+            crate::security::detect::create_command_sandbox(&root_config.security, security.autonomy);
+        tool_arcs.push(Arc::new(
+            ShellTool::new_with_syscall_detector(
+                security.clone(),
+                runtime.clone(),
+                Some(syscall_detector.clone()),
+            )
+            .with_sandbox(command_sandbox.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            ProcessTool::new_with_syscall_detector(
+                security.clone(),
+                runtime.clone(),
+                Some(syscall_detector),
+            )
+            .with_sandbox(command_sandbox),
+        ));
         tool_arcs.push(Arc::new(GitOperationsTool::new(
             security.clone(),
             workspace_dir.to_path_buf(),
@@ -502,7 +548,9 @@ pub fn all_tools_with_runtime(
         tool_arcs.push(Arc::new(FileReadTool::new(security.clone())));
         tool_arcs.push(Arc::new(FileWriteTool::new(security.clone())));
         tool_arcs.push(Arc::new(FileEditTool::new(security.clone())));
-        tool_arcs.push(Arc::new(ApplyPatchTool::new()));
+        tool_arcs.push(Arc::new(
+            ApplyPatchTool::new().with_workspace_dir(security.workspace_dir.clone()),
+        ));
         tool_arcs.push(Arc::new(GlobSearchTool::new(security.clone())));
         tool_arcs.push(Arc::new(ContentSearchTool::new(security.clone())));
         tool_arcs.push(Arc::new(workspace_folder::WorkspaceFolderTool::new(
@@ -687,6 +735,11 @@ pub fn all_tools_with_runtime(
     // XLSX text extraction
     tool_arcs.push(Arc::new(XlsxReadTool::new(security.clone())));
 
+    // Native Office authoring (DOCX / XLSX / PPTX) from structured outlines
+    tool_arcs.push(Arc::new(DocxCreateTool::new(security.clone())));
+    tool_arcs.push(Arc::new(XlsxCreateTool::new(security.clone())));
+    tool_arcs.push(Arc::new(PptxCreateTool::new(security.clone())));
+
     // Vision tools are always available
     tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
     tool_arcs.push(Arc::new(ImageInfoTool::new(security.clone())));
@@ -758,8 +811,8 @@ pub fn all_tools_with_runtime(
         }
     }
 
-    // ── Calendar tools (Google, Outlook, Kakao) ──
-    // Registered when OAuth tokens are available.
+    // ── Calendar tools (Google, Outlook, Kakao, CalDAV, ICS) ──
+    // Registered when OAuth tokens, CalDAV credentials or an ICS path are set.
     {
         let cal_cfg = &root_config.calendar;
 
@@ -800,6 +853,49 @@ pub fn all_tools_with_runtime(
                 }
             }
         }
+
+        let caldav_accounts = [
+            (
+                cal_cfg.caldav.enabled,
+                cal_cfg.caldav.url.as_str(),
+                cal_cfg.caldav.username.as_deref(),
+                cal_cfg.caldav.password.as_deref(),
+                cal_cfg.caldav.calendar.clone(),
+                cal_cfg.caldav.timezone.as_str(),
+            ),
+            (
+                cal_cfg.apple.enabled,
+                cal_cfg.apple.caldav_url.as_str(),
+                cal_cfg.apple.apple_id.as_deref(),
+                cal_cfg.apple.app_password.as_deref(),
+                None,
+                "Asia/Seoul",
+            ),
+        ];
+        for (enabled, url, username, password, calendar, timezone) in caldav_accounts {
+            let (Some(username), Some(password)) = (username, password) else {
+                continue;
+            };
+            if !enabled || url.is_empty() {
+                continue;
+            }
+            let provider = CalendarProvider::CalDav {
+                client: Arc::new(calendar_caldav::CalDavClient::new(
+                    url, username, password, calendar,
+                )),
+                timezone: timezone.parse().unwrap_or(chrono_tz::Asia::Seoul),
+            };
+            push_calendar_tools(&mut tool_arcs, provider);
+        }
+
+        if cal_cfg.ics.enabled && !cal_cfg.ics.path.is_empty() {
+            let path = shellexpand::tilde(&cal_cfg.ics.path).into_owned();
+            let provider = CalendarProvider::Ics {
+                file: calendar_ical::IcsFile::new(path),
+                timezone: cal_cfg.ics.timezone.parse().unwrap_or(chrono_tz::Asia::Seoul),
+            };
+            push_calendar_tools(&mut tool_arcs, provider);
+        }
     }
 
     if let Some(key) = composio_key {
@@ -812,33 +908,35 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    let delegate_fallback_credential = fallback_api_key.and_then(|value| {
+        let trimmed_value = value.trim();
+        (!trimmed_value.is_empty()).then(|| trimmed_value.to_owned())
+    });
+    let provider_runtime_options = crate::providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        provider_api_url: root_config.api_url.clone(),
+        provider_transport: root_config.effective_provider_transport(),
+        zeroclaw_dir: root_config
+            .config_path
+            .parent()
+            .map(std::path::PathBuf::from),
+        secrets_encrypt: root_config.secrets.encrypt,
+        reasoning_enabled: root_config.runtime.reasoning_enabled,
+        reasoning_level: root_config.effective_provider_reasoning_level(),
+        custom_provider_api_mode: root_config
+            .provider_api
+            .map(|mode| mode.as_compatible_mode()),
+        max_tokens_override: None,
+        model_support_vision: root_config.model_support_vision,
+    };
+
     // Add delegation and sub-agent orchestration tools when agents are configured
+    let mut team_coordination_bus = None;
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
             .iter()
             .map(|(name, cfg)| (name.clone(), cfg.clone()))
             .collect();
-        let delegate_fallback_credential = fallback_api_key.and_then(|value| {
-            let trimmed_value = value.trim();
-            (!trimmed_value.is_empty()).then(|| trimmed_value.to_owned())
-        });
-        let provider_runtime_options = crate::providers::ProviderRuntimeOptions {
-            auth_profile_override: None,
-            provider_api_url: root_config.api_url.clone(),
-            provider_transport: root_config.effective_provider_transport(),
-            zeroclaw_dir: root_config
-                .config_path
-                .parent()
-                .map(std::path::PathBuf::from),
-            secrets_encrypt: root_config.secrets.encrypt,
-            reasoning_enabled: root_config.runtime.reasoning_enabled,
-            reasoning_level: root_config.effective_provider_reasoning_level(),
-            custom_provider_api_mode: root_config
-                .provider_api
-                .map(|mode| mode.as_compatible_mode()),
-            max_tokens_override: None,
-            model_support_vision: root_config.model_support_vision,
-        };
         let parent_tools = Arc::new(tool_arcs.clone());
         let mut delegate_tool = DelegateTool::new_with_options(
             delegate_agents.clone(),
@@ -848,6 +946,12 @@ pub fn all_tools_with_runtime(
         )
         .with_parent_tools(parent_tools.clone())
         .with_multimodal_config(root_config.multimodal.clone());
+        if let Some(cost_observer) = crate::observability::create_ledger_cost_observer(
+            &root_config.cost,
+            &root_config.workspace_dir,
+        ) {
+            delegate_tool = delegate_tool.with_observer(Arc::new(cost_observer));
+        }
 
         if root_config.coordination.enabled {
             let coordination_lead_agent = {
@@ -858,16 +962,30 @@ pub fn all_tools_with_runtime(
                     value.to_string()
                 }
             };
-            let coordination_bus = crate::coordination::InMemoryMessageBus::with_limits(
-                crate::coordination::InMemoryMessageBusLimits {
-                    max_inbox_messages_per_agent: root_config
-                        .coordination
-                        .max_inbox_messages_per_agent,
-                    max_dead_letters: root_config.coordination.max_dead_letters,
-                    max_context_entries: root_config.coordination.max_context_entries,
-                    max_seen_message_ids: root_config.coordination.max_seen_message_ids,
-                },
-            );
+            let coordination_limits = crate::coordination::MessageBusLimits {
+                max_inbox_messages_per_agent: root_config.coordination.max_inbox_messages_per_agent,
+                max_dead_letters: root_config.coordination.max_dead_letters,
+                max_context_entries: root_config.coordination.max_context_entries,
+                max_seen_message_ids: root_config.coordination.max_seen_message_ids,
+            };
+            let coordination_bus = if root_config.coordination.backend == "sqlite" {
+                let path = root_config.workspace_dir.join("coordination.db");
+                crate::coordination::MessageBus::open_persistent(&path, coordination_limits)
+                    .unwrap_or_else(|error| {
+                        tracing::warn!(
+                            "delegate coordination: failed to open {}, using in-memory bus: {error:#}",
+                            path.display()
+                        );
+                        crate::coordination::MessageBus::with_limits(coordination_limits)
+                    })
+            } else {
+                crate::coordination::MessageBus::with_limits(coordination_limits)
+            };
+            coordination_bus.set_delivery_policy(crate::coordination::DeliveryPolicy {
+                ack_timeout_secs: root_config.coordination.ack_timeout_secs,
+                max_delivery_attempts: root_config.coordination.max_delivery_attempts,
+            });
+            coordination_bus.start_redelivery();
             if let Err(error) = coordination_bus.register_agent(coordination_lead_agent.clone()) {
                 tracing::warn!(
                     "delegate coordination: failed to register lead agent '{coordination_lead_agent}': {error}"
@@ -881,6 +999,7 @@ pub fn all_tools_with_runtime(
                 }
             }
 
+            team_coordination_bus = Some(coordination_bus.clone());
             delegate_tool = delegate_tool
                 .with_coordination_bus(coordination_bus.clone(), coordination_lead_agent);
             tool_arcs.push(Arc::new(delegate_tool));
@@ -896,9 +1015,9 @@ pub fn all_tools_with_runtime(
         let subagent_registry = Arc::new(SubAgentRegistry::new());
         tool_arcs.push(Arc::new(SubAgentSpawnTool::new(
             delegate_agents,
-            delegate_fallback_credential,
+            delegate_fallback_credential.clone(),
             security.clone(),
-            provider_runtime_options,
+            provider_runtime_options.clone(),
             subagent_registry.clone(),
             parent_tools,
             root_config.multimodal.clone(),
@@ -910,6 +1029,45 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // Add the team runner when agent teams are configured
+    if !root_config.teams.is_empty() {
+        let mut team_tool = TeamRunTool::new(
+            root_config.teams.clone(),
+            delegate_fallback_credential.clone(),
+            security.clone(),
+            provider_runtime_options.clone(),
+        )
+        .with_parent_tools(Arc::new(tool_arcs.clone()))
+        .with_multimodal_config(root_config.multimodal.clone());
+        if let Some(bus) = team_coordination_bus {
+            team_tool = team_tool.with_coordination_bus(bus);
+        }
+        if root_config.cost.enabled {
+            match crate::cost::shared_tracker(&root_config.cost, &root_config.workspace_dir) {
+                Ok(tracker) => {
+                    team_tool =
+                        team_tool.with_cost_tracker(tracker, root_config.cost.prices.clone());
+                }
+                Err(error) => {
+                    tracing::warn!("team_run: cost tracking disabled: {error}");
+                }
+            }
+        }
+        tool_arcs.push(Arc::new(team_tool));
+    }
+
+    // Let agents put questions and drafts in the human task inbox
+    if root_config.inbox.enabled {
+        if let Some(inbox) = crate::inbox::global() {
+            tool_arcs.push(Arc::new(AskHumanTool::new(inbox, security.clone())));
+        }
+    }
+
+    // Let agents plan and track the goals the daemon works on
+    if root_config.goal_loop.enabled {
+        tool_arcs.push(Arc::new(GoalsTool::new(workspace_dir, security.clone())));
+    }
+
     // Feishu document tools (enabled when channel-lark feature is active)
     #[cfg(feature = "channel-lark")]
     {
@@ -954,6 +1112,14 @@ pub fn all_tools_with_runtime(
                 parameters: tool.parameters.clone(),
             })));
         }
+        // Tools registered by WASM component and process plugins.
+        for backend in &registry.backends {
+            tool_arcs.extend(
+                crate::plugins::host::plugin_tools(backend)
+                    .into_iter()
+                    .map(Arc::from),
+            );
+        }
     }
 
     // ── Ontology tools ──────────────────────────────────────────────
@@ -1212,6 +1378,9 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"docx_read"));
+        assert!(names.contains(&"docx_create"));
+        assert!(names.contains(&"xlsx_create"));
+        assert!(names.contains(&"pptx_create"));
         assert!(names.contains(&"pdf_read"));
     }
 
@@ -0,0 +1,312 @@
+//! Shared plumbing for the native Office authoring tools
+//! (`docx_create`, `xlsx_create`, `pptx_create`).
+//!
+//! Office Open XML documents are ZIP packages of XML parts. This module owns
+//! the pieces every writer needs: XML escaping, package assembly, embedded
+//! image loading, and the workspace-policy checks that gate where a generated
+//! document may be written.
+
+use crate::security::file_link_guard::has_multiple_hard_links;
+use crate::security::SecurityPolicy;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Maximum size of a single embedded image (20 MB).
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// EMUs (English Metric Units) per pixel at 96 DPI.
+pub const EMU_PER_PX: i64 = 9525;
+
+/// Escape text for use in XML element content and attribute values.
+pub fn xml_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline/CR are invalid in XML 1.0.
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// An in-memory OOXML package, written out as a ZIP archive.
+#[derive(Default)]
+pub struct Package {
+    parts: Vec<(String, Vec<u8>)>,
+}
+
+impl Package {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: impl Into<String>, data: impl Into<Vec<u8>>) {
+        self.parts.push((name.into(), data.into()));
+    }
+
+    /// Serialize the package. `[Content_Types].xml` should be added first so
+    /// consumers that stream the archive find it immediately.
+    pub fn finish(self) -> anyhow::Result<Vec<u8>> {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        for (name, data) in self.parts {
+            zip.start_file(name, options)?;
+            zip.write_all(&data)?;
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+/// Raster image format accepted for embedding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpeg",
+        }
+    }
+}
+
+/// An image loaded from the workspace, ready to embed.
+pub struct ImageData {
+    pub bytes: Vec<u8>,
+    pub format: ImageFormat,
+    pub width_px: u32,
+    pub height_px: u32,
+}
+
+impl ImageData {
+    /// Target size in EMUs. Missing dimensions keep the aspect ratio; when
+    /// neither is given the image is scaled down to fit `max_width_px`.
+    pub fn extent_emu(
+        &self,
+        width_px: Option<u32>,
+        height_px: Option<u32>,
+        max_width_px: u32,
+    ) -> (i64, i64) {
+        let (w, h) = (
+            f64::from(self.width_px.max(1)),
+            f64::from(self.height_px.max(1)),
+        );
+        let (tw, th) = match (width_px, height_px) {
+            (Some(tw), Some(th)) => (f64::from(tw), f64::from(th)),
+            (Some(tw), None) => (f64::from(tw), f64::from(tw) * h / w),
+            (None, Some(th)) => (f64::from(th) * w / h, f64::from(th)),
+            (None, None) => {
+                let scale = (f64::from(max_width_px) / w).min(1.0);
+                (w * scale, h * scale)
+            }
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let to_emu = |px: f64| (px.round() as i64).max(1) * EMU_PER_PX;
+        (to_emu(tw), to_emu(th))
+    }
+}
+
+/// Load a PNG or JPEG image from a workspace-relative path, applying the same
+/// path policy as the read tools.
+pub async fn load_image(security: &SecurityPolicy, path: &str) -> Result<ImageData, String> {
+    if !security.is_path_allowed(path) {
+        return Err(format!("Image path not allowed by security policy: {path}"));
+    }
+    let full_path = security.workspace_dir.join(path);
+    let resolved = tokio::fs::canonicalize(&full_path)
+        .await
+        .map_err(|e| format!("Failed to resolve image path '{path}': {e}"))?;
+    if !security.is_resolved_path_allowed(&resolved) {
+        return Err(security.resolved_path_violation_message(&resolved));
+    }
+    let meta = tokio::fs::metadata(&resolved)
+        .await
+        .map_err(|e| format!("Failed to read image metadata '{path}': {e}"))?;
+    if meta.len() > MAX_IMAGE_BYTES {
+        return Err(format!(
+            "Image too large: {path} is {} bytes (limit: {MAX_IMAGE_BYTES} bytes)",
+            meta.len()
+        ));
+    }
+    let bytes = tokio::fs::read(&resolved)
+        .await
+        .map_err(|e| format!("Failed to read image '{path}': {e}"))?;
+    decode_image(bytes).map_err(|e| format!("Unsupported image '{path}': {e}"))
+}
+
+fn decode_image(bytes: Vec<u8>) -> anyhow::Result<ImageData> {
+    let format = match image::guess_format(&bytes)? {
+        image::ImageFormat::Png => ImageFormat::Png,
+        image::ImageFormat::Jpeg => ImageFormat::Jpeg,
+        other => anyhow::bail!("only PNG and JPEG are supported (got {other:?})"),
+    };
+    let (width_px, height_px) = image::ImageReader::new(std::io::Cursor::new(&bytes))
+        .with_guessed_format()?
+        .into_dimensions()?;
+    Ok(ImageData {
+        bytes,
+        format,
+        width_px,
+        height_px,
+    })
+}
+
+/// Validate `path` as a writable output location for a document with the
+/// given extension and return the resolved target. Errors are user-facing
+/// messages suitable for `ToolResult::error`.
+pub async fn resolve_output_path(
+    security: &SecurityPolicy,
+    path: &str,
+    extension: &str,
+) -> Result<PathBuf, String> {
+    if path.trim().is_empty() {
+        return Err("output_path must not be empty".into());
+    }
+    let has_extension = Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension));
+    if !has_extension {
+        return Err(format!("output_path must end with .{extension}: {path}"));
+    }
+    if !security.can_act() {
+        return Err("Action blocked: autonomy is read-only".into());
+    }
+    if security.is_rate_limited() {
+        return Err("Rate limit exceeded: too many actions in the last hour".into());
+    }
+    if !security.is_path_allowed(path) {
+        return Err(format!("Path not allowed by security policy: {path}"));
+    }
+
+    let full_path = security.workspace_dir.join(path);
+    let (Some(parent), Some(file_name)) = (full_path.parent(), full_path.file_name()) else {
+        return Err("Invalid path: missing parent directory or file name".into());
+    };
+    tokio::fs::create_dir_all(parent)
+        .await
+        .map_err(|e| format!("Failed to create parent directory: {e}"))?;
+    // Resolve parent AFTER creation to block symlink escapes.
+    let resolved_parent = tokio::fs::canonicalize(parent)
+        .await
+        .map_err(|e| format!("Failed to resolve file path: {e}"))?;
+    if !security.is_resolved_path_allowed(&resolved_parent) {
+        return Err(security.resolved_path_violation_message(&resolved_parent));
+    }
+
+    let target = resolved_parent.join(file_name);
+    if let Ok(meta) = tokio::fs::symlink_metadata(&target).await {
+        if meta.file_type().is_symlink() {
+            return Err(format!(
+                "Refusing to write through symlink: {}",
+                target.display()
+            ));
+        }
+        if has_multiple_hard_links(&meta) {
+            return Err(format!(
+                "Writing multiply-linked file '{}' is blocked by policy \
+(potential hard-link escape).",
+                target.display()
+            ));
+        }
+    }
+    Ok(target)
+}
+
+/// Consume an action from the budget, checkpoint the previous contents and
+/// write the finished document.
+pub async fn write_output(
+    security: &SecurityPolicy,
+    target: &Path,
+    bytes: &[u8],
+) -> Result<(), String> {
+    if !security.record_action() {
+        return Err("Rate limit exceeded: action budget exhausted".into());
+    }
+    crate::checkpoint::record_before_write(&security.workspace_dir, target);
+    tokio::fs::write(target, bytes)
+        .await
+        .map_err(|e| format!("Failed to write file: {e}"))
+}
+
+/// Convert a 1-based column number to its spreadsheet letters (1 → A, 27 → AA).
+pub fn column_letters(mut col: usize) -> String {
+    let mut letters = Vec::new();
+    while col > 0 {
+        let rem = (col - 1) % 26;
+        letters.push(b'A' + u8::try_from(rem).unwrap_or(0));
+        col = (col - 1) / 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_escape_handles_markup_and_control_chars() {
+        assert_eq!(xml_escape("a<b>&\"c'"), "a&lt;b&gt;&amp;&quot;c&apos;");
+        assert_eq!(xml_escape("x\u{1}y\tz"), "xy\tz");
+    }
+
+    #[test]
+    fn column_letters_roll_over() {
+        assert_eq!(column_letters(1), "A");
+        assert_eq!(column_letters(26), "Z");
+        assert_eq!(column_letters(27), "AA");
+        assert_eq!(column_letters(703), "AAA");
+    }
+
+    #[test]
+    fn extent_preserves_aspect_ratio() {
+        let img = ImageData {
+            bytes: Vec::new(),
+            format: ImageFormat::Png,
+            width_px: 200,
+            height_px: 100,
+        };
+        assert_eq!(
+            img.extent_emu(Some(100), None, 600),
+            (100 * EMU_PER_PX, 50 * EMU_PER_PX)
+        );
+        assert_eq!(
+            img.extent_emu(None, None, 100),
+            (100 * EMU_PER_PX, 50 * EMU_PER_PX)
+        );
+        assert_eq!(
+            img.extent_emu(None, None, 600),
+            (200 * EMU_PER_PX, 100 * EMU_PER_PX)
+        );
+    }
+
+    #[tokio::test]
+    async fn output_path_requires_matching_extension() {
+        let tmp = tempfile::tempdir().unwrap();
+        let security = SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        };
+        let err = resolve_output_path(&security, "report.txt", "docx")
+            .await
+            .unwrap_err();
+        assert!(err.contains(".docx"));
+        let err = resolve_output_path(&security, "../escape.docx", "docx")
+            .await
+            .unwrap_err();
+        assert!(err.contains("not allowed"));
+        assert!(resolve_output_path(&security, "out/report.DOCX", "docx")
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,702 @@
+use super::docx_create::{cell_text, core_properties};
+use super::ooxml::{self, xml_escape, ImageData, Package};
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Maximum number of slides in a single deck.
+const MAX_SLIDES: usize = 500;
+
+/// 16:9 slide size in EMUs (13.333" x 7.5").
+const SLIDE_WIDTH: i64 = 12_192_000;
+const SLIDE_HEIGHT: i64 = 6_858_000;
+const MARGIN: i64 = 457_200;
+const TITLE_TOP: i64 = 365_125;
+const TITLE_HEIGHT: i64 = 1_325_563;
+const BODY_TOP: i64 = 1_825_625;
+const BODY_HEIGHT: i64 = 4_351_338;
+const COLUMN_GAP: i64 = 304_800;
+
+/// Create a PowerPoint (.pptx) deck from a list of slide outlines.
+pub struct PptxCreateTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl PptxCreateTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PptxCreateArgs {
+    output_path: String,
+    #[serde(default)]
+    title: Option<String>,
+    slides: Vec<SlideSpec>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Layout {
+    Title,
+    TitleContent,
+    TwoContent,
+    Section,
+    Image,
+    Table,
+    Blank,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SlideSpec {
+    #[serde(default)]
+    layout: Option<Layout>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    subtitle: Option<String>,
+    #[serde(default)]
+    bullets: Vec<String>,
+    #[serde(default)]
+    left: Vec<String>,
+    #[serde(default)]
+    right: Vec<String>,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    table: Vec<Vec<Value>>,
+    #[serde(default = "default_true")]
+    header: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl SlideSpec {
+    /// Explicit layout, or the one implied by which fields are populated.
+    fn layout(&self) -> Layout {
+        if let Some(layout) = self.layout {
+            return layout;
+        }
+        if self.image.is_some() {
+            Layout::Image
+        } else if !self.table.is_empty() {
+            Layout::Table
+        } else if !self.left.is_empty() || !self.right.is_empty() {
+            Layout::TwoContent
+        } else if !self.bullets.is_empty() {
+            Layout::TitleContent
+        } else {
+            Layout::Title
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x: i64,
+    y: i64,
+    cx: i64,
+    cy: i64,
+}
+
+const TITLE_RECT: Rect = Rect {
+    x: MARGIN,
+    y: TITLE_TOP,
+    cx: SLIDE_WIDTH - 2 * MARGIN,
+    cy: TITLE_HEIGHT,
+};
+const BODY_RECT: Rect = Rect {
+    x: MARGIN,
+    y: BODY_TOP,
+    cx: SLIDE_WIDTH - 2 * MARGIN,
+    cy: BODY_HEIGHT,
+};
+
+struct TextStyle {
+    size: u32,
+    bold: bool,
+    centered: bool,
+    bullets: bool,
+}
+
+/// Accumulates the shapes of one slide.
+struct SlideBuilder {
+    shapes: String,
+    next_id: u32,
+    image: Option<(String, usize)>,
+}
+
+impl SlideBuilder {
+    fn new() -> Self {
+        Self {
+            shapes: String::new(),
+            next_id: 2,
+            image: None,
+        }
+    }
+
+    fn id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn text_box(&mut self, rect: Rect, lines: &[&str], style: &TextStyle, anchor: &str) {
+        if lines.is_empty() {
+            return;
+        }
+        let id = self.id();
+        let _ = write!(
+            self.shapes,
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="TextBox {id}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:noFill/></p:spPr><p:txBody><a:bodyPr wrap="square" anchor="{anchor}"><a:normAutofit/></a:bodyPr><a:lstStyle/>"#,
+            rect.x, rect.y, rect.cx, rect.cy
+        );
+        for line in lines {
+            self.shapes.push_str("<a:p>");
+            if style.bullets {
+                self.shapes.push_str(
+                    r#"<a:pPr marL="342900" indent="-342900"><a:spcBef><a:spcPts val="600"/></a:spcBef><a:buFont typeface="Arial"/><a:buChar char="&#8226;"/></a:pPr>"#,
+                );
+            } else if style.centered {
+                self.shapes.push_str(r#"<a:pPr algn="ctr"/>"#);
+            }
+            let _ = write!(
+                self.shapes,
+                r#"<a:r><a:rPr lang="en-US" sz="{}" b="{}" dirty="0"/><a:t>{}</a:t></a:r></a:p>"#,
+                style.size,
+                u8::from(style.bold),
+                xml_escape(line)
+            );
+        }
+        self.shapes.push_str("</p:txBody></p:sp>");
+    }
+
+    fn title(&mut self, text: Option<&str>) {
+        let lines: Vec<&str> = text.into_iter().collect();
+        let style = TextStyle {
+            size: 3600,
+            bold: true,
+            centered: false,
+            bullets: false,
+        };
+        self.text_box(TITLE_RECT, &lines, &style, "b");
+    }
+
+    fn bullets(&mut self, rect: Rect, items: &[String]) {
+        let lines: Vec<&str> = items.iter().map(String::as_str).collect();
+        let style = TextStyle {
+            size: 2400,
+            bold: false,
+            centered: false,
+            bullets: true,
+        };
+        self.text_box(rect, &lines, &style, "t");
+    }
+
+    fn picture(&mut self, image: &ImageData, media_index: usize, area: Rect) {
+        // Fit inside the area, preserving aspect ratio, centered.
+        let (w, h) = (
+            f64::from(image.width_px.max(1)),
+            f64::from(image.height_px.max(1)),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        let scale = (area.cx as f64 / w).min(area.cy as f64 / h);
+        #[allow(clippy::cast_possible_truncation)]
+        let (cx, cy) = ((w * scale) as i64, (h * scale) as i64);
+        let (x, y) = (area.x + (area.cx - cx) / 2, area.y + (area.cy - cy) / 2);
+        let id = self.id();
+        let _ = write!(
+            self.shapes,
+            r#"<p:pic><p:nvPicPr><p:cNvPr id="{id}" name="Picture {id}"/><p:cNvPicPr><a:picLocks noChangeAspect="1"/></p:cNvPicPr><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="rId2"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic>"#
+        );
+        self.image = Some((
+            format!("image{media_index}.{}", image.format.extension()),
+            media_index,
+        ));
+    }
+
+    fn table(&mut self, rows: &[Vec<Value>], header: bool, area: Rect) {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let columns_i64 = i64::try_from(columns).unwrap_or(i64::MAX);
+        let col_width = area.cx / columns_i64;
+        let row_height: i64 = 370_840;
+        let rows_i64 = i64::try_from(rows.len()).unwrap_or(i64::MAX);
+        let id = self.id();
+        let _ = write!(
+            self.shapes,
+            r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="{id}" name="Table {id}"/><p:cNvGraphicFramePr><a:graphicFrameLocks noGrp="1"/></p:cNvGraphicFramePr><p:nvPr/></p:nvGraphicFramePr><p:xfrm><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"><a:tbl><a:tblPr firstRow="{}" bandRow="1"/><a:tblGrid>"#,
+            area.x,
+            area.y,
+            col_width * columns_i64,
+            row_height * rows_i64,
+            u8::from(header)
+        );
+        for _ in 0..columns {
+            let _ = write!(self.shapes, r#"<a:gridCol w="{col_width}"/>"#);
+        }
+        self.shapes.push_str("</a:tblGrid>");
+        let border = |tag: &str| {
+            format!(
+                r#"<a:{tag} w="12700"><a:solidFill><a:srgbClr val="7F7F7F"/></a:solidFill></a:{tag}>"#
+            )
+        };
+        let borders: String = ["lnL", "lnR", "lnT", "lnB"]
+            .iter()
+            .map(|t| border(t))
+            .collect();
+        for (r, row) in rows.iter().enumerate() {
+            let is_header = header && r == 0;
+            let _ = write!(self.shapes, r#"<a:tr h="{row_height}">"#);
+            for c in 0..columns {
+                let text = row.get(c).map(cell_text).unwrap_or_default();
+                let fill = if is_header {
+                    r#"<a:solidFill><a:srgbClr val="D9E2F3"/></a:solidFill>"#
+                } else {
+                    ""
+                };
+                let _ = write!(
+                    self.shapes,
+                    r#"<a:tc><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US" sz="1600" b="{}" dirty="0"/><a:t>{}</a:t></a:r></a:p></a:txBody><a:tcPr>{borders}{fill}</a:tcPr></a:tc>"#,
+                    u8::from(is_header),
+                    xml_escape(&text)
+                );
+            }
+            self.shapes.push_str("</a:tr>");
+        }
+        self.shapes
+            .push_str("</a:tbl></a:graphicData></a:graphic></p:graphicFrame>");
+    }
+
+    fn finish(self) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="0" cy="0"/><a:chOff x="0" y="0"/><a:chExt cx="0" cy="0"/></a:xfrm></p:grpSpPr>{}</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>"#,
+            self.shapes
+        )
+    }
+}
+
+/// Render one slide. Returns the slide XML and, if it embeds a picture,
+/// the media file name.
+fn render_slide(spec: &SlideSpec, image: Option<(&ImageData, usize)>) -> (String, Option<String>) {
+    let mut slide = SlideBuilder::new();
+    let title = spec.title.as_deref();
+    match spec.layout() {
+        Layout::Title | Layout::Section => {
+            let is_title = spec.layout() == Layout::Title;
+            let (title_y, title_size) = if is_title {
+                (2_130_425, 4400)
+            } else {
+                (2_766_219, 4000)
+            };
+            slide.text_box(
+                Rect {
+                    x: MARGIN,
+                    y: title_y,
+                    cx: SLIDE_WIDTH - 2 * MARGIN,
+                    cy: 1_470_025,
+                },
+                &title.into_iter().collect::<Vec<_>>(),
+                &TextStyle {
+                    size: title_size,
+                    bold: true,
+                    centered: is_title,
+                    bullets: false,
+                },
+                "b",
+            );
+            slide.text_box(
+                Rect {
+                    x: MARGIN,
+                    y: title_y + 1_600_200,
+                    cx: SLIDE_WIDTH - 2 * MARGIN,
+                    cy: 1_752_600,
+                },
+                &spec.subtitle.as_deref().into_iter().collect::<Vec<_>>(),
+                &TextStyle {
+                    size: 2400,
+                    bold: false,
+                    centered: is_title,
+                    bullets: false,
+                },
+                "t",
+            );
+        }
+        Layout::TitleContent => {
+            slide.title(title);
+            slide.bullets(BODY_RECT, &spec.bullets);
+        }
+        Layout::TwoContent => {
+            slide.title(title);
+            let half = (BODY_RECT.cx - COLUMN_GAP) / 2;
+            let left = if spec.left.is_empty() {
+                &spec.bullets
+            } else {
+                &spec.left
+            };
+            slide.bullets(
+                Rect {
+                    cx: half,
+                    ..BODY_RECT
+                },
+                left,
+            );
+            slide.bullets(
+                Rect {
+                    x: BODY_RECT.x + half + COLUMN_GAP,
+                    cx: half,
+                    ..BODY_RECT
+                },
+                &spec.right,
+            );
+        }
+        Layout::Image => {
+            slide.title(title);
+            if let Some((image, media_index)) = image {
+                slide.picture(image, media_index, BODY_RECT);
+            }
+        }
+        Layout::Table => {
+            slide.title(title);
+            slide.table(&spec.table, spec.header, BODY_RECT);
+        }
+        Layout::Blank => {
+            if let Some((image, media_index)) = image {
+                slide.picture(
+                    image,
+                    media_index,
+                    Rect {
+                        x: 0,
+                        y: 0,
+                        cx: SLIDE_WIDTH,
+                        cy: SLIDE_HEIGHT,
+                    },
+                );
+            }
+        }
+    }
+    let media = slide.image.as_ref().map(|(name, _)| name.clone());
+    (slide.finish(), media)
+}
+
+/// Build a PPTX package. `images[i]` holds the picture for slide `i`, if any.
+fn build_pptx(
+    title: Option<&str>,
+    slides: &[SlideSpec],
+    images: &[Option<ImageData>],
+) -> anyhow::Result<Vec<u8>> {
+    let mut pkg = Package::new();
+    let mut content_types = String::from(CONTENT_TYPES_HEAD);
+    let mut presentation_rels = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rIdMaster" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideMaster" Target="slideMasters/slideMaster1.xml"/><Relationship Id="rIdTheme" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="theme/theme1.xml"/>"#,
+    );
+    let mut slide_ids = String::new();
+    let mut parts = Vec::new();
+    let mut media_count = 0usize;
+
+    for (i, spec) in slides.iter().enumerate() {
+        let n = i + 1;
+        let image = images.get(i).and_then(Option::as_ref).map(|img| {
+            media_count += 1;
+            (img, media_count)
+        });
+        let (xml, media) = render_slide(spec, image);
+        let _ = write!(
+            content_types,
+            r#"<Override PartName="/ppt/slides/slide{n}.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slide+xml"/>"#
+        );
+        let _ = write!(
+            presentation_rels,
+            r#"<Relationship Id="rIdSlide{n}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" Target="slides/slide{n}.xml"/>"#
+        );
+        let _ = write!(
+            slide_ids,
+            r#"<p:sldId id="{}" r:id="rIdSlide{n}"/>"#,
+            255 + n
+        );
+        let mut rels = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout" Target="../slideLayouts/slideLayout1.xml"/>"#,
+        );
+        if let (Some(name), Some((img, _))) = (&media, image) {
+            let _ = write!(
+                rels,
+                r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/{name}"/>"#
+            );
+            parts.push((format!("ppt/media/{name}"), img.bytes.clone()));
+        }
+        rels.push_str("</Relationships>");
+        parts.push((format!("ppt/slides/slide{n}.xml"), xml.into_bytes()));
+        parts.push((
+            format!("ppt/slides/_rels/slide{n}.xml.rels"),
+            rels.into_bytes(),
+        ));
+    }
+    content_types.push_str("</Types>");
+    presentation_rels.push_str("</Relationships>");
+
+    let presentation = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:presentation xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" saveSubsetFonts="1"><p:sldMasterIdLst><p:sldMasterId id="2147483648" r:id="rIdMaster"/></p:sldMasterIdLst><p:sldIdLst>{slide_ids}</p:sldIdLst><p:sldSz cx="{SLIDE_WIDTH}" cy="{SLIDE_HEIGHT}"/><p:notesSz cx="6858000" cy="9144000"/></p:presentation>"#
+    );
+
+    pkg.add("[Content_Types].xml", content_types);
+    pkg.add("_rels/.rels", ROOT_RELS);
+    pkg.add("docProps/core.xml", core_properties(title));
+    pkg.add("ppt/presentation.xml", presentation);
+    pkg.add("ppt/_rels/presentation.xml.rels", presentation_rels);
+    pkg.add("ppt/slideMasters/slideMaster1.xml", SLIDE_MASTER);
+    pkg.add(
+        "ppt/slideMasters/_rels/slideMaster1.xml.rels",
+        SLIDE_MASTER_RELS,
+    );
+    pkg.add("ppt/slideLayouts/slideLayout1.xml", SLIDE_LAYOUT);
+    pkg.add(
+        "ppt/slideLayouts/_rels/slideLayout1.xml.rels",
+        SLIDE_LAYOUT_RELS,
+    );
+    pkg.add("ppt/theme/theme1.xml", THEME);
+    for (name, data) in parts {
+        pkg.add(name, data);
+    }
+    pkg.finish()
+}
+
+const CONTENT_TYPES_HEAD: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Default Extension="jpeg" ContentType="image/jpeg"/><Override PartName="/ppt/presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/><Override PartName="/ppt/slideMasters/slideMaster1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideMaster+xml"/><Override PartName="/ppt/slideLayouts/slideLayout1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideLayout+xml"/><Override PartName="/ppt/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="ppt/presentation.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const SLIDE_MASTER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sldMaster xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:bg><p:bgRef idx="1001"><a:schemeClr val="bg1"/></p:bgRef></p:bg><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="0" cy="0"/><a:chOff x="0" y="0"/><a:chExt cx="0" cy="0"/></a:xfrm></p:grpSpPr></p:spTree></p:cSld><p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink"/><p:sldLayoutIdLst><p:sldLayoutId id="2147483649" r:id="rId1"/></p:sldLayoutIdLst></p:sldMaster>"#;
+
+const SLIDE_MASTER_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout" Target="../slideLayouts/slideLayout1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="../theme/theme1.xml"/></Relationships>"#;
+
+const SLIDE_LAYOUT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sldLayout xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" type="blank" preserve="1"><p:cSld name="Blank"><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="0" cy="0"/><a:chOff x="0" y="0"/><a:chExt cx="0" cy="0"/></a:xfrm></p:grpSpPr></p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sldLayout>"#;
+
+const SLIDE_LAYOUT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideMaster" Target="../slideMasters/slideMaster1.xml"/></Relationships>"#;
+
+const THEME: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office Theme"><a:themeElements><a:clrScheme name="Office"><a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1><a:lt1><a:sysClr val="window" lastClr="FFFFFF"/></a:lt1><a:dk2><a:srgbClr val="44546A"/></a:dk2><a:lt2><a:srgbClr val="E7E6E6"/></a:lt2><a:accent1><a:srgbClr val="4472C4"/></a:accent1><a:accent2><a:srgbClr val="ED7D31"/></a:accent2><a:accent3><a:srgbClr val="A5A5A5"/></a:accent3><a:accent4><a:srgbClr val="FFC000"/></a:accent4><a:accent5><a:srgbClr val="5B9BD5"/></a:accent5><a:accent6><a:srgbClr val="70AD47"/></a:accent6><a:hlink><a:srgbClr val="0563C1"/></a:hlink><a:folHlink><a:srgbClr val="954F72"/></a:folHlink></a:clrScheme><a:fontScheme name="Office"><a:majorFont><a:latin typeface="Calibri Light"/><a:ea typeface=""/><a:cs typeface=""/><a:font script="Hang" typeface="맑은 고딕"/></a:majorFont><a:minorFont><a:latin typeface="Calibri"/><a:ea typeface=""/><a:cs typeface=""/><a:font script="Hang" typeface="맑은 고딕"/></a:minorFont></a:fontScheme><a:fmtScheme name="Office"><a:fillStyleLst><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:fillStyleLst><a:lnStyleLst><a:ln w="6350"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln><a:ln w="12700"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln><a:ln w="19050"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln></a:lnStyleLst><a:effectStyleLst><a:effectStyle><a:effectLst/></a:effectStyle><a:effectStyle><a:effectLst/></a:effectStyle><a:effectStyle><a:effectLst/></a:effectStyle></a:effectStyleLst><a:bgFillStyleLst><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:bgFillStyleLst></a:fmtScheme></a:themeElements></a:theme>"#;
+
+fn error_result(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for PptxCreateTool {
+    fn name(&self) -> &str {
+        "pptx_create"
+    }
+
+    fn safe_for_slm(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &str {
+        "Create a PowerPoint (.pptx) deck in the workspace from slide outlines. Layouts: title, \
+         title_content (bullets), two_content (left/right bullets), section, image, table, blank. \
+         When layout is omitted it is inferred from the fields provided."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "output_path": {
+                    "type": "string",
+                    "description": "Where to save the .pptx file. Relative paths resolve from workspace."
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional deck title stored in document properties"
+                },
+                "slides": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "layout": {
+                                "type": "string",
+                                "enum": ["title", "title_content", "two_content", "section", "image", "table", "blank"]
+                            },
+                            "title": { "type": "string" },
+                            "subtitle": { "type": "string", "description": "title/section layouts" },
+                            "bullets": { "type": "array", "items": { "type": "string" } },
+                            "left": { "type": "array", "items": { "type": "string" }, "description": "two_content left column" },
+                            "right": { "type": "array", "items": { "type": "string" }, "description": "two_content right column" },
+                            "image": { "type": "string", "description": "workspace path of a PNG/JPEG image" },
+                            "table": { "type": "array", "items": { "type": "array" }, "description": "table rows" },
+                            "header": { "type": "boolean", "description": "style the first table row as a header (default: true)" }
+                        }
+                    }
+                }
+            },
+            "required": ["output_path", "slides"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let parsed: PptxCreateArgs = serde_json::from_value(args)
+            .map_err(|e| anyhow::anyhow!("invalid pptx_create arguments: {e}"))?;
+        if parsed.slides.is_empty() {
+            return Ok(error_result("At least one slide is required"));
+        }
+        if parsed.slides.len() > MAX_SLIDES {
+            return Ok(error_result(format!(
+                "Too many slides: {} (limit: {MAX_SLIDES})",
+                parsed.slides.len()
+            )));
+        }
+
+        let target =
+            match ooxml::resolve_output_path(&self.security, &parsed.output_path, "pptx").await {
+                Ok(target) => target,
+                Err(e) => return Ok(error_result(e)),
+            };
+
+        let mut images = Vec::with_capacity(parsed.slides.len());
+        for slide in &parsed.slides {
+            let image = match &slide.image {
+                Some(path) => match ooxml::load_image(&self.security, path).await {
+                    Ok(image) => Some(image),
+                    Err(e) => return Ok(error_result(e)),
+                },
+                None => None,
+            };
+            images.push(image);
+        }
+
+        let bytes = build_pptx(parsed.title.as_deref(), &parsed.slides, &images)?;
+        if let Err(e) = ooxml::write_output(&self.security, &target, &bytes).await {
+            return Ok(error_result(e));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Wrote PPTX deck ({} slides, {} bytes) to {}",
+                parsed.slides.len(),
+                bytes.len(),
+                parsed.output_path
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use crate::tools::pptx_read::extract_pptx_text_from_path;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn layout_is_inferred_from_fields() {
+        let spec = |v: Value| serde_json::from_value::<SlideSpec>(v).unwrap().layout();
+        assert_eq!(spec(json!({ "title": "Hi" })), Layout::Title);
+        assert_eq!(spec(json!({ "bullets": ["a"] })), Layout::TitleContent);
+        assert_eq!(spec(json!({ "left": ["a"] })), Layout::TwoContent);
+        assert_eq!(spec(json!({ "table": [["a"]] })), Layout::Table);
+        assert_eq!(spec(json!({ "image": "a.png" })), Layout::Image);
+        assert_eq!(
+            spec(json!({ "layout": "section", "bullets": ["a"] })),
+            Layout::Section
+        );
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_pptx_reader() {
+        let tmp = tempfile::tempdir().unwrap();
+        image::RgbImage::new(64, 48)
+            .save(tmp.path().join("photo.png"))
+            .unwrap();
+        let tool = PptxCreateTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({
+                "output_path": "deck/pitch.pptx",
+                "title": "Pitch",
+                "slides": [
+                    { "title": "Product Launch", "subtitle": "Q4 & beyond" },
+                    { "title": "Agenda", "bullets": ["Market", "Roadmap"] },
+                    { "title": "Compare", "left": ["Before"], "right": ["After"] },
+                    { "layout": "section", "title": "Appendix" },
+                    { "title": "Numbers", "table": [["Metric", "Value"], ["Users", 1200]] },
+                    { "title": "Photo", "image": "photo.png" }
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let path = tmp.path().join("deck/pitch.pptx");
+        let text = extract_pptx_text_from_path(&path).unwrap();
+        let order = [
+            "Product Launch",
+            "Q4 & beyond",
+            "Agenda",
+            "Market",
+            "Roadmap",
+            "Before",
+            "After",
+            "Appendix",
+            "Metric",
+            "1200",
+            "Photo",
+        ];
+        let mut cursor = 0;
+        for expected in order {
+            let pos = text[cursor..]
+                .find(expected)
+                .unwrap_or_else(|| panic!("missing {expected:?} after {cursor} in {text:?}"));
+            cursor += pos;
+        }
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert!(archive.by_name("ppt/media/image1.png").is_ok());
+        assert!(archive.by_name("ppt/theme/theme1.xml").is_ok());
+    }
+
+    #[tokio::test]
+    async fn image_outside_workspace_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tool = PptxCreateTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({
+                "output_path": "deck.pptx",
+                "slides": [{ "title": "x", "image": "/etc/passwd" }]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(!tmp.path().join("deck.pptx").exists());
+    }
+}
@@ -3,11 +3,12 @@ use super::traits::{Tool, ToolResult};
 use crate::runtime::RuntimeAdapter;
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
-use crate::security::SyscallAnomalyDetector;
+use crate::security::{Sandbox, SyscallAnomalyDetector};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 use tokio::io::AsyncReadExt;
@@ -33,6 +34,7 @@ struct ProcessEntry {
     stdout_buf: Arc<Mutex<OutputBuffer>>,
     stderr_buf: Arc<Mutex<OutputBuffer>>,
     analyzed_offsets: Mutex<(u64, u64)>,
+    violation_reported: AtomicBool,
 }
 
 /// Background process management tool.
@@ -44,6 +46,7 @@ pub struct ProcessTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
     syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
+    sandbox: Option<Arc<dyn Sandbox>>,
     processes: Arc<RwLock<HashMap<usize, ProcessEntry>>>,
     next_id: Mutex<usize>,
 }
@@ -62,11 +65,18 @@ impl ProcessTool {
             security,
             runtime,
             syscall_detector,
+            sandbox: None,
             processes: Arc::new(RwLock::new(HashMap::new())),
             next_id: Mutex::new(0),
         }
     }
 
+    /// Apply `sandbox` to every process this tool spawns.
+    pub fn with_sandbox(mut self, sandbox: Option<Arc<dyn Sandbox>>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
     fn handle_spawn(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.runtime.supports_long_running() {
             return Ok(ToolResult {
@@ -157,6 +167,18 @@ impl ProcessTool {
             }
         };
 
+        // Wrap before the environment is reset so wrapper launchers start
+        // with the same scrubbed environment the command would have had.
+        if let Some(sandbox) = &self.sandbox {
+            if let Err(e) = sandbox.wrap_command(cmd.as_std_mut()) {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to apply {} sandbox: {e}", sandbox.name())),
+                });
+            }
+        }
+
         cmd.stdin(Stdio::null());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -208,6 +230,7 @@ impl ProcessTool {
             stdout_buf,
             stderr_buf,
             analyzed_offsets: Mutex::new((0, 0)),
+            violation_reported: AtomicBool::new(false),
         };
 
         self.processes.write().unwrap().insert(id, entry);
@@ -296,7 +319,8 @@ impl ProcessTool {
         let stdout_snapshot = snapshot_output_buffer(&entry.stdout_buf);
         let stderr_snapshot = snapshot_output_buffer(&entry.stderr_buf);
         let stdout = stdout_snapshot.data;
-        let stderr = stderr_snapshot.data;
+        let mut stderr = stderr_snapshot.data;
+        let violation = self.sandbox_violation(entry);
 
         if let Some(detector) = &self.syscall_detector {
             let mut offsets = entry.analyzed_offsets.lock().unwrap();
@@ -319,6 +343,18 @@ impl ProcessTool {
                     None,
                 );
             }
+            if let Some(report) = &violation {
+                if !entry.violation_reported.swap(true, Ordering::Relaxed) {
+                    let _ = detector.inspect_command_output(&entry.command, "", report, None);
+                }
+            }
+        }
+
+        if let Some(report) = violation {
+            if !stderr.is_empty() {
+                stderr.push('\n');
+            }
+            stderr.push_str(&report);
         }
 
         Ok(ToolResult {
@@ -332,6 +368,13 @@ impl ProcessTool {
         })
     }
 
+    /// Violation report for an entry the sandbox killed, if any.
+    fn sandbox_violation(&self, entry: &ProcessEntry) -> Option<String> {
+        let sandbox = self.sandbox.as_ref()?;
+        let status = entry.child.lock().ok()?.try_wait().ok()??;
+        sandbox.describe_violation(status)
+    }
+
     fn handle_kill(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
         if let Err(e) = self
             .security
@@ -1,15 +1,21 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolContext, ToolResult};
 use crate::runtime::RuntimeAdapter;
 use crate::security::SecurityPolicy;
-use crate::security::SyscallAnomalyDetector;
+use crate::security::{Sandbox, SyscallAnomalyDetector};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashSet;
-use std::sync::Arc;
-use std::time::Duration;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 /// Maximum shell command execution time before kill.
 const SHELL_TIMEOUT_SECS: u64 = 60;
+/// Interval between progress updates for a running command.
+const SHELL_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+/// How long to wait for output pipes to drain after the child is killed.
+const SHELL_DRAIN_GRACE: Duration = Duration::from_secs(1);
 /// Maximum output size in bytes (1MB).
 const MAX_OUTPUT_BYTES: usize = 1_048_576;
 /// Environment variables safe to pass to shell commands.
@@ -29,11 +35,114 @@ fn truncate_utf8_to_max_bytes(text: &mut String, max_bytes: usize) {
     text.truncate(cutoff);
 }
 
+/// Why a shell child stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShellStop {
+    Exited(std::process::ExitStatus),
+    TimedOut,
+    Cancelled,
+}
+
+/// Read `pipe` to EOF into `buffer`, keeping at most `MAX_OUTPUT_BYTES + 1`
+/// bytes so callers can still detect and mark truncation.
+async fn drain_pipe<R: AsyncRead + Unpin>(mut pipe: R, buffer: Arc<Mutex<Vec<u8>>>) {
+    let mut chunk = [0_u8; 8192];
+    loop {
+        match pipe.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                let mut buf = buffer.lock().unwrap_or_else(|e| e.into_inner());
+                let room = (MAX_OUTPUT_BYTES + 1).saturating_sub(buf.len());
+                buf.extend_from_slice(&chunk[..n.min(room)]);
+            }
+        }
+    }
+}
+
+/// Spawn `cmd` and wait for it to exit, time out, or be cancelled through
+/// `ctx`. Output gathered up to that point is returned in every case.
+async fn run_shell_child(
+    mut cmd: tokio::process::Command,
+    ctx: &ToolContext,
+) -> std::io::Result<(ShellStop, Vec<u8>, Vec<u8>)> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd.spawn()?;
+
+    let stdout_buf = Arc::new(Mutex::new(Vec::new()));
+    let stderr_buf = Arc::new(Mutex::new(Vec::new()));
+    let mut readers = Vec::new();
+    if let Some(pipe) = child.stdout.take() {
+        readers.push(tokio::spawn(drain_pipe(pipe, Arc::clone(&stdout_buf))));
+    }
+    if let Some(pipe) = child.stderr.take() {
+        readers.push(tokio::spawn(drain_pipe(pipe, Arc::clone(&stderr_buf))));
+    }
+
+    let started = Instant::now();
+    let deadline = tokio::time::sleep(Duration::from_secs(SHELL_TIMEOUT_SECS));
+    tokio::pin!(deadline);
+    let mut ticker = tokio::time::interval_at(
+        tokio::time::Instant::now() + SHELL_PROGRESS_INTERVAL,
+        SHELL_PROGRESS_INTERVAL,
+    );
+
+    let stop = loop {
+        tokio::select! {
+            status = child.wait() => break ShellStop::Exited(status?),
+            () = &mut deadline => break ShellStop::TimedOut,
+            () = ctx.cancelled() => break ShellStop::Cancelled,
+            _ = ticker.tick() => {
+                let captured = stdout_buf.lock().map(|buf| buf.len()).unwrap_or_default();
+                ctx.progress.report(format!(
+                    "running for {}s, {captured} bytes of output so far",
+                    started.elapsed().as_secs()
+                ));
+            }
+        }
+    };
+
+    if !matches!(stop, ShellStop::Exited(_)) {
+        let _ = child.kill().await;
+    }
+    for reader in readers {
+        // Background grandchildren can hold the pipes open; don't wait on them.
+        let abort = reader.abort_handle();
+        if tokio::time::timeout(SHELL_DRAIN_GRACE, reader).await.is_err() {
+            abort.abort();
+        }
+    }
+
+    let take = |buffer: &Arc<Mutex<Vec<u8>>>| {
+        std::mem::take(&mut *buffer.lock().unwrap_or_else(|e| e.into_inner()))
+    };
+    Ok((stop, take(&stdout_buf), take(&stderr_buf)))
+}
+
+fn render_captured_output(stdout: &[u8], stderr: &[u8]) -> (String, String) {
+    let mut stdout = String::from_utf8_lossy(stdout).to_string();
+    let mut stderr = String::from_utf8_lossy(stderr).to_string();
+
+    // Truncate output to prevent OOM
+    if stdout.len() > MAX_OUTPUT_BYTES {
+        truncate_utf8_to_max_bytes(&mut stdout, MAX_OUTPUT_BYTES);
+        stdout.push_str("\n... [output truncated at 1MB]");
+    }
+    if stderr.len() > MAX_OUTPUT_BYTES {
+        truncate_utf8_to_max_bytes(&mut stderr, MAX_OUTPUT_BYTES);
+        stderr.push_str("\n... [stderr truncated at 1MB]");
+    }
+    (stdout, stderr)
+}
+
 /// Shell command execution tool with sandboxing
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
     syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
+    sandbox: Option<Arc<dyn Sandbox>>,
 }
 
 impl ShellTool {
@@ -50,8 +159,15 @@ impl ShellTool {
             security,
             runtime,
             syscall_detector,
+            sandbox: None,
         }
     }
+
+    /// Apply `sandbox` to every command this tool spawns.
+    pub fn with_sandbox(mut self, sandbox: Option<Arc<dyn Sandbox>>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
 }
 
 fn is_valid_env_var_name(name: &str) -> bool {
@@ -63,7 +179,7 @@ fn is_valid_env_var_name(name: &str) -> bool {
     chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
 }
 
-pub(super) fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
+pub(crate) fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
     let mut out = Vec::new();
     let mut seen = HashSet::new();
     for key in SAFE_ENV_VARS
@@ -152,8 +268,17 @@ impl Tool for ShellTool {
         })
     }
 
-    #[allow(clippy::incompatible_msrv)]
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.execute_with_context(args, &ToolContext::default())
+            .await
+    }
+
+    #[allow(clippy::incompatible_msrv)]
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
         let command = extract_command_argument(&args)
             .ok_or_else(|| anyhow::anyhow!("Missing 'command' parameter"))?;
         let approved = args
@@ -212,6 +337,19 @@ impl Tool for ShellTool {
                 });
             }
         };
+
+        // Wrap before the environment is reset so wrapper launchers start
+        // with the same scrubbed environment the command would have had.
+        if let Some(sandbox) = &self.sandbox {
+            if let Err(e) = sandbox.wrap_command(cmd.as_std_mut()) {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to apply {} sandbox: {e}", sandbox.name())),
+                });
+            }
+        }
+
         cmd.env_clear();
 
         for var in collect_allowed_shell_env_vars(&self.security) {
@@ -220,35 +358,27 @@ impl Tool for ShellTool {
             }
         }
 
-        let result =
-            tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
-
-        match result {
-            Ok(Ok(output)) => {
-                let mut stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let mut stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-                // Truncate output to prevent OOM
-                if stdout.len() > MAX_OUTPUT_BYTES {
-                    truncate_utf8_to_max_bytes(&mut stdout, MAX_OUTPUT_BYTES);
-                    stdout.push_str("\n... [output truncated at 1MB]");
-                }
-                if stderr.len() > MAX_OUTPUT_BYTES {
-                    truncate_utf8_to_max_bytes(&mut stderr, MAX_OUTPUT_BYTES);
-                    stderr.push_str("\n... [stderr truncated at 1MB]");
+        match run_shell_child(cmd, ctx).await {
+            Ok((ShellStop::Exited(status), stdout, stderr)) => {
+                let (stdout, mut stderr) = render_captured_output(&stdout, &stderr);
+                if let Some(report) = self
+                    .sandbox
+                    .as_ref()
+                    .and_then(|sandbox| sandbox.describe_violation(status))
+                {
+                    if !stderr.is_empty() {
+                        stderr.push('\n');
+                    }
+                    stderr.push_str(&report);
                 }
 
                 if let Some(detector) = &self.syscall_detector {
-                    let _ = detector.inspect_command_output(
-                        &command,
-                        &stdout,
-                        &stderr,
-                        output.status.code(),
-                    );
+                    let _ =
+                        detector.inspect_command_output(&command, &stdout, &stderr, status.code());
                 }
 
                 Ok(ToolResult {
-                    success: output.status.success(),
+                    success: status.success(),
                     output: stdout,
                     error: if stderr.is_empty() {
                         None
@@ -257,18 +387,26 @@ impl Tool for ShellTool {
                     },
                 })
             }
-            Ok(Err(e)) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to execute command: {e}")),
-            }),
-            Err(_) => Ok(ToolResult {
+            Ok((ShellStop::Cancelled, stdout, stderr)) => {
+                let (mut stdout, stderr) = render_captured_output(&stdout, &stderr);
+                if !stderr.is_empty() {
+                    stdout.push_str("\n[stderr]\n");
+                    stdout.push_str(&stderr);
+                }
+                Ok(ToolResult::cancelled(stdout))
+            }
+            Ok((ShellStop::TimedOut, stdout, _)) => Ok(ToolResult {
                 success: false,
-                output: String::new(),
+                output: render_captured_output(&stdout, &[]).0,
                 error: Some(format!(
                     "Command timed out after {SHELL_TIMEOUT_SECS}s and was killed"
                 )),
             }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to execute command: {e}")),
+            }),
         }
     }
 }
@@ -508,6 +646,33 @@ mod tests {
         })
     }
 
+    #[tokio::test]
+    async fn shell_cancellation_keeps_partial_output() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["echo".into(), "sleep".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+        let ctx = ToolContext::default();
+        let token = ctx.cancellation.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            token.cancel();
+        });
+
+        let started = Instant::now();
+        let result = tool
+            .execute_with_context(json!({"command": "echo partial-line; sleep 30"}), &ctx)
+            .await
+            .expect("cancelled command should return a result");
+
+        assert!(result.is_cancelled());
+        assert!(result.output.contains("partial-line"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
     fn test_security_with_env_passthrough(vars: &[&str]) -> Arc<SecurityPolicy> {
         Arc::new(SecurityPolicy {
             autonomy: AutonomyLevel::Supervised,
@@ -804,4 +969,83 @@ mod tests {
         assert!(log.contains("\"kind\":\"unknown_syscall\""));
         assert!(log.contains("\"syscall\":\"openat\""));
     }
+
+    /// Sandbox stub that marks wrapped commands and reports every non-zero
+    /// exit as a violation.
+    struct MarkingSandbox;
+
+    impl Sandbox for MarkingSandbox {
+        fn wrap_command(&self, cmd: &mut std::process::Command) -> std::io::Result<()> {
+            let mut wrapped = std::process::Command::new("env");
+            wrapped
+                .arg("ZEROCLAW_TEST_SANDBOX=1")
+                .arg(cmd.get_program())
+                .args(cmd.get_args());
+            if let Some(dir) = cmd.get_current_dir() {
+                wrapped.current_dir(dir);
+            }
+            *cmd = wrapped;
+            Ok(())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            "marking"
+        }
+
+        fn description(&self) -> &str {
+            "test sandbox"
+        }
+
+        fn describe_violation(&self, status: std::process::ExitStatus) -> Option<String> {
+            (!status.success()).then(|| "seccomp: bad system call (SIGSYS) denied".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn shell_sandbox_violation_feeds_syscall_detector() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let log_path = tmp.path().join("shell-syscall-anomalies.log");
+        let detector = Arc::new(SyscallAnomalyDetector::new(
+            SyscallAnomalyConfig {
+                strict_mode: true,
+                log_path: log_path.to_string_lossy().to_string(),
+                ..SyscallAnomalyConfig::default()
+            },
+            tmp.path(),
+            AuditConfig {
+                enabled: false,
+                ..AuditConfig::default()
+            },
+        ));
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["env".into(), "ls".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new_with_syscall_detector(security, test_runtime(), Some(detector))
+            .with_sandbox(Some(Arc::new(MarkingSandbox)));
+
+        let marked = tool
+            .execute(json!({"command": "env"}))
+            .await
+            .expect("command execution should return result");
+        assert!(marked.output.contains("ZEROCLAW_TEST_SANDBOX=1"));
+
+        let result = tool
+            .execute(json!({"command": "ls definitely-missing-zeroclaw-path"}))
+            .await
+            .expect("command execution should return result");
+        assert!(!result.success);
+        assert!(result.error.as_deref().unwrap_or("").contains("SIGSYS"));
+
+        let log = tokio::fs::read_to_string(&log_path)
+            .await
+            .expect("syscall anomaly log should be written");
+        assert!(log.contains("\"kind\":\"denied_syscall\""));
+    }
 }
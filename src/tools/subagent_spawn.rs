@@ -5,7 +5,7 @@
 //! See `AGENTS.md` §7.3 for the tool change playbook.
 
 use super::subagent_registry::{SubAgentRegistry, SubAgentSession, SubAgentStatus};
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolContext, ToolResult};
 use crate::config::DelegateAgentConfig;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
@@ -375,6 +375,14 @@ impl Tool for ToolArcRef {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        self.inner.execute_with_context(args, ctx).await
+    }
 }
 
 struct NoopObserver;
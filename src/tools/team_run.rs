@@ -0,0 +1,355 @@
+//! `team_run` — hand a goal to a configured agent team.
+//!
+//! The team's supervisor plans the goal into subtasks for its roles, runs
+//! them and merges the results; see [`crate::agent::team`].
+
+use super::traits::{Tool, ToolContext, ToolResult};
+use crate::agent::team::TeamRunner;
+use crate::config::schema::ModelPricing;
+use crate::config::{MultimodalConfig, TeamConfig};
+use crate::coordination::MessageBus;
+use crate::cost::CostTracker;
+use crate::providers::ProviderRuntimeOptions;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+pub struct TeamRunTool {
+    teams: HashMap<String, TeamConfig>,
+    description: String,
+    security: Arc<SecurityPolicy>,
+    fallback_credential: Option<String>,
+    provider_runtime_options: ProviderRuntimeOptions,
+    parent_tools: Arc<Vec<Arc<dyn Tool>>>,
+    multimodal_config: MultimodalConfig,
+    coordination_bus: MessageBus,
+    cost_tracker: Option<Arc<CostTracker>>,
+    cost_prices: HashMap<String, ModelPricing>,
+}
+
+impl TeamRunTool {
+    pub fn new(
+        teams: HashMap<String, TeamConfig>,
+        fallback_credential: Option<String>,
+        security: Arc<SecurityPolicy>,
+        provider_runtime_options: ProviderRuntimeOptions,
+    ) -> Self {
+        let mut names: Vec<&String> = teams.keys().collect();
+        names.sort();
+        let mut description = String::from(
+            "Hand a goal to an agent team. The team's supervisor splits it into subtasks for \
+             specialised roles, runs them and returns one merged answer. Use for work that \
+             needs several kinds of expertise. Teams:",
+        );
+        for name in names {
+            let _ = write!(description, "\n- {name}: {}", teams[name].description);
+        }
+        Self {
+            teams,
+            description,
+            security,
+            fallback_credential,
+            provider_runtime_options,
+            parent_tools: Arc::new(Vec::new()),
+            multimodal_config: MultimodalConfig::default(),
+            coordination_bus: MessageBus::new(),
+            cost_tracker: None,
+            cost_prices: HashMap::new(),
+        }
+    }
+
+    /// Attach parent tools used to build role allowlist registries.
+    pub fn with_parent_tools(mut self, parent_tools: Arc<Vec<Arc<dyn Tool>>>) -> Self {
+        self.parent_tools = parent_tools;
+        self
+    }
+
+    /// Attach multimodal configuration for agentic role loops.
+    pub fn with_multimodal_config(mut self, config: MultimodalConfig) -> Self {
+        self.multimodal_config = config;
+        self
+    }
+
+    /// Carry team traffic on a shared coordination bus.
+    pub fn with_coordination_bus(mut self, bus: MessageBus) -> Self {
+        self.coordination_bus = bus;
+        self
+    }
+
+    /// Record team spend on `tracker` and enforce team budgets against it.
+    pub fn with_cost_tracker(
+        mut self,
+        tracker: Arc<CostTracker>,
+        prices: HashMap<String, ModelPricing>,
+    ) -> Self {
+        self.cost_tracker = Some(tracker);
+        self.cost_prices = prices;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for TeamRunTool {
+    fn name(&self) -> &str {
+        "team_run"
+    }
+
+    // A team run fans out into several sub-agent loops.
+    fn safe_for_slm(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let mut names: Vec<&str> = self.teams.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "team": {
+                    "type": "string",
+                    "enum": names,
+                    "description": "Name of the team to run"
+                },
+                "goal": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "What the team should achieve, with any context it needs"
+                }
+            },
+            "required": ["team", "goal"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.execute_with_context(args, &ToolContext::default())
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        let team_name = args
+            .get("team")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'team' parameter"))?;
+        let goal = args
+            .get("goal")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|goal| !goal.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'goal' parameter"))?;
+
+        let Some(team) = self.teams.get(team_name) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Unknown team '{team_name}'")),
+            });
+        };
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "team_run")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        ctx.progress
+            .report(format!("team '{team_name}' is planning"));
+        let mut runner = TeamRunner::new(
+            team_name,
+            team.clone(),
+            self.fallback_credential.clone(),
+            self.security.clone(),
+            self.provider_runtime_options.clone(),
+        )
+        .with_parent_tools(self.parent_tools.clone())
+        .with_multimodal_config(self.multimodal_config.clone())
+        .with_coordination_bus(self.coordination_bus.clone());
+        if let Some(tracker) = &self.cost_tracker {
+            runner = runner.with_cost_tracker(tracker.clone(), self.cost_prices.clone());
+        }
+
+        let report = match runner.run(goal, ctx).await {
+            Ok(report) => report,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Team '{team_name}' failed: {error:#}")),
+                })
+            }
+        };
+
+        let succeeded = report.outcomes.iter().filter(|o| o.success).count();
+        let mut output = format!(
+            "[Team '{team_name}' run {}: {succeeded}/{} subtasks succeeded",
+            report.run_id,
+            report.outcomes.len()
+        );
+        if let Some(spent) = report.spent_usd {
+            let _ = write!(output, ", ${spent:.4} spent");
+        }
+        if let Some(reason) = &report.stopped {
+            let _ = write!(output, ", stopped: {reason}");
+        }
+        output.push_str("]\n");
+        output.push_str(&report.answer);
+
+        Ok(ToolResult {
+            success: report.stopped.is_none() && succeeded > 0,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    /// OpenAI-compatible endpoint playing the supervisor (`plan`, then a
+    /// merged answer) and the roles.
+    async fn llm(plan: &'static str) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(move |request: &Request| {
+                let body = String::from_utf8_lossy(&request.body);
+                let reply = if body.contains("Combine the results") {
+                    "merged answer"
+                } else if body.contains("Break the goal") {
+                    plan
+                } else {
+                    "role output"
+                };
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "choices": [{ "message": { "role": "assistant", "content": reply } }]
+                }))
+            })
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn teams(provider: &str) -> HashMap<String, TeamConfig> {
+        let agent = format!("provider = \"{provider}\"\nmodel = \"mock\"\napi_key = \"test-key\"");
+        let team: TeamConfig = toml::from_str(&format!(
+            "description = \"Writes documentation\"\n\
+             [supervisor]\n{agent}\n\
+             [roles.writer]\n{agent}\ndescription = \"Writes prose\"\n"
+        ))
+        .unwrap();
+        HashMap::from([("docs".to_string(), team)])
+    }
+
+    fn tool(teams: HashMap<String, TeamConfig>, security: SecurityPolicy) -> TeamRunTool {
+        TeamRunTool::new(
+            teams,
+            None,
+            Arc::new(security),
+            ProviderRuntimeOptions::default(),
+        )
+    }
+
+    #[test]
+    fn schema_and_description_list_configured_teams() {
+        let tool = tool(teams("ollama"), SecurityPolicy::default());
+        assert_eq!(tool.name(), "team_run");
+        assert!(tool.description().contains("- docs: Writes documentation"));
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["properties"]["team"]["enum"], json!(["docs"]));
+        assert_eq!(schema["required"], json!(["team", "goal"]));
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_goal_and_unknown_team() {
+        let tool = tool(teams("ollama"), SecurityPolicy::default());
+        assert!(tool
+            .execute(json!({ "team": "docs", "goal": "  " }))
+            .await
+            .is_err());
+
+        let result = tool
+            .execute(json!({ "team": "legal", "goal": "review" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("Unknown team 'legal'"));
+    }
+
+    #[tokio::test]
+    async fn blocked_in_readonly_mode() {
+        let readonly = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let result = tool(teams("ollama"), readonly)
+            .execute(json!({ "team": "docs", "goal": "write the docs" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn runs_the_team_and_returns_the_merged_answer() {
+        let server = llm(r#"[{"id": "draft", "role": "writer", "task": "draft the docs"}]"#).await;
+        let bus = MessageBus::new();
+        let tool = tool(
+            teams(&format!("custom:{}", server.uri())),
+            SecurityPolicy::default(),
+        )
+        .with_coordination_bus(bus.clone());
+
+        let result = tool
+            .execute(json!({ "team": "docs", "goal": "write the docs" }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("[Team 'docs' run "));
+        assert!(result
+            .output
+            .ends_with("1/1 subtasks succeeded]\nmerged answer"));
+        // The run's traffic went over the shared bus.
+        assert!(bus.registered_agents().contains(&"docs.writer".to_string()));
+        assert!(bus.context_count() > 0);
+    }
+
+    #[tokio::test]
+    async fn reports_a_failed_plan() {
+        let server = llm("I would rather not plan.").await;
+        let result = tool(
+            teams(&format!("custom:{}", server.uri())),
+            SecurityPolicy::default(),
+        )
+        .execute(json!({ "team": "docs", "goal": "write the docs" }))
+        .await
+        .unwrap();
+
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.starts_with("Team 'docs' failed"), "{error}");
+        assert!(error.contains("not a JSON array"), "{error}");
+    }
+}
@@ -1,5 +1,11 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// Error string carried by [`ToolResult::cancelled`].
+pub const TOOL_CANCELLED_ERROR: &str = "Cancelled by user";
 
 /// Result of a tool execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +15,102 @@ pub struct ToolResult {
     pub error: Option<String>,
 }
 
+impl ToolResult {
+    /// Result for a tool that stopped because its turn was cancelled.
+    /// `partial_output` keeps whatever the tool produced before stopping.
+    pub fn cancelled(partial_output: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            output: partial_output.into(),
+            error: Some(TOOL_CANCELLED_ERROR.to_string()),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        !self.success && self.error.as_deref() == Some(TOOL_CANCELLED_ERROR)
+    }
+}
+
+/// Incremental progress update emitted by a long-running tool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolProgress {
+    pub message: String,
+    /// Completion fraction in `0.0..=1.0`, when the tool can estimate it.
+    pub fraction: Option<f32>,
+}
+
+/// Destination for [`ToolProgress`] updates. Cloning is cheap; the default
+/// sink discards every update.
+#[derive(Clone, Default)]
+pub struct ToolProgressSink {
+    callback: Option<Arc<dyn Fn(ToolProgress) + Send + Sync>>,
+}
+
+impl ToolProgressSink {
+    pub fn new(callback: impl Fn(ToolProgress) + Send + Sync + 'static) -> Self {
+        Self {
+            callback: Some(Arc::new(callback)),
+        }
+    }
+
+    pub fn report(&self, message: impl Into<String>) {
+        self.emit(ToolProgress {
+            message: message.into(),
+            fraction: None,
+        });
+    }
+
+    /// Report `done` out of `total` units of work.
+    pub fn report_step(&self, message: impl Into<String>, done: usize, total: usize) {
+        #[allow(clippy::cast_precision_loss)]
+        let fraction = (total > 0).then(|| (done.min(total) as f32) / (total as f32));
+        self.emit(ToolProgress {
+            message: message.into(),
+            fraction,
+        });
+    }
+
+    fn emit(&self, progress: ToolProgress) {
+        if let Some(callback) = &self.callback {
+            callback(progress);
+        }
+    }
+}
+
+impl fmt::Debug for ToolProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolProgressSink")
+            .field("attached", &self.callback.is_some())
+            .finish()
+    }
+}
+
+/// Per-call context handed to [`Tool::execute_with_context`]: the turn's
+/// cancellation token and a sink for progress updates.
+#[derive(Debug, Clone, Default)]
+pub struct ToolContext {
+    pub cancellation: CancellationToken,
+    pub progress: ToolProgressSink,
+}
+
+impl ToolContext {
+    pub fn new(cancellation: CancellationToken, progress: ToolProgressSink) -> Self {
+        Self {
+            cancellation,
+            progress,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Resolves once the turn owning this call is cancelled.
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await;
+    }
+}
+
 /// Description of a tool for the LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolSpec {
@@ -32,6 +134,24 @@ pub trait Tool: Send + Sync {
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
+    /// Execute with a cancellation token and progress sink.
+    ///
+    /// The agent loop always calls this entry point. The default races
+    /// [`Tool::execute`] against cancellation and drops the in-flight work
+    /// when the turn is stopped. Long-running tools should override it to
+    /// stop cooperatively and return [`ToolResult::cancelled`] carrying the
+    /// output they gathered so far.
+    async fn execute_with_context(
+        &self,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> anyhow::Result<ToolResult> {
+        tokio::select! {
+            () = ctx.cancelled() => Ok(ToolResult::cancelled(String::new())),
+            result = self.execute(args) => result,
+        }
+    }
+
     /// Get the full spec for LLM registration
     fn spec(&self) -> ToolSpec {
         ToolSpec {
@@ -120,6 +240,57 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    struct SlowTool;
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn name(&self) -> &str {
+            "slow_tool"
+        }
+
+        fn description(&self) -> &str {
+            "Never finishes on its own"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn default_execute_with_context_stops_on_cancellation() {
+        let ctx = ToolContext::default();
+        ctx.cancellation.cancel();
+
+        let result = SlowTool
+            .execute_with_context(serde_json::json!({}), &ctx)
+            .await
+            .unwrap();
+
+        assert!(result.is_cancelled());
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn progress_sink_forwards_updates() {
+        let seen = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&seen);
+        let sink = ToolProgressSink::new(move |progress| captured.lock().push(progress));
+
+        sink.report("starting");
+        sink.report_step("halfway", 1, 2);
+        ToolProgressSink::default().report("dropped");
+
+        let seen = seen.lock();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].fraction, None);
+        assert_eq!(seen[1].fraction, Some(0.5));
+    }
+
     #[test]
     fn tool_result_serialization_roundtrip() {
         let result = ToolResult {
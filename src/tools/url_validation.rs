@@ -149,7 +149,7 @@ fn is_non_global_ip(ip: IpAddr) -> bool {
     }
 }
 
-fn parse_cidr(raw: &str) -> anyhow::Result<(IpAddr, u8)> {
+pub(crate) fn parse_cidr(raw: &str) -> anyhow::Result<(IpAddr, u8)> {
     let (ip_raw, prefix_raw) = raw
         .trim()
         .split_once('/')
@@ -172,7 +172,7 @@ fn parse_cidr(raw: &str) -> anyhow::Result<(IpAddr, u8)> {
     Ok((ip, prefix))
 }
 
-fn cidr_contains_ip(cidr: (IpAddr, u8), ip: IpAddr) -> bool {
+pub(crate) fn cidr_contains_ip(cidr: (IpAddr, u8), ip: IpAddr) -> bool {
     match (cidr.0, ip) {
         (IpAddr::V4(net), IpAddr::V4(candidate)) => {
             let net_u32 = u32::from(net);
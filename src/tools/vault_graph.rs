@@ -76,7 +76,7 @@ impl Tool for LegalGraphNeighborsTool {
         let depth = arg_int(&args, "depth", 1, 1, 3);
         let kinds = arg_kinds(&args);
 
-        let workspace = self.workspace_dir.clone();
+        let workspace = crate::tenancy::workspace_dir(&self.workspace_dir);
         let result = tokio::task::spawn_blocking(move || -> Result<Subgraph> {
             let conn = open_brain_db(&workspace)?;
             graph_query::neighbors(&conn, &node, depth, &kinds)
@@ -176,7 +176,7 @@ impl Tool for LegalGraphShortestPathTool {
         let from = arg_str(&args, "from")?;
         let to = arg_str(&args, "to")?;
         let max_depth = arg_int(&args, "max_depth", 4, 1, 6);
-        let workspace = self.workspace_dir.clone();
+        let workspace = crate::tenancy::workspace_dir(&self.workspace_dir);
 
         let path = tokio::task::spawn_blocking(move || -> Result<Option<Vec<String>>> {
             let conn = open_brain_db(&workspace)?;
@@ -243,7 +243,7 @@ impl Tool for LegalGraphSubgraphTool {
         let node = arg_str(&args, "node")?;
         let depth = arg_int(&args, "depth", 1, 1, 3);
         let kinds = arg_kinds(&args);
-        let workspace = self.workspace_dir.clone();
+        let workspace = crate::tenancy::workspace_dir(&self.workspace_dir);
 
         let sg = tokio::task::spawn_blocking(move || -> Result<Subgraph> {
             let conn = open_brain_db(&workspace)?;
@@ -304,7 +304,7 @@ impl Tool for LegalGraphFindTool {
     async fn execute(&self, args: Value) -> Result<ToolResult> {
         let query = arg_str(&args, "query")?;
         let limit = arg_int(&args, "limit", 5, 1, 20);
-        let workspace = self.workspace_dir.clone();
+        let workspace = crate::tenancy::workspace_dir(&self.workspace_dir);
 
         let hits = tokio::task::spawn_blocking(move || -> Result<Vec<FindHit>> {
             let conn = open_brain_db(&workspace)?;
@@ -389,7 +389,7 @@ impl Tool for LegalReadArticleTool {
                     .collect()
             })
             .unwrap_or_default();
-        let workspace = self.workspace_dir.clone();
+        let workspace = crate::tenancy::workspace_dir(&self.workspace_dir);
         let slug_for_err = slug.clone();
 
         let article = tokio::task::spawn_blocking(move || -> Result<Option<ArticleContent>> {
@@ -581,7 +581,7 @@ Returns the chosen supplement slug, its effective_date / promulgation info, the
             .and_then(Value::as_str)
             .filter(|s| s.len() == 8)
             .map(str::to_string);
-        let workspace = self.workspace_dir.clone();
+        let workspace = crate::tenancy::workspace_dir(&self.workspace_dir);
 
         let decision = tokio::task::spawn_blocking(move || -> Result<ApplicableVersion> {
             let conn = open_brain_db(&workspace)?;
@@ -1221,6 +1221,7 @@ impl Tool for WebSearchTool {
             while attempt < retry_attempts {
                 match self.search_with_provider(provider, query).await {
                     Ok(output) => {
+                        crate::cost::record_metered(crate::cost::CostKind::Search, provider, 1.0);
                         result = Some(output);
                         success = true;
                         break;
@@ -0,0 +1,524 @@
+use super::docx_create::core_properties;
+use super::ooxml::{self, column_letters, xml_escape, Package};
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Excel's hard limits.
+const MAX_ROWS: usize = 1_048_576;
+const MAX_COLUMNS: usize = 16_384;
+const MAX_SHEET_NAME_CHARS: usize = 31;
+/// Upper bound on cells per workbook to keep generated packages reasonable.
+const MAX_CELLS: usize = 1_000_000;
+
+/// Cell style indices in `xl/styles.xml` (`cellXfs`).
+const STYLE_BOLD: u32 = 1;
+
+/// Create an Excel (.xlsx) workbook from structured sheet data.
+pub struct XlsxCreateTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl XlsxCreateTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct XlsxCreateArgs {
+    output_path: String,
+    #[serde(default)]
+    title: Option<String>,
+    sheets: Vec<SheetSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SheetSpec {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    rows: Vec<Vec<Value>>,
+    /// Bold the first row and freeze it while scrolling.
+    #[serde(default)]
+    header: bool,
+    /// Column widths in characters, starting at column A.
+    #[serde(default)]
+    column_widths: Vec<f64>,
+}
+
+/// A single cell after interpreting the JSON value.
+#[derive(Debug, PartialEq)]
+enum Cell {
+    Empty,
+    Text(String),
+    Number(String),
+    Bool(bool),
+    Formula(String),
+}
+
+impl Cell {
+    /// Strings starting with `=` and `{"formula": ...}` objects become formulas;
+    /// `{"value": ...}` wraps a literal (e.g. text that itself starts with `=`).
+    fn from_json(value: &Value) -> (Self, bool) {
+        match value {
+            Value::Null => (Self::Empty, false),
+            Value::Bool(b) => (Self::Bool(*b), false),
+            Value::Number(n) => (Self::Number(n.to_string()), false),
+            Value::String(s) => match s.strip_prefix('=') {
+                Some(formula) if !formula.is_empty() => (Self::Formula(formula.to_string()), false),
+                _ => (Self::Text(s.clone()), false),
+            },
+            Value::Object(map) => {
+                let bold = map.get("bold").and_then(Value::as_bool).unwrap_or(false);
+                let cell = if let Some(formula) = map.get("formula").and_then(Value::as_str) {
+                    Self::Formula(formula.trim_start_matches('=').to_string())
+                } else {
+                    match map.get("value") {
+                        Some(Value::String(s)) => Self::Text(s.clone()),
+                        Some(other) => Self::from_json(other).0,
+                        None => Self::Empty,
+                    }
+                };
+                (cell, bold)
+            }
+            Value::Array(_) => (Self::Text(value.to_string()), false),
+        }
+    }
+}
+
+/// Deduplicating shared string table.
+#[derive(Default)]
+struct SharedStrings {
+    strings: Vec<String>,
+    index: HashMap<String, usize>,
+    references: usize,
+}
+
+impl SharedStrings {
+    fn intern(&mut self, text: &str) -> usize {
+        self.references += 1;
+        if let Some(&idx) = self.index.get(text) {
+            return idx;
+        }
+        let idx = self.strings.len();
+        self.strings.push(text.to_string());
+        self.index.insert(text.to_string(), idx);
+        idx
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="{}" uniqueCount="{}">"#,
+            self.references,
+            self.strings.len()
+        );
+        for s in &self.strings {
+            let _ = write!(
+                xml,
+                r#"<si><t xml:space="preserve">{}</t></si>"#,
+                xml_escape(s)
+            );
+        }
+        xml.push_str("</sst>");
+        xml
+    }
+}
+
+/// Validate sheet names and fill in defaults (`Sheet1`, `Sheet2`, ...).
+fn sheet_names(sheets: &[SheetSpec]) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    let mut names = Vec::with_capacity(sheets.len());
+    for (i, sheet) in sheets.iter().enumerate() {
+        let name = sheet
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map_or_else(|| format!("Sheet{}", i + 1), str::to_string);
+        if name.chars().count() > MAX_SHEET_NAME_CHARS {
+            return Err(format!(
+                "Sheet name '{name}' exceeds {MAX_SHEET_NAME_CHARS} characters"
+            ));
+        }
+        if name.contains(['[', ']', ':', '*', '?', '/', '\\']) {
+            return Err(format!(
+                "Sheet name '{name}' contains a character Excel does not allow ([ ] : * ? / \\)"
+            ));
+        }
+        if !seen.insert(name.to_lowercase()) {
+            return Err(format!("Duplicate sheet name '{name}'"));
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+fn sheet_xml(sheet: &SheetSpec, strings: &mut SharedStrings) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+    );
+    if sheet.header && !sheet.rows.is_empty() {
+        xml.push_str(
+            r#"<sheetViews><sheetView workbookViewId="0"><pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/></sheetView></sheetViews>"#,
+        );
+    }
+    if !sheet.column_widths.is_empty() {
+        xml.push_str("<cols>");
+        for (i, width) in sheet.column_widths.iter().enumerate() {
+            let width = width.clamp(0.0, 255.0);
+            let _ = write!(
+                xml,
+                r#"<col min="{n}" max="{n}" width="{width}" customWidth="1"/>"#,
+                n = i + 1
+            );
+        }
+        xml.push_str("</cols>");
+    }
+    xml.push_str("<sheetData>");
+    for (r, row) in sheet.rows.iter().enumerate() {
+        let row_num = r + 1;
+        let _ = write!(xml, r#"<row r="{row_num}">"#);
+        for (c, value) in row.iter().enumerate() {
+            let (cell, bold) = Cell::from_json(value);
+            let reference = format!("{}{row_num}", column_letters(c + 1));
+            let style = if bold || (sheet.header && r == 0) {
+                format!(r#" s="{STYLE_BOLD}""#)
+            } else {
+                String::new()
+            };
+            match cell {
+                Cell::Empty if style.is_empty() => {}
+                Cell::Empty => {
+                    let _ = write!(xml, r#"<c r="{reference}"{style}/>"#);
+                }
+                Cell::Text(text) => {
+                    let idx = strings.intern(&text);
+                    let _ = write!(xml, r#"<c r="{reference}"{style} t="s"><v>{idx}</v></c>"#);
+                }
+                Cell::Number(n) => {
+                    let _ = write!(xml, r#"<c r="{reference}"{style}><v>{n}</v></c>"#);
+                }
+                Cell::Bool(b) => {
+                    let _ = write!(
+                        xml,
+                        r#"<c r="{reference}"{style} t="b"><v>{}</v></c>"#,
+                        u8::from(b)
+                    );
+                }
+                Cell::Formula(formula) => {
+                    // No cached value: the workbook requests a full recalculation on load.
+                    let _ = write!(
+                        xml,
+                        r#"<c r="{reference}"{style}><f>{}</f></c>"#,
+                        xml_escape(&formula)
+                    );
+                }
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn build_xlsx(
+    title: Option<&str>,
+    sheets: &[SheetSpec],
+    names: &[String],
+) -> anyhow::Result<Vec<u8>> {
+    let mut strings = SharedStrings::default();
+    let mut pkg = Package::new();
+
+    let mut content_types = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/><Override PartName="/xl/sharedStrings.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#,
+    );
+    let mut workbook = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>"#,
+    );
+    let mut rels = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    );
+    let mut sheet_parts = Vec::with_capacity(sheets.len());
+    for (i, (sheet, name)) in sheets.iter().zip(names).enumerate() {
+        let n = i + 1;
+        let _ = write!(
+            content_types,
+            r#"<Override PartName="/xl/worksheets/sheet{n}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#
+        );
+        let _ = write!(
+            workbook,
+            r#"<sheet name="{}" sheetId="{n}" r:id="rId{n}"/>"#,
+            xml_escape(name)
+        );
+        let _ = write!(
+            rels,
+            r#"<Relationship Id="rId{n}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{n}.xml"/>"#
+        );
+        sheet_parts.push((
+            format!("xl/worksheets/sheet{n}.xml"),
+            sheet_xml(sheet, &mut strings),
+        ));
+    }
+    content_types.push_str("</Types>");
+    workbook.push_str(r#"</sheets><calcPr calcId="191029" fullCalcOnLoad="1"/></workbook>"#);
+    let _ = write!(
+        rels,
+        r#"<Relationship Id="rIdStyles" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rIdStrings" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings" Target="sharedStrings.xml"/></Relationships>"#
+    );
+
+    pkg.add("[Content_Types].xml", content_types);
+    pkg.add("_rels/.rels", ROOT_RELS);
+    pkg.add("docProps/core.xml", core_properties(title));
+    pkg.add("xl/workbook.xml", workbook);
+    pkg.add("xl/_rels/workbook.xml.rels", rels);
+    pkg.add("xl/styles.xml", STYLES);
+    for (name, xml) in sheet_parts {
+        pkg.add(name, xml);
+    }
+    pkg.add("xl/sharedStrings.xml", strings.to_xml());
+    pkg.finish()
+}
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="2"><font><sz val="11"/><name val="Calibri"/><family val="2"/></font><font><b/><sz val="11"/><name val="Calibri"/><family val="2"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="2"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs><cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles></styleSheet>"#;
+
+fn error_result(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for XlsxCreateTool {
+    fn name(&self) -> &str {
+        "xlsx_create"
+    }
+
+    fn safe_for_slm(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &str {
+        "Create an Excel (.xlsx) workbook in the workspace from one or more sheets of rows. \
+         Cells may be text, numbers, booleans or formulas (strings starting with '=' or {\"formula\": \"SUM(B2:B9)\"}); \
+         formulas are recalculated when the workbook is opened."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "output_path": {
+                    "type": "string",
+                    "description": "Where to save the .xlsx file. Relative paths resolve from workspace."
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional workbook title stored in document properties"
+                },
+                "sheets": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string", "description": "Sheet name (max 31 chars, defaults to SheetN)" },
+                            "rows": {
+                                "type": "array",
+                                "description": "Rows of cells. A cell is a string, number, boolean, null, or {value|formula, bold}.",
+                                "items": { "type": "array" }
+                            },
+                            "header": { "type": "boolean", "description": "Bold and freeze the first row" },
+                            "column_widths": {
+                                "type": "array",
+                                "items": { "type": "number" },
+                                "description": "Column widths in characters, starting at column A"
+                            }
+                        }
+                    }
+                }
+            },
+            "required": ["output_path", "sheets"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let parsed: XlsxCreateArgs = serde_json::from_value(args)
+            .map_err(|e| anyhow::anyhow!("invalid xlsx_create arguments: {e}"))?;
+        if parsed.sheets.is_empty() {
+            return Ok(error_result("At least one sheet is required"));
+        }
+        let names = match sheet_names(&parsed.sheets) {
+            Ok(names) => names,
+            Err(e) => return Ok(error_result(e)),
+        };
+        let mut total_cells = 0usize;
+        for (sheet, name) in parsed.sheets.iter().zip(&names) {
+            if sheet.rows.len() > MAX_ROWS {
+                return Ok(error_result(format!(
+                    "Sheet '{name}' has {} rows (limit: {MAX_ROWS})",
+                    sheet.rows.len()
+                )));
+            }
+            if let Some(wide) = sheet.rows.iter().find(|r| r.len() > MAX_COLUMNS) {
+                return Ok(error_result(format!(
+                    "Sheet '{name}' has a row with {} cells (limit: {MAX_COLUMNS})",
+                    wide.len()
+                )));
+            }
+            total_cells += sheet.rows.iter().map(Vec::len).sum::<usize>();
+        }
+        if total_cells > MAX_CELLS {
+            return Ok(error_result(format!(
+                "Workbook has {total_cells} cells (limit: {MAX_CELLS})"
+            )));
+        }
+
+        let target =
+            match ooxml::resolve_output_path(&self.security, &parsed.output_path, "xlsx").await {
+                Ok(target) => target,
+                Err(e) => return Ok(error_result(e)),
+            };
+        let bytes = build_xlsx(parsed.title.as_deref(), &parsed.sheets, &names)?;
+        if let Err(e) = ooxml::write_output(&self.security, &target, &bytes).await {
+            return Ok(error_result(e));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Wrote XLSX workbook ({} sheets, {total_cells} cells, {} bytes) to {}",
+                names.len(),
+                bytes.len(),
+                parsed.output_path
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use crate::tools::xlsx_read::extract_xlsx_text_from_path;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn cell_interpretation() {
+        assert_eq!(
+            Cell::from_json(&json!("=SUM(A1:A2)")).0,
+            Cell::Formula("SUM(A1:A2)".into())
+        );
+        assert_eq!(
+            Cell::from_json(&json!({ "value": "=literal" })).0,
+            Cell::Text("=literal".into())
+        );
+        assert_eq!(
+            Cell::from_json(&json!({ "formula": "=A1*2", "bold": true })),
+            (Cell::Formula("A1*2".into()), true)
+        );
+        assert_eq!(Cell::from_json(&json!(1.5)).0, Cell::Number("1.5".into()));
+        assert_eq!(Cell::from_json(&json!("=")).0, Cell::Text("=".into()));
+    }
+
+    #[test]
+    fn sheet_names_are_validated() {
+        let spec = |name: &str| SheetSpec {
+            name: Some(name.into()),
+            rows: Vec::new(),
+            header: false,
+            column_widths: Vec::new(),
+        };
+        assert!(sheet_names(&[spec("a/b")]).is_err());
+        assert!(sheet_names(&[spec("Data"), spec("data")]).is_err());
+        assert!(sheet_names(&[spec(&"x".repeat(32))]).is_err());
+        assert_eq!(
+            sheet_names(&[spec(""), spec("Totals")]).unwrap(),
+            vec!["Sheet1", "Totals"]
+        );
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_xlsx_reader() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tool = XlsxCreateTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({
+                "output_path": "budget.xlsx",
+                "sheets": [
+                    {
+                        "name": "Budget",
+                        "header": true,
+                        "column_widths": [20, 12],
+                        "rows": [
+                            ["Item", "Cost"],
+                            ["Rent & utilities", 1200],
+                            ["Food", 350.5],
+                            ["Total", "=SUM(B2:B3)"],
+                            ["Approved", true]
+                        ]
+                    },
+                    { "name": "Notes", "rows": [["Food", "<shared>"]] }
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let path = tmp.path().join("budget.xlsx");
+        let text = extract_xlsx_text_from_path(&path).unwrap();
+        assert!(text.contains("--- Sheet: Budget ---"), "{text}");
+        assert!(text.contains("Item\tCost"), "{text}");
+        assert!(text.contains("Rent & utilities\t1200"), "{text}");
+        assert!(text.contains("Food\t350.5"), "{text}");
+        assert!(text.contains("Approved\tTRUE"), "{text}");
+        assert!(text.contains("--- Sheet: Notes ---"), "{text}");
+        assert!(text.contains("Food\t<shared>"), "{text}");
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let mut sheet = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("xl/worksheets/sheet1.xml").unwrap(),
+            &mut sheet,
+        )
+        .unwrap();
+        assert!(sheet.contains("<f>SUM(B2:B3)</f>"));
+        assert!(sheet.contains(r#"state="frozen""#));
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_extension() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tool = XlsxCreateTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({ "output_path": "data.csv", "sheets": [{ "rows": [[1]] }] }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains(".xlsx"));
+    }
+}
@@ -25,13 +25,31 @@
 //!
 //! Conference mode charges per-participant-minute to the room creator.
 //! Each active session consumes credits independently.
+//!
+//! ## Meeting record
+//!
+//! With [`ConferenceRoom::start_recording`] (or a [`ConferenceManager`] built
+//! with [`ConferenceManager::with_archiver`], which `[voice] record_meetings`
+//! does for the gateway) every source-language transcript is kept as a
+//! diarized, timestamped record; closing the room through the manager
+//! archives it and produces minutes (see [`super::meeting_minutes`]).
+//!
+//! A segment runs from the first sign of speech — [`ConferenceRoom::set_speaking`]
+//! or [`ConferenceRoom::mark_segment_start`] — to the transcript that
+//! commits it.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use super::local_stt::Transcript;
+use super::meeting_minutes::{MeetingArchiver, MeetingRecord, MeetingRecorder, MeetingTranscript};
 use super::pipeline::LanguageCode;
+use crate::config::Config;
+use crate::memory::Memory;
+use crate::ontology::OntologyRepo;
+use crate::providers::Provider;
 
 // ── Conference configuration ─────────────────────────────────────
 
@@ -53,6 +71,20 @@ pub struct ConferenceConfig {
     pub api_key: String,
 }
 
+impl ConferenceConfig {
+    /// A room with the default participant limit whose participants bring
+    /// their own voice sessions (and keys).
+    pub fn new(room_id: impl Into<String>, creator_user_id: impl Into<String>) -> Self {
+        Self {
+            room_id: room_id.into(),
+            creator_user_id: creator_user_id.into(),
+            max_participants: DEFAULT_MAX_PARTICIPANTS,
+            default_target_lang: LanguageCode::En,
+            api_key: String::new(),
+        }
+    }
+}
+
 // ── Participant ─────────────────────────────────────────────────
 
 /// A participant in a conference interpretation session.
@@ -101,6 +133,8 @@ pub struct ConferenceRoom {
     participants: Arc<Mutex<HashMap<String, ParticipantState>>>,
     /// Room status.
     status: Arc<Mutex<RoomStatus>>,
+    /// Meeting record, present while recording.
+    recorder: Arc<Mutex<Option<MeetingRecorder>>>,
 }
 
 impl std::fmt::Debug for ConferenceRoom {
@@ -119,6 +153,8 @@ struct ParticipantState {
     status: ParticipantStatus,
     /// Channel to send translated audio to this participant.
     audio_tx: tokio::sync::mpsc::Sender<ConferenceEvent>,
+    /// Recording offset (ms) where the participant's uncommitted segment began.
+    segment_start_ms: Option<u64>,
 }
 
 /// Conference room status.
@@ -200,6 +236,7 @@ impl ConferenceRoom {
             config,
             participants: Arc::new(Mutex::new(HashMap::new())),
             status: Arc::new(Mutex::new(RoomStatus::Waiting)),
+            recorder: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -260,10 +297,12 @@ impl ConferenceRoom {
                 info,
                 status: ParticipantStatus::Joined,
                 audio_tx: audio_tx.clone(),
+                segment_start_ms: None,
             },
         );
 
         // Notify all other participants
+        let join_display_name = display_name.clone();
         let join_event = ConferenceEvent::ParticipantJoined {
             participant_id: participant_id.clone(),
             display_name,
@@ -278,6 +317,9 @@ impl ConferenceRoom {
 
         // Update room status if this is the first participant
         drop(participants);
+        if let Some(recorder) = self.recorder.lock().await.as_mut() {
+            recorder.add_participant(&participant_id, &join_display_name);
+        }
         let mut status = self.status.lock().await;
         if *status == RoomStatus::Waiting {
             *status = RoomStatus::Active;
@@ -366,12 +408,31 @@ impl ConferenceRoom {
     }
 
     /// Broadcast a transcript to all participants.
+    ///
+    /// While recording, source-language transcripts are also added to the
+    /// meeting record, attributed to `participant_id`. The utterance spans
+    /// from the participant's segment start (see [`Self::mark_segment_start`])
+    /// to now; without one it is recorded as an instant.
     pub async fn broadcast_transcript(
         &self,
         participant_id: &str,
         text: &str,
         is_source: bool,
     ) -> anyhow::Result<()> {
+        if is_source {
+            let segment_start = self
+                .participants
+                .lock()
+                .await
+                .get_mut(participant_id)
+                .and_then(|state| state.segment_start_ms.take());
+            if let Some(recorder) = self.recorder.lock().await.as_mut() {
+                let end = recorder.elapsed_ms();
+                let start = segment_start.map_or(end, |start| start.min(end));
+                recorder.record(participant_id, text, start, end);
+            }
+        }
+
         let participants = self.participants.lock().await;
 
         let event = ConferenceEvent::Transcript {
@@ -389,12 +450,31 @@ impl ConferenceRoom {
         Ok(())
     }
 
-    /// Update a participant's speaking status.
+    /// Note that `participant_id` started a new segment (speech onset or the
+    /// first partial transcript). Later calls before the segment is committed
+    /// by [`Self::broadcast_transcript`] keep the earliest start.
+    pub async fn mark_segment_start(&self, participant_id: &str) {
+        let Some(now) = self.recording_elapsed_ms().await else {
+            return;
+        };
+        if let Some(state) = self.participants.lock().await.get_mut(participant_id) {
+            state.segment_start_ms.get_or_insert(now);
+        }
+    }
+
+    /// Update a participant's speaking status. Starting to speak also marks
+    /// the start of the participant's next segment.
     pub async fn set_speaking(&self, participant_id: &str, speaking: bool) {
+        let now = self.recording_elapsed_ms().await;
         let mut participants = self.participants.lock().await;
 
         if let Some(state) = participants.get_mut(participant_id) {
             state.info.speaking = speaking;
+            if speaking {
+                if let Some(now) = now {
+                    state.segment_start_ms.get_or_insert(now);
+                }
+            }
             state.status = if speaking {
                 ParticipantStatus::Active
             } else {
@@ -445,6 +525,77 @@ impl ConferenceRoom {
         }
     }
 
+    /// Start keeping a meeting record, seeded with the current roster.
+    pub async fn start_recording(&self, title: Option<&str>) -> anyhow::Result<()> {
+        let mut recorder = self.recorder.lock().await;
+        if recorder.is_some() {
+            anyhow::bail!(
+                "Conference room {} is already recording",
+                self.config.room_id
+            );
+        }
+        let mut new_recorder = MeetingRecorder::new(&self.config.room_id, title);
+        for state in self.participants.lock().await.values() {
+            new_recorder.add_participant(&state.info.participant_id, &state.info.display_name);
+        }
+        *recorder = Some(new_recorder);
+        tracing::info!(room_id = %self.config.room_id, "Conference recording started");
+        Ok(())
+    }
+
+    /// Whether a meeting record is being kept.
+    pub async fn is_recording(&self) -> bool {
+        self.recorder.lock().await.is_some()
+    }
+
+    /// Milliseconds since recording started, for callers that timestamp
+    /// audio themselves. `None` when not recording.
+    pub async fn recording_elapsed_ms(&self) -> Option<u64> {
+        self.recorder
+            .lock()
+            .await
+            .as_ref()
+            .map(MeetingRecorder::elapsed_ms)
+    }
+
+    /// Record an utterance with explicit timings (ms from recording start).
+    pub async fn record_utterance(
+        &self,
+        participant_id: &str,
+        text: &str,
+        start_ms: u64,
+        end_ms: u64,
+    ) {
+        if let Some(recorder) = self.recorder.lock().await.as_mut() {
+            recorder.record(participant_id, text, start_ms, end_ms);
+        }
+    }
+
+    /// Record a clip from a shared microphone, splitting it into diarized
+    /// speaker turns. `offset_ms` is the clip start on the recording timeline.
+    pub async fn record_shared_mic(
+        &self,
+        participant_id: &str,
+        samples: &[f32],
+        transcript: &Transcript,
+        offset_ms: u64,
+    ) {
+        if let Some(recorder) = self.recorder.lock().await.as_mut() {
+            recorder.record_shared_mic(participant_id, samples, transcript, offset_ms);
+        }
+    }
+
+    /// Stop recording and return the finished transcript.
+    pub async fn stop_recording(&self) -> Option<MeetingTranscript> {
+        let recorder = self.recorder.lock().await.take()?;
+        tracing::info!(
+            room_id = %self.config.room_id,
+            utterances = recorder.utterance_count(),
+            "Conference recording stopped"
+        );
+        Some(recorder.finish())
+    }
+
     /// Close the conference room and disconnect all participants.
     pub async fn close(&self) {
         let mut status = self.status.lock().await;
@@ -474,6 +625,8 @@ impl ConferenceRoom {
 pub struct ConferenceManager {
     rooms: Arc<Mutex<HashMap<String, Arc<ConferenceRoom>>>>,
     max_rooms: usize,
+    /// When set, new rooms record automatically and are archived on close.
+    archiver: Option<Arc<MeetingArchiver>>,
 }
 
 impl ConferenceManager {
@@ -482,16 +635,73 @@ impl ConferenceManager {
         Self {
             rooms: Arc::new(Mutex::new(HashMap::new())),
             max_rooms,
+            archiver: None,
+        }
+    }
+
+    /// Build the manager described by `[voice]`. With `record_meetings` set,
+    /// rooms are archived to the workspace, `memory` and the ontology, with
+    /// minutes written by `provider`/`model`.
+    pub fn from_config(
+        config: &Config,
+        memory: Arc<dyn Memory>,
+        provider: Arc<dyn Provider>,
+        model: &str,
+    ) -> Self {
+        let manager = Self::new(config.voice.max_conference_rooms);
+        if !config.voice.record_meetings {
+            return manager;
         }
+
+        let mut archiver = MeetingArchiver::new(&config.workspace_dir, "default_user")
+            .with_memory(memory)
+            .with_summarizer(provider, model);
+        match OntologyRepo::open(&config.workspace_dir) {
+            Ok(repo) => archiver = archiver.with_ontology(Arc::new(repo)),
+            Err(e) => {
+                tracing::warn!("Meeting records will skip the ontology: {e}");
+            }
+        }
+        manager.with_archiver(Arc::new(archiver))
+    }
+
+    /// Enable meeting-record mode for every room created by this manager.
+    pub fn with_archiver(mut self, archiver: Arc<MeetingArchiver>) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+
+    /// Whether rooms from this manager keep a meeting record.
+    pub fn records_meetings(&self) -> bool {
+        self.archiver.is_some()
     }
 
     /// Create a new conference room.
     pub async fn create_room(
         &self,
         config: ConferenceConfig,
+    ) -> anyhow::Result<Arc<ConferenceRoom>> {
+        self.insert_room(config, false).await
+    }
+
+    /// Return the room named by `config.room_id`, creating it if needed.
+    pub async fn open_room(&self, config: ConferenceConfig) -> anyhow::Result<Arc<ConferenceRoom>> {
+        self.insert_room(config, true).await
+    }
+
+    async fn insert_room(
+        &self,
+        config: ConferenceConfig,
+        reuse_existing: bool,
     ) -> anyhow::Result<Arc<ConferenceRoom>> {
         let mut rooms = self.rooms.lock().await;
 
+        if reuse_existing {
+            if let Some(room) = rooms.get(&config.room_id) {
+                return Ok(Arc::clone(room));
+            }
+        }
+
         if rooms.len() >= self.max_rooms {
             anyhow::bail!(
                 "Maximum concurrent conference rooms ({}) reached",
@@ -505,6 +715,9 @@ impl ConferenceManager {
 
         let room_id = config.room_id.clone();
         let room = Arc::new(ConferenceRoom::new(config));
+        if self.archiver.is_some() {
+            room.start_recording(None).await?;
+        }
         rooms.insert(room_id, Arc::clone(&room));
 
         Ok(room)
@@ -517,15 +730,55 @@ impl ConferenceManager {
     }
 
     /// Close and remove a conference room.
-    pub async fn close_room(&self, room_id: &str) -> anyhow::Result<()> {
-        let mut rooms = self.rooms.lock().await;
-
-        let room = rooms
+    ///
+    /// In meeting-record mode the transcript is archived under the room
+    /// creator and the resulting record returned; otherwise `None`.
+    pub async fn close_room(&self, room_id: &str) -> anyhow::Result<Option<MeetingRecord>> {
+        let room = self
+            .rooms
+            .lock()
+            .await
             .remove(room_id)
             .ok_or_else(|| anyhow::anyhow!("Conference room {} not found", room_id))?;
+        self.finish_room(&room).await
+    }
 
+    /// Remove `participant_id` from `room_id`. When that empties the room it
+    /// is closed like [`Self::close_room`] and its record returned.
+    pub async fn leave_room(
+        &self,
+        room_id: &str,
+        participant_id: &str,
+    ) -> anyhow::Result<Option<MeetingRecord>> {
+        let room = {
+            // Held across the leave so `open_room` cannot hand out a room
+            // that is about to close.
+            let mut rooms = self.rooms.lock().await;
+            let room = rooms
+                .get(room_id)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Conference room {} not found", room_id))?;
+            room.leave(participant_id).await?;
+            if room.status().await != RoomStatus::Closed {
+                return Ok(None);
+            }
+            rooms.remove(room_id);
+            room
+        };
+        self.finish_room(&room).await
+    }
+
+    async fn finish_room(&self, room: &ConferenceRoom) -> anyhow::Result<Option<MeetingRecord>> {
         room.close().await;
-        Ok(())
+        let transcript = room.stop_recording().await;
+        match (transcript, &self.archiver) {
+            (Some(transcript), Some(archiver)) => Ok(Some(
+                archiver
+                    .archive_for(&room.config.creator_user_id, &transcript)
+                    .await?,
+            )),
+            _ => Ok(None),
+        }
     }
 
     /// List all active conference rooms.
@@ -759,4 +1012,190 @@ mod tests {
         assert_eq!(room.status().await, RoomStatus::Closed);
         assert_eq!(room.summary().await.participant_count, 0);
     }
+
+    #[tokio::test]
+    async fn recording_attributes_source_transcripts() {
+        let room = ConferenceRoom::new(test_config());
+        let _rx1 = room
+            .join(
+                "p1".into(),
+                "Alice".into(),
+                LanguageCode::Ko,
+                LanguageCode::En,
+            )
+            .await
+            .unwrap();
+        room.start_recording(Some("Standup")).await.unwrap();
+        assert!(room.start_recording(None).await.is_err());
+        let _rx2 = room
+            .join(
+                "p2".into(),
+                "Bob".into(),
+                LanguageCode::En,
+                LanguageCode::Ko,
+            )
+            .await
+            .unwrap();
+
+        room.mark_segment_start("p1").await;
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        room.broadcast_transcript("p1", "안녕하세요", true)
+            .await
+            .unwrap();
+        // Translations are not part of the record.
+        room.broadcast_transcript("p1", "Hello", false)
+            .await
+            .unwrap();
+        room.broadcast_transcript("p2", "Morning", true)
+            .await
+            .unwrap();
+
+        let transcript = room.stop_recording().await.unwrap();
+        assert_eq!(transcript.title, "Standup");
+        assert_eq!(transcript.participants.len(), 2);
+        let lines: Vec<(&str, &str)> = transcript
+            .utterances
+            .iter()
+            .map(|u| (u.speaker.as_str(), u.text.as_str()))
+            .collect();
+        assert_eq!(lines, [("Alice", "안녕하세요"), ("Bob", "Morning")]);
+        // Alice's segment spans from its start mark to the commit; Bob's had
+        // no start and is an instant.
+        let alice = &transcript.utterances[0];
+        assert!(alice.end_ms >= alice.start_ms + 20, "{alice:?}");
+        let bob = &transcript.utterances[1];
+        assert_eq!(bob.start_ms, bob.end_ms);
+        assert!(!room.is_recording().await);
+    }
+
+    #[tokio::test]
+    async fn segments_start_when_speaking_starts() {
+        let room = ConferenceRoom::new(test_config());
+        let _rx = room
+            .join(
+                "p1".into(),
+                "Alice".into(),
+                LanguageCode::En,
+                LanguageCode::Ko,
+            )
+            .await
+            .unwrap();
+        // Speech before recording starts leaves no segment open.
+        room.set_speaking("p1", true).await;
+        room.start_recording(None).await.unwrap();
+        room.broadcast_transcript("p1", "one", true).await.unwrap();
+
+        room.set_speaking("p1", true).await;
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        // A later mark does not move the start of an open segment.
+        room.mark_segment_start("p1").await;
+        room.broadcast_transcript("p1", "two", true).await.unwrap();
+        room.broadcast_transcript("p1", "three", true)
+            .await
+            .unwrap();
+
+        let u = room.stop_recording().await.unwrap().utterances;
+        assert_eq!(u[0].start_ms, u[0].end_ms);
+        assert!(u[1].end_ms >= u[1].start_ms + 20, "{:?}", u[1]);
+        assert!(u[1].start_ms <= u[0].end_ms + 5, "{:?}", u[1]);
+        assert_eq!(u[2].start_ms, u[2].end_ms);
+    }
+
+    #[tokio::test]
+    async fn manager_with_archiver_archives_on_close() {
+        let tmp = tempfile::tempdir().unwrap();
+        let manager = ConferenceManager::new(2)
+            .with_archiver(Arc::new(MeetingArchiver::new(tmp.path(), "owner")));
+        let room = manager.create_room(test_config()).await.unwrap();
+        assert!(room.is_recording().await);
+        let _rx = room
+            .join(
+                "p1".into(),
+                "Alice".into(),
+                LanguageCode::En,
+                LanguageCode::Ko,
+            )
+            .await
+            .unwrap();
+        room.broadcast_transcript("p1", "We agreed to launch.", true)
+            .await
+            .unwrap();
+
+        let record = manager.close_room("test-room").await.unwrap().unwrap();
+        assert!(record.transcript_path.exists());
+        assert_eq!(record.minutes.decisions, ["We agreed to launch."]);
+    }
+
+    #[tokio::test]
+    async fn last_participant_leaving_archives_the_room() {
+        let tmp = tempfile::tempdir().unwrap();
+        let manager = ConferenceManager::new(1)
+            .with_archiver(Arc::new(MeetingArchiver::new(tmp.path(), "owner")));
+        let room = manager
+            .open_room(ConferenceConfig::new("standup", "creator"))
+            .await
+            .unwrap();
+        let _rx1 = room
+            .join(
+                "p1".into(),
+                "Alice".into(),
+                LanguageCode::En,
+                LanguageCode::Ko,
+            )
+            .await
+            .unwrap();
+        // A second session naming the same room joins it instead of
+        // counting against the room limit.
+        let same = manager
+            .open_room(ConferenceConfig::new("standup", "someone-else"))
+            .await
+            .unwrap();
+        assert!(Arc::ptr_eq(&room, &same));
+        let _rx2 = same
+            .join(
+                "p2".into(),
+                "Bob".into(),
+                LanguageCode::Ko,
+                LanguageCode::En,
+            )
+            .await
+            .unwrap();
+        room.broadcast_transcript("p2", "We decided to ship.", true)
+            .await
+            .unwrap();
+
+        assert!(manager.leave_room("standup", "p1").await.unwrap().is_none());
+        assert_eq!(manager.room_count().await, 1);
+        let record = manager.leave_room("standup", "p2").await.unwrap().unwrap();
+        assert_eq!(manager.room_count().await, 0);
+        assert_eq!(record.minutes.decisions, ["We decided to ship."]);
+        assert!(manager.leave_room("standup", "p2").await.is_err());
+    }
+
+    #[test]
+    fn from_config_records_meetings_only_when_enabled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let memory: Arc<dyn Memory> = Arc::new(crate::memory::NoneMemory::new());
+        let provider: Arc<dyn Provider> =
+            Arc::from(crate::providers::create_provider("ollama", None).unwrap());
+
+        let manager = ConferenceManager::from_config(
+            &config,
+            Arc::clone(&memory),
+            Arc::clone(&provider),
+            "m",
+        );
+        assert!(!manager.records_meetings());
+        assert_eq!(manager.max_rooms, 10);
+
+        config.voice.record_meetings = true;
+        config.voice.max_conference_rooms = 2;
+        let manager = ConferenceManager::from_config(&config, memory, provider, "m");
+        assert!(manager.records_meetings());
+        assert_eq!(manager.max_rooms, 2);
+    }
 }
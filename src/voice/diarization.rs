@@ -0,0 +1,329 @@
+//! Lightweight speaker diarization for shared microphones.
+//!
+//! When several people talk into one conference-room mic, the room roster
+//! cannot tell them apart. This module finds speaker changes in 16 kHz mono
+//! audio and clusters the resulting turns into anonymous speakers
+//! ("Speaker 1", "Speaker 2", …).
+//!
+//! The features are deliberately cheap: per 30 ms frame, log energies at a
+//! fixed set of mel-spaced frequencies (Goertzel filters), with the frame's
+//! mean removed so only spectral *shape* — not loudness — is compared.
+//! Adjacent one-second windows are compared to find change points, and each
+//! turn is assigned online to the nearest speaker centroid or opens a new one.
+//! This is good enough to split a handful of distinct voices on a shared mic;
+//! it is not a substitute for a neural embedding model.
+
+use super::local_stt::audio::TARGET_SAMPLE_RATE;
+use super::local_stt::Word;
+
+/// Samples per analysis frame (30 ms).
+const FRAME: usize = TARGET_SAMPLE_RATE as usize * 30 / 1000;
+
+/// Frames per comparison window (~1 s) and per hop (~0.5 s).
+const WINDOW_FRAMES: usize = 33;
+const HOP_FRAMES: usize = 16;
+
+/// Frames quieter than this RMS are ignored when building features.
+const SILENCE_RMS: f32 = 0.01;
+
+/// Probe frequencies (Hz), roughly mel-spaced over the speech band.
+const PROBE_HZ: [f32; 20] = [
+    100.0, 150.0, 200.0, 260.0, 330.0, 410.0, 500.0, 610.0, 730.0, 870.0, 1030.0, 1210.0, 1420.0,
+    1660.0, 1940.0, 2260.0, 2630.0, 3060.0, 3550.0, 4120.0,
+];
+
+/// Tuning knobs for [`diarize`].
+#[derive(Debug, Clone, Copy)]
+pub struct DiarizationParams {
+    /// Feature distance between adjacent windows that marks a speaker change.
+    pub change_threshold: f32,
+    /// Maximum distance from a speaker centroid to reuse that speaker.
+    pub cluster_threshold: f32,
+    /// Upper bound on distinct speakers on one mic.
+    pub max_speakers: usize,
+    /// Turns shorter than this (seconds) are merged into a neighbour.
+    pub min_turn_secs: f64,
+}
+
+impl Default for DiarizationParams {
+    fn default() -> Self {
+        Self {
+            change_threshold: 2.0,
+            cluster_threshold: 2.5,
+            max_speakers: 6,
+            min_turn_secs: 1.0,
+        }
+    }
+}
+
+/// A stretch of audio attributed to one anonymous speaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerTurn {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    /// Zero-based speaker index, stable within one [`diarize`] call.
+    pub speaker: usize,
+}
+
+fn frame_secs(frames: usize) -> f64 {
+    (frames * FRAME) as f64 / f64::from(TARGET_SAMPLE_RATE)
+}
+
+/// Per-frame spectral-shape features; `None` for silent frames.
+fn frame_features(samples: &[f32]) -> Vec<Option<Vec<f32>>> {
+    let coeffs: Vec<f32> = PROBE_HZ
+        .iter()
+        .map(|hz| 2.0 * (2.0 * std::f32::consts::PI * hz / TARGET_SAMPLE_RATE as f32).cos())
+        .collect();
+    samples
+        .chunks_exact(FRAME)
+        .map(|frame| {
+            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / FRAME as f32).sqrt();
+            if rms < SILENCE_RMS {
+                return None;
+            }
+            let mut logs: Vec<f32> = coeffs
+                .iter()
+                .map(|&coeff| {
+                    // Goertzel: power of a single DFT bin.
+                    let (mut s1, mut s2) = (0.0f32, 0.0f32);
+                    for &x in frame {
+                        let s0 = x + coeff * s1 - s2;
+                        s2 = s1;
+                        s1 = s0;
+                    }
+                    let power = s1 * s1 + s2 * s2 - coeff * s1 * s2;
+                    (power.max(0.0) + 1e-6).ln()
+                })
+                .collect();
+            let mean = logs.iter().sum::<f32>() / logs.len() as f32;
+            for v in &mut logs {
+                *v -= mean;
+            }
+            Some(logs)
+        })
+        .collect()
+}
+
+/// Mean of the voiced frames in `frames`, if there are enough of them.
+fn mean_feature(frames: &[Option<Vec<f32>>]) -> Option<Vec<f32>> {
+    let voiced: Vec<&Vec<f32>> = frames.iter().flatten().collect();
+    if voiced.len() < frames.len().max(1) / 4 || voiced.is_empty() {
+        return None;
+    }
+    let mut mean = vec![0.0; PROBE_HZ.len()];
+    for f in &voiced {
+        for (m, v) in mean.iter_mut().zip(f.iter()) {
+            *m += v;
+        }
+    }
+    for m in &mut mean {
+        *m /= voiced.len() as f32;
+    }
+    Some(mean)
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    (a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>() / a.len() as f32).sqrt() * 2.0
+}
+
+/// Find speaker turns in 16 kHz mono audio.
+pub fn diarize(samples: &[f32], params: DiarizationParams) -> Vec<SpeakerTurn> {
+    let features = frame_features(samples);
+    let n = features.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // 1. Change points where the left and right windows differ most.
+    let mut scores: Vec<(usize, f32)> = Vec::new();
+    let mut t = WINDOW_FRAMES;
+    while t + WINDOW_FRAMES <= n {
+        let left = mean_feature(&features[t - WINDOW_FRAMES..t]);
+        let right = mean_feature(&features[t..t + WINDOW_FRAMES]);
+        if let (Some(l), Some(r)) = (left, right) {
+            scores.push((t, distance(&l, &r)));
+        }
+        t += HOP_FRAMES;
+    }
+    let mut boundaries = vec![0usize];
+    for i in 0..scores.len() {
+        let (frame, score) = scores[i];
+        let is_peak = (i == 0 || scores[i - 1].1 <= score)
+            && (i + 1 == scores.len() || scores[i + 1].1 < score);
+        if score > params.change_threshold && is_peak {
+            boundaries.push(frame);
+        }
+    }
+    boundaries.push(n);
+
+    // 2. Assign each turn to the nearest speaker centroid.
+    let mut centroids: Vec<(Vec<f32>, usize)> = Vec::new();
+    let mut turns: Vec<SpeakerTurn> = Vec::new();
+    for pair in boundaries.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let Some(feature) = mean_feature(&features[start..end]) else {
+            continue;
+        };
+        let nearest = centroids
+            .iter()
+            .enumerate()
+            .map(|(i, (c, _))| (i, distance(c, &feature)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let speaker = match nearest {
+            Some((i, d)) if d <= params.cluster_threshold => i,
+            Some((i, _)) if centroids.len() >= params.max_speakers => i,
+            _ => {
+                centroids.push((vec![0.0; PROBE_HZ.len()], 0));
+                centroids.len() - 1
+            }
+        };
+        // Running mean weighted by turn length.
+        let weight = end - start;
+        let (centroid, total) = &mut centroids[speaker];
+        for (c, v) in centroid.iter_mut().zip(&feature) {
+            *c = (*c * *total as f32 + v * weight as f32) / (*total + weight) as f32;
+        }
+        *total += weight;
+        turns.push(SpeakerTurn {
+            start_secs: frame_secs(start),
+            end_secs: frame_secs(end),
+            speaker,
+        });
+    }
+
+    merge_turns(turns, params.min_turn_secs)
+}
+
+/// Merge adjacent turns by the same speaker and absorb very short turns into
+/// the preceding one.
+fn merge_turns(turns: Vec<SpeakerTurn>, min_turn_secs: f64) -> Vec<SpeakerTurn> {
+    let mut out: Vec<SpeakerTurn> = Vec::with_capacity(turns.len());
+    for turn in turns {
+        match out.last_mut() {
+            Some(prev)
+                if prev.speaker == turn.speaker
+                    || turn.end_secs - turn.start_secs < min_turn_secs =>
+            {
+                prev.end_secs = turn.end_secs;
+            }
+            _ => out.push(turn),
+        }
+    }
+    out
+}
+
+/// Group timed words by the speaker turn their midpoint falls in. Words
+/// outside every turn go to the closest one.
+pub fn assign_words<'a>(turns: &[SpeakerTurn], words: &'a [Word]) -> Vec<(usize, Vec<&'a Word>)> {
+    let mut groups: Vec<(usize, Vec<&Word>)> = Vec::new();
+    for word in words {
+        let mid = f64::midpoint(word.start, word.end);
+        let speaker = turns
+            .iter()
+            .min_by(|a, b| {
+                let da = gap(a, mid);
+                let db = gap(b, mid);
+                da.total_cmp(&db)
+            })
+            .map_or(0, |t| t.speaker);
+        match groups.last_mut() {
+            Some((s, list)) if *s == speaker => list.push(word),
+            _ => groups.push((speaker, vec![word])),
+        }
+    }
+    groups
+}
+
+fn gap(turn: &SpeakerTurn, at: f64) -> f64 {
+    if at < turn.start_secs {
+        turn.start_secs - at
+    } else if at > turn.end_secs {
+        at - turn.end_secs
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A crude "voice": a harmonic series with a given pitch and spectral tilt,
+    /// amplitude-modulated at syllable rate.
+    fn voice(pitch: f32, tilt: f32, secs: f32) -> Vec<f32> {
+        let n = (TARGET_SAMPLE_RATE as f32 * secs) as usize;
+        let sr = TARGET_SAMPLE_RATE as f32;
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / sr;
+                let mut v = 0.0;
+                let mut h = 1.0;
+                while pitch * h < 4_000.0 {
+                    v += (2.0 * std::f32::consts::PI * pitch * h * t).sin() * tilt.powf(h);
+                    h += 1.0;
+                }
+                let envelope = 0.6 + 0.4 * (2.0 * std::f32::consts::PI * 4.0 * t).sin();
+                v * 0.2 * envelope
+            })
+            .collect()
+    }
+
+    #[test]
+    fn separates_two_alternating_voices() {
+        let a = || voice(110.0, 0.9, 4.0);
+        let b = || voice(230.0, 0.5, 4.0);
+        let mut audio = a();
+        audio.extend(b());
+        audio.extend(a());
+        let turns = diarize(&audio, DiarizationParams::default());
+        let speakers: Vec<usize> = turns.iter().map(|t| t.speaker).collect();
+        assert_eq!(speakers, vec![0, 1, 0], "{turns:?}");
+        assert!((turns[1].start_secs - 4.0).abs() < 0.6, "{turns:?}");
+    }
+
+    #[test]
+    fn single_voice_is_one_speaker() {
+        let turns = diarize(&voice(150.0, 0.8, 6.0), DiarizationParams::default());
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].speaker, 0);
+    }
+
+    #[test]
+    fn silence_has_no_turns() {
+        assert!(diarize(&vec![0.0; 48_000], DiarizationParams::default()).is_empty());
+    }
+
+    #[test]
+    fn words_follow_turns() {
+        let turns = vec![
+            SpeakerTurn {
+                start_secs: 0.0,
+                end_secs: 2.0,
+                speaker: 0,
+            },
+            SpeakerTurn {
+                start_secs: 2.0,
+                end_secs: 4.0,
+                speaker: 1,
+            },
+        ];
+        let word = |w: &str, start: f64| Word {
+            word: w.into(),
+            start,
+            end: start + 0.3,
+            confidence: None,
+        };
+        let words = vec![
+            word("hi", 0.2),
+            word("there", 1.0),
+            word("yes", 2.5),
+            word("late", 4.5),
+        ];
+        let groups = assign_words(&turns, &words);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, 1);
+        assert_eq!(groups[1].1.len(), 2);
+    }
+}
@@ -56,6 +56,14 @@ pub enum ClientMessage {
         /// Typecast voice clone ID (speak_resource_id) for cloned-voice TTS.
         #[serde(default, skip_serializing_if = "Option::is_none", rename = "voiceCloneId")]
         voice_clone_id: Option<String>,
+        /// Conference room to join (created on first join). Omit for a
+        /// private interpretation session.
+        #[serde(default, skip_serializing_if = "Option::is_none", rename = "roomId")]
+        room_id: Option<String>,
+        /// Name shown to other participants and in meeting records
+        /// (defaults to the device ID).
+        #[serde(default, skip_serializing_if = "Option::is_none", rename = "displayName")]
+        display_name: Option<String>,
     },
 
     /// Stop the current session.
@@ -236,10 +244,13 @@ mod tests {
             voice_gender: None,
             voice_age: None,
             voice_clone_id: None,
+            room_id: None,
+            display_name: None,
         };
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("session_start"));
         assert!(json.contains("sessionId"));
+        assert!(!json.contains("roomId"));
 
         // Round-trip
         let parsed: ClientMessage = serde_json::from_str(&json).unwrap();
@@ -251,6 +262,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn session_start_reads_conference_room() {
+        let json = r#"{"type":"session_start","sessionId":"s1","sourceLang":"ko","targetLang":"en","mode":"simul","deviceId":"d1","roomId":"standup","displayName":"Alice"}"#;
+        match serde_json::from_str::<ClientMessage>(json).unwrap() {
+            ClientMessage::SessionStart {
+                room_id,
+                display_name,
+                ..
+            } => {
+                assert_eq!(room_id.as_deref(), Some("standup"));
+                assert_eq!(display_name.as_deref(), Some("Alice"));
+            }
+            _ => panic!("Wrong variant"),
+        }
+    }
+
     #[test]
     fn server_message_serialization() {
         let msg = ServerMessage::PartialSrc {
@@ -0,0 +1,401 @@
+//! Audio decoding for local speech-to-text: container/codec → 16 kHz mono `f32`.
+//!
+//! WAV (PCM 8/16/24/32-bit and IEEE float) is parsed natively so the
+//! streaming path and tests need no extra dependencies. Compressed formats
+//! (Ogg, WebM/Matroska, MP4/M4A, MP3, FLAC) go through Symphonia behind the
+//! `stt-local` feature; Opus packets — what Telegram, WhatsApp and browser
+//! `MediaRecorder` produce — are decoded with libopus behind `stt-opus`.
+
+// Sample-format conversion and resampling are inherently lossy casts.
+#![allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+
+use anyhow::{bail, Context, Result};
+
+/// Sample rate every local STT engine consumes.
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+/// Opus always decodes at 48 kHz internally.
+#[cfg(feature = "stt-opus")]
+const OPUS_SAMPLE_RATE: u32 = 48_000;
+
+/// Decode an audio file into 16 kHz mono samples in `[-1.0, 1.0]`.
+///
+/// `extension` is a hint for container probing (e.g. `"ogg"`, `"m4a"`); the
+/// actual format is sniffed from the bytes where possible.
+pub fn decode_to_mono_16k(bytes: &[u8], extension: &str) -> Result<Vec<f32>> {
+    let (samples, sample_rate) = if is_wav(bytes) {
+        decode_wav(bytes)?
+    } else {
+        decode_compressed(bytes, extension)?
+    };
+    Ok(resample(&samples, sample_rate, TARGET_SAMPLE_RATE))
+}
+
+fn is_wav(bytes: &[u8]) -> bool {
+    bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE"
+}
+
+/// Parse a RIFF/WAVE file and return mono samples plus the source rate.
+pub fn decode_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32)> {
+    if !is_wav(bytes) {
+        bail!("not a RIFF/WAVE file");
+    }
+    let mut pos = 12;
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let len = u32::from_le_bytes([
+            bytes[pos + 4],
+            bytes[pos + 5],
+            bytes[pos + 6],
+            bytes[pos + 7],
+        ]) as usize;
+        let body_start = pos + 8;
+        // Streaming writers leave the data length at 0 or 0xFFFFFFFF; clamp
+        // to what is actually present.
+        let body_end = body_start.saturating_add(len).min(bytes.len());
+        let body = &bytes[body_start..body_end];
+        match id {
+            b"fmt " => {
+                if body.len() < 16 {
+                    bail!("WAV fmt chunk too short");
+                }
+                let mut tag = u16::from_le_bytes([body[0], body[1]]);
+                let channels = u16::from_le_bytes([body[2], body[3]]);
+                let rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                let bits = u16::from_le_bytes([body[14], body[15]]);
+                // WAVE_FORMAT_EXTENSIBLE: the real format tag is the first two
+                // bytes of the sub-format GUID.
+                if tag == 0xFFFE && body.len() >= 26 {
+                    tag = u16::from_le_bytes([body[24], body[25]]);
+                }
+                format = Some((tag, channels, rate, bits));
+            }
+            b"data" => {
+                let (tag, channels, rate, bits) =
+                    format.context("WAV data chunk appears before fmt chunk")?;
+                let data = if len == 0 { &bytes[body_start..] } else { body };
+                let interleaved = wav_samples(data, tag, bits)?;
+                return Ok((downmix(&interleaved, usize::from(channels.max(1))), rate));
+            }
+            _ => {}
+        }
+        // Chunks are word-aligned.
+        pos = body_start.saturating_add(len).saturating_add(len & 1);
+    }
+    bail!("WAV file has no data chunk")
+}
+
+fn wav_samples(data: &[u8], tag: u16, bits: u16) -> Result<Vec<f32>> {
+    const PCM: u16 = 1;
+    const IEEE_FLOAT: u16 = 3;
+    let samples = match (tag, bits) {
+        (PCM, 8) => data
+            .iter()
+            .map(|&b| (f32::from(b) - 128.0) / 128.0)
+            .collect(),
+        (PCM, 16) => data
+            .chunks_exact(2)
+            .map(|c| f32::from(i16::from_le_bytes([c[0], c[1]])) / 32_768.0)
+            .collect(),
+        (PCM, 24) => data
+            .chunks_exact(3)
+            .map(|c| {
+                let v = i32::from_le_bytes([0, c[0], c[1], c[2]]) >> 8;
+                v as f32 / 8_388_608.0
+            })
+            .collect(),
+        (PCM, 32) => data
+            .chunks_exact(4)
+            .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f32 / 2_147_483_648.0)
+            .collect(),
+        (IEEE_FLOAT, 32) => data
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+        (IEEE_FLOAT, 64) => data
+            .chunks_exact(8)
+            .map(|c| f64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]) as f32)
+            .collect(),
+        _ => bail!("unsupported WAV encoding (format tag {tag}, {bits} bits)"),
+    };
+    Ok(samples)
+}
+
+/// Average interleaved channels into one.
+pub fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Convert 16-bit little-endian PCM to `f32` samples.
+pub fn pcm16le_to_f32(pcm: &[u8]) -> Vec<f32> {
+    pcm.chunks_exact(2)
+        .map(|c| f32::from(i16::from_le_bytes([c[0], c[1]])) / 32_768.0)
+        .collect()
+}
+
+/// Convert `f32` samples to 16-bit little-endian PCM (clipping out-of-range values).
+pub fn f32_to_pcm16le(samples: &[f32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(samples.len() * 2);
+    for &s in samples {
+        let v = (s.clamp(-1.0, 1.0) * 32_767.0).round() as i16;
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out
+}
+
+/// Half-width of the windowed-sinc kernel, in input samples at unity ratio.
+const SINC_HALF_TAPS: usize = 16;
+
+/// Band-limited resampling with a Hann-windowed sinc kernel.
+///
+/// When downsampling, the kernel cutoff drops to the output Nyquist so that
+/// 44.1/48 kHz recordings do not alias into the speech band.
+pub fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || samples.is_empty() || from == 0 || to == 0 {
+        return samples.to_vec();
+    }
+    let ratio = f64::from(to) / f64::from(from);
+    let cutoff = ratio.min(1.0);
+    let half_width = (SINC_HALF_TAPS as f64 / cutoff).ceil() as isize;
+    let out_len = ((samples.len() as f64) * ratio).round() as usize;
+    let mut out = Vec::with_capacity(out_len);
+    for n in 0..out_len {
+        let center = n as f64 / ratio;
+        let base = center.floor() as isize;
+        let mut acc = 0.0f64;
+        let mut weight = 0.0f64;
+        for k in (base - half_width + 1)..=(base + half_width) {
+            if k < 0 || k as usize >= samples.len() {
+                continue;
+            }
+            let x = center - k as f64;
+            let window_pos = x / (half_width as f64);
+            if window_pos.abs() >= 1.0 {
+                continue;
+            }
+            let window = 0.5 * (1.0 + (std::f64::consts::PI * window_pos).cos());
+            let arg = x * cutoff;
+            let sinc = if arg.abs() < 1e-9 {
+                1.0
+            } else {
+                (std::f64::consts::PI * arg).sin() / (std::f64::consts::PI * arg)
+            };
+            let w = cutoff * sinc * window;
+            acc += f64::from(samples[k as usize]) * w;
+            weight += w;
+        }
+        // Normalizing by the kernel sum keeps DC gain at 1 near the edges.
+        let value = if weight.abs() > 1e-9 {
+            acc / weight
+        } else {
+            0.0
+        };
+        out.push(value as f32);
+    }
+    out
+}
+
+#[cfg(not(feature = "stt-local"))]
+fn decode_compressed(_bytes: &[u8], extension: &str) -> Result<(Vec<f32>, u32)> {
+    bail!(
+        "decoding .{extension} audio locally requires building with the `stt-local` feature \
+         (and `stt-opus` for Ogg/WebM Opus voice notes)"
+    )
+}
+
+#[cfg(feature = "stt-local")]
+fn decode_compressed(bytes: &[u8], extension: &str) -> Result<(Vec<f32>, u32)> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL, CODEC_TYPE_OPUS};
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let source = std::io::Cursor::new(bytes.to_vec());
+    let stream = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut hint = Hint::new();
+    if !extension.is_empty() {
+        hint.with_extension(&extension.to_ascii_lowercase());
+    }
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .with_context(|| format!("unrecognized audio container (.{extension})"))?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .context("audio file has no decodable track")?;
+    let track_id = track.id;
+    let params = track.codec_params.clone();
+    let channels = params.channels.map_or(1, |c| c.count()).max(1);
+
+    if params.codec == CODEC_TYPE_OPUS {
+        return decode_opus_packets(
+            || loop {
+                match format.next_packet() {
+                    Ok(packet) if packet.track_id() == track_id => {
+                        return Some(packet.data.to_vec())
+                    }
+                    Ok(_) => {}
+                    Err(_) => return None,
+                }
+            },
+            channels,
+        );
+    }
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&params, &DecoderOptions::default())
+        .context("unsupported audio codec")?;
+    let mut sample_rate = params.sample_rate.unwrap_or(TARGET_SAMPLE_RATE);
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(e).context("failed to read audio packet"),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                mono.extend(downmix(buffer.samples(), spec.channels.count()));
+            }
+            // Corrupt frames are skipped rather than failing the whole note.
+            Err(SymphoniaError::DecodeError(_)) => {}
+            Err(e) => return Err(e).context("audio decode failed"),
+        }
+    }
+    Ok((mono, sample_rate))
+}
+
+#[cfg(all(feature = "stt-local", not(feature = "stt-opus")))]
+fn decode_opus_packets(
+    _next_packet: impl FnMut() -> Option<Vec<u8>>,
+    _channels: usize,
+) -> Result<(Vec<f32>, u32)> {
+    bail!("Opus audio requires building with the `stt-opus` feature (links libopus)")
+}
+
+#[cfg(feature = "stt-opus")]
+fn decode_opus_packets(
+    mut next_packet: impl FnMut() -> Option<Vec<u8>>,
+    channels: usize,
+) -> Result<(Vec<f32>, u32)> {
+    use audiopus::coder::Decoder;
+    use audiopus::packet::Packet;
+    use audiopus::{Channels, MutSignals, SampleRate};
+
+    let opus_channels = if channels >= 2 {
+        Channels::Stereo
+    } else {
+        Channels::Mono
+    };
+    let channel_count = if channels >= 2 { 2 } else { 1 };
+    let mut decoder = Decoder::new(SampleRate::Hz48000, opus_channels)
+        .map_err(|e| anyhow::anyhow!("failed to create Opus decoder: {e}"))?;
+    // 120 ms is the longest Opus frame.
+    let mut frame = vec![0f32; 5_760 * channel_count];
+    let mut mono = Vec::new();
+    while let Some(data) = next_packet() {
+        let Ok(packet) = Packet::try_from(data.as_slice()) else {
+            continue;
+        };
+        let Ok(signals) = MutSignals::try_from(frame.as_mut_slice()) else {
+            continue;
+        };
+        match decoder.decode_float(Some(packet), signals, false) {
+            Ok(samples_per_channel) => mono.extend(downmix(
+                &frame[..samples_per_channel * channel_count],
+                channel_count,
+            )),
+            Err(e) => tracing::debug!(error = %e, "skipping undecodable Opus packet"),
+        }
+    }
+    Ok((mono, OPUS_SAMPLE_RATE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice::gemma_asr::wrap_pcm16_in_wav;
+
+    fn sine(freq: f32, rate: u32, secs: f32) -> Vec<f32> {
+        let n = (rate as f32 * secs) as usize;
+        (0..n)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn wav_round_trip_and_downmix() {
+        // Stereo 16-bit: left = +0.5, right = -0.5 → mono 0.
+        let mut pcm = Vec::new();
+        for _ in 0..100 {
+            pcm.extend_from_slice(&16_384i16.to_le_bytes());
+            pcm.extend_from_slice(&(-16_384i16).to_le_bytes());
+        }
+        let wav = wrap_pcm16_in_wav(&pcm, 16_000, 2);
+        let (mono, rate) = decode_wav(&wav).unwrap();
+        assert_eq!(rate, 16_000);
+        assert_eq!(mono.len(), 100);
+        assert!(mono.iter().all(|s| s.abs() < 1e-4));
+    }
+
+    #[test]
+    fn resample_preserves_duration_and_tone() {
+        let input = sine(440.0, 48_000, 1.0);
+        let output = resample(&input, 48_000, 16_000);
+        assert_eq!(output.len(), 16_000);
+        // Energy of a 0.5-amplitude sine is 0.125; allow small filter loss.
+        let energy: f32 = output[1_000..15_000].iter().map(|s| s * s).sum::<f32>() / 14_000.0;
+        assert!((energy - 0.125).abs() < 0.01, "energy {energy}");
+    }
+
+    #[test]
+    fn resample_attenuates_content_above_new_nyquist() {
+        // 12 kHz tone cannot be represented at 16 kHz and must be filtered out.
+        let input = sine(12_000.0, 48_000, 0.5);
+        let output = resample(&input, 48_000, 16_000);
+        let energy: f32 = output[500..7_500].iter().map(|s| s * s).sum::<f32>() / 7_000.0;
+        assert!(energy < 0.005, "aliased energy {energy}");
+    }
+
+    #[test]
+    fn decode_to_mono_16k_converts_wav_rate() {
+        let samples = sine(300.0, 8_000, 0.5);
+        let wav = wrap_pcm16_in_wav(&f32_to_pcm16le(&samples), 8_000, 1);
+        let decoded = decode_to_mono_16k(&wav, "wav").unwrap();
+        assert_eq!(decoded.len(), 8_000);
+    }
+
+    #[cfg(not(feature = "stt-local"))]
+    #[test]
+    fn compressed_audio_without_feature_explains_how_to_enable() {
+        let err = decode_to_mono_16k(b"OggS\0\0\0\0", "ogg").unwrap_err();
+        assert!(err.to_string().contains("stt-local"));
+    }
+}
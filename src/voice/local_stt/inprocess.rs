@@ -0,0 +1,162 @@
+//! In-process Whisper inference via whisper.cpp (`whisper-rs`).
+//!
+//! Compiled only with the `stt-whisper` feature, which builds whisper.cpp
+//! from source (needs cmake and a C++ toolchain). Without it, selecting
+//! `engine = "in_process"` fails with a message naming the feature.
+
+use anyhow::Result;
+
+use super::Transcript;
+use crate::config::LocalSttConfig;
+
+#[cfg(not(feature = "stt-whisper"))]
+#[allow(clippy::unused_async)] // Same signature as the `stt-whisper` build.
+pub async fn transcribe(
+    _cfg: &LocalSttConfig,
+    _samples: Vec<f32>,
+    _language: Option<&str>,
+) -> Result<Transcript> {
+    anyhow::bail!(
+        "in-process speech-to-text requires building with the `stt-whisper` feature; \
+         alternatively set [transcription.local].engine = \"server\" and run whisper.cpp's server"
+    )
+}
+
+#[cfg(feature = "stt-whisper")]
+pub use imp::transcribe;
+
+#[cfg(feature = "stt-whisper")]
+mod imp {
+    use anyhow::{Context, Result};
+    use parking_lot::Mutex;
+    use std::sync::{Arc, OnceLock};
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    use super::super::{Transcript, TranscriptSegment, Word};
+    use crate::config::LocalSttConfig;
+
+    /// Loaded model, keyed by path. Loading a ggml model takes seconds and
+    /// hundreds of MB, so one context is kept and shared across requests.
+    static MODEL: OnceLock<Mutex<Option<(String, Arc<WhisperContext>)>>> = OnceLock::new();
+
+    fn context_for(path: &str) -> Result<Arc<WhisperContext>> {
+        let slot = MODEL.get_or_init(|| Mutex::new(None));
+        let mut guard = slot.lock();
+        if let Some((loaded, ctx)) = guard.as_ref() {
+            if loaded == path {
+                return Ok(Arc::clone(ctx));
+            }
+        }
+        let ctx = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .with_context(|| format!("Failed to load Whisper model from {path}"))?;
+        let ctx = Arc::new(ctx);
+        *guard = Some((path.to_string(), Arc::clone(&ctx)));
+        Ok(ctx)
+    }
+
+    pub async fn transcribe(
+        cfg: &LocalSttConfig,
+        samples: Vec<f32>,
+        language: Option<&str>,
+    ) -> Result<Transcript> {
+        let model_path = cfg
+            .model_path
+            .clone()
+            .filter(|p| !p.trim().is_empty())
+            .context("[transcription.local].model_path is required for the in_process engine")?;
+        let threads = cfg.threads;
+        let word_timestamps = cfg.word_timestamps;
+        let language = language.map(str::to_string);
+        tokio::task::spawn_blocking(move || {
+            run(
+                &model_path,
+                &samples,
+                language.as_deref(),
+                threads,
+                word_timestamps,
+            )
+        })
+        .await
+        .context("Whisper inference task panicked")?
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn run(
+        model_path: &str,
+        samples: &[f32],
+        language: Option<&str>,
+        threads: usize,
+        word_timestamps: bool,
+    ) -> Result<Transcript> {
+        let ctx = context_for(model_path)?;
+        let mut state = ctx
+            .create_state()
+            .context("Failed to create Whisper state")?;
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(Some(language.unwrap_or("auto")));
+        params.set_token_timestamps(word_timestamps);
+        if threads > 0 {
+            params.set_n_threads(threads as i32);
+        }
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_special(false);
+        params.set_print_timestamps(false);
+        state
+            .full(params, samples)
+            .context("Whisper inference failed")?;
+
+        let eot = ctx.token_eot();
+        let mut segments = Vec::new();
+        for s in 0..state.full_n_segments()? {
+            // whisper.cpp reports times in centiseconds.
+            let start = state.full_get_segment_t0(s)? as f64 / 100.0;
+            let end = state.full_get_segment_t1(s)? as f64 / 100.0;
+            let text = state.full_get_segment_text_lossy(s)?.trim().to_string();
+            let mut words: Vec<Word> = Vec::new();
+            if word_timestamps {
+                for t in 0..state.full_n_tokens(s)? {
+                    let data = state.full_get_token_data(s, t)?;
+                    // Special tokens ([_BEG_], timestamps, EOT) sort after EOT.
+                    if data.id >= eot {
+                        continue;
+                    }
+                    let piece = state.full_get_token_text_lossy(s, t)?;
+                    let (t0, t1) = (data.t0 as f64 / 100.0, data.t1 as f64 / 100.0);
+                    // Tokens are sub-word pieces; a leading space starts a new word.
+                    match words.last_mut() {
+                        Some(last) if !piece.starts_with(' ') => {
+                            last.word.push_str(&piece);
+                            last.end = t1;
+                            last.confidence = Some(last.confidence.unwrap_or(1.0).min(data.p));
+                        }
+                        _ => words.push(Word {
+                            word: piece.trim().to_string(),
+                            start: t0,
+                            end: t1,
+                            confidence: Some(data.p),
+                        }),
+                    }
+                }
+                words.retain(|w| !w.word.is_empty());
+            }
+            segments.push(TranscriptSegment {
+                start,
+                end,
+                text,
+                words,
+            });
+        }
+        let text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(Transcript {
+            text,
+            language: language.map(str::to_string),
+            segments,
+        })
+    }
+}
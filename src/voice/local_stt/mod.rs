@@ -0,0 +1,276 @@
+//! Offline speech-to-text.
+//!
+//! Transcribes voice notes and live audio without a cloud provider, either by
+//! calling a whisper.cpp-style HTTP server on the local network or by running
+//! a ggml Whisper model in-process. The pipeline is:
+//!
+//! 1. [`audio`] decodes the container/codec (WAV natively; Ogg/Opus, WebM,
+//!    M4A, MP3, FLAC via the `stt-local` / `stt-opus` features) to 16 kHz
+//!    mono PCM.
+//! 2. [`vad`] splits long recordings into speech segments so each request
+//!    stays within the model's 30 s window and silence is never sent.
+//! 3. Each segment is transcribed by the configured engine and its timings
+//!    are shifted back onto the recording's timeline. Engines that return
+//!    no word timings get evenly spread estimates so callers can always rely
+//!    on per-word `start`/`end`.
+//!
+//! Selection is per channel via `[transcription].backend` and
+//! `[transcription].channel_backends`; see [`crate::config::TranscriptionConfig`].
+
+pub mod audio;
+pub mod inprocess;
+pub mod server;
+pub mod session;
+pub mod vad;
+
+pub use session::LocalSttSession;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::config::{LocalSttConfig, LocalSttEngine};
+use crate::voice::gemma_asr::wrap_pcm16_in_wav;
+
+/// A recognized word with timings in seconds from the start of the recording.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// One stretch of continuous speech.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    pub words: Vec<Word>,
+}
+
+/// Full transcription result for a recording.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Transcript {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    /// All words across segments, in order.
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.segments.iter().flat_map(|s| s.words.iter())
+    }
+
+    fn shift(&mut self, offset: f64) {
+        for segment in &mut self.segments {
+            segment.start += offset;
+            segment.end += offset;
+            for word in &mut segment.words {
+                word.start += offset;
+                word.end += offset;
+            }
+        }
+    }
+}
+
+impl LocalSttConfig {
+    pub(crate) fn vad_params(&self) -> vad::VadParams {
+        vad::VadParams {
+            aggressiveness: self.vad_aggressiveness.min(3),
+            min_silence_ms: self.min_silence_ms,
+            max_segment_secs: self.max_segment_secs,
+        }
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    crate::config::build_runtime_proxy_client("transcription.local")
+}
+
+/// Transcribe a complete recording (voice note, uploaded file).
+///
+/// `file_name` supplies the container hint. When the audio cannot be decoded
+/// locally but the server engine is in use, the original file is forwarded
+/// unchanged and the server does its own decoding (whisper.cpp's server can
+/// with `--convert`), trading VAD segmentation for compatibility.
+pub async fn transcribe(
+    audio_data: Vec<u8>,
+    file_name: &str,
+    cfg: &LocalSttConfig,
+    language: Option<&str>,
+) -> Result<Transcript> {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, e)| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let samples = match audio::decode_to_mono_16k(&audio_data, &extension) {
+        Ok(samples) => samples,
+        Err(e) if cfg.engine == LocalSttEngine::Server => {
+            tracing::debug!(error = %e, "local decode unavailable; forwarding original audio");
+            let mime = mime_for_extension(&extension);
+            let mut transcript =
+                server::transcribe(&http_client(), cfg, audio_data, file_name, mime, language)
+                    .await?;
+            fill_missing_word_timings(&mut transcript);
+            return Ok(transcript);
+        }
+        Err(e) => return Err(e),
+    };
+
+    transcribe_samples(&samples, cfg, language).await
+}
+
+/// Transcribe 16 kHz mono samples, segmenting with VAD first.
+pub async fn transcribe_samples(
+    samples: &[f32],
+    cfg: &LocalSttConfig,
+    language: Option<&str>,
+) -> Result<Transcript> {
+    let spans = vad::segment(samples, cfg.vad_params());
+    let client = http_client();
+    let mut result = Transcript::default();
+    for span in spans {
+        let mut part =
+            transcribe_clip(&client, &samples[span.start..span.end], cfg, language).await?;
+        part.shift(span.start_secs());
+        if result.language.is_none() {
+            result.language = part.language.take();
+        }
+        result.segments.append(&mut part.segments);
+    }
+    result.segments.retain(|s| !s.text.is_empty());
+    fill_missing_word_timings(&mut result);
+    result.text = result
+        .segments
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(result)
+}
+
+/// Transcribe one already-segmented clip; timings are relative to the clip.
+pub(crate) async fn transcribe_clip(
+    client: &reqwest::Client,
+    samples: &[f32],
+    cfg: &LocalSttConfig,
+    language: Option<&str>,
+) -> Result<Transcript> {
+    match cfg.engine {
+        LocalSttEngine::Server => {
+            let wav = wrap_pcm16_in_wav(
+                &audio::f32_to_pcm16le(samples),
+                audio::TARGET_SAMPLE_RATE,
+                1,
+            );
+            server::transcribe(client, cfg, wav, "segment.wav", "audio/wav", language).await
+        }
+        LocalSttEngine::InProcess => inprocess::transcribe(cfg, samples.to_vec(), language).await,
+    }
+}
+
+fn mime_for_extension(extension: &str) -> &'static str {
+    match extension {
+        "flac" => "audio/flac",
+        "mp3" | "mpeg" | "mpga" => "audio/mpeg",
+        "mp4" | "m4a" => "audio/mp4",
+        "ogg" | "oga" => "audio/ogg",
+        "opus" => "audio/opus",
+        "wav" => "audio/wav",
+        "webm" => "audio/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Give every segment word timings, spreading words evenly (weighted by
+/// length) across the segment when the engine did not report any.
+fn fill_missing_word_timings(transcript: &mut Transcript) {
+    for segment in &mut transcript.segments {
+        if !segment.words.is_empty() || segment.end <= segment.start {
+            continue;
+        }
+        let tokens: Vec<&str> = segment.text.split_whitespace().collect();
+        let total: usize = tokens.iter().map(|t| t.chars().count()).sum();
+        if total == 0 {
+            continue;
+        }
+        let duration = segment.end - segment.start;
+        let mut cursor = segment.start;
+        for token in tokens {
+            let span = duration * token.chars().count() as f64 / total as f64;
+            segment.words.push(Word {
+                word: token.to_string(),
+                start: cursor,
+                end: cursor + span,
+                confidence: None,
+            });
+            cursor += span;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(secs: f32) -> Vec<f32> {
+        let n = (audio::TARGET_SAMPLE_RATE as f32 * secs) as usize;
+        (0..n).map(|i| (i as f32 * 0.2).sin() * 0.3).collect()
+    }
+
+    #[test]
+    fn estimated_word_timings_cover_segment() {
+        let mut t = Transcript {
+            text: "hi there".into(),
+            language: None,
+            segments: vec![TranscriptSegment {
+                start: 1.0,
+                end: 2.4,
+                text: "hi there".into(),
+                words: Vec::new(),
+            }],
+        };
+        fill_missing_word_timings(&mut t);
+        let words: Vec<_> = t.words().collect();
+        assert_eq!(words.len(), 2);
+        assert!((words[0].end - 1.4).abs() < 1e-9);
+        assert!((words[1].end - 2.4).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn segments_are_offset_onto_recording_timeline() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"text":"word","segments":[{"text":"word","start":0.1,"end":0.5}]}"#,
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let cfg = LocalSttConfig {
+            server_url: format!("{}/inference", server.uri()),
+            ..LocalSttConfig::default()
+        };
+
+        let mut audio = vec![0.0; 16_000];
+        audio.extend(tone(1.0));
+        audio.extend(vec![0.0; 32_000]);
+        audio.extend(tone(1.0));
+        let wav = wrap_pcm16_in_wav(&audio::f32_to_pcm16le(&audio), 16_000, 1);
+
+        let t = transcribe(wav, "note.wav", &cfg, None).await.unwrap();
+        assert_eq!(t.text, "word word");
+        assert_eq!(t.segments.len(), 2);
+        // Second clip starts ~3.8 s in (4.0 s minus VAD padding).
+        assert!(t.segments[1].start > 3.5, "{:?}", t.segments);
+        assert_eq!(t.words().count(), 2);
+    }
+}
@@ -0,0 +1,259 @@
+//! Client for a locally hosted Whisper HTTP server.
+//!
+//! Targets the whisper.cpp `server` example (`POST /inference`) and the
+//! OpenAI-compatible `/v1/audio/transcriptions` shape exposed by
+//! faster-whisper-server, LocalAI and friends. Both accept a multipart upload
+//! and return `verbose_json` with segment timings; word timings arrive either
+//! nested per segment (whisper.cpp) or as a top-level `words` array (OpenAI).
+
+use anyhow::{bail, Context, Result};
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::{Transcript, TranscriptSegment, Word};
+use crate::config::LocalSttConfig;
+
+#[derive(Debug, Deserialize)]
+struct VerboseResponse {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    #[serde(default)]
+    words: Vec<VerboseWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    start: f64,
+    #[serde(default)]
+    end: f64,
+    #[serde(default)]
+    words: Vec<VerboseWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseWord {
+    #[serde(alias = "text")]
+    word: String,
+    start: f64,
+    end: f64,
+    #[serde(default, alias = "p")]
+    probability: Option<f32>,
+}
+
+impl From<VerboseWord> for Word {
+    fn from(w: VerboseWord) -> Self {
+        Self {
+            word: w.word.trim().to_string(),
+            start: w.start,
+            end: w.end,
+            confidence: w.probability,
+        }
+    }
+}
+
+/// Upload one audio clip and return its transcript with timings relative to
+/// the start of the clip.
+pub async fn transcribe(
+    client: &reqwest::Client,
+    cfg: &LocalSttConfig,
+    audio: Vec<u8>,
+    file_name: &str,
+    mime: &str,
+    language: Option<&str>,
+) -> Result<Transcript> {
+    let part = Part::bytes(audio)
+        .file_name(file_name.to_string())
+        .mime_str(mime)?;
+    let mut form = Form::new()
+        .part("file", part)
+        .text("response_format", "verbose_json")
+        .text("temperature", "0");
+    if let Some(model) = cfg.server_model.as_deref().filter(|m| !m.is_empty()) {
+        form = form.text("model", model.to_string());
+    }
+    if let Some(lang) = language {
+        form = form.text("language", lang.to_string());
+    }
+    if cfg.word_timestamps {
+        form = form
+            .text("timestamp_granularities[]", "word")
+            .text("timestamp_granularities[]", "segment");
+    }
+
+    let resp = client
+        .post(&cfg.server_url)
+        .timeout(Duration::from_secs(cfg.timeout_secs.max(1)))
+        .multipart(form)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach local STT server at {}", cfg.server_url))?;
+
+    let status = resp.status();
+    let body = resp
+        .text()
+        .await
+        .context("Failed to read local STT response")?;
+    if !status.is_success() {
+        bail!(
+            "Local STT server error ({status}): {}",
+            crate::util::truncate_with_ellipsis(body.trim(), 300)
+        );
+    }
+    parse_response(&body)
+}
+
+/// Parse a `verbose_json` (or plain `json`) transcription body.
+fn parse_response(body: &str) -> Result<Transcript> {
+    let parsed: VerboseResponse =
+        serde_json::from_str(body).context("Failed to parse local STT response")?;
+    let mut top_level_words: Vec<Word> = parsed.words.into_iter().map(Word::from).collect();
+
+    let mut segments: Vec<TranscriptSegment> = parsed
+        .segments
+        .into_iter()
+        .map(|s| TranscriptSegment {
+            start: s.start,
+            end: s.end,
+            text: s.text.trim().to_string(),
+            words: s.words.into_iter().map(Word::from).collect(),
+        })
+        .collect();
+
+    // OpenAI-style responses put words at the top level; distribute them
+    // into the segments they fall inside.
+    if !top_level_words.is_empty() {
+        if segments.is_empty() {
+            let end = top_level_words.last().map_or(0.0, |w| w.end);
+            segments.push(TranscriptSegment {
+                start: top_level_words.first().map_or(0.0, |w| w.start),
+                end,
+                text: parsed.text.trim().to_string(),
+                words: std::mem::take(&mut top_level_words),
+            });
+        } else {
+            for word in top_level_words {
+                let midpoint = f64::midpoint(word.start, word.end);
+                let index = segments
+                    .iter()
+                    .position(|s| midpoint < s.end)
+                    .unwrap_or(segments.len() - 1);
+                segments[index].words.push(word);
+            }
+        }
+    }
+
+    let text = if parsed.text.trim().is_empty() {
+        segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        parsed.text.trim().to_string()
+    };
+    if segments.is_empty() && !text.is_empty() {
+        segments.push(TranscriptSegment {
+            start: 0.0,
+            end: 0.0,
+            text: text.clone(),
+            words: Vec::new(),
+        });
+    }
+    Ok(Transcript {
+        text,
+        language: parsed.language,
+        segments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_whisper_cpp_nested_words() {
+        let body = r#"{
+            "task": "transcribe", "language": "english", "duration": 2.1,
+            "text": " Hello world.",
+            "segments": [{
+                "id": 0, "text": " Hello world.", "start": 0.0, "end": 2.0,
+                "words": [
+                    {"word": " Hello", "start": 0.1, "end": 0.5, "probability": 0.9},
+                    {"word": " world.", "start": 0.6, "end": 1.1, "probability": 0.8}
+                ]
+            }]
+        }"#;
+        let t = parse_response(body).unwrap();
+        assert_eq!(t.text, "Hello world.");
+        assert_eq!(t.language.as_deref(), Some("english"));
+        assert_eq!(t.segments.len(), 1);
+        assert_eq!(t.segments[0].words[1].word, "world.");
+        assert_eq!(t.segments[0].words[0].confidence, Some(0.9));
+    }
+
+    #[test]
+    fn distributes_openai_top_level_words_into_segments() {
+        let body = r#"{
+            "text": "one two",
+            "segments": [
+                {"text": "one", "start": 0.0, "end": 1.0},
+                {"text": "two", "start": 1.0, "end": 2.0}
+            ],
+            "words": [
+                {"word": "one", "start": 0.2, "end": 0.6},
+                {"word": "two", "start": 1.2, "end": 1.6}
+            ]
+        }"#;
+        let t = parse_response(body).unwrap();
+        assert_eq!(t.segments[0].words.len(), 1);
+        assert_eq!(t.segments[1].words[0].word, "two");
+    }
+
+    #[test]
+    fn plain_json_becomes_single_segment() {
+        let t = parse_response(r#"{"text": " just text "}"#).unwrap();
+        assert_eq!(t.text, "just text");
+        assert_eq!(t.segments.len(), 1);
+        assert!(t.segments[0].words.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sends_verbose_json_multipart_to_server() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/inference"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"text":"hi","segments":[{"text":"hi","start":0.0,"end":0.4}]}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cfg = LocalSttConfig {
+            server_url: format!("{}/inference", server.uri()),
+            ..LocalSttConfig::default()
+        };
+        let client = reqwest::Client::new();
+        let t = transcribe(&client, &cfg, vec![0; 64], "a.wav", "audio/wav", Some("en"))
+            .await
+            .unwrap();
+        assert_eq!(t.text, "hi");
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("verbose_json"));
+        assert!(body.contains("timestamp_granularities[]"));
+        assert!(body.contains("name=\"language\""));
+    }
+}
@@ -0,0 +1,255 @@
+//! Streaming local STT session for the `/ws/stt` socket.
+//!
+//! Same call surface as [`crate::voice::DeepgramSttSession`]: push 16 kHz
+//! mono PCM16 with `send_audio`, read [`SttEvent`]s with `recv_event`. Audio
+//! is cut into utterances by [`StreamingVad`](super::vad::StreamingVad) and
+//! each utterance is transcribed as a whole, so there are no interim
+//! results — every transcript arrives as a `Final` with word timings on the
+//! session timeline.
+
+use anyhow::Result;
+use tokio::sync::{mpsc, Mutex};
+
+use super::audio::pcm16le_to_f32;
+use super::vad::{StreamingVad, VadEvent};
+use crate::config::LocalSttConfig;
+use crate::voice::deepgram_stt::{SttEvent, SttWord};
+
+enum Control {
+    Audio(Vec<u8>),
+    Finalize,
+    Close,
+}
+
+pub struct LocalSttSession {
+    control_tx: mpsc::Sender<Control>,
+    event_rx: Mutex<mpsc::Receiver<SttEvent>>,
+    session_id: String,
+}
+
+impl LocalSttSession {
+    /// Spawn the VAD + transcription task. `language` of `None` lets the
+    /// engine auto-detect.
+    pub async fn start(
+        session_id: String,
+        cfg: LocalSttConfig,
+        language: Option<String>,
+    ) -> Result<Self> {
+        let (control_tx, control_rx) = mpsc::channel(512);
+        let (event_tx, event_rx) = mpsc::channel(64);
+        let _ = event_tx
+            .send(SttEvent::Ready {
+                request_id: format!("local-{session_id}"),
+            })
+            .await;
+        tokio::spawn(session_loop(cfg, language, control_rx, event_tx));
+        Ok(Self {
+            control_tx,
+            event_rx: Mutex::new(event_rx),
+            session_id,
+        })
+    }
+
+    /// Push a chunk of 16 kHz mono PCM16 (little-endian).
+    pub async fn send_audio(&self, pcm: Vec<u8>) -> Result<()> {
+        self.control_tx
+            .send(Control::Audio(pcm))
+            .await
+            .map_err(|_| anyhow::anyhow!("local STT session is closed"))
+    }
+
+    /// Transcribe whatever speech is buffered without waiting for silence.
+    pub async fn finalize(&self) -> Result<()> {
+        self.control_tx
+            .send(Control::Finalize)
+            .await
+            .map_err(|_| anyhow::anyhow!("local STT session is closed"))
+    }
+
+    /// Flush pending speech and stop the session.
+    pub async fn close(&self) {
+        let _ = self.control_tx.send(Control::Close).await;
+    }
+
+    pub async fn recv_event(&self) -> Option<SttEvent> {
+        self.event_rx.lock().await.recv().await
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+}
+
+async fn session_loop(
+    cfg: LocalSttConfig,
+    language: Option<String>,
+    mut control_rx: mpsc::Receiver<Control>,
+    event_tx: mpsc::Sender<SttEvent>,
+) {
+    let client = crate::config::build_runtime_proxy_client("transcription.local");
+    let mut vad = StreamingVad::new(cfg.vad_params());
+    // A trailing odd byte is carried over so PCM16 frames never straddle chunks.
+    let mut carry: Option<u8> = None;
+
+    while let Some(control) = control_rx.recv().await {
+        let (events, done) = match control {
+            Control::Audio(mut bytes) => {
+                if let Some(b) = carry.take() {
+                    bytes.insert(0, b);
+                }
+                if bytes.len() % 2 == 1 {
+                    carry = bytes.pop();
+                }
+                (vad.push(&pcm16le_to_f32(&bytes)), false)
+            }
+            Control::Finalize => (vad.flush().into_iter().collect(), false),
+            Control::Close => (vad.flush().into_iter().collect(), true),
+        };
+        for event in events {
+            if !emit(&client, &cfg, language.as_deref(), &event_tx, event).await {
+                return;
+            }
+        }
+        if done {
+            break;
+        }
+    }
+    let _ = event_tx.send(SttEvent::Closed).await;
+}
+
+/// Forward a VAD event; returns `false` once the consumer is gone.
+async fn emit(
+    client: &reqwest::Client,
+    cfg: &LocalSttConfig,
+    language: Option<&str>,
+    event_tx: &mpsc::Sender<SttEvent>,
+    event: VadEvent,
+) -> bool {
+    let (start_secs, samples) = match event {
+        VadEvent::SpeechStarted { at_secs } => {
+            return event_tx
+                .send(SttEvent::SpeechStarted { timestamp: at_secs })
+                .await
+                .is_ok();
+        }
+        VadEvent::Utterance {
+            start_secs,
+            samples,
+        } => (start_secs, samples),
+    };
+
+    let event = match super::transcribe_clip(client, &samples, cfg, language).await {
+        Ok(mut transcript) => {
+            transcript.shift(start_secs);
+            super::fill_missing_word_timings(&mut transcript);
+            let text = transcript
+                .segments
+                .iter()
+                .map(|s| s.text.as_str())
+                .filter(|t| !t.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if text.is_empty() {
+                return true;
+            }
+            let words: Vec<SttWord> = transcript
+                .words()
+                .map(|w| SttWord {
+                    word: w.word.clone(),
+                    start: w.start,
+                    end: w.end,
+                    confidence: f64::from(w.confidence.unwrap_or(1.0)),
+                    speaker: None,
+                })
+                .collect();
+            let confidence = if words.is_empty() {
+                1.0
+            } else {
+                words.iter().map(|w| w.confidence).sum::<f64>() / words.len() as f64
+            };
+            let last_word_end = words.last().map(|w| w.end);
+            if event_tx
+                .send(SttEvent::Final {
+                    text,
+                    confidence,
+                    speech_final: true,
+                    language: transcript.language.or_else(|| language.map(str::to_string)),
+                    words,
+                })
+                .await
+                .is_err()
+            {
+                return false;
+            }
+            SttEvent::UtteranceEnd {
+                last_word_end: last_word_end.unwrap_or(start_secs),
+            }
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "local STT transcription failed");
+            SttEvent::Error {
+                message: format!("Local transcription failed: {e}"),
+            }
+        }
+    };
+    event_tx.send(event).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice::local_stt::audio::f32_to_pcm16le;
+
+    #[tokio::test]
+    async fn streams_final_transcript_after_utterance() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"text":"hello","segments":[{"text":"hello","start":0.0,"end":0.8}]}"#,
+            ))
+            .mount(&server)
+            .await;
+        let cfg = LocalSttConfig {
+            server_url: format!("{}/inference", server.uri()),
+            min_silence_ms: 300,
+            ..LocalSttConfig::default()
+        };
+
+        let session = LocalSttSession::start("t".into(), cfg, Some("en".into()))
+            .await
+            .unwrap();
+        let mut audio = vec![0.0f32; 8_000];
+        audio.extend((0..16_000).map(|i| (i as f32 * 0.2).sin() * 0.3));
+        audio.extend(vec![0.0f32; 12_000]);
+        // Odd-sized chunks exercise the PCM16 carry-over.
+        for chunk in f32_to_pcm16le(&audio).chunks(1_001) {
+            session.send_audio(chunk.to_vec()).await.unwrap();
+        }
+        session.close().await;
+
+        let mut events = Vec::new();
+        while let Some(event) = session.recv_event().await {
+            let closed = matches!(event, SttEvent::Closed);
+            events.push(event);
+            if closed {
+                break;
+            }
+        }
+        let final_event = events
+            .iter()
+            .find_map(|e| match e {
+                SttEvent::Final { text, words, .. } => Some((text.clone(), words.clone())),
+                _ => None,
+            })
+            .expect("final transcript");
+        assert_eq!(final_event.0, "hello");
+        // Word timing is on the session timeline (speech began at 0.5 s).
+        assert!(final_event.1[0].start >= 0.25, "{:?}", final_event.1);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SttEvent::SpeechStarted { .. })));
+    }
+}
@@ -0,0 +1,346 @@
+//! Energy-based voice activity detection for segmenting long recordings.
+//!
+//! Frames are 30 ms at 16 kHz. A frame counts as speech when its RMS energy
+//! clears both an absolute floor and a multiple of an adaptive noise estimate;
+//! the `aggressiveness` knob (0–3, WebRTC-style) raises both thresholds.
+//! Speech runs are closed after `min_silence_ms` of non-speech, padded on both
+//! sides so word onsets are not clipped, and split when they exceed the
+//! engine's maximum segment length.
+
+use super::audio::TARGET_SAMPLE_RATE;
+
+/// Samples per 30 ms analysis frame at 16 kHz.
+pub const FRAME_SAMPLES: usize = (TARGET_SAMPLE_RATE as usize) * 30 / 1000;
+
+/// Padding added on each side of a detected speech run.
+const PAD_MS: u32 = 200;
+
+/// Speech runs shorter than this are treated as clicks and dropped.
+const MIN_SPEECH_MS: u32 = 150;
+
+/// VAD tuning derived from [`crate::config::LocalSttConfig`].
+#[derive(Debug, Clone, Copy)]
+pub struct VadParams {
+    pub aggressiveness: u8,
+    pub min_silence_ms: u32,
+    pub max_segment_secs: u32,
+}
+
+impl VadParams {
+    /// (absolute RMS floor, multiple of noise estimate) per aggressiveness level.
+    fn thresholds(self) -> (f32, f32) {
+        match self.aggressiveness {
+            0 => (0.004, 1.5),
+            1 => (0.006, 2.0),
+            2 => (0.010, 2.5),
+            _ => (0.015, 3.5),
+        }
+    }
+
+    fn silence_frames(self) -> usize {
+        ms_to_frames(self.min_silence_ms).max(1)
+    }
+
+    fn max_segment_samples(self) -> usize {
+        (self.max_segment_secs.max(1) as usize) * TARGET_SAMPLE_RATE as usize
+    }
+}
+
+fn ms_to_frames(ms: u32) -> usize {
+    (ms as usize * TARGET_SAMPLE_RATE as usize / 1000).div_ceil(FRAME_SAMPLES)
+}
+
+/// A span of the input, in samples, that contains speech.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeechSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SpeechSpan {
+    pub fn start_secs(self) -> f64 {
+        self.start as f64 / f64::from(TARGET_SAMPLE_RATE)
+    }
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+/// Frame classifier with an adaptive noise floor.
+#[derive(Debug, Clone)]
+struct FrameClassifier {
+    floor: f32,
+    factor: f32,
+    noise: Option<f32>,
+}
+
+impl FrameClassifier {
+    fn new(params: VadParams) -> Self {
+        let (floor, factor) = params.thresholds();
+        Self {
+            floor,
+            factor,
+            noise: None,
+        }
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> bool {
+        let energy = rms(frame);
+        // Seed from the first frame but never above the absolute floor, so a
+        // recording that opens mid-word is not mistaken for background noise.
+        let noise = *self.noise.get_or_insert(energy.min(self.floor));
+        let speech = energy > self.floor && energy > noise * self.factor;
+        // Track the noise floor only on non-speech frames; decay slowly
+        // upwards and quickly downwards so a loud room does not lock the
+        // detector open.
+        if !speech {
+            let rate = if energy < noise { 0.2 } else { 0.02 };
+            self.noise = Some(noise + (energy - noise) * rate);
+        }
+        speech
+    }
+}
+
+/// Split a whole recording into padded speech spans.
+pub fn segment(samples: &[f32], params: VadParams) -> Vec<SpeechSpan> {
+    let mut classifier = FrameClassifier::new(params);
+    let silence_frames = params.silence_frames();
+    let mut runs = Vec::new();
+    let mut run_start: Option<usize> = None;
+    let mut last_speech_frame = 0usize;
+
+    for (index, frame) in samples.chunks(FRAME_SAMPLES).enumerate() {
+        if classifier.is_speech(frame) {
+            run_start.get_or_insert(index);
+            last_speech_frame = index;
+        } else if let Some(start) = run_start {
+            if index - last_speech_frame >= silence_frames {
+                runs.push((start, last_speech_frame + 1));
+                run_start = None;
+            }
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push((start, last_speech_frame + 1));
+    }
+
+    let pad = ms_to_frames(PAD_MS) * FRAME_SAMPLES;
+    let min_speech = ms_to_frames(MIN_SPEECH_MS);
+    let mut spans: Vec<SpeechSpan> = Vec::new();
+    for (start, end) in runs {
+        if end - start < min_speech {
+            continue;
+        }
+        let span = SpeechSpan {
+            start: (start * FRAME_SAMPLES).saturating_sub(pad),
+            end: (end * FRAME_SAMPLES + pad).min(samples.len()),
+        };
+        // Padding can make neighbours overlap; merge them.
+        match spans.last_mut() {
+            Some(prev) if span.start <= prev.end => prev.end = prev.end.max(span.end),
+            _ => spans.push(span),
+        }
+    }
+    split_long(samples, spans, params)
+}
+
+/// Split spans longer than the engine limit at the quietest frame near the
+/// limit so cuts land between words where possible.
+fn split_long(samples: &[f32], spans: Vec<SpeechSpan>, params: VadParams) -> Vec<SpeechSpan> {
+    let max = params.max_segment_samples();
+    let mut out = Vec::with_capacity(spans.len());
+    for span in spans {
+        let mut start = span.start;
+        while span.end - start > max {
+            // Search the last quarter of the allowed window for a quiet frame.
+            let search_from = start + max * 3 / 4;
+            let search_to = start + max;
+            let cut = (search_from..search_to)
+                .step_by(FRAME_SAMPLES)
+                .min_by(|&a, &b| {
+                    let ea = rms(&samples[a..(a + FRAME_SAMPLES).min(samples.len())]);
+                    let eb = rms(&samples[b..(b + FRAME_SAMPLES).min(samples.len())]);
+                    ea.total_cmp(&eb)
+                })
+                .unwrap_or(search_to);
+            out.push(SpeechSpan { start, end: cut });
+            start = cut;
+        }
+        out.push(SpeechSpan {
+            start,
+            end: span.end,
+        });
+    }
+    out
+}
+
+/// Incremental VAD for live audio: feed 16 kHz samples, get back completed
+/// utterances once trailing silence is long enough.
+#[derive(Debug, Clone)]
+pub struct StreamingVad {
+    params: VadParams,
+    classifier: FrameClassifier,
+    pending: Vec<f32>,
+    utterance: Vec<f32>,
+    in_speech: bool,
+    silent_frames: usize,
+    /// Samples consumed before the current utterance started.
+    consumed: usize,
+    utterance_start: usize,
+}
+
+/// Events emitted by [`StreamingVad::push`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum VadEvent {
+    SpeechStarted { at_secs: f64 },
+    Utterance { start_secs: f64, samples: Vec<f32> },
+}
+
+impl StreamingVad {
+    pub fn new(params: VadParams) -> Self {
+        Self {
+            params,
+            classifier: FrameClassifier::new(params),
+            pending: Vec::new(),
+            utterance: Vec::new(),
+            in_speech: false,
+            silent_frames: 0,
+            consumed: 0,
+            utterance_start: 0,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) -> Vec<VadEvent> {
+        self.pending.extend_from_slice(samples);
+        let mut events = Vec::new();
+        let silence_frames = self.params.silence_frames();
+        let max = self.params.max_segment_samples();
+        while self.pending.len() >= FRAME_SAMPLES {
+            let frame: Vec<f32> = self.pending.drain(..FRAME_SAMPLES).collect();
+            let speech = self.classifier.is_speech(&frame);
+            if speech && !self.in_speech {
+                self.in_speech = true;
+                self.utterance_start = self.consumed;
+                events.push(VadEvent::SpeechStarted {
+                    at_secs: self.consumed as f64 / f64::from(TARGET_SAMPLE_RATE),
+                });
+            }
+            self.consumed += frame.len();
+            if !self.in_speech {
+                continue;
+            }
+            self.utterance.extend_from_slice(&frame);
+            self.silent_frames = if speech { 0 } else { self.silent_frames + 1 };
+            if self.silent_frames >= silence_frames || self.utterance.len() >= max {
+                events.extend(self.take_utterance());
+            }
+        }
+        events
+    }
+
+    /// Flush whatever speech is buffered (end of stream / explicit finalize).
+    pub fn flush(&mut self) -> Option<VadEvent> {
+        if self.in_speech {
+            self.utterance.append(&mut self.pending);
+        }
+        self.pending.clear();
+        self.take_utterance()
+    }
+
+    fn take_utterance(&mut self) -> Option<VadEvent> {
+        self.in_speech = false;
+        self.silent_frames = 0;
+        let samples = std::mem::take(&mut self.utterance);
+        if samples.len() < ms_to_frames(MIN_SPEECH_MS) * FRAME_SAMPLES {
+            return None;
+        }
+        Some(VadEvent::Utterance {
+            start_secs: self.utterance_start as f64 / f64::from(TARGET_SAMPLE_RATE),
+            samples,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: VadParams = VadParams {
+        aggressiveness: 2,
+        min_silence_ms: 300,
+        max_segment_secs: 30,
+    };
+
+    fn tone(secs: f32) -> Vec<f32> {
+        let n = (TARGET_SAMPLE_RATE as f32 * secs) as usize;
+        (0..n).map(|i| (i as f32 * 0.2).sin() * 0.3).collect()
+    }
+
+    fn silence(secs: f32) -> Vec<f32> {
+        vec![0.0005; (TARGET_SAMPLE_RATE as f32 * secs) as usize]
+    }
+
+    #[test]
+    fn segments_speech_separated_by_silence() {
+        let mut audio = silence(1.0);
+        audio.extend(tone(1.0));
+        audio.extend(silence(1.0));
+        audio.extend(tone(0.5));
+        audio.extend(silence(0.5));
+        let spans = segment(&audio, PARAMS);
+        assert_eq!(spans.len(), 2, "{spans:?}");
+        assert!((spans[0].start_secs() - 0.8).abs() < 0.05);
+        assert!((spans[1].start_secs() - 2.8).abs() < 0.05);
+    }
+
+    #[test]
+    fn short_pauses_do_not_split_segments() {
+        let mut audio = tone(1.0);
+        audio.extend(silence(0.1));
+        audio.extend(tone(1.0));
+        assert_eq!(segment(&audio, PARAMS).len(), 1);
+    }
+
+    #[test]
+    fn long_speech_is_split_at_max_length() {
+        let params = VadParams {
+            max_segment_secs: 2,
+            ..PARAMS
+        };
+        let audio = tone(5.0);
+        let spans = segment(&audio, params);
+        assert!(spans.len() >= 3);
+        for span in &spans {
+            assert!(span.end - span.start <= 2 * TARGET_SAMPLE_RATE as usize);
+        }
+        assert_eq!(spans.last().unwrap().end, audio.len());
+    }
+
+    #[test]
+    fn silence_only_yields_nothing() {
+        assert!(segment(&silence(3.0), PARAMS).is_empty());
+    }
+
+    #[test]
+    fn streaming_vad_emits_utterance_after_silence() {
+        let mut vad = StreamingVad::new(PARAMS);
+        let mut events = vad.push(&silence(0.5));
+        events.extend(vad.push(&tone(1.0)));
+        events.extend(vad.push(&silence(0.6)));
+        assert!(matches!(events[0], VadEvent::SpeechStarted { .. }));
+        let VadEvent::Utterance {
+            start_secs,
+            ref samples,
+        } = events[1]
+        else {
+            panic!("expected utterance, got {events:?}");
+        };
+        assert!((start_secs - 0.5).abs() < 0.05);
+        assert!(samples.len() >= TARGET_SAMPLE_RATE as usize);
+        assert!(vad.flush().is_none());
+    }
+}
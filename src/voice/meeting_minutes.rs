@@ -0,0 +1,714 @@
+//! Meeting-record mode for conference rooms.
+//!
+//! A [`MeetingRecorder`] accumulates a timestamped transcript for one room:
+//! every source-language utterance is attributed to the participant who
+//! spoke it (from the room roster), and audio from a shared microphone is
+//! split further with [`super::diarization`] into "Speaker N" turns.
+//!
+//! When the meeting ends, a [`MeetingArchiver`] persists the transcript
+//! (JSON + Markdown under `<workspace>/meetings/<room>/`), derives structured
+//! minutes — summary, decisions, action items with owners, open questions —
+//! with the configured LLM (falling back to keyword heuristics), stores the
+//! minutes in memory, and records a `Meeting` object in the ontology linked
+//! to each participant's `Contact` and to a `Task` per action item.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::diarization::{assign_words, diarize, DiarizationParams};
+use super::local_stt::Transcript;
+use crate::memory::{Memory, MemoryCategory};
+use crate::ontology::OntologyRepo;
+use crate::providers::traits::Provider;
+
+// ── Transcript ──────────────────────────────────────────────────
+
+/// A roster entry captured for the record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetingParticipant {
+    pub participant_id: String,
+    pub display_name: String,
+}
+
+/// One attributed, timestamped utterance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Utterance {
+    pub participant_id: String,
+    /// Display label: the participant's name, or `"Name (Speaker N)"` for
+    /// diarized turns on a shared microphone.
+    pub speaker: String,
+    /// Offsets from the start of the recording.
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// Full diarized transcript of one meeting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingTranscript {
+    pub room_id: String,
+    pub title: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub participants: Vec<MeetingParticipant>,
+    pub utterances: Vec<Utterance>,
+}
+
+fn clock(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+impl MeetingTranscript {
+    /// Render as Markdown, one `[hh:mm:ss] Speaker: text` line per utterance.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.title);
+        let _ = writeln!(
+            out,
+            "- Room: {}\n- Started: {}",
+            self.room_id,
+            self.started_at.to_rfc3339()
+        );
+        if let Some(ended) = self.ended_at {
+            let _ = writeln!(out, "- Ended: {}", ended.to_rfc3339());
+        }
+        let names: Vec<&str> = self
+            .participants
+            .iter()
+            .map(|p| p.display_name.as_str())
+            .collect();
+        let _ = writeln!(out, "- Participants: {}\n", names.join(", "));
+        for u in &self.utterances {
+            let _ = writeln!(out, "[{}] {}: {}", clock(u.start_ms), u.speaker, u.text);
+        }
+        out
+    }
+}
+
+// ── Recorder ────────────────────────────────────────────────────
+
+/// Accumulates the transcript of an in-progress meeting.
+#[derive(Debug)]
+pub struct MeetingRecorder {
+    transcript: MeetingTranscript,
+    started: Instant,
+    diarization: DiarizationParams,
+}
+
+impl MeetingRecorder {
+    pub fn new(room_id: &str, title: Option<&str>) -> Self {
+        let started_at = Utc::now();
+        Self {
+            transcript: MeetingTranscript {
+                room_id: room_id.to_string(),
+                title: title.map_or_else(
+                    || format!("Meeting {room_id} {}", started_at.format("%Y-%m-%d %H:%M")),
+                    str::to_string,
+                ),
+                started_at,
+                ended_at: None,
+                participants: Vec::new(),
+                utterances: Vec::new(),
+            },
+            started: Instant::now(),
+            diarization: DiarizationParams::default(),
+        }
+    }
+
+    pub fn with_diarization(mut self, params: DiarizationParams) -> Self {
+        self.diarization = params;
+        self
+    }
+
+    /// Milliseconds since recording started.
+    pub fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    /// Add (or rename) a roster entry. Participants who leave stay on the record.
+    pub fn add_participant(&mut self, participant_id: &str, display_name: &str) {
+        match self
+            .transcript
+            .participants
+            .iter_mut()
+            .find(|p| p.participant_id == participant_id)
+        {
+            Some(p) => p.display_name = display_name.to_string(),
+            None => self.transcript.participants.push(MeetingParticipant {
+                participant_id: participant_id.to_string(),
+                display_name: display_name.to_string(),
+            }),
+        }
+    }
+
+    fn display_name(&self, participant_id: &str) -> String {
+        self.transcript
+            .participants
+            .iter()
+            .find(|p| p.participant_id == participant_id)
+            .map_or_else(|| participant_id.to_string(), |p| p.display_name.clone())
+    }
+
+    /// Record an utterance by a roster participant.
+    pub fn record(&mut self, participant_id: &str, text: &str, start_ms: u64, end_ms: u64) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let speaker = self.display_name(participant_id);
+        self.push(Utterance {
+            participant_id: participant_id.to_string(),
+            speaker,
+            start_ms,
+            end_ms: end_ms.max(start_ms),
+            text: text.to_string(),
+        });
+    }
+
+    /// Record audio from a shared microphone: `samples` is the 16 kHz mono
+    /// clip that produced `transcript`, and `offset_ms` is where the clip
+    /// starts on the meeting timeline. Words are grouped into diarized
+    /// turns; transcripts without word timings fall back to one utterance.
+    pub fn record_shared_mic(
+        &mut self,
+        participant_id: &str,
+        samples: &[f32],
+        transcript: &Transcript,
+        offset_ms: u64,
+    ) {
+        let words: Vec<_> = transcript.words().cloned().collect();
+        let clip_ms = (samples.len() as u64 * 1000) / 16_000;
+        if words.is_empty() {
+            self.record(
+                participant_id,
+                &transcript.text,
+                offset_ms,
+                offset_ms + clip_ms,
+            );
+            return;
+        }
+        let turns = diarize(samples, self.diarization);
+        let distinct = {
+            let mut ids: Vec<usize> = turns.iter().map(|t| t.speaker).collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids.len()
+        };
+        let base = self.display_name(participant_id);
+        for (speaker, group) in assign_words(&turns, &words) {
+            let label = if distinct > 1 {
+                format!("{base} (Speaker {})", speaker + 1)
+            } else {
+                base.clone()
+            };
+            let text = group
+                .iter()
+                .map(|w| w.word.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let to_ms = |secs: f64| offset_ms + (secs.max(0.0) * 1000.0).round() as u64;
+            self.push(Utterance {
+                participant_id: participant_id.to_string(),
+                speaker: label,
+                start_ms: to_ms(group.first().map_or(0.0, |w| w.start)),
+                end_ms: to_ms(group.last().map_or(0.0, |w| w.end)),
+                text,
+            });
+        }
+    }
+
+    /// Keep utterances ordered by start time; late transcripts (slow STT)
+    /// are inserted where they belong.
+    fn push(&mut self, utterance: Utterance) {
+        let at = self
+            .transcript
+            .utterances
+            .partition_point(|u| u.start_ms <= utterance.start_ms);
+        self.transcript.utterances.insert(at, utterance);
+    }
+
+    pub fn utterance_count(&self) -> usize {
+        self.transcript.utterances.len()
+    }
+
+    /// Close the record.
+    pub fn finish(mut self) -> MeetingTranscript {
+        self.transcript.ended_at = Some(Utc::now());
+        self.transcript
+    }
+}
+
+// ── Minutes ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub task: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub due: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeetingMinutes {
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub decisions: Vec<String>,
+    #[serde(default)]
+    pub action_items: Vec<ActionItem>,
+    #[serde(default)]
+    pub open_questions: Vec<String>,
+}
+
+impl MeetingMinutes {
+    pub fn to_markdown(&self, title: &str) -> String {
+        let mut out = format!("# Minutes: {title}\n\n");
+        if !self.summary.is_empty() {
+            let _ = writeln!(out, "{}\n", self.summary);
+        }
+        let section = |out: &mut String, heading: &str, items: &[String]| {
+            if items.is_empty() {
+                return;
+            }
+            let _ = writeln!(out, "## {heading}");
+            for item in items {
+                let _ = writeln!(out, "- {item}");
+            }
+            out.push('\n');
+        };
+        section(&mut out, "Decisions", &self.decisions);
+        let actions: Vec<String> = self
+            .action_items
+            .iter()
+            .map(|a| {
+                let mut line = a.task.clone();
+                if let Some(owner) = &a.owner {
+                    let _ = write!(line, " — {owner}");
+                }
+                if let Some(due) = &a.due {
+                    let _ = write!(line, " (due {due})");
+                }
+                line
+            })
+            .collect();
+        section(&mut out, "Action items", &actions);
+        section(&mut out, "Open questions", &self.open_questions);
+        out
+    }
+}
+
+const MINUTES_SYSTEM_PROMPT: &str = "You write meeting minutes. Given a \
+     timestamped transcript, respond with ONLY a JSON object of the form \
+     {\"summary\": string, \"decisions\": [string], \"action_items\": \
+     [{\"task\": string, \"owner\": string|null, \"due\": string|null}], \
+     \"open_questions\": [string]}. Owners must be speaker names exactly as \
+     they appear in the transcript. Write in the transcript's language. \
+     Do not invent items that were not discussed.";
+
+/// Transcript characters sent to the model; longer meetings keep the tail,
+/// where conclusions and action items cluster.
+const MAX_PROMPT_CHARS: usize = 60_000;
+
+/// Ask `provider` for structured minutes.
+pub async fn generate_minutes(
+    provider: &dyn Provider,
+    model: &str,
+    transcript: &MeetingTranscript,
+) -> Result<MeetingMinutes> {
+    let mut body = transcript.to_markdown();
+    if body.len() > MAX_PROMPT_CHARS {
+        let mut cut = body.len() - MAX_PROMPT_CHARS;
+        while !body.is_char_boundary(cut) {
+            cut += 1;
+        }
+        body = format!("[…transcript truncated…]\n{}", &body[cut..]);
+    }
+    let reply = provider
+        .chat_with_system(Some(MINUTES_SYSTEM_PROMPT), &body, model, 0.2)
+        .await
+        .context("minutes LLM call failed")?;
+    parse_minutes(&reply)
+}
+
+/// Parse the model reply, tolerating code fences and surrounding prose.
+fn parse_minutes(reply: &str) -> Result<MeetingMinutes> {
+    let start = reply
+        .find('{')
+        .context("minutes reply contains no JSON object")?;
+    let end = reply
+        .rfind('}')
+        .context("minutes reply contains no JSON object")?;
+    serde_json::from_str(&reply[start..=end]).context("minutes reply is not valid JSON")
+}
+
+/// Keyword-based minutes for when no model is configured or the call fails.
+pub fn heuristic_minutes(transcript: &MeetingTranscript) -> MeetingMinutes {
+    const DECISION_CUES: [&str; 6] = [
+        "we decided",
+        "decided to",
+        "we agreed",
+        "agreed to",
+        "let's go with",
+        "decision:",
+    ];
+    const ACTION_CUES: [&str; 6] = [
+        "i will ",
+        "i'll ",
+        "action item",
+        "todo",
+        "will take care of",
+        "can you ",
+    ];
+    let mut minutes = MeetingMinutes::default();
+    for u in &transcript.utterances {
+        let lower = u.text.to_lowercase();
+        if DECISION_CUES.iter().any(|c| lower.contains(c)) {
+            minutes.decisions.push(u.text.clone());
+        }
+        if ACTION_CUES.iter().any(|c| lower.contains(c)) {
+            // "Can you …" assigns the task to someone else; the addressee is
+            // unknown without NLP, so leave the owner open.
+            let owner = (!lower.contains("can you ")).then(|| u.speaker.clone());
+            minutes.action_items.push(ActionItem {
+                task: u.text.clone(),
+                owner,
+                due: None,
+            });
+        }
+        if u.text.trim_end().ends_with('?') {
+            minutes.open_questions.push(u.text.clone());
+        }
+    }
+    minutes.summary = format!(
+        "{} utterances from {} participant(s).",
+        transcript.utterances.len(),
+        transcript.participants.len()
+    );
+    minutes
+}
+
+// ── Archiver ────────────────────────────────────────────────────
+
+/// Where a finished meeting ended up.
+#[derive(Debug, Clone)]
+pub struct MeetingRecord {
+    pub transcript_path: PathBuf,
+    pub minutes: MeetingMinutes,
+    pub meeting_object_id: Option<i64>,
+}
+
+/// Persists finished meetings to disk, memory and the ontology.
+pub struct MeetingArchiver {
+    workspace_dir: PathBuf,
+    owner_user_id: String,
+    memory: Option<Arc<dyn Memory>>,
+    ontology: Option<Arc<OntologyRepo>>,
+    summarizer: Option<(Arc<dyn Provider>, String)>,
+}
+
+impl MeetingArchiver {
+    pub fn new(workspace_dir: impl Into<PathBuf>, owner_user_id: impl Into<String>) -> Self {
+        Self {
+            workspace_dir: workspace_dir.into(),
+            owner_user_id: owner_user_id.into(),
+            memory: None,
+            ontology: None,
+            summarizer: None,
+        }
+    }
+
+    pub fn with_memory(mut self, memory: Arc<dyn Memory>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    pub fn with_ontology(mut self, ontology: Arc<OntologyRepo>) -> Self {
+        self.ontology = Some(ontology);
+        self
+    }
+
+    pub fn with_summarizer(
+        mut self,
+        provider: Arc<dyn Provider>,
+        model: impl Into<String>,
+    ) -> Self {
+        self.summarizer = Some((provider, model.into()));
+        self
+    }
+
+    /// Persist `transcript` and its minutes. Memory and ontology failures are
+    /// logged rather than returned so one broken sink does not lose the
+    /// transcript.
+    pub async fn archive(&self, transcript: &MeetingTranscript) -> Result<MeetingRecord> {
+        self.archive_for(&self.owner_user_id, transcript).await
+    }
+
+    /// Like [`Self::archive`], but owns the ontology objects as `owner_user_id`
+    /// (e.g. the conference room creator) instead of the archiver's default.
+    pub async fn archive_for(
+        &self,
+        owner_user_id: &str,
+        transcript: &MeetingTranscript,
+    ) -> Result<MeetingRecord> {
+        let stem = transcript.started_at.format("%Y%m%dT%H%M%SZ").to_string();
+        let dir = self
+            .workspace_dir
+            .join("meetings")
+            .join(sanitize_component(&transcript.room_id));
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let transcript_path = dir.join(format!("{stem}.json"));
+        tokio::fs::write(&transcript_path, serde_json::to_vec_pretty(transcript)?).await?;
+
+        let minutes = match &self.summarizer {
+            Some((provider, model)) => generate_minutes(provider.as_ref(), model, transcript)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(room_id = %transcript.room_id, "minutes generation failed, using heuristics: {e}");
+                    heuristic_minutes(transcript)
+                }),
+            None => heuristic_minutes(transcript),
+        };
+        let minutes_md = minutes.to_markdown(&transcript.title);
+        let markdown = format!("{minutes_md}\n---\n\n{}", transcript.to_markdown());
+        tokio::fs::write(dir.join(format!("{stem}.md")), markdown).await?;
+
+        if let Some(memory) = &self.memory {
+            let key = format!("meeting:{}:{stem}", transcript.room_id);
+            if let Err(e) = memory
+                .store(&key, &minutes_md, MemoryCategory::Core, None)
+                .await
+            {
+                tracing::warn!(key, "failed to store meeting minutes in memory: {e}");
+            }
+        }
+
+        let meeting_object_id = match &self.ontology {
+            Some(repo) => {
+                match Self::record_in_ontology(
+                    repo,
+                    owner_user_id,
+                    transcript,
+                    &minutes,
+                    &transcript_path,
+                ) {
+                    Ok(id) => Some(id),
+                    Err(e) => {
+                        tracing::warn!(room_id = %transcript.room_id, "failed to record meeting in ontology: {e}");
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        Ok(MeetingRecord {
+            transcript_path,
+            minutes,
+            meeting_object_id,
+        })
+    }
+
+    fn record_in_ontology(
+        repo: &OntologyRepo,
+        owner: &str,
+        transcript: &MeetingTranscript,
+        minutes: &MeetingMinutes,
+        transcript_path: &std::path::Path,
+    ) -> Result<i64> {
+        let meeting_id = repo.create_object(
+            "Meeting",
+            Some(&transcript.title),
+            &serde_json::json!({
+                "status": "ended",
+                "room_id": transcript.room_id,
+                "started_at": transcript.started_at.to_rfc3339(),
+                "ended_at": transcript.ended_at.map(|t| t.to_rfc3339()),
+                "transcript_path": transcript_path.display().to_string(),
+                "summary": minutes.summary,
+                "decisions": minutes.decisions,
+                "open_questions": minutes.open_questions,
+            }),
+            owner,
+        )?;
+
+        let mut contacts = std::collections::HashMap::new();
+        for p in &transcript.participants {
+            let contact_id =
+                repo.ensure_object("Contact", &p.display_name, &serde_json::json!({}), owner)?;
+            repo.create_link(
+                "involves",
+                meeting_id,
+                contact_id,
+                Some(&serde_json::json!({"participant_id": p.participant_id})),
+            )?;
+            contacts.insert(p.display_name.clone(), contact_id);
+        }
+
+        for item in &minutes.action_items {
+            let task_id = repo.create_object(
+                "Task",
+                Some(&item.task),
+                &serde_json::json!({
+                    "status": "open",
+                    "owner": item.owner,
+                    "due": item.due,
+                    "source": "meeting",
+                }),
+                owner,
+            )?;
+            repo.create_link("belongs_to", task_id, meeting_id, None)?;
+            // Diarized labels look like "Name (Speaker 2)"; match on the name.
+            let assignee = item
+                .owner
+                .as_deref()
+                .map(|o| o.split(" (Speaker").next().unwrap_or(o).trim())
+                .and_then(|name| contacts.get(name));
+            if let Some(&contact_id) = assignee {
+                repo.create_link("assigned_to", task_id, contact_id, None)?;
+            }
+        }
+        Ok(meeting_id)
+    }
+}
+
+fn sanitize_component(raw: &str) -> String {
+    let cleaned: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if cleaned.is_empty() {
+        "room".into()
+    } else {
+        cleaned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice::local_stt::{TranscriptSegment, Word};
+
+    fn sample_transcript() -> MeetingTranscript {
+        let mut rec = MeetingRecorder::new("room-1", Some("Weekly sync"));
+        rec.add_participant("p1", "Alice");
+        rec.add_participant("p2", "Bob");
+        rec.record("p2", "I'll update the release notes.", 5_000, 7_000);
+        rec.record("p1", "We decided to ship on Friday.", 1_000, 3_000);
+        rec.record("p1", "Who owns the migration?", 8_000, 9_000);
+        rec.finish()
+    }
+
+    #[test]
+    fn recorder_orders_and_attributes_utterances() {
+        let t = sample_transcript();
+        let speakers: Vec<&str> = t.utterances.iter().map(|u| u.speaker.as_str()).collect();
+        assert_eq!(speakers, ["Alice", "Bob", "Alice"]);
+        assert!(t.ended_at.is_some());
+        let md = t.to_markdown();
+        assert!(md.contains("[00:00:01] Alice: We decided to ship on Friday."));
+    }
+
+    #[test]
+    fn shared_mic_without_word_timings_is_single_utterance() {
+        let mut rec = MeetingRecorder::new("r", None);
+        rec.add_participant("mic", "Room mic");
+        let transcript = Transcript {
+            text: "hello all".into(),
+            language: None,
+            segments: Vec::new(),
+        };
+        rec.record_shared_mic("mic", &vec![0.0; 16_000], &transcript, 2_000);
+        let t = rec.finish();
+        assert_eq!(t.utterances[0].speaker, "Room mic");
+        assert_eq!(
+            (t.utterances[0].start_ms, t.utterances[0].end_ms),
+            (2_000, 3_000)
+        );
+    }
+
+    #[test]
+    fn shared_mic_words_keep_meeting_offsets() {
+        let mut rec = MeetingRecorder::new("r", None);
+        rec.add_participant("mic", "Room mic");
+        let transcript = Transcript {
+            text: "good morning".into(),
+            language: None,
+            segments: vec![TranscriptSegment {
+                start: 0.5,
+                end: 1.5,
+                text: "good morning".into(),
+                words: vec![
+                    Word {
+                        word: "good".into(),
+                        start: 0.5,
+                        end: 0.9,
+                        confidence: None,
+                    },
+                    Word {
+                        word: "morning".into(),
+                        start: 1.0,
+                        end: 1.5,
+                        confidence: None,
+                    },
+                ],
+            }],
+        };
+        rec.record_shared_mic("mic", &vec![0.0; 32_000], &transcript, 10_000);
+        let t = rec.finish();
+        assert_eq!(t.utterances.len(), 1);
+        assert_eq!(t.utterances[0].text, "good morning");
+        assert_eq!(t.utterances[0].start_ms, 10_500);
+    }
+
+    #[test]
+    fn heuristic_minutes_extracts_cues() {
+        let m = heuristic_minutes(&sample_transcript());
+        assert_eq!(m.decisions, ["We decided to ship on Friday."]);
+        assert_eq!(m.action_items[0].owner.as_deref(), Some("Bob"));
+        assert_eq!(m.open_questions, ["Who owns the migration?"]);
+    }
+
+    #[test]
+    fn parses_fenced_minutes_json() {
+        let reply = "```json\n{\"summary\":\"s\",\"decisions\":[\"d\"],\"action_items\":[{\"task\":\"t\",\"owner\":\"Bob\"}]}\n```";
+        let m = parse_minutes(reply).unwrap();
+        assert_eq!(m.action_items[0].owner.as_deref(), Some("Bob"));
+        assert!(m.open_questions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn archive_writes_files_and_links_ontology() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = Arc::new(OntologyRepo::open(tmp.path()).unwrap());
+        let archiver = MeetingArchiver::new(tmp.path(), "owner-1").with_ontology(Arc::clone(&repo));
+        let record = archiver.archive(&sample_transcript()).await.unwrap();
+
+        assert!(record.transcript_path.exists());
+        assert!(record.transcript_path.with_extension("md").exists());
+        let meeting_id = record.meeting_object_id.unwrap();
+        let meeting = repo.get_object(meeting_id).unwrap().unwrap();
+        assert_eq!(meeting.title.as_deref(), Some("Weekly sync"));
+        let links = repo.links_from(meeting_id, "owner-1").unwrap();
+        assert_eq!(links.len(), 2, "{links:?}");
+    }
+}
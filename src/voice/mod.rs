@@ -21,11 +21,14 @@ pub mod conference;
 pub mod cosyvoice2;
 pub mod deepgram_simul;
 pub mod deepgram_stt;
+pub mod diarization;
 pub mod events;
 pub mod gemini_live;
 pub mod gemma_asr;
 pub mod gemma_simul;
 pub mod kokoro_tts;
+pub mod local_stt;
+pub mod meeting_minutes;
 pub mod openai_realtime;
 pub mod pipeline;
 pub mod secretary_migrator;
@@ -72,6 +75,10 @@ pub use deepgram_stt::{DeepgramConfig, DeepgramSttSession, SttEvent};
 #[allow(unused_imports)]
 pub use gemma_asr::{GemmaAsrConfig, GemmaAsrSession};
 #[allow(unused_imports)]
+pub use local_stt::{LocalSttSession, Transcript, TranscriptSegment};
+#[allow(unused_imports)]
+pub use meeting_minutes::{MeetingArchiver, MeetingMinutes, MeetingRecord, MeetingTranscript};
+#[allow(unused_imports)]
 pub use openai_realtime::OpenAiRealtimeSession;
 #[allow(unused_imports)]
 pub use pipeline::{
@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use super::conference::ConferenceManager;
+
 // ── Language codes (25 supported languages) ──────────────────────
 
 /// ISO 639-1 language codes supported by the voice pipeline.
@@ -620,6 +622,9 @@ impl InterpreterSession {
 
 // ── Session manager ──────────────────────────────────────────────
 
+/// Conference room limit when none is configured.
+const DEFAULT_MAX_CONFERENCE_ROOMS: usize = 10;
+
 /// Manages active voice interpretation sessions.
 pub struct VoiceSessionManager {
     /// Active sessions indexed by session ID.
@@ -634,6 +639,8 @@ pub struct VoiceSessionManager {
     default_target_language: String,
     /// Default voice provider ("gemini" or "openai").
     default_provider: Option<String>,
+    /// Conference rooms joined by sessions that name a room.
+    conferences: Arc<ConferenceManager>,
 }
 
 impl VoiceSessionManager {
@@ -646,6 +653,7 @@ impl VoiceSessionManager {
             default_source_language: "ko".to_string(),
             default_target_language: "en".to_string(),
             default_provider: None,
+            conferences: Arc::new(ConferenceManager::new(DEFAULT_MAX_CONFERENCE_ROOMS)),
         }
     }
 
@@ -664,9 +672,22 @@ impl VoiceSessionManager {
             default_source_language,
             default_target_language,
             default_provider,
+            conferences: Arc::new(ConferenceManager::new(DEFAULT_MAX_CONFERENCE_ROOMS)),
         }
     }
 
+    /// Replace the conference manager (e.g. one built with
+    /// [`ConferenceManager::from_config`]).
+    pub fn with_conferences(mut self, conferences: ConferenceManager) -> Self {
+        self.conferences = Arc::new(conferences);
+        self
+    }
+
+    /// Conference rooms shared by this manager's sessions.
+    pub fn conferences(&self) -> &Arc<ConferenceManager> {
+        &self.conferences
+    }
+
     /// Get the default source language code.
     pub fn default_source_language(&self) -> &str {
         &self.default_source_language
@@ -4,20 +4,48 @@
 // enforcing limits. Each step is dispatched by type.
 
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context, Result};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
+use tokio_util::sync::CancellationToken;
 
 use super::parser::{step_id, Limits, Step, WorkflowSpec};
 use super::registry::ToolRegistry;
+use crate::inbox::{HumanInbox, HumanTaskKind, HumanTaskStatus, NewHumanTask, TaskWaiter};
+
+/// Longest a `user_confirm` step waits when neither the step's
+/// `timeout_sec` nor the workflow's `max_runtime_sec` bounds it.
+pub const DEFAULT_CONFIRM_WAIT: Duration = Duration::from_secs(3600);
 
 /// Execution context threaded through all steps.
 pub struct ExecContext {
+    pub workflow: String,
     pub device_id: String,
     pub vars: HashMap<String, serde_json::Value>,
     pub cost: CostTracker,
     pub limits: Limits,
+    /// Inbox that `user_confirm` steps ask; without one they pass.
+    pub inbox: Option<Arc<HumanInbox>>,
+    /// Cancels the run, including a pending `user_confirm` wait.
+    pub cancel: CancellationToken,
+    pub started_at: Instant,
+}
+
+impl ExecContext {
+    /// How long a `user_confirm` step may wait: its own timeout, else what
+    /// is left of the run's `max_runtime_sec`, else [`DEFAULT_CONFIRM_WAIT`].
+    fn confirm_wait(&self, timeout_sec: Option<u32>) -> Duration {
+        match (timeout_sec, self.limits.max_runtime_sec) {
+            (Some(secs), _) => Duration::from_secs(u64::from(secs)),
+            (None, Some(secs)) => {
+                Duration::from_secs(u64::from(secs)).saturating_sub(self.started_at.elapsed())
+            }
+            (None, None) => DEFAULT_CONFIRM_WAIT,
+        }
+    }
 }
 
 /// Tracks token and LLM call costs during execution.
@@ -55,16 +83,31 @@ pub struct WorkflowRunResult {
 
 /// Execute a workflow spec with given inputs.
 pub async fn execute(
+    spec: &WorkflowSpec,
+    inputs: serde_json::Value,
+    tools: &ToolRegistry,
+    device_id: &str,
+) -> Result<WorkflowRunResult> {
+    execute_with_cancel(spec, inputs, tools, device_id, CancellationToken::new()).await
+}
+
+/// Execute a workflow spec, stopping when `cancel` fires.
+pub async fn execute_with_cancel(
     spec: &WorkflowSpec,
     inputs: serde_json::Value,
     _tools: &ToolRegistry,
     device_id: &str,
+    cancel: CancellationToken,
 ) -> Result<WorkflowRunResult> {
     let mut ctx = ExecContext {
+        workflow: spec.name.clone(),
         device_id: device_id.to_string(),
         vars: HashMap::new(),
         cost: CostTracker::default(),
         limits: spec.limits.clone(),
+        inbox: crate::inbox::global(),
+        cancel,
+        started_at: Instant::now(),
     };
 
     let run_uuid = uuid::Uuid::new_v4().to_string();
@@ -77,13 +120,17 @@ pub async fn execute(
         }
     }
 
-    // Execute steps sequentially
-    for step in &spec.steps {
-        execute_step(step, &mut ctx)
-            .await
-            .with_context(|| format!("step failed: {}", step_id(step)))?;
-        ctx.cost.check(&ctx.limits)?;
-    }
+    // Execute steps sequentially; spend is attributed to this workflow.
+    crate::cost::attribution::scope(crate::cost::CostAttribution::workflow(&spec.name), async {
+        for step in &spec.steps {
+            execute_step(step, &mut ctx)
+                .await
+                .with_context(|| format!("step failed: {}", step_id(step)))?;
+            ctx.cost.check(&ctx.limits)?;
+        }
+        anyhow::Ok(())
+    })
+    .await?;
 
     let output = serde_json::to_value(&ctx.vars)?;
     let output_sha256 = sha256_hex(&serde_json::to_vec(&output)?);
@@ -218,9 +265,42 @@ async fn execute_step_inner(step: &Step, ctx: &mut ExecContext) -> Result<()> {
                 }
             }
         }
-        Step::UserConfirm(_s) => {
-            // In production: send confirmation to Tauri IPC, wait for response
-            // For now, auto-approve
+        Step::UserConfirm(s) => {
+            // Without an inbox there is nobody to ask; the step passes.
+            let Some(inbox) = ctx.inbox.clone() else {
+                return Ok(());
+            };
+            let message = render_template(&s.message, &ctx.vars)?;
+            let task = inbox.create(
+                NewHumanTask::new(HumanTaskKind::WorkflowConfirm, message)
+                    .with_waiter(TaskWaiter::Workflow {
+                        workflow: ctx.workflow.clone(),
+                        step: s.id.clone(),
+                    })
+                    .with_deadline_secs(s.timeout_sec.map(u64::from)),
+            )?;
+            let max_wait = ctx.confirm_wait(s.timeout_sec);
+            let task = inbox
+                .wait(&task.id, Some(max_wait), Some(&ctx.cancel))
+                .await?;
+            if task.is_open() {
+                // Nobody is waiting any more; take the request off the inbox.
+                inbox.cancel(&task.id, "workflow")?;
+                if ctx.cancel.is_cancelled() {
+                    bail!(
+                        "workflow cancelled while waiting for confirmation {}",
+                        task.id
+                    );
+                }
+                bail!("confirmation {} was not answered in time", task.id);
+            }
+            ctx.vars.insert(
+                format!("{}.response", s.id),
+                serde_json::json!(task.response),
+            );
+            if task.status != HumanTaskStatus::Approved {
+                bail!("confirmation {} was {}", task.id, task.status);
+            }
         }
     }
     Ok(())
@@ -417,4 +497,51 @@ limits:
         assert!(!result.input_sha256.is_empty());
         assert_eq!(result.cost.llm_calls, 1);
     }
+
+    #[tokio::test]
+    async fn user_confirm_wait_is_bounded_and_cancellable() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let inbox =
+            Arc::new(HumanInbox::open(&tmp.path().join("tasks.db"), Default::default()).unwrap());
+        let mut ctx = ExecContext {
+            workflow: "publish".into(),
+            device_id: "dev1".into(),
+            vars: HashMap::new(),
+            cost: CostTracker::default(),
+            limits: Limits {
+                max_tokens_per_run: 1000,
+                max_llm_calls_per_run: 10,
+                max_runtime_sec: Some(1),
+            },
+            inbox: Some(Arc::clone(&inbox)),
+            cancel: CancellationToken::new(),
+            started_at: Instant::now(),
+        };
+        let step = Step::UserConfirm(super::super::parser::UserConfirmStep {
+            id: "confirm".into(),
+            message: "Publish?".into(),
+            timeout_sec: None,
+        });
+
+        // The run's max_runtime_sec bounds the wait.
+        let error = execute_step(&step, &mut ctx).await.unwrap_err();
+        assert!(error.to_string().contains("not answered in time"), "{error}");
+
+        // Cancelling the run stops the wait.
+        ctx.limits.max_runtime_sec = None;
+        let cancel = ctx.cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel.cancel();
+        });
+        let error = execute_step(&step, &mut ctx).await.unwrap_err();
+        assert!(error.to_string().contains("cancelled"), "{error}");
+
+        // Neither abandoned request is left open in the inbox.
+        let tasks = inbox.list(true, 10).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks
+            .iter()
+            .all(|task| task.status == HumanTaskStatus::Cancelled));
+    }
 }
@@ -21,7 +21,7 @@ pub mod scaffold;
 pub mod skill_registry;
 
 // Re-export key types
-pub use exec::{execute, CostTracker, ExecContext, WorkflowRunResult};
+pub use exec::{execute, execute_with_cancel, CostTracker, ExecContext, WorkflowRunResult};
 pub use hooks::{ConsentLevel, HookContext, SecurityHooks};
 pub use intent::{classify_heuristic, classify_intent, IntentConfig, WorkflowIntent};
 pub use learning::{analyze_workflow, run_learning_loop, LearningConfig, SuggestionType, WorkflowSuggestion};
@@ -0,0 +1,93 @@
+//! Replays a recorded provider cassette through a full agent turn.
+//!
+//! Cassettes are written by `zeroclaw agent --record <path>` (or
+//! `RecordingProvider`) and let a real session be reproduced offline, without
+//! provider credentials or network access.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use zeroclaw::agent::agent::Agent;
+use zeroclaw::agent::dispatcher::NativeToolDispatcher;
+use zeroclaw::config::{CassetteMatch, MemoryConfig};
+use zeroclaw::memory;
+use zeroclaw::observability::NoopObserver;
+use zeroclaw::providers::cassette::ReplayProvider;
+use zeroclaw::providers::Provider;
+use zeroclaw::tools::{Tool, ToolResult};
+
+struct EchoTool;
+
+#[async_trait]
+impl Tool for EchoTool {
+    fn name(&self) -> &str {
+        "echo"
+    }
+    fn description(&self) -> &str {
+        "Echoes the input message"
+    }
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {"type": "string"}
+            }
+        })
+    }
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        Ok(ToolResult {
+            success: true,
+            output: args["message"].as_str().unwrap_or("(empty)").to_string(),
+            error: None,
+        })
+    }
+}
+
+fn cassette(name: &str) -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/cassettes")
+        .join(name)
+}
+
+fn build_agent(provider: Box<dyn Provider>) -> Agent {
+    let memory_config = MemoryConfig {
+        backend: "none".into(),
+        ..MemoryConfig::default()
+    };
+    Agent::builder()
+        .provider(provider)
+        .tools(vec![Box::new(EchoTool)])
+        .memory(Arc::from(
+            memory::create_memory(&memory_config, &std::env::temp_dir(), None).unwrap(),
+        ))
+        .observer(Arc::new(NoopObserver {}))
+        .tool_dispatcher(Box::new(NativeToolDispatcher))
+        .workspace_dir(std::env::temp_dir())
+        .build()
+        .unwrap()
+}
+
+/// The system prompt and model differ from the recording, so the cassette is
+/// replayed with fuzzy matching, as when reproducing a user's bug report.
+#[tokio::test]
+async fn replayed_cassette_drives_a_tool_call_turn() {
+    let replay =
+        ReplayProvider::from_file(&cassette("echo_tool_turn.json"), CassetteMatch::Fuzzy).unwrap();
+    assert!(replay.supports_native_tools());
+
+    let mut agent = build_agent(Box::new(replay));
+    let response = agent.turn("say hello through the echo tool").await.unwrap();
+    assert_eq!(response, "The echo tool replied: hello from the cassette");
+}
+
+/// Strict replay refuses a request that was never recorded.
+#[tokio::test]
+async fn strict_replay_rejects_an_unrecorded_conversation() {
+    let replay =
+        ReplayProvider::from_file(&cassette("echo_tool_turn.json"), CassetteMatch::Strict).unwrap();
+    let mut agent = build_agent(Box::new(replay));
+    let err = agent.turn("something else entirely").await.unwrap_err();
+    assert!(err.to_string().contains("cassette mismatch"), "{err}");
+}
@@ -0,0 +1,81 @@
+//! Integration tests for the seccomp sandbox launcher (`zeroclaw sandbox-exec`).
+//!
+//! These tests validate that:
+//! 1. Commands run normally under the `standard` syscall profile
+//! 2. The `strict` profile kills commands that open network sockets with SIGSYS
+//! 3. CPU and memory rlimits are applied before the command starts
+#![cfg(target_os = "linux")]
+
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Output};
+
+fn seccomp_supported() -> bool {
+    cfg!(any(target_arch = "x86_64", target_arch = "aarch64"))
+        && std::fs::read_to_string("/proc/self/status")
+            .map(|status| status.lines().any(|line| line.starts_with("Seccomp:")))
+            .unwrap_or(false)
+}
+
+fn sandbox_exec(extra: &[&str], command: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zeroclaw"))
+        .arg("sandbox-exec")
+        .args(extra)
+        .arg("--")
+        .args(command)
+        .output()
+        .expect("sandbox-exec should start")
+}
+
+#[test]
+fn standard_profile_runs_shell_pipelines() {
+    if !seccomp_supported() {
+        return;
+    }
+    let output = sandbox_exec(
+        &["--profile", "standard"],
+        &["sh", "-c", "echo sandboxed | tr a-z A-Z"],
+    );
+    assert!(output.status.success(), "status: {:?}", output.status);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "SANDBOXED");
+}
+
+#[test]
+fn strict_profile_kills_inet_sockets() {
+    if !seccomp_supported() || Command::new("python3").arg("-V").output().is_err() {
+        return;
+    }
+    let script = "import socket; socket.socket(socket.AF_INET, socket.SOCK_STREAM)";
+    let output = sandbox_exec(&["--profile", "strict"], &["python3", "-c", script]);
+    assert_eq!(output.status.signal(), Some(libc::SIGSYS));
+
+    let allowed = sandbox_exec(&["--profile", "standard"], &["python3", "-c", script]);
+    assert!(allowed.status.success(), "status: {:?}", allowed.status);
+}
+
+#[test]
+fn rlimits_apply_before_exec() {
+    if !seccomp_supported() {
+        return;
+    }
+    let output = sandbox_exec(
+        &[
+            "--profile",
+            "permissive",
+            "--cpu-seconds",
+            "7",
+            "--memory-bytes",
+            "536870912",
+        ],
+        &["sh", "-c", "ulimit -t; ulimit -d"],
+    );
+    assert!(output.status.success(), "status: {:?}", output.status);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let limits: Vec<&str> = stdout.lines().collect();
+    assert_eq!(limits, ["7", "524288"]);
+}
+
+#[test]
+fn unknown_profile_is_rejected() {
+    let output = sandbox_exec(&["--profile", "lenient"], &["true"]);
+    assert!(!output.status.success());
+}
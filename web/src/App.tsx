@@ -73,10 +73,47 @@ function KakaoCallback() {
   );
 }
 
+// OIDC single sign-on callback: the gateway passes the session token (or an
+// error) in the URL fragment so it never reaches server logs.
+function OidcCallback() {
+  const { refreshAuth } = useAuth();
+  const params = new URLSearchParams(window.location.hash.slice(1));
+  const token = params.get('token');
+  const returnTo = params.get('return_to') || '/';
+  const error = token ? null : params.get('error') || 'SSO login failed';
+
+  useEffect(() => {
+    if (!token) {
+      return;
+    }
+    setToken(token);
+    refreshAuth();
+    window.location.replace(returnTo);
+  }, [token, returnTo, refreshAuth]);
+
+  if (error) {
+    return (
+      <div className="min-h-screen bg-gray-950 flex items-center justify-center">
+        <div className="bg-gray-900 rounded-xl p-8 max-w-md border border-gray-800 text-center">
+          <p className="text-red-400 mb-4">{error}</p>
+          <a href="/" className="text-blue-400 hover:text-blue-300">Back to login</a>
+        </div>
+      </div>
+    );
+  }
+
+  return (
+    <div className="min-h-screen bg-gray-950 flex items-center justify-center">
+      <p className="text-gray-400">Completing sign-in...</p>
+    </div>
+  );
+}
+
 function AppContent() {
   const { isAuthenticated, loading, pair, logout, refreshAuth } = useAuth();
   const [locale, setLocaleState] = useState<Locale>('en');
   const [requiresPairing, setRequiresPairing] = useState(true);
+  const [ssoEnabled, setSsoEnabled] = useState(false);
 
   const setAppLocale = (newLocale: Locale) => {
     setLocaleState(newLocale);
@@ -91,6 +128,9 @@ function AppContent() {
         if (data && typeof data.require_pairing === 'boolean') {
           setRequiresPairing(data.require_pairing);
         }
+        if (data && typeof data.sso === 'boolean') {
+          setSsoEnabled(data.sso);
+        }
       })
       .catch(() => {});
   }, []);
@@ -126,6 +166,7 @@ function AppContent() {
           path="/auth/kakao/callback"
           element={<KakaoCallback />}
         />
+        <Route path="/auth/oidc/callback" element={<OidcCallback />} />
         <Route
           path="*"
           element={
@@ -136,6 +177,7 @@ function AppContent() {
               }}
               onPair={pair}
               showPairing={requiresPairing}
+              showSso={ssoEnabled}
             />
           }
         />
@@ -147,6 +189,7 @@ function AppContent() {
     <LocaleContext.Provider value={{ locale, setAppLocale }}>
       <Routes>
         <Route path="/auth/kakao/callback" element={<KakaoCallback />} />
+        <Route path="/auth/oidc/callback" element={<OidcCallback />} />
         <Route element={<Layout />}>
           <Route path="/" element={<Dashboard />} />
           <Route path="/agent" element={<AgentChat />} />
@@ -7,6 +7,7 @@ interface AuthPageProps {
   onAuthSuccess: (token: string) => void;
   onPair: (code: string) => Promise<void>;
   showPairing: boolean;
+  showSso?: boolean;
 }
 
 const KAKAO_REST_KEY = import.meta.env.VITE_KAKAO_REST_API_KEY || '';
@@ -15,7 +16,7 @@ function getKakaoRedirectUri(): string {
   return `${window.location.origin}/api/auth/kakao/redirect`;
 }
 
-export default function AuthPage({ onAuthSuccess, onPair, showPairing }: AuthPageProps) {
+export default function AuthPage({ onAuthSuccess, onPair, showPairing, showSso = false }: AuthPageProps) {
   const [mode, setMode] = useState<AuthMode>(showPairing ? 'pairing' : 'login');
   const [username, setUsername] = useState('');
   const [password, setPassword] = useState('');
@@ -198,6 +199,26 @@ export default function AuthPage({ onAuthSuccess, onPair, showPairing }: AuthPag
               {loading ? 'Logging in...' : 'Log In'}
             </button>
 
+            {/* Single sign-on (OIDC) */}
+            {showSso && (
+              <>
+                <div className="relative my-4">
+                  <div className="absolute inset-0 flex items-center">
+                    <div className="w-full border-t border-gray-700" />
+                  </div>
+                  <div className="relative flex justify-center text-sm">
+                    <span className="px-3 bg-gray-900 text-gray-500">or</span>
+                  </div>
+                </div>
+                <a
+                  href="/api/auth/oidc/login"
+                  className="block w-full py-3 bg-gray-800 hover:bg-gray-700 text-white text-center rounded-lg font-medium transition-colors"
+                >
+                  Sign in with SSO
+                </a>
+              </>
+            )}
+
             {/* Kakao Login */}
             {KAKAO_REST_KEY && (
               <>